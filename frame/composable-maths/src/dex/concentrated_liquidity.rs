@@ -0,0 +1,353 @@
+//! Math of a concentrated liquidity market maker, as described by
+//! https://uniswap.org/whitepaper-v3.pdf
+//!
+//! Prices are expressed in quote per base and tracked through their square root, represented as a
+//! [`FixedU128`]. The price space is split in ticks, the price at tick `i` being `1.0001^i`.
+//! Liquidity is the `L` of the virtual reserves `x = L / sqrt(P)` and `y = L * sqrt(P)`, with `x`
+//! the base and `y` the quote reserve.
+
+use sp_arithmetic::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{CheckedDiv, CheckedMul},
+	ArithmeticError, FixedPointNumber, FixedU128, Rounding,
+};
+
+/// Lowest tick, the price being roughly `4.2e-18`.
+pub const MIN_TICK: i32 = -400_000;
+/// Highest tick, the price being roughly `2.4e17`.
+pub const MAX_TICK: i32 = 400_000;
+
+/// `sqrt(1.0001)`, the ratio of the square root prices of two consecutive ticks.
+const SQRT_TICK_RATIO: FixedU128 = FixedU128::from_inner(1_000_049_998_750_062_496);
+
+const ONE: u128 = FixedU128::DIV;
+
+/// Outcome of a swap within a range of constant liquidity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapStep {
+	/// Square root price reached by the step.
+	pub sqrt_price: FixedU128,
+	pub amount_in: u128,
+	pub amount_out: u128,
+}
+
+fn mul_div(a: u128, b: u128, c: u128, round_up: bool) -> Result<u128, ArithmeticError> {
+	if c == 0 {
+		return Err(ArithmeticError::DivisionByZero)
+	}
+	let rounding = if round_up { Rounding::Up } else { Rounding::Down };
+	multiply_by_rational_with_rounding(a, b, c, rounding).ok_or(ArithmeticError::Overflow)
+}
+
+/// Square root of the price at `tick`.
+pub fn sqrt_price_at_tick(tick: i32) -> Result<FixedU128, ArithmeticError> {
+	if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+		return Err(ArithmeticError::Overflow)
+	}
+	let mut exponent = tick.unsigned_abs();
+	let mut ratio = SQRT_TICK_RATIO;
+	let mut sqrt_price = FixedU128::from_inner(ONE);
+	while exponent > 0 {
+		if exponent & 1 == 1 {
+			sqrt_price = sqrt_price.checked_mul(&ratio).ok_or(ArithmeticError::Overflow)?;
+		}
+		exponent >>= 1;
+		if exponent > 0 {
+			ratio = ratio.checked_mul(&ratio).ok_or(ArithmeticError::Overflow)?;
+		}
+	}
+	if tick < 0 {
+		FixedU128::from_inner(ONE)
+			.checked_div(&sqrt_price)
+			.ok_or(ArithmeticError::DivisionByZero)
+	} else {
+		Ok(sqrt_price)
+	}
+}
+
+/// Greatest tick whose square root price is lower or equal to `sqrt_price`.
+pub fn tick_at_sqrt_price(sqrt_price: FixedU128) -> Result<i32, ArithmeticError> {
+	if sqrt_price < sqrt_price_at_tick(MIN_TICK)? {
+		return Err(ArithmeticError::Underflow)
+	}
+	if sqrt_price > sqrt_price_at_tick(MAX_TICK)? {
+		return Err(ArithmeticError::Overflow)
+	}
+	let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+	while low < high {
+		let middle = low + (high - low + 1) / 2;
+		if sqrt_price_at_tick(middle)? <= sqrt_price {
+			low = middle;
+		} else {
+			high = middle - 1;
+		}
+	}
+	Ok(low)
+}
+
+fn ordered(a: FixedU128, b: FixedU128) -> (u128, u128) {
+	if a <= b {
+		(a.into_inner(), b.into_inner())
+	} else {
+		(b.into_inner(), a.into_inner())
+	}
+}
+
+/// Amount of base asset backing `liquidity` between the two square root prices, i.e.
+/// `L * (sqrt(Pb) - sqrt(Pa)) / (sqrt(Pa) * sqrt(Pb))`.
+pub fn base_delta(
+	sqrt_price_a: FixedU128,
+	sqrt_price_b: FixedU128,
+	liquidity: u128,
+	round_up: bool,
+) -> Result<u128, ArithmeticError> {
+	let (low, high) = ordered(sqrt_price_a, sqrt_price_b);
+	let scaled = mul_div(liquidity, high - low, high, round_up)?;
+	mul_div(scaled, ONE, low, round_up)
+}
+
+/// Amount of quote asset backing `liquidity` between the two square root prices, i.e.
+/// `L * (sqrt(Pb) - sqrt(Pa))`.
+pub fn quote_delta(
+	sqrt_price_a: FixedU128,
+	sqrt_price_b: FixedU128,
+	liquidity: u128,
+	round_up: bool,
+) -> Result<u128, ArithmeticError> {
+	let (low, high) = ordered(sqrt_price_a, sqrt_price_b);
+	mul_div(liquidity, high - low, ONE, round_up)
+}
+
+/// Amounts of base and quote asset backing `liquidity` over the range `[sqrt_price_lower,
+/// sqrt_price_upper]` at the current `sqrt_price`.
+pub fn amounts_for_liquidity(
+	sqrt_price: FixedU128,
+	sqrt_price_lower: FixedU128,
+	sqrt_price_upper: FixedU128,
+	liquidity: u128,
+	round_up: bool,
+) -> Result<(u128, u128), ArithmeticError> {
+	if sqrt_price <= sqrt_price_lower {
+		Ok((base_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?, 0))
+	} else if sqrt_price < sqrt_price_upper {
+		Ok((
+			base_delta(sqrt_price, sqrt_price_upper, liquidity, round_up)?,
+			quote_delta(sqrt_price_lower, sqrt_price, liquidity, round_up)?,
+		))
+	} else {
+		Ok((0, quote_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?))
+	}
+}
+
+/// Greatest liquidity over the range `[sqrt_price_lower, sqrt_price_upper]` at the current
+/// `sqrt_price` that can be backed by the given amounts.
+pub fn liquidity_for_amounts(
+	sqrt_price: FixedU128,
+	sqrt_price_lower: FixedU128,
+	sqrt_price_upper: FixedU128,
+	base_amount: u128,
+	quote_amount: u128,
+) -> Result<u128, ArithmeticError> {
+	let liquidity_for_base = |low: FixedU128, high: FixedU128| {
+		let (low, high) = ordered(low, high);
+		let scaled = mul_div(base_amount, low, ONE, false)?;
+		mul_div(scaled, high, high - low, false)
+	};
+	let liquidity_for_quote = |low: FixedU128, high: FixedU128| {
+		let (low, high) = ordered(low, high);
+		mul_div(quote_amount, ONE, high - low, false)
+	};
+	if sqrt_price <= sqrt_price_lower {
+		liquidity_for_base(sqrt_price_lower, sqrt_price_upper)
+	} else if sqrt_price < sqrt_price_upper {
+		Ok(liquidity_for_base(sqrt_price, sqrt_price_upper)?
+			.min(liquidity_for_quote(sqrt_price_lower, sqrt_price)?))
+	} else {
+		liquidity_for_quote(sqrt_price_lower, sqrt_price_upper)
+	}
+}
+
+/// Square root price after adding (or removing) `amount` of base asset to the virtual reserves,
+/// rounded up.
+pub fn next_sqrt_price_from_base(
+	sqrt_price: FixedU128,
+	liquidity: u128,
+	amount: u128,
+	add: bool,
+) -> Result<FixedU128, ArithmeticError> {
+	if amount == 0 {
+		return Ok(sqrt_price)
+	}
+	let sqrt_price = sqrt_price.into_inner();
+	// L * sqrt(P) / (L +- amount * sqrt(P))
+	let product = mul_div(amount, sqrt_price, ONE, !add)?;
+	let denominator = if add {
+		liquidity.checked_add(product).ok_or(ArithmeticError::Overflow)?
+	} else {
+		liquidity.checked_sub(product).ok_or(ArithmeticError::Underflow)?
+	};
+	mul_div(liquidity, sqrt_price, denominator, true).map(FixedU128::from_inner)
+}
+
+/// Square root price after adding (or removing) `amount` of quote asset to the virtual reserves,
+/// rounded down.
+pub fn next_sqrt_price_from_quote(
+	sqrt_price: FixedU128,
+	liquidity: u128,
+	amount: u128,
+	add: bool,
+) -> Result<FixedU128, ArithmeticError> {
+	let sqrt_price = sqrt_price.into_inner();
+	// sqrt(P) +- amount / L
+	let quotient = mul_div(amount, ONE, liquidity, !add)?;
+	let next = if add {
+		sqrt_price.checked_add(quotient).ok_or(ArithmeticError::Overflow)?
+	} else {
+		sqrt_price.checked_sub(quotient).ok_or(ArithmeticError::Underflow)?
+	};
+	Ok(FixedU128::from_inner(next))
+}
+
+/// Swap within a range of constant `liquidity`, moving the square root price from
+/// `sqrt_price_current` toward `sqrt_price_target`, without overshooting it.
+///
+/// Base asset is sold if the target is below the current price, quote asset otherwise.
+/// `amount_remaining` is the input amount if `exact_input`, the output amount otherwise. If the
+/// target is not reached, `amount_remaining` is fully consumed, the price having been rounded in
+/// favor of the pool.
+pub fn compute_swap_step(
+	sqrt_price_current: FixedU128,
+	sqrt_price_target: FixedU128,
+	liquidity: u128,
+	amount_remaining: u128,
+	exact_input: bool,
+) -> Result<SwapStep, ArithmeticError> {
+	let base_for_quote = sqrt_price_target < sqrt_price_current;
+	let amount_in = |sqrt_price| {
+		if base_for_quote {
+			base_delta(sqrt_price, sqrt_price_current, liquidity, true)
+		} else {
+			quote_delta(sqrt_price_current, sqrt_price, liquidity, true)
+		}
+	};
+	let amount_out = |sqrt_price| {
+		if base_for_quote {
+			quote_delta(sqrt_price, sqrt_price_current, liquidity, false)
+		} else {
+			base_delta(sqrt_price_current, sqrt_price, liquidity, false)
+		}
+	};
+
+	let sqrt_price = if exact_input {
+		if amount_remaining >= amount_in(sqrt_price_target)? {
+			sqrt_price_target
+		} else if base_for_quote {
+			next_sqrt_price_from_base(sqrt_price_current, liquidity, amount_remaining, true)?
+		} else {
+			next_sqrt_price_from_quote(sqrt_price_current, liquidity, amount_remaining, true)?
+		}
+	} else if amount_remaining >= amount_out(sqrt_price_target)? {
+		sqrt_price_target
+	} else if base_for_quote {
+		next_sqrt_price_from_quote(sqrt_price_current, liquidity, amount_remaining, false)?
+	} else {
+		next_sqrt_price_from_base(sqrt_price_current, liquidity, amount_remaining, false)?
+	};
+
+	let reached = sqrt_price == sqrt_price_target;
+	let amount_in = if exact_input && !reached { amount_remaining } else { amount_in(sqrt_price)? };
+	let amount_out = if exact_input {
+		amount_out(sqrt_price)?
+	} else if reached {
+		amount_out(sqrt_price)?.min(amount_remaining)
+	} else {
+		amount_remaining
+	};
+	Ok(SwapStep { sqrt_price, amount_in, amount_out })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_arithmetic::traits::IntegerSquareRoot;
+
+	fn sqrt_price(price: u128) -> FixedU128 {
+		FixedU128::from_inner((price * ONE).integer_sqrt() * 1_000_000_000)
+	}
+
+	#[test]
+	fn ticks_are_monotonic() {
+		assert_eq!(sqrt_price_at_tick(0), Ok(FixedU128::from_inner(ONE)));
+		assert_eq!(sqrt_price_at_tick(1), Ok(SQRT_TICK_RATIO));
+		assert_eq!(sqrt_price_at_tick(MAX_TICK + 1), Err(ArithmeticError::Overflow));
+		let mut previous = sqrt_price_at_tick(MIN_TICK).expect("in range");
+		for tick in (MIN_TICK + 1..=MAX_TICK).step_by(997) {
+			let current = sqrt_price_at_tick(tick).expect("in range");
+			assert!(current > previous);
+			assert_eq!(tick_at_sqrt_price(current), Ok(tick));
+			previous = current;
+		}
+	}
+
+	#[test]
+	fn tick_of_price() {
+		// 1.0001^46054 ~ 100.00
+		let tick = tick_at_sqrt_price(sqrt_price(100)).expect("in range");
+		assert_eq!(tick, 46_054);
+		assert!(sqrt_price_at_tick(tick + 1).expect("in range") > sqrt_price(100));
+	}
+
+	#[test]
+	fn liquidity_roundtrip() {
+		let (lower, upper) = (sqrt_price(50), sqrt_price(200));
+		let current = sqrt_price(100);
+		let liquidity = liquidity_for_amounts(current, lower, upper, 1_000_000, 100_000_000)
+			.expect("no overflow");
+		let (base, quote) =
+			amounts_for_liquidity(current, lower, upper, liquidity, true).expect("no overflow");
+		assert!(base <= 1_000_000 && quote <= 100_000_000);
+		// the binding side is fully used, up to rounding
+		assert!(base.abs_diff(1_000_000) <= 1 || quote.abs_diff(100_000_000) <= 1);
+		// out of range positions are made of a single asset
+		assert_eq!(
+			amounts_for_liquidity(sqrt_price(10), lower, upper, liquidity, false)
+				.expect("no overflow")
+				.1,
+			0
+		);
+		assert_eq!(
+			amounts_for_liquidity(sqrt_price(400), lower, upper, liquidity, false)
+				.expect("no overflow")
+				.0,
+			0
+		);
+	}
+
+	#[test]
+	fn swap_step_reaching_target() {
+		let liquidity = 1_000_000_000_000;
+		let (current, target) = (sqrt_price(100), sqrt_price(99));
+		let step = compute_swap_step(current, target, liquidity, u128::MAX / 2, true)
+			.expect("no overflow");
+		assert_eq!(step.sqrt_price, target);
+		assert_eq!(step.amount_in, base_delta(target, current, liquidity, true).expect("valid"));
+		// price of roughly 99.5 quote per base
+		assert!(step.amount_out / step.amount_in == 99);
+	}
+
+	#[test]
+	fn swap_step_exact_input_and_output_agree() {
+		let liquidity = 1_000_000_000_000;
+		let (current, target) = (sqrt_price(100), sqrt_price(121));
+		let exact_input =
+			compute_swap_step(current, target, liquidity, 1_000_000_000, true).expect("valid");
+		assert!(exact_input.sqrt_price < target && exact_input.sqrt_price > current);
+		assert_eq!(exact_input.amount_in, 1_000_000_000);
+		let exact_output =
+			compute_swap_step(current, target, liquidity, exact_input.amount_out, false)
+				.expect("valid");
+		assert_eq!(exact_output.amount_out, exact_input.amount_out);
+		assert!(exact_output.amount_in <= exact_input.amount_in);
+		assert!(exact_input.amount_in - exact_output.amount_in <= 1_000);
+	}
+}
@@ -10,6 +10,7 @@ use sp_runtime::{
 	traits::{IntegerSquareRoot, One, Zero},
 	ArithmeticError, PerThing,
 };
+use sp_std::vec::Vec;
 
 /// From https://balancer.fi/whitepaper.pdf, equation (2)
 /// Compute the spot price of an asset pair.
@@ -63,15 +64,27 @@ pub fn compute_out_given_in<T: PerThing>(
 	bo: u128,
 	ai: u128,
 ) -> Result<u128, ArithmeticError>
+where
+	T::Inner: Into<u32>,
+{
+	ensure_weights_sum_to_one(wi, wo)?;
+	compute_weighted_out_given_in(wi, wo, bi, bo, ai)
+}
+
+/// Same as [`compute_out_given_in`], for the pair of assets of a pool of any number of assets,
+/// whose weights don't sum to one.
+pub fn compute_weighted_out_given_in<T: PerThing>(
+	wi: T,
+	wo: T,
+	bi: u128,
+	bo: u128,
+	ai: u128,
+) -> Result<u128, ArithmeticError>
 where
 	T::Inner: Into<u32>,
 {
 	let wi: u32 = wi.deconstruct().into();
 	let wo: u32 = wo.deconstruct().into();
-	let weight_sum = wi.safe_add(&wo)?;
-	let expected_weight_sum: u32 = T::one().deconstruct().into();
-	ensure!(weight_sum == expected_weight_sum, ArithmeticError::Overflow);
-
 	let ai = Decimal::from_u128(ai).ok_or(ArithmeticError::Overflow)?;
 	let bi = Decimal::from_u128(bi).ok_or(ArithmeticError::Overflow)?;
 	let bo = Decimal::from_u128(bo).ok_or(ArithmeticError::Overflow)?;
@@ -100,15 +113,27 @@ pub fn compute_in_given_out<T: PerThing>(
 	bo: u128,
 	ao: u128,
 ) -> Result<u128, ArithmeticError>
+where
+	T::Inner: Into<u32>,
+{
+	ensure_weights_sum_to_one(wi, wo)?;
+	compute_weighted_in_given_out(wi, wo, bi, bo, ao)
+}
+
+/// Same as [`compute_in_given_out`], for the pair of assets of a pool of any number of assets,
+/// whose weights don't sum to one.
+pub fn compute_weighted_in_given_out<T: PerThing>(
+	wi: T,
+	wo: T,
+	bi: u128,
+	bo: u128,
+	ao: u128,
+) -> Result<u128, ArithmeticError>
 where
 	T::Inner: Into<u32>,
 {
 	let wi: u32 = wi.deconstruct().into();
 	let wo: u32 = wo.deconstruct().into();
-	let weight_sum = wi.safe_add(&wo)?;
-	let expected_weight_sum: u32 = T::one().deconstruct().into();
-	ensure!(weight_sum == expected_weight_sum, ArithmeticError::Overflow);
-
 	let ao = Decimal::from_u128(ao).ok_or(ArithmeticError::Overflow)?;
 	let bi = Decimal::from_u128(bi).ok_or(ArithmeticError::Overflow)?;
 	let bo = Decimal::from_u128(bo).ok_or(ArithmeticError::Overflow)?;
@@ -123,6 +148,18 @@ where
 	Ok(ai)
 }
 
+fn ensure_weights_sum_to_one<T: PerThing>(wi: T, wo: T) -> Result<(), ArithmeticError>
+where
+	T::Inner: Into<u32>,
+{
+	let wi: u32 = wi.deconstruct().into();
+	let wo: u32 = wo.deconstruct().into();
+	let weight_sum = wi.safe_add(&wo)?;
+	let expected_weight_sum: u32 = T::one().deconstruct().into();
+	ensure!(weight_sum == expected_weight_sum, ArithmeticError::Overflow);
+	Ok(())
+}
+
 /// https://uniswap.org/whitepaper.pdf, equation (13)
 /// Compute the initial share of an LP provider.
 /// - `base_amount` the base asset amount deposited.
@@ -163,3 +200,203 @@ pub fn compute_deposit_lp(
 		Ok((overwritten_quote_amount, lp_to_mint))
 	}
 }
+
+/// Weight of a pool of any number of assets, as a decimal.
+fn decimal_weight<T: PerThing>(weight: T) -> Result<Decimal, ArithmeticError>
+where
+	T::Inner: Into<u32>,
+{
+	let full_perthing =
+		Decimal::from_u32(T::one().deconstruct().into()).ok_or(ArithmeticError::Overflow)?;
+	let weight = Decimal::from_u32(weight.deconstruct().into()).ok_or(ArithmeticError::Overflow)?;
+	weight.safe_div(&full_perthing)
+}
+
+/// Compute the initial share of an LP provider of a pool of any number of assets, as the
+/// weighted geometric mean of the deposited amounts.
+/// - `deposits` the weight of each asset, summing to one, along the deposited amount.
+pub fn compute_weighted_first_deposit_lp<T: PerThing>(
+	deposits: &[(T, u128)],
+) -> Result<u128, ArithmeticError>
+where
+	T::Inner: Into<u32>,
+{
+	let (_, reference) = deposits.first().ok_or(ArithmeticError::DivisionByZero)?;
+	let reference = Decimal::from_u128(*reference).ok_or(ArithmeticError::Overflow)?;
+	// amounts are taken relative to the first one to keep the powers in range
+	let mut lp = reference;
+	for (weight, amount) in deposits {
+		let amount = Decimal::from_u128(*amount).ok_or(ArithmeticError::Overflow)?;
+		let ratio = amount.safe_div(&reference)?;
+		lp = lp.safe_mul(
+			&ratio.checked_powd(decimal_weight(*weight)?).ok_or(ArithmeticError::Overflow)?,
+		)?;
+	}
+	lp.to_u128().ok_or(ArithmeticError::Overflow)
+}
+
+/// From https://balancer.fi/whitepaper.pdf, equation (25) generalised to deposits of any
+/// number of assets.
+/// Compute the share of an LP provider for an existing, non-empty pool. The part of a deposit
+/// exceeding the proportional deposit is charged `fee` scaled by `(1 - w)`, as if swapped.
+/// - `lp_total_issuance` the total LP already issued to other LP providers.
+/// - `deposits` the weight of each asset, summing to one, along the pool balance and the deposited
+///   amount.
+/// - `fee` the trading fee of the pool.
+pub fn compute_weighted_deposit_lp<T: PerThing>(
+	lp_total_issuance: u128,
+	deposits: &[(T, u128, u128)],
+	fee: T,
+) -> Result<u128, ArithmeticError>
+where
+	T::Inner: Into<u32>,
+{
+	let fee = decimal_weight(fee)?;
+	let mut ratios = Vec::with_capacity(deposits.len());
+	for (_, balance, amount) in deposits {
+		let balance = Decimal::from_u128(*balance).ok_or(ArithmeticError::Overflow)?;
+		let amount = Decimal::from_u128(*amount).ok_or(ArithmeticError::Overflow)?;
+		ratios.push((balance, amount, amount.safe_div(&balance)?));
+	}
+	let proportional_ratio = ratios
+		.iter()
+		.map(|(_, _, ratio)| *ratio)
+		.min()
+		.ok_or(ArithmeticError::DivisionByZero)?;
+
+	let mut invariant_ratio = Decimal::one();
+	for ((weight, ..), (balance, amount, _)) in deposits.iter().zip(ratios) {
+		let weight = decimal_weight(*weight)?;
+		let taxable_amount = amount.safe_sub(&balance.safe_mul(&proportional_ratio)?)?;
+		let charged_fee =
+			taxable_amount.safe_mul(&fee)?.safe_mul(&Decimal::one().safe_sub(&weight)?)?;
+		let balance_ratio =
+			balance.safe_add(&amount.safe_sub(&charged_fee)?)?.safe_div(&balance)?;
+		invariant_ratio = invariant_ratio
+			.safe_mul(&balance_ratio.checked_powd(weight).ok_or(ArithmeticError::Overflow)?)?;
+	}
+	let lp_total_issuance =
+		Decimal::from_u128(lp_total_issuance).ok_or(ArithmeticError::Overflow)?;
+	lp_total_issuance
+		.safe_mul(&invariant_ratio.safe_sub(&Decimal::one())?)?
+		.to_u128()
+		.ok_or(ArithmeticError::Overflow)
+}
+
+/// From https://balancer.fi/whitepaper.pdf, equation (31)
+/// Compute the amount of a single asset redeemed for the given LP amount. The part of the
+/// withdrawal exceeding the proportional withdrawal is charged `fee` scaled by `(1 - w)`, as if
+/// swapped.
+/// - `lp_total_issuance` the total LP issued.
+/// - `lp_amount` the LP amount redeemed.
+/// - `weight` the weight of the withdrawn asset, the weights of the pool summing to one.
+/// - `balance` the pool balance of the withdrawn asset.
+/// - `fee` the trading fee of the pool.
+pub fn compute_weighted_single_asset_redeem<T: PerThing>(
+	lp_total_issuance: u128,
+	lp_amount: u128,
+	weight: T,
+	balance: u128,
+	fee: T,
+) -> Result<u128, ArithmeticError>
+where
+	T::Inner: Into<u32>,
+{
+	let weight = decimal_weight(weight)?;
+	let fee = decimal_weight(fee)?;
+	let lp_total_issuance =
+		Decimal::from_u128(lp_total_issuance).ok_or(ArithmeticError::Overflow)?;
+	let lp_amount = Decimal::from_u128(lp_amount).ok_or(ArithmeticError::Overflow)?;
+	let balance = Decimal::from_u128(balance).ok_or(ArithmeticError::Overflow)?;
+
+	let lp_ratio = Decimal::one().safe_sub(&lp_amount.safe_div(&lp_total_issuance)?)?;
+	let balance_ratio = lp_ratio
+		.checked_powd(Decimal::one().safe_div(&weight)?)
+		.ok_or(ArithmeticError::Overflow)?;
+	let amount = balance.safe_mul(&Decimal::one().safe_sub(&balance_ratio)?)?;
+	// only the part exceeding the proportional withdrawal is charged
+	let proportional_amount = balance.safe_mul(&Decimal::one().safe_sub(&lp_ratio)?)?;
+	let taxable_amount = amount.safe_sub(&proportional_amount)?;
+	let charged_fee =
+		taxable_amount.safe_mul(&fee)?.safe_mul(&Decimal::one().safe_sub(&weight)?)?;
+	amount.safe_sub(&charged_fee)?.to_u128().ok_or(ArithmeticError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::Permill;
+
+	const UNIT: u128 = 1_000_000_000_000;
+
+	#[test]
+	fn weighted_swap_only_depends_on_weights_ratio() {
+		let expected = compute_out_given_in(
+			Permill::from_percent(30),
+			Permill::from_percent(70),
+			1_000_000,
+			2_000_000,
+			1_000,
+		);
+		assert_eq!(
+			compute_weighted_out_given_in(
+				Permill::from_percent(15),
+				Permill::from_percent(35),
+				1_000_000,
+				2_000_000,
+				1_000
+			),
+			expected
+		);
+	}
+
+	#[test]
+	fn weighted_liquidity() {
+		let weights =
+			[Permill::from_percent(50), Permill::from_percent(25), Permill::from_percent(25)];
+		let fee = Permill::from_percent(1);
+		let lp_total_issuance = compute_weighted_first_deposit_lp(&[
+			(weights[0], 100 * UNIT),
+			(weights[1], 400 * UNIT),
+			(weights[2], 400 * UNIT),
+		])
+		.expect("in range");
+		assert!(lp_total_issuance.abs_diff(200 * UNIT) < 10_000_000);
+
+		let proportional_lp = compute_weighted_deposit_lp(
+			lp_total_issuance,
+			&[
+				(weights[0], 100 * UNIT, 10 * UNIT),
+				(weights[1], 400 * UNIT, 40 * UNIT),
+				(weights[2], 400 * UNIT, 40 * UNIT),
+			],
+			fee,
+		)
+		.expect("in range");
+		assert!(proportional_lp.abs_diff(lp_total_issuance / 10) < 10_000_000);
+
+		// worth 5% of the pool at spot price, less the fees
+		let single_asset_lp = compute_weighted_deposit_lp(
+			lp_total_issuance,
+			&[
+				(weights[0], 100 * UNIT, 10 * UNIT),
+				(weights[1], 400 * UNIT, 0),
+				(weights[2], 400 * UNIT, 0),
+			],
+			fee,
+		)
+		.expect("in range");
+		assert!(single_asset_lp < lp_total_issuance / 20);
+
+		// no round trip profit
+		let redeemed = compute_weighted_single_asset_redeem(
+			lp_total_issuance + single_asset_lp,
+			single_asset_lp,
+			weights[0],
+			110 * UNIT,
+			fee,
+		)
+		.expect("in range");
+		assert!(redeemed < 10 * UNIT && redeemed > 9 * UNIT);
+	}
+}
@@ -1,3 +1,4 @@
+pub mod concentrated_liquidity;
 pub mod constant_product;
 pub mod price;
 pub mod stable_swap;
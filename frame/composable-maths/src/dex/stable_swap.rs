@@ -134,3 +134,143 @@ pub fn compute_base(new_quote: u128, amp_coeff: u128, d: u128) -> Result<u128, D
 	}
 	Err(DispatchError::Other("could not compute d"))
 }
+
+/// Invariant D of a pool holding any number of assets, see [`compute_d`] for the formula.
+///
+/// Every balance must be positive.
+pub fn compute_d_for_balances(balances: &[u128], amp_coeff: u128) -> Result<u128, DispatchError> {
+	let n = to_big_uint(balances.len() as u128);
+	let one = to_big_uint(1_u128);
+	let sum = balances
+		.iter()
+		.try_fold(0_u128, |sum, balance| sum.checked_add(*balance))
+		.ok_or(ArithmeticError::Overflow)?;
+	if sum == 0 {
+		return Ok(0_u128)
+	}
+	if balances.contains(&0) {
+		return Err(ArithmeticError::DivisionByZero.into())
+	}
+	let sum = to_big_uint(sum);
+	let ann = balances.iter().fold(to_big_uint(amp_coeff), |ann, _| ann.mul(&n));
+	let mut d = sum.clone();
+
+	for _ in 0..255 {
+		// d_p = d^(n + 1) / (n^n * prod(x_i))
+		let mut d_p = d.clone();
+		for balance in balances {
+			let mut d_p_d = d_p.mul(&d);
+			d_p = safe_div(&mut d_p_d, &mut to_big_uint(*balance).mul(&n))?;
+		}
+
+		let d_prev = d.clone();
+		// d = (ann * sum + d_p * n) * d / (ann * d + (n + 1) * d_p - d)
+		let mut numerator = ann.clone().mul(&sum).add(&d_p.clone().mul(&n)).mul(&d);
+		let mut denominator = ann
+			.clone()
+			.mul(&d)
+			.add(&n.clone().add(&one).mul(&d_p))
+			.sub(&d)
+			.map_err(|_| ArithmeticError::Underflow)?;
+		d = safe_div(&mut numerator, &mut denominator)?;
+
+		if d.clone() > d_prev {
+			if d.clone() - d_prev <= one {
+				d.lstrip();
+				return Ok(d.try_into().map_err(|_| ArithmeticError::Overflow)?)
+			}
+		} else if d_prev - d.clone() <= one {
+			d.lstrip();
+			return Ok(d.try_into().map_err(|_| ArithmeticError::Overflow)?)
+		}
+	}
+	Err(DispatchError::Other("could not compute d"))
+}
+
+/// Balance of the asset at `index` keeping the invariant of the pool equal to `d`, the other
+/// assets having the given `balances`. The balance at `index` is ignored.
+///
+/// Generalisation of [`compute_base`] to any number of assets:
+///
+/// ```pseudocode
+/// c = D^(n+1) / (n^n * prod' * A * n^n)
+/// b = sum' + D / (A * n^n)
+///
+/// x = (x^2 + c) / (2 * x + b - D)
+/// ```
+pub fn compute_balance_for_d(
+	balances: &[u128],
+	index: usize,
+	amp_coeff: u128,
+	d: u128,
+) -> Result<u128, DispatchError> {
+	let n = to_big_uint(balances.len() as u128);
+	let one = to_big_uint(1_u128);
+	let two = to_big_uint(2_u128);
+	let d = to_big_uint(d);
+	let mut ann = balances.iter().fold(to_big_uint(amp_coeff), |ann, _| ann.mul(&n));
+
+	let mut c = d.clone();
+	let mut s = to_big_uint(0_u128);
+	for (i, balance) in balances.iter().enumerate() {
+		if i == index {
+			continue
+		}
+		if *balance == 0 {
+			return Err(ArithmeticError::DivisionByZero.into())
+		}
+		s = s.add(&to_big_uint(*balance));
+		let mut c_d = c.mul(&d);
+		c = safe_div(&mut c_d, &mut to_big_uint(*balance).mul(&n))?;
+	}
+	let mut c_d = c.mul(&d);
+	let c = safe_div(&mut c_d, &mut ann.clone().mul(&n))?;
+	let mut d_ann = d.clone();
+	let b = s.add(&safe_div(&mut d_ann, &mut ann)?);
+
+	let mut y = d.clone();
+	for _ in 0..255 {
+		let y_prev = y.clone();
+		let mut numerator = y.clone().mul(&y).add(&c);
+		let mut denominator =
+			two.clone().mul(&y).add(&b).sub(&d).map_err(|_| ArithmeticError::Underflow)?;
+		y = safe_div(&mut numerator, &mut denominator)?;
+		if y.clone() > y_prev {
+			if y.clone() - y_prev <= one {
+				y.lstrip();
+				return Ok(y.try_into().map_err(|_| ArithmeticError::Overflow)?)
+			}
+		} else if y_prev - y.clone() <= one {
+			y.lstrip();
+			return Ok(y.try_into().map_err(|_| ArithmeticError::Overflow)?)
+		}
+	}
+	Err(DispatchError::Other("could not compute balance"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const UNIT: u128 = 1_000_000_000_000;
+
+	#[test]
+	fn invariant_of_two_assets() {
+		for (base, quote, amp) in
+			[(1_000, 1_000, 100), (5_000 * UNIT, 3_000 * UNIT, 10), (10, 10_000, 1)]
+		{
+			assert_eq!(compute_d_for_balances(&[base, quote], amp), compute_d(base, quote, amp),);
+		}
+	}
+
+	#[test]
+	fn swap_between_three_assets_near_par() {
+		let balances = [1_000_000 * UNIT, 1_000_000 * UNIT, 1_000_000 * UNIT];
+		let d = compute_d_for_balances(&balances, 100).expect("converges");
+		assert!(d.abs_diff(3_000_000 * UNIT) <= 3);
+		let new_balances = [balances[0] + 10_000 * UNIT, balances[1], balances[2]];
+		let new_balance = compute_balance_for_d(&new_balances, 1, 100, d).expect("converges");
+		let amount_out = balances[1] - new_balance;
+		assert!(amount_out < 10_000 * UNIT && amount_out > 9_990 * UNIT);
+	}
+}
@@ -0,0 +1,143 @@
+use composable_traits::{
+	defi::{CurrencyPair, Rate},
+	dex::{Amm, PoolTwap, RedeemableAssets, RemoveLiquiditySimulationResult},
+};
+use sp_runtime::DispatchError;
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData};
+
+/// The error returned by every operation of [`NoPools`].
+pub const NO_POOLS: DispatchError = DispatchError::Other("no pools");
+
+/// An [`Amm`] and [`PoolTwap`] without any pool, for test runtimes that need one but never trade
+/// through it. Every operation fails with [`NO_POOLS`].
+///
+/// # Examples
+///
+/// ```
+/// # use composable_tests_helpers::test::dex::{NoPools, NO_POOLS};
+/// # use composable_traits::dex::Amm;
+/// type Pools = NoPools<u128, u128, u64, u128>;
+///
+/// assert!(!Pools::pool_exists(1));
+/// assert_eq!(Pools::lp_token(1), Err(NO_POOLS));
+/// ```
+pub struct NoPools<AssetId, Balance, AccountId, PoolId>(
+	PhantomData<(AssetId, Balance, AccountId, PoolId)>,
+);
+
+impl<AssetId, Balance, AccountId, PoolId> PoolTwap
+	for NoPools<AssetId, Balance, AccountId, PoolId>
+{
+	type AssetId = AssetId;
+	type PoolId = PoolId;
+
+	fn twap(_pool_id: PoolId, _pair: CurrencyPair<AssetId>) -> Result<Rate, DispatchError> {
+		Err(NO_POOLS)
+	}
+}
+
+impl<AssetId, Balance, AccountId, PoolId> Amm for NoPools<AssetId, Balance, AccountId, PoolId> {
+	type AssetId = AssetId;
+	type Balance = Balance;
+	type AccountId = AccountId;
+	type PoolId = PoolId;
+
+	fn pool_exists(_pool_id: PoolId) -> bool {
+		false
+	}
+
+	fn currency_pair(_pool_id: PoolId) -> Result<CurrencyPair<AssetId>, DispatchError> {
+		Err(NO_POOLS)
+	}
+
+	fn lp_token(_pool_id: PoolId) -> Result<AssetId, DispatchError> {
+		Err(NO_POOLS)
+	}
+
+	fn redeemable_assets_for_lp_tokens(
+		_pool_id: PoolId,
+		_lp_amount: Balance,
+		_min_expected_amounts: BTreeMap<AssetId, Balance>,
+	) -> Result<RedeemableAssets<AssetId, Balance>, DispatchError> {
+		Err(NO_POOLS)
+	}
+
+	fn simulate_add_liquidity(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_amounts: BTreeMap<AssetId, Balance>,
+	) -> Result<Balance, DispatchError> {
+		Err(NO_POOLS)
+	}
+
+	fn simulate_remove_liquidity(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_lp_amount: Balance,
+		_min_expected_amounts: BTreeMap<AssetId, Balance>,
+	) -> Result<RemoveLiquiditySimulationResult<AssetId, Balance>, DispatchError> {
+		Err(NO_POOLS)
+	}
+
+	fn get_exchange_value(
+		_pool_id: PoolId,
+		_asset_id: AssetId,
+		_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Err(NO_POOLS)
+	}
+
+	fn buy(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_asset_id: AssetId,
+		_amount: Balance,
+		_min_receive: Balance,
+		_keep_alive: bool,
+	) -> Result<Balance, DispatchError> {
+		Err(NO_POOLS)
+	}
+
+	fn sell(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_asset_id: AssetId,
+		_amount: Balance,
+		_min_receive: Balance,
+		_keep_alive: bool,
+	) -> Result<Balance, DispatchError> {
+		Err(NO_POOLS)
+	}
+
+	fn add_liquidity(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_base_amount: Balance,
+		_quote_amount: Balance,
+		_min_mint_amount: Balance,
+		_keep_alive: bool,
+	) -> Result<(), DispatchError> {
+		Err(NO_POOLS)
+	}
+
+	fn remove_liquidity(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_lp_amount: Balance,
+		_min_base_amount: Balance,
+		_min_quote_amount: Balance,
+	) -> Result<(), DispatchError> {
+		Err(NO_POOLS)
+	}
+
+	fn exchange(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_pair: CurrencyPair<AssetId>,
+		_quote_amount: Balance,
+		_min_receive: Balance,
+		_keep_alive: bool,
+	) -> Result<Balance, DispatchError> {
+		Err(NO_POOLS)
+	}
+}
@@ -1,4 +1,5 @@
 pub mod block;
 pub mod currency;
+pub mod dex;
 pub mod helper;
 pub mod proptest;
@@ -1,4 +1,7 @@
-use crate::{currency::BalanceLike, defi::CurrencyPair};
+use crate::{
+	currency::BalanceLike,
+	defi::{CurrencyPair, Rate},
+};
 use codec::{Decode, Encode, MaxEncodedLen};
 use composable_support::math::safe::{SafeAdd, SafeSub};
 use frame_support::{
@@ -134,6 +137,81 @@ pub trait Amm {
 	) -> Result<Self::Balance, DispatchError>;
 }
 
+/// Extension of [`Amm`] to pools holding more than two assets, which don't have a currency pair.
+pub trait MultiAssetAmm: Amm {
+	/// Assets held by the pool, i.e. the pair for two assets pools.
+	fn assets(pool_id: Self::PoolId) -> Result<Vec<Self::AssetId>, DispatchError>;
+
+	/// Get pure exchange value of `amount` of `asset_out`, i.e. the amount of `asset_in` required
+	/// to obtain it. (Note this does not include fees.)
+	fn get_exchange_value_between(
+		pool_id: Self::PoolId,
+		asset_in: Self::AssetId,
+		asset_out: Self::AssetId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError>;
+
+	/// Trade `amount_in` of `asset_in` against `asset_out`, returning the amount received.
+	#[allow(clippy::too_many_arguments)]
+	fn exchange_assets(
+		who: &Self::AccountId,
+		pool_id: Self::PoolId,
+		asset_in: Self::AssetId,
+		amount_in: Self::Balance,
+		asset_out: Self::AssetId,
+		min_receive: Self::Balance,
+		keep_alive: bool,
+	) -> Result<Self::Balance, DispatchError>;
+
+	/// Deposit any subset of the assets of the pool, possibly a single one, returning the amount
+	/// of LP tokens minted.
+	/// Deposits which are not proportional to the pool balances are charged fees.
+	fn add_liquidity_assets(
+		who: &Self::AccountId,
+		pool_id: Self::PoolId,
+		amounts: BTreeMap<Self::AssetId, Self::Balance>,
+		min_mint_amount: Self::Balance,
+		keep_alive: bool,
+	) -> Result<Self::Balance, DispatchError>
+	where
+		Self::AssetId: sp_std::cmp::Ord;
+
+	/// Withdraw all the assets of the pool proportionally to its balances.
+	/// `min_amounts` - minimum amounts of underlying coins to receive, missing ones being zero.
+	fn remove_liquidity_assets(
+		who: &Self::AccountId,
+		pool_id: Self::PoolId,
+		lp_amount: Self::Balance,
+		min_amounts: BTreeMap<Self::AssetId, Self::Balance>,
+	) -> Result<BTreeMap<Self::AssetId, Self::Balance>, DispatchError>
+	where
+		Self::AssetId: sp_std::cmp::Ord;
+
+	/// Withdraw a single asset of the pool, returning the amount received.
+	/// The withdrawal is charged fees as it unbalances the pool.
+	fn remove_liquidity_single_asset(
+		who: &Self::AccountId,
+		pool_id: Self::PoolId,
+		lp_amount: Self::Balance,
+		asset_id: Self::AssetId,
+		min_amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError>;
+}
+
+/// Time weighted average prices of the pools of an AMM, used as a fallback price source.
+pub trait PoolTwap {
+	type AssetId;
+	type PoolId;
+
+	/// Time weighted average price of `pair.base` in `pair.quote`, i.e. the amount of `pair.quote`
+	/// for one `pair.base`.
+	/// Fails if the pool doesn't hold `pair` or doesn't track its average price.
+	fn twap(
+		pool_id: Self::PoolId,
+		pair: CurrencyPair<Self::AssetId>,
+	) -> Result<Rate, DispatchError>;
+}
+
 #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, Copy, RuntimeDebug)]
 pub enum RewardPoolType {
 	LP,
@@ -301,6 +379,45 @@ pub struct ConstantProductPoolInfo<AccountId, AssetId> {
 	pub quote_weight: Permill,
 }
 
+/// Pool concentrating liquidity within price ranges chosen by the liquidity providers.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct ConcentratedLiquidityPoolInfo<AccountId, AssetId> {
+	/// Owner of pool
+	pub owner: AccountId,
+	/// Swappable assets, the price being expressed in quote per base
+	pub pair: CurrencyPair<AssetId>,
+	/// Collection of the financial NFTs representing the liquidity positions
+	pub position_collection: AssetId,
+	/// Amount of the fee pool charges for the exchange
+	pub fee_config: FeeConfig,
+	/// Positions must be bounded by ticks multiple of the spacing
+	pub tick_spacing: u32,
+}
+
+/// Invariant maintained by a pool holding any number of assets.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum MultiAssetPoolKind {
+	/// Curve invariant, the assets being traded close to par. All the weights must be equal.
+	StableSwap { amplification_coefficient: u16 },
+	/// Balancer invariant, the value of each asset being its weight of the pool value.
+	ConstantProduct,
+}
+
+/// Pool holding any number of assets, with per asset weights.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MultiAssetPoolInfo<AccountId, AssetId, Weights> {
+	/// Owner of pool
+	pub owner: AccountId,
+	/// Swappable assets, along their weight. The weights sum to one.
+	pub assets: Weights,
+	/// AssetId of LP token
+	pub lp_token: AssetId,
+	/// Amount of the fee pool charges for the exchange
+	pub fee_config: FeeConfig,
+	/// Invariant of the pool
+	pub kind: MultiAssetPoolKind,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum SaleState {
 	NotStarted,
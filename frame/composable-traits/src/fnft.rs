@@ -25,6 +25,10 @@ pub trait FinancialNft<AccountId>: Inspect<AccountId> {
 	/// Retrieve the next valid financial NFT ID for the given collection in order to
 	/// mint a new NFT.
 	fn get_next_nft_id(collection: &Self::CollectionId) -> Result<Self::ItemId, DispatchError>;
+
+	/// Whether the financial NFT is listed for sale. Protocols must not take value out of listed
+	/// financial NFTs, as the buyer pays for the value at the time of listing.
+	fn is_listed(collection: &Self::CollectionId, instance: &Self::ItemId) -> bool;
 }
 
 /// Trait to be implemented by protocol supporting financial NFTs.
@@ -4,7 +4,7 @@ use composable_support::{
 	validation::Validate,
 };
 use scale_info::TypeInfo;
-use sp_std::{cmp::Ordering, convert::TryInto};
+use sp_std::{cmp::Ordering, convert::TryInto, vec::Vec};
 
 use sp_runtime::{
 	traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Saturating, Zero},
@@ -107,6 +107,48 @@ impl InterestRateModel {
 
 		rate_to_pool.saturating_mul(util)
 	}
+
+	/// Evaluates the model at utilization ratios from 0% to 100%, every `utilization_step`, with
+	/// `reserve_factor` of the interest kept out of the supply rate.
+	///
+	/// Models with state, like [`DynamicPIDControllerModel`], are evaluated from their current
+	/// state at every point. Returns `None` if `utilization_step` is zero or the model has no rate
+	/// at some point.
+	pub fn simulate(
+		&self,
+		utilization_step: Percent,
+		reserve_factor: ZeroToOneFixedU128,
+	) -> Option<Vec<InterestRatePoint>> {
+		if utilization_step.is_zero() {
+			return None
+		}
+		let mut utilizations: Vec<Percent> = (0..=100)
+			.step_by(utilization_step.deconstruct().into())
+			.map(Percent::from_percent)
+			.collect();
+		if utilizations.last() != Some(&Percent::one()) {
+			utilizations.push(Percent::one());
+		}
+		utilizations
+			.into_iter()
+			.map(|utilization| {
+				let mut model = *self;
+				let borrow_rate = model.get_borrow_rate(utilization)?;
+				let supply_rate =
+					Self::get_supply_rate(borrow_rate, utilization.into(), reserve_factor);
+				Some(InterestRatePoint { utilization, borrow_rate, supply_rate })
+			})
+			.collect()
+	}
+}
+
+/// The rates of an [`InterestRateModel`] at a utilization ratio.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+pub struct InterestRatePoint {
+	pub utilization: Percent,
+	pub borrow_rate: Rate,
+	pub supply_rate: Rate,
 }
 
 pub struct InteresteRateModelIsValid;
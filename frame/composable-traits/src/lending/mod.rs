@@ -40,6 +40,12 @@ pub struct UpdateInput<LiquidationStrategyId, BlockNumber> {
 	pub liquidators: Vec<LiquidationStrategyId>,
 	/// Count of blocks until throw error PriceIsTooOld
 	pub max_price_age: BlockNumber,
+	/// Collateral/debt ratio under which loans can be liquidated, at most `collateral_factor`.
+	pub liquidation_threshold: MoreThanOneFixedU128,
+	/// Share of the debt of a loan which can be repaid by a single liquidation.
+	pub close_factor: Perquintill,
+	/// Collateral given to liquidators on top of the value of the debt they repaid.
+	pub liquidation_incentive: Perquintill,
 }
 
 /// input to create market extrinsic
@@ -86,6 +92,12 @@ pub struct MarketConfig<VaultId, AssetId, AccountId, LiquidationStrategyId, Bloc
 	pub interest_rate_model: InterestRateModel,
 	pub under_collateralized_warn_percent: Percent,
 	pub liquidators: Vec<LiquidationStrategyId>,
+	/// Collateral/debt ratio under which loans can be liquidated, at most `collateral_factor`.
+	pub liquidation_threshold: MoreThanOneFixedU128,
+	/// Share of the debt of a loan which can be repaid by a single liquidation.
+	pub close_factor: Perquintill,
+	/// Collateral given to liquidators on top of the value of the debt they repaid.
+	pub liquidation_incentive: Perquintill,
 }
 
 /// Different ways that a market can be repaid.
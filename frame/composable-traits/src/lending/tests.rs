@@ -71,6 +71,29 @@ fn get_supply_rate_works() {
 	);
 }
 
+#[test]
+fn simulate_evaluates_model_across_utilization() {
+	let model = InterestRateModel::default();
+	let reserve_factor = ZeroToOneFixedU128::saturating_from_rational(10, 100);
+	let points = model.simulate(Percent::from_percent(30), reserve_factor).unwrap();
+
+	let utilizations: Vec<_> = points.iter().map(|point| point.utilization).collect();
+	assert_eq!(utilizations, [0, 30, 60, 90, 100].map(Percent::from_percent).to_vec(),);
+	for point in points {
+		let mut evaluated_model = model;
+		assert_eq!(Some(point.borrow_rate), evaluated_model.get_borrow_rate(point.utilization));
+		assert_eq!(
+			point.supply_rate,
+			InterestRateModel::get_supply_rate(
+				point.borrow_rate,
+				point.utilization.into(),
+				reserve_factor
+			),
+		);
+	}
+	assert_eq!(model.simulate(Percent::zero(), reserve_factor), None);
+}
+
 #[test]
 fn curve_model_correctly_calculates_borrow_rate() {
 	let mut model = CurveModel::new(Rate::saturating_from_rational(2, 100)).unwrap();
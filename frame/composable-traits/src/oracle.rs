@@ -3,15 +3,56 @@ use crate::{
 	defi::{CurrencyPair, Ratio},
 };
 use frame_support::{dispatch::DispatchError, pallet_prelude::*};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::Percent;
 
 // block timestamped value
 #[derive(Encode, Decode, MaxEncodedLen, Default, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Price<PriceValue, BlockNumber> {
 	/// value
 	pub price: PriceValue,
 	pub block: BlockNumber,
 }
 
+/// Price of an asset as consumers get it, along where it comes from.
+#[derive(Encode, Decode, Default, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CurrentPrice<PriceValue, BlockNumber> {
+	pub price: PriceValue,
+	pub block: BlockNumber,
+	/// The price submitted by the oracles is frozen by the circuit breaker of the asset.
+	pub frozen: bool,
+	/// The price comes from a fallback source rather than from the oracles.
+	pub fallback: bool,
+}
+
+/// Open, high, low and close prices of an asset over a bucket of blocks.
+#[derive(Encode, Decode, Default, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Ohlc<PriceValue, BlockNumber> {
+	/// First block of the bucket.
+	pub start: BlockNumber,
+	pub open: PriceValue,
+	pub high: PriceValue,
+	pub low: PriceValue,
+	pub close: PriceValue,
+}
+
+/// Accuracy of the prices submitted by an oracle for an asset.
+#[derive(Encode, Decode, Default, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OracleAccuracy<AccountId> {
+	pub oracle: AccountId,
+	/// Answers taken into account when aggregating a price.
+	pub answers: u32,
+	/// Answers out of the threshold of the asset, for which the oracle has been slashed.
+	pub slashes: u32,
+	/// Average closeness of the answers to the aggregated prices.
+	pub average_accuracy: Percent,
+}
+
 /// Statistics useful for oracle rewarding and tracking.
 #[derive(Encode, Decode, MaxEncodedLen, Default, Debug, PartialEq, Eq, TypeInfo, Clone)]
 pub struct RewardTracker<Balance, Timestamp> {
@@ -133,12 +133,12 @@ pub struct RewardPool<AccountId, AssetId, Balance, BlockNumber, DurationPresets,
 
 	// possible lock config for this pool
 	pub lock: LockConfig<DurationPresets>,
-	// TODO (vim): Introduce asset ids for financial NFT as well as the shares of the pool
-	// Asset ID issued as shares for staking in the pool. Eg: for PBLO -> xPBLO
-	// pub share_asset_id: AssetId;
 
-	// Asset ID (collection ID) of the financial NFTs issued for staking positions of this pool
-	// pub financial_nft_asset_id: AssetId;
+	/// Asset ID issued as shares for staking in the pool. Eg: for PBLO -> xPBLO
+	pub share_asset_id: AssetId,
+
+	/// Asset ID (collection ID) of the financial NFTs issued for staking positions of this pool
+	pub financial_nft_asset_id: AssetId,
 }
 
 /// Default transfer limit on new asset added as rewards.
@@ -179,12 +179,6 @@ pub enum RewardPoolConfiguration<AccountId, AssetId, BlockNumber, RewardConfigs,
 		reward_configs: RewardConfigs,
 		// possible lock config for this reward
 		lock: LockConfig<DurationPresets>,
-		// TODO (vim): Introduce asset ids for financial NFT as well as the shares of the pool
-		// Asset ID issued as shares for staking in the pool. Eg: for PBLO -> xPBLO
-		// share_asset_id: AssetId,
-
-		// Asset ID (collection ID) of the financial NFTs issued for staking positions of this pool
-		// financial_nft_asset_id: AssetId
 	},
 }
 
@@ -192,11 +186,11 @@ pub enum RewardPoolConfiguration<AccountId, AssetId, BlockNumber, RewardConfigs,
 /// should exist for each position when stored in the runtime storage.
 /// TODO refer to the relevant section in the design doc.
 #[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, TypeInfo)]
-pub struct Stake<AccountId, RewardPoolId, Balance, Reductions> {
-	/// Protocol or the user account that owns this stake
-	// TODO (vim): Remove the owner and track the financial NFT ID. In order to prevent a direct
-	// dependancy to NFTs we can also just use nft ID as position ID. 	pub financial_nft_id: ItemId
-	pub owner: AccountId,
+pub struct Stake<ItemId, RewardPoolId, Balance, Reductions> {
+	/// Financial NFT representing this stake, in the financial NFT collection of the reward pool.
+	/// The owner of the NFT owns the stake, and the assets of the stake are held by the account of
+	/// the NFT.
+	pub fnft_instance_id: ItemId,
 
 	/// Reward Pool ID from which pool to allocate rewards for this
 	pub reward_pool_id: RewardPoolId,
@@ -264,6 +258,7 @@ pub trait ProtocolStaking {
 /// Interface for protocol staking.
 pub trait Staking {
 	type AccountId;
+	type AssetId;
 	type RewardPoolId;
 	type Balance;
 	type PositionId;
@@ -306,6 +301,43 @@ pub trait Staking {
 		position: &Self::PositionId,
 		ratio: Permill,
 	) -> Result<[Self::PositionId; 2], DispatchError>;
+
+	/// Claim the pending rewards of a position without unstaking it.
+	///
+	/// Arguments
+	///
+	/// * `assets` the reward assets to claim, all the reward assets of the pool if `None`.
+	/// * `to` the account to transfer the claimed rewards to.
+	fn claim(
+		who: &Self::AccountId,
+		position: &Self::PositionId,
+		assets: Option<&[Self::AssetId]>,
+		to: &Self::AccountId,
+	) -> DispatchResult;
+
+	/// Reinvest the pending rewards of a position paid in its staked asset into the position,
+	/// under its current lock. Returns the amount reinvested.
+	fn compound(
+		who: &Self::AccountId,
+		position: &Self::PositionId,
+	) -> Result<Self::Balance, DispatchError>;
+}
+
+/// Voting power derived from the shares of staking pools, for use by governance.
+pub trait StakingVotingPower {
+	type AccountId;
+	type RewardPoolId;
+	type Balance;
+
+	/// Voting power of `who` in the pool, the amount of the pool share asset it holds, directly and
+	/// in the accounts of the financial NFTs of the staking positions it owns.
+	fn voting_power(
+		pool_id: &Self::RewardPoolId,
+		who: &Self::AccountId,
+	) -> Result<Self::Balance, DispatchError>;
+
+	/// Total voting power in the pool, the total issuance of the pool share asset.
+	fn total_voting_power(pool_id: &Self::RewardPoolId) -> Result<Self::Balance, DispatchError>;
 }
 
 /// Interface for managing staking through financial NFTs.
@@ -188,6 +188,90 @@ pub trait StrategicVault: Vault {
 	) -> Result<(), DispatchError>;
 }
 
+/// A way for vaults to put their funds to work, such as lending markets. Each strategy is an
+/// account in the `strategies` of a vault, moving funds in and out of it with the vault's
+/// [`StrategicVault`] implementation.
+///
+/// Implemented by the pallets running strategies, and by tuples of them to let a vault rebalance
+/// the strategies of several pallets. Only lending markets implement it for now, Pablo liquidity
+/// and staking rewards pools following as described in
+/// `rfcs/0007-vault-strategies-pablo-staking.md`.
+pub trait VaultStrategy<AccountId, VaultId, Balance> {
+	/// Whether `strategy` is an account run by this implementation.
+	fn is_strategy(strategy: &AccountId) -> bool;
+
+	/// Moves funds between `vault` and `strategy` towards the allocation of the strategy, as given
+	/// by [`StrategicVault::available_funds`].
+	fn rebalance(vault: &VaultId, strategy: &AccountId) -> DispatchResult;
+
+	/// The value, in the asset of `vault`, of the funds held by `strategy`. Only includes the
+	/// yield already realised by the strategy.
+	fn balance(vault: &VaultId, strategy: &AccountId) -> Result<Balance, DispatchError>;
+
+	/// The maximum weight of [`rebalance`](VaultStrategy::rebalance).
+	fn rebalance_weight() -> Weight;
+}
+
+impl<AccountId, VaultId, Balance> VaultStrategy<AccountId, VaultId, Balance> for () {
+	fn is_strategy(_strategy: &AccountId) -> bool {
+		false
+	}
+
+	fn rebalance(_vault: &VaultId, _strategy: &AccountId) -> DispatchResult {
+		Err(DispatchError::CannotLookup)
+	}
+
+	fn balance(_vault: &VaultId, _strategy: &AccountId) -> Result<Balance, DispatchError> {
+		Err(DispatchError::CannotLookup)
+	}
+
+	fn rebalance_weight() -> Weight {
+		0
+	}
+}
+
+macro_rules! impl_vault_strategy_for_tuple {
+	($($strategy:ident),+) => {
+		impl<AccountId, VaultId, Balance, $($strategy),+> VaultStrategy<AccountId, VaultId, Balance>
+			for ($($strategy,)+)
+		where
+			$($strategy: VaultStrategy<AccountId, VaultId, Balance>),+
+		{
+			fn is_strategy(strategy: &AccountId) -> bool {
+				$($strategy::is_strategy(strategy))||+
+			}
+
+			fn rebalance(vault: &VaultId, strategy: &AccountId) -> DispatchResult {
+				$(
+					if $strategy::is_strategy(strategy) {
+						return $strategy::rebalance(vault, strategy)
+					}
+				)+
+				Err(DispatchError::CannotLookup)
+			}
+
+			fn balance(vault: &VaultId, strategy: &AccountId) -> Result<Balance, DispatchError> {
+				$(
+					if $strategy::is_strategy(strategy) {
+						return $strategy::balance(vault, strategy)
+					}
+				)+
+				Err(DispatchError::CannotLookup)
+			}
+
+			fn rebalance_weight() -> Weight {
+				let weight: Weight = 0;
+				weight $(.max($strategy::rebalance_weight()))+
+			}
+		}
+	};
+}
+
+impl_vault_strategy_for_tuple!(A);
+impl_vault_strategy_for_tuple!(A, B);
+impl_vault_strategy_for_tuple!(A, B, C);
+impl_vault_strategy_for_tuple!(A, B, C, D);
+
 /// A vault which allow the strategy to do periodic report.
 pub trait ReportableStrategicVault: StrategicVault {
 	type Report;
@@ -193,6 +193,9 @@ impl pallet_staking_rewards::Config for Test {
 parameter_types! {
 	pub const MaxStakingRewardPools: u32 = 10;
 	pub const MillisecsPerBlock: u32 = 12000;
+	pub const MaxAssetsPerPool: u32 = 8;
+	pub const MaxOrdersPerBlock: u32 = 16;
+	pub const MaxSwapSteps: u32 = 64;
 }
 
 impl pallet_pablo::Config for Test {
@@ -221,6 +224,12 @@ impl pallet_pablo::Config for Test {
 	type ManageStaking = StakingRewards;
 	type ProtocolStaking = StakingRewards;
 	type MsPerBlock = MillisecsPerBlock;
+	type FinancialNftInstanceId = u128;
+	type FinancialNft = MockFnft;
+	type MaxAssetsPerPool = MaxAssetsPerPool;
+	type OrderId = u128;
+	type MaxOrdersPerBlock = MaxOrdersPerBlock;
+	type MaxSwapSteps = MaxSwapSteps;
 }
 
 parameter_types! {
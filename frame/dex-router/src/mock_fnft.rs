@@ -2,15 +2,15 @@
 use composable_traits::fnft::FinancialNft;
 use frame_support::{
 	dispatch::DispatchResult,
-	traits::tokens::nonfungibles::{Create, Inspect, Mutate},
+	traits::tokens::nonfungibles::{Create, Inspect, InspectEnumerable, Mutate},
 };
 use sp_runtime::DispatchError;
 
 pub struct MockFnft;
 
 impl Inspect<u128> for MockFnft {
-	type ItemId = ();
-	type CollectionId = ();
+	type ItemId = u128;
+	type CollectionId = u128;
 
 	fn owner(collection: &Self::CollectionId, item: &Self::ItemId) -> Option<u128> {
 		todo!()
@@ -25,6 +25,31 @@ impl FinancialNft<u128> for MockFnft {
 	fn get_next_nft_id(collection: &Self::CollectionId) -> Result<Self::ItemId, DispatchError> {
 		todo!()
 	}
+
+	fn is_listed(_collection: &Self::CollectionId, _instance: &Self::ItemId) -> bool {
+		false
+	}
+}
+
+impl InspectEnumerable<u128> for MockFnft {
+	fn collections() -> Box<dyn Iterator<Item = Self::CollectionId>> {
+		Box::new(core::iter::empty())
+	}
+
+	fn items(_collection: &Self::CollectionId) -> Box<dyn Iterator<Item = Self::ItemId>> {
+		Box::new(core::iter::empty())
+	}
+
+	fn owned(_who: &u128) -> Box<dyn Iterator<Item = (Self::CollectionId, Self::ItemId)>> {
+		Box::new(core::iter::empty())
+	}
+
+	fn owned_in_collection(
+		_collection: &Self::CollectionId,
+		_who: &u128,
+	) -> Box<dyn Iterator<Item = Self::ItemId>> {
+		Box::new(core::iter::empty())
+	}
 }
 
 impl Create<u128> for MockFnft {
@@ -33,7 +58,8 @@ impl Create<u128> for MockFnft {
 		who: &u128,
 		admin: &u128,
 	) -> DispatchResult {
-		todo!()
+		// staking pools of the Pablo pools create their financial NFT collections
+		Ok(())
 	}
 }
 
@@ -0,0 +1,76 @@
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use core::{fmt::Display, str::FromStr};
+use fnft_runtime_api::FnftRuntimeApi;
+use jsonrpsee::{
+	core::{Error as RpcError, RpcResult},
+	proc_macros::rpc,
+	types::{error::CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeSerializeDeserialize},
+};
+use sp_std::{marker::PhantomData, sync::Arc};
+
+#[rpc(client, server)]
+pub trait FnftApi<BlockHash, CollectionId, InstanceId, Balance>
+where
+	Balance: FromStr + Display,
+{
+	#[method(name = "fnft_valueOf")]
+	fn value_of(
+		&self,
+		collection: CollectionId,
+		instance: InstanceId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(CollectionId, SafeRpcWrapper<Balance>)>>;
+}
+
+/// A struct that implements the `FnftApi`.
+pub struct Fnft<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, M> Fnft<C, M> {
+	/// Create new `Fnft` instance with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, CollectionId, InstanceId, Balance>
+	FnftApiServer<<Block as BlockT>::Hash, CollectionId, InstanceId, Balance>
+	for Fnft<C, (Block, CollectionId, InstanceId, Balance)>
+where
+	Block: BlockT,
+	CollectionId: Send + Sync + 'static + Codec + MaybeSerializeDeserialize,
+	InstanceId: Send + Sync + 'static + Codec + MaybeSerializeDeserialize,
+	Balance: Send + Sync + 'static + Codec + FromStr + Display,
+	C: Send + Sync + ProvideRuntimeApi<Block> + HeaderBackend<Block> + 'static,
+	C::Api: FnftRuntimeApi<Block, CollectionId, InstanceId, Balance>,
+{
+	fn value_of(
+		&self,
+		collection: CollectionId,
+		instance: InstanceId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(CollectionId, SafeRpcWrapper<Balance>)>> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.value_of(&at, collection, instance);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+}
@@ -0,0 +1,22 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use sp_std::vec::Vec;
+
+// Financial NFT Runtime API declaration. Implemented for each runtime at
+// `runtime/<runtime-name>/src/lib.rs`.
+sp_api::decl_runtime_apis! {
+	pub trait FnftRuntimeApi<CollectionId, InstanceId, Balance>
+	where
+		CollectionId: Codec,
+		InstanceId: Codec,
+		Balance: Codec,
+	{
+		/// Retrieve the value of the position wrapped into the given instance, per asset.
+		fn value_of(
+			collection: CollectionId,
+			instance: InstanceId,
+		) -> Vec<(CollectionId, SafeRpcWrapper<Balance>)>;
+	}
+}
@@ -0,0 +1,156 @@
+//! Benchmarks
+use crate::*;
+
+use composable_traits::fnft::FinancialNft;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{fungibles::Mutate as FungiblesMutate, tokens::nonfungibles::Mutate};
+use frame_system::{EventRecord, RawOrigin};
+
+const COLLECTION_ID: u128 = 1;
+const ASSET_ID: u128 = 100;
+const PRICE: u128 = 1_000;
+
+fn mint_nft<T: Config>(owner: &T::AccountId) -> FinancialNftInstanceIdOf<T>
+where
+	T::FinancialNftCollectionId: From<u128>,
+{
+	let collection = COLLECTION_ID.into();
+	let instance = <Pallet<T> as FinancialNft<T::AccountId>>::get_next_nft_id(&collection)
+		.expect("next NFT id should be available; qed;");
+	<Pallet<T> as Mutate<T::AccountId>>::mint_into(&collection, &instance, owner)
+		.expect("minting a new NFT should succeed; qed;");
+	instance
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+	let events = frame_system::Pallet::<T>::events();
+	let system_event: <T as frame_system::Config>::Event = generic_event.into();
+	// compare to the last event record
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+benchmarks! {
+	where_clause {
+		where
+			T::FinancialNftCollectionId: From<u128>,
+			T::AssetId: From<u128>,
+			T::Balance: From<u128>,
+			T::BlockNumber: From<u32>,
+			T::Assets: FungiblesMutate<T::AccountId, AssetId = T::AssetId, Balance = T::Balance>,
+	}
+
+	transfer {
+		let owner: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, 0);
+		let to: T::AccountId = account("to", 0, 0);
+		let instance = mint_nft::<T>(&owner);
+		Approvals::<T>::insert((T::FinancialNftCollectionId::from(COLLECTION_ID), instance), (operator.clone(), None::<T::BlockNumber>));
+	}: _(RawOrigin::Signed(operator), COLLECTION_ID.into(), instance, to.clone())
+	verify {
+		assert_last_event::<T>(Event::FinancialNftTransferred { collection_id: COLLECTION_ID.into(), instance_id: instance, to }.into());
+	}
+
+	approve {
+		let owner: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, 0);
+		let instance = mint_nft::<T>(&owner);
+		let expires_at: T::BlockNumber = 100_u32.into();
+	}: _(RawOrigin::Signed(owner), COLLECTION_ID.into(), instance, operator.clone(), Some(expires_at))
+	verify {
+		assert_last_event::<T>(Event::OperatorApproved { collection_id: COLLECTION_ID.into(), instance_id: instance, operator, expires_at: Some(expires_at) }.into());
+	}
+
+	revoke_approval {
+		let owner: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, 0);
+		let instance = mint_nft::<T>(&owner);
+		Approvals::<T>::insert((T::FinancialNftCollectionId::from(COLLECTION_ID), instance), (operator, None::<T::BlockNumber>));
+	}: _(RawOrigin::Signed(owner), COLLECTION_ID.into(), instance)
+	verify {
+		assert_last_event::<T>(Event::ApprovalRevoked { collection_id: COLLECTION_ID.into(), instance_id: instance }.into());
+	}
+
+	list {
+		let owner: T::AccountId = whitelisted_caller();
+		let instance = mint_nft::<T>(&owner);
+		let sale = Sale::Auction { asset_id: ASSET_ID.into(), reserve: PRICE.into(), ends_at: 100_u32.into() };
+	}: _(RawOrigin::Signed(owner), COLLECTION_ID.into(), instance, sale)
+	verify {
+		assert!(Listings::<T>::get((T::FinancialNftCollectionId::from(COLLECTION_ID), instance)).is_some());
+	}
+
+	cancel_listing {
+		let owner: T::AccountId = whitelisted_caller();
+		let instance = mint_nft::<T>(&owner);
+		let sale = Sale::FixedPrice { asset_id: ASSET_ID.into(), price: PRICE.into() };
+		Listings::<T>::insert((T::FinancialNftCollectionId::from(COLLECTION_ID), instance), sale);
+	}: _(RawOrigin::Signed(owner), COLLECTION_ID.into(), instance)
+	verify {
+		assert_last_event::<T>(Event::ListingCancelled { collection_id: COLLECTION_ID.into(), instance_id: instance }.into());
+	}
+
+	buy {
+		let seller: T::AccountId = account("seller", 0, 0);
+		let buyer: T::AccountId = whitelisted_caller();
+		let instance = mint_nft::<T>(&seller);
+		let sale = Sale::FixedPrice { asset_id: ASSET_ID.into(), price: PRICE.into() };
+		Listings::<T>::insert((T::FinancialNftCollectionId::from(COLLECTION_ID), instance), sale);
+		T::Assets::mint_into(ASSET_ID.into(), &buyer, PRICE.into())?;
+	}: _(RawOrigin::Signed(buyer.clone()), COLLECTION_ID.into(), instance, ASSET_ID.into(), PRICE.into())
+	verify {
+		assert_last_event::<T>(Event::Sold {
+			collection_id: COLLECTION_ID.into(),
+			instance_id: instance,
+			seller,
+			buyer,
+			asset_id: ASSET_ID.into(),
+			price: PRICE.into(),
+		}.into());
+	}
+
+	bid {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let previous_bidder: T::AccountId = account("previous_bidder", 0, 0);
+		let bidder: T::AccountId = whitelisted_caller();
+		let instance = mint_nft::<T>(&owner);
+		let sale = Sale::Auction { asset_id: ASSET_ID.into(), reserve: PRICE.into(), ends_at: 100_u32.into() };
+		Listings::<T>::insert((T::FinancialNftCollectionId::from(COLLECTION_ID), instance), sale);
+		// worst case: the previous bid is refunded
+		T::Assets::mint_into(ASSET_ID.into(), &Pallet::<T>::account_id(), PRICE.into())?;
+		Bids::<T>::insert((T::FinancialNftCollectionId::from(COLLECTION_ID), instance), (previous_bidder, T::Balance::from(PRICE)));
+		T::Assets::mint_into(ASSET_ID.into(), &bidder, (PRICE * 2).into())?;
+	}: _(RawOrigin::Signed(bidder.clone()), COLLECTION_ID.into(), instance, ASSET_ID.into(), (PRICE * 2).into())
+	verify {
+		assert_last_event::<T>(Event::BidPlaced {
+			collection_id: COLLECTION_ID.into(),
+			instance_id: instance,
+			bidder,
+			amount: (PRICE * 2).into(),
+		}.into());
+	}
+
+	settle_auction {
+		let seller: T::AccountId = account("seller", 0, 0);
+		let buyer: T::AccountId = account("buyer", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		let instance = mint_nft::<T>(&seller);
+		let sale = Sale::Auction { asset_id: ASSET_ID.into(), reserve: PRICE.into(), ends_at: 100_u32.into() };
+		Listings::<T>::insert((T::FinancialNftCollectionId::from(COLLECTION_ID), instance), sale);
+		T::Assets::mint_into(ASSET_ID.into(), &Pallet::<T>::account_id(), PRICE.into())?;
+		Bids::<T>::insert((T::FinancialNftCollectionId::from(COLLECTION_ID), instance), (buyer.clone(), T::Balance::from(PRICE)));
+		frame_system::Pallet::<T>::set_block_number(100_u32.into());
+	}: _(RawOrigin::Signed(caller), COLLECTION_ID.into(), instance)
+	verify {
+		assert_last_event::<T>(Event::Sold {
+			collection_id: COLLECTION_ID.into(),
+			instance_id: instance,
+			seller,
+			buyer,
+			asset_id: ASSET_ID.into(),
+			price: PRICE.into(),
+		}.into());
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::test::mock::new_test_ext(), crate::test::mock::MockRuntime);
+}
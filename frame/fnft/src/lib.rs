@@ -1,5 +1,7 @@
 //! Overview
-//! Allows to add new assets internally. User facing mutating API is provided by other pallets.
+//! Allows to add new assets internally. Minting and burning is provided by other pallets, owners
+//! can transfer their instances, approve operators to transfer them and sell them on a
+//! marketplace with fixed price or auction listings.
 #![cfg_attr(
 	not(test),
 	deny(
@@ -34,32 +36,39 @@
 	unused_extern_crates
 )]
 
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarking;
 #[cfg(test)]
 mod test;
+pub mod weights;
 
 pub use pallet::*;
 
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::weights::WeightInfo;
 	use codec::FullCodec;
 	use composable_support::math::safe::SafeAdd;
 	use composable_traits::{
 		account_proxy::AccountProxy,
-		currency::AssetIdLike,
-		fnft::{FinancialNft, FnftAccountProxyTypeSelector},
+		currency::{AssetIdLike, BalanceLike},
+		fnft::{FinancialNft, FinancialNftProtocol, FnftAccountProxyTypeSelector},
 	};
 	use core::fmt::Debug;
 	use frame_support::{
 		pallet_prelude::*,
 		traits::{
-			tokens::nonfungibles::{Create, Inspect, Mutate, Transfer},
+			fungibles,
+			tokens::nonfungibles::{Create, Inspect, InspectEnumerable, Mutate, Transfer},
 			IsType,
 		},
-		PalletId,
+		transactional, PalletId,
 	};
+	use frame_system::pallet_prelude::*;
 	use sp_arithmetic::traits::One;
 	use sp_runtime::traits::{AccountIdConversion, Zero};
 	use sp_std::{
+		boxed::Box,
 		collections::{btree_map::BTreeMap, btree_set::BTreeSet},
 		vec::Vec,
 	};
@@ -67,6 +76,21 @@ pub mod pallet {
 	pub(crate) type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 	pub(crate) type FinancialNftCollectionIdOf<T> = <T as Config>::FinancialNftCollectionId;
 	pub(crate) type FinancialNftInstanceIdOf<T> = <T as Config>::FinancialNftInstanceId;
+	pub(crate) type SaleOf<T> = Sale<
+		<T as Config>::AssetId,
+		<T as Config>::Balance,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// How a listed instance is sold.
+	#[derive(RuntimeDebug, PartialEq, Eq, Clone, Encode, Decode, TypeInfo)]
+	pub enum Sale<AssetId, Balance, BlockNumber> {
+		/// Sold to the first buyer paying `price` of `asset_id`.
+		FixedPrice { asset_id: AssetId, price: Balance },
+		/// Sold to the highest bidder in `asset_id` once the auction ends at block `ends_at`.
+		/// Bids lower than `reserve` are not accepted.
+		Auction { asset_id: AssetId, reserve: Balance, ends_at: BlockNumber },
+	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub (crate) fn deposit_event)]
@@ -84,6 +108,44 @@ pub mod pallet {
 			instance_id: FinancialNftInstanceIdOf<T>,
 			to: AccountIdOf<T>,
 		},
+		/// The owner approved `operator` to transfer the instance until block `expires_at`.
+		OperatorApproved {
+			collection_id: FinancialNftCollectionIdOf<T>,
+			instance_id: FinancialNftInstanceIdOf<T>,
+			operator: AccountIdOf<T>,
+			expires_at: Option<T::BlockNumber>,
+		},
+		ApprovalRevoked {
+			collection_id: FinancialNftCollectionIdOf<T>,
+			instance_id: FinancialNftInstanceIdOf<T>,
+		},
+		/// The instance was listed for sale. `value` is the value of the underlying position.
+		Listed {
+			collection_id: FinancialNftCollectionIdOf<T>,
+			instance_id: FinancialNftInstanceIdOf<T>,
+			sale: SaleOf<T>,
+			value: Vec<(FinancialNftCollectionIdOf<T>, T::Balance)>,
+		},
+		/// The listing was cancelled by its owner, the instance was transferred or burnt, or the
+		/// auction ended without bids.
+		ListingCancelled {
+			collection_id: FinancialNftCollectionIdOf<T>,
+			instance_id: FinancialNftInstanceIdOf<T>,
+		},
+		BidPlaced {
+			collection_id: FinancialNftCollectionIdOf<T>,
+			instance_id: FinancialNftInstanceIdOf<T>,
+			bidder: AccountIdOf<T>,
+			amount: T::Balance,
+		},
+		Sold {
+			collection_id: FinancialNftCollectionIdOf<T>,
+			instance_id: FinancialNftInstanceIdOf<T>,
+			seller: AccountIdOf<T>,
+			buyer: AccountIdOf<T>,
+			asset_id: T::AssetId,
+			price: T::Balance,
+		},
 	}
 
 	#[pallet::error]
@@ -93,6 +155,29 @@ pub mod pallet {
 		CollectionNotFound,
 		InstanceNotFound,
 		MustBeOwner,
+		/// Only the owner or an operator it approved can transfer the instance.
+		MustBeOwnerOrApprovedOperator,
+		/// The approval of an operator must expire after the current block.
+		ExpiryMustBeInTheFuture,
+		/// Listed instances cannot be transferred or approved.
+		InstanceIsListed,
+		InstanceNotListed,
+		NotFixedPriceListing,
+		NotAuctionListing,
+		/// An auction must end after the current block.
+		AuctionEndMustBeInTheFuture,
+		AuctionEnded,
+		AuctionNotEnded,
+		/// The listing of an auction with bids cannot be cancelled.
+		AuctionHasBids,
+		/// A bid must be at least the reserve of the auction and higher than the current bid.
+		BidTooLow,
+		/// The owner cannot buy or bid on its own instance.
+		OwnerCannotBuy,
+		/// The instance is listed for another asset than the one the buyer or bidder pays with.
+		ListingAssetMismatch,
+		/// The price of the listing is higher than the maximum the buyer is willing to pay.
+		PriceAboveMaximum,
 	}
 
 	#[pallet::config]
@@ -133,8 +218,30 @@ pub mod pallet {
 
 		type ProxyTypeSelector: FnftAccountProxyTypeSelector<Self::ProxyType>;
 
+		/// The asset ID type listings are settled in.
+		type AssetId: Parameter + Member + AssetIdLike + MaybeSerializeDeserialize + Ord;
+
+		/// The balance type of listings.
+		type Balance: Parameter + Member + BalanceLike;
+
+		/// Used to settle listings, holding the bids of auctions in the pallet account.
+		type Assets: fungibles::Transfer<
+			AccountIdOf<Self>,
+			AssetId = Self::AssetId,
+			Balance = Self::Balance,
+		>;
+
+		/// The protocol of the positions wrapped into the financial NFTs, used to value them.
+		type FinancialNftProtocol: FinancialNftProtocol<
+			ItemId = Self::FinancialNftInstanceId,
+			AssetId = Self::FinancialNftCollectionId,
+			Balance = Self::Balance,
+		>;
+
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
@@ -201,6 +308,361 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The operator approved to transfer an instance on behalf of its owner, and the block the
+	/// approval expires at.
+	#[pallet::storage]
+	#[pallet::getter(fn approval)]
+	pub type Approvals<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(FinancialNftCollectionIdOf<T>, FinancialNftInstanceIdOf<T>),
+		(AccountIdOf<T>, Option<T::BlockNumber>),
+		OptionQuery,
+	>;
+
+	/// Instances listed for sale by their owner.
+	#[pallet::storage]
+	#[pallet::getter(fn listing)]
+	pub type Listings<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(FinancialNftCollectionIdOf<T>, FinancialNftInstanceIdOf<T>),
+		SaleOf<T>,
+		OptionQuery,
+	>;
+
+	/// The highest bid of listed auctions, held in the pallet account.
+	#[pallet::storage]
+	#[pallet::getter(fn bid)]
+	pub type Bids<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(FinancialNftCollectionIdOf<T>, FinancialNftInstanceIdOf<T>),
+		(AccountIdOf<T>, T::Balance),
+		OptionQuery,
+	>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Transfer an instance owned by, or approved for, the caller to `to`.
+		///
+		/// Emits `FinancialNftTransferred` event when successful.
+		#[pallet::weight(T::WeightInfo::transfer())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			collection: FinancialNftCollectionIdOf<T>,
+			instance: FinancialNftInstanceIdOf<T>,
+			to: AccountIdOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let owner = Self::owner(&collection, &instance).ok_or(Error::<T>::InstanceNotFound)?;
+			ensure!(
+				who == owner || Self::is_approved_operator(&collection, &instance, &who),
+				Error::<T>::MustBeOwnerOrApprovedOperator
+			);
+			ensure!(
+				Listings::<T>::get((collection, instance)).is_none(),
+				Error::<T>::InstanceIsListed
+			);
+			<Self as Transfer<AccountIdOf<T>>>::transfer(&collection, &instance, &to)
+		}
+
+		/// Approve `operator` to transfer an instance of the caller, until block `expires_at` if
+		/// provided. Replaces any previous approval of the instance.
+		///
+		/// Emits `OperatorApproved` event when successful.
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			collection: FinancialNftCollectionIdOf<T>,
+			instance: FinancialNftInstanceIdOf<T>,
+			operator: AccountIdOf<T>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&collection, &instance, &who)?;
+			ensure!(
+				Listings::<T>::get((collection, instance)).is_none(),
+				Error::<T>::InstanceIsListed
+			);
+			if let Some(expires_at) = expires_at {
+				ensure!(
+					expires_at > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::ExpiryMustBeInTheFuture
+				);
+			}
+			Approvals::<T>::insert((collection, instance), (operator.clone(), expires_at));
+			Self::deposit_event(Event::OperatorApproved {
+				collection_id: collection,
+				instance_id: instance,
+				operator,
+				expires_at,
+			});
+			Ok(())
+		}
+
+		/// Revoke the approval of the operator of an instance of the caller.
+		///
+		/// Emits `ApprovalRevoked` event when successful.
+		#[pallet::weight(T::WeightInfo::revoke_approval())]
+		pub fn revoke_approval(
+			origin: OriginFor<T>,
+			collection: FinancialNftCollectionIdOf<T>,
+			instance: FinancialNftInstanceIdOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&collection, &instance, &who)?;
+			Approvals::<T>::remove((collection, instance));
+			Self::deposit_event(Event::ApprovalRevoked {
+				collection_id: collection,
+				instance_id: instance,
+			});
+			Ok(())
+		}
+
+		/// List an instance of the caller for sale. The instance cannot be transferred until it
+		/// is sold or the listing is cancelled.
+		///
+		/// Emits `Listed` event when successful.
+		#[pallet::weight(T::WeightInfo::list())]
+		pub fn list(
+			origin: OriginFor<T>,
+			collection: FinancialNftCollectionIdOf<T>,
+			instance: FinancialNftInstanceIdOf<T>,
+			sale: SaleOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&collection, &instance, &who)?;
+			ensure!(
+				Listings::<T>::get((collection, instance)).is_none(),
+				Error::<T>::InstanceIsListed
+			);
+			if let Sale::Auction { ends_at, .. } = sale {
+				ensure!(
+					ends_at > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::AuctionEndMustBeInTheFuture
+				);
+			}
+			Listings::<T>::insert((collection, instance), sale.clone());
+			Self::deposit_event(Event::Listed {
+				collection_id: collection,
+				instance_id: instance,
+				sale,
+				value: Self::value_of(&collection, &instance),
+			});
+			Ok(())
+		}
+
+		/// Cancel the listing of an instance of the caller. Auctions can only be cancelled while
+		/// they have no bids.
+		///
+		/// Emits `ListingCancelled` event when successful.
+		#[pallet::weight(T::WeightInfo::cancel_listing())]
+		pub fn cancel_listing(
+			origin: OriginFor<T>,
+			collection: FinancialNftCollectionIdOf<T>,
+			instance: FinancialNftInstanceIdOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&collection, &instance, &who)?;
+			ensure!(
+				Listings::<T>::get((collection, instance)).is_some(),
+				Error::<T>::InstanceNotListed
+			);
+			ensure!(Bids::<T>::get((collection, instance)).is_none(), Error::<T>::AuctionHasBids);
+			Self::remove_listing(&collection, &instance)
+		}
+
+		/// Buy an instance listed at a fixed price, paying the price to its owner. Fails if the
+		/// listing is not in `asset_id` or its price is above `max_price`, in case the owner
+		/// relisted the instance in the meantime.
+		///
+		/// Emits `Sold` event when successful.
+		#[pallet::weight(T::WeightInfo::buy())]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			collection: FinancialNftCollectionIdOf<T>,
+			instance: FinancialNftInstanceIdOf<T>,
+			asset_id: T::AssetId,
+			max_price: T::Balance,
+		) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+			let seller = Self::owner(&collection, &instance).ok_or(Error::<T>::InstanceNotFound)?;
+			ensure!(buyer != seller, Error::<T>::OwnerCannotBuy);
+			let (listed_asset_id, price) = match Listings::<T>::get((collection, instance)) {
+				Some(Sale::FixedPrice { asset_id, price }) => Ok((asset_id, price)),
+				Some(Sale::Auction { .. }) => Err(Error::<T>::NotFixedPriceListing),
+				None => Err(Error::<T>::InstanceNotListed),
+			}?;
+			ensure!(listed_asset_id == asset_id, Error::<T>::ListingAssetMismatch);
+			ensure!(price <= max_price, Error::<T>::PriceAboveMaximum);
+			T::Assets::transfer(asset_id, &buyer, &seller, price, true)?;
+			Listings::<T>::remove((collection, instance));
+			<Self as Transfer<AccountIdOf<T>>>::transfer(&collection, &instance, &buyer)?;
+			Self::deposit_event(Event::Sold {
+				collection_id: collection,
+				instance_id: instance,
+				seller,
+				buyer,
+				asset_id,
+				price,
+			});
+			Ok(())
+		}
+
+		/// Bid `amount` of `asset_id` on an auction. The bid is held until it is outbid, refunding
+		/// it, or the auction is settled. Fails if the auction is not in `asset_id`.
+		///
+		/// Emits `BidPlaced` event when successful.
+		#[pallet::weight(T::WeightInfo::bid())]
+		#[transactional]
+		pub fn bid(
+			origin: OriginFor<T>,
+			collection: FinancialNftCollectionIdOf<T>,
+			instance: FinancialNftInstanceIdOf<T>,
+			asset_id: T::AssetId,
+			amount: T::Balance,
+		) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
+			let owner = Self::owner(&collection, &instance).ok_or(Error::<T>::InstanceNotFound)?;
+			ensure!(bidder != owner, Error::<T>::OwnerCannotBuy);
+			let (listed_asset_id, reserve, ends_at) =
+				match Listings::<T>::get((collection, instance)) {
+					Some(Sale::Auction { asset_id, reserve, ends_at }) =>
+						Ok((asset_id, reserve, ends_at)),
+					Some(Sale::FixedPrice { .. }) => Err(Error::<T>::NotAuctionListing),
+					None => Err(Error::<T>::InstanceNotListed),
+				}?;
+			ensure!(listed_asset_id == asset_id, Error::<T>::ListingAssetMismatch);
+			ensure!(frame_system::Pallet::<T>::block_number() < ends_at, Error::<T>::AuctionEnded);
+			ensure!(amount >= reserve, Error::<T>::BidTooLow);
+
+			let previous_bid = Bids::<T>::get((collection, instance));
+			if let Some((_, previous_amount)) = previous_bid {
+				ensure!(amount > previous_amount, Error::<T>::BidTooLow);
+			}
+
+			T::Assets::transfer(asset_id, &bidder, &Self::account_id(), amount, true)?;
+			if let Some((previous_bidder, previous_amount)) = previous_bid {
+				T::Assets::transfer(
+					asset_id,
+					&Self::account_id(),
+					&previous_bidder,
+					previous_amount,
+					false,
+				)?;
+			}
+			Bids::<T>::insert((collection, instance), (bidder.clone(), amount));
+			Self::deposit_event(Event::BidPlaced {
+				collection_id: collection,
+				instance_id: instance,
+				bidder,
+				amount,
+			});
+			Ok(())
+		}
+
+		/// Settle an ended auction, transferring the instance to the highest bidder and the bid
+		/// to the owner. Without bids, the listing is cancelled. Can be called by anybody.
+		///
+		/// Emits `Sold` or `ListingCancelled` event when successful.
+		#[pallet::weight(T::WeightInfo::settle_auction())]
+		#[transactional]
+		pub fn settle_auction(
+			origin: OriginFor<T>,
+			collection: FinancialNftCollectionIdOf<T>,
+			instance: FinancialNftInstanceIdOf<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let seller = Self::owner(&collection, &instance).ok_or(Error::<T>::InstanceNotFound)?;
+			let (asset_id, ends_at) = match Listings::<T>::get((collection, instance)) {
+				Some(Sale::Auction { asset_id, ends_at, .. }) => Ok((asset_id, ends_at)),
+				Some(Sale::FixedPrice { .. }) => Err(Error::<T>::NotAuctionListing),
+				None => Err(Error::<T>::InstanceNotListed),
+			}?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= ends_at,
+				Error::<T>::AuctionNotEnded
+			);
+			match Bids::<T>::take((collection, instance)) {
+				Some((buyer, price)) => {
+					T::Assets::transfer(asset_id, &Self::account_id(), &seller, price, false)?;
+					Listings::<T>::remove((collection, instance));
+					<Self as Transfer<AccountIdOf<T>>>::transfer(&collection, &instance, &buyer)?;
+					Self::deposit_event(Event::Sold {
+						collection_id: collection,
+						instance_id: instance,
+						seller,
+						buyer,
+						asset_id,
+						price,
+					});
+					Ok(())
+				},
+				None => Self::remove_listing(&collection, &instance),
+			}
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding the bids of auctions.
+		pub(crate) fn account_id() -> AccountIdOf<T> {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// The value of the position wrapped into an instance, as reported by its protocol.
+		pub fn value_of(
+			collection: &FinancialNftCollectionIdOf<T>,
+			instance: &FinancialNftInstanceIdOf<T>,
+		) -> Vec<(FinancialNftCollectionIdOf<T>, T::Balance)> {
+			T::FinancialNftProtocol::value_of(collection, instance)
+		}
+
+		fn ensure_owner(
+			collection: &FinancialNftCollectionIdOf<T>,
+			instance: &FinancialNftInstanceIdOf<T>,
+			who: &AccountIdOf<T>,
+		) -> DispatchResult {
+			let owner = Self::owner(collection, instance).ok_or(Error::<T>::InstanceNotFound)?;
+			ensure!(who == &owner, Error::<T>::MustBeOwner);
+			Ok(())
+		}
+
+		fn is_approved_operator(
+			collection: &FinancialNftCollectionIdOf<T>,
+			instance: &FinancialNftInstanceIdOf<T>,
+			who: &AccountIdOf<T>,
+		) -> bool {
+			matches!(
+				Approvals::<T>::get((collection, instance)),
+				Some((operator, expires_at)) if &operator == who &&
+					expires_at.map_or(true, |expires_at| {
+						frame_system::Pallet::<T>::block_number() < expires_at
+					})
+			)
+		}
+
+		/// Removes the listing of an instance if any, refunding the bid of auctions.
+		fn remove_listing(
+			collection: &FinancialNftCollectionIdOf<T>,
+			instance: &FinancialNftInstanceIdOf<T>,
+		) -> DispatchResult {
+			if let Some(sale) = Listings::<T>::take((collection, instance)) {
+				if let (Sale::Auction { asset_id, .. }, Some((bidder, amount))) =
+					(sale, Bids::<T>::take((collection, instance)))
+				{
+					T::Assets::transfer(asset_id, &Self::account_id(), &bidder, amount, false)?;
+				}
+				Self::deposit_event(Event::ListingCancelled {
+					collection_id: *collection,
+					instance_id: *instance,
+				});
+			}
+			Ok(())
+		}
+	}
+
 	impl<T: Config> Inspect<AccountIdOf<T>> for Pallet<T> {
 		type ItemId = FinancialNftInstanceIdOf<T>;
 		type CollectionId = FinancialNftCollectionIdOf<T>;
@@ -227,6 +689,38 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> InspectEnumerable<AccountIdOf<T>> for Pallet<T> {
+		fn collections() -> Box<dyn Iterator<Item = Self::CollectionId>> {
+			Box::new(Collection::<T>::iter_keys())
+		}
+
+		fn items(collection: &Self::CollectionId) -> Box<dyn Iterator<Item = Self::ItemId>> {
+			Box::new(CollectionInstances::<T>::get(collection).unwrap_or_default().into_iter())
+		}
+
+		fn owned(
+			who: &AccountIdOf<T>,
+		) -> Box<dyn Iterator<Item = (Self::CollectionId, Self::ItemId)>> {
+			Box::new(OwnerInstances::<T>::get(who).unwrap_or_default().into_iter())
+		}
+
+		fn owned_in_collection(
+			collection: &Self::CollectionId,
+			who: &AccountIdOf<T>,
+		) -> Box<dyn Iterator<Item = Self::ItemId>> {
+			let collection = *collection;
+			Box::new(OwnerInstances::<T>::get(who).unwrap_or_default().into_iter().filter_map(
+				move |(owned_collection, instance)| {
+					if owned_collection == collection {
+						Some(instance)
+					} else {
+						None
+					}
+				},
+			))
+		}
+	}
+
 	impl<T: Config> Create<AccountIdOf<T>> for Pallet<T> {
 		fn create_collection(
 			collection: &Self::CollectionId,
@@ -248,6 +742,9 @@ pub mod pallet {
 			instance: &Self::ItemId,
 			destination: &AccountIdOf<T>,
 		) -> DispatchResult {
+			// approvals and listings are made by the previous owner
+			Approvals::<T>::remove((collection, instance));
+			Self::remove_listing(collection, instance)?;
 			Instance::<T>::try_mutate((collection, instance), |entry| match entry {
 				Some((owner, _)) => {
 					OwnerInstances::<T>::mutate(owner.clone(), |x| match x {
@@ -324,6 +821,8 @@ pub mod pallet {
 			instance: &Self::ItemId,
 			_maybe_check_owner: Option<&AccountIdOf<T>>,
 		) -> DispatchResult {
+			Approvals::<T>::remove((collection, instance));
+			Self::remove_listing(collection, instance)?;
 			Instance::<T>::try_mutate_exists((collection, instance), |entry| -> DispatchResult {
 				match entry {
 					Some((owner, _)) => {
@@ -433,6 +932,10 @@ pub mod pallet {
 				},
 			)
 		}
+
+		fn is_listed(collection: &Self::CollectionId, instance: &Self::ItemId) -> bool {
+			Listings::<T>::contains_key((collection, instance))
+		}
 	}
 
 	/// Returns a closure that inserts the given value into the contained set, initializing the set
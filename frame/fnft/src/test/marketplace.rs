@@ -0,0 +1,491 @@
+use composable_tests_helpers::test::{
+	block::process_and_progress_blocks, helper::assert_last_event,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungibles::{Inspect as FungiblesInspect, Mutate as FungiblesMutate},
+		tokens::nonfungibles::Inspect,
+	},
+};
+
+use crate::{
+	test::{
+		mock::{new_test_ext, AssetId, Balance, Event, MockRuntime, Origin, Tokens},
+		prelude::*,
+		ALICE, BOB, CHARLIE,
+	},
+	Approvals, Bids, Error, Listings, Pallet, Sale,
+};
+
+const ASSET_ID: AssetId = 100;
+const PRICE: Balance = 1_000;
+
+fn fixed_price() -> Sale<AssetId, Balance, u64> {
+	Sale::FixedPrice { asset_id: ASSET_ID, price: PRICE }
+}
+
+fn auction(ends_at: u64) -> Sale<AssetId, Balance, u64> {
+	Sale::Auction { asset_id: ASSET_ID, reserve: PRICE, ends_at }
+}
+
+fn balance(who: u128) -> Balance {
+	<Tokens as FungiblesInspect<_>>::balance(ASSET_ID, &who)
+}
+
+/// Tests the `transfer` extrinsic by the owner and by approved operators.
+mod transfer {
+	use super::*;
+
+	#[test]
+	fn by_owner() {
+		new_test_ext().execute_with(|| {
+			let instance = mint_into_and_assert();
+
+			assert_ok!(Pallet::<MockRuntime>::transfer(
+				Origin::signed(ALICE),
+				TEST_COLLECTION_ID,
+				instance,
+				BOB
+			));
+
+			assert_eq!(Pallet::<MockRuntime>::owner(&TEST_COLLECTION_ID, &instance), Some(BOB));
+			assert_last_event::<MockRuntime>(Event::Nft(crate::Event::FinancialNftTransferred {
+				collection_id: TEST_COLLECTION_ID,
+				instance_id: instance,
+				to: BOB,
+			}));
+		})
+	}
+
+	#[test]
+	fn by_approved_operator_clears_approval() {
+		new_test_ext().execute_with(|| {
+			let instance = mint_into_and_assert();
+
+			assert_noop!(
+				Pallet::<MockRuntime>::transfer(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance,
+					CHARLIE
+				),
+				Error::<MockRuntime>::MustBeOwnerOrApprovedOperator
+			);
+
+			assert_ok!(Pallet::<MockRuntime>::approve(
+				Origin::signed(ALICE),
+				TEST_COLLECTION_ID,
+				instance,
+				BOB,
+				None
+			));
+			assert_ok!(Pallet::<MockRuntime>::transfer(
+				Origin::signed(BOB),
+				TEST_COLLECTION_ID,
+				instance,
+				CHARLIE
+			));
+
+			assert_eq!(Pallet::<MockRuntime>::owner(&TEST_COLLECTION_ID, &instance), Some(CHARLIE));
+			assert_eq!(
+				Approvals::<MockRuntime>::get((TEST_COLLECTION_ID, instance)),
+				None,
+				"approvals of the previous owner should be cleared on transfer"
+			);
+		})
+	}
+
+	#[test]
+	fn approval_expires() {
+		new_test_ext().execute_with(|| {
+			let instance = mint_into_and_assert();
+
+			assert_noop!(
+				Pallet::<MockRuntime>::approve(
+					Origin::signed(ALICE),
+					TEST_COLLECTION_ID,
+					instance,
+					BOB,
+					Some(1)
+				),
+				Error::<MockRuntime>::ExpiryMustBeInTheFuture
+			);
+
+			assert_ok!(Pallet::<MockRuntime>::approve(
+				Origin::signed(ALICE),
+				TEST_COLLECTION_ID,
+				instance,
+				BOB,
+				Some(5)
+			));
+
+			process_and_progress_blocks::<Pallet<MockRuntime>, MockRuntime>(4);
+
+			assert_noop!(
+				Pallet::<MockRuntime>::transfer(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance,
+					CHARLIE
+				),
+				Error::<MockRuntime>::MustBeOwnerOrApprovedOperator
+			);
+		})
+	}
+
+	#[test]
+	fn revoked_approval() {
+		new_test_ext().execute_with(|| {
+			let instance = mint_into_and_assert();
+
+			assert_ok!(Pallet::<MockRuntime>::approve(
+				Origin::signed(ALICE),
+				TEST_COLLECTION_ID,
+				instance,
+				BOB,
+				None
+			));
+			assert_noop!(
+				Pallet::<MockRuntime>::revoke_approval(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance
+				),
+				Error::<MockRuntime>::MustBeOwner
+			);
+			assert_ok!(Pallet::<MockRuntime>::revoke_approval(
+				Origin::signed(ALICE),
+				TEST_COLLECTION_ID,
+				instance
+			));
+
+			assert_noop!(
+				Pallet::<MockRuntime>::transfer(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance,
+					CHARLIE
+				),
+				Error::<MockRuntime>::MustBeOwnerOrApprovedOperator
+			);
+		})
+	}
+}
+
+/// Tests fixed price listings.
+mod fixed_price {
+	use super::*;
+
+	#[test]
+	fn list_and_buy() {
+		new_test_ext().execute_with(|| {
+			let instance = mint_into_and_assert();
+
+			assert_ok!(Pallet::<MockRuntime>::list(
+				Origin::signed(ALICE),
+				TEST_COLLECTION_ID,
+				instance,
+				fixed_price()
+			));
+			assert_last_event::<MockRuntime>(Event::Nft(crate::Event::Listed {
+				collection_id: TEST_COLLECTION_ID,
+				instance_id: instance,
+				sale: fixed_price(),
+				value: [(TEST_COLLECTION_ID, 1)].into(),
+			}));
+
+			assert_noop!(
+				Pallet::<MockRuntime>::transfer(
+					Origin::signed(ALICE),
+					TEST_COLLECTION_ID,
+					instance,
+					CHARLIE
+				),
+				Error::<MockRuntime>::InstanceIsListed
+			);
+			assert_noop!(
+				Pallet::<MockRuntime>::buy(
+					Origin::signed(ALICE),
+					TEST_COLLECTION_ID,
+					instance,
+					ASSET_ID,
+					PRICE
+				),
+				Error::<MockRuntime>::OwnerCannotBuy
+			);
+
+			assert_ok!(<Tokens as FungiblesMutate<_>>::mint_into(ASSET_ID, &BOB, PRICE));
+			assert_noop!(
+				Pallet::<MockRuntime>::buy(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance,
+					ASSET_ID + 1,
+					PRICE
+				),
+				Error::<MockRuntime>::ListingAssetMismatch
+			);
+			assert_noop!(
+				Pallet::<MockRuntime>::buy(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance,
+					ASSET_ID,
+					PRICE - 1
+				),
+				Error::<MockRuntime>::PriceAboveMaximum
+			);
+			assert_ok!(Pallet::<MockRuntime>::buy(
+				Origin::signed(BOB),
+				TEST_COLLECTION_ID,
+				instance,
+				ASSET_ID,
+				PRICE
+			));
+
+			assert_eq!(Pallet::<MockRuntime>::owner(&TEST_COLLECTION_ID, &instance), Some(BOB));
+			assert_eq!(balance(ALICE), PRICE);
+			assert_eq!(balance(BOB), 0);
+			assert_eq!(Listings::<MockRuntime>::get((TEST_COLLECTION_ID, instance)), None);
+			assert_last_event::<MockRuntime>(Event::Nft(crate::Event::Sold {
+				collection_id: TEST_COLLECTION_ID,
+				instance_id: instance,
+				seller: ALICE,
+				buyer: BOB,
+				asset_id: ASSET_ID,
+				price: PRICE,
+			}));
+		})
+	}
+
+	#[test]
+	fn buy_without_funds_fails() {
+		new_test_ext().execute_with(|| {
+			let instance = mint_into_and_assert();
+
+			assert_ok!(Pallet::<MockRuntime>::list(
+				Origin::signed(ALICE),
+				TEST_COLLECTION_ID,
+				instance,
+				fixed_price()
+			));
+			assert!(Pallet::<MockRuntime>::buy(
+				Origin::signed(BOB),
+				TEST_COLLECTION_ID,
+				instance,
+				ASSET_ID,
+				PRICE
+			)
+			.is_err());
+			assert_eq!(Pallet::<MockRuntime>::owner(&TEST_COLLECTION_ID, &instance), Some(ALICE));
+		})
+	}
+
+	#[test]
+	fn cancel_listing() {
+		new_test_ext().execute_with(|| {
+			let instance = mint_into_and_assert();
+
+			assert_noop!(
+				Pallet::<MockRuntime>::cancel_listing(
+					Origin::signed(ALICE),
+					TEST_COLLECTION_ID,
+					instance
+				),
+				Error::<MockRuntime>::InstanceNotListed
+			);
+			assert_ok!(Pallet::<MockRuntime>::list(
+				Origin::signed(ALICE),
+				TEST_COLLECTION_ID,
+				instance,
+				fixed_price()
+			));
+			assert_noop!(
+				Pallet::<MockRuntime>::cancel_listing(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance
+				),
+				Error::<MockRuntime>::MustBeOwner
+			);
+			assert_ok!(Pallet::<MockRuntime>::cancel_listing(
+				Origin::signed(ALICE),
+				TEST_COLLECTION_ID,
+				instance
+			));
+
+			assert_noop!(
+				Pallet::<MockRuntime>::buy(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance,
+					ASSET_ID,
+					PRICE
+				),
+				Error::<MockRuntime>::InstanceNotListed
+			);
+		})
+	}
+}
+
+/// Tests auction listings.
+mod auction {
+	use super::*;
+
+	#[test]
+	fn bid_outbid_and_settle() {
+		new_test_ext().execute_with(|| {
+			let instance = mint_into_and_assert();
+
+			assert_noop!(
+				Pallet::<MockRuntime>::list(
+					Origin::signed(ALICE),
+					TEST_COLLECTION_ID,
+					instance,
+					auction(1)
+				),
+				Error::<MockRuntime>::AuctionEndMustBeInTheFuture
+			);
+			assert_ok!(Pallet::<MockRuntime>::list(
+				Origin::signed(ALICE),
+				TEST_COLLECTION_ID,
+				instance,
+				auction(10)
+			));
+
+			assert_ok!(<Tokens as FungiblesMutate<_>>::mint_into(ASSET_ID, &BOB, PRICE));
+			assert_ok!(<Tokens as FungiblesMutate<_>>::mint_into(ASSET_ID, &CHARLIE, PRICE * 2));
+
+			assert_noop!(
+				Pallet::<MockRuntime>::bid(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance,
+					ASSET_ID + 1,
+					PRICE
+				),
+				Error::<MockRuntime>::ListingAssetMismatch
+			);
+			assert_noop!(
+				Pallet::<MockRuntime>::bid(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance,
+					ASSET_ID,
+					PRICE - 1
+				),
+				Error::<MockRuntime>::BidTooLow
+			);
+			assert_ok!(Pallet::<MockRuntime>::bid(
+				Origin::signed(BOB),
+				TEST_COLLECTION_ID,
+				instance,
+				ASSET_ID,
+				PRICE
+			));
+			assert_eq!(balance(BOB), 0);
+
+			assert_noop!(
+				Pallet::<MockRuntime>::bid(
+					Origin::signed(CHARLIE),
+					TEST_COLLECTION_ID,
+					instance,
+					ASSET_ID,
+					PRICE
+				),
+				Error::<MockRuntime>::BidTooLow
+			);
+			assert_noop!(
+				Pallet::<MockRuntime>::cancel_listing(
+					Origin::signed(ALICE),
+					TEST_COLLECTION_ID,
+					instance
+				),
+				Error::<MockRuntime>::AuctionHasBids
+			);
+			assert_ok!(Pallet::<MockRuntime>::bid(
+				Origin::signed(CHARLIE),
+				TEST_COLLECTION_ID,
+				instance,
+				ASSET_ID,
+				PRICE * 2
+			));
+			assert_eq!(balance(BOB), PRICE, "outbid bidder should be refunded");
+			assert_eq!(
+				Bids::<MockRuntime>::get((TEST_COLLECTION_ID, instance)),
+				Some((CHARLIE, PRICE * 2))
+			);
+
+			assert_noop!(
+				Pallet::<MockRuntime>::settle_auction(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance
+				),
+				Error::<MockRuntime>::AuctionNotEnded
+			);
+
+			process_and_progress_blocks::<Pallet<MockRuntime>, MockRuntime>(9);
+
+			assert_noop!(
+				Pallet::<MockRuntime>::bid(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance,
+					ASSET_ID,
+					PRICE * 3
+				),
+				Error::<MockRuntime>::AuctionEnded
+			);
+			assert_ok!(Pallet::<MockRuntime>::settle_auction(
+				Origin::signed(BOB),
+				TEST_COLLECTION_ID,
+				instance
+			));
+
+			assert_eq!(Pallet::<MockRuntime>::owner(&TEST_COLLECTION_ID, &instance), Some(CHARLIE));
+			assert_eq!(balance(ALICE), PRICE * 2);
+			assert_eq!(balance(Pallet::<MockRuntime>::account_id()), 0);
+			assert_eq!(Listings::<MockRuntime>::get((TEST_COLLECTION_ID, instance)), None);
+			assert_eq!(Bids::<MockRuntime>::get((TEST_COLLECTION_ID, instance)), None);
+		})
+	}
+
+	#[test]
+	fn settle_without_bids_cancels_listing() {
+		new_test_ext().execute_with(|| {
+			let instance = mint_into_and_assert();
+
+			assert_ok!(Pallet::<MockRuntime>::list(
+				Origin::signed(ALICE),
+				TEST_COLLECTION_ID,
+				instance,
+				auction(10)
+			));
+			assert_noop!(
+				Pallet::<MockRuntime>::buy(
+					Origin::signed(BOB),
+					TEST_COLLECTION_ID,
+					instance,
+					ASSET_ID,
+					PRICE
+				),
+				Error::<MockRuntime>::NotFixedPriceListing
+			);
+
+			process_and_progress_blocks::<Pallet<MockRuntime>, MockRuntime>(9);
+
+			assert_ok!(Pallet::<MockRuntime>::settle_auction(
+				Origin::signed(BOB),
+				TEST_COLLECTION_ID,
+				instance
+			));
+			assert_eq!(Pallet::<MockRuntime>::owner(&TEST_COLLECTION_ID, &instance), Some(ALICE));
+			assert_last_event::<MockRuntime>(Event::Nft(crate::Event::ListingCancelled {
+				collection_id: TEST_COLLECTION_ID,
+				instance_id: instance,
+			}));
+		})
+	}
+}
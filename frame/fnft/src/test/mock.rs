@@ -1,19 +1,27 @@
 #![cfg(test)]
 
+use crate::test::prelude::TEST_COLLECTION_ID;
 use composable_tests_helpers::test::block::{process_and_progress_blocks, MILLISECS_PER_BLOCK};
-use composable_traits::{account_proxy::ProxyType, fnft::FnftAccountProxyType};
+use composable_traits::{
+	account_proxy::ProxyType,
+	fnft::{FinancialNftProtocol, FnftAccountProxyType},
+};
 use frame_support::{
 	parameter_types,
 	traits::{ConstU32, ConstU64, Everything, InstanceFilter},
 	PalletId,
 };
 use frame_system as system;
+use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
 
+pub type Balance = u128;
+pub type AssetId = u128;
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<MockRuntime>;
 type Block = frame_system::mocking::MockBlock<MockRuntime>;
 
@@ -26,8 +34,9 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
-		Nft: crate::{Pallet, Storage , Event<T>},
+		Nft: crate::{Pallet, Call, Storage , Event<T>},
 		Proxy: pallet_account_proxy::{Pallet, Call, Storage , Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>},
 	}
 );
 
@@ -38,12 +47,59 @@ impl crate::Config for MockRuntime {
 	type Event = Event;
 
 	type MaxProperties = ConstU32<16>;
-	type FinancialNftCollectionId = u16;
+	type FinancialNftCollectionId = u128;
 	type FinancialNftInstanceId = u64;
 	type ProxyType = ProxyType;
 	type AccountProxy = Proxy;
 	type ProxyTypeSelector = FnftAccountProxyType;
+	type AssetId = AssetId;
+	type Balance = Balance;
+	type Assets = Tokens;
+	type FinancialNftProtocol = MockProtocol;
 	type PalletId = FnftPalletId;
+	type WeightInfo = ();
+}
+
+/// Values every instance at the instance id in the collection asset.
+pub struct MockProtocol;
+
+impl FinancialNftProtocol for MockProtocol {
+	type ItemId = u64;
+	type AssetId = u128;
+	type Balance = Balance;
+
+	fn collection_asset_ids() -> Vec<Self::AssetId> {
+		[TEST_COLLECTION_ID].into()
+	}
+
+	fn value_of(
+		collection: &Self::AssetId,
+		instance: &Self::ItemId,
+	) -> Vec<(Self::AssetId, Self::Balance)> {
+		[(*collection, Balance::from(*instance))].into()
+	}
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+		0
+	};
+}
+
+impl orml_tokens::Config for MockRuntime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = i128;
+	type CurrencyId = AssetId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ConstU32<2>;
+	type ReserveIdentifier = [u8; 8];
+	type MaxReserves = ConstU32<2>;
+	type DustRemovalWhitelist = Everything;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
 }
 
 impl pallet_timestamp::Config for MockRuntime {
@@ -4,6 +4,9 @@ pub(crate) mod mock;
 /// Various helpers used throughout this test suite.
 pub(crate) mod prelude;
 
+/// Tests the transfer, approval and marketplace extrinsics.
+mod marketplace;
+
 const ALICE: u128 = 0;
 const BOB: u128 = 1;
 const CHARLIE: u128 = 2;
@@ -31,12 +34,15 @@ mod nonfungibles {
 	/// Tests the pallet's [`Inspect`] implementation.
 	mod inspect;
 
+	/// Tests the pallet's [`InspectEnumerable`] implementation.
+	mod inspect_enumerable;
+
 	/// Tests the pallet's [`Create`] implementation.
 	#[test]
 	fn create() {
 		new_test_ext().execute_with(|| {
 			assert_eq!(
-				Pallet::<MockRuntime>::create_collection(&255_u16, &ALICE, &BOB),
+				Pallet::<MockRuntime>::create_collection(&255_u128, &ALICE, &BOB),
 				Ok(()),
 				"class creation should be successful"
 			);
@@ -48,7 +54,7 @@ mod nonfungibles {
 			);
 
 			assert_eq!(
-				Pallet::<MockRuntime>::create_collection(&255_u16, &ALICE, &BOB),
+				Pallet::<MockRuntime>::create_collection(&255_u128, &ALICE, &BOB),
 				Err(Error::<MockRuntime>::CollectionAlreadyExists.into()),
 				"should not be able to create class that already exists"
 			);
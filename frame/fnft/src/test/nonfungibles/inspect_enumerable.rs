@@ -0,0 +1,49 @@
+use std::collections::BTreeSet;
+
+use frame_support::traits::tokens::nonfungibles::{Create, InspectEnumerable, Mutate};
+
+use crate::{
+	test::{
+		mock::{new_test_ext, MockRuntime},
+		prelude::*,
+		ALICE, BOB,
+	},
+	Pallet,
+};
+
+#[test]
+/// Tests that the owned instances are enumerated per collection.
+pub(crate) fn owned_in_collection() {
+	new_test_ext().execute_with(|| {
+		let other_collection = TEST_COLLECTION_ID + 1;
+		assert_eq!(
+			Pallet::<MockRuntime>::create_collection(&TEST_COLLECTION_ID, &ALICE, &ALICE),
+			Ok(())
+		);
+		assert_eq!(
+			Pallet::<MockRuntime>::create_collection(&other_collection, &ALICE, &ALICE),
+			Ok(())
+		);
+		let [first, second] = mint_many_nfts_and_assert::<2>(ALICE);
+		Pallet::<MockRuntime>::mint_into(&other_collection, &first, &ALICE).unwrap();
+
+		assert_eq!(
+			Pallet::<MockRuntime>::collections().collect::<BTreeSet<_>>(),
+			BTreeSet::from([TEST_COLLECTION_ID, other_collection])
+		);
+		assert_eq!(
+			Pallet::<MockRuntime>::items(&TEST_COLLECTION_ID).collect::<BTreeSet<_>>(),
+			BTreeSet::from([first, second])
+		);
+		assert_eq!(Pallet::<MockRuntime>::owned(&ALICE).count(), 3);
+		assert_eq!(
+			Pallet::<MockRuntime>::owned_in_collection(&TEST_COLLECTION_ID, &ALICE)
+				.collect::<BTreeSet<_>>(),
+			BTreeSet::from([first, second])
+		);
+		assert_eq!(
+			Pallet::<MockRuntime>::owned_in_collection(&TEST_COLLECTION_ID, &BOB).count(),
+			0
+		);
+	})
+}
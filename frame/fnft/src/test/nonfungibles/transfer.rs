@@ -34,7 +34,11 @@ fn simple() {
 			"owner before transfer should be ALICE"
 		);
 
-		assert_ok!(Pallet::<MockRuntime>::transfer(&TEST_COLLECTION_ID, &created_nft_id, &BOB));
+		assert_ok!(<Pallet<MockRuntime> as Transfer<_>>::transfer(
+			&TEST_COLLECTION_ID,
+			&created_nft_id,
+			&BOB
+		));
 
 		process_and_progress_blocks::<Pallet<MockRuntime>, MockRuntime>(10);
 
@@ -78,7 +82,11 @@ fn roundtrip() {
 		process_and_progress_blocks::<Pallet<MockRuntime>, MockRuntime>(10);
 
 		// send one of ALICE's NFTs to BOB
-		assert_ok!(Pallet::<MockRuntime>::transfer(&TEST_COLLECTION_ID, &nft_to_trade, &BOB));
+		assert_ok!(<Pallet<MockRuntime> as Transfer<_>>::transfer(
+			&TEST_COLLECTION_ID,
+			&nft_to_trade,
+			&BOB
+		));
 		assert_last_event::<MockRuntime>(Event::Nft(crate::Event::FinancialNftTransferred {
 			collection_id: TEST_COLLECTION_ID,
 			instance_id: nft_to_trade,
@@ -88,7 +96,11 @@ fn roundtrip() {
 		process_and_progress_blocks::<Pallet<MockRuntime>, MockRuntime>(10);
 
 		// send said NFT back
-		assert_ok!(Pallet::<MockRuntime>::transfer(&TEST_COLLECTION_ID, &nft_to_trade, &ALICE));
+		assert_ok!(<Pallet<MockRuntime> as Transfer<_>>::transfer(
+			&TEST_COLLECTION_ID,
+			&nft_to_trade,
+			&ALICE
+		));
 		assert_last_event::<MockRuntime>(Event::Nft(crate::Event::FinancialNftTransferred {
 			collection_id: TEST_COLLECTION_ID,
 			instance_id: nft_to_trade,
@@ -136,7 +148,7 @@ fn many() {
 		}
 
 		// transfer one of ALICE's NFTs to BOB
-		assert_ok!(Pallet::<MockRuntime>::transfer(&TEST_COLLECTION_ID, &a0, &BOB));
+		assert_ok!(<Pallet<MockRuntime> as Transfer<_>>::transfer(&TEST_COLLECTION_ID, &a0, &BOB));
 		assert_last_event::<MockRuntime>(Event::Nft(crate::Event::FinancialNftTransferred {
 			collection_id: TEST_COLLECTION_ID,
 			instance_id: a0,
@@ -159,7 +171,11 @@ fn many() {
 
 		// transfer all of CHARLIES's NFTs to BOB
 		for nft_id in charlies_nfts.iter() {
-			assert_ok!(Pallet::<MockRuntime>::transfer(&TEST_COLLECTION_ID, nft_id, &BOB));
+			assert_ok!(<Pallet<MockRuntime> as Transfer<_>>::transfer(
+				&TEST_COLLECTION_ID,
+				nft_id,
+				&BOB
+			));
 			assert_last_event::<MockRuntime>(Event::Nft(crate::Event::FinancialNftTransferred {
 				collection_id: TEST_COLLECTION_ID,
 				instance_id: *nft_id,
@@ -183,7 +199,11 @@ fn many() {
 		]);
 
 		// transfer one of (what was originally CHARLIES's) NFTs from BOB to ALICE
-		assert_ok!(Pallet::<MockRuntime>::transfer(&TEST_COLLECTION_ID, &c9, &ALICE));
+		assert_ok!(<Pallet<MockRuntime> as Transfer<_>>::transfer(
+			&TEST_COLLECTION_ID,
+			&c9,
+			&ALICE
+		));
 		assert_last_event::<MockRuntime>(Event::Nft(crate::Event::FinancialNftTransferred {
 			collection_id: TEST_COLLECTION_ID,
 			instance_id: c9,
@@ -205,7 +225,11 @@ fn many() {
 		]);
 
 		// transfer one of (what was originally CHARLIES's) NFTs from ALICE back to CHARLIE
-		assert_ok!(Pallet::<MockRuntime>::transfer(&TEST_COLLECTION_ID, &c9, &CHARLIE),);
+		assert_ok!(<Pallet<MockRuntime> as Transfer<_>>::transfer(
+			&TEST_COLLECTION_ID,
+			&c9,
+			&CHARLIE
+		),);
 		assert_last_event::<MockRuntime>(Event::Nft(crate::Event::FinancialNftTransferred {
 			collection_id: TEST_COLLECTION_ID,
 			instance_id: c9,
@@ -233,7 +257,7 @@ fn many() {
 fn instance_not_found() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Pallet::<MockRuntime>::transfer(&TEST_COLLECTION_ID, &1, &ALICE),
+			<Pallet<MockRuntime> as Transfer<_>>::transfer(&TEST_COLLECTION_ID, &1, &ALICE),
 			DispatchError::from(crate::Error::<MockRuntime>::InstanceNotFound)
 		);
 	});
@@ -19,7 +19,7 @@ use crate::{
 	AccountIdOf, FinancialNftInstanceIdOf, Pallet,
 };
 
-pub const TEST_COLLECTION_ID: u16 = 1;
+pub const TEST_COLLECTION_ID: u128 = 1;
 
 /// Mints a single NFT into ALICE and checks that it was created properly, returning the id of the
 /// newly created NFT.
@@ -120,7 +120,7 @@ pub(crate) fn mint_many_nfts_and_assert<const AMOUNT: usize>(
 /// Creates a BTreeSet from the provided [`NftInstanceId`]s.
 pub(crate) fn to_btree(
 	nfts: &[FinancialNftInstanceIdOf<MockRuntime>],
-) -> BTreeSet<(u16, FinancialNftInstanceIdOf<MockRuntime>)> {
+) -> BTreeSet<(u128, FinancialNftInstanceIdOf<MockRuntime>)> {
 	nfts.into_iter().copied().map(|id| (TEST_COLLECTION_ID, id)).collect()
 }
 
@@ -131,7 +131,7 @@ pub(crate) fn add_attributes_and_assert<
 	K: Encode,
 	V: Encode + Decode + PartialEq + fmt::Debug + Clone,
 >(
-	class: u16,
+	class: u128,
 	instance: &FinancialNftInstanceIdOf<MockRuntime>,
 	owner: u128,
 	attributes: &[(K, V)],
@@ -0,0 +1,46 @@
+use frame_support::dispatch::Weight;
+
+pub trait WeightInfo {
+	fn transfer() -> Weight;
+	fn approve() -> Weight;
+	fn revoke_approval() -> Weight;
+	fn list() -> Weight;
+	fn cancel_listing() -> Weight;
+	fn buy() -> Weight;
+	fn bid() -> Weight;
+	fn settle_auction() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn transfer() -> Weight {
+		10_000
+	}
+
+	fn approve() -> Weight {
+		10_000
+	}
+
+	fn revoke_approval() -> Weight {
+		10_000
+	}
+
+	fn list() -> Weight {
+		10_000
+	}
+
+	fn cancel_listing() -> Weight {
+		10_000
+	}
+
+	fn buy() -> Weight {
+		10_000
+	}
+
+	fn bid() -> Weight {
+		10_000
+	}
+
+	fn settle_auction() -> Weight {
+		10_000
+	}
+}
@@ -1,6 +1,9 @@
 use codec::Codec;
 use composable_support::rpc_helpers::SafeRpcWrapper;
-use composable_traits::defi::Rate;
+use composable_traits::{
+	defi::Rate,
+	lending::math::{InterestRateModel, InterestRatePoint},
+};
 use core::{fmt::Display, str::FromStr};
 use jsonrpsee::{
 	core::{Error as RpcError, RpcResult},
@@ -10,7 +13,7 @@ use jsonrpsee::{
 use lending_runtime_api::LendingRuntimeApi;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT, Percent};
 use sp_std::sync::Arc;
 
 #[rpc(client, server)]
@@ -24,6 +27,14 @@ where
 		market_id: SafeRpcWrapper<MarketId>,
 		at: Option<BlockHash>,
 	) -> RpcResult<SafeRpcWrapper<Rate>>;
+
+	#[method(name = "lending_simulateInterestRateModel")]
+	fn simulate_interest_rate_model(
+		&self,
+		interest_rate_model: InterestRateModel,
+		utilization_step: Percent,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Vec<InterestRatePoint>>>;
 }
 
 pub struct Lending<C, Block> {
@@ -66,4 +77,25 @@ where
 			)))
 		})
 	}
+
+	fn simulate_interest_rate_model(
+		&self,
+		interest_rate_model: InterestRateModel,
+		utilization_step: Percent,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Vec<InterestRatePoint>>> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let runtime_api_result =
+			api.simulate_interest_rate_model(&at, interest_rate_model, utilization_step);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
 }
@@ -2,7 +2,12 @@
 
 use codec::Codec;
 use composable_support::rpc_helpers::SafeRpcWrapper;
-use composable_traits::defi::Rate;
+use composable_traits::{
+	defi::Rate,
+	lending::math::{InterestRateModel, InterestRatePoint},
+};
+use sp_arithmetic::per_things::Percent;
+use sp_std::vec::Vec;
 
 // Lending Runtime API declaration. Implemented for each runtime at
 // `runtime/<runtime-name>/src/lib.rs`.
@@ -13,5 +18,13 @@ sp_api::decl_runtime_apis! {
 	{
 		/// Retrieve the current interest rate for the given `market_id`.
 		fn current_interest_rate(market_id: MarketId) -> SafeRpcWrapper<Rate>;
+
+		/// Evaluate the borrow and supply rates of `interest_rate_model` at utilization ratios
+		/// from 0% to 100%, every `utilization_step`. Returns `None` if the model is not valid or
+		/// the step is zero.
+		fn simulate_interest_rate_model(
+			interest_rate_model: InterestRateModel,
+			utilization_step: Percent,
+		) -> Option<Vec<InterestRatePoint>>;
 	}
 }
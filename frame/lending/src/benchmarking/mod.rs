@@ -7,10 +7,10 @@ use super::*;
 use crate::{self as pallet_lending, Pallet as Lending};
 use composable_traits::{
 	defi::{CurrencyPair, DeFiComposableConfig},
-	lending::{CreateInput, Lending as LendingTrait, RepayStrategy},
+	lending::{math::InterestRateModel, CreateInput, Lending as LendingTrait, RepayStrategy},
 	vault::StrategicVault,
 };
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_support::{
 	traits::{fungible, fungibles::Mutate, Get},
 	BoundedVec,
@@ -198,6 +198,105 @@ benchmarks! {
 		}
 	}: _(origin, market_id, BoundedVec::<_,T::MaxLiquidationBatchSize>::try_from(borrowers).unwrap())
 
+	liquidate_borrow {
+		let LendingBenchmarkingSetup {
+			caller,
+			origin,
+			bank,
+			pair,
+			input,
+		} = lending_benchmarking_setup::<T>();
+
+		let amount: BalanceOf<T> = 1_000_000_000_u64.into();
+		let part: BalanceOf<T> = 1_000_u64.into();
+		let repay_amount: BalanceOf<T> = 250_u64.into();
+
+		<pallet_balances::Pallet::<T> as fungible::Mutate<T::AccountId>>::mint_into(&caller, 10_000_000_000_000_u64.into()).unwrap();
+
+		let market_id = create_market_from_raw_origin::<T>(origin.clone(), input);
+
+		<pallet_balances::Pallet::<T> as fungible::Mutate<T::AccountId>>::mint_into(&Lending::<T>::account_id(&market_id), 10_000_000_000_000_u64.into()).unwrap();
+
+		let borrower: T::AccountId = account("borrower", 0, 0);
+		<pallet_balances::Pallet::<T> as fungible::Mutate<T::AccountId>>::mint_into(&borrower, 10_000_000_000_000_u64.into()).unwrap();
+		<T as Config>::MultiCurrency::mint_into(pair.base, &borrower, bank).unwrap();
+		Lending::<T>::deposit_collateral(RawOrigin::Signed(borrower.clone()).into(), market_id, amount, false).unwrap();
+		Lending::<T>::borrow(RawOrigin::Signed(borrower.clone()).into(), market_id, part).unwrap();
+
+		produce_block::<T>(42_u32.into(),4200_u64.into());
+		produce_block::<T>(43_u32.into(),4300_u64.into());
+
+		// collateral loses almost all of its value
+		set_price::<T>(pair.base, 1_u64);
+	}: _(origin, market_id, borrower, repay_amount, false)
+
+	enter_cross_margin {
+		let LendingBenchmarkingSetup {
+			caller,
+			origin,
+			bank,
+			pair,
+			input,
+		} = lending_benchmarking_setup::<T>();
+
+		let market_id = create_market_from_raw_origin::<T>(origin.clone(), input);
+	}: _(origin, market_id)
+
+	exit_cross_margin {
+		let m in 1..T::MaxCrossMarginMarkets::get();
+		let LendingBenchmarkingSetup {
+			caller,
+			origin,
+			bank,
+			pair,
+			input,
+		} = lending_benchmarking_setup::<T>();
+
+		let amount: BalanceOf<T> = 1_000_000_000_u64.into();
+		let part: BalanceOf<T> = 1_000_u64.into();
+
+		<pallet_balances::Pallet::<T> as fungible::Mutate<T::AccountId>>::mint_into(&caller, 10_000_000_000_000_u64.into()).unwrap();
+
+		// markets are indexed from one, in order of creation
+		let markets: Vec<MarketIndex> = (1..=m).map(MarketIndex::new).collect();
+		for market_id in markets.iter().copied() {
+			Lending::<T>::create_market(origin.clone().into(), input.clone(), false).unwrap();
+			<pallet_balances::Pallet::<T> as fungible::Mutate<T::AccountId>>::mint_into(&Lending::<T>::account_id(&market_id), 10_000_000_000_000_u64.into()).unwrap();
+			Lending::<T>::deposit_collateral(origin.clone().into(), market_id, amount, false).unwrap();
+			Lending::<T>::enter_cross_margin(origin.clone().into(), market_id).unwrap();
+		}
+		// the remaining markets are valued after the exit
+		Lending::<T>::borrow(origin.clone().into(), markets[0], part).unwrap();
+	}: _(origin, markets[0])
+
+	flash_loan {
+		let LendingBenchmarkingSetup {
+			caller,
+			origin,
+			bank,
+			pair,
+			input,
+		} = lending_benchmarking_setup::<T>();
+
+		let amount: BalanceOf<T> = 1_000_000_u64.into();
+
+		<pallet_balances::Pallet::<T> as fungible::Mutate<T::AccountId>>::mint_into(&caller, 10_000_000_000_000_u64.into()).unwrap();
+
+		let market_id = create_market_from_raw_origin::<T>(origin.clone(), input);
+		let market_account = Lending::<T>::account_id(&market_id);
+
+		// whichever asset is borrowed, the market can lend it and the caller can pay the fee
+		for asset_id in [pair.base, pair.quote] {
+			<T as Config>::MultiCurrency::mint_into(asset_id, &market_account, bank).unwrap();
+			<T as Config>::MultiCurrency::mint_into(asset_id, &caller, amount).unwrap();
+		}
+	}: _(origin, market_id, amount, Vec::new())
+
+	update_interest_rate_model {
+		let LendingBenchmarkingSetup { origin, input, .. } = lending_benchmarking_setup::<T>();
+		let market_id = create_market_from_raw_origin::<T>(origin, input);
+	}: _(RawOrigin::Root, market_id, InterestRateModel::default())
+
 	// HOOKS
 
 	now {}: {
@@ -100,6 +100,11 @@ pub(crate) fn create_market_config<T: Config>(
 			under_collateralized_warn_percent: Percent::from_percent(10),
 			liquidators: Default::default(),
 			max_price_age,
+			liquidation_threshold: MoreThanOneFixedU128::saturating_from_rational(
+				200_u128, 100_u128,
+			),
+			close_factor: Perquintill::from_percent(50),
+			liquidation_incentive: Perquintill::from_percent(5),
 		},
 		reserved_factor: Perquintill::from_percent(10),
 		currency_pair: CurrencyPair::new(collateral_asset, borrow_asset),
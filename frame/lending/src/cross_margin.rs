@@ -0,0 +1,184 @@
+//! Cross-margin accounts, where the collateral of an account in several markets backs its borrows
+//! in all of them.
+//!
+//! The positions of a cross-margin account are valued together through the oracle. Each market
+//! keeps its own `collateral_factor` and `liquidation_threshold`, which weight its collateral in
+//! the borrow limit and the health factor of the account:
+//!
+//! ```text
+//! borrow_limit  = sum(collateral_value / collateral_factor) - sum(borrow_value)
+//! health_factor = sum(collateral_value / liquidation_threshold) / sum(borrow_value)
+//! ```
+
+use crate::{models::borrower_data::BorrowerData, *};
+
+use composable_support::{
+	math::safe::{SafeAdd, SafeDiv, SafeSub},
+	validation::TryIntoValidated,
+};
+use composable_traits::{
+	defi::{DeFiEngine, LiftedFixedBalance},
+	lending::Lending,
+	vault::Vault,
+};
+use frame_support::pallet_prelude::*;
+use sp_runtime::{traits::Zero, DispatchError, FixedPointNumber};
+
+impl<T: Config> Pallet<T> {
+	pub(crate) fn do_enter_cross_margin(
+		account: &T::AccountId,
+		market_id: MarketIndex,
+	) -> Result<(), DispatchError> {
+		Self::ensure_no_flash_loan()?;
+		Self::get_market(&market_id)?;
+		CrossMarginMarkets::<T>::try_mutate(account, |markets| {
+			let markets = markets.get_or_insert_with(Default::default);
+			ensure!(!markets.contains(&market_id), Error::<T>::MarketAlreadyInCrossMargin);
+			markets
+				.try_push(market_id)
+				.map_err(|_| Error::<T>::MaxCrossMarginMarketsExceeded)?;
+			Result::<(), DispatchError>::Ok(())
+		})?;
+		CrossMarginAccounts::<T>::insert(market_id, account, ());
+		// the position is now valued with the rest of the cross-margin account
+		Self::update_liquidation_index(&market_id, account)
+	}
+
+	pub(crate) fn do_exit_cross_margin(
+		account: &T::AccountId,
+		market_id: MarketIndex,
+	) -> Result<(), DispatchError> {
+		Self::ensure_no_flash_loan()?;
+		let remaining_markets = CrossMarginMarkets::<T>::try_mutate_exists(account, |markets| {
+			let remaining_markets = markets.as_mut().ok_or(Error::<T>::MarketNotInCrossMargin)?;
+			let position = remaining_markets
+				.iter()
+				.position(|entered| *entered == market_id)
+				.ok_or(Error::<T>::MarketNotInCrossMargin)?;
+			remaining_markets.remove(position);
+			let remaining_markets = remaining_markets.clone();
+			if remaining_markets.is_empty() {
+				*markets = None;
+			}
+			Result::<_, DispatchError>::Ok(remaining_markets)
+		})?;
+		CrossMarginAccounts::<T>::remove(market_id, account);
+
+		// the isolated position and the rest of the cross-margin account must stand on their own
+		let (_, market) = Self::get_market(&market_id)?;
+		for markets in [&[market_id][..], &remaining_markets[..]] {
+			ensure!(
+				!Self::cross_margin_borrower_data(markets, &market, account, None)?
+					.exceeds_borrow_limit()?,
+				Error::<T>::WouldGoUnderCollateralized
+			);
+		}
+		Self::update_liquidation_index(&market_id, account)
+	}
+
+	/// The markets of the cross-margin account of `account`, if `market_id` is part of it.
+	pub(crate) fn cross_margin_markets_of(
+		market_id: &MarketIndex,
+		account: &T::AccountId,
+	) -> Option<BoundedVec<MarketIndex, T::MaxCrossMarginMarkets>> {
+		CrossMarginMarkets::<T>::get(account).filter(|markets| markets.contains(market_id))
+	}
+
+	/// Values all of the positions of `account` in `markets` together.
+	///
+	/// The `collateral_factor` and `liquidation_threshold` of the returned [`BorrowerData`] are
+	/// the ones of the markets weighted by the value of their collateral, falling back to the ones
+	/// of `market` if there is no collateral. `withdrawal` is deducted from the collateral of the
+	/// account in the given market.
+	pub(crate) fn cross_margin_borrower_data(
+		markets: &[MarketIndex],
+		market: &MarketConfigOf<T>,
+		account: &T::AccountId,
+		withdrawal: Option<(&MarketIndex, T::Balance)>,
+	) -> Result<BorrowerData, DispatchError> {
+		let mut collateral_value = T::Balance::zero();
+		let mut borrow_value = T::Balance::zero();
+		// sum(collateral_value / collateral_factor)
+		let mut borrow_capacity = LiftedFixedBalance::zero();
+		// sum(collateral_value / liquidation_threshold)
+		let mut liquidation_capacity = LiftedFixedBalance::zero();
+
+		for market_id in markets {
+			let (_, entered_market) = Self::get_market(market_id)?;
+			let mut collateral =
+				AccountCollateral::<T>::get(market_id, account).unwrap_or_else(T::Balance::zero);
+			if let Some((withdrawal_market_id, amount)) = withdrawal {
+				if withdrawal_market_id == market_id {
+					collateral = collateral.safe_sub(&amount)?;
+				}
+			}
+			let market_collateral_value =
+				Self::get_price(entered_market.collateral_asset, collateral)?;
+			let borrow_asset = T::Vault::asset_id(&entered_market.borrow_asset_vault)?;
+			let debt = Self::total_debt_with_interest(market_id, account)?.unwrap_or_zero();
+
+			collateral_value = collateral_value.safe_add(&market_collateral_value)?;
+			borrow_value = borrow_value.safe_add(&Self::get_price(borrow_asset, debt)?)?;
+
+			let market_collateral_value =
+				LiftedFixedBalance::saturating_from_integer(market_collateral_value.into());
+			borrow_capacity = borrow_capacity
+				.safe_add(&market_collateral_value.safe_div(&entered_market.collateral_factor)?)?;
+			liquidation_capacity = liquidation_capacity.safe_add(
+				&market_collateral_value.safe_div(&entered_market.liquidation_threshold)?,
+			)?;
+		}
+
+		let (collateral_factor, liquidation_threshold) = if borrow_capacity.is_zero() {
+			(market.collateral_factor, market.liquidation_threshold)
+		} else {
+			let collateral_value =
+				LiftedFixedBalance::saturating_from_integer(collateral_value.into());
+			(
+				collateral_value.safe_div(&borrow_capacity)?,
+				collateral_value.safe_div(&liquidation_capacity)?,
+			)
+		};
+
+		Ok(BorrowerData::new(
+			collateral_value,
+			borrow_value,
+			collateral_factor
+				.try_into_validated()
+				.map_err(|_| Error::<T>::CollateralFactorMustBeMoreThanOne)?,
+			liquidation_threshold
+				.try_into_validated()
+				.map_err(|_| Error::<T>::CollateralFactorMustBeMoreThanOne)?,
+			market.under_collateralized_warn_percent,
+		))
+	}
+
+	/// The market to take collateral from when liquidating the borrow of `account` in
+	/// `market_id`.
+	///
+	/// Isolated positions are backed by their own collateral. Cross-margin accounts give up the
+	/// collateral of the market where they have the most value first, which covers the most of
+	/// the debt with the least positions left with dust.
+	pub(crate) fn collateral_market_to_seize(
+		market_id: &MarketIndex,
+		account: &T::AccountId,
+	) -> Result<MarketIndex, DispatchError> {
+		let markets = match Self::cross_margin_markets_of(market_id, account) {
+			Some(markets) => markets,
+			None => return Ok(*market_id),
+		};
+		let mut best = (*market_id, T::Balance::zero());
+		for entered_market_id in markets {
+			let collateral = match AccountCollateral::<T>::get(entered_market_id, account) {
+				Some(collateral) if !collateral.is_zero() => collateral,
+				_ => continue,
+			};
+			let (_, entered_market) = Self::get_market(&entered_market_id)?;
+			let collateral_value = Self::get_price(entered_market.collateral_asset, collateral)?;
+			if collateral_value > best.1 {
+				best = (entered_market_id, collateral_value);
+			}
+		}
+		Ok(best.0)
+	}
+}
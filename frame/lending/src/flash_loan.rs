@@ -0,0 +1,95 @@
+//! Flash loans of the borrowable liquidity of a market, lent and repaid within a single
+//! extrinsic.
+//!
+//! The borrow asset held by the market is lent to an account, which uses it in a batch of calls
+//! dispatched on its behalf. Once they are done, the amount lent plus the `FlashLoanFee` is taken
+//! back from the account, and the fee is transferred to the vault of the market as yield of the
+//! market, so that it grows the assets of the vault. The whole extrinsic fails if the account
+//! cannot repay.
+//!
+//! No other lending operation can happen while the calls are dispatched, so that the liquidity
+//! lent cannot be used to move the positions of the market it was taken from.
+
+use crate::*;
+
+use composable_support::math::safe::SafeAdd;
+use composable_traits::{lending::Lending, vault::Vault};
+use frame_support::{
+	pallet_prelude::*,
+	traits::fungibles::{Inspect, Transfer},
+};
+use sp_runtime::{
+	traits::{Dispatchable, Zero},
+	DispatchError,
+};
+use sp_std::vec::Vec;
+
+impl<T: Config> Pallet<T> {
+	/// Fails if a flash loan is in progress, to keep the calls it dispatches from reentering the
+	/// pallet.
+	pub(crate) fn ensure_no_flash_loan() -> Result<(), DispatchError> {
+		ensure!(!FlashLoanInProgress::<T>::exists(), Error::<T>::FlashLoanInProgress);
+		Ok(())
+	}
+
+	/// Lends `amount` of the borrow asset of `market_id` to `account` for the duration of `calls`.
+	///
+	/// Returns the fee paid.
+	pub(crate) fn do_flash_loan(
+		account: &T::AccountId,
+		market_id: &MarketIndex,
+		amount: T::Balance,
+		calls: Vec<<T as Config>::Call>,
+	) -> Result<T::Balance, DispatchError> {
+		Self::ensure_no_flash_loan()?;
+		ensure!(!amount.is_zero(), Error::<T>::FlashLoanAmountMustBeGreaterThanZero);
+		let (_, market) = Self::get_market(market_id)?;
+		let borrow_asset = T::Vault::asset_id(&market.borrow_asset_vault)?;
+		let market_account = Self::account_id(market_id);
+		let fee = T::FlashLoanFee::get().mul_ceil(amount);
+		let expected_balance =
+			<T as Config>::MultiCurrency::balance(borrow_asset, &market_account).safe_add(&fee)?;
+
+		<T as Config>::MultiCurrency::transfer(
+			borrow_asset,
+			&market_account,
+			account,
+			amount,
+			false,
+		)
+		.map_err(|_| Error::<T>::NotEnoughBorrowAsset)?;
+
+		FlashLoanInProgress::<T>::put(market_id);
+		for call in calls {
+			call.dispatch(frame_system::RawOrigin::Signed(account.clone()).into())
+				.map_err(|error| error.error)?;
+		}
+		FlashLoanInProgress::<T>::kill();
+
+		<T as Config>::MultiCurrency::transfer(
+			borrow_asset,
+			account,
+			&market_account,
+			amount.safe_add(&fee)?,
+			false,
+		)
+		.map_err(|_| Error::<T>::FlashLoanNotRepaid)?;
+		ensure!(
+			<T as Config>::MultiCurrency::balance(borrow_asset, &market_account) >=
+				expected_balance,
+			Error::<T>::FlashLoanNotRepaid
+		);
+
+		// The fee is not returned principal, so the balance of the market in the vault is kept.
+		if !fee.is_zero() {
+			<T as Config>::MultiCurrency::transfer(
+				borrow_asset,
+				&market_account,
+				&T::Vault::account_id(&market.borrow_asset_vault),
+				fee,
+				false,
+			)?;
+		}
+		Ok(fee)
+	}
+}
@@ -8,17 +8,18 @@ use crate::{
 	},
 };
 use composable_support::{
-	math::safe::{SafeAdd, SafeDiv, SafeMul, SafeSub},
+	math::safe::{safe_multiply_by_rational, SafeAdd, SafeDiv, SafeMul, SafeSub},
 	validation::{TryIntoValidated, Validated},
 };
 use composable_traits::{
 	currency::CurrencyFactory,
 	defi::{
 		CurrencyPair, DeFiComposableConfig, DeFiEngine, LiftedFixedBalance, MoreThanOneFixedU128,
-		OneOrMoreFixedU128, Sell,
+		OneOrMoreFixedU128, Sell, ZeroToOneFixedU128,
 	},
 	lending::{
-		math::InterestRate, BorrowAmountOf, CollateralLpAmountOf, Lending, MarketConfig,
+		math::{InterestRate, InterestRateModel, InterestRatePoint, InteresteRateModelIsValid},
+		BorrowAmountOf, CollateralLpAmountOf, Lending, MarketConfig, TotalDebtWithInterest,
 		UpdateInput,
 	},
 	liquidation::Liquidation,
@@ -112,12 +113,16 @@ impl<T: Config> Pallet<T> {
 					.updatable
 					.under_collateralized_warn_percent,
 				liquidators: config_input.updatable.liquidators,
+				liquidation_threshold: config_input.updatable.liquidation_threshold,
+				close_factor: config_input.updatable.close_factor,
+				liquidation_incentive: config_input.updatable.liquidation_incentive,
 			};
 			// TODO: pass ED from API,
 			let debt_token_id = T::CurrencyFactory::reserve_lp_token_id(T::Balance::default())?;
 
 			DebtTokenForMarket::<T>::insert(market_id, debt_token_id);
 			Markets::<T>::insert(market_id, market_config);
+			MarketOfAccount::<T>::insert(Self::account_id(&market_id), market_id);
 			BorrowIndex::<T>::insert(market_id, FixedU128::one());
 
 			Ok((market_id, borrow_asset_vault))
@@ -139,6 +144,7 @@ impl<T: Config> Pallet<T> {
 			collateral_balance.replace(new_collateral_balance);
 			Result::<(), DispatchError>::Ok(())
 		})?;
+		Self::update_liquidation_index(market_id, account)?;
 
 		<T as Config>::MultiCurrency::transfer(
 			market.collateral_asset,
@@ -165,27 +171,44 @@ impl<T: Config> Pallet<T> {
 
 		ensure!(amount <= collateral_balance, Error::<T>::NotEnoughCollateralToWithdraw);
 
-		let borrow_asset = T::Vault::asset_id(&market.borrow_asset_vault)?;
-		let borrower_balance_with_interest =
-			Self::total_debt_with_interest(market_id, account)?.unwrap_or_zero();
-
-		let borrow_balance_value = Self::get_price(borrow_asset, borrower_balance_with_interest)?;
-
-		let collateral_balance_after_withdrawal_value =
-			Self::get_price(market.collateral_asset, collateral_balance.safe_sub(&amount)?)?;
-
-		let borrower_after_withdrawal = BorrowerData::new(
-			collateral_balance_after_withdrawal_value,
-			borrow_balance_value,
-			market
-				.collateral_factor
-				.try_into_validated()
-				.map_err(|_| Error::<T>::Overflow)?, // TODO: Use a proper error mesage?
-			market.under_collateralized_warn_percent,
-		);
+		let borrower_after_withdrawal = match Self::cross_margin_markets_of(market_id, account) {
+			Some(markets) => Self::cross_margin_borrower_data(
+				&markets,
+				&market,
+				account,
+				Some((market_id, amount)),
+			)?,
+			None => {
+				let borrow_asset = T::Vault::asset_id(&market.borrow_asset_vault)?;
+				let borrower_balance_with_interest =
+					Self::total_debt_with_interest(market_id, account)?.unwrap_or_zero();
+
+				let borrow_balance_value =
+					Self::get_price(borrow_asset, borrower_balance_with_interest)?;
+
+				let collateral_balance_after_withdrawal_value = Self::get_price(
+					market.collateral_asset,
+					collateral_balance.safe_sub(&amount)?,
+				)?;
+
+				BorrowerData::new(
+					collateral_balance_after_withdrawal_value,
+					borrow_balance_value,
+					market
+						.collateral_factor
+						.try_into_validated()
+						.map_err(|_| Error::<T>::Overflow)?, // TODO: Use a proper error mesage?
+					market
+						.liquidation_threshold
+						.try_into_validated()
+						.map_err(|_| Error::<T>::Overflow)?,
+					market.under_collateralized_warn_percent,
+				)
+			},
+		};
 
 		ensure!(
-			!borrower_after_withdrawal.should_liquidate()?,
+			!borrower_after_withdrawal.exceeds_borrow_limit()?,
 			Error::<T>::WouldGoUnderCollateralized
 		);
 
@@ -220,6 +243,7 @@ impl<T: Config> Pallet<T> {
 
 			Result::<(), DispatchError>::Ok(())
 		})?;
+		Self::update_liquidation_index(market_id, account)?;
 		<T as Config>::MultiCurrency::transfer(
 			market.collateral_asset,
 			&market_account,
@@ -248,9 +272,16 @@ impl<T: Config> Pallet<T> {
 					market.collateral_factor >= input.collateral_factor,
 					Error::<T>::CannotIncreaseCollateralFactorOfOpenMarket
 				);
+				ensure!(
+					market.liquidation_threshold >= input.liquidation_threshold,
+					Error::<T>::CannotIncreaseLiquidationThresholdOfOpenMarket
+				);
 				market.collateral_factor = input.collateral_factor;
 				market.under_collateralized_warn_percent = input.under_collateralized_warn_percent;
 				market.liquidators = input.liquidators.clone();
+				market.liquidation_threshold = input.liquidation_threshold;
+				market.close_factor = input.close_factor;
+				market.liquidation_incentive = input.liquidation_incentive;
 				Ok(())
 			} else {
 				Err(Error::<T>::MarketDoesNotExist)
@@ -260,6 +291,42 @@ impl<T: Config> Pallet<T> {
 		Ok(().into())
 	}
 
+	/// Replaces the interest rate model of a market.
+	///
+	/// `on_initialize` only accrues interest up to the timestamp of the previous block, and not at
+	/// all if it failed, so the interest of the market is accrued up to now at the rate of the
+	/// previous model first. The next accrual of the market starts from `InterestAccruedAt`.
+	pub(crate) fn do_update_interest_rate_model(
+		market_id: &MarketIndex,
+		interest_rate_model: Validated<InterestRateModel, InteresteRateModelIsValid>,
+	) -> Result<(), DispatchError> {
+		Self::ensure_no_flash_loan()?;
+		let now = Self::now();
+		Self::accrue_interest(market_id, now)?;
+		InterestAccruedAt::<T>::insert(market_id, now);
+		Markets::<T>::try_mutate(market_id, |market| {
+			let market = market.as_mut().ok_or(Error::<T>::MarketDoesNotExist)?;
+			market.interest_rate_model = interest_rate_model.value();
+			Result::<(), DispatchError>::Ok(())
+		})
+	}
+
+	/// Evaluates `interest_rate_model` at utilization ratios from 0% to 100%, every
+	/// `utilization_step`, to preview it before it is used by a market. Lending keeps no reserve
+	/// out of the interest paid by borrowers, so all of it goes to the lenders.
+	///
+	/// Returns `None` if the model is not valid.
+	pub fn simulate_interest_rate_model(
+		interest_rate_model: InterestRateModel,
+		utilization_step: Percent,
+	) -> Option<Vec<InterestRatePoint>> {
+		interest_rate_model
+			.try_into_validated::<InteresteRateModelIsValid>()
+			.ok()?
+			.value()
+			.simulate(utilization_step, ZeroToOneFixedU128::zero())
+	}
+
 	/// Returns pair of market's id and market (as 'MarketConfing') via market's id
 	/// - `market_id` : Market index as a key in 'Markets' storage
 	pub(crate) fn get_market(
@@ -389,12 +456,19 @@ impl<T: Config> Pallet<T> {
 
 	/// Creates a new [`BorrowerData`] for the given market and account. See [`BorrowerData`]
 	/// for more information.
+	///
+	/// If the market is part of the cross-margin account of `account`, the data covers all of the
+	/// markets of the cross-margin account.
 	pub fn create_borrower_data(
 		market_id: &<Self as Lending>::MarketId,
 		account: &<Self as DeFiEngine>::AccountId,
 	) -> Result<BorrowerData, DispatchError> {
 		let (_, market) = Self::get_market(market_id)?;
 
+		if let Some(markets) = Self::cross_margin_markets_of(market_id, account) {
+			return Self::cross_margin_borrower_data(&markets, &market, account, None)
+		}
+
 		let collateral_balance_value = Self::get_price(
 			market.collateral_asset,
 			Self::collateral_of_account(market_id, account)?,
@@ -415,6 +489,10 @@ impl<T: Config> Pallet<T> {
 				.try_into_validated()
 				.map_err(|_| Error::<T>::CollateralFactorMustBeMoreThanOne)?, /* TODO: Use a proper
 			                                                                * error mesage */
+			market
+				.liquidation_threshold
+				.try_into_validated()
+				.map_err(|_| Error::<T>::CollateralFactorMustBeMoreThanOne)?,
 			market.under_collateralized_warn_percent,
 		);
 
@@ -444,6 +522,9 @@ impl<T: Config> Pallet<T> {
 	/// Initiate liquidation of individual position for particular borrower within mentioned
 	/// market. Returns 'Ok(())' in the case of successful initiation, 'Err(DispatchError)' in
 	/// the opposite case.
+	///
+	/// The market repays the `close_factor` of the debt itself, and sends the collateral taken in
+	/// exchange to the liquidation engine to get the borrow asset back.
 	/// - `liquidator` : Liquidator's account id.
 	/// - `market_pair` : Index and configuration of the market from which tokens were borrowed.
 	/// - `account` : Borrower's account id whose debt are going to be liquidated.
@@ -454,26 +535,184 @@ impl<T: Config> Pallet<T> {
 		account: &<Self as DeFiEngine>::AccountId,
 	) -> Result<(), DispatchError> {
 		let (market_id, market) = market_pair;
+		let source_target_account = Self::account_id(market_id);
+
+		let (collateral_market_id, collateral_to_liquidate) = Self::repay_and_seize(
+			liquidator,
+			&source_target_account,
+			market_id,
+			market,
+			account,
+			None,
+			false,
+		)?;
+
+		// collateral of cross-margin accounts may be held by another market, the proceeds of the
+		// sale go to the market that repaid the debt
+		let collateral_asset = if collateral_market_id == **market_id {
+			market.collateral_asset
+		} else {
+			let (_, collateral_market) = Self::get_market(&collateral_market_id)?;
+			<T as Config>::MultiCurrency::transfer(
+				collateral_market.collateral_asset,
+				&Self::account_id(&collateral_market_id),
+				&source_target_account,
+				collateral_to_liquidate,
+				false,
+			)?;
+			collateral_market.collateral_asset
+		};
+
+		let unit_price = T::Oracle::get_ratio(CurrencyPair::new(collateral_asset, borrow_asset))?;
+
+		let sell = Sell::new(collateral_asset, borrow_asset, collateral_to_liquidate, unit_price);
+		T::Liquidation::liquidate(&source_target_account, sell, market.liquidators.clone())?;
+		Ok(())
+	}
+
+	/// Repays part of the debt of `account` under the liquidation threshold, paid by `payer`, and
+	/// takes the collateral it is worth plus the `liquidation_incentive` out of the position. The
+	/// collateral taken stays in the account of the market holding it, which is another market of
+	/// the cross-margin account of `account` if the position is part of it.
+	///
+	/// Once all of the debt is repaid the borrow is closed and its rent goes to `liquidator`.
+	/// - `repay_amount` : Debt to repay, at most the `close_factor` of it. `None` repays as much as
+	///   the `close_factor` allows.
+	///
+	/// Returns the market the collateral was taken from and the amount taken.
+	fn repay_and_seize(
+		liquidator: &<Self as DeFiEngine>::AccountId,
+		payer: &<Self as DeFiEngine>::AccountId,
+		market_id: &<Self as Lending>::MarketId,
+		market: &MarketConfigOf<T>,
+		account: &<Self as DeFiEngine>::AccountId,
+		repay_amount: Option<T::Balance>,
+		keep_alive: bool,
+	) -> Result<(MarketIndex, T::Balance), DispatchError> {
 		ensure!(
 			Self::should_liquidate(market_id, account)?,
-			DispatchError::Other("Tried liquidate position which is not supposed to be liquidated")
+			Error::<T>::CannotLiquidateHealthyPosition
 		);
+		let total_debt = match Self::total_debt_with_interest(market_id, account)? {
+			TotalDebtWithInterest::Amount(amount) => amount,
+			TotalDebtWithInterest::NoDebt => return Err(Error::<T>::BorrowDoesNotExist.into()),
+		};
+		let max_repay_amount = market.close_factor.mul_ceil(total_debt);
+		let repay_amount = repay_amount.unwrap_or(max_repay_amount);
+		ensure!(!repay_amount.is_zero(), Error::<T>::RepayAmountMustBeGreaterThanZero);
+		ensure!(repay_amount <= max_repay_amount, Error::<T>::LiquidationExceedsCloseFactor);
+
+		let MarketAssets { borrow_asset, debt_asset } = Self::get_assets_for_market(market_id)?;
+		let collateral_market_id = Self::collateral_market_to_seize(market_id, account)?;
+		let collateral_asset = if collateral_market_id == *market_id {
+			market.collateral_asset
+		} else {
+			Self::get_market(&collateral_market_id)?.1.collateral_asset
+		};
+		let collateral_to_seize = Self::collateral_to_seize(
+			&collateral_market_id,
+			collateral_asset,
+			borrow_asset,
+			account,
+			repay_amount,
+			market.liquidation_incentive,
+		)?;
 
-		let collateral_to_liquidate = Self::collateral_of_account(market_id, account)?;
-
-		let source_target_account = Self::account_id(market_id);
+		let market_account = Self::account_id(market_id);
+		crate::repay_borrow::repay_debt::<T>(
+			borrow_asset,
+			debt_asset,
+			payer,
+			&market_account,
+			account,
+			total_debt,
+			repay_amount,
+			keep_alive,
+		)?;
+		AccountCollateral::<T>::try_mutate(collateral_market_id, account, |collateral_balance| {
+			let new_collateral_balance =
+				collateral_balance.unwrap_or_default().safe_sub(&collateral_to_seize)?;
+			collateral_balance.replace(new_collateral_balance);
+			Result::<(), DispatchError>::Ok(())
+		})?;
 
-		let unit_price =
-			T::Oracle::get_ratio(CurrencyPair::new(market.collateral_asset, borrow_asset))?;
+		if repay_amount == total_debt {
+			BorrowTimestamp::<T>::remove(market_id, account);
+			DebtIndex::<T>::remove(market_id, account);
+			if let Some(deposit) = BorrowRent::<T>::take(market_id, account) {
+				<T as Config>::NativeCurrency::transfer(
+					&market_account,
+					liquidator,
+					deposit,
+					false,
+				)?;
+			}
+		}
+		Self::update_liquidation_index(market_id, account)?;
+		if collateral_market_id != *market_id {
+			Self::update_liquidation_index(&collateral_market_id, account)?;
+		}
+		Ok((collateral_market_id, collateral_to_seize))
+	}
 
-		let sell =
-			Sell::new(market.collateral_asset, borrow_asset, collateral_to_liquidate, unit_price);
-		T::Liquidation::liquidate(&source_target_account, sell, market.liquidators.clone())?;
-		if let Some(deposit) = BorrowRent::<T>::get(market_id, account) {
-			let market_account = Self::account_id(market_id);
-			<T as Config>::NativeCurrency::transfer(&market_account, liquidator, deposit, false)?;
+	/// The collateral of `account` in `collateral_market_id` worth `repay_amount` of borrow asset
+	/// plus the `liquidation_incentive`, bounded by the collateral of the account.
+	fn collateral_to_seize(
+		collateral_market_id: &<Self as Lending>::MarketId,
+		collateral_asset: <T as DeFiComposableConfig>::MayBeAssetId,
+		borrow_asset: <T as DeFiComposableConfig>::MayBeAssetId,
+		account: &<Self as DeFiEngine>::AccountId,
+		repay_amount: T::Balance,
+		liquidation_incentive: Perquintill,
+	) -> Result<T::Balance, DispatchError> {
+		let collateral_balance = Self::collateral_of_account(collateral_market_id, account)?;
+		let collateral_balance_value = Self::get_price(collateral_asset, collateral_balance)?;
+		let repay_value = Self::get_price(borrow_asset, repay_amount)?;
+		let seize_value = repay_value.safe_add(&liquidation_incentive.mul_floor(repay_value))?;
+		if seize_value >= collateral_balance_value {
+			return Ok(collateral_balance)
 		}
-		Ok(())
+		let collateral_to_seize = safe_multiply_by_rational(
+			collateral_balance.into(),
+			seize_value.into(),
+			collateral_balance_value.into(),
+		)?;
+		Ok(collateral_to_seize.into())
+	}
+
+	/// Repays `repay_amount` of the debt of `borrower` in exchange for the collateral it is worth
+	/// plus the `liquidation_incentive`, transferred to `liquidator`.
+	///
+	/// Returns the market the collateral was taken from and the amount received by `liquidator`.
+	pub(crate) fn do_liquidate_borrow(
+		liquidator: &<Self as DeFiEngine>::AccountId,
+		market_id: &<Self as Lending>::MarketId,
+		borrower: &<Self as DeFiEngine>::AccountId,
+		repay_amount: T::Balance,
+		keep_alive: bool,
+	) -> Result<(MarketIndex, T::Balance), DispatchError> {
+		Self::ensure_no_flash_loan()?;
+		ensure!(liquidator != borrower, Error::<T>::CannotLiquidateOwnBorrow);
+		let (_, market) = Self::get_market(market_id)?;
+		Self::ensure_price_is_recent(&market)?;
+		let (collateral_market_id, collateral_seized) = Self::repay_and_seize(
+			liquidator,
+			liquidator,
+			market_id,
+			&market,
+			borrower,
+			Some(repay_amount),
+			keep_alive,
+		)?;
+		let (_, collateral_market) = Self::get_market(&collateral_market_id)?;
+		<T as Config>::MultiCurrency::transfer(
+			collateral_market.collateral_asset,
+			&Self::account_id(&collateral_market_id),
+			liquidator,
+			collateral_seized,
+			false,
+		)?;
+		Ok((collateral_market_id, collateral_seized))
 	}
 
 	/// Liquidates debt for each borrower in the vector within mentioned market.
@@ -486,6 +725,7 @@ impl<T: Config> Pallet<T> {
 		market_id: &<Self as Lending>::MarketId,
 		borrowers: BoundedVec<<Self as DeFiEngine>::AccountId, T::MaxLiquidationBatchSize>,
 	) -> Result<Vec<<Self as DeFiEngine>::AccountId>, DispatchError> {
+		Self::ensure_no_flash_loan()?;
 		// Vector of borrowers whose positions are involved in the liquidation process.
 		let mut subjected_borrowers: Vec<<Self as DeFiEngine>::AccountId> = Vec::new();
 		let market_pair = Self::get_market(market_id)?;
@@ -508,12 +748,9 @@ impl<T: Config> Pallet<T> {
 				});
 
 			// If storage transaction succeeded,
-			// push borrower to the output vector,
-			// remove debt records from storages.
+			// push borrower to the output vector.
 			if storage_transaction_succeeded.is_ok() {
 				subjected_borrowers.push(account.clone());
-				BorrowTimestamp::<T>::remove(market_id, account);
-				DebtIndex::<T>::remove(market_id, account);
 			}
 		}
 		Ok(subjected_borrowers)
@@ -553,19 +790,12 @@ impl<T: Config> Pallet<T> {
 					// The intermediate transfer(vault->market) while borrowing would
 					// allow the vault to update the strategy balance (market = borrow vault
 					// strategy).
-					match Self::available_funds(&config, &market_account)? {
-						FundsAvailability::Withdrawable(balance) => {
-							Self::handle_withdrawable(&config, &market_account, balance)?;
-							call_counters.handle_withdrawable += 1;
-						},
-						FundsAvailability::Depositable(balance) => {
-							Self::handle_depositable(&config, &market_account, balance)?;
-							call_counters.handle_depositable += 1;
-						},
-						FundsAvailability::MustLiquidate => {
-							Self::handle_must_liquidate(&config, &market_account)?;
-							call_counters.handle_must_liquidate += 1;
-						},
+					match Self::rebalance_market(&config, &market_account)? {
+						FundsAvailability::Withdrawable(_) =>
+							call_counters.handle_withdrawable += 1,
+						FundsAvailability::Depositable(_) => call_counters.handle_depositable += 1,
+						FundsAvailability::MustLiquidate =>
+							call_counters.handle_must_liquidate += 1,
 					}
 
 					call_counters.available_funds += 1;
@@ -608,6 +838,24 @@ impl<T: Config> Pallet<T> {
 		<T::Vault as StrategicVault>::available_funds(&config.borrow_asset_vault, market_account)
 	}
 
+	/// Moves the borrow asset between the market and its vault as the vault requests, returning
+	/// the request that was handled.
+	pub(crate) fn rebalance_market(
+		config: &MarketConfigOf<T>,
+		market_account: &T::AccountId,
+	) -> Result<FundsAvailability<T::Balance>, DispatchError> {
+		let availability = Self::available_funds(config, market_account)?;
+		match &availability {
+			FundsAvailability::Withdrawable(balance) =>
+				Self::handle_withdrawable(config, market_account, *balance)?,
+			FundsAvailability::Depositable(balance) =>
+				Self::handle_depositable(config, market_account, *balance)?,
+			FundsAvailability::MustLiquidate =>
+				Self::handle_must_liquidate(config, market_account)?,
+		}
+		Ok(availability)
+	}
+
 	pub(crate) fn handle_withdrawable(
 		config: &MarketConfigOf<T>,
 		market_account: &T::AccountId,
@@ -24,7 +24,7 @@ use frame_support::{
 };
 use sp_runtime::{
 	traits::{AccountIdConversion, Zero},
-	ArithmeticError, DispatchError, FixedPointNumber, FixedU128, Percent,
+	ArithmeticError, DispatchError, FixedPointNumber, Percent,
 };
 use sp_std::vec::Vec;
 
@@ -40,6 +40,7 @@ impl<T: Config> Lending for Pallet<T> {
 		input: CreateInputOf<T>,
 		keep_alive: bool,
 	) -> Result<(Self::MarketId, Self::VaultId), DispatchError> {
+		Self::ensure_no_flash_loan()?;
 		Self::do_create_market(manager, input.try_into_validated()?, keep_alive)
 	}
 
@@ -48,6 +49,7 @@ impl<T: Config> Lending for Pallet<T> {
 		market_id: Self::MarketId,
 		input: UpdateInput<Self::LiquidationStrategyId, Self::BlockNumber>,
 	) -> DispatchResultWithPostInfo {
+		Self::ensure_no_flash_loan()?;
 		Self::do_update_market(manager, market_id, input.try_into_validated()?)
 	}
 
@@ -61,6 +63,7 @@ impl<T: Config> Lending for Pallet<T> {
 		amount: CollateralLpAmountOf<Self>,
 		keep_alive: bool,
 	) -> Result<(), DispatchError> {
+		Self::ensure_no_flash_loan()?;
 		Self::do_deposit_collateral(market_id, account, amount.try_into_validated()?, keep_alive)
 	}
 
@@ -69,6 +72,7 @@ impl<T: Config> Lending for Pallet<T> {
 		account: &Self::AccountId,
 		amount: CollateralLpAmountOf<Self>,
 	) -> Result<(), DispatchError> {
+		Self::ensure_no_flash_loan()?;
 		Self::do_withdraw_collateral(market_id, account, amount.try_into_validated()?)
 	}
 
@@ -83,9 +87,16 @@ impl<T: Config> Lending for Pallet<T> {
 		borrowing_account: &Self::AccountId,
 		amount_to_borrow: BorrowAmountOf<Self>,
 	) -> Result<(), DispatchError> {
+		Self::ensure_no_flash_loan()?;
 		let (_, market) = Self::get_market(market_id)?;
 
 		Self::ensure_price_is_recent(&market)?;
+		// the borrow is backed by the collateral of all the markets of the cross-margin account
+		for cross_margin_market_id in
+			Self::cross_margin_markets_of(market_id, borrowing_account).unwrap_or_default()
+		{
+			Self::ensure_price_is_recent(&Self::get_market(&cross_margin_market_id)?.1)?;
+		}
 
 		let MarketAssets { borrow_asset, debt_asset: debt_asset_id } =
 			Self::get_assets_for_market(market_id)?;
@@ -154,6 +165,7 @@ impl<T: Config> Lending for Pallet<T> {
 		} else {
 			// REVIEW
 		}
+		Self::update_liquidation_index(market_id, borrowing_account)?;
 
 		Ok(())
 	}
@@ -166,8 +178,7 @@ impl<T: Config> Lending for Pallet<T> {
 		total_repay_amount: RepayStrategy<BorrowAmountOf<Self>>,
 		keep_alive: bool,
 	) -> Result<BorrowAmountOf<Self>, DispatchError> {
-		use crate::repay_borrow::{pay_interest, repay_principal};
-
+		Self::ensure_no_flash_loan()?;
 		// cannot repay in the same block as the borrow
 		let timestamp = BorrowTimestamp::<T>::get(market_id, beneficiary)
 			.ok_or(Error::<T>::BorrowDoesNotExist)?;
@@ -188,46 +199,13 @@ impl<T: Config> Lending for Pallet<T> {
 
 		let MarketAssets { borrow_asset, debt_asset } = Self::get_assets_for_market(market_id)?;
 
-		// initial borrow amount
-		let beneficiary_borrow_asset_principal =
-			<T as Config>::MultiCurrency::balance(debt_asset, beneficiary);
-		// interest accrued
-		let beneficiary_interest_on_market =
-			beneficiary_total_debt_with_interest.safe_sub(&beneficiary_borrow_asset_principal)?;
-
 		ensure!(
 			!beneficiary_total_debt_with_interest.is_zero(),
 			Error::<T>::CannotRepayZeroBalance
 		);
 
 		let repaid_amount = match total_repay_amount {
-			RepayStrategy::TotalDebt => {
-				// pay interest, from -> market
-				// burn debt token interest from market
-				pay_interest::<T>(
-					borrow_asset,
-					debt_asset,
-					from,
-					&market_account,
-					beneficiary_interest_on_market,
-					keep_alive,
-				)?;
-
-				// release and burn debt token from beneficiary and transfer borrow asset to
-				// market, paid by `from`
-				repay_principal::<T>(
-					borrow_asset,
-					debt_asset,
-					from,
-					&market_account,
-					beneficiary,
-					beneficiary_borrow_asset_principal,
-					keep_alive,
-				)?;
-
-				beneficiary_total_debt_with_interest
-			},
-
+			RepayStrategy::TotalDebt => beneficiary_total_debt_with_interest,
 			// attempt to repay a partial amount of the debt, paying off interest and principal
 			// proportional to how much of each there is.
 			RepayStrategy::PartialAmount(partial_repay_amount) => {
@@ -235,58 +213,23 @@ impl<T: Config> Lending for Pallet<T> {
 					partial_repay_amount <= beneficiary_total_debt_with_interest,
 					Error::<T>::CannotRepayMoreThanTotalDebt
 				);
-
-				// INVARIANT: ArithmeticError::Overflow is used as the error here as
-				// beneficiary_total_debt_with_interest is known to be non-zero at this point
-				// due to the check above (CannotRepayZeroBalance)
-
-				let interest_percentage = FixedU128::checked_from_rational(
-					beneficiary_interest_on_market,
-					beneficiary_total_debt_with_interest,
-				)
-				.ok_or(ArithmeticError::Overflow)?;
-
-				let principal_percentage = FixedU128::checked_from_rational(
-					beneficiary_borrow_asset_principal,
-					beneficiary_total_debt_with_interest,
-				)
-				.ok_or(ArithmeticError::Overflow)?;
-
-				// pay interest, from -> market
-				// burn interest (debt token) from market
-				pay_interest::<T>(
-					borrow_asset,
-					debt_asset,
-					from,
-					&market_account,
-					interest_percentage
-						.checked_mul_int::<u128>(partial_repay_amount.into())
-						.ok_or(ArithmeticError::Overflow)?
-						.into(),
-					keep_alive,
-				)?;
-
-				// release and burn debt token from beneficiary and transfer borrow asset to
-				// market, paid by `from`
-				repay_principal::<T>(
-					borrow_asset,
-					debt_asset,
-					from,
-					&market_account,
-					beneficiary,
-					principal_percentage
-						.checked_mul_int::<u128>(partial_repay_amount.into())
-						.ok_or(ArithmeticError::Overflow)?
-						.into(),
-					keep_alive,
-				)?;
-
-				// the above will short circuit if amount cannot be paid, so if this is reached
-				// then we know `partial_repay_amount` has been repaid
 				partial_repay_amount
 			},
 		};
 
+		// the below will short circuit if amount cannot be paid, so if it succeeds then we know
+		// `repaid_amount` has been repaid
+		crate::repay_borrow::repay_debt::<T>(
+			borrow_asset,
+			debt_asset,
+			from,
+			&market_account,
+			beneficiary,
+			beneficiary_total_debt_with_interest,
+			repaid_amount,
+			keep_alive,
+		)?;
+
 		// if the borrow is completely repaid, remove the borrow information
 		if repaid_amount == beneficiary_total_debt_with_interest {
 			// borrow no longer exists as it has been repaid in entirety, remove the
@@ -305,6 +248,7 @@ impl<T: Config> Lending for Pallet<T> {
 				false, // we do not need to keep the market account alive
 			)?;
 		}
+		Self::update_liquidation_index(market_id, beneficiary)?;
 
 		Ok(repaid_amount)
 	}
@@ -351,7 +295,16 @@ impl<T: Config> Lending for Pallet<T> {
 			total_borrowed_from_market_excluding_interest,
 		)?;
 
-		let delta_time = now.checked_sub(LastBlockTimestamp::<T>::get()).ok_or(
+		// the interest may have been accrued ahead of the other markets, see
+		// `do_update_interest_rate_model`
+		let accrued_at = match InterestAccruedAt::<T>::get(market_id) {
+			Some(accrued_at) => {
+				InterestAccruedAt::<T>::remove(market_id);
+				accrued_at.max(LastBlockTimestamp::<T>::get())
+			},
+			None => LastBlockTimestamp::<T>::get(),
+		};
+		let delta_time = now.checked_sub(accrued_at).ok_or(
 			// REVIEW: INVARIANT: this error should never happen, `now` should always
 			// be `> LastBlockTimestamp`
 			Error::<T>::Underflow,
@@ -473,7 +426,10 @@ impl<T: Config> Lending for Pallet<T> {
 			// afterwards.
 			.unwrap_or_else(CollateralLpAmountOf::<Self>::zero);
 
-		if collateral_balance > T::Balance::zero() {
+		// collateral of a cross-margin account may be held by its other markets
+		if collateral_balance > T::Balance::zero() ||
+			Self::cross_margin_markets_of(market_id, account).is_some()
+		{
 			let borrower = Self::create_borrower_data(market_id, account)?;
 			let balance = borrower
 				.get_borrow_limit()
@@ -1 +1,2 @@
 mod lending;
+mod vault_strategy;
@@ -0,0 +1,65 @@
+use crate::{weights::WeightInfo, *};
+
+use composable_support::math::safe::SafeAdd;
+use composable_traits::{
+	lending::Lending,
+	vault::{Vault, VaultStrategy},
+};
+use frame_support::pallet_prelude::*;
+use sp_runtime::DispatchError;
+
+/// Markets are the strategies of the vault holding their borrow asset: the vault can rebalance
+/// them and the balance they report is the borrow asset they hold plus the principal lent out.
+/// Interest only counts once it is repaid to the market, as realised yield of the vault.
+impl<T: Config> VaultStrategy<T::AccountId, <T::Vault as Vault>::VaultId, T::Balance>
+	for Pallet<T>
+{
+	fn is_strategy(strategy: &T::AccountId) -> bool {
+		MarketOfAccount::<T>::contains_key(strategy)
+	}
+
+	fn rebalance(vault: &<T::Vault as Vault>::VaultId, strategy: &T::AccountId) -> DispatchResult {
+		Self::ensure_no_flash_loan()?;
+		let (_, config) = Self::market_of_strategy(vault, strategy)?;
+		Self::rebalance_market(&config, strategy)?;
+		Ok(())
+	}
+
+	fn balance(
+		vault: &<T::Vault as Vault>::VaultId,
+		strategy: &T::AccountId,
+	) -> Result<T::Balance, DispatchError> {
+		let (market_id, _) = Self::market_of_strategy(vault, strategy)?;
+		let available = <Self as Lending>::total_available_to_be_borrowed(&market_id)?;
+		let borrowed =
+			<Self as Lending>::total_borrowed_from_market_excluding_interest(&market_id)?;
+		Ok(available.safe_add(&borrowed)?)
+	}
+
+	fn rebalance_weight() -> Weight {
+		// market of the account and its config
+		T::DbWeight::get()
+			.reads(2)
+			.saturating_add(<T as Config>::WeightInfo::available_funds())
+			.saturating_add(
+				<T as Config>::WeightInfo::handle_withdrawable()
+					.max(<T as Config>::WeightInfo::handle_depositable())
+					.max(<T as Config>::WeightInfo::handle_must_liquidate()),
+			)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the market whose account is `strategy` and whose borrow asset is held by `vault`.
+	fn market_of_strategy(
+		vault: &<T::Vault as Vault>::VaultId,
+		strategy: &T::AccountId,
+	) -> Result<(MarketIndex, MarketConfigOf<T>), DispatchError> {
+		let market_id =
+			MarketOfAccount::<T>::get(strategy).ok_or(Error::<T>::MarketDoesNotExist)?;
+		let config = Markets::<T>::get(market_id)
+			.filter(|config| config.borrow_asset_vault == *vault)
+			.ok_or(Error::<T>::MarketDoesNotExist)?;
+		Ok((market_id, config))
+	}
+}
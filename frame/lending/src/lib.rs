@@ -42,9 +42,13 @@ pub mod validation;
 pub mod weights;
 pub use crate::weights::WeightInfo;
 
+mod cross_margin;
 pub mod crypto;
+mod flash_loan;
 mod helpers;
 mod impls;
+mod liquidation_index;
+pub mod migrations;
 mod models;
 mod types;
 
@@ -71,19 +75,21 @@ pub mod pallet {
 	pub(crate) use crate::types::{CreateInputOf, MarketAssets};
 	pub use crate::{
 		helpers::swap,
-		types::{MarketId, MarketIndex},
+		types::{LiquidationBucket, MarketId, MarketIndex},
 	};
 	use codec::Codec;
+	use composable_support::validation::TryIntoValidated;
 	use composable_traits::{
 		currency::CurrencyFactory,
 		defi::{DeFiComposableConfig, *},
-		lending::{Lending, MarketConfig, RepayStrategy, UpdateInput},
+		lending::{math::InterestRateModel, Lending, MarketConfig, RepayStrategy, UpdateInput},
 		liquidation::Liquidation,
 		oracle::Oracle,
 		time::Timestamp,
 		vault::StrategicVault,
 	};
 	use frame_support::{
+		dispatch::{GetDispatchInfo, PostDispatchInfo},
 		pallet_prelude::*,
 		traits::{
 			fungible::{Inspect as NativeInspect, Transfer as NativeTransfer},
@@ -99,8 +105,8 @@ pub mod pallet {
 		pallet_prelude::*,
 	};
 	use sp_core::crypto::KeyTypeId;
-	use sp_runtime::KeyTypeId as CryptoKeyTypeId;
-	use sp_std::{fmt::Debug, vec, vec::Vec};
+	use sp_runtime::{traits::Dispatchable, KeyTypeId as CryptoKeyTypeId, Perquintill};
+	use sp_std::{collections::btree_map::BTreeMap, fmt::Debug, vec::Vec};
 
 	/// Simple type alias around [`MarketConfig`] for this pallet.
 	pub(crate) type MarketConfigOf<T> = MarketConfig<
@@ -217,13 +223,34 @@ pub mod pallet {
 		/// The maximum size of batch for liquidation.
 		type MaxLiquidationBatchSize: Get<u32>;
 
+		/// The maximum number of markets merged into the cross-margin account of an account.
+		#[pallet::constant]
+		type MaxCrossMarginMarkets: Get<u32>;
+
+		/// The overarching call type, dispatched with the amount of flash loans.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ IsType<<Self as frame_system::Config>::Call>;
+
+		/// The share of the amount of flash loans paid back to the vault of the market on top of
+		/// it.
+		#[pallet::constant]
+		type FlashLoanFee: Get<Perquintill>;
+
+		/// The origin allowed to change the interest rate model of an open market.
+		type UpdateInterestRateModelOrigin: EnsureOrigin<Self::Origin>;
+
 		/// Convert a weight value into a deductible fee based on the currency type.
 		type WeightToFee: WeightToFeePolynomial<Balance = Self::Balance>
 			+ WeightToFee<Balance = Self::Balance>;
 	}
 
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -249,6 +276,12 @@ pub mod pallet {
 			weight
 		}
 
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::migrate_to_v1::<T>()
+				.saturating_add(crate::migrations::migrate_to_v2::<T>())
+				.saturating_add(crate::migrations::migrate_to_v3::<T>())
+		}
+
 		fn offchain_worker(_block_number: T::BlockNumber) {
 			log::info!("Off-chain worker running");
 			let signer = Signer::<T, <T as Config>::AuthorityId>::all_accounts();
@@ -256,42 +289,45 @@ pub mod pallet {
 				log::warn!("No signer");
 				return
 			}
-			for (market_id, account, _) in DebtIndex::<T>::iter() {
-				//Check that it should liquidate before liquidations
-				let should_be_liquidated =
-					match Self::should_liquidate(&market_id, &account) {
-						Ok(status) => status,
-						Err(error) => {
-							log::error!("Liquidation necessity check failed, market_id: {:?}, account: {:?},
-                                        error: {:?}", market_id, account, error);
-							false
-						},
-					};
-				if !should_be_liquidated {
-					continue
-				}
-				let results = signer.send_signed_transaction(|_account| Call::liquidate {
-					market_id,
-					// Unwrapped since we push only one borrower in the vector
-					borrowers: BoundedVec::<_, T::MaxLiquidationBatchSize>::try_from(vec![
-						account.clone()
-					])
-					.expect("This function never panics"),
-				});
-
-				for (_acc, res) in &results {
-					match res {
-						Ok(()) => log::info!(
-							"Liquidation succeed, market_id: {:?}, account: {:?}",
-							market_id,
-							account
-						),
-						Err(e) => log::error!(
-							"Liquidation failed, market_id: {:?}, account: {:?}, error: {:?}",
+			let batch_size = T::MaxLiquidationBatchSize::get() as usize;
+			if batch_size == 0 {
+				return
+			}
+			for (market_id, market) in Markets::<T>::iter() {
+				let borrowers = match Self::liquidation_candidates(&market_id, &market) {
+					Ok(borrowers) => borrowers,
+					Err(error) => {
+						log::error!(
+							"Liquidation candidates lookup failed, market_id: {:?}, error: {:?}",
 							market_id,
-							account,
-							e
-						),
+							error
+						);
+						continue
+					},
+				};
+				for borrowers in borrowers.chunks(batch_size) {
+					let borrowers =
+						BoundedVec::<_, T::MaxLiquidationBatchSize>::try_from(borrowers.to_vec())
+							.expect("chunks are at most MaxLiquidationBatchSize long; qed");
+					let results = signer.send_signed_transaction(|_account| Call::liquidate {
+						market_id,
+						borrowers: borrowers.clone(),
+					});
+
+					for (_acc, res) in &results {
+						match res {
+							Ok(()) => log::info!(
+								"Liquidation succeed, market_id: {:?}, accounts: {:?}",
+								market_id,
+								borrowers
+							),
+							Err(e) => log::error!(
+								"Liquidation failed, market_id: {:?}, accounts: {:?}, error: {:?}",
+								market_id,
+								borrowers,
+								e
+							),
+						}
 					}
 				}
 			}
@@ -386,6 +422,27 @@ pub mod pallet {
 		// If Vault is unbalanced we can not borrow from it, since
 		// we do not know how many asset it needs to balance.
 		CannotBorrowFromMarketWithUnbalancedVault,
+
+		// Open market liquidation threshold increase would make healthy positions liquidatable.
+		CannotIncreaseLiquidationThresholdOfOpenMarket,
+		/// Attempted to liquidate a position above the liquidation threshold.
+		CannotLiquidateHealthyPosition,
+		/// Attempted to repay more than the close factor of the debt in one liquidation.
+		LiquidationExceedsCloseFactor,
+		/// Borrowers cannot liquidate their own position.
+		CannotLiquidateOwnBorrow,
+
+		/// The market is already part of the cross-margin account.
+		MarketAlreadyInCrossMargin,
+		/// The market is not part of the cross-margin account.
+		MarketNotInCrossMargin,
+		/// The cross-margin account already contains `MaxCrossMarginMarkets` markets.
+		MaxCrossMarginMarketsExceeded,
+		/// Lending operations cannot be dispatched by the calls of a flash loan.
+		FlashLoanInProgress,
+		FlashLoanAmountMustBeGreaterThanZero,
+		/// The amount of a flash loan plus its fee could not be taken back from the borrower.
+		FlashLoanNotRepaid,
 	}
 
 	#[pallet::event]
@@ -417,6 +474,28 @@ pub mod pallet {
 		},
 		/// Event emitted when a liquidation is initiated for a loan.
 		LiquidationInitiated { market_id: MarketIndex, borrowers: Vec<T::AccountId> },
+		/// Event emitted when a liquidator repays part of a borrow in exchange for collateral.
+		BorrowLiquidated {
+			liquidator: T::AccountId,
+			market_id: MarketIndex,
+			borrower: T::AccountId,
+			repaid: T::Balance,
+			collateral_market_id: MarketIndex,
+			collateral_seized: T::Balance,
+		},
+		/// Event emitted when an account adds a market to its cross-margin account.
+		CrossMarginEntered { account: T::AccountId, market_id: MarketIndex },
+		/// Event emitted when an account removes a market from its cross-margin account.
+		CrossMarginExited { account: T::AccountId, market_id: MarketIndex },
+		/// Event emitted when the interest rate model of a market is replaced.
+		InterestRateModelUpdated { market_id: MarketIndex, interest_rate_model: InterestRateModel },
+		/// Event emitted when a flash loan is repaid.
+		FlashLoaned {
+			account: T::AccountId,
+			market_id: MarketIndex,
+			amount: T::Balance,
+			fee: T::Balance,
+		},
 		/// Event emitted to warn that loan may go under collaterlized soon.
 		MayGoUnderCollateralizedSoon { market_id: MarketIndex, account: T::AccountId },
 	}
@@ -498,6 +577,12 @@ pub mod pallet {
 	pub type BorrowIndex<T: Config> =
 		StorageMap<_, Twox64Concat, MarketIndex, ZeroToOneFixedU128, OptionQuery>;
 
+	/// The timestamp up to which the interest of a market was accrued ahead of
+	/// `LastBlockTimestamp`, when its interest rate model was replaced during the block.
+	#[pallet::storage]
+	pub type InterestAccruedAt<T: Config> =
+		StorageMap<_, Twox64Concat, MarketIndex, Timestamp, OptionQuery>;
+
 	/// (Market, Account) -> Collateral
 	#[pallet::storage]
 	pub type AccountCollateral<T: Config> = StorageDoubleMap<
@@ -510,6 +595,80 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The market owning each market account, for the vaults to find the market of a strategy.
+	///
+	/// Account -> Market
+	#[pallet::storage]
+	pub type MarketOfAccount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, MarketIndex, OptionQuery>;
+
+	/// Markets in which the collateral of an account backs its borrows in all of the others.
+	///
+	/// Account -> [Market]
+	#[pallet::storage]
+	pub type CrossMarginMarkets<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<MarketIndex, T::MaxCrossMarginMarkets>,
+		OptionQuery,
+	>;
+
+	/// Cross-margin accounts of each market, the reverse of [`CrossMarginMarkets`].
+	///
+	/// Market -> Account -> ()
+	#[pallet::storage]
+	pub type CrossMarginAccounts<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		MarketIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Borrows of each market by the bucket of their normalized liquidation price, see the
+	/// [`liquidation_index`](crate::liquidation_index) module. Cross-margin accounts are not
+	/// indexed here, but in [`CrossMarginAccounts`].
+	///
+	/// (Market, Bucket) -> Account -> ()
+	#[pallet::storage]
+	pub type LiquidationCandidates<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(MarketIndex, LiquidationBucket),
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// The bucket each borrow is in, in [`LiquidationCandidates`].
+	///
+	/// (Market, Account) -> Bucket
+	#[pallet::storage]
+	pub type LiquidationBucketOf<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		MarketIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		LiquidationBucket,
+		OptionQuery,
+	>;
+
+	/// The number of borrows in each non-empty bucket of a market, in [`LiquidationCandidates`].
+	///
+	/// Market -> Bucket -> Count
+	#[pallet::storage]
+	pub type LiquidationBuckets<T: Config> =
+		StorageMap<_, Twox64Concat, MarketIndex, BTreeMap<LiquidationBucket, u32>, OptionQuery>;
+
+	/// The market of the flash loan whose calls are being dispatched, if any.
+	#[pallet::storage]
+	pub type FlashLoanInProgress<T: Config> = StorageValue<_, MarketIndex, OptionQuery>;
+
 	/// The timestamp of the previous block or defaults to timestamp at genesis.
 	#[pallet::storage]
 	#[allow(clippy::disallowed_types)] // LastBlockTimestamp is set on genesis (see below) so it will always be set.
@@ -589,6 +748,32 @@ pub mod pallet {
 			<Self as Lending>::update_market(who, market_id, input)
 		}
 
+		/// Replace the interest rate model of an open market. The interest of the borrows of the
+		/// market is accrued at the rate of the previous model up to now, the new model is used
+		/// from then on.
+		/// - `origin` : `UpdateInterestRateModelOrigin`.
+		/// - `market_id` : Market index of the market to update.
+		/// - `interest_rate_model` : The new interest rate model, which can be previewed with the
+		///   `simulate_interest_rate_model` runtime API.
+		#[pallet::weight(<T as Config>::WeightInfo::update_interest_rate_model())]
+		#[transactional]
+		pub fn update_interest_rate_model(
+			origin: OriginFor<T>,
+			market_id: MarketIndex,
+			interest_rate_model: InterestRateModel,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateInterestRateModelOrigin::ensure_origin(origin)?;
+			Self::do_update_interest_rate_model(
+				&market_id,
+				interest_rate_model.try_into_validated()?,
+			)?;
+			Self::deposit_event(Event::<T>::InterestRateModelUpdated {
+				market_id,
+				interest_rate_model,
+			});
+			Ok(().into())
+		}
+
 		/// Deposit collateral to market.
 		/// - `origin` : Sender of this extrinsic.
 		/// - `market` : Market index to which collateral will be deposited.
@@ -704,6 +889,100 @@ pub mod pallet {
 			}
 			Ok(().into())
 		}
+
+		/// Repay part of the borrow of an account under the liquidation threshold, receiving
+		/// the collateral it is worth plus the market's liquidation incentive.
+		/// - `origin` : Sender of this extrinsic, the liquidator.
+		/// - `market_id` : Market index from which `borrower` has taken borrow.
+		/// - `borrower` : Account whose borrow is liquidated.
+		/// - `repay_amount` : Debt to repay, at most the close factor of the borrow.
+		#[pallet::weight(<T as Config>::WeightInfo::liquidate_borrow())]
+		#[transactional]
+		pub fn liquidate_borrow(
+			origin: OriginFor<T>,
+			market_id: MarketIndex,
+			borrower: T::AccountId,
+			repay_amount: T::Balance,
+			keep_alive: bool,
+		) -> DispatchResultWithPostInfo {
+			let liquidator = ensure_signed(origin)?;
+			let (collateral_market_id, collateral_seized) = Self::do_liquidate_borrow(
+				&liquidator,
+				&market_id,
+				&borrower,
+				repay_amount,
+				keep_alive,
+			)?;
+			Self::deposit_event(Event::<T>::BorrowLiquidated {
+				liquidator,
+				market_id,
+				borrower,
+				repaid: repay_amount,
+				collateral_market_id,
+				collateral_seized,
+			});
+			Ok(().into())
+		}
+
+		/// Merge the position of the sender in a market into its cross-margin account. The
+		/// collateral of all the markets of the cross-margin account backs the borrows in all of
+		/// them, and they are liquidated together.
+		/// - `origin` : Sender of this extrinsic.
+		/// - `market_id` : Market index of the position.
+		#[pallet::weight(<T as Config>::WeightInfo::enter_cross_margin())]
+		#[transactional]
+		pub fn enter_cross_margin(
+			origin: OriginFor<T>,
+			market_id: MarketIndex,
+		) -> DispatchResultWithPostInfo {
+			let account = ensure_signed(origin)?;
+			Self::do_enter_cross_margin(&account, market_id)?;
+			Self::deposit_event(Event::<T>::CrossMarginEntered { account, market_id });
+			Ok(().into())
+		}
+
+		/// Isolate the position of the sender in a market from its cross-margin account. Both the
+		/// position and the rest of the cross-margin account must stay within their borrow limit.
+		/// - `origin` : Sender of this extrinsic.
+		/// - `market_id` : Market index of the position.
+		#[pallet::weight(<T as Config>::WeightInfo::exit_cross_margin(T::MaxCrossMarginMarkets::get()))]
+		#[transactional]
+		pub fn exit_cross_margin(
+			origin: OriginFor<T>,
+			market_id: MarketIndex,
+		) -> DispatchResultWithPostInfo {
+			let account = ensure_signed(origin)?;
+			Self::do_exit_cross_margin(&account, market_id)?;
+			Self::deposit_event(Event::<T>::CrossMarginExited { account, market_id });
+			Ok(().into())
+		}
+
+		/// Borrow from the liquidity of a market and repay it within the same extrinsic. Once
+		/// `calls` are dispatched on behalf of the sender, the amount plus the flash loan fee is
+		/// taken back from it, and the whole extrinsic fails if it cannot be.
+		/// - `origin` : Sender of this extrinsic, the borrower.
+		/// - `market_id` : Market index to borrow the borrow asset of.
+		/// - `amount` : Amount to borrow.
+		/// - `calls` : Calls using the amount borrowed. They cannot use the lending pallet.
+		#[pallet::weight({
+			let dispatch_weight = calls
+				.iter()
+				.map(|call| call.get_dispatch_info().weight)
+				.fold(0, |total: Weight, weight| total.saturating_add(weight));
+			<T as Config>::WeightInfo::flash_loan().saturating_add(dispatch_weight)
+		})]
+		#[transactional]
+		pub fn flash_loan(
+			origin: OriginFor<T>,
+			market_id: MarketIndex,
+			amount: T::Balance,
+			calls: Vec<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			let account = ensure_signed(origin)?;
+			let fee = Self::do_flash_loan(&account, &market_id, amount, calls)?;
+			Self::deposit_event(Event::<T>::FlashLoaned { account, market_id, amount, fee });
+			Ok(().into())
+		}
 	}
 
 	impl<T: Config> DeFiEngine for Pallet<T> {
@@ -0,0 +1,184 @@
+//! Index of the borrows of each market by the price at which they can be liquidated, so that the
+//! offchain worker only looks at the borrows that crossed the liquidation threshold.
+//!
+//! A borrow can be liquidated once the value of its collateral falls under its debt times the
+//! `liquidation_threshold` of the market. In raw amounts of the assets:
+//!
+//! ```text
+//! collateral * collateral_price < liquidation_threshold * principal * borrow_index / debt_index * borrow_price
+//! ```
+//!
+//! This splits into a side only changed by the borrower and a side shared by the whole market:
+//!
+//! ```text
+//! principal / (debt_index * collateral) > collateral_price / (borrow_price * liquidation_threshold * borrow_index)
+//! ```
+//!
+//! The left side, the normalized liquidation price of the borrow, is indexed whenever the borrow or
+//! its collateral changes. Oracle price moves, interest accrual and market updates only move the
+//! right side, the liquidation price threshold of the market, and leave the index untouched.
+//!
+//! Borrows are bucketed by the magnitude of their normalized liquidation price, with a precision of
+//! 1/16th of it. All of the borrows that can be liquidated are in the bucket of the threshold or
+//! above. Borrows of cross-margin accounts are valued together with the other markets of the
+//! account, so they are not indexed by price: the cross-margin accounts of each market are checked
+//! one by one.
+
+use crate::{types::LiquidationBucket, *};
+
+use composable_support::math::safe::SafeDiv;
+use composable_traits::vault::Vault;
+use frame_support::{pallet_prelude::*, traits::fungibles::InspectHold};
+use sp_runtime::{traits::Zero, ArithmeticError, DispatchError, FixedPointNumber, FixedU128};
+use sp_std::vec::Vec;
+
+/// Bits of the normalized liquidation price kept in its bucket, after the most significant one.
+const BUCKET_MANTISSA_BITS: u32 = 4;
+
+/// Amount of the assets priced to get the ratio of their prices. Large enough to not lose
+/// precision on assets with few decimals.
+const PRICE_REFERENCE_AMOUNT: u64 = 1_000_000_000_000_000_000;
+
+/// The bucket of a normalized liquidation price: the position of its most significant bit followed
+/// by the [`BUCKET_MANTISSA_BITS`] next ones. Buckets are ordered like the prices they hold.
+pub(crate) fn liquidation_bucket(price: FixedU128) -> LiquidationBucket {
+	let price = price.into_inner();
+	if price.is_zero() {
+		return 0
+	}
+	let exponent = u128::BITS - price.leading_zeros();
+	let mantissa = if exponent > BUCKET_MANTISSA_BITS {
+		price >> (exponent - BUCKET_MANTISSA_BITS - 1)
+	} else {
+		price << (BUCKET_MANTISSA_BITS + 1 - exponent)
+	};
+	let mantissa = (mantissa & ((1 << BUCKET_MANTISSA_BITS) - 1)) as LiquidationBucket;
+	(exponent << BUCKET_MANTISSA_BITS) | mantissa
+}
+
+impl<T: Config> Pallet<T> {
+	/// Moves the borrow of `account` in `market_id` to the bucket of its current normalized
+	/// liquidation price. Must be called after any change to the borrow or its collateral.
+	pub(crate) fn update_liquidation_index(
+		market_id: &MarketIndex,
+		account: &T::AccountId,
+	) -> Result<(), DispatchError> {
+		let bucket = if Self::cross_margin_markets_of(market_id, account).is_some() {
+			None
+		} else {
+			Self::normalized_liquidation_price(market_id, account)?.map(liquidation_bucket)
+		};
+		let previous_bucket = LiquidationBucketOf::<T>::get(market_id, account);
+		if bucket == previous_bucket {
+			return Ok(())
+		}
+
+		if let Some(previous_bucket) = previous_bucket {
+			LiquidationBucketOf::<T>::remove(market_id, account);
+			LiquidationCandidates::<T>::remove((market_id, previous_bucket), account);
+			LiquidationBuckets::<T>::mutate_exists(market_id, |buckets| {
+				if let Some(buckets) = buckets {
+					if let Some(borrows) = buckets.get_mut(&previous_bucket) {
+						*borrows = borrows.saturating_sub(1);
+						if borrows.is_zero() {
+							buckets.remove(&previous_bucket);
+						}
+					}
+				}
+				if buckets.as_ref().map_or(false, |buckets| buckets.is_empty()) {
+					*buckets = None;
+				}
+			});
+		}
+
+		if let Some(bucket) = bucket {
+			LiquidationBucketOf::<T>::insert(market_id, account, bucket);
+			LiquidationCandidates::<T>::insert((market_id, bucket), account, ());
+			LiquidationBuckets::<T>::mutate(market_id, |buckets| {
+				let borrows =
+					buckets.get_or_insert_with(Default::default).entry(bucket).or_default();
+				*borrows = borrows.saturating_add(1);
+			});
+		}
+		Ok(())
+	}
+
+	/// `principal / (debt_index * collateral)` of the borrow of `account` in `market_id`, or
+	/// `None` if there is no borrow.
+	///
+	/// Borrows without collateral, or too little of it to be represented, get the highest price so
+	/// that they are always looked at.
+	pub(crate) fn normalized_liquidation_price(
+		market_id: &MarketIndex,
+		account: &T::AccountId,
+	) -> Result<Option<FixedU128>, DispatchError> {
+		let debt_index = match DebtIndex::<T>::get(market_id, account) {
+			Some(debt_index) => debt_index,
+			None => return Ok(None),
+		};
+		let debt_token =
+			DebtTokenForMarket::<T>::get(market_id).ok_or(Error::<T>::MarketDoesNotExist)?;
+		let principal = <T as Config>::MultiCurrency::balance_on_hold(debt_token, account);
+		if principal.is_zero() {
+			return Ok(None)
+		}
+		let collateral =
+			AccountCollateral::<T>::get(market_id, account).unwrap_or_else(T::Balance::zero);
+		let price = FixedU128::checked_from_rational(principal.into(), collateral.into())
+			.and_then(|price| price.checked_div(&debt_index))
+			.unwrap_or_else(FixedU128::max_value);
+		Ok(Some(price))
+	}
+
+	/// `collateral_price / (borrow_price * liquidation_threshold * borrow_index)` of `market_id`.
+	/// The borrows with a normalized liquidation price above it can be liquidated.
+	pub(crate) fn liquidation_price_threshold(
+		market_id: &MarketIndex,
+		market: &MarketConfigOf<T>,
+	) -> Result<FixedU128, DispatchError> {
+		let borrow_asset = T::Vault::asset_id(&market.borrow_asset_vault)?;
+		let reference_amount = T::Balance::from(PRICE_REFERENCE_AMOUNT);
+		let collateral_price = Self::get_price(market.collateral_asset, reference_amount)?;
+		let borrow_price = Self::get_price(borrow_asset, reference_amount)?;
+		let borrow_index =
+			BorrowIndex::<T>::get(market_id).ok_or(Error::<T>::MarketDoesNotExist)?;
+		let threshold =
+			FixedU128::checked_from_rational(collateral_price.into(), borrow_price.into())
+				.ok_or(ArithmeticError::Overflow)?
+				.safe_div(&market.liquidation_threshold)?
+				.safe_div(&borrow_index)?;
+		Ok(threshold)
+	}
+
+	/// Borrows of `market_id` that can be liquidated: the indexed ones in the buckets at or above
+	/// the liquidation price threshold of the market, and the ones of cross-margin accounts.
+	pub(crate) fn liquidation_candidates(
+		market_id: &MarketIndex,
+		market: &MarketConfigOf<T>,
+	) -> Result<Vec<T::AccountId>, DispatchError> {
+		let threshold_bucket =
+			liquidation_bucket(Self::liquidation_price_threshold(market_id, market)?);
+		let buckets = LiquidationBuckets::<T>::get(market_id).unwrap_or_default();
+		let indexed_borrowers = buckets.range(threshold_bucket..).flat_map(|(bucket, _)| {
+			LiquidationCandidates::<T>::iter_key_prefix((market_id, bucket))
+		});
+		let cross_margin_borrowers = CrossMarginAccounts::<T>::iter_key_prefix(market_id)
+			.filter(|account| DebtIndex::<T>::contains_key(market_id, account));
+
+		Ok(indexed_borrowers
+			.chain(cross_margin_borrowers)
+			.filter(|account| match Self::should_liquidate(market_id, account) {
+				Ok(should_liquidate) => should_liquidate,
+				Err(error) => {
+					log::error!(
+						"Liquidation necessity check failed, market_id: {:?}, account: {:?}, error: {:?}",
+						market_id,
+						account,
+						error
+					);
+					false
+				},
+			})
+			.collect())
+	}
+}
@@ -0,0 +1,99 @@
+use crate::{Config, DebtIndex, MarketConfigOf, MarketOfAccount, Markets, Pallet};
+use codec::{Decode, Encode};
+use composable_traits::{
+	defi::MoreThanOneFixedU128,
+	lending::{math::InterestRateModel, Lending},
+};
+use frame_support::{
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::{Percent, Perquintill};
+use sp_std::vec::Vec;
+
+/// `MarketConfig` before partial liquidations were introduced.
+#[derive(Encode, Decode)]
+pub struct MarketConfigV0<VaultId, AssetId, AccountId, LiquidationStrategyId, BlockNumber> {
+	pub manager: AccountId,
+	pub borrow_asset_vault: VaultId,
+	pub collateral_asset: AssetId,
+	pub max_price_age: BlockNumber,
+	pub collateral_factor: MoreThanOneFixedU128,
+	pub interest_rate_model: InterestRateModel,
+	pub under_collateralized_warn_percent: Percent,
+	pub liquidators: Vec<LiquidationStrategyId>,
+}
+
+/// Keeps the markets liquidating whole positions at the collateral factor, without incentive.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= 1 {
+		return T::DbWeight::get().reads(1)
+	}
+	let mut translated: Weight = 0;
+	Markets::<T>::translate::<
+		MarketConfigV0<
+			T::VaultId,
+			T::MayBeAssetId,
+			T::AccountId,
+			T::LiquidationStrategyId,
+			T::BlockNumber,
+		>,
+		_,
+	>(|_, market| {
+		translated += 1;
+		Some(MarketConfigOf::<T> {
+			manager: market.manager,
+			borrow_asset_vault: market.borrow_asset_vault,
+			collateral_asset: market.collateral_asset,
+			max_price_age: market.max_price_age,
+			collateral_factor: market.collateral_factor,
+			interest_rate_model: market.interest_rate_model,
+			under_collateralized_warn_percent: market.under_collateralized_warn_percent,
+			liquidators: market.liquidators,
+			liquidation_threshold: market.collateral_factor,
+			close_factor: Perquintill::one(),
+			liquidation_incentive: Perquintill::zero(),
+		})
+	});
+	StorageVersion::new(1).put::<Pallet<T>>();
+	T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+}
+
+/// Indexes the existing borrows by their liquidation price, for the offchain worker to find the
+/// ones to liquidate.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= 2 {
+		return T::DbWeight::get().reads(1)
+	}
+	let mut indexed: Weight = 0;
+	for (market_id, account, _) in DebtIndex::<T>::iter() {
+		indexed += 1;
+		if let Err(error) = Pallet::<T>::update_liquidation_index(&market_id, &account) {
+			log::error!(
+				"Failed to index borrow for liquidation, market_id: {:?}, account: {:?}, error: {:?}",
+				market_id,
+				account,
+				error
+			);
+		}
+	}
+	StorageVersion::new(2).put::<Pallet<T>>();
+	// debt index, debt token, principal, collateral, cross-margin markets and previous bucket read,
+	// bucket, candidate and bucket count written per borrow
+	T::DbWeight::get().reads_writes(6 * indexed + 1, 3 * indexed + 1)
+}
+
+/// Indexes the accounts of the existing markets, for the vaults to find the market of a strategy
+/// without iterating over all of them.
+pub fn migrate_to_v3<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= 3 {
+		return T::DbWeight::get().reads(1)
+	}
+	let mut indexed: Weight = 0;
+	for market_id in Markets::<T>::iter_keys() {
+		indexed += 1;
+		MarketOfAccount::<T>::insert(Pallet::<T>::account_id(&market_id), market_id);
+	}
+	StorageVersion::new(3).put::<Pallet<T>>();
+	T::DbWeight::get().reads_writes(indexed + 1, indexed + 1)
+}
@@ -27,7 +27,7 @@ use sp_runtime::{
 	traits::{
 		BlakeTwo256, ConvertInto, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify,
 	},
-	DispatchError, Perbill,
+	DispatchError, Perbill, Perquintill,
 };
 use xcm::latest::SendXcm;
 
@@ -161,6 +161,7 @@ parameter_types! {
 	pub const MinimumWithdrawal: Balance = 0;
 	pub const VaultPalletId: PalletId = PalletId(*b"cubic___");
 	pub const TombstoneDuration: u64 = 42;
+	pub const RebalanceInterval: u64 = 10;
 }
 
 impl pallet_vault::Config for Runtime {
@@ -181,6 +182,9 @@ impl pallet_vault::Config for Runtime {
 	type VaultId = VaultId;
 	type TombstoneDuration = TombstoneDuration;
 	type WeightInfo = ();
+	type Strategy = Lending;
+	type StrategyApprovalOrigin = EnsureRoot<AccountId>;
+	type RebalanceInterval = RebalanceInterval;
 }
 
 parameter_type_with_key! {
@@ -262,6 +266,9 @@ impl LocalAssets<CurrencyId> for Decimals {
 	}
 }
 
+pub type NoPools =
+	composable_tests_helpers::test::dex::NoPools<CurrencyId, Balance, AccountId, u128>;
+
 impl pallet_oracle::Config for Runtime {
 	type Event = Event;
 	type Currency = Assets;
@@ -286,6 +293,9 @@ impl pallet_oracle::Config for Runtime {
 	type Moment = Moment;
 	type Time = Timestamp;
 	type PalletId = OraclePalletId;
+	type PoolId = u128;
+	type PoolTwap = NoPools;
+	type MaxFallbacks = ConstU32<0>;
 }
 
 impl DeFiComposableConfig for Runtime {
@@ -348,6 +358,11 @@ impl pallet_dutch_auction::Config for Runtime {
 	type XcmSender = XcmFake;
 }
 
+parameter_types! {
+	pub ParachainId: ParaId = ParaId::from(2000);
+	pub const XcmSellRequestWeight: u64 = 1_000_000_000;
+}
+
 impl pallet_liquidations::Config for Runtime {
 	type Event = Event;
 	type UnixTime = Timestamp;
@@ -358,7 +373,14 @@ impl pallet_liquidations::Config for Runtime {
 	type WeightInfo = pallet_liquidations::weights::SubstrateWeight<Self>;
 	type CanModifyStrategies = EnsureRoot<Self::AccountId>;
 	type XcmSender = XcmFake;
+	type XcmOrigin = XcmFake;
 	type MaxLiquidationStrategiesAmount = ConstU32<3>;
+	type Amm = NoPools;
+	type PoolId = u128;
+	type Oracle = Oracle;
+	type MultiCurrency = Assets;
+	type ParachainId = ParachainId;
+	type XcmSellRequestWeight = XcmSellRequestWeight;
 }
 
 pub type Extrinsic = TestXt<Call, ()>;
@@ -396,6 +418,8 @@ parameter_types! {
 	pub LendingPalletId: PalletId = PalletId(*b"liqiudat");
 	pub OracleMarketCreationStake: Balance = NORMALIZED::ONE;
 	pub const MaxLiquidationBatchSize: u32 = 5;
+	pub const MaxCrossMarginMarkets: u32 = 5;
+	pub const FlashLoanFee: Perquintill = Perquintill::from_percent(1);
 }
 
 parameter_types! {
@@ -432,6 +456,10 @@ impl pallet_lending::Config for Runtime {
 	type PalletId = LendingPalletId;
 	type OracleMarketCreationStake = OracleMarketCreationStake;
 	type MaxLiquidationBatchSize = MaxLiquidationBatchSize;
+	type MaxCrossMarginMarkets = MaxCrossMarginMarkets;
+	type Call = Call;
+	type FlashLoanFee = FlashLoanFee;
+	type UpdateInterestRateModelOrigin = EnsureRoot<AccountId>;
 
 	type WeightToFee = WeightToFee;
 }
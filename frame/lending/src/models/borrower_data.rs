@@ -8,12 +8,13 @@ use composable_traits::{
 	lending::CollateralRatio,
 };
 use sp_runtime::{
-	traits::{Saturating, Zero},
+	traits::{One, Saturating, Zero},
 	ArithmeticError, FixedPointNumber, FixedU128, Percent,
 };
 
 /// Information about a borrower, including the total values of the collateral and borrow assets,
-/// and the `collateral_factor` and `under_collateralized_warn_percent` of the market.
+/// and the `collateral_factor`, `liquidation_threshold` and `under_collateralized_warn_percent` of
+/// the market.
 #[derive(Debug)]
 pub struct BorrowerData {
 	/// The value of the total amount of collateral asset that the borrower has deposited into the
@@ -27,6 +28,12 @@ pub struct BorrowerData {
 	///
 	/// [`MarketConfig::collateral_factor`]: composable_traits::lending::MarketConfig
 	pub collateral_factor: Validated<FixedU128, MoreThanOne>,
+	/// The collateral ratio under which the borrower can be liquidated.
+	///
+	/// See [`MarketConfig::liquidation_threshold`] for more information.
+	///
+	/// [`MarketConfig::liquidation_threshold`]: composable_traits::lending::MarketConfig
+	pub liquidation_threshold: Validated<FixedU128, MoreThanOne>,
 	pub under_collateralized_warn_percent: Percent,
 }
 
@@ -36,6 +43,7 @@ impl BorrowerData {
 		collateral_balance_total_value: T,
 		borrow_balance_total_value: T,
 		collateral_factor: Validated<FixedU128, MoreThanOne>,
+		liquidation_threshold: Validated<FixedU128, MoreThanOne>,
 		under_collateralized_warn_percent: Percent,
 	) -> Self {
 		Self {
@@ -46,6 +54,7 @@ impl BorrowerData {
 				borrow_balance_total_value.into(),
 			),
 			collateral_factor,
+			liquidation_threshold,
 			under_collateralized_warn_percent,
 		}
 	}
@@ -76,15 +85,40 @@ impl BorrowerData {
 		self.collateral_balance_total_value.safe_div(&self.collateral_factor)
 	}
 
-	/// Determines whether the loan should trigger a liquidation, based on the
-	/// [`current_collateral_ratio`].
+	/// Determines whether the loan should trigger a liquidation, i.e. whether the
+	/// [`health_factor`] went under one.
 	///
-	/// [`current_collateral_ratio`]: BorrowerData::current_collateral_ratio
+	/// [`health_factor`]: BorrowerData::health_factor
 	#[inline(always)]
 	pub fn should_liquidate(&self) -> Result<bool, ArithmeticError> {
+		match self.health_factor()? {
+			Some(health_factor) => Ok(health_factor < FixedU128::one()),
+			// No liquidation necessary if the borrower's borrow asset balance has no value
+			None => Ok(false),
+		}
+	}
+
+	/// The [`current_collateral_ratio`] relative to the [`liquidation_threshold`]. The loan can
+	/// be liquidated once it is under one.
+	///
+	/// Returns `None` if the borrower's borrow asset balance has no value.
+	///
+	/// [`current_collateral_ratio`]: BorrowerData::current_collateral_ratio
+	/// [`liquidation_threshold`]: BorrowerData::liquidation_threshold
+	#[inline(always)]
+	pub fn health_factor(&self) -> Result<Option<FixedU128>, ArithmeticError> {
+		match self.current_collateral_ratio()? {
+			CollateralRatio::Ratio(ratio) => ratio.safe_div(&self.liquidation_threshold).map(Some),
+			CollateralRatio::NoBorrowValue => Ok(None),
+		}
+	}
+
+	/// Determines whether the loan is over the borrow limit set by the `collateral_factor`. Such
+	/// loans cannot grow, but are only liquidated under the `liquidation_threshold`.
+	#[inline(always)]
+	pub fn exceeds_borrow_limit(&self) -> Result<bool, ArithmeticError> {
 		match self.current_collateral_ratio()? {
 			CollateralRatio::Ratio(ratio) => Ok(ratio < *self.collateral_factor),
-			// No liquidation necessary if the borrower's borrow asset balance has no value
 			CollateralRatio::NoBorrowValue => Ok(false),
 		}
 	}
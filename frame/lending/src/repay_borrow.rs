@@ -1,6 +1,7 @@
+use composable_support::math::safe::SafeSub;
 use composable_traits::defi::DeFiComposableConfig;
 use frame_support::traits::fungibles::{Inspect, Mutate, MutateHold, Transfer};
-use sp_runtime::DispatchError;
+use sp_runtime::{ArithmeticError, DispatchError, FixedPointNumber, FixedU128};
 
 use crate::Config;
 
@@ -119,3 +120,76 @@ pub(crate) fn pay_interest<'a, T: Config>(
 
 	Ok(())
 }
+
+/// Repay `amount_to_repay` of `beneficiary_account`'s total debt.
+///
+/// Repaying the total debt pays off all of the interest and principal, otherwise interest and
+/// principal are paid off proportional to how much of each there is.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn repay_debt<'a, T: Config>(
+	borrow_asset: <T as DeFiComposableConfig>::MayBeAssetId,
+	debt_asset: <T as DeFiComposableConfig>::MayBeAssetId,
+	payer_account: &'a T::AccountId,
+	market_account: &'a T::AccountId,
+	beneficiary_account: &'a T::AccountId,
+
+	// Principal + interest owed by `beneficiary_account`.
+	//
+	// NOTE: This is assumed to be non-zero.
+	total_debt_with_interest: <T as DeFiComposableConfig>::Balance,
+
+	// NOTE: This is assumed to be `<=` `total_debt_with_interest`.
+	amount_to_repay: <T as DeFiComposableConfig>::Balance,
+	keep_alive: bool,
+) -> Result<(), DispatchError> {
+	// initial borrow amount
+	let principal = <T as Config>::MultiCurrency::balance(debt_asset, beneficiary_account);
+	// interest accrued
+	let interest = total_debt_with_interest.safe_sub(&principal)?;
+
+	let (interest_to_repay, principal_to_repay) = if amount_to_repay == total_debt_with_interest {
+		(interest, principal)
+	} else {
+		// INVARIANT: ArithmeticError::Overflow is used as the error here as
+		// total_debt_with_interest is known to be non-zero
+		let interest_percentage =
+			FixedU128::checked_from_rational(interest, total_debt_with_interest)
+				.ok_or(ArithmeticError::Overflow)?;
+		let principal_percentage =
+			FixedU128::checked_from_rational(principal, total_debt_with_interest)
+				.ok_or(ArithmeticError::Overflow)?;
+		(
+			interest_percentage
+				.checked_mul_int::<u128>(amount_to_repay.into())
+				.ok_or(ArithmeticError::Overflow)?
+				.into(),
+			principal_percentage
+				.checked_mul_int::<u128>(amount_to_repay.into())
+				.ok_or(ArithmeticError::Overflow)?
+				.into(),
+		)
+	};
+
+	// pay interest, payer -> market
+	// burn interest (debt token) from market
+	pay_interest::<T>(
+		borrow_asset,
+		debt_asset,
+		payer_account,
+		market_account,
+		interest_to_repay,
+		keep_alive,
+	)?;
+
+	// release and burn debt token from beneficiary and transfer borrow asset to
+	// market, paid by `payer_account`
+	repay_principal::<T>(
+		borrow_asset,
+		debt_asset,
+		payer_account,
+		market_account,
+		beneficiary_account,
+		principal_to_repay,
+		keep_alive,
+	)
+}
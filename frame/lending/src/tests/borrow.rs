@@ -93,6 +93,10 @@ fn test_borrow_math() {
 	let borrower = BorrowerData::new(
 		100_u128,
 		0,
+		MoreThanOneFixedU128::checked_from_rational(200_u8, 100_u8)
+			.unwrap()
+			.try_into_validated()
+			.unwrap(),
 		MoreThanOneFixedU128::checked_from_rational(200_u8, 100_u8)
 			.unwrap()
 			.try_into_validated()
@@ -397,6 +401,10 @@ proptest! {
 		let borrower = BorrowerData::new(
 			collateral_balance * collateral_price,
 			borrower_balance_with_interest * borrow_price,
+			MoreThanOneFixedU128::checked_from_rational(101_u8, 100_u8)
+				.unwrap()
+				.try_into_validated()
+				.unwrap(),
 			MoreThanOneFixedU128::checked_from_rational(101_u8, 100_u8)
 				.unwrap()
 				.try_into_validated()
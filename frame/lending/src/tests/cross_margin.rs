@@ -0,0 +1,145 @@
+use super::prelude::*;
+use crate::{
+	currency::Currency,
+	tests::{borrow, create_market_for_liquidation_test},
+	MarketIndex,
+};
+
+#[allow(clippy::upper_case_acronyms)]
+type KSM = Currency<3000, 12>;
+
+/// Creates a market with BTC as collateral and a market with KSM as collateral, both lending USDT
+/// with a collateral factor of two. BTC is priced 50_000 USDT and KSM 100 USDT.
+fn create_cross_margin_markets() -> (MarketIndex, MarketIndex) {
+	let manager = *ALICE;
+	let lender = *CHARLIE;
+	let (btc_market, btc_vault) = create_market_for_liquidation_test::<Runtime>(manager);
+	let (ksm_market, ksm_vault) = create_market::<Runtime, 100>(
+		USDT::instance(),
+		KSM::instance(),
+		manager,
+		DEFAULT_MARKET_VAULT_RESERVE,
+		MoreThanOneFixedU128::saturating_from_integer(2_u128),
+	);
+	for vault_id in [btc_vault, ksm_vault] {
+		let vault_value = USDT::units(100_000_000);
+		assert_ok!(Tokens::mint_into(USDT::ID, &lender, vault_value));
+		assert_ok!(Vault::deposit(Origin::signed(lender), vault_id, vault_value));
+	}
+	test::block::process_and_progress_blocks::<Lending, Runtime>(1);
+	(btc_market, ksm_market)
+}
+
+#[test]
+fn can_enter_and_exit_cross_margin() {
+	new_test_ext().execute_with(|| {
+		let (btc_market, ksm_market) = create_cross_margin_markets();
+
+		for market_id in [btc_market, ksm_market] {
+			assert_extrinsic_event::<Runtime>(
+				Lending::enter_cross_margin(Origin::signed(*BOB), market_id),
+				Event::Lending(crate::Event::CrossMarginEntered { account: *BOB, market_id }),
+			);
+		}
+		assert_eq!(
+			crate::CrossMarginMarkets::<Runtime>::get(*BOB).unwrap().into_inner(),
+			vec![btc_market, ksm_market]
+		);
+		assert!(crate::CrossMarginAccounts::<Runtime>::contains_key(btc_market, *BOB));
+		assert!(crate::CrossMarginAccounts::<Runtime>::contains_key(ksm_market, *BOB));
+		assert_noop!(
+			Lending::enter_cross_margin(Origin::signed(*BOB), btc_market),
+			Error::<Runtime>::MarketAlreadyInCrossMargin
+		);
+
+		for market_id in [btc_market, ksm_market] {
+			assert_extrinsic_event::<Runtime>(
+				Lending::exit_cross_margin(Origin::signed(*BOB), market_id),
+				Event::Lending(crate::Event::CrossMarginExited { account: *BOB, market_id }),
+			);
+		}
+		assert!(!crate::CrossMarginMarkets::<Runtime>::contains_key(*BOB));
+		assert_eq!(crate::CrossMarginAccounts::<Runtime>::iter().count(), 0);
+		assert_noop!(
+			Lending::exit_cross_margin(Origin::signed(*BOB), btc_market),
+			Error::<Runtime>::MarketNotInCrossMargin
+		);
+	})
+}
+
+#[test]
+fn cross_margin_collateral_backs_borrows_in_other_markets() {
+	new_test_ext().execute_with(|| {
+		let (btc_market, ksm_market) = create_cross_margin_markets();
+		mint_and_deposit_collateral::<Runtime>(*BOB, BTC::units(1), btc_market, BTC::ID);
+
+		// isolated, the KSM market has no collateral to borrow against
+		assert_noop!(
+			Lending::borrow(Origin::signed(*BOB), ksm_market, USDT::units(10_000)),
+			Error::<Runtime>::NotEnoughCollateralToBorrow
+		);
+
+		assert_ok!(Lending::enter_cross_margin(Origin::signed(*BOB), btc_market));
+		assert_ok!(Lending::enter_cross_margin(Origin::signed(*BOB), ksm_market));
+		assert_eq!(Lending::get_borrow_limit(&ksm_market, &BOB), Ok(USDT::units(25_000)));
+		borrow::<Runtime>(*BOB, ksm_market, USDT::units(10_000));
+		assert_eq!(Lending::get_borrow_limit(&btc_market, &BOB), Ok(USDT::units(15_000)));
+
+		// the BTC still backs the borrow in the KSM market
+		assert_noop!(
+			Lending::withdraw_collateral(Origin::signed(*BOB), btc_market, BTC::units(1)),
+			Error::<Runtime>::WouldGoUnderCollateralized
+		);
+		assert_noop!(
+			Lending::exit_cross_margin(Origin::signed(*BOB), btc_market),
+			Error::<Runtime>::WouldGoUnderCollateralized
+		);
+	})
+}
+
+#[test]
+fn cross_margin_liquidation_seizes_most_valuable_collateral() {
+	new_test_ext().execute_with(|| {
+		let (btc_market, ksm_market) = create_cross_margin_markets();
+		mint_and_deposit_collateral::<Runtime>(*BOB, BTC::units(1), btc_market, BTC::ID);
+		mint_and_deposit_collateral::<Runtime>(*BOB, KSM::units(10), ksm_market, KSM::ID);
+		assert_ok!(Lending::enter_cross_margin(Origin::signed(*BOB), btc_market));
+		assert_ok!(Lending::enter_cross_margin(Origin::signed(*BOB), ksm_market));
+		borrow::<Runtime>(*BOB, ksm_market, USDT::units(20_000));
+		assert_eq!(Lending::should_liquidate(&ksm_market, &BOB), Ok(false));
+
+		// 28_000 / 2 + 1_000 / 2 = 14_500 USDT of borrow capacity left for 20_000 USDT of debt
+		set_price(BTC::ID, NORMALIZED::units(28_000));
+		assert_eq!(Lending::should_liquidate(&ksm_market, &BOB), Ok(true));
+
+		let liquidator = *CHARLIE;
+		let repay_amount = USDT::units(5_000);
+		assert_ok!(Tokens::mint_into(USDT::ID, &liquidator, repay_amount));
+		assert_ok!(Lending::liquidate_borrow(
+			Origin::signed(liquidator),
+			ksm_market,
+			*BOB,
+			repay_amount,
+			false
+		));
+
+		let collateral_seized = Tokens::balance(BTC::ID, &liquidator);
+		System::assert_last_event(Event::Lending(crate::Event::BorrowLiquidated {
+			liquidator,
+			market_id: ksm_market,
+			borrower: *BOB,
+			repaid: repay_amount,
+			collateral_market_id: btc_market,
+			collateral_seized,
+		}));
+		assert!(collateral_seized > 0);
+		assert_eq!(
+			crate::AccountCollateral::<Runtime>::get(btc_market, *BOB),
+			Some(BTC::units(1) - collateral_seized)
+		);
+		assert_eq!(
+			crate::AccountCollateral::<Runtime>::get(ksm_market, *BOB),
+			Some(KSM::units(10))
+		);
+	})
+}
@@ -0,0 +1,111 @@
+use super::prelude::*;
+use crate::{tests::create_market_for_liquidation_test, MarketIndex};
+use composable_traits::vault::Vault as VaultTrait;
+
+/// Creates a market lending USDT with 100_000_000 USDT in its vault, part of which is moved to the
+/// market.
+fn create_market_with_liquidity() -> (MarketIndex, VaultId) {
+	let (market_id, vault_id) = create_market_for_liquidation_test::<Runtime>(*ALICE);
+	let vault_value = USDT::units(100_000_000);
+	assert_ok!(Tokens::mint_into(USDT::ID, &CHARLIE, vault_value));
+	assert_ok!(Vault::deposit(Origin::signed(*CHARLIE), vault_id, vault_value));
+	test::block::process_and_progress_blocks::<Lending, Runtime>(1);
+	(market_id, vault_id)
+}
+
+#[test]
+fn flash_loan_fee_goes_to_the_vault() {
+	new_test_ext().execute_with(|| {
+		let (market_id, vault_id) = create_market_with_liquidity();
+		let market_account = Lending::account_id(&market_id);
+		let vault_account = <Vault as VaultTrait>::account_id(&vault_id);
+		let market_balance = Tokens::balance(USDT::ID, &market_account);
+		let vault_balance = Tokens::balance(USDT::ID, &vault_account);
+		let assets_under_management = || {
+			Tokens::balance(USDT::ID, &vault_account) +
+				pallet_vault::CapitalStructure::<Runtime>::get(vault_id, market_account).balance
+		};
+		let vault_assets = assets_under_management();
+		// the market holds liquidity of the vault
+		assert!(
+			pallet_vault::CapitalStructure::<Runtime>::get(vault_id, market_account).balance > 0
+		);
+
+		let amount = USDT::units(1_000_000);
+		let fee = USDT::units(10_000);
+		assert_ok!(Tokens::mint_into(USDT::ID, &BOB, fee));
+		assert_extrinsic_event::<Runtime>(
+			Lending::flash_loan(
+				Origin::signed(*BOB),
+				market_id,
+				amount,
+				vec![Call::System(frame_system::Call::remark { remark: vec![] })],
+			),
+			Event::Lending(crate::Event::FlashLoaned { account: *BOB, market_id, amount, fee }),
+		);
+
+		assert_eq!(Tokens::balance(USDT::ID, &BOB), 0);
+		assert_eq!(Tokens::balance(USDT::ID, &market_account), market_balance);
+		assert_eq!(Tokens::balance(USDT::ID, &vault_account), vault_balance + fee);
+		// the fee is yield of the vault, not principal returned by the market
+		assert_eq!(assets_under_management(), vault_assets + fee);
+		assert!(!crate::FlashLoanInProgress::<Runtime>::exists());
+	})
+}
+
+#[test]
+fn flash_loan_must_be_repaid_with_fee() {
+	new_test_ext().execute_with(|| {
+		let (market_id, _) = create_market_with_liquidity();
+		let amount = USDT::units(1_000_000);
+
+		assert_noop!(
+			Lending::flash_loan(Origin::signed(*BOB), market_id, amount, vec![]),
+			Error::<Runtime>::FlashLoanNotRepaid
+		);
+
+		assert_ok!(Tokens::mint_into(USDT::ID, &BOB, USDT::units(10_000)));
+		assert_noop!(
+			Lending::flash_loan(
+				Origin::signed(*BOB),
+				market_id,
+				amount,
+				vec![Call::Tokens(orml_tokens::Call::transfer {
+					dest: *CHARLIE,
+					currency_id: USDT::ID,
+					amount,
+				})],
+			),
+			Error::<Runtime>::FlashLoanNotRepaid
+		);
+
+		assert_noop!(
+			Lending::flash_loan(Origin::signed(*BOB), market_id, 0, vec![]),
+			Error::<Runtime>::FlashLoanAmountMustBeGreaterThanZero
+		);
+	})
+}
+
+#[test]
+fn flash_loan_calls_cannot_use_lending() {
+	new_test_ext().execute_with(|| {
+		let (market_id, _) = create_market_with_liquidity();
+		let amount = USDT::units(1_000_000);
+		assert_ok!(Tokens::mint_into(USDT::ID, &BOB, USDT::units(10_000)));
+
+		for call in [
+			crate::Call::borrow { market_id, amount_to_borrow: USDT::units(1) },
+			crate::Call::flash_loan { market_id, amount, calls: vec![] },
+		] {
+			assert_noop!(
+				Lending::flash_loan(
+					Origin::signed(*BOB),
+					market_id,
+					amount,
+					vec![Call::Lending(call)]
+				),
+				Error::<Runtime>::FlashLoanInProgress
+			);
+		}
+	})
+}
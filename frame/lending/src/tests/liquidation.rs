@@ -1,5 +1,10 @@
 use super::prelude::*;
-use crate::tests::{borrow, create_market_for_liquidation_test, mint_and_deposit_collateral};
+use crate::{
+	tests::{borrow, create_market_for_liquidation_test, mint_and_deposit_collateral},
+	MarketIndex,
+};
+use composable_tests_helpers::test::helper::default_acceptable_computation_error;
+use sp_runtime::Perquintill;
 
 #[test]
 fn test_liquidate_multiple() {
@@ -289,6 +294,9 @@ fn market_owner_cannot_retroactively_liquidate() {
 			under_collateralized_warn_percent: Percent::from_float(1.1),
 			liquidators: vec![],
 			max_price_age: DEFAULT_MAX_PRICE_AGE,
+			liquidation_threshold: collateral_factor,
+			close_factor: DEFAULT_CLOSE_FACTOR,
+			liquidation_incentive: DEFAULT_LIQUIDATION_INCENTIVE,
 		};
 		// ALICE is the creater of the market.
 		assert_noop!(
@@ -299,3 +307,166 @@ fn market_owner_cannot_retroactively_liquidate() {
 		assert_eq!(Lending::should_liquidate(&market_id, &BOB), Ok(false));
 	})
 }
+
+/// Creates a market where positions can be liquidated under a collateral ratio of `1.5`, `50%` of
+/// the debt at a time, giving `10%` of incentive to liquidators. Then BOB borrows 20_000 USDT
+/// against 1 BTC.
+fn create_market_with_partial_liquidations() -> MarketIndex {
+	let manager = *ALICE;
+	let lender = *CHARLIE;
+	let (market_id, vault_id) = create_market_for_liquidation_test::<Runtime>(manager);
+	let market = crate::Markets::<Runtime>::get(market_id).unwrap();
+	assert_ok!(Lending::update_market(
+		Origin::signed(manager),
+		market_id,
+		UpdateInput {
+			collateral_factor: market.collateral_factor,
+			under_collateralized_warn_percent: market.under_collateralized_warn_percent,
+			liquidators: market.liquidators,
+			max_price_age: market.max_price_age,
+			liquidation_threshold: MoreThanOneFixedU128::saturating_from_rational(3_u128, 2_u128),
+			close_factor: Perquintill::from_percent(50),
+			liquidation_incentive: Perquintill::from_percent(10),
+		}
+	));
+	let vault_value = USDT::units(100_000_000);
+	assert_ok!(Tokens::mint_into(USDT::ID, &lender, vault_value));
+	assert_ok!(Vault::deposit(Origin::signed(lender), vault_id, vault_value));
+	test::block::process_and_progress_blocks::<Lending, Runtime>(1);
+	mint_and_deposit_collateral::<Runtime>(*BOB, BTC::units(1), market_id, BTC::ID);
+	borrow::<Runtime>(*BOB, market_id, USDT::units(20_000));
+	market_id
+}
+
+#[test]
+fn liquidate_borrow_repays_part_of_debt_for_collateral_with_incentive() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market_with_partial_liquidations();
+		let liquidator = *CHARLIE;
+		let repay_amount = USDT::units(5_000);
+		assert_ok!(Tokens::mint_into(USDT::ID, &liquidator, repay_amount));
+		// Collateral ratio falls to 1.4, under the liquidation threshold.
+		set_price(BTC::ID, NORMALIZED::units(28_000));
+		let debt_before =
+			Lending::total_debt_with_interest(&market_id, &BOB).unwrap().unwrap_or_zero();
+
+		assert_ok!(Lending::liquidate_borrow(
+			Origin::signed(liquidator),
+			market_id,
+			*BOB,
+			repay_amount,
+			false
+		));
+
+		let collateral_seized = Tokens::balance(BTC::ID, &liquidator);
+		System::assert_last_event(Event::Lending(crate::Event::BorrowLiquidated {
+			liquidator,
+			market_id,
+			borrower: *BOB,
+			repaid: repay_amount,
+			collateral_market_id: market_id,
+			collateral_seized,
+		}));
+		// More collateral than the value repaid, thanks to the incentive.
+		assert!(collateral_seized > BTC::units(5_000) / 28_000);
+		assert!(collateral_seized <= BTC::units(5_500) / 28_000);
+		assert_eq!(
+			crate::AccountCollateral::<Runtime>::get(market_id, *BOB),
+			Some(BTC::units(1) - collateral_seized)
+		);
+		assert_eq!(Tokens::balance(USDT::ID, &liquidator), 0);
+		let debt_after =
+			Lending::total_debt_with_interest(&market_id, &BOB).unwrap().unwrap_or_zero();
+		assert_ok!(default_acceptable_computation_error(debt_after, debt_before - repay_amount));
+		// The borrow stays open.
+		assert!(crate::DebtIndex::<Runtime>::contains_key(market_id, *BOB));
+	})
+}
+
+#[test]
+fn liquidate_borrow_cannot_exceed_close_factor() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market_with_partial_liquidations();
+		let repay_amount = USDT::units(15_000);
+		assert_ok!(Tokens::mint_into(USDT::ID, &CHARLIE, repay_amount));
+		set_price(BTC::ID, NORMALIZED::units(28_000));
+
+		assert_noop!(
+			Lending::liquidate_borrow(
+				Origin::signed(*CHARLIE),
+				market_id,
+				*BOB,
+				repay_amount,
+				false
+			),
+			Error::<Runtime>::LiquidationExceedsCloseFactor
+		);
+	})
+}
+
+#[test]
+fn positions_above_liquidation_threshold_cannot_be_liquidated() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market_with_partial_liquidations();
+		let repay_amount = USDT::units(5_000);
+		assert_ok!(Tokens::mint_into(USDT::ID, &CHARLIE, repay_amount));
+		// Collateral ratio falls to 1.9, under the collateral factor but above the liquidation
+		// threshold.
+		set_price(BTC::ID, NORMALIZED::units(38_000));
+
+		assert_noop!(
+			Lending::liquidate_borrow(
+				Origin::signed(*CHARLIE),
+				market_id,
+				*BOB,
+				repay_amount,
+				false
+			),
+			Error::<Runtime>::CannotLiquidateHealthyPosition
+		);
+		assert_ok!(Lending::liquidate(
+			Origin::signed(*CHARLIE),
+			market_id,
+			TestBoundedVec::try_from(vec![*BOB]).unwrap(),
+		));
+		assert!(crate::AccountCollateral::<Runtime>::get(market_id, *BOB) == Some(BTC::units(1)));
+	})
+}
+
+#[test]
+fn cannot_liquidate_own_borrow() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market_with_partial_liquidations();
+		set_price(BTC::ID, NORMALIZED::units(28_000));
+
+		assert_noop!(
+			Lending::liquidate_borrow(Origin::signed(*BOB), market_id, *BOB, USDT::units(1), false),
+			Error::<Runtime>::CannotLiquidateOwnBorrow
+		);
+	})
+}
+
+#[test]
+fn liquidation_engine_only_liquidates_close_factor_of_debt() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market_with_partial_liquidations();
+		set_price(BTC::ID, NORMALIZED::units(28_000));
+		let debt_before =
+			Lending::total_debt_with_interest(&market_id, &BOB).unwrap().unwrap_or_zero();
+
+		assert_extrinsic_event::<Runtime>(
+			Lending::liquidate(
+				Origin::signed(*CHARLIE),
+				market_id,
+				TestBoundedVec::try_from(vec![*BOB]).unwrap(),
+			),
+			Event::Lending(crate::Event::LiquidationInitiated { market_id, borrowers: vec![*BOB] }),
+		);
+
+		let debt_after =
+			Lending::total_debt_with_interest(&market_id, &BOB).unwrap().unwrap_or_zero();
+		assert_ok!(default_acceptable_computation_error(debt_after, debt_before / 2));
+		assert!(crate::DebtIndex::<Runtime>::contains_key(market_id, *BOB));
+		assert!(crate::BorrowTimestamp::<Runtime>::contains_key(market_id, *BOB));
+	})
+}
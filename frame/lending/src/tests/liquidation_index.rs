@@ -0,0 +1,95 @@
+use super::prelude::*;
+use crate::{
+	liquidation_index::liquidation_bucket,
+	tests::{borrow, create_market_for_liquidation_test},
+	LiquidationBucketOf, LiquidationBuckets,
+};
+
+#[test]
+fn liquidation_buckets_hold_prices_within_one_sixteenth() {
+	let bucket = |inner| liquidation_bucket(FixedU128::from_inner(inner));
+	assert_eq!(bucket(0), 0);
+	assert_eq!(bucket(1_024), bucket(1_087));
+	assert!(bucket(1_087) < bucket(1_088));
+	assert!(bucket(1) < bucket(2));
+	assert!(bucket(u128::MAX - 1) <= bucket(u128::MAX));
+}
+
+#[test]
+fn liquidation_index_follows_borrows() {
+	new_test_ext().execute_with(|| {
+		let risky_borrower = *ALICE;
+		let reliable_borrower = *BOB;
+		let (market_id, vault_id) = create_market_for_liquidation_test::<Runtime>(*ALICE);
+		let vault_value = USDT::units(100_000_000);
+		assert_ok!(Tokens::mint_into(USDT::ID, &CHARLIE, vault_value));
+		assert_ok!(Vault::deposit(Origin::signed(*CHARLIE), vault_id, vault_value));
+		test::block::process_and_progress_blocks::<Lending, Runtime>(1);
+		let market = crate::Markets::<Runtime>::get(market_id).unwrap();
+
+		mint_and_deposit_collateral::<Runtime>(risky_borrower, BTC::units(1), market_id, BTC::ID);
+		mint_and_deposit_collateral::<Runtime>(
+			reliable_borrower,
+			BTC::units(100),
+			market_id,
+			BTC::ID,
+		);
+		// collateral without borrow is never liquidated
+		assert!(!LiquidationBucketOf::<Runtime>::contains_key(market_id, risky_borrower));
+
+		borrow::<Runtime>(risky_borrower, market_id, USDT::units(20_000));
+		borrow::<Runtime>(reliable_borrower, market_id, USDT::units(20_000));
+		let risky_bucket = LiquidationBucketOf::<Runtime>::get(market_id, risky_borrower).unwrap();
+		let reliable_bucket =
+			LiquidationBucketOf::<Runtime>::get(market_id, reliable_borrower).unwrap();
+		assert!(reliable_bucket < risky_bucket);
+		assert_eq!(Lending::liquidation_candidates(&market_id, &market), Ok(vec![]));
+
+		// the index is left untouched, only the threshold moves
+		set_price(BTC::ID, NORMALIZED::units(38_000));
+		assert_eq!(
+			LiquidationBucketOf::<Runtime>::get(market_id, risky_borrower),
+			Some(risky_bucket)
+		);
+		assert_eq!(Lending::liquidation_candidates(&market_id, &market), Ok(vec![risky_borrower]));
+
+		// more collateral lowers the liquidation price of the borrow
+		mint_and_deposit_collateral::<Runtime>(risky_borrower, BTC::units(1), market_id, BTC::ID);
+		assert!(
+			LiquidationBucketOf::<Runtime>::get(market_id, risky_borrower).unwrap() < risky_bucket
+		);
+		assert_eq!(Lending::liquidation_candidates(&market_id, &market), Ok(vec![]));
+
+		test::block::process_and_progress_blocks::<Lending, Runtime>(1);
+		assert_ok!(Tokens::mint_into(USDT::ID, &reliable_borrower, USDT::units(1_000)));
+		assert_ok!(Lending::repay_borrow(
+			Origin::signed(reliable_borrower),
+			market_id,
+			reliable_borrower,
+			RepayStrategy::TotalDebt,
+			false,
+		));
+		assert!(!LiquidationBucketOf::<Runtime>::contains_key(market_id, reliable_borrower));
+		assert_eq!(LiquidationBuckets::<Runtime>::get(market_id).unwrap().len(), 1);
+
+		// cross-margin accounts are valued with their other markets
+		assert_ok!(Lending::enter_cross_margin(Origin::signed(risky_borrower), market_id));
+		assert!(!LiquidationBucketOf::<Runtime>::contains_key(market_id, risky_borrower));
+		assert!(!LiquidationBuckets::<Runtime>::contains_key(market_id));
+		assert_ok!(Lending::exit_cross_margin(Origin::signed(risky_borrower), market_id));
+		assert!(LiquidationBucketOf::<Runtime>::contains_key(market_id, risky_borrower));
+	})
+}
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(10_000))]
+
+	#[test]
+	fn liquidation_buckets_are_ordered_like_prices(a: u128, b: u128) {
+		let (low, high) = (a.min(b), a.max(b));
+		prop_assert!(
+			liquidation_bucket(FixedU128::from_inner(low))
+				<= liquidation_bucket(FixedU128::from_inner(high))
+		);
+	}
+}
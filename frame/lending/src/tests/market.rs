@@ -1,7 +1,15 @@
 use super::prelude::*;
-use crate::{tests::default_create_input, validation::UpdateInputValid, MarketIndex};
-use composable_traits::{defi::CurrencyPair, oracle, vault};
+use crate::{
+	helpers::current_interest_rate,
+	tests::{default_create_input, new_jump_model},
+	validation::UpdateInputValid,
+	MarketIndex,
+};
+use composable_traits::{
+	defi::CurrencyPair, lending::math::JumpModel, oracle, time::SECONDS_PER_YEAR_NAIVE, vault,
+};
 use frame_system::{EventRecord, Phase};
+use std::ops::Add;
 
 #[test]
 fn can_update_market() {
@@ -19,6 +27,9 @@ fn can_update_market() {
 			under_collateralized_warn_percent: market.under_collateralized_warn_percent,
 			liquidators: market.liquidators.clone(),
 			max_price_age: market.max_price_age,
+			liquidation_threshold: market.liquidation_threshold,
+			close_factor: market.close_factor,
+			liquidation_incentive: market.liquidation_incentive,
 		};
 		let updated = Lending::update_market(origin, market_id, update_input.clone());
 		// check if the market was successfully updated
@@ -34,6 +45,9 @@ fn can_update_market() {
 			under_collateralized_warn_percent: market.under_collateralized_warn_percent,
 			liquidators: market.liquidators,
 			max_price_age: market.max_price_age,
+			liquidation_threshold: market.liquidation_threshold,
+			close_factor: market.close_factor,
+			liquidation_incentive: market.liquidation_incentive,
 		};
 		assert_err!(
 			update_input.try_into_validated::<UpdateInputValid>(),
@@ -42,6 +56,117 @@ fn can_update_market() {
 	})
 }
 
+#[test]
+fn can_update_interest_rate_model() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let ((market_id, _), _) = create_simple_vaulted_market(BTC::instance(), *ALICE);
+		let interest_rate_model = InterestRateModel::Curve(
+			CurveModel::new(FixedU128::saturating_from_rational(5_u128, 100_u128)).unwrap(),
+		);
+
+		assert_noop!(
+			Lending::update_interest_rate_model(
+				Origin::signed(*ALICE),
+				market_id,
+				interest_rate_model
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_extrinsic_event::<Runtime>(
+			Lending::update_interest_rate_model(Origin::root(), market_id, interest_rate_model),
+			Event::Lending(crate::Event::InterestRateModelUpdated {
+				market_id,
+				interest_rate_model,
+			}),
+		);
+		assert_eq!(
+			crate::Markets::<Runtime>::get(market_id).unwrap().interest_rate_model,
+			interest_rate_model
+		);
+
+		let invalid_model = InterestRateModel::Jump(JumpModel {
+			base_rate: FixedU128::saturating_from_integer(1_u128),
+			jump_rate: FixedU128::saturating_from_integer(1_u128),
+			full_rate: FixedU128::saturating_from_integer(1_u128),
+			target_utilization: Percent::from_percent(80),
+		});
+		assert_noop!(
+			Lending::update_interest_rate_model(Origin::root(), market_id, invalid_model),
+			DispatchError::Other("interest rate model is not valid")
+		);
+		assert_eq!(
+			Lending::simulate_interest_rate_model(invalid_model, Percent::from_percent(10)),
+			None
+		);
+	})
+}
+
+#[test]
+fn interest_is_accrued_at_the_previous_rate_when_the_interest_rate_model_is_updated() {
+	new_test_ext().execute_with(|| {
+		let (market_id, vault) = create_simple_market();
+		mint_and_deposit_collateral::<Runtime>(*ALICE, BTC::units(100), market_id, BTC::ID);
+		let borrow_asset_deposit = USDT::units(1_000_000);
+		assert_ok!(Tokens::mint_into(USDT::ID, &CHARLIE, borrow_asset_deposit));
+		assert_ok!(Vault::deposit(Origin::signed(*CHARLIE), vault, borrow_asset_deposit));
+		test::block::process_and_progress_blocks::<Lending, Runtime>(1);
+
+		crate::tests::borrow::<Runtime>(*ALICE, market_id, USDT::units(100_000));
+		test::block::process_and_progress_blocks::<Lending, Runtime>(10);
+
+		// `on_initialize` accrued up to the timestamp of the previous block only
+		let delta_time = Lending::now() - crate::LastBlockTimestamp::<Runtime>::get();
+		assert!(delta_time > 0);
+		let previous_rate = current_interest_rate::<Runtime>(market_id.0).unwrap();
+		let borrow_index = crate::BorrowIndex::<Runtime>::get(market_id).unwrap();
+		let expected_borrow_index = previous_rate
+			.mul(FixedU128::saturating_from_integer(delta_time))
+			.div(FixedU128::saturating_from_integer(SECONDS_PER_YEAR_NAIVE))
+			.mul(borrow_index)
+			.add(borrow_index);
+
+		let interest_rate_model = InterestRateModel::Curve(
+			CurveModel::new(FixedU128::saturating_from_rational(5_u128, 100_u128)).unwrap(),
+		);
+		assert_ok!(Lending::update_interest_rate_model(
+			Origin::root(),
+			market_id,
+			interest_rate_model
+		));
+		assert_ne!(current_interest_rate::<Runtime>(market_id.0).unwrap(), previous_rate);
+		assert_eq!(crate::BorrowIndex::<Runtime>::get(market_id), Some(expected_borrow_index));
+
+		// the next block does not accrue the same interval again at the new rate
+		test::block::process_and_progress_blocks::<Lending, Runtime>(1);
+		assert_eq!(crate::BorrowIndex::<Runtime>::get(market_id), Some(expected_borrow_index));
+		assert_eq!(crate::InterestAccruedAt::<Runtime>::get(market_id), None);
+
+		test::block::process_and_progress_blocks::<Lending, Runtime>(1);
+		assert!(crate::BorrowIndex::<Runtime>::get(market_id).unwrap() > expected_borrow_index);
+	})
+}
+
+#[test]
+fn can_simulate_interest_rate_model() {
+	let (_, interest_rate_model) = new_jump_model();
+	let points =
+		Lending::simulate_interest_rate_model(interest_rate_model, Percent::from_percent(20))
+			.unwrap();
+	assert_eq!(
+		points.iter().map(|point| point.utilization).collect::<Vec<_>>(),
+		[0, 20, 40, 60, 80, 100].map(Percent::from_percent).to_vec()
+	);
+	// lending keeps no reserve, so at full utilization lenders earn the whole borrow rate
+	let full_utilization = points.last().unwrap();
+	assert_eq!(full_utilization.supply_rate, full_utilization.borrow_rate);
+	assert_eq!(
+		full_utilization.borrow_rate,
+		FixedU128::saturating_from_rational(32_u128, 100_u128)
+	);
+}
+
 #[test]
 /// Tests market creation and the associated event(s).
 fn can_create_valid_market() {
@@ -16,8 +16,11 @@ use pallet_vault::models::VaultInfo;
 use sp_runtime::{FixedPointNumber, Percent, Perquintill};
 
 pub mod borrow;
+pub mod cross_margin;
+pub mod flash_loan;
 pub mod interest;
 pub mod liquidation;
+pub mod liquidation_index;
 pub mod market;
 pub mod offchain;
 pub mod prelude;
@@ -28,6 +31,8 @@ pub const DEFAULT_MARKET_VAULT_RESERVE: Perquintill = Perquintill::from_percent(
 pub const DEFAULT_COLLATERAL_FACTOR: u128 = 2;
 pub const DEFAULT_MAX_PRICE_AGE: u64 = 1020;
 pub const DEFAULT_MARKET_VAULT_STRATEGY_SHARE: Perquintill = Perquintill::from_percent(90);
+pub const DEFAULT_CLOSE_FACTOR: Perquintill = Perquintill::one();
+pub const DEFAULT_LIQUIDATION_INCENTIVE: Perquintill = Perquintill::zero();
 
 type SystemAccountIdOf<T> = <T as frame_system::Config>::AccountId;
 type SystemOriginOf<T> = <T as frame_system::Config>::Origin;
@@ -55,6 +60,9 @@ fn default_create_input<AssetId, BlockNumber: sp_runtime::traits::Bounded>(
 			under_collateralized_warn_percent: default_under_collateralized_warn_percent(),
 			liquidators: vec![],
 			max_price_age: BlockNumber::max_value(),
+			liquidation_threshold: default_collateral_factor(),
+			close_factor: DEFAULT_CLOSE_FACTOR,
+			liquidation_incentive: DEFAULT_LIQUIDATION_INCENTIVE,
 		},
 		interest_rate_model: InterestRateModel::default(),
 		reserved_factor: DEFAULT_MARKET_VAULT_RESERVE,
@@ -151,6 +159,9 @@ where
 			under_collateralized_warn_percent: default_under_collateralized_warn_percent(),
 			liquidators: vec![],
 			max_price_age: DEFAULT_MAX_PRICE_AGE,
+			liquidation_threshold: collateral_factor,
+			close_factor: DEFAULT_CLOSE_FACTOR,
+			liquidation_incentive: DEFAULT_LIQUIDATION_INCENTIVE,
 		},
 		interest_rate_model: InterestRateModel::default(),
 		reserved_factor,
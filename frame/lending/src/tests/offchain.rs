@@ -8,7 +8,11 @@ use crate::{
 };
 use codec::Decode;
 use composable_tests_helpers::test;
-use frame_support::{assert_ok, traits::fungibles::Mutate, BoundedVec};
+use frame_support::{
+	assert_ok,
+	traits::{fungibles::Mutate, Currency},
+	BoundedVec,
+};
 use sp_core::{
 	offchain::{testing, TransactionPoolExt},
 	H256,
@@ -103,3 +107,57 @@ fn test_liquidation_offchain_worker() {
 		assert!(System::events().iter().filter(|record| record.event == event).count() == 1);
 	});
 }
+
+#[test]
+fn test_liquidation_offchain_worker_batches_borrowers() {
+	let account_id = *ALICE;
+	let authority_id = authority_id_wrapper::UintAuthorityIdWrapper::from(account_id);
+	authority_id_wrapper::UintAuthorityIdWrapper::set_all_keys(vec![authority_id]);
+	let mut ext = new_test_ext();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.execute_with(|| {
+		let manager = *ALICE;
+		let lender = *CHARLIE;
+		let (market_id, vault_id) = create_market_for_liquidation_test::<Runtime>(manager);
+		let vault_value = USDT::units(100_000_000);
+		assert_ok!(Tokens::mint_into(USDT::ID, &lender, vault_value));
+		assert_ok!(Vault::deposit(Origin::signed(lender), vault_id, vault_value));
+		test::block::process_and_progress_blocks::<Lending, Runtime>(1);
+
+		// One more risky borrower than fits in a single liquidation.
+		let risky_borrowers: Vec<AccountId> =
+			(10..11 + u64::from(MaxLiquidationBatchSize::get())).collect();
+		for borrower in &risky_borrowers {
+			Balances::make_free_balance_be(borrower, 1_000_000_000);
+			mint_and_deposit_collateral::<Runtime>(*borrower, BTC::units(1), market_id, BTC::ID);
+			borrow::<Runtime>(*borrower, market_id, USDT::units(20_000));
+		}
+		set_price(BTC::ID, NORMALIZED::units(38_000));
+
+		let header =
+			Header::new(2, H256::default(), H256::default(), [69u8; 32].into(), Digest::default());
+		Executive::offchain_worker(&header);
+
+		let mut liquidated: Vec<AccountId> = Vec::new();
+		let mut batch_sizes: Vec<usize> = Vec::new();
+		for tx in pool_state.write().transactions.drain(..) {
+			let tx = Extrinsic::decode(&mut &*tx).unwrap();
+			match tx.call {
+				Call::Lending(crate::Call::liquidate {
+					market_id: liquidated_market,
+					borrowers,
+				}) => {
+					assert_eq!(liquidated_market, market_id);
+					batch_sizes.push(borrowers.len());
+					liquidated.extend(borrowers.into_inner());
+				},
+				call => panic!("unexpected call {:?}", call),
+			}
+		}
+		batch_sizes.sort_unstable();
+		assert_eq!(batch_sizes, vec![1, MaxLiquidationBatchSize::get() as usize]);
+		liquidated.sort_unstable();
+		assert_eq!(liquidated, risky_borrowers);
+	});
+}
@@ -3,8 +3,8 @@ pub use crate::{
 	tests::{
 		assert_extrinsic_event, assert_no_event, create_market, create_simple_market,
 		create_simple_vaulted_market, get_price, mint_and_deposit_collateral, TestBoundedVec,
-		DEFAULT_COLLATERAL_FACTOR, DEFAULT_MARKET_VAULT_RESERVE,
-		DEFAULT_MARKET_VAULT_STRATEGY_SHARE, DEFAULT_MAX_PRICE_AGE,
+		DEFAULT_CLOSE_FACTOR, DEFAULT_COLLATERAL_FACTOR, DEFAULT_LIQUIDATION_INCENTIVE,
+		DEFAULT_MARKET_VAULT_RESERVE, DEFAULT_MARKET_VAULT_STRATEGY_SHARE, DEFAULT_MAX_PRICE_AGE,
 	},
 	Error,
 };
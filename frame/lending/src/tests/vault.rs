@@ -1,8 +1,13 @@
 use super::prelude::*;
 use crate::tests::borrow;
 use codec::Decode;
-use composable_traits::{lending::TotalDebtWithInterest, vault::Vault as VaultTrate};
-use frame_support::traits::{fungible::Mutate as FungibleMutateTrate, fungibles::Mutate};
+use composable_traits::{
+	lending::TotalDebtWithInterest,
+	vault::{Vault as VaultTrate, VaultStrategy},
+};
+use frame_support::traits::{
+	fungible::Mutate as FungibleMutateTrate, fungibles::Mutate, GetStorageVersion, StorageVersion,
+};
 use sp_runtime::{traits::TrailingZeroInput, Perquintill};
 
 #[test]
@@ -210,3 +215,21 @@ proptest! {
 		})?;
 	}
 }
+
+#[test]
+fn market_accounts_are_indexed_as_strategies_of_their_vault() {
+	new_test_ext().execute_with(|| {
+		let (market_id, vault_id) = create_simple_market();
+		let account = <Lending as LendingTrait>::account_id(&market_id);
+		assert_eq!(crate::MarketOfAccount::<Runtime>::get(&account), Some(market_id));
+		assert!(<Lending as VaultStrategy<_, _, _>>::is_strategy(&account));
+		assert!(<Lending as VaultStrategy<_, _, _>>::balance(&vault_id, &account).is_ok());
+		assert!(!<Lending as VaultStrategy<_, _, _>>::is_strategy(&ALICE));
+
+		crate::MarketOfAccount::<Runtime>::remove(&account);
+		StorageVersion::new(2).put::<Lending>();
+		crate::migrations::migrate_to_v3::<Runtime>();
+		assert_eq!(crate::MarketOfAccount::<Runtime>::get(&account), Some(market_id));
+		assert_eq!(Lending::on_chain_storage_version(), 3);
+	});
+}
@@ -20,6 +20,10 @@ pub(crate) struct InitializeBlockCallCounters {
 
 pub type MarketId = u32;
 
+/// Bucket of the borrows of a market with close liquidation prices, see the
+/// [`liquidation_index`](crate::liquidation_index) module.
+pub type LiquidationBucket = u32;
+
 // REVIEW: Maybe move this to `models::market_index`?
 // TODO: Rename to `MarketId`.
 #[derive(Default, Debug, Copy, Clone, Encode, Decode, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
@@ -21,6 +21,16 @@ impl<LiquidationStrategyId, BlockNumber>
 			return Err("Collateral factor must be more than one.")
 		}
 
+		if update_input.liquidation_threshold < MoreThanOneFixedU128::one() ||
+			update_input.liquidation_threshold > update_input.collateral_factor
+		{
+			return Err("Liquidation threshold must be between one and the collateral factor.")
+		}
+
+		if update_input.close_factor.is_zero() {
+			return Err("Close factor must be more than zero.")
+		}
+
 		Ok(update_input)
 	}
 }
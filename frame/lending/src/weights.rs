@@ -16,6 +16,11 @@ pub trait WeightInfo {
 	fn borrow() -> Weight;
 	fn repay_borrow() -> Weight;
 	fn liquidate(b: u32) -> Weight;
+	fn liquidate_borrow() -> Weight;
+	fn enter_cross_margin() -> Weight;
+	fn exit_cross_margin(m: u32) -> Weight;
+	fn flash_loan() -> Weight;
+	fn update_interest_rate_model() -> Weight;
 	fn now() -> Weight;
 	fn accrue_interest(x: u32) -> Weight;
 	fn account_id() -> Weight;
@@ -56,13 +61,40 @@ impl WeightInfo for () {
 			.saturating_add((7_877_000 as Weight).saturating_mul(b as Weight))
 			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
 	}
+	fn liquidate_borrow() -> Weight {
+		(245_116_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(17 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn enter_cross_margin() -> Weight {
+		(21_604_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn exit_cross_margin(m: u32) -> Weight {
+		(32_210_000 as Weight)
+			.saturating_add((58_377_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((9 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn flash_loan() -> Weight {
+		(84_312_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn update_interest_rate_model() -> Weight {
+		(100_476_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
 	fn now() -> Weight {
 		(4_744_000 as Weight).saturating_add(RocksDbWeight::get().reads(1 as Weight))
 	}
 	fn accrue_interest(_x: u32) -> Weight {
 		(76_626_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
 	fn account_id() -> Weight {
 		(3_126_000 as Weight)
@@ -1,14 +1,20 @@
 use super::*;
 use crate::Pallet as Liquidations;
 use codec::Decode;
+use composable_support::abstractions::utils::increment::Increment;
 use composable_traits::{
 	defi::{CurrencyPair, DeFiComposableConfig, Ratio, Sell},
 	time::{LinearDecrease, TimeReleaseFunction},
+	xcm::{SellResponse, XcmSellInitialResponseTransact},
 };
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
 use frame_support::traits::{fungibles::Mutate, Currency, Get};
 use frame_system::RawOrigin;
-use sp_runtime::{traits::Saturating, FixedPointNumber};
+use polkadot_parachain::primitives::{Id as ParaId, Sibling};
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating},
+	FixedPointNumber,
+};
 use sp_std::prelude::*;
 pub type AssetIdOf<T> = <T as DeFiComposableConfig>::MayBeAssetId;
 fn assets<T>() -> CurrencyPair<AssetIdOf<T>>
@@ -29,6 +35,7 @@ benchmarks! {
 			T: pallet_assets::Config + DeFiComposableConfig + orml_tokens::Config + pallet_balances::Config + pallet_dutch_auction::Config,
 			<T as orml_tokens::Config>::CurrencyId: From<<T as DeFiComposableConfig>::MayBeAssetId>,
 			<T as pallet_dutch_auction::Config>::NativeCurrency: Currency<T::AccountId>,
+			<T as frame_system::Config>::Origin: From<cumulus_pallet_xcm::Origin>,
 				}
 
 	add_liquidation_strategy {
@@ -59,6 +66,32 @@ benchmarks! {
 		let mut configurations:Vec<T::LiquidationStrategyId> = (begin..end).map(|x| x.into()).collect();
 		configurations.push(1.into());
 		   }: _(origin, order, configurations)
+
+	xcm_sell_response {
+		let pair = assets::<T>();
+		let engine = ParaId::from(3000);
+		let engine_account: T::AccountId = Sibling::from(engine).into_account_truncating();
+		let caller: T::AccountId = whitelisted_caller();
+		let amount: <T as DeFiComposableConfig>::Balance = 1_000_000u32.into();
+		orml_tokens::Pallet::<T>::mint_into(pair.base.into(), &engine_account, amount).unwrap();
+		orml_tokens::Pallet::<T>::mint_into(pair.quote.into(), &engine_account, amount).unwrap();
+		XcmSellOrders::<T>::insert(0, XcmSellOrder {
+			order_id: OrderIndex::<T>::increment(),
+			parachain_id: engine,
+			from_to: caller,
+			asset_id: pair.base,
+			quote_asset_id: pair.quote,
+			amount,
+		});
+		// the engine sold half of the position, the other half is returned with the proceeds
+		let response = SellResponse::Final(XcmSellInitialResponseTransact {
+			total_amount_taken: 500_000,
+			minimal_price: 250_000,
+			order_id: 0,
+		});
+		let origin: <T as frame_system::Config>::Origin =
+			cumulus_pallet_xcm::Origin::SiblingParachain(engine).into();
+	}: _<T::Origin>(origin, response)
 }
 
 impl_benchmark_test_suite!(
@@ -61,19 +61,32 @@ pub mod pallet {
 	};
 	use composable_traits::{
 		defi::{DeFiComposableConfig, DeFiEngine, Sell, SellEngine},
+		dex::Amm,
 		liquidation::Liquidation,
+		oracle::Oracle,
 		time::{LinearDecrease, StairstepExponentialDecrease, TimeReleaseFunction},
+		xcm::{
+			SellResponse, XcmCumulusDispatch, XcmSellRequest, XcmSellRequestTransactConfiguration,
+		},
 	};
+	use cumulus_pallet_xcm::{ensure_sibling_para, Origin as CumulusOrigin};
 	use frame_support::{
-		dispatch::DispatchResultWithPostInfo,
+		dispatch::{DispatchResult, DispatchResultWithPostInfo},
+		ensure,
 		pallet_prelude::{OptionQuery, StorageMap, StorageValue},
-		traits::{EnsureOrigin, Get, IsType, UnixTime},
-		BoundedVec, PalletId, Parameter, Twox64Concat,
+		storage::weak_bounded_vec::WeakBoundedVec,
+		traits::{fungibles::Transfer, EnsureOrigin, Get, IsType, UnixTime},
+		transactional, BoundedVec, PalletId, Parameter, Twox64Concat,
 	};
 	use frame_system::{ensure_signed, pallet_prelude::OriginFor};
+	use polkadot_parachain::primitives::{Id as ParaId, Sibling};
 	use scale_info::TypeInfo;
-	use sp_runtime::{DispatchError, Permill, Perquintill};
-	use sp_std::vec::Vec;
+	use sp_runtime::{
+		traits::{AccountIdConversion, Saturating, Zero},
+		ArithmeticError, DispatchError, FixedPointNumber, Permill, Perquintill,
+	};
+	use sp_std::{convert::TryInto, vec::Vec};
+	use xcm::latest::QueryId;
 
 	#[cfg(feature = "std")]
 	use frame_support::traits::GenesisBuild;
@@ -103,7 +116,41 @@ pub mod pallet {
 			+ Copy
 			+ From<u32>;
 
-		type OrderId: Default + FullCodec + MaxEncodedLen + sp_std::fmt::Debug;
+		type OrderId: Default
+			+ FullCodec
+			+ MaxEncodedLen
+			+ sp_std::fmt::Debug
+			+ Parameter
+			+ Copy
+			+ WrappingNext
+			+ TryInto<QueryId>;
+
+		/// AMM the `Pablo` strategy sells positions on.
+		type Amm: Amm<
+			AssetId = Self::MayBeAssetId,
+			Balance = Self::Balance,
+			AccountId = Self::AccountId,
+			PoolId = Self::PoolId,
+		>;
+
+		type PoolId: Parameter + MaxEncodedLen + Copy;
+
+		/// Prices the `Pablo` strategy bounds the slippage of its swaps with.
+		type Oracle: Oracle<AssetId = Self::MayBeAssetId, Balance = Self::Balance>;
+
+		/// Moves the positions sold by the `Xcm` strategy to the sovereign account of the engine.
+		type MultiCurrency: Transfer<
+			Self::AccountId,
+			AssetId = Self::MayBeAssetId,
+			Balance = Self::Balance,
+		>;
+
+		/// Id of this parachain, locating the assets it is the reserve of.
+		type ParachainId: Get<ParaId>;
+
+		/// Weight allowed to the sell request dispatched on the engine parachain.
+		#[pallet::constant]
+		type XcmSellRequestWeight: Get<u64>;
 
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -115,6 +162,10 @@ pub mod pallet {
 		/// is used to talk to external liquidation engines
 		type XcmSender: xcm::latest::SendXcm;
 
+		/// Origin of the responses of the external liquidation engines.
+		type XcmOrigin: From<<Self as frame_system::Config>::Origin>
+			+ Into<Result<CumulusOrigin, <Self as Config>::XcmOrigin>>;
+
 		type CanModifyStrategies: EnsureOrigin<Self::Origin>;
 		type MaxLiquidationStrategiesAmount: Get<u32>;
 	}
@@ -123,13 +174,53 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub (crate) fn deposit_event)]
 	pub enum Event<T: Config> {
 		PositionWasSentToLiquidation {},
+		/// A strategy failed to liquidate a position, the next one of the configuration is tried.
+		LiquidationStrategyFailed {
+			strategy_id: T::LiquidationStrategyId,
+			error: DispatchError,
+		},
+		/// A position was sold on a pool of the `Amm`.
+		PositionWasSwapped {
+			order_id: T::OrderId,
+			pool_id: T::PoolId,
+			amount: T::Balance,
+			received: T::Balance,
+		},
+		/// A position was sent to be sold by the engine of a sibling parachain.
+		XcmSellRequestSent {
+			order_id: T::OrderId,
+			parachain_id: ParaId,
+		},
+		/// The engine took part of a position, the rest being returned to its owner.
+		XcmSellOrderTaken {
+			order_id: T::OrderId,
+			amount_taken: T::Balance,
+			amount_returned: T::Balance,
+		},
+		/// The engine sold part of a position for `received` of the quote asset, credited to its
+		/// owner with the rest of the position.
+		XcmSellOrderSettled {
+			order_id: T::OrderId,
+			amount_sold: T::Balance,
+			amount_returned: T::Balance,
+			received: T::Balance,
+		},
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		NoLiquidationEngineFound,
 		InvalidLiquidationStrategiesVector,
-		OnlyDutchAuctionStrategyIsImplemented,
+		/// The pool of the strategy does not trade the pair of the position.
+		PoolPairMismatch,
+		/// The position cannot be represented in the sell request of the engine.
+		XcmCannotEncodeSellRequest,
+		/// The sell request could not be sent to the engine.
+		XcmSellRequestNotSent,
+		/// No position was sent to the engine with this order id.
+		XcmSellOrderNotFound,
+		/// The engine took more than the position it was sent.
+		XcmSellResponseExceedsPosition,
 	}
 
 	#[pallet::pallet]
@@ -144,7 +235,7 @@ pub mod pallet {
 			// TODO: make it validated
 			// TODO: User parachains pallet to validate parachain is connected
 			// TODO: use hardocded swap interface to validate native token is supported
-			configuraiton: LiquidationStrategyConfiguration,
+			configuraiton: LiquidationStrategyConfigurationOf<T>,
 		) -> DispatchResultWithPostInfo {
 			T::CanModifyStrategies::ensure_origin(origin)?;
 			let index = StrategyIndex::<T>::increment();
@@ -163,7 +254,82 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		// TODO: Add API to manage callback from liquidation engine and managing it state
+		/// Settle a position sent to the engine of a sibling parachain on its response, the part of
+		/// the position the engine did not take being returned to its owner. On the final response,
+		/// the owner is also credited with the quote asset the engine sold the position for, which
+		/// is `minimal_price` for the `total_amount_taken`.
+		///
+		/// Only callable by the engine the position was sent to.
+		#[pallet::weight(T::WeightInfo::xcm_sell_response())]
+		pub fn xcm_sell_response(
+			origin: OriginFor<T>,
+			response: SellResponse,
+		) -> DispatchResultWithPostInfo {
+			let parachain_id = ensure_sibling_para(<T as Config>::XcmOrigin::from(origin))?;
+			let (response, is_final) = match response {
+				SellResponse::Initial(response) => (response, false),
+				SellResponse::Final(response) => (response, true),
+			};
+			XcmSellOrders::<T>::try_mutate_exists(response.order_id, |order| -> DispatchResult {
+				let sell_order = order
+					.as_mut()
+					.filter(|order| order.parachain_id == parachain_id)
+					.ok_or(Error::<T>::XcmSellOrderNotFound)?;
+				let amount_taken: T::Balance = response.total_amount_taken.into();
+				ensure!(
+					amount_taken <= sell_order.amount,
+					Error::<T>::XcmSellResponseExceedsPosition
+				);
+				let amount_returned = sell_order.amount.saturating_sub(amount_taken);
+				let engine_account: T::AccountId =
+					Sibling::from(parachain_id).into_account_truncating();
+				if !amount_returned.is_zero() {
+					T::MultiCurrency::transfer(
+						sell_order.asset_id,
+						&engine_account,
+						&sell_order.from_to,
+						amount_returned,
+						false,
+					)?;
+				}
+				let order_id = sell_order.order_id;
+				if is_final {
+					// the proceeds are held by the sovereign account of this parachain on the
+					// engine, which is backed by the account of the engine here
+					let received: T::Balance = if amount_taken.is_zero() {
+						Zero::zero()
+					} else {
+						response.minimal_price.into()
+					};
+					if !received.is_zero() {
+						T::MultiCurrency::transfer(
+							sell_order.quote_asset_id,
+							&engine_account,
+							&sell_order.from_to,
+							received,
+							false,
+						)?;
+					}
+					*order = None;
+					Self::deposit_event(Event::<T>::XcmSellOrderSettled {
+						order_id,
+						amount_sold: amount_taken,
+						amount_returned,
+						received,
+					});
+				} else {
+					sell_order.amount = amount_taken;
+					Self::deposit_event(Event::<T>::XcmSellOrderTaken {
+						order_id,
+						amount_taken,
+						amount_returned,
+					});
+				}
+				Ok(())
+			})?;
+			Ok(().into())
+		}
+
 		// TODO: each step from request to have its slots so can tackle
 		// TODO: add incetivised API to allow "progress" finalization if it stalled (or OCW)
 	}
@@ -174,10 +340,23 @@ pub mod pallet {
 		_,
 		Twox64Concat,
 		T::LiquidationStrategyId,
-		LiquidationStrategyConfiguration,
+		LiquidationStrategyConfigurationOf<T>,
 		OptionQuery,
 	>;
 
+	/// Positions held by the engines of sibling parachains, by the order id of the sell request,
+	/// until the engine settles them.
+	#[pallet::storage]
+	#[pallet::getter(fn xcm_sell_order)]
+	pub type XcmSellOrders<T: Config> =
+		StorageMap<_, Twox64Concat, QueryId, XcmSellOrderOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn order_index)]
+	#[allow(clippy::disallowed_types)]
+	pub type OrderIndex<T: Config> =
+		StorageValue<_, T::OrderId, ValueQuery, Nonce<DefaultInit, WrappingIncrement>>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn strategy_index)]
 	#[allow(clippy::disallowed_types)]
@@ -214,12 +393,42 @@ pub mod pallet {
 	}
 
 	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
-	pub enum LiquidationStrategyConfiguration {
+	pub enum LiquidationStrategyConfiguration<PoolId> {
 		DutchAuction(TimeReleaseFunction),
-		Pablo { slippage: Perquintill },
-		Xcm(composable_traits::xcm::XcmSellRequestTransactConfiguration),
+		/// Sells on `pool_id`, for no less than the oracle price of the position minus
+		/// `slippage`.
+		Pablo {
+			pool_id: PoolId,
+			slippage: Perquintill,
+		},
+		Xcm(XcmSellRequestTransactConfiguration),
+	}
+
+	pub type LiquidationStrategyConfigurationOf<T> =
+		LiquidationStrategyConfiguration<<T as Config>::PoolId>;
+
+	/// Position sent to the engine of a sibling parachain, until the engine settles it.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+	pub struct XcmSellOrder<OrderId, AccountId, AssetId, Balance> {
+		pub order_id: OrderId,
+		pub parachain_id: ParaId,
+		/// Owner of the position, receiving the part the engine does not take and the proceeds of
+		/// the part it sells.
+		pub from_to: AccountId,
+		pub asset_id: AssetId,
+		/// Asset the position is sold for.
+		pub quote_asset_id: AssetId,
+		/// Amount held by the sovereign account of the engine.
+		pub amount: Balance,
 	}
 
+	pub type XcmSellOrderOf<T> = XcmSellOrder<
+		<T as Config>::OrderId,
+		<T as frame_system::Config>::AccountId,
+		<T as DeFiComposableConfig>::MayBeAssetId,
+		<T as DeFiComposableConfig>::Balance,
+	>;
+
 	#[cfg(feature = "std")]
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig {
@@ -272,19 +481,171 @@ pub mod pallet {
 			for id in configuration {
 				let configuration = Strategies::<T>::get(id);
 				if let Some(configuration) = configuration {
-					let result = match configuration {
-						LiquidationStrategyConfiguration::DutchAuction(configuration) =>
-							T::DutchAuction::ask(from_to, order.clone(), configuration),
-						_ => return Err(Error::<T>::OnlyDutchAuctionStrategyIsImplemented.into()),
-					};
-					if let Ok(order_id) = result {
-						Self::deposit_event(Event::<T>::PositionWasSentToLiquidation {});
-						return Ok(order_id)
+					match Self::liquidate_with(from_to, order.clone(), configuration) {
+						Ok(order_id) => {
+							Self::deposit_event(Event::<T>::PositionWasSentToLiquidation {});
+							return Ok(order_id)
+						},
+						Err(error) => Self::deposit_event(Event::<T>::LiquidationStrategyFailed {
+							strategy_id: id,
+							error,
+						}),
 					}
 				}
 			}
 
 			Err(Error::<T>::NoLiquidationEngineFound.into())
 		}
+
+		/// Liquidates with a single strategy, reverting its changes if it fails so that the next
+		/// strategy starts from a clean state.
+		#[transactional]
+		fn liquidate_with(
+			from_to: &T::AccountId,
+			order: Sell<T::MayBeAssetId, T::Balance>,
+			configuration: LiquidationStrategyConfigurationOf<T>,
+		) -> Result<T::OrderId, DispatchError> {
+			match configuration {
+				LiquidationStrategyConfiguration::DutchAuction(configuration) =>
+					T::DutchAuction::ask(from_to, order, configuration),
+				LiquidationStrategyConfiguration::Pablo { pool_id, slippage } =>
+					Self::swap(from_to, order, pool_id, slippage),
+				LiquidationStrategyConfiguration::Xcm(configuration) =>
+					Self::xcm_sell(from_to, order, configuration),
+			}
+		}
+
+		/// The limit of the order is the starting price of auctions, swaps are bounded by the
+		/// oracle price instead.
+		fn swap(
+			from_to: &T::AccountId,
+			order: Sell<T::MayBeAssetId, T::Balance>,
+			pool_id: T::PoolId,
+			slippage: Perquintill,
+		) -> Result<T::OrderId, DispatchError> {
+			ensure!(T::Amm::currency_pair(pool_id)? == order.pair, Error::<T>::PoolPairMismatch);
+			let amount = order.take.amount;
+			let oracle_price = T::Oracle::get_ratio(order.pair)?
+				.checked_mul_int(amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			let min_receive = oracle_price.saturating_sub(slippage.mul_ceil(oracle_price));
+			let received =
+				T::Amm::sell(from_to, pool_id, order.pair.base, amount, min_receive, false)?;
+
+			let order_id = OrderIndex::<T>::increment();
+			Self::deposit_event(Event::<T>::PositionWasSwapped {
+				order_id,
+				pool_id,
+				amount,
+				received,
+			});
+			Ok(order_id)
+		}
+
+		/// Moves the position to the sovereign account of the engine, this parachain being the
+		/// reserve of it, and requests the engine to sell it.
+		fn xcm_sell(
+			from_to: &T::AccountId,
+			order: Sell<T::MayBeAssetId, T::Balance>,
+			configuration: XcmSellRequestTransactConfiguration,
+		) -> Result<T::OrderId, DispatchError> {
+			use xcm::latest::{prelude::*, WeightLimit::Unlimited};
+
+			let parachain_id = configuration.location.parachain_id;
+			// engine uses the sovereign account of this parachain to sell the position
+			let sovereign_account: T::AccountId =
+				Sibling::from(T::ParachainId::get()).into_account_truncating();
+			let sovereign_account: [u8; 32] = sovereign_account
+				.encode()
+				.try_into()
+				.map_err(|_| Error::<T>::XcmCannotEncodeSellRequest)?;
+			let remote_asset_id = |asset_id: T::MayBeAssetId| {
+				u128::decode(&mut &asset_id.encode()[..])
+					.map_err(|_| Error::<T>::XcmCannotEncodeSellRequest)
+			};
+			let amount: u128 = order
+				.take
+				.amount
+				.try_into()
+				.map_err(|_| Error::<T>::XcmCannotEncodeSellRequest)?;
+
+			let engine_account: T::AccountId =
+				Sibling::from(parachain_id).into_account_truncating();
+			T::MultiCurrency::transfer(
+				order.pair.base,
+				from_to,
+				&engine_account,
+				order.take.amount,
+				false,
+			)?;
+
+			let order_id = OrderIndex::<T>::increment();
+			let query_id: QueryId =
+				order_id.try_into().map_err(|_| Error::<T>::XcmCannotEncodeSellRequest)?;
+			XcmSellOrders::<T>::insert(
+				query_id,
+				XcmSellOrder {
+					order_id,
+					parachain_id,
+					from_to: from_to.clone(),
+					asset_id: order.pair.base,
+					quote_asset_id: order.pair.quote,
+					amount: order.take.amount,
+				},
+			);
+			let request = XcmSellRequest {
+				order_id: query_id,
+				from_to: sovereign_account,
+				order: Sell::new(
+					remote_asset_id(order.pair.base)?,
+					remote_asset_id(order.pair.quote)?,
+					amount,
+					order.take.limit,
+				),
+				configuration: configuration.configuration_id,
+			};
+			let request = XcmCumulusDispatch::new(
+				configuration.location.method_id.pallet_instance,
+				configuration.location.method_id.method_id,
+				request,
+			);
+			let fees = MultiAsset {
+				id: Concrete(MultiLocation::here()),
+				fun: Fungible(configuration.fee),
+			};
+			let position = MultiAsset {
+				id: Concrete(MultiLocation::new(
+					1,
+					X2(
+						Parachain(T::ParachainId::get().into()),
+						GeneralKey(WeakBoundedVec::force_from(order.pair.base.encode(), None)),
+					),
+				)),
+				fun: Fungible(amount),
+			};
+			let message = Xcm(vec![
+				WithdrawAsset(fees.clone().into()),
+				BuyExecution { fees, weight_limit: Unlimited },
+				ReserveAssetDeposited(position.clone().into()),
+				DepositAsset {
+					assets: Definite(position.into()),
+					max_assets: 1,
+					beneficiary: MultiLocation::new(
+						0,
+						X1(AccountId32 { network: Any, id: sovereign_account }),
+					),
+				},
+				Transact {
+					origin_type: OriginKind::Native,
+					require_weight_at_most: T::XcmSellRequestWeight::get(),
+					call: request.encode().into(),
+				},
+			]);
+			T::XcmSender::send_xcm((Parent, Parachain(parachain_id.into())), message)
+				.map_err(|_| Error::<T>::XcmSellRequestNotSent)?;
+
+			Self::deposit_event(Event::<T>::XcmSellRequestSent { order_id, parachain_id });
+			Ok(order_id)
+		}
 	}
 }
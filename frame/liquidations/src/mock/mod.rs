@@ -3,4 +3,6 @@ pub mod currency;
 #[cfg(test)]
 pub mod governance_registry;
 #[cfg(test)]
+pub mod pricing;
+#[cfg(test)]
 pub mod runtime;
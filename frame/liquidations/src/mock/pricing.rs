@@ -0,0 +1,194 @@
+use super::{
+	currency::{CurrencyId, KUSD, PICA},
+	runtime::{AccountId, Balance, Tokens},
+};
+use composable_traits::{
+	defi::{CurrencyPair, Ratio},
+	dex::{Amm, RedeemableAssets, RemoveLiquiditySimulationResult},
+	oracle::{Oracle, Price},
+};
+use frame_support::{
+	ensure, parameter_types,
+	traits::{fungibles::Mutate, ConstU16, ConstU32},
+};
+use sp_runtime::{ArithmeticError, DispatchError, FixedPointNumber};
+use sp_std::collections::btree_map::BTreeMap;
+
+pub type PoolId = u32;
+
+/// The only pool of [`MockAmm`], trading `KUSD` for `PICA`.
+pub const POOL_ID: PoolId = 1;
+
+/// The error of the operations of the mocks that liquidations never calls.
+const NOT_USED_BY_LIQUIDATIONS: DispatchError = DispatchError::Other("not used by liquidations");
+
+parameter_types! {
+	/// `PICA` received per `KUSD` sold on [`POOL_ID`].
+	pub static PoolRatio: Ratio = Ratio::saturating_from_integer(1);
+	/// `PICA` per `KUSD` according to [`MockOracle`].
+	pub static OracleRatio: Ratio = Ratio::saturating_from_integer(1);
+}
+
+/// Constant rate pool, minting what it gives out.
+pub struct MockAmm;
+
+impl Amm for MockAmm {
+	type AssetId = CurrencyId;
+	type Balance = Balance;
+	type AccountId = AccountId;
+	type PoolId = PoolId;
+
+	fn pool_exists(pool_id: Self::PoolId) -> bool {
+		pool_id == POOL_ID
+	}
+
+	fn currency_pair(pool_id: Self::PoolId) -> Result<CurrencyPair<Self::AssetId>, DispatchError> {
+		ensure!(Self::pool_exists(pool_id), DispatchError::Other("pool not found"));
+		Ok(CurrencyPair::new(KUSD, PICA))
+	}
+
+	fn lp_token(_pool_id: Self::PoolId) -> Result<Self::AssetId, DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+
+	fn redeemable_assets_for_lp_tokens(
+		_pool_id: Self::PoolId,
+		_lp_amount: Self::Balance,
+		_min_expected_amounts: BTreeMap<Self::AssetId, Self::Balance>,
+	) -> Result<RedeemableAssets<Self::AssetId, Self::Balance>, DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+
+	fn simulate_add_liquidity(
+		_who: &Self::AccountId,
+		_pool_id: Self::PoolId,
+		_amounts: BTreeMap<Self::AssetId, Self::Balance>,
+	) -> Result<Self::Balance, DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+
+	fn simulate_remove_liquidity(
+		_who: &Self::AccountId,
+		_pool_id: Self::PoolId,
+		_lp_amount: Self::Balance,
+		_min_expected_amounts: BTreeMap<Self::AssetId, Self::Balance>,
+	) -> Result<RemoveLiquiditySimulationResult<Self::AssetId, Self::Balance>, DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+
+	fn get_exchange_value(
+		_pool_id: Self::PoolId,
+		_asset_id: Self::AssetId,
+		_amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+
+	fn buy(
+		_who: &Self::AccountId,
+		_pool_id: Self::PoolId,
+		_asset_id: Self::AssetId,
+		_amount: Self::Balance,
+		_min_receive: Self::Balance,
+		_keep_alive: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+
+	fn sell(
+		who: &Self::AccountId,
+		pool_id: Self::PoolId,
+		asset_id: Self::AssetId,
+		amount: Self::Balance,
+		min_receive: Self::Balance,
+		_keep_alive: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		let pair = Self::currency_pair(pool_id)?;
+		let (ratio, asset_received) = if asset_id == pair.base {
+			(PoolRatio::get(), pair.quote)
+		} else {
+			(PoolRatio::get().reciprocal().ok_or(ArithmeticError::DivisionByZero)?, pair.base)
+		};
+		let received = ratio.checked_mul_int(amount).ok_or(ArithmeticError::Overflow)?;
+		ensure!(received >= min_receive, DispatchError::Other("cannot satisfy min_receive"));
+		<Tokens as Mutate<AccountId>>::burn_from(asset_id, who, amount)?;
+		<Tokens as Mutate<AccountId>>::mint_into(asset_received, who, received)?;
+		Ok(received)
+	}
+
+	fn add_liquidity(
+		_who: &Self::AccountId,
+		_pool_id: Self::PoolId,
+		_base_amount: Self::Balance,
+		_quote_amount: Self::Balance,
+		_min_mint_amount: Self::Balance,
+		_keep_alive: bool,
+	) -> Result<(), DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+
+	fn remove_liquidity(
+		_who: &Self::AccountId,
+		_pool_id: Self::PoolId,
+		_lp_amount: Self::Balance,
+		_min_base_amount: Self::Balance,
+		_min_quote_amount: Self::Balance,
+	) -> Result<(), DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+
+	fn exchange(
+		_who: &Self::AccountId,
+		_pool_id: Self::PoolId,
+		_pair: CurrencyPair<Self::AssetId>,
+		_quote_amount: Self::Balance,
+		_min_receive: Self::Balance,
+		_keep_alive: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+}
+
+/// Only knows the ratio of the pair of [`POOL_ID`].
+pub struct MockOracle;
+
+impl Oracle for MockOracle {
+	type AssetId = CurrencyId;
+	type Balance = Balance;
+	type Timestamp = ();
+	type LocalAssets = ();
+	type MaxAnswerBound = ConstU32<0>;
+	type TwapWindow = ConstU16<0>;
+
+	fn get_price(
+		_asset_id: Self::AssetId,
+		_amount: Self::Balance,
+	) -> Result<Price<Self::Balance, Self::Timestamp>, DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+
+	fn get_twap_for_amount(
+		_asset_id: Self::AssetId,
+		_amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+
+	fn get_ratio(pair: CurrencyPair<Self::AssetId>) -> Result<Ratio, DispatchError> {
+		ensure!(pair == CurrencyPair::new(KUSD, PICA), DispatchError::Other("no price"));
+		if pair.base == KUSD {
+			Ok(OracleRatio::get())
+		} else {
+			OracleRatio::get()
+				.reciprocal()
+				.ok_or_else(|| ArithmeticError::DivisionByZero.into())
+		}
+	}
+
+	fn get_price_inverse(
+		_asset_id: Self::AssetId,
+		_amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		Err(NOT_USED_BY_LIQUIDATIONS)
+	}
+}
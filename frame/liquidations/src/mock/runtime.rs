@@ -1,6 +1,9 @@
 use crate::{
 	self as pallet_liquidations,
-	mock::currency::{CurrencyId, NativeAssetId},
+	mock::{
+		currency::{CurrencyId, NativeAssetId},
+		pricing::{MockAmm, MockOracle, PoolId},
+	},
 	weights::SubstrateWeight,
 };
 
@@ -14,6 +17,7 @@ use frame_support::{
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use hex_literal::hex;
 use orml_traits::parameter_type_with_key;
+use polkadot_parachain::primitives::Id as ParaId;
 use primitives::currency::ValidateCurrencyId;
 use smallvec::smallvec;
 use sp_core::{
@@ -25,7 +29,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
 	Perbill,
 };
-use xcm::latest::SendXcm;
+use xcm::latest::{MultiLocation, SendXcm, Xcm};
 
 use super::governance_registry::GovernanceRegistry;
 
@@ -53,6 +57,7 @@ frame_support::construct_runtime! {
 		Assets: pallet_assets::{Pallet, Call, Storage},
 		DutchAuction: pallet_dutch_auction::{Pallet, Call, Storage, Event<T>},
 		Liquidations: pallet_liquidations::{Pallet, Call, Storage, Event<T>},
+		CumulusXcm: cumulus_pallet_xcm::{Pallet, Call, Event<T>, Origin},
 	}
 }
 
@@ -219,6 +224,22 @@ impl SendXcm for XcmFake {
 	}
 }
 
+parameter_types! {
+	/// Messages sent by liquidations, in order.
+	pub static SentXcm: Vec<(MultiLocation, Xcm<()>)> = vec![];
+}
+
+pub struct XcmRecorder;
+impl SendXcm for XcmRecorder {
+	fn send_xcm(
+		destination: impl Into<MultiLocation>,
+		message: Xcm<()>,
+	) -> xcm::latest::SendResult {
+		SENT_XCM.with(|sent| sent.borrow_mut().push((destination.into(), message)));
+		Ok(())
+	}
+}
+
 impl pallet_dutch_auction::Config for Runtime {
 	type Event = Event;
 	type UnixTime = Timestamp;
@@ -235,6 +256,8 @@ impl pallet_dutch_auction::Config for Runtime {
 
 parameter_types! {
 	pub const LiquidationPalletId : PalletId = PalletId(*b"liqudatn");
+	pub ParachainId: ParaId = ParaId::from(2000);
+	pub const XcmSellRequestWeight: u64 = 1_000_000_000;
 }
 
 type LiquidationStrategyId = u32;
@@ -247,8 +270,20 @@ impl pallet_liquidations::Config for Runtime {
 	type LiquidationStrategyId = LiquidationStrategyId;
 	type PalletId = LiquidationPalletId;
 	type CanModifyStrategies = EnsureRoot<Self::AccountId>;
-	type XcmSender = XcmFake;
+	type XcmSender = XcmRecorder;
+	type XcmOrigin = Origin;
 	type MaxLiquidationStrategiesAmount = ConstU32<3>;
+	type Amm = MockAmm;
+	type PoolId = PoolId;
+	type Oracle = MockOracle;
+	type MultiCurrency = Assets;
+	type ParachainId = ParachainId;
+	type XcmSellRequestWeight = XcmSellRequestWeight;
+}
+
+impl cumulus_pallet_xcm::Config for Runtime {
+	type Event = Event;
+	type XcmExecutor = ();
 }
 
 #[allow(dead_code)] // not really dead
@@ -1,17 +1,31 @@
 use crate::{
 	self as pallet_liquidations,
-	mock::{currency::*, runtime::*},
+	mock::{currency::*, pricing::*, runtime::*},
+	LiquidationStrategyConfiguration,
 };
 use codec::Encode;
 use composable_traits::{
 	defi::{Ratio, Sell},
 	liquidation::Liquidation,
+	time::{LinearDecrease, TimeReleaseFunction},
+	xcm::{
+		SellResponse, XcmSellInitialResponseTransact, XcmSellRequestTransactConfiguration,
+		XcmTransactConfiguration,
+	},
 };
 use frame_support::{
-	assert_noop,
-	traits::{fungible::Mutate as NativeMutate, fungibles::Mutate},
+	assert_noop, assert_ok,
+	traits::{
+		fungible::Mutate as NativeMutate,
+		fungibles::{Inspect, Mutate},
+	},
 };
-use sp_runtime::{FixedPointNumber, FixedU128};
+use polkadot_parachain::primitives::{Id as ParaId, Sibling};
+use sp_runtime::{
+	traits::{AccountIdConversion, BadOrigin},
+	FixedPointNumber, FixedU128, Perquintill,
+};
+use xcm::latest::{prelude::*, MultiLocation};
 
 // ensure that we take extra for sell, at least amount to remove
 #[test]
@@ -93,4 +107,269 @@ fn pallet_do_not_treat_infinitely_large_strategies_vector() {
 	});
 }
 
-// TODO: add XCM end to end tests with callbacks
+fn add_strategy(configuration: LiquidationStrategyConfiguration<PoolId>) -> u32 {
+	assert_ok!(Liquidations::add_liquidation_strategy(Origin::root(), configuration));
+	Liquidations::strategy_index()
+}
+
+fn pablo(slippage: Perquintill) -> u32 {
+	add_strategy(LiquidationStrategyConfiguration::Pablo { pool_id: POOL_ID, slippage })
+}
+
+fn dutch_auction() -> u32 {
+	add_strategy(LiquidationStrategyConfiguration::DutchAuction(
+		TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 10 * 60 }),
+	))
+}
+
+#[test]
+fn pablo_swaps_position() {
+	new_test_externalities().execute_with(|| {
+		let who = AccountId::from_raw(ALICE.0);
+		Tokens::mint_into(KUSD, &who, 1_000).unwrap();
+		OracleRatio::set(Ratio::saturating_from_integer(2));
+		PoolRatio::set(Ratio::saturating_from_rational(19, 10));
+		let strategy = pablo(Perquintill::from_percent(10));
+
+		let order_id = <Liquidations as Liquidation>::liquidate(
+			&who,
+			Sell::new(KUSD, PICA, 100, Ratio::saturating_from_integer(2)),
+			vec![strategy],
+		)
+		.expect("swap is within slippage of the oracle price");
+
+		System::assert_has_event(Event::Liquidations(crate::Event::PositionWasSwapped {
+			order_id,
+			pool_id: POOL_ID,
+			amount: 100,
+			received: 190,
+		}));
+		System::assert_last_event(Event::Liquidations(
+			crate::Event::PositionWasSentToLiquidation {},
+		));
+		assert_eq!(Tokens::balance(KUSD, &who), 900);
+		assert_eq!(Tokens::balance(PICA, &who), 190);
+	});
+}
+
+#[test]
+fn pablo_with_wrong_pair_fails() {
+	new_test_externalities().execute_with(|| {
+		let who = AccountId::from_raw(ALICE.0);
+		let not_in_pool = KUSD + 1;
+		Tokens::mint_into(not_in_pool, &who, 1_000).unwrap();
+		let strategy = pablo(Perquintill::from_percent(10));
+
+		assert!(<Liquidations as Liquidation>::liquidate(
+			&who,
+			Sell::new(not_in_pool, PICA, 100, Ratio::saturating_from_integer(1)),
+			vec![strategy],
+		)
+		.is_err());
+		System::assert_has_event(Event::Liquidations(crate::Event::LiquidationStrategyFailed {
+			strategy_id: strategy,
+			error: crate::Error::<Runtime>::PoolPairMismatch.into(),
+		}));
+	});
+}
+
+#[test]
+fn falls_back_to_next_strategy_on_slippage() {
+	new_test_externalities().execute_with(|| {
+		let who = AccountId::from_raw(ALICE.0);
+		Tokens::mint_into(KUSD, &who, 1_000).unwrap();
+		<Balances as NativeMutate<_>>::mint_into(&who, NativeExistentialDeposit::get() * 3)
+			.unwrap();
+		OracleRatio::set(Ratio::saturating_from_integer(2));
+		PoolRatio::set(Ratio::saturating_from_rational(17, 10));
+		let swap = pablo(Perquintill::from_percent(10));
+		let auction = dutch_auction();
+
+		let order_id = <Liquidations as Liquidation>::liquidate(
+			&who,
+			Sell::new(KUSD, PICA, 100, Ratio::saturating_from_integer(2)),
+			vec![swap, auction],
+		)
+		.expect("auction takes the position the pool could not");
+
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::Liquidations(crate::Event::LiquidationStrategyFailed { strategy_id, .. })
+				if strategy_id == swap
+		)));
+		let order =
+			pallet_dutch_auction::SellOrders::<Runtime>::get(order_id).expect("order was placed");
+		assert_eq!(order.order.take.amount, 100);
+		assert_eq!(Tokens::balance(PICA, &who), 0);
+	});
+}
+
+fn xcm(engine: ParaId) -> u32 {
+	add_strategy(LiquidationStrategyConfiguration::Xcm(XcmSellRequestTransactConfiguration {
+		location: XcmTransactConfiguration::new(engine, 42, 1),
+		configuration_id: 7,
+		fee: 10,
+	}))
+}
+
+/// Sends 100 KUSD out of the 1000 of `who` to be sold by `engine`.
+fn send_to_engine(who: &AccountId, engine: ParaId) -> OrderId {
+	Tokens::mint_into(KUSD, who, 1_000).unwrap();
+	let strategy = xcm(engine);
+	<Liquidations as Liquidation>::liquidate(
+		who,
+		Sell::new(KUSD, PICA, 100, Ratio::saturating_from_integer(2)),
+		vec![strategy],
+	)
+	.expect("position is sent to the engine")
+}
+
+fn sell_response(
+	order_id: OrderId,
+	total_amount_taken: u128,
+	price: u128,
+	is_final: bool,
+) -> SellResponse {
+	let response = XcmSellInitialResponseTransact {
+		total_amount_taken,
+		minimal_price: price,
+		order_id: order_id.into(),
+	};
+	if is_final {
+		SellResponse::Final(response)
+	} else {
+		SellResponse::Initial(response)
+	}
+}
+
+fn sibling(engine: ParaId) -> Origin {
+	cumulus_pallet_xcm::Origin::SiblingParachain(engine).into()
+}
+
+#[test]
+fn xcm_sends_sell_request_to_engine() {
+	new_test_externalities().execute_with(|| {
+		let who = AccountId::from_raw(ALICE.0);
+		let engine = ParaId::from(3000);
+		let order_id = send_to_engine(&who, engine);
+
+		System::assert_has_event(Event::Liquidations(crate::Event::XcmSellRequestSent {
+			order_id,
+			parachain_id: engine,
+		}));
+		let engine_account: AccountId = Sibling::from(engine).into_account_truncating();
+		assert_eq!(Tokens::balance(KUSD, &who), 900);
+		assert_eq!(Tokens::balance(KUSD, &engine_account), 100);
+
+		let sent = SentXcm::get();
+		assert_eq!(sent.len(), 1);
+		let (destination, message) = &sent[0];
+		assert_eq!(destination, &MultiLocation::new(1, X1(Parachain(3000))));
+		assert!(matches!(
+			message.0.last(),
+			Some(Transact { origin_type: OriginKind::Native, require_weight_at_most, .. })
+				if *require_weight_at_most == XcmSellRequestWeight::get()
+		));
+	});
+}
+
+#[test]
+fn xcm_sell_response_settles_position() {
+	new_test_externalities().execute_with(|| {
+		let who = AccountId::from_raw(ALICE.0);
+		let engine = ParaId::from(3000);
+		let engine_account: AccountId = Sibling::from(engine).into_account_truncating();
+		let order_id = send_to_engine(&who, engine);
+		assert!(Liquidations::xcm_sell_order(u64::from(order_id)).is_some());
+		// reserve of the proceeds of the engine
+		Tokens::mint_into(PICA, &engine_account, 1_000).unwrap();
+
+		assert_ok!(Liquidations::xcm_sell_response(
+			sibling(engine),
+			sell_response(order_id, 60, 120, true)
+		));
+
+		System::assert_last_event(Event::Liquidations(crate::Event::XcmSellOrderSettled {
+			order_id,
+			amount_sold: 60,
+			amount_returned: 40,
+			received: 120,
+		}));
+		assert_eq!(Tokens::balance(KUSD, &who), 940);
+		assert_eq!(Tokens::balance(KUSD, &engine_account), 60);
+		assert_eq!(Tokens::balance(PICA, &who), 120);
+		assert_eq!(Tokens::balance(PICA, &engine_account), 880);
+		assert!(Liquidations::xcm_sell_order(u64::from(order_id)).is_none());
+	});
+}
+
+#[test]
+fn xcm_sell_response_returns_what_the_engine_could_not_sell() {
+	new_test_externalities().execute_with(|| {
+		let who = AccountId::from_raw(ALICE.0);
+		let engine = ParaId::from(3000);
+		let engine_account: AccountId = Sibling::from(engine).into_account_truncating();
+		let order_id = send_to_engine(&who, engine);
+
+		assert_ok!(Liquidations::xcm_sell_response(
+			sibling(engine),
+			sell_response(order_id, 80, 160, false)
+		));
+		System::assert_last_event(Event::Liquidations(crate::Event::XcmSellOrderTaken {
+			order_id,
+			amount_taken: 80,
+			amount_returned: 20,
+		}));
+		assert_eq!(Tokens::balance(KUSD, &who), 920);
+		let order =
+			Liquidations::xcm_sell_order(u64::from(order_id)).expect("order is still pending");
+		assert_eq!(order.amount, 80);
+
+		// nothing sold in the end, the whole position is back
+		assert_ok!(Liquidations::xcm_sell_response(
+			sibling(engine),
+			sell_response(order_id, 0, 0, true)
+		));
+		assert_eq!(Tokens::balance(KUSD, &who), 1_000);
+		assert_eq!(Tokens::balance(KUSD, &engine_account), 0);
+		assert!(Liquidations::xcm_sell_order(u64::from(order_id)).is_none());
+	});
+}
+
+#[test]
+fn xcm_sell_response_is_only_accepted_from_the_engine_of_the_order() {
+	new_test_externalities().execute_with(|| {
+		let who = AccountId::from_raw(ALICE.0);
+		let engine = ParaId::from(3000);
+		let order_id = send_to_engine(&who, engine);
+
+		assert_noop!(
+			Liquidations::xcm_sell_response(
+				Origin::signed(who),
+				sell_response(order_id, 100, 200, true)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			Liquidations::xcm_sell_response(
+				sibling(ParaId::from(3001)),
+				sell_response(order_id, 100, 200, true)
+			),
+			crate::Error::<Runtime>::XcmSellOrderNotFound
+		);
+		assert_noop!(
+			Liquidations::xcm_sell_response(
+				sibling(engine),
+				sell_response(order_id + 1, 100, 200, true)
+			),
+			crate::Error::<Runtime>::XcmSellOrderNotFound
+		);
+		assert_noop!(
+			Liquidations::xcm_sell_response(
+				sibling(engine),
+				sell_response(order_id, 101, 200, true)
+			),
+			crate::Error::<Runtime>::XcmSellResponseExceedsPosition
+		);
+	});
+}
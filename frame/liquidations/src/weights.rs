@@ -9,6 +9,7 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn add_liquidation_strategy() -> Weight;
 	fn sell(vector_length: u32) -> Weight;
+	fn xcm_sell_response() -> Weight;
 }
 
 /// Weight functions for `liquidations`.
@@ -35,4 +36,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(x as Weight)))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Storage: Liquidations XcmSellOrders (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn xcm_sell_response() -> Weight {
+		(36_412_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 }
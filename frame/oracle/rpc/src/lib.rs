@@ -0,0 +1,147 @@
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::oracle::{CurrentPrice, Ohlc, OracleAccuracy, Price};
+use core::{fmt::Display, str::FromStr};
+use jsonrpsee::{
+	core::{Error as RpcError, RpcResult},
+	proc_macros::rpc,
+	types::{error::CallError, ErrorObject},
+};
+use oracle_runtime_api::OracleRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_std::sync::Arc;
+
+#[rpc(client, server)]
+pub trait OracleApi<BlockHash, AssetId, PriceValue, BlockNumber, AccountId>
+where
+	AssetId: FromStr + Display,
+	PriceValue: FromStr + Display,
+	BlockNumber: FromStr + Display,
+	AccountId: FromStr + Display,
+{
+	#[method(name = "oracle_currentPrice")]
+	fn current_price(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<CurrentPrice<SafeRpcWrapper<PriceValue>, SafeRpcWrapper<BlockNumber>>>>;
+
+	#[method(name = "oracle_priceHistory")]
+	fn price_history(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Price<SafeRpcWrapper<PriceValue>, SafeRpcWrapper<BlockNumber>>>>;
+
+	#[method(name = "oracle_twap")]
+	fn twap(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		window: SafeRpcWrapper<BlockNumber>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<SafeRpcWrapper<PriceValue>>>;
+
+	#[method(name = "oracle_ohlc")]
+	fn ohlc(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		bucket_size: SafeRpcWrapper<BlockNumber>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Ohlc<SafeRpcWrapper<PriceValue>, SafeRpcWrapper<BlockNumber>>>>;
+
+	#[method(name = "oracle_oracleAccuracy")]
+	fn oracle_accuracy(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<OracleAccuracy<SafeRpcWrapper<AccountId>>>>;
+}
+
+pub struct Oracle<C, Block> {
+	client: Arc<C>,
+	_marker: sp_std::marker::PhantomData<Block>,
+}
+
+impl<C, M> Oracle<C, M> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError::Call(CallError::Custom(ErrorObject::owned(
+		9876, // no real reason for this value
+		"Something wrong",
+		Some(format!("{:?}", e)),
+	)))
+}
+
+impl<C, Block, AssetId, PriceValue, BlockNumber, AccountId>
+	OracleApiServer<<Block as BlockT>::Hash, AssetId, PriceValue, BlockNumber, AccountId>
+	for Oracle<C, (Block, AssetId, PriceValue, BlockNumber, AccountId)>
+where
+	Block: BlockT,
+	AssetId: Send + Sync + 'static + Codec + FromStr + Display,
+	PriceValue: Send + Sync + 'static + Codec + FromStr + Display,
+	BlockNumber: Send + Sync + 'static + Codec + FromStr + Display,
+	AccountId: Send + Sync + 'static + Codec + FromStr + Display,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: OracleRuntimeApi<Block, AssetId, PriceValue, BlockNumber, AccountId>,
+{
+	fn current_price(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<CurrentPrice<SafeRpcWrapper<PriceValue>, SafeRpcWrapper<BlockNumber>>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.current_price(&at, asset_id).map_err(runtime_error_into_rpc_error)
+	}
+
+	fn price_history(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Price<SafeRpcWrapper<PriceValue>, SafeRpcWrapper<BlockNumber>>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.price_history(&at, asset_id).map_err(runtime_error_into_rpc_error)
+	}
+
+	fn twap(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		window: SafeRpcWrapper<BlockNumber>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<SafeRpcWrapper<PriceValue>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.twap(&at, asset_id, window).map_err(runtime_error_into_rpc_error)
+	}
+
+	fn ohlc(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		bucket_size: SafeRpcWrapper<BlockNumber>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Ohlc<SafeRpcWrapper<PriceValue>, SafeRpcWrapper<BlockNumber>>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.ohlc(&at, asset_id, bucket_size).map_err(runtime_error_into_rpc_error)
+	}
+
+	fn oracle_accuracy(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<OracleAccuracy<SafeRpcWrapper<AccountId>>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.oracle_accuracy(&at, asset_id).map_err(runtime_error_into_rpc_error)
+	}
+}
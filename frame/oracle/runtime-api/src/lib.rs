@@ -0,0 +1,48 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::oracle::{CurrentPrice, Ohlc, OracleAccuracy, Price};
+use sp_std::vec::Vec;
+
+// Oracle Runtime API declaration. Implemented for each runtime at
+// `runtime/<runtime-name>/src/lib.rs`.
+sp_api::decl_runtime_apis! {
+	pub trait OracleRuntimeApi<AssetId, PriceValue, BlockNumber, AccountId>
+	where
+		AssetId: Codec,
+		PriceValue: Codec,
+		BlockNumber: Codec,
+		AccountId: Codec,
+	{
+		/// Retrieve the price of one unit of `asset_id`, as consumers of the oracle get it.
+		fn current_price(
+			asset_id: SafeRpcWrapper<AssetId>,
+		) -> Option<CurrentPrice<SafeRpcWrapper<PriceValue>, SafeRpcWrapper<BlockNumber>>>;
+
+		/// Retrieve the latest prices of `asset_id`, oldest first.
+		fn price_history(
+			asset_id: SafeRpcWrapper<AssetId>,
+		) -> Vec<Price<SafeRpcWrapper<PriceValue>, SafeRpcWrapper<BlockNumber>>>;
+
+		/// Retrieve the time weighted average price of `asset_id` over the last `window` blocks.
+		fn twap(
+			asset_id: SafeRpcWrapper<AssetId>,
+			window: SafeRpcWrapper<BlockNumber>,
+		) -> Option<SafeRpcWrapper<PriceValue>>;
+
+		/// Retrieve the open, high, low and close prices of `asset_id` in buckets of
+		/// `bucket_size` blocks.
+		fn ohlc(
+			asset_id: SafeRpcWrapper<AssetId>,
+			bucket_size: SafeRpcWrapper<BlockNumber>,
+		) -> Vec<Ohlc<SafeRpcWrapper<PriceValue>, SafeRpcWrapper<BlockNumber>>>;
+
+		/// Retrieve the accuracy of the prices submitted by each oracle for `asset_id`.
+		fn oracle_accuracy(
+			asset_id: SafeRpcWrapper<AssetId>,
+		) -> Vec<OracleAccuracy<SafeRpcWrapper<AccountId>>>;
+	}
+}
@@ -3,6 +3,7 @@ use super::*;
 use crate::validation::ValidBlockInterval;
 #[allow(unused)]
 use crate::Pallet as Oracle;
+use codec::Decode;
 use composable_support::validation::Validated;
 use composable_traits::oracle::Price;
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
@@ -12,7 +13,7 @@ use frame_support::{
 	BoundedVec,
 };
 use frame_system::{EventRecord, RawOrigin};
-use sp_runtime::{DispatchResult, Percent};
+use sp_runtime::{traits::TrailingZeroInput, DispatchResult, Percent};
 
 use sp_std::{prelude::*, vec};
 
@@ -123,6 +124,7 @@ benchmarks! {
 			reward_weight: T::Currency::minimum_balance(),
 			slash: T::Currency::minimum_balance(),
 			emit_price_changes: false,
+			max_price_change: None,
 		});
 		frame_system::Pallet::<T>::set_block_number(6u32.into());
 		PrePrices::<T>::mutate(asset_id, |current_prices| -> DispatchResult {
@@ -154,6 +156,7 @@ benchmarks! {
 			reward_weight: T::Currency::minimum_balance(),
 			slash: T::Currency::minimum_balance(),
 			emit_price_changes: false,
+			max_price_change: None,
 		};
 		let pre_prices = (0..p).map(|i| {
 			PrePrice {
@@ -181,6 +184,7 @@ benchmarks! {
 			reward_weight: T::Currency::minimum_balance(),
 			slash: T::Currency::minimum_balance(),
 			emit_price_changes: false,
+			max_price_change: None,
 		};
 		let pre_prices = (0..p).map(|_| {
 			PrePrice {
@@ -197,6 +201,164 @@ benchmarks! {
 	}: {
 		Oracle::<T>::update_price(asset_id, asset_info.into(), block, pre_prices)
 	}
+
+	set_circuit_breaker {
+		let asset_id: T::AssetId = T::AssetId::from(1u128);
+		AssetsInfo::<T>::insert(asset_id, AssetInfo::default());
+		let max_price_change = Some(Percent::from_percent(10));
+	}: _(RawOrigin::Root, asset_id, max_price_change)
+	verify {
+		assert_last_event::<T>(Event::CircuitBreakerSet(asset_id, max_price_change).into());
+	}
+
+	unfreeze_price {
+		let asset_id: T::AssetId = T::AssetId::from(1u128);
+		let block = frame_system::Pallet::<T>::block_number();
+		let price = Price { price: 100u32.into(), block };
+		let historic_prices = BoundedVec::try_from(vec![price.clone(); T::MaxHistory::get() as usize]).unwrap();
+		PriceHistory::<T>::insert(asset_id, historic_prices);
+		FrozenPrices::<T>::insert(asset_id, price);
+		let new_price: Option<T::PriceValue> = Some(110u32.into());
+	}: _(RawOrigin::Root, asset_id, new_price)
+	verify {
+		assert_last_event::<T>(Event::PriceUnfrozen(asset_id, new_price).into());
+	}
+
+	set_price_fallbacks {
+		let s in 1 .. T::MaxFallbacks::get();
+		let asset_id: T::AssetId = T::AssetId::from(1u128);
+		AssetsInfo::<T>::insert(asset_id, AssetInfo::default());
+		let pool_id = T::PoolId::decode(&mut TrailingZeroInput::zeroes()).unwrap();
+		let sources = (0..s).map(|quote| FallbackSource::PoolTwap { pool_id, quote: T::AssetId::from(quote as u128 + 2) }).collect::<Vec<_>>();
+		let sources = BoundedVec::try_from(sources).unwrap();
+	}: _(RawOrigin::Root, asset_id, 10u32.into(), sources)
+	verify {
+		assert_last_event::<T>(Event::PriceFallbacksSet(asset_id).into());
+	}
+
+	set_commit_reveal {
+		let p in 1 .. T::MaxAnswerBound::get();
+		let asset_id: T::AssetId = T::AssetId::from(1u128);
+		AssetsInfo::<T>::insert(asset_id, AssetInfo::default());
+		let block = frame_system::Pallet::<T>::block_number();
+		// the worst scenario is disabling commit-reveal, releasing all the commitments
+		let commitments = (0..p).map(|c| PriceCommitment {
+			commitment: T::Hash::default(),
+			block,
+			who: account("candidate", c, SEED),
+			revealed: false,
+		})
+		.collect::<Vec<_>>();
+		PriceCommitments::<T>::insert(asset_id, BoundedVec::try_from(commitments).unwrap());
+	}: _(RawOrigin::Root, asset_id, None)
+	verify {
+		assert_last_event::<T>(Event::CommitRevealSet(asset_id, None).into());
+	}
+
+	commit_price {
+		let p in 1 .. T::MaxAnswerBound::get();
+		let p = p - 1; // We will commit now, then the number of commitments will equal T::MaxAnswerBound::get().
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id: T::AssetId = 1.into();
+		OracleStake::<T>::insert(&caller, T::MinStake::get());
+		AssetsInfo::<T>::insert(asset_id, AssetInfo {
+			threshold: Percent::from_percent(80),
+			min_answers: 1,
+			max_answers: T::MaxAnswerBound::get(),
+			block_interval: 0u32.into(),
+			reward_weight: T::Currency::minimum_balance(),
+			slash: T::Currency::minimum_balance(),
+			emit_price_changes: false,
+			max_price_change: None,
+		});
+		CommitRevealPeriods::<T>::insert(asset_id, CommitRevealPeriod {
+			commit_period: 10u32.into(),
+			reveal_period: 10u32.into(),
+		});
+		frame_system::Pallet::<T>::set_block_number(6u32.into());
+		let block = frame_system::Pallet::<T>::block_number();
+		let commitments = (0..p).map(|c| PriceCommitment {
+			commitment: T::Hash::default(),
+			block,
+			who: account("candidate", c, SEED),
+			revealed: false,
+		})
+		.collect::<Vec<_>>();
+		PriceCommitments::<T>::insert(asset_id, BoundedVec::try_from(commitments).unwrap());
+		let commitment = Oracle::<T>::price_commitment(&caller, 100_000u128.into(), &[0; 32]);
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, commitment)
+	verify {
+		assert_last_event::<T>(Event::PriceCommitted(caller, asset_id).into())
+	}
+
+	reveal_price {
+		let p in 1 .. T::MaxAnswerBound::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id: T::AssetId = 1.into();
+		let price: T::PriceValue = 100_000u128.into();
+		let salt = [0; 32];
+		CommitRevealPeriods::<T>::insert(asset_id, CommitRevealPeriod {
+			commit_period: 10u32.into(),
+			reveal_period: 10u32.into(),
+		});
+		let block = frame_system::Pallet::<T>::block_number();
+		// the caller is the last to reveal
+		let mut commitments = (1..p).map(|c| PriceCommitment {
+			commitment: T::Hash::default(),
+			block,
+			who: account("candidate", c, SEED),
+			revealed: true,
+		})
+		.collect::<Vec<_>>();
+		commitments.push(PriceCommitment {
+			commitment: Oracle::<T>::price_commitment(&caller, price, &salt),
+			block,
+			who: caller.clone(),
+			revealed: false,
+		});
+		PriceCommitments::<T>::insert(asset_id, BoundedVec::try_from(commitments).unwrap());
+		let pre_prices = (1..p).map(|c| PrePrice {
+			price,
+			block,
+			who: account("candidate", c, SEED),
+		})
+		.collect::<Vec<_>>();
+		PrePrices::<T>::insert(asset_id, BoundedVec::try_from(pre_prices).unwrap());
+		frame_system::Pallet::<T>::set_block_number(block + 10u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, price, salt)
+	verify {
+		assert_last_event::<T>(Event::PriceRevealed(caller, asset_id, price).into())
+	}
+
+	close_commit_reveal_round {
+		let p in 1 .. T::MaxAnswerBound::get();
+		let asset_id: T::AssetId = 1.into();
+		let asset_info = AssetInfo {
+			threshold: Percent::from_percent(80),
+			min_answers: 1,
+			max_answers: p,
+			block_interval: T::StalePrice::get(),
+			reward_weight: T::Currency::minimum_balance(),
+			slash: T::Currency::minimum_balance(),
+			emit_price_changes: false,
+			max_price_change: None,
+		};
+		let period = CommitRevealPeriod { commit_period: 10u32.into(), reveal_period: 10u32.into() };
+		let block = frame_system::Pallet::<T>::block_number();
+		// the worst scenario is when no price was revealed, every oracle being slashed
+		let commitments = (0..p).map(|c| {
+			let who: T::AccountId = account("candidate", c, SEED);
+			OracleStake::<T>::insert(&who, T::MinStake::get());
+			PriceCommitment { commitment: T::Hash::default(), block, who, revealed: false }
+		})
+		.collect::<Vec<_>>();
+		PriceCommitments::<T>::insert(asset_id, BoundedVec::try_from(commitments).unwrap());
+	}: {
+		Oracle::<T>::close_commit_reveal_round(asset_id, &asset_info, &period, block + 20u32.into())
+	}
+	verify {
+		assert!(PriceCommitments::<T>::get(asset_id).is_empty());
+	}
 }
 
 impl_benchmark_test_suite!(Oracle, crate::mock::new_test_ext(), crate::mock::Test,);
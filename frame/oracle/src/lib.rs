@@ -17,6 +17,8 @@ pub use pallet::*;
 
 mod validation;
 
+pub mod migrations;
+
 #[cfg(test)]
 mod mock;
 
@@ -45,7 +47,9 @@ pub mod pallet {
 	};
 	use composable_traits::{
 		currency::{BalanceLike, LocalAssets},
-		oracle::{Oracle, Price, RewardTracker},
+		defi::CurrencyPair,
+		dex::PoolTwap,
+		oracle::{CurrentPrice, Ohlc, Oracle, OracleAccuracy, Price, RewardTracker},
 		time::MS_PER_YEAR_NAIVE,
 	};
 	use frame_support::{
@@ -62,19 +66,19 @@ pub mod pallet {
 	};
 	use frame_system::{
 		offchain::{
-			AppCrypto, CreateSignedTransaction, SendSignedTransaction, SignedPayload, Signer,
-			SigningTypes,
+			AppCrypto, CreateSignedTransaction, ForAll, SendSignedTransaction, SignedPayload,
+			Signer, SigningTypes,
 		},
 		pallet_prelude::*,
 	};
 	use lite_json::json::JsonValue;
 	use scale_info::TypeInfo;
-	use sp_core::crypto::KeyTypeId;
+	use sp_core::{crypto::KeyTypeId, sr25519};
 	use sp_runtime::{
-		offchain::{http, Duration},
+		offchain::{http, storage::StorageValueRef, Duration},
 		traits::{
 			AtLeast32Bit, AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub,
-			Saturating, UniqueSaturatedInto as _, Zero,
+			Hash as _, One, Saturating, UniqueSaturatedInto as _, Zero,
 		},
 		AccountId32, ArithmeticError, FixedPointNumber, FixedU128, KeyTypeId as CryptoKeyTypeId,
 		PerThing, Percent, RuntimeDebug,
@@ -87,6 +91,12 @@ pub mod pallet {
 	pub const KEY_ID: [u8; 4] = *b"orac";
 	pub const KEY_TYPE: KeyTypeId = KeyTypeId(KEY_ID);
 	pub const CRYPTO_KEY_TYPE: CryptoKeyTypeId = CryptoKeyTypeId(KEY_ID);
+	// Prefix of the offchain storage of the prices committed by the offchain worker
+	const COMMITTED_PRICE_KEY: &[u8] = b"oracle::committed_price";
+	// Maximum age, in seconds, of the signed prices submitted by the offchain worker
+	const MAX_SIGNED_PRICE_AGE: i64 = 60;
+	// Tolerated drift, in seconds, between the clocks of the price feed and the offchain worker
+	const MAX_SIGNED_PRICE_CLOCK_DRIFT: i64 = 5;
 
 	pub mod crypto {
 		use super::KEY_TYPE;
@@ -191,6 +201,17 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+
+		/// Identifier of the AMM pools used as fallback price sources.
+		type PoolId: FullCodec + MaxEncodedLen + Copy + Eq + Debug + TypeInfo;
+
+		/// Time weighted average prices of the AMM pools, consulted when the price of an asset is
+		/// stale or frozen.
+		type PoolTwap: PoolTwap<AssetId = Self::AssetId, PoolId = Self::PoolId>;
+
+		/// Upper bound for the fallback sources of an asset.
+		#[pallet::constant]
+		type MaxFallbacks: Get<u32>;
 	}
 
 	#[derive(Encode, Decode, MaxEncodedLen, Default, Debug, PartialEq, Eq, TypeInfo, Clone)]
@@ -221,12 +242,99 @@ pub mod pallet {
 		pub reward_weight: Balance,
 		pub slash: Balance,
 		pub emit_price_changes: bool,
+		/// Circuit breaker, maximum change of the price per `block_interval`.
+		/// An aggregated price exceeding it is not published and the price of the asset is frozen
+		/// until governance unfreezes it.
+		pub max_price_change: Option<Percent>,
+	}
+
+	/// A source of price used when the price submitted by the oracles is stale or frozen.
+	#[derive(Encode, Decode, MaxEncodedLen, Debug, PartialEq, Eq, Clone, Copy, TypeInfo)]
+	pub enum FallbackSource<AssetId, PoolId> {
+		/// Time weighted average price of the asset in a pool, converted with the price of
+		/// `quote`, the other asset of the pool.
+		PoolTwap { pool_id: PoolId, quote: AssetId },
+	}
+
+	#[derive(
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		CloneNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		RuntimeDebugNoBound,
+		TypeInfo,
+	)]
+	#[scale_info(skip_type_params(MaxFallbacks))]
+	pub struct PriceFallback<
+		AssetId: Clone + PartialEq + Eq + Debug,
+		PoolId: Clone + PartialEq + Eq + Debug,
+		BlockNumber: Clone + PartialEq + Eq + Debug,
+		MaxFallbacks: Get<u32>,
+	> {
+		/// Age, in blocks, after which the price submitted by the oracles is considered stale.
+		pub max_price_age: BlockNumber,
+		/// Sources consulted in order, the first one able to price the asset is used.
+		pub sources: BoundedVec<FallbackSource<AssetId, PoolId>, MaxFallbacks>,
+	}
+
+	pub(crate) type PriceFallbackOf<T> = PriceFallback<
+		<T as Config>::AssetId,
+		<T as Config>::PoolId,
+		<T as frame_system::Config>::BlockNumber,
+		<T as Config>::MaxFallbacks,
+	>;
+
+	/// Periods of the commit-reveal rounds of an asset.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, Default, Debug, PartialEq, Eq, Clone, Copy, TypeInfo,
+	)]
+	pub struct CommitRevealPeriod<BlockNumber> {
+		/// Blocks, from the first commitment of a round, during which oracles commit to a price.
+		pub commit_period: BlockNumber,
+		/// Blocks, following the commit period, during which oracles reveal their price.
+		pub reveal_period: BlockNumber,
+	}
+
+	#[derive(
+		Encode, Decode, MaxEncodedLen, Clone, Copy, Default, Debug, PartialEq, Eq, TypeInfo,
+	)]
+	pub struct PriceCommitment<Hash, BlockNumber, AccountId> {
+		/// Hash of the price, salt and account of the oracle, see `Pallet::price_commitment`.
+		pub commitment: Hash,
+		/// The block the commitment was submitted at.
+		pub block: BlockNumber,
+		/// The account that submitted the commitment.
+		pub who: AccountId,
+		/// Whether the price has been revealed.
+		pub revealed: bool,
+	}
+
+	/// Answers of an oracle for an asset, tracking its accuracy.
+	#[derive(Encode, Decode, MaxEncodedLen, Default, Debug, PartialEq, Eq, TypeInfo, Clone)]
+	pub struct AnswerStatistics {
+		/// Answers taken into account when aggregating a price.
+		pub answers: u32,
+		/// Answers out of the threshold of the asset.
+		pub slashes: u32,
+		/// Sum of the accuracy of the answers, in percents.
+		pub accuracy_sum: u64,
 	}
 
 	type BalanceOf<T> = <T as Config>::Balance;
 
+	pub(crate) type PriceCommitmentOf<T> = PriceCommitment<
+		<T as frame_system::Config>::Hash,
+		<T as frame_system::Config>::BlockNumber,
+		<T as frame_system::Config>::AccountId,
+	>;
+
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -323,6 +431,60 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn answer_statistics)]
+	/// Accuracy of the answers of an oracle for an asset
+	pub type OracleAnswerStatistics<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		AnswerStatistics,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_price)]
+	/// Latest price rejected by the circuit breaker of a frozen asset
+	pub type FrozenPrices<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Price<T::PriceValue, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn price_fallback)]
+	/// Fallback sources of price for an asset
+	pub type PriceFallbacks<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, PriceFallbackOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn commit_reveal_period)]
+	/// Periods of the commit-reveal rounds of the assets priced with commit-reveal
+	pub type CommitRevealPeriods<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		CommitRevealPeriod<T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn price_commitments)]
+	#[allow(clippy::disallowed_types)] // no commitment for an asset is an empty list, which is valid in this context.
+	/// Commitments of the current commit-reveal round of an asset, the round starting with the
+	/// first of them
+	pub type PriceCommitments<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		BoundedVec<PriceCommitmentOf<T>, T::MaxPrePrices>,
+		ValueQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -349,6 +511,21 @@ pub mod pallet {
 		AnswerPruned(T::AccountId, T::PriceValue),
 		/// Price changed by oracle \[asset_id, price\]
 		PriceChanged(T::AssetId, T::PriceValue),
+		/// Circuit breaker of an asset changed. \[asset_id, max_price_change\]
+		CircuitBreakerSet(T::AssetId, Option<Percent>),
+		/// Price rejected by the circuit breaker, the asset is frozen. \[asset_id, last_price,
+		/// rejected_price\]
+		PriceFrozen(T::AssetId, T::PriceValue, T::PriceValue),
+		/// Asset unfrozen by governance. \[asset_id, new_price\]
+		PriceUnfrozen(T::AssetId, Option<T::PriceValue>),
+		/// Fallback sources of an asset changed. \[asset_id\]
+		PriceFallbacksSet(T::AssetId),
+		/// Commit-reveal of an asset changed. \[asset_id, period\]
+		CommitRevealSet(T::AssetId, Option<CommitRevealPeriod<T::BlockNumber>>),
+		/// Price committed by oracle. \[oracle_address, asset_id\]
+		PriceCommitted(T::AccountId, T::AssetId),
+		/// Committed price revealed by oracle. \[oracle_address, asset_id, price\]
+		PriceRevealed(T::AccountId, T::AssetId, T::PriceValue),
 	}
 
 	#[pallet::error]
@@ -414,10 +591,32 @@ pub mod pallet {
 		NoRewardTrackerSet,
 		/// Annual rewarding cost too high
 		AnnualRewardLessThanAlreadyRewarded,
+		/// Price of the asset is not frozen
+		PriceNotFrozen,
+		/// Prices of the asset must be committed then revealed
+		CommitRevealRequired,
+		/// Prices of the asset are submitted in the clear
+		CommitRevealDisabled,
+		/// Commit and reveal periods must be non zero, the reveal period at most the stale price
+		InvalidCommitRevealPeriod,
+		/// Commit period of the round is over
+		CommitPeriodOver,
+		/// Round is not in its reveal period
+		NotRevealPeriod,
+		/// No commitment to reveal
+		CommitmentNotFound,
+		/// Commitment already revealed
+		AlreadyRevealed,
+		/// Revealed price and salt do not match the commitment
+		InvalidReveal,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::migrate_to_v1::<T>()
+		}
+
 		fn on_initialize(block: T::BlockNumber) -> Weight {
 			Self::reset_reward_tracker_if_expired();
 			Self::update_prices(block)
@@ -437,15 +636,15 @@ pub mod pallet {
 		type MaxAnswerBound = T::MaxAnswerBound;
 		type TwapWindow = T::TwapWindow;
 
+		/// The fallback sources of the asset are consulted when its price is stale or frozen.
+		/// If none of them can price the asset, the last accepted price is returned.
 		fn get_price(
 			asset_id: Self::AssetId,
 			amount: Self::Balance,
 		) -> Result<Price<Self::Balance, Self::Timestamp>, DispatchError> {
-			let Price { price, block } =
-				Prices::<T>::try_get(asset_id).map_err(|_| Error::<T>::PriceNotFound)?;
-			// dbg!(&price);
+			let CurrentPrice { price, block, .. } =
+				Self::current_price(asset_id).ok_or(Error::<T>::PriceNotFound)?;
 			let price = Self::quote(asset_id, price, amount)?;
-			// dbg!(&price);
 			Ok(Price { price, block })
 		}
 
@@ -548,6 +747,8 @@ pub mod pallet {
 				reward_weight,
 				slash,
 				emit_price_changes,
+				max_price_change: Self::asset_info(asset_id)
+					.and_then(|asset_info| asset_info.max_price_change),
 			};
 			// track reward total weight for all assets
 			let mut reward_tracker = RewardTrackerStore::<T>::get().unwrap_or_default();
@@ -712,17 +913,11 @@ pub mod pallet {
 			asset_id: T::AssetId,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let author_stake = OracleStake::<T>::get(&who).unwrap_or_else(Zero::zero);
-			ensure!(Self::is_requested(&asset_id), Error::<T>::PriceNotRequested);
+			let asset_info = Self::ensure_can_answer(&who, asset_id)?;
 			ensure!(
-				author_stake >=
-					T::MinStake::get().saturating_add(
-						Self::answer_in_transit(&who).unwrap_or_else(Zero::zero)
-					),
-				Error::<T>::NotEnoughStake
+				!CommitRevealPeriods::<T>::contains_key(asset_id),
+				Error::<T>::CommitRevealRequired
 			);
-			let asset_info = Self::asset_info(asset_id).ok_or(Error::<T>::InvalidAssetId)?;
-			ensure!(author_stake >= asset_info.slash, Error::<T>::NotEnoughStake);
 
 			PrePrices::<T>::try_mutate(asset_id, |current_prices| -> Result<(), DispatchError> {
 				// current_prices.len() can be casted to u32 safely because current_prices.len() is
@@ -752,6 +947,225 @@ pub mod pallet {
 			Self::deposit_event(Event::PriceSubmitted(who, asset_id, price));
 			Ok(Pays::No.into())
 		}
+
+		/// Permissioned call to set the circuit breaker of an asset
+		///
+		/// - `asset_id`: Id for the asset
+		/// - `max_price_change`: maximum change of the price per `block_interval`, `None` disables
+		///   the circuit breaker
+		///
+		/// Emits `CircuitBreakerSet` event when successful.
+		#[pallet::weight(T::WeightInfo::set_circuit_breaker())]
+		pub fn set_circuit_breaker(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			max_price_change: Option<Percent>,
+		) -> DispatchResultWithPostInfo {
+			T::AddOracle::ensure_origin(origin)?;
+			AssetsInfo::<T>::try_mutate(asset_id, |asset_info| -> DispatchResult {
+				let asset_info = asset_info.as_mut().ok_or(Error::<T>::InvalidAssetId)?;
+				asset_info.max_price_change = max_price_change;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::CircuitBreakerSet(asset_id, max_price_change));
+			Ok(().into())
+		}
+
+		/// Permissioned call to unfreeze the price of an asset frozen by its circuit breaker
+		///
+		/// - `asset_id`: Id for the asset
+		/// - `price`: price to publish as the new reference of the circuit breaker, otherwise the
+		///   next aggregated price is checked against the last accepted price
+		///
+		/// Emits `PriceUnfrozen` event when successful.
+		#[pallet::weight(T::WeightInfo::unfreeze_price())]
+		pub fn unfreeze_price(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			price: Option<T::PriceValue>,
+		) -> DispatchResultWithPostInfo {
+			T::AddOracle::ensure_origin(origin)?;
+			FrozenPrices::<T>::take(asset_id).ok_or(Error::<T>::PriceNotFrozen)?;
+			if let Some(price) = price {
+				let block = frame_system::Pallet::<T>::block_number();
+				Self::set_price(asset_id, Price { price, block })?;
+			}
+			Self::deposit_event(Event::PriceUnfrozen(asset_id, price));
+			Ok(().into())
+		}
+
+		/// Permissioned call to set the fallback sources of price of an asset
+		///
+		/// - `asset_id`: Id for the asset
+		/// - `max_price_age`: blocks after which the price submitted by the oracles is stale
+		/// - `sources`: sources consulted in order, empty to remove the fallbacks
+		///
+		/// Emits `PriceFallbacksSet` event when successful.
+		#[pallet::weight(T::WeightInfo::set_price_fallbacks(T::MaxFallbacks::get()))]
+		pub fn set_price_fallbacks(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			max_price_age: T::BlockNumber,
+			sources: BoundedVec<FallbackSource<T::AssetId, T::PoolId>, T::MaxFallbacks>,
+		) -> DispatchResultWithPostInfo {
+			T::AddOracle::ensure_origin(origin)?;
+			ensure!(AssetsInfo::<T>::contains_key(asset_id), Error::<T>::InvalidAssetId);
+			if sources.is_empty() {
+				PriceFallbacks::<T>::remove(asset_id);
+			} else {
+				PriceFallbacks::<T>::insert(asset_id, PriceFallback { max_price_age, sources });
+			}
+			Self::deposit_event(Event::PriceFallbacksSet(asset_id));
+			Ok(().into())
+		}
+
+		/// Permissioned call to have the prices of an asset committed then revealed
+		///
+		/// - `asset_id`: Id for the asset
+		/// - `period`: periods of the commit-reveal rounds, `None` to have prices submitted in the
+		///   clear again, the pending commitments being released without slashing
+		///
+		/// Emits `CommitRevealSet` event when successful.
+		#[pallet::weight(T::WeightInfo::set_commit_reveal(T::MaxAnswerBound::get()))]
+		pub fn set_commit_reveal(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			period: Option<CommitRevealPeriod<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			T::AddOracle::ensure_origin(origin)?;
+			let asset_info = Self::asset_info(asset_id).ok_or(Error::<T>::InvalidAssetId)?;
+			if let Some(period) = period {
+				ensure!(
+					!period.commit_period.is_zero() &&
+						!period.reveal_period.is_zero() &&
+						period.reveal_period <= T::StalePrice::get(),
+					Error::<T>::InvalidCommitRevealPeriod
+				);
+				CommitRevealPeriods::<T>::insert(asset_id, period);
+			} else {
+				CommitRevealPeriods::<T>::remove(asset_id);
+				for commitment in PriceCommitments::<T>::take(asset_id) {
+					if !commitment.revealed {
+						Self::remove_price_in_transit(&commitment.who, &asset_info);
+					}
+				}
+			}
+			Self::deposit_event(Event::CommitRevealSet(asset_id, period));
+			Ok(().into())
+		}
+
+		/// Call for an oracle to commit to the price of an asset priced with commit-reveal.
+		/// This is an operational transaction.
+		///
+		/// - `asset_id`: id for the asset
+		/// - `commitment`: hash of the price, a salt and the oracle, see `Pallet::price_commitment`
+		///
+		/// The first commitment starts a round, the price is to be revealed with `reveal_price`
+		/// once its commit period is over. An oracle not revealing its price within the reveal
+		/// period is slashed.
+		///
+		/// Emits `PriceCommitted` event when successful.
+		#[pallet::weight((T::WeightInfo::commit_price(T::MaxAnswerBound::get()), Operational))]
+		pub fn commit_price(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			commitment: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let period =
+				Self::commit_reveal_period(asset_id).ok_or(Error::<T>::CommitRevealDisabled)?;
+			let asset_info = Self::ensure_can_answer(&who, asset_id)?;
+			ensure!(
+				!PrePrices::<T>::get(asset_id).iter().any(|candidate| candidate.who == who),
+				Error::<T>::AlreadySubmitted
+			);
+			let block = frame_system::Pallet::<T>::block_number();
+
+			PriceCommitments::<T>::try_mutate(asset_id, |commitments| -> DispatchResult {
+				if let Some(first) = commitments.first() {
+					ensure!(
+						block < first.block.saturating_add(period.commit_period),
+						Error::<T>::CommitPeriodOver
+					);
+				}
+				ensure!((commitments.len() as u32) < asset_info.max_answers, Error::<T>::MaxPrices);
+				ensure!(
+					!commitments.iter().any(|candidate| candidate.who == who),
+					Error::<T>::AlreadySubmitted
+				);
+				commitments
+					.try_push(PriceCommitment {
+						commitment,
+						block,
+						who: who.clone(),
+						revealed: false,
+					})
+					.map_err(|_| Error::<T>::MaxPrePrices)?;
+				Ok(())
+			})?;
+
+			AnswerInTransit::<T>::mutate(&who, |transit| {
+				*transit =
+					Some(transit.unwrap_or_else(Zero::zero).saturating_add(asset_info.slash));
+			});
+
+			Self::deposit_event(Event::PriceCommitted(who, asset_id));
+			Ok(Pays::No.into())
+		}
+
+		/// Call for an oracle to reveal the price it committed to.
+		/// This is an operational transaction.
+		///
+		/// - `asset_id`: id for the asset
+		/// - `price`: committed price, normalized to 12 decimals
+		/// - `salt`: salt of the commitment
+		///
+		/// Emits `PriceRevealed` event when successful.
+		#[pallet::weight((T::WeightInfo::reveal_price(T::MaxAnswerBound::get()), Operational))]
+		pub fn reveal_price(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			price: T::PriceValue,
+			salt: [u8; 32],
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let period =
+				Self::commit_reveal_period(asset_id).ok_or(Error::<T>::CommitRevealDisabled)?;
+			let block = frame_system::Pallet::<T>::block_number();
+
+			PriceCommitments::<T>::try_mutate(asset_id, |commitments| -> DispatchResult {
+				let reveal_start = commitments
+					.first()
+					.ok_or(Error::<T>::CommitmentNotFound)?
+					.block
+					.saturating_add(period.commit_period);
+				ensure!(
+					block >= reveal_start &&
+						block < reveal_start.saturating_add(period.reveal_period),
+					Error::<T>::NotRevealPeriod
+				);
+				let commitment = commitments
+					.iter_mut()
+					.find(|candidate| candidate.who == who)
+					.ok_or(Error::<T>::CommitmentNotFound)?;
+				ensure!(!commitment.revealed, Error::<T>::AlreadyRevealed);
+				ensure!(
+					commitment.commitment == Self::price_commitment(&who, price, &salt),
+					Error::<T>::InvalidReveal
+				);
+				commitment.revealed = true;
+				Ok(())
+			})?;
+
+			PrePrices::<T>::try_mutate(asset_id, |current_prices| {
+				current_prices
+					.try_push(PrePrice { price, block, who: who.clone() })
+					.map_err(|_| Error::<T>::MaxPrePrices)
+			})?;
+
+			Self::deposit_event(Event::PriceRevealed(who, asset_id, price));
+			Ok(Pays::No.into())
+		}
 	}
 
 	/// Payload used by this example crate to hold price
@@ -786,6 +1200,34 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Checks `who` may answer for `asset_id`, staking enough to be slashed for it.
+		fn ensure_can_answer(
+			who: &T::AccountId,
+			asset_id: T::AssetId,
+		) -> Result<AssetInfo<Percent, T::BlockNumber, BalanceOf<T>>, DispatchError> {
+			let author_stake = OracleStake::<T>::get(who).unwrap_or_else(Zero::zero);
+			ensure!(Self::is_requested(&asset_id), Error::<T>::PriceNotRequested);
+			ensure!(
+				author_stake >=
+					T::MinStake::get()
+						.saturating_add(Self::answer_in_transit(who).unwrap_or_else(Zero::zero)),
+				Error::<T>::NotEnoughStake
+			);
+			let asset_info = Self::asset_info(asset_id).ok_or(Error::<T>::InvalidAssetId)?;
+			ensure!(author_stake >= asset_info.slash, Error::<T>::NotEnoughStake);
+			Ok(asset_info)
+		}
+
+		/// Commitment of `who` to `price`, to submit with `commit_price` then reveal with
+		/// `reveal_price` along with `salt`.
+		pub fn price_commitment(
+			who: &T::AccountId,
+			price: T::PriceValue,
+			salt: &[u8; 32],
+		) -> T::Hash {
+			T::Hashing::hash_of(&(who, price, salt))
+		}
+
 		pub fn handle_payout(
 			pre_prices: &[PrePrice<T::PriceValue, T::BlockNumber, T::AccountId>],
 			price: T::PriceValue,
@@ -803,32 +1245,9 @@ pub mod pallet {
 					PerThing::from_rational(adjusted_number, price)
 				};
 				let min_accuracy = asset_info.threshold;
+				Self::record_answer(asset_id, &answer.who, accuracy, accuracy < min_accuracy);
 				if accuracy < min_accuracy {
-					let slash_amount = asset_info.slash;
-					let new_amount_staked = Self::oracle_stake(answer.who.clone())
-						.unwrap_or_else(|| 0_u32.into())
-						.saturating_sub(slash_amount);
-					OracleStake::<T>::insert(&answer.who, new_amount_staked);
-					let result = T::Currency::repatriate_reserved(
-						&answer.who,
-						&T::TreasuryAccount::get(),
-						slash_amount,
-						BalanceStatus::Free,
-					);
-					match result {
-						Ok(remaning_val) =>
-							if remaning_val > BalanceOf::<T>::zero() {
-								log::warn!("Only slashed {:?}", slash_amount - remaning_val);
-							},
-						Err(e) => {
-							log::warn!("Failed to slash {:?} due to {:?}", answer.who, e);
-						},
-					}
-					Self::deposit_event(Event::UserSlashed(
-						answer.who.clone(),
-						asset_id,
-						slash_amount,
-					));
+					Self::slash(&answer.who, asset_id, asset_info);
 				} else {
 					let controller = SignerToController::<T>::get(&answer.who)
 						.unwrap_or_else(|| answer.who.clone());
@@ -867,6 +1286,51 @@ pub mod pallet {
 			Ok(())
 		}
 
+		fn record_answer(
+			asset_id: T::AssetId,
+			who: &T::AccountId,
+			accuracy: Percent,
+			slashed: bool,
+		) {
+			OracleAnswerStatistics::<T>::mutate(asset_id, who, |statistics| {
+				let statistics = statistics.get_or_insert_with(Default::default);
+				statistics.answers = statistics.answers.saturating_add(1);
+				if slashed {
+					statistics.slashes = statistics.slashes.saturating_add(1);
+				}
+				statistics.accuracy_sum =
+					statistics.accuracy_sum.saturating_add(accuracy.deconstruct().into());
+			});
+		}
+
+		fn slash(
+			who: &T::AccountId,
+			asset_id: T::AssetId,
+			asset_info: &AssetInfo<Percent, T::BlockNumber, BalanceOf<T>>,
+		) {
+			let slash_amount = asset_info.slash;
+			let new_amount_staked = Self::oracle_stake(who.clone())
+				.unwrap_or_else(|| 0_u32.into())
+				.saturating_sub(slash_amount);
+			OracleStake::<T>::insert(who, new_amount_staked);
+			let result = T::Currency::repatriate_reserved(
+				who,
+				&T::TreasuryAccount::get(),
+				slash_amount,
+				BalanceStatus::Free,
+			);
+			match result {
+				Ok(remaning_val) =>
+					if remaning_val > BalanceOf::<T>::zero() {
+						log::warn!("Only slashed {:?}", slash_amount - remaning_val);
+					},
+				Err(e) => {
+					log::warn!("Failed to slash {:?} due to {:?}", who, e);
+				},
+			}
+			Self::deposit_event(Event::UserSlashed(who.clone(), asset_id, slash_amount));
+		}
+
 		fn get_reward_tracker_if_enabled(
 		) -> Option<RewardTracker<<T as Config>::Balance, <T as Config>::Moment>> {
 			RewardTrackerStore::<T>::get().and_then(|r| {
@@ -909,6 +1373,16 @@ pub mod pallet {
 			let one_read = T::DbWeight::get().reads(1);
 			for (asset_id, asset_info) in AssetsInfo::<T>::iter() {
 				total_weight += one_read;
+				if let Some(period) = Self::commit_reveal_period(asset_id) {
+					total_weight += one_read;
+					match Self::close_commit_reveal_round(asset_id, &asset_info, &period, block) {
+						Some(commitments_len) =>
+							total_weight +=
+								T::WeightInfo::close_commit_reveal_round(commitments_len as u32),
+						// prices are aggregated once all of them are revealed
+						None => continue,
+					}
+				}
 				if let Ok((removed_pre_prices_len, pre_prices)) =
 					Self::update_pre_prices(asset_id, &asset_info, block)
 				{
@@ -924,6 +1398,36 @@ pub mod pallet {
 			total_weight
 		}
 
+		/// Closes the commit-reveal round of an asset once its reveal period is over, slashing the
+		/// oracles which did not reveal their price.
+		///
+		/// Returns the number of commitments of the closed round, `None` while the round is open.
+		pub fn close_commit_reveal_round(
+			asset_id: T::AssetId,
+			asset_info: &AssetInfo<Percent, T::BlockNumber, BalanceOf<T>>,
+			period: &CommitRevealPeriod<T::BlockNumber>,
+			block: T::BlockNumber,
+		) -> Option<usize> {
+			let commitments = PriceCommitments::<T>::get(asset_id);
+			let round_end = match commitments.first() {
+				Some(first) => first
+					.block
+					.saturating_add(period.commit_period)
+					.saturating_add(period.reveal_period),
+				None => return Some(0),
+			};
+			if block < round_end {
+				return None
+			}
+			PriceCommitments::<T>::remove(asset_id);
+			for commitment in commitments.iter().filter(|commitment| !commitment.revealed) {
+				Self::record_answer(asset_id, &commitment.who, Percent::zero(), true);
+				Self::slash(&commitment.who, asset_id, asset_info);
+				Self::remove_price_in_transit(&commitment.who, asset_info);
+			}
+			Some(commitments.len())
+		}
+
 		#[allow(clippy::type_complexity)]
 		pub fn update_pre_prices(
 			asset_id: T::AssetId,
@@ -976,24 +1480,45 @@ pub mod pallet {
 						_ => Zero::zero(),
 					};
 
-					Prices::<T>::insert(asset_id, Price { price, block });
-					PriceHistory::<T>::try_mutate(asset_id, |prices| -> DispatchResult {
-						if prices.len() as u32 >= T::MaxHistory::get() {
-							prices.remove(0);
-						}
-						if block != 0_u32.into() {
-							prices
-								.try_push(Price { price, block })
-								.map_err(|_| Error::<T>::MaxHistory)?;
-						}
-						Ok(())
-					})?;
+					let accepted_price = Prices::<T>::get(asset_id);
+					let accepted = if FrozenPrices::<T>::contains_key(asset_id) {
+						// frozen until governance steps in, keep track of the latest price
+						FrozenPrices::<T>::insert(asset_id, Price { price, block });
+						false
+					} else if Self::exceeds_max_price_change(
+						&asset_info,
+						&accepted_price,
+						price,
+						block,
+					) {
+						FrozenPrices::<T>::insert(asset_id, Price { price, block });
+						Self::deposit_event(Event::PriceFrozen(
+							asset_id,
+							accepted_price.price,
+							price,
+						));
+						false
+					} else {
+						Self::set_price(asset_id, Price { price, block })?;
+						true
+					};
 					PrePrices::<T>::remove(asset_id);
 
-					Self::handle_payout(&pre_prices, price, asset_id, &asset_info)?;
+					if accepted {
+						Self::handle_payout(&pre_prices, price, asset_id, &asset_info)?;
+					} else {
+						// the answers are judged against a price nobody relies on: they are
+						// neither rewarded nor slashed, only released
+						for answer in &pre_prices {
+							Self::remove_price_in_transit(&answer.who, &asset_info);
+						}
+					}
 
 					// Emit `PriceChanged` event when prices have changed, if required.
-					if price != last_price && asset_info.emit_price_changes {
+					if !FrozenPrices::<T>::contains_key(asset_id) &&
+						price != last_price &&
+						asset_info.emit_price_changes
+					{
 						Self::deposit_event(Event::PriceChanged(asset_id, price));
 					}
 				}
@@ -1001,6 +1526,185 @@ pub mod pallet {
 			Ok(())
 		}
 
+		fn set_price(
+			asset_id: T::AssetId,
+			price: Price<T::PriceValue, T::BlockNumber>,
+		) -> DispatchResult {
+			Prices::<T>::insert(asset_id, price.clone());
+			PriceHistory::<T>::try_mutate(asset_id, |prices| -> DispatchResult {
+				if prices.len() as u32 >= T::MaxHistory::get() {
+					prices.remove(0);
+				}
+				if price.block != 0_u32.into() {
+					prices.try_push(price).map_err(|_| Error::<T>::MaxHistory)?;
+				}
+				Ok(())
+			})
+		}
+
+		/// Whether `price` moved further from the accepted price than allowed by the circuit
+		/// breaker of the asset, the allowed change growing with every `block_interval` elapsed
+		/// since the accepted price.
+		pub fn exceeds_max_price_change(
+			asset_info: &AssetInfo<Percent, T::BlockNumber, BalanceOf<T>>,
+			accepted_price: &Price<T::PriceValue, T::BlockNumber>,
+			price: T::PriceValue,
+			block: T::BlockNumber,
+		) -> bool {
+			let max_price_change = match asset_info.max_price_change {
+				Some(max_price_change) if !accepted_price.price.is_zero() => max_price_change,
+				_ => return false,
+			};
+			let intervals: u128 = block
+				.saturating_sub(accepted_price.block)
+				.checked_div(&asset_info.block_interval)
+				.unwrap_or_else(Zero::zero)
+				.max(One::one())
+				.unique_saturated_into();
+			let max_change = max_price_change
+				.mul_floor(accepted_price.price)
+				.saturating_mul(intervals.into());
+			let change = if price > accepted_price.price {
+				price - accepted_price.price
+			} else {
+				accepted_price.price - price
+			};
+			change > max_change
+		}
+
+		/// The price submitted by the oracles, unless it is frozen or older than the
+		/// `max_price_age` of the fallbacks of the asset.
+		fn usable_price(asset_id: T::AssetId) -> Option<Price<T::PriceValue, T::BlockNumber>> {
+			let price = Prices::<T>::try_get(asset_id).ok()?;
+			if FrozenPrices::<T>::contains_key(asset_id) {
+				return None
+			}
+			match PriceFallbacks::<T>::get(asset_id) {
+				Some(fallback)
+					if frame_system::Pallet::<T>::block_number().saturating_sub(price.block) >
+						fallback.max_price_age =>
+					None,
+				_ => Some(price),
+			}
+		}
+
+		/// The price of the asset according to the first of its fallback sources able to price
+		/// it.
+		pub fn fallback_price(
+			asset_id: T::AssetId,
+		) -> Option<Price<T::PriceValue, T::BlockNumber>> {
+			let block = frame_system::Pallet::<T>::block_number();
+			let unit = T::LocalAssets::unit::<u128>(asset_id).ok()?;
+			PriceFallbacks::<T>::get(asset_id)?.sources.into_iter().find_map(
+				|source| match source {
+					FallbackSource::PoolTwap { pool_id, quote } => {
+						let twap =
+							T::PoolTwap::twap(pool_id, CurrencyPair::new(asset_id, quote)).ok()?;
+						// fallbacks of the quote asset are not consulted, avoiding cycles
+						let quote_price = Self::usable_price(quote)?;
+						let quote_amount: u128 = twap.checked_mul_int(unit)?;
+						let price =
+							Self::quote(quote, quote_price.price, quote_amount.into()).ok()?;
+						Some(Price { price, block })
+					},
+				},
+			)
+		}
+
+		/// The price consumers get for the asset through `Oracle::get_price`.
+		/// The fallback sources of the asset are consulted when its price is stale or frozen.
+		/// If none of them can price the asset, the last accepted price is returned.
+		pub fn current_price(
+			asset_id: T::AssetId,
+		) -> Option<CurrentPrice<T::PriceValue, T::BlockNumber>> {
+			let frozen = FrozenPrices::<T>::contains_key(asset_id);
+			let (Price { price, block }, fallback) = match Self::usable_price(asset_id) {
+				Some(price) => (price, false),
+				None => match Self::fallback_price(asset_id) {
+					Some(price) => (price, true),
+					None => (Prices::<T>::try_get(asset_id).ok()?, false),
+				},
+			};
+			Some(CurrentPrice { price, block, frozen, fallback })
+		}
+
+		/// Time weighted average price of the asset over the last `window` blocks, each price of
+		/// the history weighted by the blocks it has been the price of the asset in the window.
+		pub fn twap_over_window(
+			asset_id: T::AssetId,
+			window: T::BlockNumber,
+		) -> Option<T::PriceValue> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let start = now.saturating_sub(window);
+			let history = Self::price_history(asset_id);
+			let mut weighted_prices_sum: u128 = 0;
+			let mut weights_sum: u128 = 0;
+			for (index, price) in history.iter().enumerate() {
+				let until = history.get(index + 1).map_or(now, |next| next.block);
+				let from = price.block.max(start);
+				if until > from {
+					let weight: u128 = (until - from).unique_saturated_into();
+					weighted_prices_sum = weighted_prices_sum
+						.saturating_add(Into::<u128>::into(price.price).saturating_mul(weight));
+					weights_sum = weights_sum.saturating_add(weight);
+				}
+			}
+			if weights_sum.is_zero() {
+				// updated during the current block, or an empty window
+				Prices::<T>::try_get(asset_id).ok().map(|price| price.price)
+			} else {
+				Some((weighted_prices_sum / weights_sum).into())
+			}
+		}
+
+		/// Open, high, low and close prices of the asset from its price history, in buckets of
+		/// `bucket_size` blocks. Buckets without any price are skipped.
+		pub fn ohlc(
+			asset_id: T::AssetId,
+			bucket_size: T::BlockNumber,
+		) -> Vec<Ohlc<T::PriceValue, T::BlockNumber>> {
+			let mut buckets: Vec<Ohlc<T::PriceValue, T::BlockNumber>> = Vec::new();
+			if bucket_size.is_zero() {
+				return buckets
+			}
+			for Price { price, block } in Self::price_history(asset_id) {
+				let start = block - block % bucket_size;
+				match buckets.last_mut() {
+					Some(bucket) if bucket.start == start => {
+						bucket.high = bucket.high.max(price);
+						bucket.low = bucket.low.min(price);
+						bucket.close = price;
+					},
+					_ => buckets.push(Ohlc {
+						start,
+						open: price,
+						high: price,
+						low: price,
+						close: price,
+					}),
+				}
+			}
+			buckets
+		}
+
+		/// Accuracy of the answers submitted by each oracle for the asset.
+		pub fn oracle_accuracy(asset_id: T::AssetId) -> Vec<OracleAccuracy<T::AccountId>> {
+			OracleAnswerStatistics::<T>::iter_prefix(asset_id)
+				.map(|(oracle, statistics)| OracleAccuracy {
+					oracle,
+					answers: statistics.answers,
+					slashes: statistics.slashes,
+					average_accuracy: Percent::from_parts(
+						statistics
+							.accuracy_sum
+							.checked_div(statistics.answers.into())
+							.unwrap_or_default()
+							.unique_saturated_into(),
+					),
+				})
+				.collect()
+		}
+
 		#[allow(clippy::type_complexity)]
 		pub fn prune_old_pre_prices(
 			asset_info: &AssetInfo<Percent, T::BlockNumber, BalanceOf<T>>,
@@ -1207,6 +1911,16 @@ pub mod pallet {
 			let address: T::AccountId =
 				T::AccountId::decode(&mut to32).map_err(|_| "Could not decode account")?;
 
+			if let Some(period) = Self::commit_reveal_period(*price_id) {
+				return Self::commit_or_reveal_price_and_send_signed(
+					price_id,
+					&asset_info,
+					&period,
+					&address,
+					&signer,
+				)
+			}
+
 			if prices.len() as u32 >= asset_info.max_answers {
 				log::info!("Max answers reached");
 				return Err("Max answers reached")
@@ -1243,6 +1957,66 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Commits to the price of an asset priced with commit-reveal, keeping the price and salt
+		/// in the offchain storage of the node until they are revealed.
+		fn commit_or_reveal_price_and_send_signed(
+			price_id: &T::AssetId,
+			asset_info: &AssetInfo<Percent, T::BlockNumber, BalanceOf<T>>,
+			period: &CommitRevealPeriod<T::BlockNumber>,
+			address: &T::AccountId,
+			signer: &Signer<T, T::AuthorityId, ForAll>,
+		) -> Result<(), &'static str> {
+			let committed_price =
+				StorageValueRef::persistent(&(COMMITTED_PRICE_KEY, price_id).encode());
+			let commitments = PriceCommitments::<T>::get(*price_id);
+			let block = frame_system::Pallet::<T>::block_number();
+			let reveal_start = commitments
+				.first()
+				.map(|first| first.block.saturating_add(period.commit_period));
+
+			let call = match commitments.iter().find(|commitment| &commitment.who == address) {
+				Some(commitment) if commitment.revealed => {
+					log::info!("Tx already submitted");
+					return Err("Tx already submitted")
+				},
+				Some(_) => {
+					if reveal_start.map_or(true, |reveal_start| block < reveal_start) {
+						return Err("Reveal period not started")
+					}
+					let (price, salt) = committed_price
+						.get::<(T::PriceValue, [u8; 32])>()
+						.ok()
+						.flatten()
+						.ok_or("No committed price")?;
+					Call::reveal_price { asset_id: *price_id, price, salt }
+				},
+				None => {
+					if reveal_start.map_or(false, |reveal_start| block >= reveal_start) {
+						return Err("Commit period over")
+					}
+					if commitments.len() as u32 >= asset_info.max_answers {
+						log::info!("Max answers reached");
+						return Err("Max answers reached")
+					}
+					let price: T::PriceValue =
+						Self::fetch_price(price_id).map_err(|_| "Failed to fetch price")?.into();
+					let salt = sp_io::offchain::random_seed();
+					committed_price.set(&(price, salt));
+					let commitment = Self::price_commitment(address, price, &salt);
+					Call::commit_price { asset_id: *price_id, commitment }
+				},
+			};
+
+			for (acc, res) in &signer.send_signed_transaction(|_account| call.clone()) {
+				match res {
+					Ok(()) => log::info!("[{:?}] Submitted commit-reveal transaction", acc.id),
+					Err(e) => log::error!("[{:?}] Failed to submit transaction: {:?}", acc.id, e),
+				}
+			}
+
+			Ok(())
+		}
+
 		pub fn fetch_price(price_id: &T::AssetId) -> Result<u64, http::Error> {
 			// We want to keep the offchain worker execution time reasonable, so we set a hard-coded
 			// deadline to 2s to complete the external call.
@@ -1292,6 +2066,17 @@ pub mod pallet {
 				},
 			}?;
 
+			// If the operator of the price feed is known, only submit the prices it signed.
+			if let Some(signer) = sp_io::offchain::local_storage_get(kind, b"ocw-feed-signer") {
+				let signer =
+					sr25519::Public::try_from(&signer[..]).map_err(|_| http::Error::Unknown)?;
+				let now = (sp_io::offchain::timestamp().unix_millis() / 1_000) as i64;
+				if !Self::verify_price(body_str, (*price_id).into(), price, &signer, now) {
+					log::warn!("Invalid price signature: {:?}", body_str);
+					return Err(http::Error::Unknown)
+				}
+			}
+
 			log::warn!("Got price: {} cents", price);
 
 			Ok(price)
@@ -1312,5 +2097,69 @@ pub mod pallet {
 			};
 			Some(price.integer as u64)
 		}
+
+		/// Check that the price has been signed by `signer`, the signed payload being the SCALE
+		/// encoded `(asset_id, price, timestamp)`.
+		///
+		/// The price must have been signed at most `MAX_SIGNED_PRICE_AGE` seconds before `now`, a
+		/// UNIX timestamp in seconds, and not in the future, up to the tolerated clock drift.
+		pub fn verify_price(
+			price_str: &str,
+			asset_id: u128,
+			price: u64,
+			signer: &sr25519::Public,
+			now: i64,
+		) -> bool {
+			let fields = match lite_json::parse_json(price_str) {
+				Ok(JsonValue::Object(fields)) => fields,
+				_ => return false,
+			};
+			let field = |name: &str| {
+				fields
+					.iter()
+					.find(|(k, _)| k.iter().copied().eq(name.chars()))
+					.map(|(_, v)| v.clone())
+			};
+			let timestamp = match field("timestamp") {
+				Some(JsonValue::Number(number)) if !number.negative => number.integer as i64,
+				_ => return false,
+			};
+			if timestamp < now.saturating_sub(MAX_SIGNED_PRICE_AGE) ||
+				timestamp > now.saturating_add(MAX_SIGNED_PRICE_CLOCK_DRIFT)
+			{
+				return false
+			}
+			let signature = match field("signature") {
+				Some(JsonValue::String(signature)) => signature,
+				_ => return false,
+			};
+			match decode_hex(&signature).and_then(|raw| sr25519::Signature::try_from(&raw[..]).ok())
+			{
+				Some(signature) => sp_io::crypto::sr25519_verify(
+					&signature,
+					&(asset_id, price, timestamp).encode(),
+					signer,
+				),
+				None => false,
+			}
+		}
+	}
+
+	/// Decode a `0x` prefixed hexadecimal string.
+	fn decode_hex(chars: &[char]) -> Option<Vec<u8>> {
+		let digits = match chars {
+			['0', 'x', digits @ ..] => digits,
+			_ => return None,
+		};
+		if digits.len() % 2 != 0 {
+			return None
+		}
+		digits
+			.chunks(2)
+			.map(|pair| match pair {
+				[high, low] => Some((high.to_digit(16)? * 16 + low.to_digit(16)?) as u8),
+				_ => None,
+			})
+			.collect()
 	}
 }
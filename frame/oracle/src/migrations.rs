@@ -0,0 +1,44 @@
+use crate::{AssetInfo, AssetsInfo, Config, Pallet, STORAGE_VERSION};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::Percent;
+
+/// `AssetInfo` before the circuit breaker was introduced.
+#[derive(Encode, Decode)]
+pub struct AssetInfoV0<Percent, BlockNumber, Balance> {
+	pub threshold: Percent,
+	pub min_answers: u32,
+	pub max_answers: u32,
+	pub block_interval: BlockNumber,
+	pub reward_weight: Balance,
+	pub slash: Balance,
+	pub emit_price_changes: bool,
+}
+
+/// Adds the circuit breaker to the info of the assets, disabled.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+		return T::DbWeight::get().reads(1)
+	}
+	let mut translated: Weight = 0;
+	AssetsInfo::<T>::translate::<AssetInfoV0<Percent, T::BlockNumber, T::Balance>, _>(
+		|_, asset_info| {
+			translated += 1;
+			Some(AssetInfo {
+				threshold: asset_info.threshold,
+				min_answers: asset_info.min_answers,
+				max_answers: asset_info.max_answers,
+				block_interval: asset_info.block_interval,
+				reward_weight: asset_info.reward_weight,
+				slash: asset_info.slash,
+				emit_price_changes: asset_info.emit_price_changes,
+				max_price_change: None,
+			})
+		},
+	);
+	StorageVersion::new(1).put::<Pallet<T>>();
+	T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+}
@@ -1,6 +1,10 @@
 use crate as pallet_oracle;
 use crate::*;
 
+use composable_traits::{
+	defi::{CurrencyPair, Rate},
+	dex::PoolTwap,
+};
 use frame_support::{
 	ord_parameter_types,
 	pallet_prelude::ConstU32,
@@ -14,7 +18,9 @@ use sp_core::{sr25519, sr25519::Signature, H256};
 use sp_runtime::{
 	testing::{Header, TestXt},
 	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify},
+	DispatchError,
 };
+use std::{cell::RefCell, collections::BTreeMap};
 use system::EnsureRoot;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -152,6 +158,34 @@ parameter_types! {
 	pub const TreasuryAccountId : AccountId= sr25519::Public([10u8; 32]);
 	pub const OraclePalletId: PalletId = PalletId(*b"plt_orac");
 	pub const MsPerBlock: u64 = MILLISECS_PER_BLOCK;
+	pub const MaxFallbacks: u32 = 2;
+}
+
+pub type PoolId = u128;
+
+thread_local! {
+	static POOL_TWAPS: RefCell<BTreeMap<(PoolId, AssetId, AssetId), Rate>> = RefCell::new(BTreeMap::new());
+}
+
+/// Average prices of pools, set by the tests.
+pub struct MockPoolTwap;
+
+impl MockPoolTwap {
+	/// Set the average price of `base` in `quote` in the pool.
+	pub fn set_twap(pool_id: PoolId, base: AssetId, quote: AssetId, twap: Rate) {
+		POOL_TWAPS.with(|twaps| twaps.borrow_mut().insert((pool_id, base, quote), twap));
+	}
+}
+
+impl PoolTwap for MockPoolTwap {
+	type AssetId = AssetId;
+	type PoolId = PoolId;
+
+	fn twap(pool_id: PoolId, pair: CurrencyPair<AssetId>) -> Result<Rate, DispatchError> {
+		POOL_TWAPS
+			.with(|twaps| twaps.borrow().get(&(pool_id, pair.base, pair.quote)).copied())
+			.ok_or_else(|| DispatchError::Other("no twap"))
+	}
 }
 
 impl pallet_oracle::Config for Test {
@@ -179,6 +213,9 @@ impl pallet_oracle::Config for Test {
 	type PalletId = OraclePalletId;
 	type MsPerBlock = MsPerBlock;
 	type Balance = Balance;
+	type PoolId = PoolId;
+	type PoolTwap = MockPoolTwap;
+	type MaxFallbacks = MaxFallbacks;
 }
 
 // Build genesis storage according to the mock runtime.
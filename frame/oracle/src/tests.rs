@@ -2,19 +2,26 @@ use crate::{
 	mock::{AccountId, Call, Event, Extrinsic, *},
 	AssetInfo, Error, PrePrice, Withdraw, *,
 };
-use codec::Decode;
+use codec::{Decode, Encode};
 use composable_traits::{
 	defi::CurrencyPair,
-	oracle::{self, Price},
+	oracle::{self, CurrentPrice, Ohlc, OracleAccuracy, Price},
 };
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::{Currency as _, Hooks},
+	traits::{Currency as _, GetStorageVersion, Hooks, StorageVersion},
 	BoundedVec,
 };
 use pallet_balances::Error as BalancesError;
 use parking_lot::RwLock;
-use sp_core::offchain::{testing, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt};
+use sp_core::{
+	hexdisplay::HexDisplay,
+	offchain::{
+		testing, OffchainDbExt, OffchainWorkerExt, StorageKind, Timestamp as OffchainTimestamp,
+		TransactionPoolExt,
+	},
+	sr25519, Pair,
+};
 use sp_io::TestExternalities;
 use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
 use sp_runtime::{
@@ -58,6 +65,7 @@ prop_compose! {
 				reward_weight: reward,
 				slash,
 				emit_price_changes: false,
+				max_price_change: None,
 			}
 		}
 }
@@ -1037,6 +1045,7 @@ fn test_payout_slash() {
 			reward_weight: 0,
 			slash: 0,
 			emit_price_changes: false,
+			max_price_change: None,
 		};
 		// doesn't panic when percent not set
 		assert_ok!(Oracle::handle_payout(&vec![one, two, three, four, five], 100, 0, &asset_info));
@@ -1454,6 +1463,614 @@ fn update_price() {
 	});
 }
 
+fn add_asset_with_circuit_breaker(asset_id: u128, max_price_change: Percent) {
+	assert_ok!(Oracle::add_asset_and_info(
+		Origin::signed(get_root_account()),
+		asset_id,
+		Validated::new(Percent::from_percent(80)).unwrap(),
+		Validated::new(3).unwrap(),
+		Validated::new(5).unwrap(),
+		Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+		5,
+		5,
+		false,
+	));
+	assert_ok!(Oracle::set_circuit_breaker(
+		Origin::signed(get_root_account()),
+		asset_id,
+		Some(max_price_change)
+	));
+}
+
+fn submit_prices_and_update(asset_id: u128, prices: [u128; 3], block: u64) {
+	for price in prices {
+		add_price_storage(price, asset_id, get_account_1(), block);
+	}
+	System::set_block_number(block);
+	Oracle::on_initialize(block);
+}
+
+#[test]
+fn set_circuit_breaker() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Oracle::set_circuit_breaker(
+				Origin::signed(get_root_account()),
+				0,
+				Some(Percent::from_percent(10))
+			),
+			Error::<Test>::InvalidAssetId
+		);
+		add_asset_with_circuit_breaker(0, Percent::from_percent(10));
+		assert_noop!(
+			Oracle::set_circuit_breaker(Origin::signed(get_account_1()), 0, None),
+			BadOrigin
+		);
+		assert_eq!(
+			Oracle::asset_info(0).unwrap().max_price_change,
+			Some(Percent::from_percent(10))
+		);
+		System::assert_last_event(Event::Oracle(crate::Event::CircuitBreakerSet(
+			0,
+			Some(Percent::from_percent(10)),
+		)));
+
+		// changing the info of the asset keeps its circuit breaker
+		assert_ok!(Oracle::add_asset_and_info(
+			Origin::signed(get_root_account()),
+			0,
+			Validated::new(Percent::from_percent(70)).unwrap(),
+			Validated::new(3).unwrap(),
+			Validated::new(5).unwrap(),
+			Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+			5,
+			5,
+			false,
+		));
+		assert_eq!(
+			Oracle::asset_info(0).unwrap().max_price_change,
+			Some(Percent::from_percent(10))
+		);
+
+		assert_ok!(Oracle::set_circuit_breaker(Origin::signed(get_root_account()), 0, None));
+		assert_eq!(Oracle::asset_info(0).unwrap().max_price_change, None);
+	});
+}
+
+#[test]
+fn circuit_breaker_freezes_price() {
+	new_test_ext().execute_with(|| {
+		add_asset_with_circuit_breaker(0, Percent::from_percent(10));
+
+		// the first price has nothing to be compared with
+		submit_prices_and_update(0, [100, 101, 102], 5);
+		assert_eq!(Oracle::prices(0), Price { price: 101, block: 5 });
+
+		submit_prices_and_update(0, [200, 201, 202], 11);
+		assert_eq!(Oracle::prices(0), Price { price: 101, block: 5 });
+		assert_eq!(Oracle::frozen_price(0), Some(Price { price: 201, block: 11 }));
+		assert_eq!(Oracle::price_history(0), vec![Price { price: 101, block: 5 }]);
+		assert!(Oracle::pre_prices(0).is_empty());
+		System::assert_has_event(Event::Oracle(crate::Event::PriceFrozen(0, 101, 201)));
+
+		// frozen until governance steps in, even for prices within the allowed change
+		submit_prices_and_update(0, [101, 102, 103], 17);
+		assert_eq!(Oracle::prices(0), Price { price: 101, block: 5 });
+		assert_eq!(Oracle::frozen_price(0), Some(Price { price: 102, block: 17 }));
+	});
+}
+
+#[test]
+fn rounds_rejected_by_the_circuit_breaker_are_neither_rewarded_nor_slashed() {
+	new_test_ext().execute_with(|| {
+		add_asset_with_circuit_breaker(0, Percent::from_percent(10));
+		submit_prices_and_update(0, [100, 101, 102], 5);
+		let account_1 = get_account_1();
+		let statistics = Oracle::answer_statistics(0, account_1);
+		let stake = Oracle::oracle_stake(account_1);
+
+		// 300 would be slashed if the round was accepted
+		submit_prices_and_update(0, [200, 201, 300], 11);
+		assert_eq!(Oracle::frozen_price(0), Some(Price { price: 201, block: 11 }));
+		assert_eq!(Oracle::answer_statistics(0, account_1), statistics);
+		assert_eq!(Oracle::oracle_stake(account_1), stake);
+		assert_eq!(Oracle::answer_in_transit(account_1), Some(0));
+
+		submit_prices_and_update(0, [101, 102, 300], 17);
+		assert_eq!(Oracle::answer_statistics(0, account_1), statistics);
+		assert_eq!(Oracle::oracle_stake(account_1), stake);
+		assert_eq!(Oracle::answer_in_transit(account_1), Some(0));
+	});
+}
+
+#[test]
+fn circuit_breaker_allows_change_over_elapsed_intervals() {
+	new_test_ext().execute_with(|| {
+		add_asset_with_circuit_breaker(0, Percent::from_percent(10));
+		submit_prices_and_update(0, [100, 101, 102], 5);
+
+		// 2 block intervals elapsed, the price can move by 20%
+		submit_prices_and_update(0, [119, 120, 121], 15);
+		assert_eq!(Oracle::prices(0), Price { price: 120, block: 15 });
+		assert_eq!(Oracle::frozen_price(0), None);
+
+		submit_prices_and_update(0, [133, 134, 135], 21);
+		assert_eq!(Oracle::prices(0), Price { price: 120, block: 15 });
+		assert_eq!(Oracle::frozen_price(0), Some(Price { price: 134, block: 21 }));
+	});
+}
+
+#[test]
+fn unfreeze_price() {
+	new_test_ext().execute_with(|| {
+		add_asset_with_circuit_breaker(0, Percent::from_percent(10));
+		assert_noop!(
+			Oracle::unfreeze_price(Origin::signed(get_root_account()), 0, None),
+			Error::<Test>::PriceNotFrozen
+		);
+		submit_prices_and_update(0, [100, 101, 102], 5);
+		submit_prices_and_update(0, [200, 201, 202], 11);
+
+		assert_noop!(Oracle::unfreeze_price(Origin::signed(get_account_1()), 0, None), BadOrigin);
+		// unfreezing without a price keeps the last accepted price as reference
+		assert_ok!(Oracle::unfreeze_price(Origin::signed(get_root_account()), 0, None));
+		assert_eq!(Oracle::frozen_price(0), None);
+		assert_eq!(Oracle::prices(0), Price { price: 101, block: 5 });
+		System::assert_last_event(Event::Oracle(crate::Event::PriceUnfrozen(0, None)));
+
+		submit_prices_and_update(0, [200, 201, 202], 17);
+		assert_eq!(Oracle::frozen_price(0), Some(Price { price: 201, block: 17 }));
+
+		// governance accepts the new price
+		assert_ok!(Oracle::unfreeze_price(Origin::signed(get_root_account()), 0, Some(201)));
+		assert_eq!(Oracle::frozen_price(0), None);
+		assert_eq!(Oracle::prices(0), Price { price: 201, block: 17 });
+		assert_eq!(
+			Oracle::price_history(0),
+			vec![Price { price: 101, block: 5 }, Price { price: 201, block: 17 }]
+		);
+		System::assert_last_event(Event::Oracle(crate::Event::PriceUnfrozen(0, Some(201))));
+
+		submit_prices_and_update(0, [205, 206, 207], 23);
+		assert_eq!(Oracle::prices(0), Price { price: 206, block: 23 });
+	});
+}
+
+#[test]
+fn set_price_fallbacks() {
+	new_test_ext().execute_with(|| {
+		let sources =
+			BoundedVec::try_from(vec![FallbackSource::PoolTwap { pool_id: 7, quote: 1 }]).unwrap();
+		assert_noop!(
+			Oracle::set_price_fallbacks(Origin::signed(get_root_account()), 0, 10, sources.clone()),
+			Error::<Test>::InvalidAssetId
+		);
+		add_asset_with_circuit_breaker(0, Percent::from_percent(10));
+		assert_noop!(
+			Oracle::set_price_fallbacks(Origin::signed(get_account_1()), 0, 10, sources.clone()),
+			BadOrigin
+		);
+
+		assert_ok!(Oracle::set_price_fallbacks(
+			Origin::signed(get_root_account()),
+			0,
+			10,
+			sources.clone()
+		));
+		assert_eq!(Oracle::price_fallback(0), Some(PriceFallback { max_price_age: 10, sources }));
+		System::assert_last_event(Event::Oracle(crate::Event::PriceFallbacksSet(0)));
+
+		assert_ok!(Oracle::set_price_fallbacks(
+			Origin::signed(get_root_account()),
+			0,
+			10,
+			BoundedVec::default()
+		));
+		assert_eq!(Oracle::price_fallback(0), None);
+	});
+}
+
+#[test]
+fn get_price_falls_back_when_stale_or_frozen() {
+	new_test_ext().execute_with(|| {
+		add_asset_with_circuit_breaker(0, Percent::from_percent(10));
+		Prices::<Test>::insert(0, Price { price: 5_000, block: 1 });
+		Prices::<Test>::insert(1, Price { price: 2_000, block: 1 });
+		MockPoolTwap::set_twap(7, 0, 1, FixedU128::saturating_from_integer(3));
+		assert_ok!(Oracle::set_price_fallbacks(
+			Origin::signed(get_root_account()),
+			0,
+			10,
+			BoundedVec::try_from(vec![
+				// no average price for this pool, skipped
+				FallbackSource::PoolTwap { pool_id: 8, quote: 1 },
+				FallbackSource::PoolTwap { pool_id: 7, quote: 1 },
+			])
+			.unwrap()
+		));
+
+		System::set_block_number(11);
+		assert_eq!(
+			<Oracle as oracle::Oracle>::get_price(0, UNIT),
+			Ok(Price { price: 5_000, block: 1 })
+		);
+
+		// stale, priced from the average price in the pool and the price of the quote asset
+		System::set_block_number(12);
+		assert_eq!(
+			<Oracle as oracle::Oracle>::get_price(0, UNIT),
+			Ok(Price { price: 6_000, block: 12 })
+		);
+		assert_eq!(
+			<Oracle as oracle::Oracle>::get_price(0, 2 * UNIT),
+			Ok(Price { price: 12_000, block: 12 })
+		);
+
+		// frozen
+		Prices::<Test>::insert(0, Price { price: 5_000, block: 12 });
+		FrozenPrices::<Test>::insert(0, Price { price: 9_000, block: 12 });
+		assert_eq!(
+			<Oracle as oracle::Oracle>::get_price(0, UNIT),
+			Ok(Price { price: 6_000, block: 12 })
+		);
+
+		// no fallback able to price the asset, the last accepted price is used
+		Prices::<Test>::remove(1);
+		assert_eq!(
+			<Oracle as oracle::Oracle>::get_price(0, UNIT),
+			Ok(Price { price: 5_000, block: 12 })
+		);
+	});
+}
+
+#[test]
+fn current_price() {
+	new_test_ext().execute_with(|| {
+		add_asset_with_circuit_breaker(0, Percent::from_percent(10));
+		assert_eq!(Oracle::current_price(0), None);
+
+		submit_prices_and_update(0, [100, 101, 102], 5);
+		assert_eq!(
+			Oracle::current_price(0),
+			Some(CurrentPrice { price: 101, block: 5, frozen: false, fallback: false })
+		);
+
+		submit_prices_and_update(0, [200, 201, 202], 11);
+		assert_eq!(
+			Oracle::current_price(0),
+			Some(CurrentPrice { price: 101, block: 5, frozen: true, fallback: false })
+		);
+
+		Prices::<Test>::insert(1, Price { price: 2_000, block: 11 });
+		MockPoolTwap::set_twap(7, 0, 1, FixedU128::saturating_from_rational(1, 20));
+		assert_ok!(Oracle::set_price_fallbacks(
+			Origin::signed(get_root_account()),
+			0,
+			10,
+			BoundedVec::try_from(vec![FallbackSource::PoolTwap { pool_id: 7, quote: 1 }]).unwrap()
+		));
+		assert_eq!(
+			Oracle::current_price(0),
+			Some(CurrentPrice { price: 100, block: 11, frozen: true, fallback: true })
+		);
+	});
+}
+
+#[test]
+fn twap_over_window() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Oracle::twap_over_window(0, 10), None);
+		set_historic_prices(
+			0,
+			vec![
+				Price { price: 100, block: 10 },
+				Price { price: 200, block: 20 },
+				Price { price: 400, block: 25 },
+			],
+		);
+		Prices::<Test>::insert(0, Price { price: 400, block: 25 });
+		System::set_block_number(30);
+
+		assert_eq!(Oracle::twap_over_window(0, 5), Some(400));
+		// (200 * 5 + 400 * 5) / 10
+		assert_eq!(Oracle::twap_over_window(0, 10), Some(300));
+		// (100 * 5 + 200 * 5 + 400 * 5) / 15
+		assert_eq!(Oracle::twap_over_window(0, 15), Some(233));
+		// (100 * 10 + 200 * 5 + 400 * 5) / 20, the history starts at block 10
+		assert_eq!(Oracle::twap_over_window(0, 100), Some(200));
+		assert_eq!(Oracle::twap_over_window(0, 0), Some(400));
+	});
+}
+
+#[test]
+fn ohlc() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Oracle::ohlc(0, 10), vec![]);
+		set_historic_prices(
+			0,
+			vec![
+				Price { price: 100, block: 11 },
+				Price { price: 120, block: 15 },
+				Price { price: 90, block: 19 },
+			],
+		);
+		assert_eq!(
+			Oracle::ohlc(0, 10),
+			vec![Ohlc { start: 10, open: 100, high: 120, low: 90, close: 90 }]
+		);
+		assert_eq!(
+			Oracle::ohlc(0, 5),
+			vec![
+				Ohlc { start: 10, open: 100, high: 100, low: 100, close: 100 },
+				Ohlc { start: 15, open: 120, high: 120, low: 90, close: 90 },
+			]
+		);
+		assert_eq!(Oracle::ohlc(0, 0), vec![]);
+	});
+}
+
+#[test]
+fn oracle_accuracy() {
+	new_test_ext().execute_with(|| {
+		let account_1 = get_account_1();
+		let account_3 = get_account_3();
+		let account_4 = get_account_4();
+		let asset_info = AssetInfo {
+			threshold: Percent::from_percent(90),
+			min_answers: 1,
+			max_answers: 3,
+			block_interval: 5,
+			reward_weight: 0,
+			slash: 5,
+			emit_price_changes: false,
+			max_price_change: None,
+		};
+		let answer = |price, who| PrePrice { price, block: 0, who };
+		assert_ok!(Oracle::handle_payout(
+			&[answer(100, account_1), answer(95, account_3), answer(50, account_4)],
+			100,
+			0,
+			&asset_info
+		));
+		assert_ok!(Oracle::handle_payout(&[answer(98, account_1)], 100, 0, &asset_info));
+
+		let mut accuracy = Oracle::oracle_accuracy(0);
+		accuracy.sort_by_key(|accuracy| accuracy.answers);
+		assert_eq!(accuracy.len(), 3);
+		assert_eq!(
+			accuracy.last(),
+			Some(&OracleAccuracy {
+				oracle: account_1,
+				answers: 2,
+				slashes: 0,
+				average_accuracy: Percent::from_percent(99),
+			})
+		);
+		assert!(accuracy.contains(&OracleAccuracy {
+			oracle: account_4,
+			answers: 1,
+			slashes: 1,
+			average_accuracy: Percent::from_percent(50),
+		}));
+		assert!(Oracle::oracle_accuracy(1).is_empty());
+	});
+}
+
+fn add_asset_with_commit_reveal(asset_id: u128) -> CommitRevealPeriod<BlockNumber> {
+	let account_1 = get_account_1();
+	let account_2 = get_root_account();
+	let account_4 = get_account_4();
+	let account_5 = get_account_5();
+	assert_ok!(Oracle::add_asset_and_info(
+		Origin::signed(account_2),
+		asset_id,
+		Validated::new(Percent::from_percent(80)).unwrap(),
+		Validated::new(2).unwrap(),
+		Validated::new(3).unwrap(),
+		Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+		5,
+		5,
+		false,
+	));
+	let period = CommitRevealPeriod { commit_period: 2, reveal_period: 2 };
+	assert_ok!(Oracle::set_commit_reveal(Origin::signed(account_2), asset_id, Some(period)));
+
+	assert_ok!(Oracle::set_signer(Origin::signed(account_2), account_1));
+	assert_ok!(Oracle::set_signer(Origin::signed(account_1), account_2));
+	assert_ok!(Oracle::set_signer(Origin::signed(account_5), account_4));
+	assert_ok!(Oracle::add_stake(Origin::signed(account_1), 50));
+	assert_ok!(Oracle::add_stake(Origin::signed(account_2), 50));
+	assert_ok!(Oracle::add_stake(Origin::signed(account_5), 50));
+	period
+}
+
+#[test]
+fn set_commit_reveal() {
+	new_test_ext().execute_with(|| {
+		let account_1 = get_account_1();
+		let period = CommitRevealPeriod { commit_period: 2, reveal_period: 2 };
+		assert_noop!(
+			Oracle::set_commit_reveal(Origin::signed(get_root_account()), 0, Some(period)),
+			Error::<Test>::InvalidAssetId
+		);
+
+		add_asset_with_commit_reveal(0);
+		assert_eq!(Oracle::commit_reveal_period(0), Some(period));
+		assert_noop!(Oracle::set_commit_reveal(Origin::signed(account_1), 0, None), BadOrigin);
+		for invalid_period in [
+			CommitRevealPeriod { commit_period: 0, reveal_period: 2 },
+			CommitRevealPeriod { commit_period: 2, reveal_period: 0 },
+			CommitRevealPeriod { commit_period: 2, reveal_period: StalePrice::get() + 1 },
+		] {
+			assert_noop!(
+				Oracle::set_commit_reveal(
+					Origin::signed(get_root_account()),
+					0,
+					Some(invalid_period)
+				),
+				Error::<Test>::InvalidCommitRevealPeriod
+			);
+		}
+
+		System::set_block_number(6);
+		assert_noop!(
+			Oracle::submit_price(Origin::signed(account_1), 100, 0),
+			Error::<Test>::CommitRevealRequired
+		);
+		assert_ok!(Oracle::commit_price(
+			Origin::signed(account_1),
+			0,
+			Oracle::price_commitment(&account_1, 100, &[1; 32])
+		));
+		assert_eq!(Oracle::answer_in_transit(account_1), Some(5));
+
+		// pending commitments are released
+		assert_ok!(Oracle::set_commit_reveal(Origin::signed(get_root_account()), 0, None));
+		System::assert_last_event(Event::Oracle(crate::Event::CommitRevealSet(0, None)));
+		assert_eq!(Oracle::commit_reveal_period(0), None);
+		assert!(Oracle::price_commitments(0).is_empty());
+		assert_eq!(Oracle::answer_in_transit(account_1), Some(0));
+		assert_noop!(
+			Oracle::commit_price(Origin::signed(account_1), 0, H256::zero()),
+			Error::<Test>::CommitRevealDisabled
+		);
+		assert_ok!(Oracle::submit_price(Origin::signed(account_1), 100, 0));
+	});
+}
+
+#[test]
+fn commit_reveal_round() {
+	new_test_ext().execute_with(|| {
+		let account_1 = get_account_1();
+		let account_2 = get_root_account();
+		let account_4 = get_account_4();
+		add_asset_with_commit_reveal(0);
+		let salt = [7; 32];
+
+		System::set_block_number(6);
+		assert_ok!(Oracle::commit_price(
+			Origin::signed(account_1),
+			0,
+			Oracle::price_commitment(&account_1, 100, &salt)
+		));
+		System::assert_last_event(Event::Oracle(crate::Event::PriceCommitted(account_1, 0)));
+		assert_noop!(
+			Oracle::commit_price(Origin::signed(account_1), 0, H256::zero()),
+			Error::<Test>::AlreadySubmitted
+		);
+		// commitments do not disclose the price
+		assert!(Oracle::pre_prices(0).is_empty());
+
+		System::set_block_number(7);
+		assert_ok!(Oracle::commit_price(
+			Origin::signed(account_2),
+			0,
+			Oracle::price_commitment(&account_2, 102, &salt)
+		));
+		assert_ok!(Oracle::commit_price(
+			Origin::signed(account_4),
+			0,
+			Oracle::price_commitment(&account_4, 101, &salt)
+		));
+		assert_noop!(
+			Oracle::reveal_price(Origin::signed(account_1), 0, 100, salt),
+			Error::<Test>::NotRevealPeriod
+		);
+
+		System::set_block_number(8);
+		OracleStake::<Test>::insert(get_account_3(), 50);
+		assert_noop!(
+			Oracle::commit_price(Origin::signed(get_account_3()), 0, H256::zero()),
+			Error::<Test>::CommitPeriodOver
+		);
+		assert_noop!(
+			Oracle::reveal_price(Origin::signed(account_1), 0, 101, salt),
+			Error::<Test>::InvalidReveal
+		);
+		assert_noop!(
+			Oracle::reveal_price(Origin::signed(account_1), 0, 100, [8; 32]),
+			Error::<Test>::InvalidReveal
+		);
+		assert_noop!(
+			Oracle::reveal_price(Origin::signed(get_account_3()), 0, 100, salt),
+			Error::<Test>::CommitmentNotFound
+		);
+		assert_ok!(Oracle::reveal_price(Origin::signed(account_1), 0, 100, salt));
+		System::assert_last_event(Event::Oracle(crate::Event::PriceRevealed(account_1, 0, 100)));
+		assert_noop!(
+			Oracle::reveal_price(Origin::signed(account_1), 0, 100, salt),
+			Error::<Test>::AlreadyRevealed
+		);
+
+		System::set_block_number(9);
+		assert_ok!(Oracle::reveal_price(Origin::signed(account_2), 0, 102, salt));
+		assert_eq!(
+			Oracle::pre_prices(0),
+			vec![
+				PrePrice { price: 100, block: 8, who: account_1 },
+				PrePrice { price: 102, block: 9, who: account_2 }
+			]
+		);
+		// prices are not aggregated while the round is open
+		Oracle::on_initialize(9);
+		assert_eq!(Oracle::prices(0), Price::default());
+		assert_eq!(Oracle::price_commitments(0).len(), 3);
+
+		System::set_block_number(10);
+		assert_noop!(
+			Oracle::reveal_price(Origin::signed(account_4), 0, 101, salt),
+			Error::<Test>::NotRevealPeriod
+		);
+		let stake = Oracle::oracle_stake(account_4).unwrap();
+		Oracle::on_initialize(10);
+
+		// account 4 did not reveal its price and gets slashed
+		System::assert_has_event(Event::Oracle(crate::Event::UserSlashed(account_4, 0, 5)));
+		assert_eq!(Oracle::oracle_stake(account_4), Some(stake - 5));
+		assert_eq!(Oracle::answer_statistics(0, account_4).map(|s| s.slashes), Some(1));
+		assert_eq!(Oracle::prices(0), Price { price: 101, block: 10 });
+		assert!(Oracle::price_commitments(0).is_empty());
+		assert!(Oracle::pre_prices(0).is_empty());
+		assert_eq!(Oracle::answer_in_transit(account_1), Some(0));
+		assert_eq!(Oracle::answer_in_transit(account_2), Some(0));
+		assert_eq!(Oracle::answer_in_transit(account_4), Some(0));
+	});
+}
+
+#[test]
+fn migrate_asset_info_to_v1() {
+	new_test_ext().execute_with(|| {
+		let asset_info = crate::migrations::AssetInfoV0 {
+			threshold: Percent::from_percent(80),
+			min_answers: 3,
+			max_answers: 5,
+			block_interval: 5_u64,
+			reward_weight: 5_u128,
+			slash: 5_u128,
+			emit_price_changes: true,
+		};
+		frame_support::storage::unhashed::put(&AssetsInfo::<Test>::hashed_key_for(0), &asset_info);
+		StorageVersion::new(0).put::<Oracle>();
+
+		Oracle::on_runtime_upgrade();
+
+		assert_eq!(
+			Oracle::asset_info(0),
+			Some(AssetInfo {
+				threshold: Percent::from_percent(80),
+				min_answers: 3,
+				max_answers: 5,
+				block_interval: 5,
+				reward_weight: 5,
+				slash: 5,
+				emit_price_changes: true,
+				max_price_change: None,
+			})
+		);
+		assert_eq!(Oracle::on_chain_storage_version(), StorageVersion::new(1));
+	});
+}
+
 #[test]
 fn historic_pricing() {
 	new_test_ext().execute_with(|| {
@@ -1766,6 +2383,7 @@ fn prune_old_pre_prices_edgecase() {
 			reward_weight: 5,
 			slash: 5,
 			emit_price_changes: false,
+			max_price_change: None,
 		};
 		Oracle::prune_old_pre_prices(&asset_info, vec![], 0);
 	});
@@ -1890,6 +2508,7 @@ fn should_check_oracles_max_answer() {
 		reward_weight: 0,
 		slash: 0,
 		emit_price_changes: false,
+		max_price_change: None,
 	};
 	t.execute_with(|| {
 		Oracle::fetch_price_and_send_signed(&0, asset_info).unwrap();
@@ -1910,6 +2529,91 @@ fn parse_price_works() {
 	}
 }
 
+fn operator() -> sr25519::Pair {
+	sr25519::Pair::from_string("//Alice", None).unwrap()
+}
+
+fn signed_price_response(price: u64, timestamp: i64) -> String {
+	let signature = operator().sign(&(0_u128, price, timestamp).encode());
+	format!(
+		r#"{{"0": {}, "timestamp": {}, "signature": "0x{}"}}"#,
+		price,
+		timestamp,
+		HexDisplay::from(&signature.0)
+	)
+}
+
+fn signed_price_oracle_response(state: &mut testing::OffchainState) {
+	state.timestamp = OffchainTimestamp::from_unix_millis(50_000);
+	state.expect_request(testing::PendingRequest {
+		method: "GET".into(),
+		uri: "http://localhost:3001/price/0".into(),
+		response: Some(signed_price_response(15523, 42).into_bytes()),
+		sent: true,
+		..Default::default()
+	});
+}
+
+#[test]
+fn verify_price_works() {
+	let signer = operator().public();
+	let response = signed_price_response(15523, 42);
+	assert!(Oracle::verify_price(&response, 0, 15523, &signer, 50));
+	// the signature doesn't cover another price, asset or operator
+	assert!(!Oracle::verify_price(&response, 0, 15524, &signer, 50));
+	assert!(!Oracle::verify_price(&response, 1, 15523, &signer, 50));
+	assert!(!Oracle::verify_price(
+		&response,
+		0,
+		15523,
+		&sr25519::Pair::from_string("//Bob", None).unwrap().public(),
+		50
+	));
+	assert!(!Oracle::verify_price(&response.replace("42", "43"), 0, 15523, &signer, 50));
+	assert!(!Oracle::verify_price(r#"{"0": 15523, "timestamp": 42}"#, 0, 15523, &signer, 50));
+	assert!(!Oracle::verify_price(
+		r#"{"0": 15523, "timestamp": 42, "signature": "0xzz"}"#,
+		0,
+		15523,
+		&signer,
+		50
+	));
+}
+
+#[test]
+fn verify_price_rejects_stale_and_future_prices() {
+	let signer = operator().public();
+	let response = signed_price_response(15523, 100);
+	// up to a minute old, up to 5 seconds ahead of the clock of the offchain worker
+	assert!(Oracle::verify_price(&response, 0, 15523, &signer, 160));
+	assert!(!Oracle::verify_price(&response, 0, 15523, &signer, 161));
+	assert!(Oracle::verify_price(&response, 0, 15523, &signer, 95));
+	assert!(!Oracle::verify_price(&response, 0, 15523, &signer, 94));
+}
+
+#[test]
+fn should_only_accept_prices_signed_by_the_operator() {
+	let (mut t, _, _) = offchain_worker_env(signed_price_oracle_response);
+	t.execute_with(|| {
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			b"ocw-feed-signer",
+			&operator().public().0,
+		);
+		assert_eq!(Oracle::fetch_price(&0).unwrap(), 15523);
+	});
+
+	let (mut t, _, _) = offchain_worker_env(signed_price_oracle_response);
+	t.execute_with(|| {
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			b"ocw-feed-signer",
+			&sr25519::Pair::from_string("//Bob", None).unwrap().public().0,
+		);
+		assert!(Oracle::fetch_price(&0).is_err());
+	});
+}
+
 fn add_price_storage(price: u128, asset_id: u128, who: AccountId, block: u64) {
 	let price = PrePrice { price, block, who };
 	PrePrices::<Test>::mutate(asset_id, |current_prices| current_prices.try_push(price).unwrap());
@@ -16,6 +16,13 @@ pub trait WeightInfo {
 	fn submit_price(p: u32) -> Weight;
 	fn update_pre_prices(p: u32) -> Weight;
 	fn update_price(p: u32) -> Weight;
+	fn set_circuit_breaker() -> Weight;
+	fn unfreeze_price() -> Weight;
+	fn set_price_fallbacks(s: u32) -> Weight;
+	fn set_commit_reveal(p: u32) -> Weight;
+	fn commit_price(p: u32) -> Weight;
+	fn reveal_price(p: u32) -> Weight;
+	fn close_commit_reveal_round(p: u32) -> Weight;
 }
 
 /// Weights for pallet_oracle using the Substrate node and recommended hardware.
@@ -72,6 +79,49 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
+	fn set_circuit_breaker() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn unfreeze_price() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_price_fallbacks(s: u32) -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_commit_reveal(p: u32) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	fn commit_price(p: u32) -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add((270_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn reveal_price(p: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((300_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn close_commit_reveal_round(p: u32) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(p as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -129,4 +179,47 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
+	fn set_circuit_breaker() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unfreeze_price() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn set_price_fallbacks(s: u32) -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_commit_reveal(p: u32) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	fn commit_price(p: u32) -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add((270_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn reveal_price(p: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((300_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn close_commit_reveal_round(p: u32) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(p as Weight)))
+	}
 }
@@ -1,19 +1,32 @@
 use super::*;
 use crate::{
+	orders::OrderBook,
+	types::{OrderKind, OrderTrigger, PriceSource},
 	Pallet as Pablo,
-	PoolConfiguration::{ConstantProduct, LiquidityBootstrapping, StableSwap},
+	PoolConfiguration::{
+		ConcentratedLiquidity, ConstantProduct, LiquidityBootstrapping, MultiAsset, StableSwap,
+	},
 };
+use composable_maths::dex::concentrated_liquidity::{amounts_for_liquidity, sqrt_price_at_tick};
 use composable_traits::{
-	defi::CurrencyPair,
-	dex::{Amm, FeeConfig, LiquidityBootstrappingPoolInfo, Sale},
+	defi::{CurrencyPair, Rate},
+	dex::{
+		Amm, FeeConfig, LiquidityBootstrappingPoolInfo, MultiAssetAmm, MultiAssetPoolKind, Sale,
+	},
 };
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
 use frame_support::{
 	assert_ok,
-	traits::fungibles::{Inspect, Mutate},
+	traits::{
+		fungibles::{Inspect, Mutate},
+		Get, TryCollect,
+	},
+	BoundedBTreeMap,
 };
 use frame_system::RawOrigin;
-use sp_arithmetic::Permill;
+use sp_arithmetic::{PerThing, Permill};
+use sp_runtime::FixedPointNumber;
+use sp_std::collections::btree_map::BTreeMap;
 
 fn stable_swap_init_config<T: Config>(
 	owner: T::AccountId,
@@ -33,12 +46,141 @@ fn create_stable_swap_pool<T: Config>(
 	Pablo::<T>::do_create_pool(stable_swap_pool_init).expect("impossible; qed;")
 }
 
+fn create_concentrated_liquidity_pool<T: Config>(
+	owner: T::AccountId,
+	pair: CurrencyPair<T::AssetId>,
+) -> T::PoolId {
+	let concentrated_liquidity_pool_init = PoolInitConfiguration::ConcentratedLiquidity {
+		owner,
+		pair,
+		fee: Permill::from_percent(1),
+		tick_spacing: 10,
+		initial_tick: 0,
+	};
+	Pablo::<T>::do_create_pool(concentrated_liquidity_pool_init).expect("impossible; qed;")
+}
+
+fn create_position<T: Config>(
+	owner: &T::AccountId,
+	pool_id: T::PoolId,
+	amount: T::Balance,
+) -> T::FinancialNftInstanceId
+where
+	T::Balance: From<u128>,
+{
+	let pair = <Pablo<T> as Amm>::currency_pair(pool_id).expect("impossible; qed;");
+	assert_ok!(T::Assets::mint_into(pair.base, owner, amount));
+	assert_ok!(T::Assets::mint_into(pair.quote, owner, amount));
+	assert_ok!(Pablo::<T>::open_position(
+		RawOrigin::Signed(owner.clone()).into(),
+		pool_id,
+		-1000,
+		1000,
+		amount,
+		amount,
+		0_u128.into(),
+		0_u128.into(),
+	));
+	Positions::<T>::iter_key_prefix(pool_id).next().expect("impossible; qed;")
+}
+
+/// Stable swap pool of the maximum number of assets, with equal weights.
+fn create_multi_asset_pool<T: Config>(owner: T::AccountId) -> (T::PoolId, Vec<T::AssetId>)
+where
+	T::AssetId: From<u128>,
+{
+	let assets_count = T::MaxAssetsPerPool::get();
+	let assets: Vec<T::AssetId> =
+		(0..assets_count).map(|i| (200_u128 + i as u128).into()).collect();
+	let weight = Permill::one().deconstruct() / assets_count;
+	let first_weight = Permill::one().deconstruct() - weight * (assets_count - 1);
+	let weights = assets
+		.iter()
+		.enumerate()
+		.map(|(i, asset_id)| {
+			(*asset_id, Permill::from_parts(if i == 0 { first_weight } else { weight }))
+		})
+		.try_collect()
+		.expect("impossible; qed;");
+	let multi_asset_pool_init = PoolInitConfiguration::MultiAsset {
+		owner,
+		assets: weights,
+		kind: MultiAssetPoolKind::StableSwap { amplification_coefficient: 100 },
+		fee: Permill::from_percent(1),
+	};
+	let pool_id = Pablo::<T>::do_create_pool(multi_asset_pool_init).expect("impossible; qed;");
+	(pool_id, assets)
+}
+
+fn add_multi_asset_liquidity<T: Config>(
+	owner: &T::AccountId,
+	pool_id: T::PoolId,
+	assets: &[T::AssetId],
+	amount: T::Balance,
+) where
+	T::Balance: From<u128>,
+{
+	for asset_id in assets {
+		assert_ok!(T::Assets::mint_into(*asset_id, owner, amount));
+	}
+	assert_ok!(<Pablo<T> as MultiAssetAmm>::add_liquidity_assets(
+		owner,
+		pool_id,
+		assets.iter().map(|asset_id| (*asset_id, amount)).collect(),
+		0_u128.into(),
+		false
+	));
+}
+
+/// Stable swap pool with liquidity, along a limit order selling its quote asset which is always
+/// triggered.
+fn create_order<T: Config>(owner: &T::AccountId) -> T::OrderId
+where
+	T::AssetId: From<u128>,
+	T::Balance: From<u128>,
+	T::BlockNumber: From<u32>,
+{
+	let usdc: T::AssetId = 100.into();
+	let usdt: T::AssetId = 101.into();
+	let pair = CurrencyPair::new(usdc, usdt);
+	let pool_id = create_stable_swap_pool::<T>(owner.clone(), pair);
+	let unit = 1_000_000_000_000;
+	let initial_amount: T::Balance = (100_000_000_u128 * unit).into();
+	assert_ok!(T::Assets::mint_into(usdc, owner, initial_amount));
+	assert_ok!(T::Assets::mint_into(usdt, owner, initial_amount));
+	assert_ok!(<Pablo<T> as Amm>::add_liquidity(
+		owner,
+		pool_id,
+		initial_amount,
+		initial_amount,
+		0_u128.into(),
+		false
+	));
+	assert_ok!(T::Assets::mint_into(usdt, owner, (1001_u128 * unit).into()));
+	OrderBook::<T>::place(
+		owner,
+		pool_id,
+		pair,
+		(1000_u128 * unit).into(),
+		0_u128.into(),
+		OrderTrigger {
+			kind: OrderKind::Limit,
+			source: PriceSource::Spot,
+			price: Rate::from_inner(0),
+		},
+		unit.into(),
+		100_u32.into(),
+	)
+	.expect("impossible; qed;")
+}
+
 fn get_lp_token<T: Config>(pool_id: T::PoolId) -> T::AssetId {
 	let pool_info = Pablo::<T>::get_pool(pool_id).expect("impossible; qed;");
 	match pool_info {
 		StableSwap(pool) => pool.lp_token,
 		ConstantProduct(pool) => pool.lp_token,
-		LiquidityBootstrapping(_) => panic!("Not implemented"),
+		MultiAsset(pool) => pool.lp_token,
+		LiquidityBootstrapping(_) | ConcentratedLiquidity(_) => panic!("Not implemented"),
 	}
 }
 
@@ -276,6 +418,145 @@ benchmarks! {
 		assert_ok!(T::Assets::mint_into(usdt, &user, (1000_u128 * unit).into()));
 		// swap 1000 USDC
 	 }: _(RawOrigin::Signed(user), pool_id, CurrencyPair::new(usdc, usdt), (1000_u128 * unit).into(), 0.into(), false)
+
+	swap_concentrated_liquidity {
+		let s in 1 .. T::MaxSwapSteps::get();
+		let usdc: T::AssetId = 100.into();
+		let usdt: T::AssetId = 101.into();
+		let owner: T::AccountId = whitelisted_caller();
+		let pool_id = create_concentrated_liquidity_pool::<T>(owner.clone(), CurrencyPair::new(usdc, usdt));
+		let unit = 1_000_000_000_000;
+		// adjacent positions above the price, the sale crossing the ticks between each of them
+		for i in 1..=s as i32 {
+			let amount: T::Balance = (1000_u128 * unit).into();
+			assert_ok!(T::Assets::mint_into(usdc, &owner, amount));
+			assert_ok!(Pablo::<T>::open_position(
+				RawOrigin::Signed(owner.clone()).into(),
+				pool_id,
+				10 * (i - 1),
+				10 * i,
+				amount,
+				0_u128.into(),
+				0_u128.into(),
+				0_u128.into(),
+			));
+		}
+		// quote amount moving the price to the top of the positions, the fees keeping the price
+		// within the last one
+		let quote_amount = Positions::<T>::iter_prefix_values(pool_id).fold(0_u128, |sum, position| {
+			let sqrt_price_lower = sqrt_price_at_tick(position.lower_tick).expect("impossible; qed;");
+			let sqrt_price_upper = sqrt_price_at_tick(position.upper_tick).expect("impossible; qed;");
+			let (_, quote_amount) = amounts_for_liquidity(
+				sqrt_price_upper,
+				sqrt_price_lower,
+				sqrt_price_upper,
+				position.liquidity,
+				false,
+			)
+			.expect("impossible; qed;");
+			sum + quote_amount
+		});
+		let user = account("user", 0, 0);
+		assert_ok!(T::Assets::mint_into(usdt, &user, quote_amount.into()));
+	}: sell(RawOrigin::Signed(user), pool_id, usdt, quote_amount.into(), 0_u128.into(), false)
+
+	open_position {
+		let usdc: T::AssetId = 100.into();
+		let usdt: T::AssetId = 101.into();
+		let owner: T::AccountId = whitelisted_caller();
+		let pool_id = create_concentrated_liquidity_pool::<T>(owner.clone(), CurrencyPair::new(usdc, usdt));
+		let unit = 1_000_000_000_000;
+		let initial_usdc: T::Balance = (100_000_000_u128 * unit).into();
+		let initial_usdt: T::Balance = (100_000_000_u128 * unit).into();
+		assert_ok!(T::Assets::mint_into(usdc, &owner, initial_usdc));
+		assert_ok!(T::Assets::mint_into(usdt, &owner, initial_usdt));
+	}: _(RawOrigin::Signed(owner), pool_id, -1000, 1000, initial_usdc, initial_usdt, 0_u128.into(), 0_u128.into())
+
+	close_position {
+		let usdc: T::AssetId = 100.into();
+		let usdt: T::AssetId = 101.into();
+		let owner: T::AccountId = whitelisted_caller();
+		let pool_id = create_concentrated_liquidity_pool::<T>(owner.clone(), CurrencyPair::new(usdc, usdt));
+		let unit = 1_000_000_000_000;
+		let position_id = create_position::<T>(&owner, pool_id, (100_000_000_u128 * unit).into());
+		let user = account("user", 0, 0);
+		assert_ok!(T::Assets::mint_into(usdt, &user, (1000_u128 * unit).into()));
+		assert_ok!(<Pablo<T> as Amm>::sell(&user, pool_id, usdt, (1000_u128 * unit).into(), 0_u128.into(), false));
+	}: _(RawOrigin::Signed(owner), pool_id, position_id, 0_u128.into(), 0_u128.into())
+
+	collect_fees {
+		let usdc: T::AssetId = 100.into();
+		let usdt: T::AssetId = 101.into();
+		let owner: T::AccountId = whitelisted_caller();
+		let pool_id = create_concentrated_liquidity_pool::<T>(owner.clone(), CurrencyPair::new(usdc, usdt));
+		let unit = 1_000_000_000_000;
+		let position_id = create_position::<T>(&owner, pool_id, (100_000_000_u128 * unit).into());
+		let user = account("user", 0, 0);
+		assert_ok!(T::Assets::mint_into(usdt, &user, (1000_u128 * unit).into()));
+		assert_ok!(<Pablo<T> as Amm>::sell(&user, pool_id, usdt, (1000_u128 * unit).into(), 0_u128.into(), false));
+	}: _(RawOrigin::Signed(owner), pool_id, position_id)
+
+	add_liquidity_assets {
+		let owner: T::AccountId = whitelisted_caller();
+		let (pool_id, assets) = create_multi_asset_pool::<T>(owner.clone());
+		let unit = 1_000_000_000_000;
+		add_multi_asset_liquidity::<T>(&owner, pool_id, &assets, (100_000_000_u128 * unit).into());
+		let user: T::AccountId = account("user", 0, 0);
+		// imbalanced deposit, charged fees on every asset
+		let mut amounts = BTreeMap::new();
+		for (i, asset_id) in assets.iter().enumerate() {
+			let amount: T::Balance = ((i as u128 + 1) * 1000_u128 * unit).into();
+			assert_ok!(T::Assets::mint_into(*asset_id, &user, amount));
+			amounts.insert(*asset_id, amount);
+		}
+		let amounts = BoundedBTreeMap::try_from(amounts).expect("impossible; qed;");
+	}: _(RawOrigin::Signed(user), pool_id, amounts, 0_u128.into(), false)
+
+	remove_liquidity_assets {
+		let owner: T::AccountId = whitelisted_caller();
+		let (pool_id, assets) = create_multi_asset_pool::<T>(owner.clone());
+		let unit = 1_000_000_000_000;
+		add_multi_asset_liquidity::<T>(&owner, pool_id, &assets, (100_000_000_u128 * unit).into());
+		let lp_amount = T::Assets::balance(get_lp_token::<T>(pool_id), &owner);
+		let min_amounts = assets
+			.iter()
+			.map(|asset_id| (*asset_id, 0_u128.into()))
+			.try_collect()
+			.expect("impossible; qed;");
+	}: _(RawOrigin::Signed(owner), pool_id, lp_amount, min_amounts)
+
+	remove_liquidity_single_asset {
+		let owner: T::AccountId = whitelisted_caller();
+		let (pool_id, assets) = create_multi_asset_pool::<T>(owner.clone());
+		let unit = 1_000_000_000_000;
+		add_multi_asset_liquidity::<T>(&owner, pool_id, &assets, (100_000_000_u128 * unit).into());
+		let lp_amount =
+			Permill::from_percent(10).mul_floor(T::Assets::balance(get_lp_token::<T>(pool_id), &owner));
+		let asset_id = *assets.first().expect("impossible; qed;");
+	}: _(RawOrigin::Signed(owner), pool_id, lp_amount, asset_id, 0_u128.into())
+
+	place_order {
+		let usdc: T::AssetId = 100.into();
+		let usdt: T::AssetId = 101.into();
+		let owner: T::AccountId = whitelisted_caller();
+		let pair = CurrencyPair::new(usdc, usdt);
+		let pool_id = create_stable_swap_pool::<T>(owner.clone(), pair);
+		let unit = 1_000_000_000_000;
+		assert_ok!(T::Assets::mint_into(usdt, &owner, (1001_u128 * unit).into()));
+		let trigger =
+			OrderTrigger { kind: OrderKind::Limit, source: PriceSource::Spot, price: Rate::saturating_from_integer(1_u128) };
+	}: _(RawOrigin::Signed(owner), pool_id, pair, (1000_u128 * unit).into(), 0_u128.into(), trigger, unit.into(), 100_u32.into())
+
+	cancel_order {
+		let owner: T::AccountId = whitelisted_caller();
+		let order_id = create_order::<T>(&owner);
+	}: _(RawOrigin::Signed(owner), order_id)
+
+	fill_order {
+		let owner: T::AccountId = whitelisted_caller();
+		let order_id = create_order::<T>(&owner);
+		let keeper: T::AccountId = account("keeper", 0, 0);
+	}: _(RawOrigin::Signed(keeper), order_id)
 }
 
 impl_benchmark_test_suite!(Pablo, crate::mock::new_test_ext(), crate::mock::Test);
@@ -3,8 +3,10 @@ use crate::{
 	mock,
 	mock::{Pablo, *},
 	Config,
-	PoolConfiguration::{ConstantProduct, LiquidityBootstrapping, StableSwap},
-	PoolInitConfiguration,
+	PoolConfiguration::{
+		ConcentratedLiquidity, ConstantProduct, LiquidityBootstrapping, MultiAsset, StableSwap,
+	},
+	PoolInitConfiguration, PoolInitConfigurationOf,
 };
 use frame_support::{
 	assert_noop, assert_ok,
@@ -17,7 +19,7 @@ use sp_runtime::TokenError;
 /// `expected_lp_check` takes base_amount, quote_amount and lp_tokens in order and returns
 /// true if lp_tokens are expected for given base_amount, quote_amount.
 pub fn common_add_remove_lp(
-	init_config: PoolInitConfiguration<AccountId, AssetId, BlockNumber>,
+	init_config: PoolInitConfigurationOf<Test>,
 	init_base_amount: Balance,
 	init_quote_amount: Balance,
 	next_base_amount: Balance,
@@ -33,6 +35,8 @@ pub fn common_add_remove_lp(
 		PoolInitConfiguration::StableSwap { pair, .. } => pair,
 		PoolInitConfiguration::ConstantProduct { pair, .. } => pair,
 		PoolInitConfiguration::LiquidityBootstrapping(pool) => pool.pair,
+		PoolInitConfiguration::ConcentratedLiquidity { pair, .. } => pair,
+		PoolInitConfiguration::MultiAsset { .. } => panic!("Not implemented"),
 	};
 	// Mint the tokens
 	assert_ok!(Tokens::mint_into(pair.base, &ALICE, init_base_amount));
@@ -61,7 +65,8 @@ pub fn common_add_remove_lp(
 	let lp_token = match pool {
 		StableSwap(pool) => pool.lp_token,
 		ConstantProduct(pool) => pool.lp_token,
-		LiquidityBootstrapping(_) => panic!("Not implemented"),
+		MultiAsset(pool) => pool.lp_token,
+		LiquidityBootstrapping(_) | ConcentratedLiquidity(_) => panic!("Not implemented"),
 	};
 	// Mint the tokens
 	assert_ok!(Tokens::mint_into(pair.base, &BOB, next_base_amount));
@@ -99,7 +104,7 @@ pub fn common_add_remove_lp(
 /// `pool_base_amount` and `pool_quote_amount` parameters and returns amount of expected new
 /// lp_tokens.
 pub fn common_add_lp_with_min_mint_amount(
-	init_config: PoolInitConfiguration<AccountId, AssetId, BlockNumber>,
+	init_config: PoolInitConfigurationOf<Test>,
 	init_base_amount: Balance,
 	init_quote_amount: Balance,
 	base_amount: Balance,
@@ -111,6 +116,8 @@ pub fn common_add_lp_with_min_mint_amount(
 		PoolInitConfiguration::StableSwap { pair, .. } => pair,
 		PoolInitConfiguration::ConstantProduct { pair, .. } => pair,
 		PoolInitConfiguration::LiquidityBootstrapping(pool) => pool.pair,
+		PoolInitConfiguration::ConcentratedLiquidity { pair, .. } => pair,
+		PoolInitConfiguration::MultiAsset { .. } => panic!("Not implemented"),
 	};
 	// Mint the tokens
 	assert_ok!(Tokens::mint_into(pair.base, &ALICE, init_base_amount));
@@ -130,7 +137,8 @@ pub fn common_add_lp_with_min_mint_amount(
 	let lp_token = match pool {
 		StableSwap(pool) => pool.lp_token,
 		ConstantProduct(pool) => pool.lp_token,
-		LiquidityBootstrapping(_) => panic!("Not implemented"),
+		MultiAsset(pool) => pool.lp_token,
+		LiquidityBootstrapping(_) | ConcentratedLiquidity(_) => panic!("Not implemented"),
 	};
 	// Mint the tokens
 	assert_ok!(Tokens::mint_into(pair.base, &BOB, base_amount));
@@ -170,7 +178,7 @@ pub fn common_add_lp_with_min_mint_amount(
 }
 
 pub fn common_remove_lp_failure(
-	init_config: PoolInitConfiguration<AccountId, AssetId, BlockNumber>,
+	init_config: PoolInitConfigurationOf<Test>,
 	init_base_amount: Balance,
 	init_quote_amount: Balance,
 	base_amount: Balance,
@@ -181,6 +189,8 @@ pub fn common_remove_lp_failure(
 		PoolInitConfiguration::StableSwap { pair, .. } => pair,
 		PoolInitConfiguration::ConstantProduct { pair, .. } => pair,
 		PoolInitConfiguration::LiquidityBootstrapping(pool) => pool.pair,
+		PoolInitConfiguration::ConcentratedLiquidity { pair, .. } => pair,
+		PoolInitConfiguration::MultiAsset { .. } => panic!("Not implemented"),
 	};
 	// Mint the tokens
 	assert_ok!(Tokens::mint_into(pair.base, &ALICE, init_base_amount));
@@ -200,7 +210,8 @@ pub fn common_remove_lp_failure(
 	let lp_token = match pool {
 		StableSwap(pool) => pool.lp_token,
 		ConstantProduct(pool) => pool.lp_token,
-		LiquidityBootstrapping(_) => panic!("Not implemented"),
+		MultiAsset(pool) => pool.lp_token,
+		LiquidityBootstrapping(_) | ConcentratedLiquidity(_) => panic!("Not implemented"),
 	};
 	// Mint the tokens
 	assert_ok!(Tokens::mint_into(pair.base, &BOB, base_amount));
@@ -239,7 +250,7 @@ pub fn common_remove_lp_failure(
 }
 
 pub fn common_exchange_failure(
-	init_config: PoolInitConfiguration<AccountId, AssetId, BlockNumber>,
+	init_config: PoolInitConfigurationOf<Test>,
 	init_base_amount: Balance,
 	init_quote_amount: Balance,
 	exchange_base_amount: Balance,
@@ -249,6 +260,8 @@ pub fn common_exchange_failure(
 		PoolInitConfiguration::StableSwap { pair, .. } => pair,
 		PoolInitConfiguration::ConstantProduct { pair, .. } => pair,
 		PoolInitConfiguration::LiquidityBootstrapping(pool) => pool.pair,
+		PoolInitConfiguration::ConcentratedLiquidity { pair, .. } => pair,
+		PoolInitConfiguration::MultiAsset { .. } => panic!("Not implemented"),
 	};
 	// Mint the tokens
 	assert_ok!(Tokens::mint_into(pair.base, &ALICE, init_base_amount));
@@ -0,0 +1,584 @@
+use crate::{
+	types::{ConcentratedLiquidityState, Position, TickInfo},
+	ConcentratedLiquidityStates, Config, Error, Pallet, PoolConfiguration, PoolCount, Pools,
+	Positions, TickBitmap, Ticks,
+};
+use composable_maths::dex::concentrated_liquidity::{
+	amounts_for_liquidity, compute_swap_step, liquidity_for_amounts, sqrt_price_at_tick,
+	tick_at_sqrt_price, MAX_TICK, MIN_TICK,
+};
+use composable_support::math::safe::{safe_multiply_by_rational, SafeAdd, SafeSub};
+use composable_traits::{
+	currency::{CurrencyFactory, RangeId},
+	defi::{CurrencyPair, Rate},
+	dex::{ConcentratedLiquidityPoolInfo, Fee, FeeConfig},
+	fnft::FinancialNft,
+};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{
+		fungibles::{Inspect, Transfer},
+		tokens::nonfungibles::{Create, Inspect as NftInspect, Mutate as NftMutate},
+	},
+};
+use sp_runtime::{
+	traits::{CheckedDiv, CheckedMul, Convert, One, Zero},
+	ArithmeticError, FixedPointNumber, FixedU128, Permill,
+};
+use sp_std::vec::Vec;
+
+/// Scale of the fee growth accumulators.
+const FEE_GROWTH_ONE: u128 = FixedU128::DIV;
+
+/// Number of ticks, once divided by the tick spacing, flagged by a word of the tick bitmap.
+const TICKS_PER_WORD: i32 = 128;
+
+pub(crate) struct ConcentratedLiquidity<T>(PhantomData<T>);
+
+/// Base and quote amounts of fees collected by a position.
+type CollectedFees<T> = (<T as Config>::Balance, <T as Config>::Balance);
+
+impl<T: Config> ConcentratedLiquidity<T> {
+	pub(crate) fn do_create_pool(
+		who: &T::AccountId,
+		pair: CurrencyPair<T::AssetId>,
+		fee_config: FeeConfig,
+		tick_spacing: u32,
+		initial_tick: i32,
+	) -> Result<T::PoolId, DispatchError> {
+		ensure!(pair.base != pair.quote, Error::<T>::InvalidPair);
+		ensure!(fee_config.fee_rate < Permill::one(), Error::<T>::InvalidFees);
+		ensure!(
+			tick_spacing > 0 && tick_spacing <= MAX_TICK as u32,
+			Error::<T>::InvalidTickSpacing
+		);
+		let sqrt_price = sqrt_price_at_tick(initial_tick).map_err(|_| Error::<T>::InvalidTick)?;
+
+		let position_collection =
+			T::CurrencyFactory::create(RangeId::LP_TOKENS, T::Balance::default())?;
+
+		let pool_id =
+			PoolCount::<T>::try_mutate(|pool_count| -> Result<T::PoolId, DispatchError> {
+				let pool_id = *pool_count;
+				Pools::<T>::insert(
+					pool_id,
+					PoolConfiguration::ConcentratedLiquidity(ConcentratedLiquidityPoolInfo {
+						owner: who.clone(),
+						pair,
+						position_collection,
+						fee_config,
+						tick_spacing,
+					}),
+				);
+				*pool_count = pool_id.safe_add(&T::PoolId::one())?;
+				Ok(pool_id)
+			})?;
+
+		let pool_account = Pallet::<T>::account_id(&pool_id);
+		T::FinancialNft::create_collection(&position_collection, &pool_account, &pool_account)?;
+		ConcentratedLiquidityStates::<T>::insert(
+			pool_id,
+			ConcentratedLiquidityState { sqrt_price, tick: initial_tick, ..Default::default() },
+		);
+
+		Ok(pool_id)
+	}
+
+	/// Amount of the other asset of the pair required to buy `amount` of `asset_id`, fees
+	/// excluded.
+	pub(crate) fn get_exchange_value(
+		pool: &ConcentratedLiquidityPoolInfo<T::AccountId, T::AssetId>,
+		pool_id: &T::PoolId,
+		asset_id: T::AssetId,
+		amount: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		ensure!(pool.pair.contains(asset_id), Error::<T>::InvalidAsset);
+		let (amount_in, _) = Self::swap(
+			pool,
+			pool_id,
+			asset_id == pool.pair.quote,
+			T::Convert::convert(amount),
+			false,
+			0,
+			false,
+		)?;
+		Ok(T::Convert::convert(amount_in))
+	}
+
+	/// Swap `quote_amount` of `pair.quote` for `pair.base`, the fees being charged on the way in.
+	///
+	/// Returns the amount of `pair.base` received along the charged fees, of which the owner and
+	/// protocol fees are still to be disbursed.
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) fn exchange(
+		who: &T::AccountId,
+		pool: &ConcentratedLiquidityPoolInfo<T::AccountId, T::AssetId>,
+		pool_id: &T::PoolId,
+		pool_account: &T::AccountId,
+		pair: CurrencyPair<T::AssetId>,
+		quote_amount: T::Balance,
+		min_receive: T::Balance,
+		keep_alive: bool,
+	) -> Result<(T::Balance, Fee<T::AssetId, T::Balance>), DispatchError> {
+		ensure!(pair == pool.pair || pair == pool.pair.swap(), Error::<T>::PairMismatch);
+		let fees = pool.fee_config.calculate_fees(pair.quote, quote_amount);
+		let amount_in = quote_amount.safe_sub(&fees.fee)?;
+		let (_, base_amount) = Self::swap(
+			pool,
+			pool_id,
+			pair.quote == pool.pair.base,
+			T::Convert::convert(amount_in),
+			true,
+			T::Convert::convert(fees.lp_fee),
+			true,
+		)?;
+		let base_amount = T::Convert::convert(base_amount);
+		ensure!(base_amount > T::Balance::zero(), Error::<T>::InvalidAmount);
+		ensure!(base_amount >= min_receive, Error::<T>::CannotRespectMinimumRequested);
+
+		// the liquidity provider fees stay in the pool until collected by the positions
+		T::Assets::transfer(
+			pair.quote,
+			who,
+			pool_account,
+			amount_in.safe_add(&fees.lp_fee)?,
+			keep_alive,
+		)?;
+		// no need to keep alive the pool account
+		T::Assets::transfer(pair.base, pool_account, who, base_amount, false)?;
+		Ok((base_amount, fees))
+	}
+
+	/// Price of the pool, as the amount of `pair.base` per `pair.quote` if not `swapped`.
+	pub(crate) fn exchange_rate(pool_id: &T::PoolId, swapped: bool) -> Result<Rate, DispatchError> {
+		let state =
+			ConcentratedLiquidityStates::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		let price = state
+			.sqrt_price
+			.checked_mul(&state.sqrt_price)
+			.ok_or(ArithmeticError::Overflow)?;
+		if swapped {
+			Ok(price)
+		} else {
+			Ok(Rate::one().checked_div(&price).ok_or(ArithmeticError::DivisionByZero)?)
+		}
+	}
+
+	/// Provide liquidity over `[lower_tick, upper_tick]`, using at most the given amounts.
+	///
+	/// Returns the id of the financial NFT minted to `who`, along the liquidity and the deposited
+	/// amounts.
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) fn open_position(
+		who: &T::AccountId,
+		pool: &ConcentratedLiquidityPoolInfo<T::AccountId, T::AssetId>,
+		pool_id: &T::PoolId,
+		pool_account: &T::AccountId,
+		lower_tick: i32,
+		upper_tick: i32,
+		base_amount: T::Balance,
+		quote_amount: T::Balance,
+		min_base_amount: T::Balance,
+		min_quote_amount: T::Balance,
+	) -> Result<(T::FinancialNftInstanceId, u128, T::Balance, T::Balance), DispatchError> {
+		let spacing = pool.tick_spacing as i32;
+		ensure!(
+			MIN_TICK <= lower_tick &&
+				lower_tick < upper_tick &&
+				upper_tick <= MAX_TICK &&
+				lower_tick % spacing == 0 &&
+				upper_tick % spacing == 0,
+			Error::<T>::InvalidTickRange
+		);
+		let mut state =
+			ConcentratedLiquidityStates::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		let sqrt_price_lower = sqrt_price_at_tick(lower_tick)?;
+		let sqrt_price_upper = sqrt_price_at_tick(upper_tick)?;
+		let liquidity = liquidity_for_amounts(
+			state.sqrt_price,
+			sqrt_price_lower,
+			sqrt_price_upper,
+			T::Convert::convert(base_amount),
+			T::Convert::convert(quote_amount),
+		)?;
+		ensure!(liquidity > 0, Error::<T>::InvalidAmount);
+		let (base_amount, quote_amount) = amounts_for_liquidity(
+			state.sqrt_price,
+			sqrt_price_lower,
+			sqrt_price_upper,
+			liquidity,
+			true,
+		)?;
+		let (base_amount, quote_amount) =
+			(T::Convert::convert(base_amount), T::Convert::convert(quote_amount));
+		ensure!(
+			base_amount >= min_base_amount && quote_amount >= min_quote_amount,
+			Error::<T>::CannotRespectMinimumRequested
+		);
+
+		let liquidity_delta = i128::try_from(liquidity).map_err(|_| ArithmeticError::Overflow)?;
+		Self::update_tick(pool_id, &state, spacing, lower_tick, liquidity_delta, false)?;
+		Self::update_tick(pool_id, &state, spacing, upper_tick, liquidity_delta, true)?;
+		if lower_tick <= state.tick && state.tick < upper_tick {
+			state.liquidity = state.liquidity.safe_add(&liquidity)?;
+			ConcentratedLiquidityStates::<T>::insert(pool_id, &state);
+		}
+		let (fee_growth_inside_base_last, fee_growth_inside_quote_last) =
+			Self::fee_growth_inside(pool_id, &state, lower_tick, upper_tick);
+
+		let position_id = T::FinancialNft::get_next_nft_id(&pool.position_collection)?;
+		T::FinancialNft::mint_into(&pool.position_collection, &position_id, who)?;
+		Positions::<T>::insert(
+			pool_id,
+			position_id,
+			Position {
+				lower_tick,
+				upper_tick,
+				liquidity,
+				fee_growth_inside_base_last,
+				fee_growth_inside_quote_last,
+			},
+		);
+
+		T::Assets::transfer(pool.pair.base, who, pool_account, base_amount, true)?;
+		T::Assets::transfer(pool.pair.quote, who, pool_account, quote_amount, true)?;
+		Ok((position_id, liquidity, base_amount, quote_amount))
+	}
+
+	/// Withdraw the liquidity of a position along its uncollected fees, burning its financial NFT.
+	///
+	/// Returns the withdrawn liquidity amounts and the collected fees.
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) fn close_position(
+		who: &T::AccountId,
+		pool: &ConcentratedLiquidityPoolInfo<T::AccountId, T::AssetId>,
+		pool_id: &T::PoolId,
+		pool_account: &T::AccountId,
+		position_id: T::FinancialNftInstanceId,
+		min_base_amount: T::Balance,
+		min_quote_amount: T::Balance,
+	) -> Result<(T::Balance, T::Balance, CollectedFees<T>), DispatchError> {
+		let fees = Self::collect_fees(who, pool, pool_id, pool_account, position_id)?;
+		let position =
+			Positions::<T>::take(pool_id, position_id).ok_or(Error::<T>::PositionNotFound)?;
+		let mut state =
+			ConcentratedLiquidityStates::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+		let liquidity_delta =
+			i128::try_from(position.liquidity).map_err(|_| ArithmeticError::Overflow)?;
+		let spacing = pool.tick_spacing as i32;
+		Self::update_tick(pool_id, &state, spacing, position.lower_tick, -liquidity_delta, false)?;
+		Self::update_tick(pool_id, &state, spacing, position.upper_tick, -liquidity_delta, true)?;
+		if position.lower_tick <= state.tick && state.tick < position.upper_tick {
+			state.liquidity = state.liquidity.safe_sub(&position.liquidity)?;
+			ConcentratedLiquidityStates::<T>::insert(pool_id, &state);
+		}
+
+		let (base_amount, quote_amount) = amounts_for_liquidity(
+			state.sqrt_price,
+			sqrt_price_at_tick(position.lower_tick)?,
+			sqrt_price_at_tick(position.upper_tick)?,
+			position.liquidity,
+			false,
+		)?;
+		let (base_amount, quote_amount) =
+			(T::Convert::convert(base_amount), T::Convert::convert(quote_amount));
+		ensure!(
+			base_amount >= min_base_amount && quote_amount >= min_quote_amount,
+			Error::<T>::CannotRespectMinimumRequested
+		);
+
+		T::FinancialNft::burn(&pool.position_collection, &position_id, Some(who))?;
+		T::Assets::transfer(pool.pair.base, pool_account, who, base_amount, false)?;
+		T::Assets::transfer(pool.pair.quote, pool_account, who, quote_amount, false)?;
+		Ok((base_amount, quote_amount, fees))
+	}
+
+	/// Transfer to `who` the fees earned by a position since last collected.
+	pub(crate) fn collect_fees(
+		who: &T::AccountId,
+		pool: &ConcentratedLiquidityPoolInfo<T::AccountId, T::AssetId>,
+		pool_id: &T::PoolId,
+		pool_account: &T::AccountId,
+		position_id: T::FinancialNftInstanceId,
+	) -> Result<CollectedFees<T>, DispatchError> {
+		ensure!(
+			T::FinancialNft::owner(&pool.position_collection, &position_id).as_ref() == Some(who),
+			Error::<T>::NotPositionOwner
+		);
+		// the buyer pays for the fees accrued at the time of listing
+		ensure!(
+			!T::FinancialNft::is_listed(&pool.position_collection, &position_id),
+			Error::<T>::PositionIsListed
+		);
+		let state =
+			ConcentratedLiquidityStates::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		Positions::<T>::try_mutate(pool_id, position_id, |position| -> Result<_, DispatchError> {
+			let position = position.as_mut().ok_or(Error::<T>::PositionNotFound)?;
+			let (fee_growth_inside_base, fee_growth_inside_quote) =
+				Self::fee_growth_inside(pool_id, &state, position.lower_tick, position.upper_tick);
+			let base_fees = T::Convert::convert(safe_multiply_by_rational(
+				fee_growth_inside_base.wrapping_sub(position.fee_growth_inside_base_last),
+				position.liquidity,
+				FEE_GROWTH_ONE,
+			)?);
+			let quote_fees = T::Convert::convert(safe_multiply_by_rational(
+				fee_growth_inside_quote.wrapping_sub(position.fee_growth_inside_quote_last),
+				position.liquidity,
+				FEE_GROWTH_ONE,
+			)?);
+			position.fee_growth_inside_base_last = fee_growth_inside_base;
+			position.fee_growth_inside_quote_last = fee_growth_inside_quote;
+
+			if !base_fees.is_zero() {
+				T::Assets::transfer(pool.pair.base, pool_account, who, base_fees, false)?;
+			}
+			if !quote_fees.is_zero() {
+				T::Assets::transfer(pool.pair.quote, pool_account, who, quote_fees, false)?;
+			}
+			Ok((base_fees, quote_fees))
+		})
+	}
+
+	/// Swap along the initialized ticks, starting from the current price of the pool.
+	///
+	/// Sells base asset if `base_for_quote`, quote asset otherwise. `amount` is the input amount
+	/// if `exact_input`, the output amount otherwise. The `lp_fee`, paid in the input asset, is
+	/// credited to the liquidity the swap goes through. The state of the pool is only updated if
+	/// `commit`.
+	///
+	/// Each step of the swap either reaches the next initialized tick, the end of a word of the
+	/// tick bitmap, or exhausts the amount. The swap fails if it takes more than
+	/// `T::MaxSwapSteps`.
+	///
+	/// Returns the input and output amounts.
+	fn swap(
+		pool: &ConcentratedLiquidityPoolInfo<T::AccountId, T::AssetId>,
+		pool_id: &T::PoolId,
+		base_for_quote: bool,
+		amount: u128,
+		exact_input: bool,
+		lp_fee: u128,
+		commit: bool,
+	) -> Result<(u128, u128), DispatchError> {
+		let mut state =
+			ConcentratedLiquidityStates::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		let spacing = pool.tick_spacing as i32;
+		let mut crossed_ticks = Vec::new();
+		let (mut amount_remaining, mut amount_in, mut amount_out) = (amount, 0_u128, 0_u128);
+		let mut steps = 0_u32;
+
+		while amount_remaining > 0 {
+			ensure!(
+				steps < T::MaxSwapSteps::get(),
+				// going through empty words only, no liquidity is within reach
+				if state.liquidity.is_zero() {
+					Error::<T>::NotEnoughLiquidity
+				} else {
+					Error::<T>::TooManySwapSteps
+				}
+			);
+			steps = steps.safe_add(&1)?;
+
+			let (next_tick, initialized) = Self::next_initialized_tick_within_one_word(
+				pool_id,
+				state.tick,
+				spacing,
+				base_for_quote,
+			);
+			let next_tick = next_tick.clamp(MIN_TICK, MAX_TICK);
+			let sqrt_price_target = sqrt_price_at_tick(next_tick)?;
+			let step = compute_swap_step(
+				state.sqrt_price,
+				sqrt_price_target,
+				state.liquidity,
+				amount_remaining,
+				exact_input,
+			)?;
+			amount_remaining = amount_remaining.safe_sub(if exact_input {
+				&step.amount_in
+			} else {
+				&step.amount_out
+			})?;
+			amount_in = amount_in.safe_add(&step.amount_in)?;
+			amount_out = amount_out.safe_add(&step.amount_out)?;
+
+			if !state.liquidity.is_zero() && !lp_fee.is_zero() {
+				let step_fee = safe_multiply_by_rational(lp_fee, step.amount_in, amount)?;
+				let fee_growth =
+					safe_multiply_by_rational(step_fee, FEE_GROWTH_ONE, state.liquidity)?;
+				if base_for_quote {
+					state.fee_growth_global_base =
+						state.fee_growth_global_base.wrapping_add(fee_growth);
+				} else {
+					state.fee_growth_global_quote =
+						state.fee_growth_global_quote.wrapping_add(fee_growth);
+				}
+			}
+
+			state.sqrt_price = step.sqrt_price;
+			if step.sqrt_price == sqrt_price_target {
+				if initialized {
+					let mut info = Ticks::<T>::get(pool_id, next_tick).unwrap_or_default();
+					info.fee_growth_outside_base =
+						state.fee_growth_global_base.wrapping_sub(info.fee_growth_outside_base);
+					info.fee_growth_outside_quote =
+						state.fee_growth_global_quote.wrapping_sub(info.fee_growth_outside_quote);
+					// crossing downward leaves the ranges starting at the tick
+					let liquidity_delta =
+						if base_for_quote { -info.liquidity_net } else { info.liquidity_net };
+					state.liquidity = add_liquidity_delta(state.liquidity, liquidity_delta)?;
+					crossed_ticks.push((next_tick, info));
+				} else if (next_tick == MIN_TICK || next_tick == MAX_TICK) && amount_remaining > 0 {
+					return Err(Error::<T>::NotEnoughLiquidity.into())
+				}
+				state.tick = if base_for_quote { next_tick - 1 } else { next_tick };
+			} else {
+				state.tick = tick_at_sqrt_price(state.sqrt_price)?;
+			}
+		}
+
+		if commit {
+			for (tick, info) in crossed_ticks {
+				Ticks::<T>::insert(pool_id, tick, info);
+			}
+			ConcentratedLiquidityStates::<T>::insert(pool_id, state);
+		}
+		Ok((amount_in, amount_out))
+	}
+
+	/// Add `liquidity_delta` to the liquidity referenced by `tick`, initializing or dropping the
+	/// tick as needed.
+	fn update_tick(
+		pool_id: &T::PoolId,
+		state: &ConcentratedLiquidityState,
+		spacing: i32,
+		tick: i32,
+		liquidity_delta: i128,
+		upper: bool,
+	) -> Result<(), DispatchError> {
+		let mut info = Ticks::<T>::get(pool_id, tick).unwrap_or_default();
+		let was_initialized = !info.liquidity_gross.is_zero();
+		if !was_initialized && tick <= state.tick {
+			// by convention, all the fees were earned below an initialized tick
+			info.fee_growth_outside_base = state.fee_growth_global_base;
+			info.fee_growth_outside_quote = state.fee_growth_global_quote;
+		}
+		info.liquidity_gross = add_liquidity_delta(info.liquidity_gross, liquidity_delta)?;
+		info.liquidity_net = if upper {
+			info.liquidity_net.checked_sub(liquidity_delta)
+		} else {
+			info.liquidity_net.checked_add(liquidity_delta)
+		}
+		.ok_or(ArithmeticError::Overflow)?;
+
+		if info.liquidity_gross.is_zero() {
+			Ticks::<T>::remove(pool_id, tick);
+		} else {
+			Ticks::<T>::insert(pool_id, tick, info);
+		}
+		if was_initialized == info.liquidity_gross.is_zero() {
+			Self::flip_tick(pool_id, spacing, tick);
+		}
+		Ok(())
+	}
+
+	/// Flip the bit of `tick` in the tick bitmap, dropping the words left empty.
+	fn flip_tick(pool_id: &T::PoolId, spacing: i32, tick: i32) {
+		let (word, bit) = bitmap_position(tick.div_euclid(spacing));
+		TickBitmap::<T>::mutate_exists(pool_id, word, |bits| {
+			let flipped = bits.unwrap_or_default() ^ (1 << bit);
+			*bits = if flipped.is_zero() { None } else { Some(flipped) };
+		});
+	}
+
+	/// Next initialized tick at or below `tick` if `lte`, above `tick` otherwise, looking only in
+	/// the word of the tick bitmap containing the first tick to look at.
+	///
+	/// Returns the tick along whether it is initialized, the last tick of the word in the search
+	/// direction being returned if none is.
+	fn next_initialized_tick_within_one_word(
+		pool_id: &T::PoolId,
+		tick: i32,
+		spacing: i32,
+		lte: bool,
+	) -> (i32, bool) {
+		let compressed = tick.div_euclid(spacing);
+		let (next, initialized) = if lte {
+			let (word, bit) = bitmap_position(compressed);
+			// the bits at or below the current one
+			let masked = TickBitmap::<T>::get(pool_id, word).unwrap_or_default() &
+				(u128::MAX >> (127 - bit));
+			if masked.is_zero() {
+				(compressed - bit as i32, false)
+			} else {
+				let most_significant_bit = 127 - masked.leading_zeros();
+				(compressed - (bit - most_significant_bit) as i32, true)
+			}
+		} else {
+			let (word, bit) = bitmap_position(compressed + 1);
+			// the bits at or above the next one
+			let masked =
+				TickBitmap::<T>::get(pool_id, word).unwrap_or_default() & (u128::MAX << bit);
+			if masked.is_zero() {
+				(compressed + 1 + (127 - bit) as i32, false)
+			} else {
+				let least_significant_bit = masked.trailing_zeros();
+				(compressed + 1 + (least_significant_bit - bit) as i32, true)
+			}
+		};
+		(next.saturating_mul(spacing), initialized)
+	}
+
+	/// Base and quote fee growth within `[lower_tick, upper_tick]`.
+	fn fee_growth_inside(
+		pool_id: &T::PoolId,
+		state: &ConcentratedLiquidityState,
+		lower_tick: i32,
+		upper_tick: i32,
+	) -> (u128, u128) {
+		let lower = Ticks::<T>::get(pool_id, lower_tick).unwrap_or_default();
+		let upper = Ticks::<T>::get(pool_id, upper_tick).unwrap_or_default();
+		let inside = |global: u128, lower_outside: u128, upper_outside: u128| {
+			let below = if state.tick >= lower_tick {
+				lower_outside
+			} else {
+				global.wrapping_sub(lower_outside)
+			};
+			let above = if state.tick < upper_tick {
+				upper_outside
+			} else {
+				global.wrapping_sub(upper_outside)
+			};
+			global.wrapping_sub(below).wrapping_sub(above)
+		};
+		(
+			inside(
+				state.fee_growth_global_base,
+				lower.fee_growth_outside_base,
+				upper.fee_growth_outside_base,
+			),
+			inside(
+				state.fee_growth_global_quote,
+				lower.fee_growth_outside_quote,
+				upper.fee_growth_outside_quote,
+			),
+		)
+	}
+}
+
+/// Word of the tick bitmap, and bit within the word, flagging a tick divided by the tick spacing.
+fn bitmap_position(compressed_tick: i32) -> (i32, u32) {
+	(
+		compressed_tick.div_euclid(TICKS_PER_WORD),
+		compressed_tick.rem_euclid(TICKS_PER_WORD).unsigned_abs(),
+	)
+}
+
+fn add_liquidity_delta(liquidity: u128, delta: i128) -> Result<u128, ArithmeticError> {
+	if delta < 0 {
+		liquidity.checked_sub(delta.unsigned_abs()).ok_or(ArithmeticError::Underflow)
+	} else {
+		liquidity.checked_add(delta.unsigned_abs()).ok_or(ArithmeticError::Overflow)
+	}
+}
@@ -0,0 +1,292 @@
+#[cfg(test)]
+use crate::{
+	mock::{Pablo, *},
+	Error, PoolInitConfiguration,
+};
+use composable_tests_helpers::test::helper::{
+	acceptable_computation_error, default_acceptable_computation_error,
+};
+use composable_traits::{
+	defi::CurrencyPair,
+	dex::{Amm, FeeConfig},
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungibles::{Inspect, Mutate},
+		tokens::nonfungibles::Inspect as NftInspect,
+	},
+};
+use sp_runtime::Permill;
+
+const UNIT: Balance = 1_000_000_000_000;
+const FEE: Permill = Permill::from_percent(1);
+
+fn create_pool() -> PoolId {
+	System::set_block_number(1);
+	Pablo::do_create_pool(PoolInitConfiguration::ConcentratedLiquidity {
+		owner: ALICE,
+		pair: CurrencyPair::new(BTC, USDT),
+		fee: FEE,
+		tick_spacing: 10,
+		initial_tick: 0,
+	})
+	.expect("pool creation failed")
+}
+
+fn open_position(
+	who: AccountId,
+	pool_id: PoolId,
+	lower_tick: i32,
+	upper_tick: i32,
+	amount: Balance,
+) -> FinancialNftInstanceId {
+	assert_ok!(Tokens::mint_into(BTC, &who, amount));
+	assert_ok!(Tokens::mint_into(USDT, &who, amount));
+	assert_ok!(Pablo::open_position(
+		Origin::signed(who),
+		pool_id,
+		lower_tick,
+		upper_tick,
+		amount,
+		amount,
+		0,
+		0
+	));
+	match System::events().last().map(|e| &e.event) {
+		Some(Event::Pablo(crate::Event::PositionOpened { position_id, .. })) => *position_id,
+		_ => panic!("expected PositionOpened event"),
+	}
+}
+
+#[test]
+fn create_pool_with_invalid_ticks() {
+	new_test_ext().execute_with(|| {
+		let create = |tick_spacing, initial_tick| {
+			Pablo::do_create_pool(PoolInitConfiguration::ConcentratedLiquidity {
+				owner: ALICE,
+				pair: CurrencyPair::new(BTC, USDT),
+				fee: FEE,
+				tick_spacing,
+				initial_tick,
+			})
+		};
+		assert_noop!(create(0, 0), Error::<Test>::InvalidTickSpacing);
+		assert_noop!(create(10, 1_000_000), Error::<Test>::InvalidTick);
+	});
+}
+
+#[test]
+fn open_position_mints_financial_nft() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let position_id = open_position(BOB, pool_id, -1000, 1000, 1_000 * UNIT);
+
+		let collection = match Pablo::pools(pool_id).expect("pool not found") {
+			crate::PoolConfiguration::ConcentratedLiquidity(pool) => pool.position_collection,
+			_ => panic!("expected concentrated liquidity pool"),
+		};
+		assert_eq!(FinancialNft::owner(&collection, &position_id), Some(BOB));
+		let position = Pablo::position(pool_id, position_id).expect("position not found");
+		assert_eq!((position.lower_tick, position.upper_tick), (-1000, 1000));
+		let state = Pablo::concentrated_liquidity_state(pool_id).expect("state not found");
+		assert_eq!(state.liquidity, position.liquidity);
+		// symmetric range around a price of 1, both assets are deposited in equal amounts
+		assert!(Tokens::balance(BTC, &BOB) < UNIT / 1_000_000);
+		assert!(Tokens::balance(USDT, &BOB) < UNIT / 1_000_000);
+	});
+}
+
+#[test]
+fn open_position_out_of_range_takes_a_single_asset() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let position_id = open_position(BOB, pool_id, 1000, 2000, 1_000 * UNIT);
+		assert_eq!(Tokens::balance(USDT, &BOB), 1_000 * UNIT);
+		assert!(Tokens::balance(BTC, &BOB) < 1_000 * UNIT);
+		let state = Pablo::concentrated_liquidity_state(pool_id).expect("state not found");
+		assert_eq!(state.liquidity, 0);
+		assert!(Pablo::position(pool_id, position_id).is_some());
+	});
+}
+
+#[test]
+fn open_position_with_invalid_range() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		assert_ok!(Tokens::mint_into(BTC, &BOB, UNIT));
+		assert_ok!(Tokens::mint_into(USDT, &BOB, UNIT));
+		for (lower_tick, upper_tick) in [(100, -100), (100, 100), (-105, 100), (-1_000_000, 0)] {
+			assert_noop!(
+				Pablo::open_position(
+					Origin::signed(BOB),
+					pool_id,
+					lower_tick,
+					upper_tick,
+					UNIT,
+					UNIT,
+					0,
+					0
+				),
+				Error::<Test>::InvalidTickRange
+			);
+		}
+	});
+}
+
+#[test]
+fn swap_accrues_fees_to_position() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let position_id = open_position(BOB, pool_id, -1000, 1000, 1_000 * UNIT);
+
+		let amount = 10 * UNIT;
+		assert_ok!(Tokens::mint_into(USDT, &CHARLIE, amount));
+		assert_ok!(<Pablo as Amm>::sell(&CHARLIE, pool_id, USDT, amount, 0, false));
+		let received = Tokens::balance(BTC, &CHARLIE);
+		assert!(received > 0 && received < amount);
+
+		let fees = FeeConfig::default_from(FEE).calculate_fees(USDT, amount);
+		let btc_balance = Tokens::balance(BTC, &BOB);
+		let usdt_balance = Tokens::balance(USDT, &BOB);
+		assert_ok!(Pablo::collect_fees(Origin::signed(BOB), pool_id, position_id));
+		assert_eq!(Tokens::balance(BTC, &BOB), btc_balance);
+		let collected = Tokens::balance(USDT, &BOB) - usdt_balance;
+		assert!(collected <= fees.lp_fee);
+		assert_ok!(default_acceptable_computation_error(collected, fees.lp_fee));
+		assert_eq!(Tokens::balance(USDT, &ALICE), fees.owner_fee);
+
+		// fees are only collected once
+		assert_ok!(Pablo::collect_fees(Origin::signed(BOB), pool_id, position_id));
+		assert_eq!(Tokens::balance(BTC, &BOB), btc_balance);
+		assert_eq!(Tokens::balance(USDT, &BOB), usdt_balance + collected);
+	});
+}
+
+#[test]
+fn only_owner_can_manage_position() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let position_id = open_position(BOB, pool_id, -1000, 1000, 1_000 * UNIT);
+		assert_noop!(
+			Pablo::collect_fees(Origin::signed(CHARLIE), pool_id, position_id),
+			Error::<Test>::NotPositionOwner
+		);
+		assert_noop!(
+			Pablo::close_position(Origin::signed(CHARLIE), pool_id, position_id, 0, 0),
+			Error::<Test>::NotPositionOwner
+		);
+	});
+}
+
+#[test]
+fn listed_position_can_not_be_collected_or_closed() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let position_id = open_position(BOB, pool_id, -1000, 1000, 1_000 * UNIT);
+		let collection = match Pablo::pools(pool_id).expect("pool not found") {
+			crate::PoolConfiguration::ConcentratedLiquidity(pool) => pool.position_collection,
+			_ => panic!("expected concentrated liquidity pool"),
+		};
+		assert_ok!(FinancialNft::list(
+			Origin::signed(BOB),
+			collection,
+			position_id,
+			pallet_fnft::Sale::FixedPrice { asset_id: USDT, price: 2_000 * UNIT }
+		));
+
+		assert_noop!(
+			Pablo::collect_fees(Origin::signed(BOB), pool_id, position_id),
+			Error::<Test>::PositionIsListed
+		);
+		assert_noop!(
+			Pablo::close_position(Origin::signed(BOB), pool_id, position_id, 0, 0),
+			Error::<Test>::PositionIsListed
+		);
+	});
+}
+
+#[test]
+fn close_position_returns_liquidity() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let position_id = open_position(BOB, pool_id, -1000, 1000, 1_000 * UNIT);
+		assert_ok!(Pablo::close_position(Origin::signed(BOB), pool_id, position_id, 0, 0));
+
+		assert_ok!(acceptable_computation_error(
+			Tokens::balance(BTC, &BOB),
+			1_000 * UNIT,
+			1_000_000_000,
+			1
+		));
+		assert_ok!(acceptable_computation_error(
+			Tokens::balance(USDT, &BOB),
+			1_000 * UNIT,
+			1_000_000_000,
+			1
+		));
+		assert!(Pablo::position(pool_id, position_id).is_none());
+		assert!(Pablo::tick(pool_id, -1000).is_none());
+		assert!(Pablo::tick(pool_id, 1000).is_none());
+		assert!(Pablo::tick_bitmap(pool_id, -1).is_none());
+		assert!(Pablo::tick_bitmap(pool_id, 0).is_none());
+		let state = Pablo::concentrated_liquidity_state(pool_id).expect("state not found");
+		assert_eq!(state.liquidity, 0);
+		assert_noop!(
+			Pablo::close_position(Origin::signed(BOB), pool_id, position_id, 0, 0),
+			Error::<Test>::NotPositionOwner
+		);
+	});
+}
+
+#[test]
+fn swap_crosses_initialized_ticks() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let narrow = open_position(BOB, pool_id, -100, 100, 10 * UNIT);
+		let wide = open_position(CHARLIE, pool_id, -2000, 2000, 1_000 * UNIT);
+		let wide_liquidity = Pablo::position(pool_id, wide).expect("position not found").liquidity;
+
+		let amount = 100 * UNIT;
+		assert_ok!(Tokens::mint_into(USDT, &ALICE, amount));
+		assert_ok!(<Pablo as Amm>::sell(&ALICE, pool_id, USDT, amount, 0, false));
+		let state = Pablo::concentrated_liquidity_state(pool_id).expect("state not found");
+		assert!(state.tick >= 100);
+		assert_eq!(state.liquidity, wide_liquidity);
+
+		// above its range, the narrow position only holds quote asset
+		let btc_before = Tokens::balance(BTC, &BOB);
+		assert_ok!(Pablo::close_position(Origin::signed(BOB), pool_id, narrow, 0, 0));
+		assert_eq!(Tokens::balance(BTC, &BOB), btc_before);
+		assert!(Tokens::balance(USDT, &BOB) > 10 * UNIT);
+	});
+}
+
+#[test]
+fn swap_without_liquidity_fails() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		assert_ok!(Tokens::mint_into(USDT, &CHARLIE, UNIT));
+		assert_noop!(
+			<Pablo as Amm>::sell(&CHARLIE, pool_id, USDT, UNIT, 0, false),
+			Error::<Test>::NotEnoughLiquidity
+		);
+	});
+}
+
+#[test]
+fn swap_through_too_many_words_of_the_tick_bitmap_fails() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		open_position(BOB, pool_id, -887_270, 887_270, UNIT);
+
+		// without initialized tick on the way, each word of the tick bitmap is a step
+		let amount = 1_000 * UNIT;
+		assert_ok!(Tokens::mint_into(USDT, &ALICE, amount));
+		assert_noop!(
+			<Pablo as Amm>::sell(&ALICE, pool_id, USDT, amount, 0, false),
+			Error::<Test>::TooManySwapSteps
+		);
+		assert_ok!(<Pablo as Amm>::sell(&ALICE, pool_id, USDT, UNIT, 0, false));
+	});
+}
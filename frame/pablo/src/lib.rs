@@ -40,11 +40,15 @@ mod benchmarking;
 #[cfg(test)]
 mod common_test_functions;
 #[cfg(test)]
+mod concentrated_liquidity_tests;
+#[cfg(test)]
 mod liquidity_bootstrapping_tests;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
-mod mock_fnft;
+mod multi_asset_tests;
+#[cfg(test)]
+mod orders_tests;
 #[cfg(test)]
 mod stable_swap_tests;
 #[cfg(test)]
@@ -52,7 +56,10 @@ mod uniswap_tests;
 
 pub mod weights;
 
+mod concentrated_liquidity;
 mod liquidity_bootstrapping;
+mod multi_asset;
+mod orders;
 mod stable_swap;
 mod twap;
 mod types;
@@ -63,22 +70,33 @@ pub use crate::weights::WeightInfo;
 #[frame_support::pallet]
 pub mod pallet {
 	use crate::{
+		concentrated_liquidity::ConcentratedLiquidity,
+		multi_asset::MultiAsset,
+		orders::OrderBook,
 		stable_swap::StableSwap,
 		twap::{update_price_cumulative_state, update_twap_state},
-		types::{PriceCumulative, TimeWeightedAveragePrice},
+		types::{
+			ConcentratedLiquidityState, Order, OrderTrigger, Position, PriceCumulative, TickInfo,
+			TimeWeightedAveragePrice,
+		},
 		uniswap::Uniswap,
 		WeightInfo,
 	};
 	use codec::FullCodec;
-	use composable_support::math::safe::{safe_multiply_by_rational, SafeArithmetic, SafeSub};
+	use composable_support::{
+		abstractions::block_fold::{BlockFold, FoldStorage, FoldStrategy},
+		math::safe::{safe_multiply_by_rational, SafeArithmetic, SafeSub},
+	};
 	use composable_traits::{
 		currency::{CurrencyFactory, LocalAssets},
 		defi::{CurrencyPair, Rate},
 		dex::{
-			Amm, ConstantProductPoolInfo, Fee, LiquidityBootstrappingPoolInfo, PriceAggregate,
-			RedeemableAssets, RemoveLiquiditySimulationResult, RewardPoolType, StableSwapPoolInfo,
-			StakingRewardPool, MAX_REWARDS,
+			Amm, ConcentratedLiquidityPoolInfo, ConstantProductPoolInfo, Fee,
+			LiquidityBootstrappingPoolInfo, MultiAssetAmm, MultiAssetPoolInfo, MultiAssetPoolKind,
+			PoolTwap, PriceAggregate, RedeemableAssets, RemoveLiquiditySimulationResult,
+			RewardPoolType, StableSwapPoolInfo, StakingRewardPool, MAX_REWARDS,
 		},
+		fnft::FinancialNft,
 		staking::{
 			lock::LockConfig, ManageStaking, ProtocolStaking, RewardConfig,
 			RewardPoolConfiguration, RewardRate,
@@ -90,6 +108,7 @@ pub mod pallet {
 		pallet_prelude::*,
 		traits::{
 			fungibles::{Inspect, Mutate, Transfer},
+			tokens::nonfungibles,
 			Time, TryCollect,
 		},
 		transactional, BoundedBTreeMap, PalletId, RuntimeDebug,
@@ -112,7 +131,7 @@ pub mod pallet {
 	use sp_std::{collections::btree_map::BTreeMap, vec, vec::Vec};
 
 	#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, Clone, PartialEq, Eq, TypeInfo)]
-	pub enum PoolInitConfiguration<AccountId, AssetId, BlockNumber> {
+	pub enum PoolInitConfiguration<AccountId, AssetId, BlockNumber, Weights> {
 		StableSwap {
 			owner: AccountId,
 			pair: CurrencyPair<AssetId>,
@@ -128,13 +147,33 @@ pub mod pallet {
 			base_weight: Permill,
 		},
 		LiquidityBootstrapping(LiquidityBootstrappingPoolInfo<AccountId, AssetId, BlockNumber>),
+		ConcentratedLiquidity {
+			owner: AccountId,
+			pair: CurrencyPair<AssetId>,
+			// trading fee
+			fee: Permill,
+			// positions must be bounded by ticks multiple of the spacing
+			tick_spacing: u32,
+			// tick of the initial price, in quote per base
+			initial_tick: i32,
+		},
+		MultiAsset {
+			owner: AccountId,
+			// assets along their weight, the weights summing to one
+			assets: Weights,
+			kind: MultiAssetPoolKind,
+			// trading fee
+			fee: Permill,
+		},
 	}
 
 	#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, Clone, PartialEq, Eq, TypeInfo)]
-	pub enum PoolConfiguration<AccountId, AssetId, BlockNumber> {
+	pub enum PoolConfiguration<AccountId, AssetId, BlockNumber, Weights> {
 		StableSwap(StableSwapPoolInfo<AccountId, AssetId>),
 		ConstantProduct(ConstantProductPoolInfo<AccountId, AssetId>),
 		LiquidityBootstrapping(LiquidityBootstrappingPoolInfo<AccountId, AssetId, BlockNumber>),
+		ConcentratedLiquidity(ConcentratedLiquidityPoolInfo<AccountId, AssetId>),
+		MultiAsset(MultiAssetPoolInfo<AccountId, AssetId, Weights>),
 	}
 
 	pub(crate) type AssetIdOf<T> = <T as Config>::AssetId;
@@ -145,16 +184,30 @@ pub mod pallet {
 		<T as Config>::AssetId,
 		<T as frame_system::Config>::BlockNumber,
 	>;
+	pub(crate) type AssetWeightsOf<T> =
+		BoundedBTreeMap<<T as Config>::AssetId, Permill, <T as Config>::MaxAssetsPerPool>;
+	pub(crate) type MultiAssetPoolInfoOf<T> = MultiAssetPoolInfo<
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::AssetId,
+		AssetWeightsOf<T>,
+	>;
+	pub(crate) type AssetAmountsOf<T> = BoundedBTreeMap<
+		<T as Config>::AssetId,
+		<T as Config>::Balance,
+		<T as Config>::MaxAssetsPerPool,
+	>;
 	type PoolIdOf<T> = <T as Config>::PoolId;
 	type PoolConfigurationOf<T> = PoolConfiguration<
 		<T as frame_system::Config>::AccountId,
 		<T as Config>::AssetId,
 		<T as frame_system::Config>::BlockNumber,
+		AssetWeightsOf<T>,
 	>;
 	pub(crate) type PoolInitConfigurationOf<T> = PoolInitConfiguration<
 		<T as frame_system::Config>::AccountId,
 		<T as Config>::AssetId,
 		<T as frame_system::Config>::BlockNumber,
+		AssetWeightsOf<T>,
 	>;
 
 	type StakingRewardPoolsOf<T> = BoundedVec<
@@ -171,6 +224,14 @@ pub mod pallet {
 	pub(crate) type PriceCumulativeStateOf<T> =
 		PriceCumulative<MomentOf<T>, <T as Config>::Balance>;
 
+	pub(crate) type OrderOf<T> = Order<
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::PoolId,
+		<T as Config>::AssetId,
+		<T as Config>::Balance,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
 	type DurationPresets<T> =
 		BoundedBTreeMap<u64, Perbill, <T as Config>::MaxStakingDurationPresets>;
 
@@ -186,6 +247,16 @@ pub mod pallet {
 			// Pool assets
 			assets: CurrencyPair<AssetIdOf<T>>,
 		},
+		/// Pool of any number of assets with specified id `T::PoolId` was created successfully by
+		/// `T::AccountId`.
+		MultiAssetPoolCreated {
+			/// Id of newly created pool.
+			pool_id: T::PoolId,
+			/// Owner of the pool.
+			owner: T::AccountId,
+			/// Pool assets.
+			assets: Vec<AssetIdOf<T>>,
+		},
 		/// The sale ended, the funds repatriated and the pool deleted.
 		PoolDeleted {
 			/// Pool that was removed.
@@ -222,6 +293,29 @@ pub mod pallet {
 			/// Updated lp token supply.
 			total_issuance: T::Balance,
 		},
+		/// Liquidity added into the multi-asset pool `T::PoolId`, possibly unbalanced.
+		MultiAssetLiquidityAdded {
+			/// Account id who added liquidity.
+			who: T::AccountId,
+			/// Pool id to which liquidity added.
+			pool_id: T::PoolId,
+			/// Amounts of assets deposited.
+			amounts: BTreeMap<T::AssetId, T::Balance>,
+			/// Amount of minted lp.
+			minted_lp: T::Balance,
+		},
+		/// Liquidity removed from the multi-asset pool `T::PoolId` by `T::AccountId`, either in
+		/// balanced way or as a single asset.
+		MultiAssetLiquidityRemoved {
+			/// Account id who removed liquidity.
+			who: T::AccountId,
+			/// Pool id from which liquidity removed.
+			pool_id: T::PoolId,
+			/// Amounts of assets removed from pool.
+			amounts: BTreeMap<T::AssetId, T::Balance>,
+			/// Updated lp token supply.
+			total_issuance: T::Balance,
+		},
 		/// Token exchange happened.
 		Swapped {
 			/// Pool id on which exchange done.
@@ -239,6 +333,91 @@ pub mod pallet {
 			/// Charged fees.
 			fee: Fee<T::AssetId, T::Balance>,
 		},
+		/// Concentrated liquidity position opened, represented by the financial NFT `position_id`.
+		PositionOpened {
+			/// Owner of the position.
+			who: T::AccountId,
+			/// Pool id in which the position is opened.
+			pool_id: T::PoolId,
+			/// Id of the position within the collection of the pool.
+			position_id: T::FinancialNftInstanceId,
+			/// Lower bound of the price range.
+			lower_tick: i32,
+			/// Upper bound of the price range.
+			upper_tick: i32,
+			/// Liquidity provided over the range.
+			liquidity: u128,
+			/// Amount of base asset deposited.
+			base_amount: T::Balance,
+			/// Amount of quote asset deposited.
+			quote_amount: T::Balance,
+		},
+		/// Concentrated liquidity position closed, its financial NFT burnt.
+		PositionClosed {
+			/// Owner of the position.
+			who: T::AccountId,
+			/// Pool id in which the position was opened.
+			pool_id: T::PoolId,
+			/// Id of the position within the collection of the pool.
+			position_id: T::FinancialNftInstanceId,
+			/// Amount of base asset withdrawn, fees excluded.
+			base_amount: T::Balance,
+			/// Amount of quote asset withdrawn, fees excluded.
+			quote_amount: T::Balance,
+		},
+		/// Fees earned by a concentrated liquidity position collected.
+		PositionFeesCollected {
+			/// Owner of the position.
+			who: T::AccountId,
+			/// Pool id in which the position is opened.
+			pool_id: T::PoolId,
+			/// Id of the position within the collection of the pool.
+			position_id: T::FinancialNftInstanceId,
+			/// Amount of base asset collected.
+			base_amount: T::Balance,
+			/// Amount of quote asset collected.
+			quote_amount: T::Balance,
+		},
+		/// Order placed, its funds being escrowed until filled, cancelled or expired.
+		OrderPlaced {
+			/// Id of the order.
+			order_id: T::OrderId,
+			/// Pool id on which the order is filled.
+			pool_id: T::PoolId,
+			/// Owner of the order.
+			owner: T::AccountId,
+		},
+		/// Order cancelled by its owner, the escrowed funds returned.
+		OrderCancelled {
+			/// Id of the order.
+			order_id: T::OrderId,
+			/// Pool id on which the order was to be filled.
+			pool_id: T::PoolId,
+			/// Owner of the order.
+			owner: T::AccountId,
+		},
+		/// Order expired before being filled, the escrowed funds returned.
+		OrderExpired {
+			/// Id of the order.
+			order_id: T::OrderId,
+			/// Pool id on which the order was to be filled.
+			pool_id: T::PoolId,
+			/// Owner of the order.
+			owner: T::AccountId,
+		},
+		/// Order filled once its trigger price was crossed.
+		OrderFilled {
+			/// Id of the order.
+			order_id: T::OrderId,
+			/// Pool id on which the order was filled.
+			pool_id: T::PoolId,
+			/// Owner of the order.
+			owner: T::AccountId,
+			/// Amount of quote asset sold.
+			quote_amount: T::Balance,
+			/// Amount of base asset received by the owner.
+			base_amount: T::Balance,
+		},
 		/// TWAP updated.
 		TwapUpdated {
 			/// Pool id on which exchange done.
@@ -272,6 +451,23 @@ pub mod pallet {
 		WeightsMustBeNonZero,
 		WeightsMustSumToOne,
 		StakingPoolConfigError,
+		NoLpTokenForConcentratedLiquidity,
+		NotConcentratedLiquidityPool,
+		InvalidTickSpacing,
+		InvalidTick,
+		InvalidTickRange,
+		PositionNotFound,
+		NotPositionOwner,
+		PositionIsListed,
+		NotEnoughAssets,
+		StableSwapWeightsMustBeEqual,
+		NotMultiAssetPool,
+		OrderNotFound,
+		NotOrderOwner,
+		OrderNotTriggered,
+		InvalidOrderExpiry,
+		TwapNotEnabled,
+		TooManySwapSteps,
 	}
 
 	#[pallet::config]
@@ -392,6 +588,54 @@ pub mod pallet {
 			RewardPoolId = Self::RewardPoolId,
 		>;
 
+		/// Type representing the unique ID of a concentrated liquidity position.
+		type FinancialNftInstanceId: FullCodec
+			+ MaxEncodedLen
+			+ Default
+			+ Debug
+			+ TypeInfo
+			+ Eq
+			+ PartialEq
+			+ Ord
+			+ Copy;
+
+		/// Financial NFTs representing the concentrated liquidity positions, one collection being
+		/// created per pool.
+		type FinancialNft: nonfungibles::Mutate<AccountIdOf<Self>>
+			+ nonfungibles::Create<AccountIdOf<Self>>
+			+ FinancialNft<
+				AccountIdOf<Self>,
+				CollectionId = AssetIdOf<Self>,
+				ItemId = Self::FinancialNftInstanceId,
+			>;
+
+		/// Maximum number of assets held by a multi-asset pool.
+		#[pallet::constant]
+		type MaxAssetsPerPool: Get<u32>;
+
+		/// Type representing the unique ID of a limit or stop order.
+		type OrderId: FullCodec
+			+ MaxEncodedLen
+			+ Default
+			+ Debug
+			+ TypeInfo
+			+ Eq
+			+ PartialEq
+			+ Ord
+			+ Copy
+			+ Zero
+			+ One
+			+ SafeArithmetic;
+
+		/// Maximum number of resting orders checked for their trigger in a block.
+		#[pallet::constant]
+		type MaxOrdersPerBlock: Get<u32>;
+
+		/// Maximum number of steps of a swap through a concentrated liquidity pool, each crossing
+		/// an initialized tick or a word of the tick bitmap.
+		#[pallet::constant]
+		type MaxSwapSteps: Get<u32>;
+
 		type WeightInfo: WeightInfo;
 
 		#[pallet::constant]
@@ -433,6 +677,57 @@ pub mod pallet {
 	pub type StakingRewardPools<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::PoolId, StakingRewardPoolsOf<T>, OptionQuery>;
 
+	/// Price and in range liquidity of the concentrated liquidity pools.
+	#[pallet::storage]
+	#[pallet::getter(fn concentrated_liquidity_state)]
+	pub type ConcentratedLiquidityStates<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, ConcentratedLiquidityState, OptionQuery>;
+
+	/// Ticks of the concentrated liquidity pools bounding at least one position.
+	#[pallet::storage]
+	#[pallet::getter(fn tick)]
+	pub type Ticks<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::PoolId, Twox64Concat, i32, TickInfo, OptionQuery>;
+
+	/// Bitmap of the keys of `Ticks` divided by the tick spacing, by words of 128 ticks, to find
+	/// the next tick crossed by a swap. Empty words are dropped.
+	#[pallet::storage]
+	#[pallet::getter(fn tick_bitmap)]
+	pub type TickBitmap<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::PoolId, Twox64Concat, i32, u128, OptionQuery>;
+
+	/// Concentrated liquidity positions, by pool and financial NFT id.
+	#[pallet::storage]
+	#[pallet::getter(fn position)]
+	pub type Positions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		Blake2_128Concat,
+		T::FinancialNftInstanceId,
+		Position,
+		OptionQuery,
+	>;
+
+	#[pallet::type_value]
+	pub fn OrderCountOnEmpty<T: Config>() -> T::OrderId {
+		Zero::zero()
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn order_count)]
+	#[allow(clippy::disallowed_types)]
+	pub type OrderCount<T: Config> = StorageValue<_, T::OrderId, ValueQuery, OrderCountOnEmpty<T>>;
+
+	/// Resting limit and stop orders.
+	#[pallet::storage]
+	#[pallet::getter(fn order)]
+	pub type Orders<T: Config> = StorageMap<_, Blake2_128Concat, T::OrderId, OrderOf<T>>;
+
+	/// Progress of the check of the resting orders, continued from a block to the next.
+	#[pallet::storage]
+	pub type OrdersFold<T: Config> = StorageValue<_, BlockFold<u32, T::OrderId>, OptionQuery>;
+
 	pub(crate) enum PriceRatio {
 		Swapped,
 		NotSwapped,
@@ -453,7 +748,7 @@ pub mod pallet {
 		/// Execute a buy order on pool.
 		///
 		/// Emits `Swapped` event when successful.
-		#[pallet::weight(T::WeightInfo::buy())]
+		#[pallet::weight(Self::swap_weight(T::WeightInfo::buy()))]
 		pub fn buy(
 			origin: OriginFor<T>,
 			pool_id: T::PoolId,
@@ -461,16 +756,16 @@ pub mod pallet {
 			amount: T::Balance,
 			min_receive: T::Balance,
 			keep_alive: bool,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			let _ = <Self as Amm>::buy(&who, pool_id, asset_id, amount, min_receive, keep_alive)?;
-			Ok(())
+			Ok(Self::swap_actual_weight(pool_id, T::WeightInfo::buy()).into())
 		}
 
 		/// Execute a sell order on pool.
 		///
 		/// Emits `Swapped` event when successful.
-		#[pallet::weight(T::WeightInfo::sell())]
+		#[pallet::weight(Self::swap_weight(T::WeightInfo::sell()))]
 		pub fn sell(
 			origin: OriginFor<T>,
 			pool_id: T::PoolId,
@@ -478,10 +773,10 @@ pub mod pallet {
 			amount: T::Balance,
 			min_receive: T::Balance,
 			keep_alive: bool,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			let _ = <Self as Amm>::sell(&who, pool_id, asset_id, amount, min_receive, keep_alive)?;
-			Ok(())
+			Ok(Self::swap_actual_weight(pool_id, T::WeightInfo::sell()).into())
 		}
 
 		/// Execute a specific swap operation.
@@ -489,7 +784,7 @@ pub mod pallet {
 		/// The `quote_amount` is always the quote asset amount (A/B => B), (B/A => A).
 		///
 		/// Emits `Swapped` event when successful.
-		#[pallet::weight(T::WeightInfo::swap())]
+		#[pallet::weight(Self::swap_weight(T::WeightInfo::swap()))]
 		pub fn swap(
 			origin: OriginFor<T>,
 			pool_id: T::PoolId,
@@ -497,7 +792,7 @@ pub mod pallet {
 			quote_amount: T::Balance,
 			min_receive: T::Balance,
 			keep_alive: bool,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			let _ = <Self as Amm>::exchange(
 				&who,
@@ -507,7 +802,7 @@ pub mod pallet {
 				min_receive,
 				keep_alive,
 			)?;
-			Ok(())
+			Ok(Self::swap_actual_weight(pool_id, T::WeightInfo::swap()).into())
 		}
 
 		/// Add liquidity to the given pool.
@@ -591,6 +886,268 @@ pub mod pallet {
 			);
 			Ok(())
 		}
+
+		/// Provide liquidity to a concentrated liquidity pool over the price range
+		/// `[lower_tick, upper_tick]`, using at most `base_amount` and `quote_amount`.
+		///
+		/// The position is represented by a financial NFT minted to the caller.
+		///
+		/// Emits `PositionOpened` event when successful.
+		#[pallet::weight(T::WeightInfo::open_position())]
+		#[transactional]
+		#[allow(clippy::too_many_arguments)]
+		pub fn open_position(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			lower_tick: i32,
+			upper_tick: i32,
+			base_amount: T::Balance,
+			quote_amount: T::Balance,
+			min_base_amount: T::Balance,
+			min_quote_amount: T::Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let pool = Self::get_concentrated_liquidity_pool(pool_id)?;
+			let pool_account = Self::account_id(&pool_id);
+			let (position_id, liquidity, base_amount, quote_amount) =
+				ConcentratedLiquidity::<T>::open_position(
+					&who,
+					&pool,
+					&pool_id,
+					&pool_account,
+					lower_tick,
+					upper_tick,
+					base_amount,
+					quote_amount,
+					min_base_amount,
+					min_quote_amount,
+				)?;
+			Self::update_twap(pool_id)?;
+			Self::deposit_event(Event::<T>::PositionOpened {
+				who,
+				pool_id,
+				position_id,
+				lower_tick,
+				upper_tick,
+				liquidity,
+				base_amount,
+				quote_amount,
+			});
+			Ok(())
+		}
+
+		/// Withdraw the liquidity and the uncollected fees of a concentrated liquidity position
+		/// owned by the caller, burning its financial NFT.
+		///
+		/// Emits `PositionFeesCollected` and `PositionClosed` events when successful.
+		#[pallet::weight(T::WeightInfo::close_position())]
+		#[transactional]
+		pub fn close_position(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			position_id: T::FinancialNftInstanceId,
+			min_base_amount: T::Balance,
+			min_quote_amount: T::Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let pool = Self::get_concentrated_liquidity_pool(pool_id)?;
+			let pool_account = Self::account_id(&pool_id);
+			let (base_amount, quote_amount, (base_fees, quote_fees)) =
+				ConcentratedLiquidity::<T>::close_position(
+					&who,
+					&pool,
+					&pool_id,
+					&pool_account,
+					position_id,
+					min_base_amount,
+					min_quote_amount,
+				)?;
+			Self::update_twap(pool_id)?;
+			Self::deposit_event(Event::<T>::PositionFeesCollected {
+				who: who.clone(),
+				pool_id,
+				position_id,
+				base_amount: base_fees,
+				quote_amount: quote_fees,
+			});
+			Self::deposit_event(Event::<T>::PositionClosed {
+				who,
+				pool_id,
+				position_id,
+				base_amount,
+				quote_amount,
+			});
+			Ok(())
+		}
+
+		/// Collect the fees earned by a concentrated liquidity position owned by the caller.
+		///
+		/// Emits `PositionFeesCollected` event when successful.
+		#[pallet::weight(T::WeightInfo::collect_fees())]
+		#[transactional]
+		pub fn collect_fees(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			position_id: T::FinancialNftInstanceId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let pool = Self::get_concentrated_liquidity_pool(pool_id)?;
+			let pool_account = Self::account_id(&pool_id);
+			let (base_amount, quote_amount) = ConcentratedLiquidity::<T>::collect_fees(
+				&who,
+				&pool,
+				&pool_id,
+				&pool_account,
+				position_id,
+			)?;
+			Self::deposit_event(Event::<T>::PositionFeesCollected {
+				who,
+				pool_id,
+				position_id,
+				base_amount,
+				quote_amount,
+			});
+			Ok(())
+		}
+
+		/// Add liquidity to a multi-asset pool, depositing any subset of its assets.
+		///
+		/// Deposits which are not proportional to the pool balances are charged fees.
+		///
+		/// Emits `MultiAssetLiquidityAdded` event when successful.
+		#[pallet::weight(T::WeightInfo::add_liquidity_assets())]
+		pub fn add_liquidity_assets(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			amounts: AssetAmountsOf<T>,
+			min_mint_amount: T::Balance,
+			keep_alive: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<Self as MultiAssetAmm>::add_liquidity_assets(
+				&who,
+				pool_id,
+				amounts.into_inner(),
+				min_mint_amount,
+				keep_alive,
+			)?;
+			Ok(())
+		}
+
+		/// Remove liquidity from a multi-asset pool, withdrawing all its assets proportionally to
+		/// its balances.
+		///
+		/// Emits `MultiAssetLiquidityRemoved` event when successful.
+		#[pallet::weight(T::WeightInfo::remove_liquidity_assets())]
+		pub fn remove_liquidity_assets(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			lp_amount: T::Balance,
+			min_amounts: AssetAmountsOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<Self as MultiAssetAmm>::remove_liquidity_assets(
+				&who,
+				pool_id,
+				lp_amount,
+				min_amounts.into_inner(),
+			)?;
+			Ok(())
+		}
+
+		/// Remove liquidity from a multi-asset pool as a single asset, which is charged fees.
+		///
+		/// Emits `MultiAssetLiquidityRemoved` event when successful.
+		#[pallet::weight(T::WeightInfo::remove_liquidity_single_asset())]
+		pub fn remove_liquidity_single_asset(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			lp_amount: T::Balance,
+			asset_id: T::AssetId,
+			min_amount: T::Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<Self as MultiAssetAmm>::remove_liquidity_single_asset(
+				&who, pool_id, lp_amount, asset_id, min_amount,
+			)?;
+			Ok(())
+		}
+
+		/// Place an order selling `amount` of `pair.quote` for at least `min_receive` of
+		/// `pair.base` once the price of the pool crosses the trigger price, up to the block
+		/// `expires_at`.
+		///
+		/// The `amount` and `keeper_reward` are escrowed. Resting orders are checked at the
+		/// beginning of every block, any account may also fill a triggered order for the keeper
+		/// reward.
+		///
+		/// Emits `OrderPlaced` event when successful.
+		#[pallet::weight(T::WeightInfo::place_order())]
+		#[allow(clippy::too_many_arguments)]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			pair: CurrencyPair<T::AssetId>,
+			amount: T::Balance,
+			min_receive: T::Balance,
+			trigger: OrderTrigger,
+			keeper_reward: T::Balance,
+			expires_at: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let order_id = OrderBook::<T>::place(
+				&who,
+				pool_id,
+				pair,
+				amount,
+				min_receive,
+				trigger,
+				keeper_reward,
+				expires_at,
+			)?;
+			Self::deposit_event(Event::<T>::OrderPlaced { order_id, pool_id, owner: who });
+			Ok(())
+		}
+
+		/// Cancel a resting order, returning the escrowed funds, keeper reward included.
+		///
+		/// Emits `OrderCancelled` event when successful.
+		#[pallet::weight(T::WeightInfo::cancel_order())]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: T::OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.owner == who, Error::<T>::NotOrderOwner);
+			OrderBook::<T>::close(order_id, &order, &who)?;
+			Self::deposit_event(Event::<T>::OrderCancelled {
+				order_id,
+				pool_id: order.pool_id,
+				owner: who,
+			});
+			Ok(())
+		}
+
+		/// Fill a triggered order, or return the funds of an expired one to its owner, the
+		/// keeper reward being paid to the caller.
+		///
+		/// Emits `OrderFilled` or `OrderExpired` event when successful.
+		#[pallet::weight(T::WeightInfo::fill_order().saturating_add(
+			T::WeightInfo::swap_concentrated_liquidity(T::MaxSwapSteps::get())
+		))]
+		pub fn fill_order(origin: OriginFor<T>, order_id: T::OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			if OrderBook::<T>::is_expired(&order) {
+				OrderBook::<T>::close(order_id, &order, &who)?;
+				Self::deposit_event(Event::<T>::OrderExpired {
+					order_id,
+					pool_id: order.pool_id,
+					owner: order.owner,
+				});
+			} else {
+				let _ = OrderBook::<T>::fill(order_id, &order, &who)?;
+			}
+			Ok(())
+		}
 	}
 
 	#[pallet::hooks]
@@ -635,14 +1192,57 @@ pub mod pallet {
 					}
 				}
 			}
-			weight
+			// the fold state is the running count of processed orders, only the orders processed
+			// in this block are charged
+			let processed_before = match OrdersFold::<T>::get() {
+				Some(BlockFold::Init { state, .. }) |
+				Some(BlockFold::Cont { state, .. }) |
+				Some(BlockFold::Done { state }) => state,
+				None => 0,
+			};
+			let orders_fold = <(OrdersFold<T>, Orders<T>)>::step(
+				FoldStrategy::new_chunk(T::MaxOrdersPerBlock::get()),
+				0_u32,
+				|processed: u32, order_id, order: OrderOf<T>| {
+					OrderBook::<T>::process(order_id, &order);
+					processed.saturating_add(1)
+				},
+			);
+			let processed_orders = match orders_fold {
+				BlockFold::Init { state, .. } |
+				BlockFold::Cont { state, .. } |
+				BlockFold::Done { state } => state,
+			}
+			.saturating_sub(processed_before);
+			weight.saturating_add(
+				T::WeightInfo::fill_order()
+					.saturating_add(T::WeightInfo::swap_concentrated_liquidity(
+						T::MaxSwapSteps::get(),
+					))
+					.saturating_mul(processed_orders.into()),
+			)
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Weight of a swap, covering the maximum number of steps through a concentrated
+		/// liquidity pool.
+		pub(crate) fn swap_weight(weight: Weight) -> Weight {
+			weight.max(T::WeightInfo::swap_concentrated_liquidity(T::MaxSwapSteps::get()))
+		}
+
+		/// Actual weight of a swap through `pool_id`, the steps of the swap only being charged
+		/// for concentrated liquidity pools.
+		fn swap_actual_weight(pool_id: T::PoolId, weight: Weight) -> Option<Weight> {
+			match Pools::<T>::get(pool_id) {
+				Some(PoolConfiguration::ConcentratedLiquidity(_)) => None,
+				_ => Some(weight),
+			}
+		}
+
 		fn default_pblo_staking_pool_config(
 			pool_id: &T::PoolId,
-			pair: &CurrencyPair<T::AssetId>,
+			assets: &[T::AssetId],
 		) -> Result<
 			RewardPoolConfiguration<
 				T::AccountId,
@@ -655,20 +1255,20 @@ pub mod pallet {
 		> {
 			let max_rewards: T::Balance = T::Convert::convert(MAX_REWARDS);
 			let reward_rate = RewardRate::per_second(T::Convert::convert(0));
-			let reward_configs = [
-				(
-					pair.base,
-					RewardConfig {
-						asset_id: pair.base,
-						max_rewards,
-						reward_rate: reward_rate.clone(),
-					},
-				),
-				(pair.quote, RewardConfig { asset_id: pair.quote, max_rewards, reward_rate }),
-			]
-			.into_iter()
-			.try_collect()
-			.map_err(|_| Error::<T>::StakingPoolConfigError)?;
+			let reward_configs = assets
+				.iter()
+				.map(|asset_id| {
+					(
+						*asset_id,
+						RewardConfig {
+							asset_id: *asset_id,
+							max_rewards,
+							reward_rate: reward_rate.clone(),
+						},
+					)
+				})
+				.try_collect()
+				.map_err(|_| Error::<T>::StakingPoolConfigError)?;
 			let duration_presets =
 				[(ONE_WEEK, Perbill::from_percent(1)), (ONE_MONTH, Perbill::from_percent(10))]
 					.into_iter()
@@ -733,9 +1333,9 @@ pub mod pallet {
 		#[transactional]
 		fn create_staking_reward_pool(
 			pool_id: &T::PoolId,
-			pair: CurrencyPair<T::AssetId>,
+			assets: &[T::AssetId],
 		) -> DispatchResult {
-			let pblo_pool_config = Self::default_pblo_staking_pool_config(pool_id, &pair)?;
+			let pblo_pool_config = Self::default_pblo_staking_pool_config(pool_id, assets)?;
 			let lp_pool_config = Self::default_lp_staking_pool_config(pool_id)?;
 			let pblo_staking_pool_id = T::ManageStaking::create_staking_pool(pblo_pool_config)?;
 			let lp_staking_pool_id = T::ManageStaking::create_staking_pool(lp_pool_config)?;
@@ -753,6 +1353,25 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Pools without LP token only get a PBLO staking pool, receiving the protocol fees.
+		#[transactional]
+		fn create_pblo_staking_reward_pool(
+			pool_id: &T::PoolId,
+			assets: &[T::AssetId],
+		) -> DispatchResult {
+			let pblo_pool_config = Self::default_pblo_staking_pool_config(pool_id, assets)?;
+			let pblo_staking_pool_id = T::ManageStaking::create_staking_pool(pblo_pool_config)?;
+			let pblo_staking_pool = StakingRewardPool {
+				pool_id: pblo_staking_pool_id,
+				pool_type: RewardPoolType::PBLO,
+			};
+			let staking_reward_pools = BoundedVec::try_from(vec![pblo_staking_pool])
+				.map_err(|_| Error::<T>::StakingPoolConfigError)?;
+
+			StakingRewardPools::<T>::insert(pool_id, staking_reward_pools);
+			Ok(())
+		}
+
 		#[transactional]
 		pub fn do_create_pool(
 			init_config: PoolInitConfigurationOf<T>,
@@ -770,7 +1389,7 @@ pub mod pallet {
 						amplification_coefficient,
 						FeeConfig::default_from(fee),
 					)?;
-					Self::create_staking_reward_pool(&pool_id, pair)?;
+					Self::create_staking_reward_pool(&pool_id, &[pair.base, pair.quote])?;
 					(owner, pool_id, pair)
 				},
 				PoolInitConfiguration::ConstantProduct { owner, pair, fee, base_weight } => {
@@ -780,7 +1399,7 @@ pub mod pallet {
 						FeeConfig::default_from(fee),
 						base_weight,
 					)?;
-					Self::create_staking_reward_pool(&pool_id, pair)?;
+					Self::create_staking_reward_pool(&pool_id, &[pair.base, pair.quote])?;
 					(owner, pool_id, pair)
 				},
 				PoolInitConfiguration::LiquidityBootstrapping(pool_config) => {
@@ -792,6 +1411,40 @@ pub mod pallet {
 						pool_config.pair,
 					)
 				},
+				PoolInitConfiguration::ConcentratedLiquidity {
+					owner,
+					pair,
+					fee,
+					tick_spacing,
+					initial_tick,
+				} => {
+					let pool_id = ConcentratedLiquidity::<T>::do_create_pool(
+						&owner,
+						pair,
+						FeeConfig::default_from(fee),
+						tick_spacing,
+						initial_tick,
+					)?;
+					Self::create_pblo_staking_reward_pool(&pool_id, &[pair.base, pair.quote])?;
+					(owner, pool_id, pair)
+				},
+				PoolInitConfiguration::MultiAsset { owner, assets, kind, fee } => {
+					let asset_ids: Vec<_> = assets.keys().copied().collect();
+					let pool_id = MultiAsset::<T>::do_create_pool(
+						&owner,
+						assets,
+						kind,
+						FeeConfig::default_from(fee),
+					)?;
+					Self::create_staking_reward_pool(&pool_id, &asset_ids)?;
+					// pools of more than two assets have no currency pair
+					Self::deposit_event(Event::<T>::MultiAssetPoolCreated {
+						owner,
+						pool_id,
+						assets: asset_ids,
+					});
+					return Ok(pool_id)
+				},
 			};
 			Self::deposit_event(Event::<T>::PoolCreated { owner, pool_id, assets: pair });
 			Ok(pool_id)
@@ -803,6 +1456,24 @@ pub mod pallet {
 			Pools::<T>::get(pool_id).ok_or_else(|| Error::<T>::PoolNotFound.into())
 		}
 
+		pub(crate) fn get_concentrated_liquidity_pool(
+			pool_id: T::PoolId,
+		) -> Result<ConcentratedLiquidityPoolInfo<T::AccountId, T::AssetId>, DispatchError> {
+			match Self::get_pool(pool_id)? {
+				PoolConfiguration::ConcentratedLiquidity(info) => Ok(info),
+				_ => Err(Error::<T>::NotConcentratedLiquidityPool.into()),
+			}
+		}
+
+		pub(crate) fn get_multi_asset_pool(
+			pool_id: T::PoolId,
+		) -> Result<MultiAssetPoolInfoOf<T>, DispatchError> {
+			match Self::get_pool(pool_id)? {
+				PoolConfiguration::MultiAsset(info) => Ok(info),
+				_ => Err(Error::<T>::NotMultiAssetPool.into()),
+			}
+		}
+
 		pub(crate) fn account_id(pool_id: &T::PoolId) -> T::AccountId {
 			T::PalletId::get().into_sub_account_truncating(pool_id)
 		}
@@ -813,9 +1484,31 @@ pub mod pallet {
 		) -> Result<Rate, DispatchError> {
 			let pair = Self::currency_pair(pool_id)?;
 			let pool_account = Self::account_id(&pool_id);
-			let pair = match price_ratio {
-				PriceRatio::NotSwapped => pair,
-				PriceRatio::Swapped => pair.swap(),
+			// the spot price of weighted pools is `(base / base_weight) / (quote / quote_weight)`
+			let (base_weight, quote_weight) = match Self::get_pool(pool_id)? {
+				// the reserves include liquidity out of range and uncollected fees
+				PoolConfiguration::ConcentratedLiquidity(_) =>
+					return ConcentratedLiquidity::<T>::exchange_rate(
+						&pool_id,
+						matches!(price_ratio, PriceRatio::Swapped),
+					),
+				PoolConfiguration::ConstantProduct(info) => (info.base_weight, info.quote_weight),
+				PoolConfiguration::LiquidityBootstrapping(info) => info.sale.current_weights(
+					frame_system::Pallet::<T>::current_block_number().max(info.sale.start),
+				)?,
+				PoolConfiguration::MultiAsset(info)
+					if info.kind == MultiAssetPoolKind::ConstantProduct =>
+				{
+					let weight_of = |asset_id| {
+						info.assets.get(&asset_id).copied().ok_or(Error::<T>::InvalidAsset)
+					};
+					(weight_of(pair.base)?, weight_of(pair.quote)?)
+				},
+				_ => (Permill::one(), Permill::one()),
+			};
+			let (pair, base_weight, quote_weight) = match price_ratio {
+				PriceRatio::NotSwapped => (pair, base_weight, quote_weight),
+				PriceRatio::Swapped => (pair.swap(), quote_weight, base_weight),
 			};
 			let pool_base_asset_under_management =
 				T::Convert::convert(T::Assets::balance(pair.base, &pool_account));
@@ -831,15 +1524,19 @@ pub mod pallet {
 				Error::<T>::NotEnoughLiquidity
 			);
 
-			Ok(Rate::checked_from_rational(
+			let reserve_ratio = Rate::checked_from_rational(
 				pool_base_asset_under_management,
 				pool_quote_asset_under_management,
 			)
-			.ok_or(ArithmeticError::Overflow)?)
+			.ok_or(ArithmeticError::Overflow)?;
+			let weight_ratio =
+				Rate::checked_from_rational(quote_weight.deconstruct(), base_weight.deconstruct())
+					.ok_or(ArithmeticError::Overflow)?;
+			Ok(reserve_ratio.checked_mul(&weight_ratio).ok_or(ArithmeticError::Overflow)?)
 		}
 
 		fn update_twap(pool_id: T::PoolId) -> Result<(), DispatchError> {
-			let currency_pair = Self::currency_pair(pool_id)?; // update price cumulatives
+			// update price cumulatives
 			let (base_price_cumulative, quote_price_cumulative) =
 				PriceCumulativeState::<T>::try_mutate(
 					pool_id,
@@ -863,6 +1560,8 @@ pub mod pallet {
 					},
 				)?;
 				if let Some(updated_twap) = updated_twap {
+					// only pools with a currency pair can have TWAP enabled
+					let currency_pair = Self::currency_pair(pool_id)?;
 					Self::deposit_event(Event::<T>::TwapUpdated {
 						pool_id,
 						timestamp: updated_twap.timestamp,
@@ -923,6 +1622,8 @@ pub mod pallet {
 				PoolConfiguration::StableSwap(info) => Ok(info.pair),
 				PoolConfiguration::ConstantProduct(info) => Ok(info.pair),
 				PoolConfiguration::LiquidityBootstrapping(info) => Ok(info.pair),
+				PoolConfiguration::ConcentratedLiquidity(info) => Ok(info.pair),
+				PoolConfiguration::MultiAsset(info) => MultiAsset::<T>::currency_pair(&info),
 			}
 		}
 
@@ -933,6 +1634,9 @@ pub mod pallet {
 				PoolConfiguration::ConstantProduct(info) => Ok(info.lp_token),
 				PoolConfiguration::LiquidityBootstrapping(_) =>
 					Err(Error::<T>::NoLpTokenForLbp.into()),
+				PoolConfiguration::ConcentratedLiquidity(_) =>
+					Err(Error::<T>::NoLpTokenForConcentratedLiquidity.into()),
+				PoolConfiguration::MultiAsset(info) => Ok(info.lp_token),
 			}
 		}
 
@@ -943,6 +1647,17 @@ pub mod pallet {
 		) -> Result<Self::Balance, DispatchError> {
 			let pool = Self::get_pool(pool_id)?;
 			let pool_account = Self::account_id(&pool_id);
+			if let PoolConfiguration::MultiAsset(info) = &pool {
+				for (asset_id, amount) in &amounts {
+					ensure!(
+						T::Assets::reducible_balance(*asset_id, who, false) >= *amount,
+						Error::<T>::NotEnoughLiquidity
+					);
+				}
+				let (mint_amount, _) =
+					MultiAsset::<T>::calculate_mint_amount_and_fees(info, &pool_account, &amounts)?;
+				return Ok(mint_amount)
+			}
 			let currency_pair = Self::currency_pair(pool_id)?;
 			ensure!(amounts.len() < 3, Error::<T>::MoreThanTwoAssetsNotYetSupported);
 			let base_amount = *amounts.get(&currency_pair.base).ok_or(Error::<T>::MissingAmount)?;
@@ -967,14 +1682,6 @@ pub mod pallet {
 		) -> Result<RedeemableAssets<Self::AssetId, Self::Balance>, DispatchError> {
 			let pool = Self::get_pool(pool_id)?;
 			let pool_account = Self::account_id(&pool_id);
-			let currency_pair = Self::currency_pair(pool_id)?;
-			ensure!(min_expected_amounts.len() < 3, Error::<T>::MoreThanTwoAssetsNotYetSupported);
-			let min_base_amount = *min_expected_amounts
-				.get(&currency_pair.base)
-				.ok_or(Error::<T>::MissingMinExpectedAmount)?;
-			let min_quote_amount = *min_expected_amounts
-				.get(&currency_pair.quote)
-				.ok_or(Error::<T>::MissingMinExpectedAmount)?;
 			match pool {
 				PoolConfiguration::StableSwap(StableSwapPoolInfo { pair, lp_token, .. }) |
 				PoolConfiguration::ConstantProduct(ConstantProductPoolInfo {
@@ -982,6 +1689,16 @@ pub mod pallet {
 					lp_token,
 					..
 				}) => {
+					ensure!(
+						min_expected_amounts.len() < 3,
+						Error::<T>::MoreThanTwoAssetsNotYetSupported
+					);
+					let min_base_amount = *min_expected_amounts
+						.get(&pair.base)
+						.ok_or(Error::<T>::MissingMinExpectedAmount)?;
+					let min_quote_amount = *min_expected_amounts
+						.get(&pair.quote)
+						.ok_or(Error::<T>::MissingMinExpectedAmount)?;
 					let pool_base_aum =
 						T::Convert::convert(T::Assets::balance(pair.base, &pool_account));
 					let pool_quote_aum =
@@ -1010,16 +1727,32 @@ pub mod pallet {
 					})
 				},
 
-				// for LBP just return 0 for both balances as it does not have LP Token
+				// for LBP and concentrated liquidity just return 0 for both balances as they do not
+				// have LP Token
 				PoolConfiguration::LiquidityBootstrapping(LiquidityBootstrappingPoolInfo {
 					pair,
 					..
+				}) |
+				PoolConfiguration::ConcentratedLiquidity(ConcentratedLiquidityPoolInfo {
+					pair,
+					..
 				}) => Ok(RedeemableAssets {
 					assets: BTreeMap::from([
 						(pair.base, Self::Balance::zero()),
 						(pair.quote, Self::Balance::zero()),
 					]),
 				}),
+				PoolConfiguration::MultiAsset(info) => {
+					let assets =
+						MultiAsset::<T>::redeemable_assets(&info, &pool_account, lp_amount)?;
+					ensure!(
+						assets.iter().all(|(asset_id, amount)| min_expected_amounts
+							.get(asset_id)
+							.map_or(true, |min_amount| amount >= min_amount)),
+						Error::<T>::CannotRespectMinimumRequested
+					);
+					Ok(RedeemableAssets { assets })
+				},
 			}
 		}
 
@@ -1028,8 +1761,8 @@ pub mod pallet {
 			pool_id: Self::PoolId,
 			lp_amount: Self::Balance,
 			min_expected_amounts: BTreeMap<Self::AssetId, Self::Balance>,
-		) -> Result<RemoveLiquiditySimulationResult<Self::AssetId, Self::Balance>, DispatchError> {
-			ensure!(min_expected_amounts.len() < 3, Error::<T>::MoreThanTwoAssetsNotYetSupported);
+		) -> Result<RemoveLiquiditySimulationResult<Self::AssetId, Self::Balance>, DispatchError>
+		{
 			let redeemable_assets =
 				Self::redeemable_assets_for_lp_tokens(pool_id, lp_amount, min_expected_amounts)?;
 			let pool = Self::get_pool(pool_id)?;
@@ -1076,12 +1809,34 @@ pub mod pallet {
 				PoolConfiguration::LiquidityBootstrapping(LiquidityBootstrappingPoolInfo {
 					pair,
 					..
+				}) |
+				PoolConfiguration::ConcentratedLiquidity(ConcentratedLiquidityPoolInfo {
+					pair,
+					..
 				}) => Ok(RemoveLiquiditySimulationResult {
 					assets: BTreeMap::from([
 						(pair.base, Self::Balance::zero()),
 						(pair.quote, Self::Balance::zero()),
 					]),
 				}),
+				PoolConfiguration::MultiAsset(info) => {
+					for (asset_id, amount) in &redeemable_assets.assets {
+						ensure!(
+							T::Assets::reducible_balance(*asset_id, &pool_account, false) >=
+								*amount,
+							Error::<T>::NotEnoughLiquidity
+						);
+					}
+					ensure!(
+						T::Assets::reducible_balance(info.lp_token, who, false) >= lp_amount,
+						Error::<T>::NotEnoughLpToken
+					);
+					let total_issuance =
+						T::Assets::total_issuance(info.lp_token).safe_sub(&lp_amount)?;
+					let mut assets = redeemable_assets.assets;
+					assets.insert(info.lp_token, total_issuance);
+					Ok(RemoveLiquiditySimulationResult { assets })
+				},
 			}
 		}
 
@@ -1108,6 +1863,24 @@ pub mod pallet {
 						asset_id,
 						quote_amount,
 					),
+				PoolConfiguration::ConcentratedLiquidity(info) =>
+					ConcentratedLiquidity::<T>::get_exchange_value(
+						&info,
+						&pool_id,
+						asset_id,
+						quote_amount,
+					),
+				PoolConfiguration::MultiAsset(info) => {
+					let pair = MultiAsset::<T>::currency_pair(&info)?;
+					let asset_in = if asset_id == pair.base { pair.quote } else { pair.base };
+					MultiAsset::<T>::get_exchange_value(
+						&info,
+						&pool_account,
+						asset_in,
+						asset_id,
+						quote_amount,
+					)
+				},
 			}
 		}
 
@@ -1158,6 +1931,24 @@ pub mod pallet {
 						min_mint_amount,
 						keep_alive,
 					)?,
+				PoolConfiguration::ConcentratedLiquidity(_) =>
+					return Err(Error::<T>::NoLpTokenForConcentratedLiquidity.into()),
+				PoolConfiguration::MultiAsset(info) => {
+					let pair = MultiAsset::<T>::currency_pair(&info)?;
+					let (minted_lp, fees) = MultiAsset::<T>::add_liquidity(
+						who,
+						&info,
+						&pool_account,
+						&BTreeMap::from([(pair.base, base_amount), (pair.quote, quote_amount)]),
+						min_mint_amount,
+						keep_alive,
+					)?;
+					// imbalance fees
+					for fee in &fees {
+						Self::disburse_fees(&pool_account, &pool_id, &info.owner, fee)?;
+					}
+					(base_amount, quote_amount, minted_lp)
+				},
 			};
 			Self::update_twap(pool_id)?;
 			Self::deposit_event(Event::<T>::LiquidityAdded {
@@ -1258,6 +2049,34 @@ pub mod pallet {
 					Pools::<T>::remove(pool_id);
 					Self::deposit_event(Event::PoolDeleted { pool_id, base_amount, quote_amount });
 				},
+				PoolConfiguration::ConcentratedLiquidity(_) =>
+					return Err(Error::<T>::NoLpTokenForConcentratedLiquidity.into()),
+				PoolConfiguration::MultiAsset(info) => {
+					let (amounts, updated_lp) = MultiAsset::<T>::remove_liquidity(
+						who,
+						&info,
+						&pool_account,
+						lp_amount,
+						&BTreeMap::from([
+							(currency_pair.base, min_base_amount),
+							(currency_pair.quote, min_quote_amount),
+						]),
+					)?;
+					Self::update_twap(pool_id)?;
+					Self::deposit_event(Event::<T>::LiquidityRemoved {
+						pool_id,
+						who: who.clone(),
+						base_amount: amounts
+							.get(&currency_pair.base)
+							.copied()
+							.unwrap_or_else(Zero::zero),
+						quote_amount: amounts
+							.get(&currency_pair.quote)
+							.copied()
+							.unwrap_or_else(Zero::zero),
+						total_issuance: updated_lp,
+					});
+				},
 			}
 			Ok(())
 		}
@@ -1343,6 +2162,32 @@ pub mod pallet {
 					T::Assets::transfer(pair.base, &pool_account, who, base_amount, false)?;
 					(base_amount, info.owner, fees)
 				},
+				PoolConfiguration::ConcentratedLiquidity(info) => {
+					let (base_amount, fees) = ConcentratedLiquidity::<T>::exchange(
+						who,
+						&info,
+						&pool_id,
+						&pool_account,
+						pair,
+						quote_amount,
+						min_receive,
+						keep_alive,
+					)?;
+					(base_amount, info.owner, fees)
+				},
+				PoolConfiguration::MultiAsset(info) => {
+					let (base_amount, fees) = MultiAsset::<T>::exchange(
+						who,
+						&info,
+						&pool_account,
+						pair.quote,
+						quote_amount,
+						pair.base,
+						min_receive,
+						keep_alive,
+					)?;
+					(base_amount, info.owner, fees)
+				},
 			};
 			Self::disburse_fees(who, &pool_id, &owner, &fees)?;
 			Self::update_twap(pool_id)?;
@@ -1392,6 +2237,18 @@ pub mod pallet {
 					let quote_amount = Self::get_exchange_value(pool_id, asset_id, amount)?;
 					Self::exchange(who, pool_id, pair, quote_amount, min_receive, keep_alive)
 				},
+				PoolConfiguration::ConcentratedLiquidity(info) => {
+					let pair =
+						if asset_id == info.pair.base { info.pair } else { info.pair.swap() };
+					let quote_amount = Self::get_exchange_value(pool_id, asset_id, amount)?;
+					Self::exchange(who, pool_id, pair, quote_amount, min_receive, keep_alive)
+				},
+				PoolConfiguration::MultiAsset(info) => {
+					let pair = MultiAsset::<T>::currency_pair(&info)?;
+					let pair = if asset_id == pair.base { pair } else { pair.swap() };
+					let quote_amount = Self::get_exchange_value(pool_id, asset_id, amount)?;
+					Self::exchange(who, pool_id, pair, quote_amount, min_receive, keep_alive)
+				},
 			}
 		}
 
@@ -1421,6 +2278,198 @@ pub mod pallet {
 						if asset_id == info.pair.base { info.pair.swap() } else { info.pair };
 					Self::exchange(who, pool_id, pair, amount, min_receive, keep_alive)
 				},
+				PoolConfiguration::ConcentratedLiquidity(info) => {
+					let pair =
+						if asset_id == info.pair.base { info.pair.swap() } else { info.pair };
+					Self::exchange(who, pool_id, pair, amount, min_receive, keep_alive)
+				},
+				PoolConfiguration::MultiAsset(info) => {
+					let pair = MultiAsset::<T>::currency_pair(&info)?;
+					let pair = if asset_id == pair.base { pair.swap() } else { pair };
+					Self::exchange(who, pool_id, pair, amount, min_receive, keep_alive)
+				},
+			}
+		}
+	}
+
+	impl<T: Config> MultiAssetAmm for Pallet<T> {
+		fn assets(pool_id: Self::PoolId) -> Result<Vec<Self::AssetId>, DispatchError> {
+			match Self::get_pool(pool_id)? {
+				PoolConfiguration::MultiAsset(info) => Ok(info.assets.keys().copied().collect()),
+				_ => {
+					let pair = Self::currency_pair(pool_id)?;
+					Ok(vec![pair.base, pair.quote])
+				},
+			}
+		}
+
+		fn get_exchange_value_between(
+			pool_id: Self::PoolId,
+			asset_in: Self::AssetId,
+			asset_out: Self::AssetId,
+			amount: Self::Balance,
+		) -> Result<Self::Balance, DispatchError> {
+			let pool = Self::get_pool(pool_id)?;
+			let pool_account = Self::account_id(&pool_id);
+			if !matches!(pool, PoolConfiguration::MultiAsset(_)) {
+				let pair = Self::currency_pair(pool_id)?;
+				// the equality of pairs ignores their orientation
+				ensure!(pair == CurrencyPair::new(asset_out, asset_in), Error::<T>::PairMismatch);
+			}
+			match pool {
+				PoolConfiguration::StableSwap(info) =>
+					StableSwap::<T>::get_amount_in(&info, &pool_account, asset_out, amount),
+				PoolConfiguration::ConstantProduct(info) =>
+					Uniswap::<T>::get_amount_in(&info, &pool_account, asset_out, amount),
+				PoolConfiguration::LiquidityBootstrapping(info) =>
+					LiquidityBootstrapping::<T>::get_amount_in(
+						info,
+						pool_account,
+						asset_out,
+						amount,
+					),
+				// the input of a swap to an exact output, in either direction
+				PoolConfiguration::ConcentratedLiquidity(info) =>
+					ConcentratedLiquidity::<T>::get_exchange_value(
+						&info, &pool_id, asset_out, amount,
+					),
+				PoolConfiguration::MultiAsset(info) => MultiAsset::<T>::get_exchange_value(
+					&info,
+					&pool_account,
+					asset_in,
+					asset_out,
+					amount,
+				),
+			}
+		}
+
+		fn exchange_assets(
+			who: &Self::AccountId,
+			pool_id: Self::PoolId,
+			asset_in: Self::AssetId,
+			amount_in: Self::Balance,
+			asset_out: Self::AssetId,
+			min_receive: Self::Balance,
+			keep_alive: bool,
+		) -> Result<Self::Balance, DispatchError> {
+			Self::exchange(
+				who,
+				pool_id,
+				CurrencyPair::new(asset_out, asset_in),
+				amount_in,
+				min_receive,
+				keep_alive,
+			)
+		}
+
+		#[transactional]
+		fn add_liquidity_assets(
+			who: &Self::AccountId,
+			pool_id: Self::PoolId,
+			amounts: BTreeMap<Self::AssetId, Self::Balance>,
+			min_mint_amount: Self::Balance,
+			keep_alive: bool,
+		) -> Result<Self::Balance, DispatchError> {
+			let pool = Self::get_multi_asset_pool(pool_id)?;
+			let pool_account = Self::account_id(&pool_id);
+			let (minted_lp, fees) = MultiAsset::<T>::add_liquidity(
+				who,
+				&pool,
+				&pool_account,
+				&amounts,
+				min_mint_amount,
+				keep_alive,
+			)?;
+			// imbalance fees
+			for fee in &fees {
+				Self::disburse_fees(&pool_account, &pool_id, &pool.owner, fee)?;
+			}
+			Self::update_twap(pool_id)?;
+			Self::deposit_event(Event::<T>::MultiAssetLiquidityAdded {
+				who: who.clone(),
+				pool_id,
+				amounts,
+				minted_lp,
+			});
+			Ok(minted_lp)
+		}
+
+		#[transactional]
+		fn remove_liquidity_assets(
+			who: &Self::AccountId,
+			pool_id: Self::PoolId,
+			lp_amount: Self::Balance,
+			min_amounts: BTreeMap<Self::AssetId, Self::Balance>,
+		) -> Result<BTreeMap<Self::AssetId, Self::Balance>, DispatchError> {
+			let pool = Self::get_multi_asset_pool(pool_id)?;
+			let pool_account = Self::account_id(&pool_id);
+			let (amounts, total_issuance) = MultiAsset::<T>::remove_liquidity(
+				who,
+				&pool,
+				&pool_account,
+				lp_amount,
+				&min_amounts,
+			)?;
+			Self::update_twap(pool_id)?;
+			Self::deposit_event(Event::<T>::MultiAssetLiquidityRemoved {
+				who: who.clone(),
+				pool_id,
+				amounts: amounts.clone(),
+				total_issuance,
+			});
+			Ok(amounts)
+		}
+
+		#[transactional]
+		fn remove_liquidity_single_asset(
+			who: &Self::AccountId,
+			pool_id: Self::PoolId,
+			lp_amount: Self::Balance,
+			asset_id: Self::AssetId,
+			min_amount: Self::Balance,
+		) -> Result<Self::Balance, DispatchError> {
+			let pool = Self::get_multi_asset_pool(pool_id)?;
+			let pool_account = Self::account_id(&pool_id);
+			let (amount, fees, total_issuance) = MultiAsset::<T>::remove_liquidity_single_asset(
+				who,
+				&pool,
+				&pool_account,
+				lp_amount,
+				asset_id,
+				min_amount,
+			)?;
+			for fee in &fees {
+				Self::disburse_fees(&pool_account, &pool_id, &pool.owner, fee)?;
+			}
+			Self::update_twap(pool_id)?;
+			Self::deposit_event(Event::<T>::MultiAssetLiquidityRemoved {
+				who: who.clone(),
+				pool_id,
+				amounts: BTreeMap::from([(asset_id, amount)]),
+				total_issuance,
+			});
+			Ok(amount)
+		}
+	}
+
+	impl<T: Config> PoolTwap for Pallet<T> {
+		type AssetId = T::AssetId;
+		type PoolId = T::PoolId;
+
+		fn twap(
+			pool_id: Self::PoolId,
+			pair: CurrencyPair<Self::AssetId>,
+		) -> Result<Rate, DispatchError> {
+			let pool_pair = Self::currency_pair(pool_id)?;
+			let twap = TWAPState::<T>::get(pool_id).ok_or(Error::<T>::TwapNotEnabled)?;
+			// `base_twap` is the amount of base for one quote, `quote_twap` the amount of quote for
+			// one base.
+			if pair == pool_pair {
+				Ok(twap.quote_twap)
+			} else if pair == pool_pair.swap() {
+				Ok(twap.base_twap)
+			} else {
+				Err(Error::<T>::PairMismatch.into())
 			}
 		}
 	}
@@ -1440,7 +2489,7 @@ pub mod pallet {
 	}
 
 	fn lp_for_liquidity<T: Config>(
-		pool_config: PoolConfiguration<T::AccountId, T::AssetId, T::BlockNumber>,
+		pool_config: PoolConfigurationOf<T>,
 		pool_account: T::AccountId,
 		base_amount: T::Balance,
 		quote_amount: T::Balance,
@@ -1473,7 +2522,18 @@ pub mod pallet {
 				)?;
 				Ok(T::Convert::convert(amount_of_lp_token_to_mint))
 			},
-			PoolConfiguration::LiquidityBootstrapping(_pool) => Ok(T::Balance::zero()),
+			PoolConfiguration::MultiAsset(pool) => {
+				let pair = MultiAsset::<T>::currency_pair(&pool)?;
+				let (amount_of_lp_token_to_mint, _) =
+					MultiAsset::<T>::calculate_mint_amount_and_fees(
+						&pool,
+						&pool_account,
+						&BTreeMap::from([(pair.base, base_amount), (pair.quote, quote_amount)]),
+					)?;
+				Ok(amount_of_lp_token_to_mint)
+			},
+			PoolConfiguration::LiquidityBootstrapping(_) |
+			PoolConfiguration::ConcentratedLiquidity(_) => Ok(T::Balance::zero()),
 		}
 	}
 }
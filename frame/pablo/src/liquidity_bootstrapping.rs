@@ -2,7 +2,9 @@ use crate::{
 	AccountIdOf, AssetIdOf, BalanceOf, Config, Error, LiquidityBootstrappingPoolInfoOf,
 	PoolConfiguration, PoolCount, Pools,
 };
-use composable_maths::dex::constant_product::{compute_out_given_in, compute_spot_price};
+use composable_maths::dex::constant_product::{
+	compute_in_given_out, compute_out_given_in, compute_spot_price,
+};
 use composable_support::{
 	math::safe::{SafeAdd, SafeSub},
 	validation::{Validate, Validated},
@@ -156,6 +158,35 @@ impl<T: Config> LiquidityBootstrapping<T> {
 		Ok(base_amount)
 	}
 
+	/// Amount of the other asset of the pair required to obtain `amount` of `asset_id`, which
+	/// can be either asset of the pair. (Note this does not include fees.)
+	pub(crate) fn get_amount_in(
+		pool: LiquidityBootstrappingPoolInfoOf<T>,
+		pool_account: AccountIdOf<T>,
+		asset_id: T::AssetId,
+		amount: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		ensure!(pool.pair.contains(asset_id), Error::<T>::InvalidAsset);
+		let current_block = frame_system::Pallet::<T>::current_block_number();
+		Self::ensure_sale_state(&pool, current_block, SaleState::Ongoing)?;
+		let (base_weight, quote_weight) = pool.sale.current_weights(current_block)?;
+		let (pair, weight_in, weight_out) = if asset_id == pool.pair.base {
+			(pool.pair, quote_weight, base_weight)
+		} else {
+			(pool.pair.swap(), base_weight, quote_weight)
+		};
+		let balance_in = T::Convert::convert(T::Assets::balance(pair.quote, &pool_account));
+		let balance_out = T::Convert::convert(T::Assets::balance(pair.base, &pool_account));
+		let amount_in = compute_in_given_out(
+			weight_in,
+			weight_out,
+			balance_in,
+			balance_out,
+			T::Convert::convert(amount),
+		)?;
+		Ok(T::Convert::convert(amount_in))
+	}
+
 	pub(crate) fn add_liquidity(
 		who: &T::AccountId,
 		pool: LiquidityBootstrappingPoolInfoOf<T>,
@@ -1,9 +1,10 @@
 #![cfg(test)]
 
 use crate as pablo;
+use composable_traits::{account_proxy::ProxyType, fnft::FnftAccountProxyType};
 use frame_support::{
 	ord_parameter_types, parameter_types,
-	traits::{EnsureOneOf, Everything},
+	traits::{ConstU32, EnsureOneOf, Everything, InstanceFilter},
 	PalletId,
 };
 use frame_system::{self as system, EnsureRoot, EnsureSignedBy};
@@ -43,6 +44,8 @@ frame_support::construct_runtime!(
 		Tokens: orml_tokens::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
 		StakingRewards: pallet_staking_rewards::{Pallet, Storage, Call, Event<T>},
+		FinancialNft: pallet_fnft::{Pallet, Storage, Event<T>},
+		Proxy: pallet_account_proxy::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -103,6 +106,7 @@ pub type Amount = i128;
 pub type PoolId = u128;
 pub type RewardPoolId = u16;
 pub type PositionId = u128;
+pub type FinancialNftInstanceId = u64;
 
 parameter_type_with_key! {
 	pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
@@ -170,8 +174,67 @@ impl pallet_staking_rewards::Config for Test {
 	type RewardPoolCreationOrigin = EnsureRoot<Self::AccountId>;
 	type WeightInfo = ();
 	type RewardPoolUpdateOrigin = EnsureRoot<Self::AccountId>;
-	type FinancialNftInstanceId = u64;
-	type FinancialNft = pablo::mock_fnft::MockFnft;
+	type FinancialNftInstanceId = FinancialNftInstanceId;
+	type FinancialNft = FinancialNft;
+}
+
+parameter_types! {
+	pub const FnftPalletId: PalletId = PalletId(*b"pal_fnft");
+}
+
+impl pallet_fnft::Config for Test {
+	type Event = Event;
+	type MaxProperties = ConstU32<16>;
+	type FinancialNftCollectionId = CurrencyId;
+	type FinancialNftInstanceId = FinancialNftInstanceId;
+	type ProxyType = ProxyType;
+	type AccountProxy = Proxy;
+	type ProxyTypeSelector = FnftAccountProxyType;
+	type AssetId = CurrencyId;
+	type Balance = Balance;
+	type Assets = Tokens;
+	type FinancialNftProtocol = StakingRewards;
+	type PalletId = FnftPalletId;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub MaxProxies: u32 = 4;
+	pub MaxPending: u32 = 32;
+	pub ProxyPrice: u32 = 0;
+}
+
+impl pallet_account_proxy::Config for Test {
+	type Event = Event;
+	type Call = Call;
+	type Currency = ();
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyPrice;
+	type ProxyDepositFactor = ProxyPrice;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = ();
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = ProxyPrice;
+	type AnnouncementDepositFactor = ProxyPrice;
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::Governance => matches!(c, Call::System(..)),
+			_ => false,
+		}
+	}
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			_ => false,
+		}
+	}
 }
 
 ord_parameter_types! {
@@ -181,6 +244,9 @@ ord_parameter_types! {
 parameter_types! {
 	pub const MaxStakingRewardPools: u32 = 10;
 	pub const MillisecsPerBlock: u32 = 12000;
+	pub const MaxAssetsPerPool: u32 = 8;
+	pub const MaxOrdersPerBlock: u32 = 16;
+	pub const MaxSwapSteps: u32 = 64;
 }
 
 impl pablo::Config for Test {
@@ -212,6 +278,12 @@ impl pablo::Config for Test {
 	type ManageStaking = StakingRewards;
 	type ProtocolStaking = StakingRewards;
 	type MsPerBlock = MillisecsPerBlock;
+	type FinancialNftInstanceId = FinancialNftInstanceId;
+	type FinancialNft = FinancialNft;
+	type MaxAssetsPerPool = MaxAssetsPerPool;
+	type OrderId = u128;
+	type MaxOrdersPerBlock = MaxOrdersPerBlock;
+	type MaxSwapSteps = MaxSwapSteps;
 }
 
 // Build genesis storage according to the mock runtime.
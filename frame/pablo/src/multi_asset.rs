@@ -0,0 +1,504 @@
+use crate::{
+	AssetWeightsOf, Config, Error, MultiAssetPoolInfoOf, PoolConfiguration, PoolCount, Pools,
+};
+use composable_maths::dex::{
+	constant_product::{
+		compute_weighted_deposit_lp, compute_weighted_first_deposit_lp,
+		compute_weighted_in_given_out, compute_weighted_out_given_in,
+		compute_weighted_single_asset_redeem,
+	},
+	stable_swap::{compute_balance_for_d, compute_d_for_balances},
+};
+use composable_support::math::safe::{safe_multiply_by_rational, SafeAdd, SafeSub};
+use composable_traits::{
+	currency::{CurrencyFactory, RangeId},
+	defi::CurrencyPair,
+	dex::{Fee, FeeConfig, MultiAssetPoolInfo, MultiAssetPoolKind},
+};
+use frame_support::{
+	pallet_prelude::*,
+	traits::fungibles::{Inspect, Mutate, Transfer},
+};
+use sp_runtime::{
+	traits::{Convert, One, Zero},
+	DispatchError, PerThing, Permill,
+};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, ops::Mul, vec, vec::Vec};
+
+pub(crate) struct MultiAsset<T>(PhantomData<T>);
+
+impl<T: Config> MultiAsset<T> {
+	pub fn do_create_pool(
+		who: &T::AccountId,
+		assets: AssetWeightsOf<T>,
+		kind: MultiAssetPoolKind,
+		fee: FeeConfig,
+	) -> Result<T::PoolId, DispatchError> {
+		ensure!(assets.len() >= 2, Error::<T>::NotEnoughAssets);
+		ensure!(assets.values().all(|weight| !weight.is_zero()), Error::<T>::WeightsMustBeNonZero);
+		let weight_sum = assets
+			.values()
+			.try_fold(0_u32, |sum, weight| sum.checked_add(weight.deconstruct()))
+			.ok_or(Error::<T>::WeightsMustSumToOne)?;
+		ensure!(weight_sum == Permill::one().deconstruct(), Error::<T>::WeightsMustSumToOne);
+		if let MultiAssetPoolKind::StableSwap { amplification_coefficient } = kind {
+			ensure!(amplification_coefficient > 0, Error::<T>::AmpFactorMustBeGreaterThanZero);
+			// equal up to the rounding of one by the number of assets
+			let max_weight = assets.values().max().copied().unwrap_or_default();
+			let min_weight = assets.values().min().copied().unwrap_or_default();
+			ensure!(
+				max_weight.deconstruct() - min_weight.deconstruct() <= 1,
+				Error::<T>::StableSwapWeightsMustBeEqual
+			);
+		}
+		ensure!(fee.fee_rate < Permill::one(), Error::<T>::InvalidFees);
+
+		let lp_token = T::CurrencyFactory::create(RangeId::LP_TOKENS, T::Balance::default())?;
+		// Add new pool
+		let pool_id =
+			PoolCount::<T>::try_mutate(|pool_count| -> Result<T::PoolId, DispatchError> {
+				let pool_id = *pool_count;
+
+				Pools::<T>::insert(
+					pool_id,
+					PoolConfiguration::MultiAsset(MultiAssetPoolInfo {
+						owner: who.clone(),
+						assets,
+						lp_token,
+						fee_config: fee,
+						kind,
+					}),
+				);
+				*pool_count = pool_id.safe_add(&T::PoolId::one())?;
+				Ok(pool_id)
+			})?;
+
+		Ok(pool_id)
+	}
+
+	/// The pair of a pool holding two assets, the first one being the base asset.
+	pub(crate) fn currency_pair(
+		pool: &MultiAssetPoolInfoOf<T>,
+	) -> Result<CurrencyPair<T::AssetId>, DispatchError> {
+		match pool.assets.keys().copied().collect::<Vec<_>>().as_slice() {
+			[base, quote] => Ok(CurrencyPair::new(*base, *quote)),
+			_ => Err(Error::<T>::MoreThanTwoAssetsNotYetSupported.into()),
+		}
+	}
+
+	fn index_of(
+		pool: &MultiAssetPoolInfoOf<T>,
+		asset_id: T::AssetId,
+	) -> Result<usize, DispatchError> {
+		pool.assets
+			.keys()
+			.position(|pool_asset_id| *pool_asset_id == asset_id)
+			.ok_or_else(|| Error::<T>::InvalidAsset.into())
+	}
+
+	fn at<V: Copy>(values: &[V], index: usize) -> Result<V, DispatchError> {
+		values.get(index).copied().ok_or_else(|| Error::<T>::InvalidAsset.into())
+	}
+
+	fn weights(pool: &MultiAssetPoolInfoOf<T>) -> Vec<Permill> {
+		pool.assets.values().copied().collect()
+	}
+
+	/// Balances of the pool, in the order of the asset ids.
+	fn balances(pool: &MultiAssetPoolInfoOf<T>, pool_account: &T::AccountId) -> Vec<u128> {
+		pool.assets
+			.keys()
+			.map(|asset_id| T::Convert::convert(T::Assets::balance(*asset_id, pool_account)))
+			.collect()
+	}
+
+	/// Deposit x + withdraw y should charge about same fees as a swap, see
+	/// [`crate::stable_swap::StableSwap::calculate_mint_amount_and_fees`].
+	fn imbalance_fee_config(pool: &MultiAssetPoolInfoOf<T>) -> FeeConfig {
+		let n = pool.assets.len() as u32;
+		pool.fee_config.mul(Permill::from_rational(n, 4 * n.saturating_sub(1)))
+	}
+
+	/// Amount of `asset_in` required to obtain `amount_out` of `asset_out`, excluding fees.
+	pub fn get_exchange_value(
+		pool: &MultiAssetPoolInfoOf<T>,
+		pool_account: &T::AccountId,
+		asset_in: T::AssetId,
+		asset_out: T::AssetId,
+		amount_out: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::InvalidPair);
+		let index_in = Self::index_of(pool, asset_in)?;
+		let index_out = Self::index_of(pool, asset_out)?;
+		let balances = Self::balances(pool, pool_account);
+		ensure!(!balances.contains(&0), Error::<T>::NotEnoughLiquidity);
+		let balance_in = Self::at(&balances, index_in)?;
+		let balance_out = Self::at(&balances, index_out)?;
+		let amount_out = T::Convert::convert(amount_out);
+		ensure!(amount_out < balance_out, Error::<T>::NotEnoughLiquidity);
+		let amount_in = match pool.kind {
+			MultiAssetPoolKind::StableSwap { amplification_coefficient } => {
+				let amp = amplification_coefficient.into();
+				let d = compute_d_for_balances(&balances, amp)?;
+				let new_balances = balances
+					.iter()
+					.enumerate()
+					.map(|(index, balance)| {
+						if index == index_out {
+							balance.safe_sub(&amount_out)
+						} else {
+							Ok(*balance)
+						}
+					})
+					.collect::<Result<Vec<_>, _>>()?;
+				compute_balance_for_d(&new_balances, index_in, amp, d)?.safe_sub(&balance_in)?
+			},
+			MultiAssetPoolKind::ConstantProduct => {
+				let weights = Self::weights(pool);
+				compute_weighted_in_given_out(
+					Self::at(&weights, index_in)?,
+					Self::at(&weights, index_out)?,
+					balance_in,
+					balance_out,
+					amount_out,
+				)?
+			},
+		};
+		Ok(T::Convert::convert(amount_in))
+	}
+
+	/// Amount of `asset_out` received for `amount_in` of `asset_in`, along the fees charged on
+	/// `asset_in`.
+	pub fn do_compute_swap(
+		pool: &MultiAssetPoolInfoOf<T>,
+		pool_account: &T::AccountId,
+		asset_in: T::AssetId,
+		amount_in: T::Balance,
+		asset_out: T::AssetId,
+		apply_fees: bool,
+	) -> Result<(T::Balance, Fee<T::AssetId, T::Balance>), DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::InvalidPair);
+		let index_in = Self::index_of(pool, asset_in)?;
+		let index_out = Self::index_of(pool, asset_out)?;
+		let balances = Self::balances(pool, pool_account);
+		ensure!(!balances.contains(&0), Error::<T>::NotEnoughLiquidity);
+		let balance_in = Self::at(&balances, index_in)?;
+		let balance_out = Self::at(&balances, index_out)?;
+
+		let fee = if apply_fees {
+			pool.fee_config.calculate_fees(asset_in, amount_in)
+		} else {
+			Fee::<T::AssetId, T::Balance>::zero(asset_in)
+		};
+		// Charging fees "on the way in"
+		let amount_in_excluding_fee = T::Convert::convert(amount_in.safe_sub(&fee.fee)?);
+		let amount_out = match pool.kind {
+			MultiAssetPoolKind::StableSwap { amplification_coefficient } => {
+				let amp = amplification_coefficient.into();
+				let d = compute_d_for_balances(&balances, amp)?;
+				let new_balances = balances
+					.iter()
+					.enumerate()
+					.map(|(index, balance)| {
+						if index == index_in {
+							balance.safe_add(&amount_in_excluding_fee)
+						} else {
+							Ok(*balance)
+						}
+					})
+					.collect::<Result<Vec<_>, _>>()?;
+				let new_balance_out = compute_balance_for_d(&new_balances, index_out, amp, d)?;
+				// rounding in favor of the pool
+				balance_out.safe_sub(&new_balance_out)?.saturating_sub(1)
+			},
+			MultiAssetPoolKind::ConstantProduct => {
+				let weights = Self::weights(pool);
+				compute_weighted_out_given_in(
+					Self::at(&weights, index_in)?,
+					Self::at(&weights, index_out)?,
+					balance_in,
+					balance_out,
+					amount_in_excluding_fee,
+				)?
+			},
+		};
+		ensure!(amount_out > 0 && amount_in_excluding_fee > 0, Error::<T>::InvalidAmount);
+		Ok((T::Convert::convert(amount_out), fee))
+	}
+
+	/// Trade `amount_in` of `asset_in` against `asset_out`. The owner and protocol fees are left
+	/// to the caller, the LP fee being kept by the pool.
+	#[allow(clippy::too_many_arguments)]
+	pub fn exchange(
+		who: &T::AccountId,
+		pool: &MultiAssetPoolInfoOf<T>,
+		pool_account: &T::AccountId,
+		asset_in: T::AssetId,
+		amount_in: T::Balance,
+		asset_out: T::AssetId,
+		min_receive: T::Balance,
+		keep_alive: bool,
+	) -> Result<(T::Balance, Fee<T::AssetId, T::Balance>), DispatchError> {
+		let (amount_out, fee) =
+			Self::do_compute_swap(pool, pool_account, asset_in, amount_in, asset_out, true)?;
+		ensure!(amount_out >= min_receive, Error::<T>::CannotRespectMinimumRequested);
+
+		let amount_to_pool = amount_in.safe_sub(&fee.owner_fee)?.safe_sub(&fee.protocol_fee)?;
+		T::Assets::transfer(asset_in, who, pool_account, amount_to_pool, keep_alive)?;
+		// no need to keep alive the pool account
+		T::Assets::transfer(asset_out, pool_account, who, amount_out, false)?;
+		Ok((amount_out, fee))
+	}
+
+	/// Deposited amounts in the order of the asset ids, missing ones being zero.
+	fn deposits(
+		pool: &MultiAssetPoolInfoOf<T>,
+		amounts: &BTreeMap<T::AssetId, T::Balance>,
+	) -> Result<Vec<u128>, DispatchError> {
+		ensure!(
+			amounts.keys().all(|asset_id| pool.assets.contains_key(asset_id)),
+			Error::<T>::InvalidAsset
+		);
+		let deposits: Vec<u128> = pool
+			.assets
+			.keys()
+			.map(|asset_id| {
+				T::Convert::convert(amounts.get(asset_id).copied().unwrap_or_else(Zero::zero))
+			})
+			.collect();
+		ensure!(
+			deposits.iter().any(|amount| *amount > 0),
+			Error::<T>::AssetAmountMustBePositiveNumber
+		);
+		Ok(deposits)
+	}
+
+	pub(crate) fn calculate_mint_amount_and_fees(
+		pool: &MultiAssetPoolInfoOf<T>,
+		pool_account: &T::AccountId,
+		amounts: &BTreeMap<T::AssetId, T::Balance>,
+	) -> Result<(T::Balance, Vec<Fee<T::AssetId, T::Balance>>), DispatchError> {
+		let deposits = Self::deposits(pool, amounts)?;
+		let weights = Self::weights(pool);
+		let total_lp_issued = T::Convert::convert(T::Assets::total_issuance(pool.lp_token));
+
+		if total_lp_issued.is_zero() {
+			// the initial deposit defines the price of every asset
+			ensure!(!deposits.contains(&0), Error::<T>::MissingAmount);
+			let mint_amount = match pool.kind {
+				MultiAssetPoolKind::StableSwap { amplification_coefficient } =>
+					compute_d_for_balances(&deposits, amplification_coefficient.into())?,
+				MultiAssetPoolKind::ConstantProduct => compute_weighted_first_deposit_lp(
+					&weights.into_iter().zip(deposits).collect::<Vec<_>>(),
+				)?,
+			};
+			return Ok((T::Convert::convert(mint_amount), Vec::new()))
+		}
+
+		let balances = Self::balances(pool, pool_account);
+		let (mint_amount, fees) = match pool.kind {
+			MultiAssetPoolKind::StableSwap { amplification_coefficient } => {
+				let amp = amplification_coefficient.into();
+				let d0 = compute_d_for_balances(&balances, amp)?;
+				let new_balances = balances
+					.iter()
+					.zip(&deposits)
+					.map(|(balance, amount)| balance.safe_add(amount))
+					.collect::<Result<Vec<_>, _>>()?;
+				let d1 = compute_d_for_balances(&new_balances, amp)?;
+				ensure!(d1 > d0, Error::<T>::AssetAmountMustBePositiveNumber);
+
+				let fee_config = Self::imbalance_fee_config(pool);
+				let mut fees = Vec::with_capacity(balances.len());
+				let mut new_balances_excluding_fees = Vec::with_capacity(balances.len());
+				for ((asset_id, balance), new_balance) in
+					pool.assets.keys().zip(&balances).zip(&new_balances)
+				{
+					// differences from the ideal balance to be used in fee calculation
+					let ideal_balance = safe_multiply_by_rational(d1, *balance, d0)?;
+					let difference = ideal_balance.abs_diff(*new_balance);
+					let fee = fee_config.calculate_fees(*asset_id, T::Convert::convert(difference));
+					new_balances_excluding_fees
+						.push(new_balance.safe_sub(&T::Convert::convert(fee.fee))?);
+					fees.push(fee);
+				}
+				let d2 = compute_d_for_balances(&new_balances_excluding_fees, amp)?;
+				// minted LP is propotional to the delta of the pool invariant caused by imbalanced
+				// liquidity
+				(safe_multiply_by_rational(total_lp_issued, d2.safe_sub(&d0)?, d0)?, fees)
+			},
+			MultiAssetPoolKind::ConstantProduct => {
+				// fees are charged on the unbalanced part of the deposit and kept by the pool
+				let deposits = weights
+					.into_iter()
+					.zip(balances)
+					.zip(deposits)
+					.map(|((weight, balance), amount)| (weight, balance, amount))
+					.collect::<Vec<_>>();
+				(
+					compute_weighted_deposit_lp(
+						total_lp_issued,
+						&deposits,
+						pool.fee_config.fee_rate,
+					)?,
+					Vec::new(),
+				)
+			},
+		};
+		Ok((T::Convert::convert(mint_amount), fees))
+	}
+
+	/// Deposit any subset of the assets of the pool. Returns the minted LP along the imbalance
+	/// fees, which the caller must disburse from the pool account.
+	pub fn add_liquidity(
+		who: &T::AccountId,
+		pool: &MultiAssetPoolInfoOf<T>,
+		pool_account: &T::AccountId,
+		amounts: &BTreeMap<T::AssetId, T::Balance>,
+		min_mint_amount: T::Balance,
+		keep_alive: bool,
+	) -> Result<(T::Balance, Vec<Fee<T::AssetId, T::Balance>>), DispatchError> {
+		let (mint_amount, fees) =
+			Self::calculate_mint_amount_and_fees(pool, pool_account, amounts)?;
+		ensure!(
+			!mint_amount.is_zero() && mint_amount >= min_mint_amount,
+			Error::<T>::CannotRespectMinimumRequested
+		);
+		for (asset_id, amount) in amounts {
+			T::Assets::transfer(*asset_id, who, pool_account, *amount, keep_alive)?;
+		}
+		T::Assets::mint_into(pool.lp_token, who, mint_amount)?;
+		Ok((mint_amount, fees))
+	}
+
+	/// Amounts of every asset redeemed for `lp_amount`, proportionally to the pool balances.
+	pub(crate) fn redeemable_assets(
+		pool: &MultiAssetPoolInfoOf<T>,
+		pool_account: &T::AccountId,
+		lp_amount: T::Balance,
+	) -> Result<BTreeMap<T::AssetId, T::Balance>, DispatchError> {
+		let lp_issued = T::Assets::total_issuance(pool.lp_token);
+		ensure!(lp_amount <= lp_issued, Error::<T>::NotEnoughLpToken);
+		pool.assets
+			.keys()
+			.map(|asset_id| {
+				let amount = safe_multiply_by_rational(
+					T::Convert::convert(lp_amount),
+					T::Convert::convert(T::Assets::balance(*asset_id, pool_account)),
+					T::Convert::convert(lp_issued),
+				)?;
+				Ok((*asset_id, T::Convert::convert(amount)))
+			})
+			.collect()
+	}
+
+	/// Withdraw every asset proportionally to the pool balances.
+	/// Returns the withdrawn amounts along the updated LP supply.
+	pub fn remove_liquidity(
+		who: &T::AccountId,
+		pool: &MultiAssetPoolInfoOf<T>,
+		pool_account: &T::AccountId,
+		lp_amount: T::Balance,
+		min_amounts: &BTreeMap<T::AssetId, T::Balance>,
+	) -> Result<(BTreeMap<T::AssetId, T::Balance>, T::Balance), DispatchError> {
+		ensure!(
+			min_amounts.keys().all(|asset_id| pool.assets.contains_key(asset_id)),
+			Error::<T>::InvalidAsset
+		);
+		let amounts = Self::redeemable_assets(pool, pool_account, lp_amount)?;
+		ensure!(
+			amounts.iter().all(|(asset_id, amount)| min_amounts
+				.get(asset_id)
+				.map_or(true, |min_amount| amount >= min_amount)),
+			Error::<T>::CannotRespectMinimumRequested
+		);
+		let total_issuance = T::Assets::total_issuance(pool.lp_token).safe_sub(&lp_amount)?;
+
+		// no need to keep alive the pool account
+		for (asset_id, amount) in &amounts {
+			T::Assets::transfer(*asset_id, pool_account, who, *amount, false)?;
+		}
+		T::Assets::burn_from(pool.lp_token, who, lp_amount)?;
+		Ok((amounts, total_issuance))
+	}
+
+	/// Withdraw a single asset for `lp_amount`, which is charged fees as it unbalances the pool.
+	/// Returns the withdrawn amount, the fees to be disbursed from the pool account by the caller
+	/// and the updated LP supply.
+	#[allow(clippy::type_complexity)]
+	pub fn remove_liquidity_single_asset(
+		who: &T::AccountId,
+		pool: &MultiAssetPoolInfoOf<T>,
+		pool_account: &T::AccountId,
+		lp_amount: T::Balance,
+		asset_id: T::AssetId,
+		min_amount: T::Balance,
+	) -> Result<(T::Balance, Vec<Fee<T::AssetId, T::Balance>>, T::Balance), DispatchError> {
+		let index = Self::index_of(pool, asset_id)?;
+		let balances = Self::balances(pool, pool_account);
+		let balance = Self::at(&balances, index)?;
+		let lp_issued = T::Assets::total_issuance(pool.lp_token);
+		// the whole supply can only be redeemed for every asset
+		ensure!(!lp_amount.is_zero() && lp_amount < lp_issued, Error::<T>::NotEnoughLpToken);
+		let total_issuance = lp_issued.safe_sub(&lp_amount)?;
+		let lp_issued = T::Convert::convert(lp_issued);
+		let lp_amount_u128 = T::Convert::convert(lp_amount);
+
+		let (amount, fees) = match pool.kind {
+			MultiAssetPoolKind::StableSwap { amplification_coefficient } => {
+				let amp = amplification_coefficient.into();
+				let d0 = compute_d_for_balances(&balances, amp)?;
+				let d1 = d0.safe_sub(&safe_multiply_by_rational(lp_amount_u128, d0, lp_issued)?)?;
+				let new_balance = compute_balance_for_d(&balances, index, amp, d1)?;
+
+				// only the part of the withdrawal exceeding the ideal balances is charged
+				let fee_rate = Self::imbalance_fee_config(pool).fee_rate;
+				let mut reduced_balances = Vec::with_capacity(balances.len());
+				for (balance_index, pool_balance) in balances.iter().enumerate() {
+					let ideal_balance = safe_multiply_by_rational(*pool_balance, d1, d0)?;
+					let expected_withdrawal = if balance_index == index {
+						ideal_balance.saturating_sub(new_balance)
+					} else {
+						pool_balance.safe_sub(&ideal_balance)?
+					};
+					reduced_balances
+						.push(pool_balance.safe_sub(&fee_rate.mul_floor(expected_withdrawal))?);
+				}
+				let reduced_balance = Self::at(&reduced_balances, index)?;
+				// rounding in favor of the pool
+				let amount = reduced_balance
+					.safe_sub(&compute_balance_for_d(&reduced_balances, index, amp, d1)?)?
+					.saturating_sub(1);
+				let charged_fee = balance.safe_sub(&new_balance)?.saturating_sub(amount);
+				// the fee amount is already computed, only its split remains
+				let fee_config = FeeConfig { fee_rate: Permill::one(), ..pool.fee_config };
+				(
+					amount,
+					vec![fee_config.calculate_fees(asset_id, T::Convert::convert(charged_fee))],
+				)
+			},
+			MultiAssetPoolKind::ConstantProduct => {
+				// fees are charged on the unbalanced part of the withdrawal and kept by the pool
+				let weight = Self::at(&Self::weights(pool), index)?;
+				(
+					compute_weighted_single_asset_redeem(
+						lp_issued,
+						lp_amount_u128,
+						weight,
+						balance,
+						pool.fee_config.fee_rate,
+					)?,
+					Vec::new(),
+				)
+			},
+		};
+		ensure!(amount < balance, Error::<T>::NotEnoughLiquidity);
+		let amount = T::Convert::convert(amount);
+		ensure!(amount >= min_amount, Error::<T>::CannotRespectMinimumRequested);
+
+		// no need to keep alive the pool account
+		T::Assets::transfer(asset_id, pool_account, who, amount, false)?;
+		T::Assets::burn_from(pool.lp_token, who, lp_amount)?;
+		Ok((amount, fees, total_issuance))
+	}
+}
@@ -0,0 +1,431 @@
+#[cfg(test)]
+use crate::{
+	common_test_functions::assert_has_event,
+	mock,
+	mock::{Pablo, *},
+	AssetAmountsOf, AssetWeightsOf, Error, PoolInitConfiguration,
+};
+use composable_maths::dex::constant_product::compute_weighted_out_given_in;
+use composable_tests_helpers::test::helper::default_acceptable_computation_error;
+use composable_traits::{
+	defi::CurrencyPair,
+	dex::{Amm, FeeConfig, MultiAssetAmm, MultiAssetPoolKind},
+};
+use frame_support::{
+	assert_err, assert_noop, assert_ok,
+	traits::{
+		fungibles::{Inspect, Mutate},
+		TryCollect,
+	},
+};
+use sp_runtime::Permill;
+use sp_std::collections::btree_map::BTreeMap;
+
+const UNIT: Balance = 1_000_000_000_000;
+const FEE: Permill = Permill::from_percent(1);
+const DAI: AssetId = 3;
+const STABLE_SWAP: MultiAssetPoolKind =
+	MultiAssetPoolKind::StableSwap { amplification_coefficient: 100 };
+const CONSTANT_PRODUCT: MultiAssetPoolKind = MultiAssetPoolKind::ConstantProduct;
+
+fn stable_coins() -> [(AssetId, Permill); 3] {
+	[
+		(USDT, Permill::from_parts(333_334)),
+		(DAI, Permill::from_parts(333_333)),
+		(USDC, Permill::from_parts(333_333)),
+	]
+}
+
+fn asset_weights(weights: &[(AssetId, Permill)]) -> AssetWeightsOf<Test> {
+	weights.iter().copied().try_collect().expect("within bounds")
+}
+
+fn asset_amounts(amounts: &[(AssetId, Balance)]) -> AssetAmountsOf<Test> {
+	amounts.iter().copied().try_collect().expect("within bounds")
+}
+
+fn create_pool(kind: MultiAssetPoolKind, weights: &[(AssetId, Permill)]) -> PoolId {
+	System::set_block_number(1);
+	Pablo::do_create_pool(PoolInitConfiguration::MultiAsset {
+		owner: ALICE,
+		assets: asset_weights(weights),
+		kind,
+		fee: FEE,
+	})
+	.expect("pool creation failed")
+}
+
+/// Returns the amount of LP minted to `who`.
+fn add_liquidity(who: AccountId, pool_id: PoolId, amounts: &[(AssetId, Balance)]) -> Balance {
+	for (asset_id, amount) in amounts {
+		assert_ok!(Tokens::mint_into(*asset_id, &who, *amount));
+	}
+	let lp_token = Pablo::lp_token(pool_id).expect("pool has an lp token");
+	let lp_balance = Tokens::balance(lp_token, &who);
+	assert_ok!(Pablo::add_liquidity_assets(
+		Origin::signed(who),
+		pool_id,
+		asset_amounts(amounts),
+		0,
+		false
+	));
+	Tokens::balance(lp_token, &who) - lp_balance
+}
+
+#[test]
+fn create_pool_with_invalid_weights() {
+	new_test_ext().execute_with(|| {
+		let create = |kind, weights: &[(AssetId, Permill)]| {
+			Pablo::do_create_pool(PoolInitConfiguration::MultiAsset {
+				owner: ALICE,
+				assets: asset_weights(weights),
+				kind,
+				fee: FEE,
+			})
+		};
+		assert_noop!(
+			create(STABLE_SWAP, &[(USDT, Permill::one())]),
+			Error::<Test>::NotEnoughAssets
+		);
+		assert_noop!(
+			create(
+				CONSTANT_PRODUCT,
+				&[(BTC, Permill::from_percent(50)), (USDT, Permill::from_percent(40))]
+			),
+			Error::<Test>::WeightsMustSumToOne
+		);
+		assert_noop!(
+			create(CONSTANT_PRODUCT, &[(BTC, Permill::one()), (USDT, Permill::zero())]),
+			Error::<Test>::WeightsMustBeNonZero
+		);
+		assert_noop!(
+			create(
+				STABLE_SWAP,
+				&[(USDT, Permill::from_percent(60)), (USDC, Permill::from_percent(40))]
+			),
+			Error::<Test>::StableSwapWeightsMustBeEqual
+		);
+		assert_noop!(
+			create(
+				MultiAssetPoolKind::StableSwap { amplification_coefficient: 0 },
+				&stable_coins()
+			),
+			Error::<Test>::AmpFactorMustBeGreaterThanZero
+		);
+	});
+}
+
+#[test]
+fn stable_swap_between_three_assets() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool(STABLE_SWAP, &stable_coins());
+		assert_has_event::<Test, _>(|e| {
+			matches!(
+				&e.event,
+				mock::Event::Pablo(crate::Event::MultiAssetPoolCreated { pool_id: created, assets, .. })
+					if *created == pool_id && *assets == vec![USDT, DAI, USDC]
+			)
+		});
+		add_liquidity(
+			ALICE,
+			pool_id,
+			&[(USDT, 1_000_000 * UNIT), (DAI, 1_000_000 * UNIT), (USDC, 1_000_000 * UNIT)],
+		);
+		assert_eq!(<Pablo as MultiAssetAmm>::assets(pool_id), Ok(vec![USDT, DAI, USDC]));
+		assert_err!(
+			<Pablo as Amm>::currency_pair(pool_id),
+			Error::<Test>::MoreThanTwoAssetsNotYetSupported
+		);
+
+		let amount = 10_000 * UNIT;
+		assert_ok!(Tokens::mint_into(USDT, &BOB, amount));
+		assert_ok!(<Pablo as MultiAssetAmm>::exchange_assets(
+			&BOB, pool_id, USDT, amount, USDC, 0, false
+		));
+		// near par, less the fee
+		let received = Tokens::balance(USDC, &BOB);
+		assert!(received > 9_890 * UNIT && received < 9_900 * UNIT);
+		assert_eq!(Tokens::balance(USDT, &BOB), 0);
+		assert_eq!(Tokens::balance(DAI, &BOB), 0);
+		assert_has_event::<Test, _>(|e| {
+			matches!(
+				&e.event,
+				mock::Event::Pablo(crate::Event::Swapped { base_asset: USDC, quote_asset: USDT, base_amount, .. })
+					if *base_amount == received
+			)
+		});
+
+		// USDC is now scarcer than the other assets of the pool
+		for asset_in in [USDT, DAI] {
+			let value = <Pablo as MultiAssetAmm>::get_exchange_value_between(
+				pool_id, asset_in, USDC, received,
+			)
+			.expect("assets of the pool");
+			assert!(value > received);
+		}
+	});
+}
+
+#[test]
+fn exchange_value_between_the_assets_of_a_pair_in_both_orientations() {
+	new_test_ext().execute_with(|| {
+		let pool_id = Pablo::do_create_pool(PoolInitConfiguration::ConstantProduct {
+			owner: ALICE,
+			pair: CurrencyPair::new(BTC, USDT),
+			fee: Permill::zero(),
+			base_weight: Permill::from_percent(50),
+		})
+		.expect("pool creation failed");
+		assert_ok!(Tokens::mint_into(BTC, &ALICE, 10 * UNIT));
+		assert_ok!(Tokens::mint_into(USDT, &ALICE, 200_000 * UNIT));
+		assert_ok!(Pablo::add_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			10 * UNIT,
+			200_000 * UNIT,
+			0,
+			false
+		));
+
+		// in = balance_in * amount_out / (balance_out - amount_out) for equal weights
+		let usdt_for_btc =
+			<Pablo as MultiAssetAmm>::get_exchange_value_between(pool_id, USDT, BTC, UNIT)
+				.expect("assets of the pair");
+		assert_ok!(default_acceptable_computation_error(usdt_for_btc, 200_000 * UNIT / 9));
+		let btc_for_usdt =
+			<Pablo as MultiAssetAmm>::get_exchange_value_between(pool_id, BTC, USDT, 20_000 * UNIT)
+				.expect("assets of the pair");
+		assert_ok!(default_acceptable_computation_error(btc_for_usdt, 10 * UNIT / 9));
+
+		assert_noop!(
+			<Pablo as MultiAssetAmm>::get_exchange_value_between(pool_id, BTC, BTC, UNIT),
+			Error::<Test>::PairMismatch
+		);
+		assert_noop!(
+			<Pablo as MultiAssetAmm>::get_exchange_value_between(pool_id, USDC, BTC, UNIT),
+			Error::<Test>::PairMismatch
+		);
+	});
+}
+
+#[test]
+fn weighted_swap_between_three_assets() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool(
+			CONSTANT_PRODUCT,
+			&[
+				(BTC, Permill::from_percent(50)),
+				(USDT, Permill::from_percent(25)),
+				(USDC, Permill::from_percent(25)),
+			],
+		);
+		add_liquidity(
+			ALICE,
+			pool_id,
+			&[(BTC, 100 * UNIT), (USDT, 2_000_000 * UNIT), (USDC, 2_000_000 * UNIT)],
+		);
+
+		let amount = 1_000 * UNIT;
+		assert_ok!(Tokens::mint_into(USDT, &BOB, amount));
+		assert_ok!(Pablo::swap(
+			Origin::signed(BOB),
+			pool_id,
+			CurrencyPair::new(BTC, USDT),
+			amount,
+			0,
+			false
+		));
+		let fees = FeeConfig::default_from(FEE).calculate_fees(USDT, amount);
+		let expected = compute_weighted_out_given_in(
+			Permill::from_percent(25),
+			Permill::from_percent(50),
+			2_000_000 * UNIT,
+			100 * UNIT,
+			amount - fees.fee,
+		)
+		.expect("in range");
+		assert_eq!(Tokens::balance(BTC, &BOB), expected);
+		// the LP fee is kept by the pool
+		assert_eq!(
+			Tokens::balance(USDT, &Pablo::account_id(&pool_id)),
+			2_000_000 * UNIT + amount - fees.owner_fee - fees.protocol_fee
+		);
+	});
+}
+
+#[test]
+fn simulate_add_liquidity_of_any_assets() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool(STABLE_SWAP, &stable_coins());
+		add_liquidity(
+			ALICE,
+			pool_id,
+			&[(USDT, 1_000_000 * UNIT), (DAI, 1_000_000 * UNIT), (USDC, 1_000_000 * UNIT)],
+		);
+		let amounts = [(USDT, 20_000 * UNIT), (DAI, 10_000 * UNIT)];
+		for (asset_id, amount) in amounts {
+			assert_ok!(Tokens::mint_into(asset_id, &BOB, amount));
+		}
+		let simulated =
+			<Pablo as Amm>::simulate_add_liquidity(&BOB, pool_id, BTreeMap::from(amounts))
+				.expect("simulation failed");
+		assert_ok!(Pablo::add_liquidity_assets(
+			Origin::signed(BOB),
+			pool_id,
+			asset_amounts(&amounts),
+			simulated,
+			false
+		));
+		let lp_token = Pablo::lp_token(pool_id).expect("pool has an lp token");
+		assert_eq!(Tokens::balance(lp_token, &BOB), simulated);
+		// the imbalanced deposit is charged fees
+		assert!(simulated < 30_000 * UNIT);
+	});
+}
+
+#[test]
+fn single_asset_liquidity_round_trip_is_charged_fees() {
+	for kind in [STABLE_SWAP, CONSTANT_PRODUCT] {
+		new_test_ext().execute_with(|| {
+			let pool_id = create_pool(kind, &stable_coins());
+			add_liquidity(
+				ALICE,
+				pool_id,
+				&[(USDT, 1_000_000 * UNIT), (DAI, 1_000_000 * UNIT), (USDC, 1_000_000 * UNIT)],
+			);
+			let amount = 10_000 * UNIT;
+			let lp_amount = add_liquidity(BOB, pool_id, &[(USDT, amount)]);
+			assert!(lp_amount > 0);
+
+			assert_noop!(
+				Pablo::remove_liquidity_single_asset(
+					Origin::signed(BOB),
+					pool_id,
+					lp_amount,
+					USDT,
+					amount
+				),
+				Error::<Test>::CannotRespectMinimumRequested
+			);
+			assert_ok!(Pablo::remove_liquidity_single_asset(
+				Origin::signed(BOB),
+				pool_id,
+				lp_amount,
+				USDT,
+				0
+			));
+			let withdrawn = Tokens::balance(USDT, &BOB);
+			assert!(withdrawn < amount && withdrawn > amount * 99 / 100);
+			let lp_token = Pablo::lp_token(pool_id).expect("pool has an lp token");
+			assert_eq!(Tokens::balance(lp_token, &BOB), 0);
+		});
+	}
+}
+
+#[test]
+fn remove_liquidity_proportionally() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool(
+			CONSTANT_PRODUCT,
+			&[
+				(BTC, Permill::from_percent(50)),
+				(USDT, Permill::from_percent(25)),
+				(USDC, Permill::from_percent(25)),
+			],
+		);
+		let lp_amount = add_liquidity(
+			ALICE,
+			pool_id,
+			&[(BTC, 100 * UNIT), (USDT, 2_000_000 * UNIT), (USDC, 2_000_000 * UNIT)],
+		);
+		assert_noop!(
+			Pablo::remove_liquidity_assets(
+				Origin::signed(ALICE),
+				pool_id,
+				lp_amount / 2,
+				asset_amounts(&[(BTC, 51 * UNIT)])
+			),
+			Error::<Test>::CannotRespectMinimumRequested
+		);
+		assert_ok!(Pablo::remove_liquidity_assets(
+			Origin::signed(ALICE),
+			pool_id,
+			lp_amount / 2,
+			asset_amounts(&[(BTC, 49 * UNIT)])
+		));
+		assert_ok!(default_acceptable_computation_error(Tokens::balance(BTC, &ALICE), 50 * UNIT));
+		assert_ok!(default_acceptable_computation_error(
+			Tokens::balance(USDT, &ALICE),
+			1_000_000 * UNIT
+		));
+		assert_ok!(default_acceptable_computation_error(
+			Tokens::balance(USDC, &ALICE),
+			1_000_000 * UNIT
+		));
+		assert_has_event::<Test, _>(|e| {
+			matches!(
+				&e.event,
+				mock::Event::Pablo(crate::Event::MultiAssetLiquidityRemoved { amounts, .. })
+					if amounts.len() == 3
+			)
+		});
+	});
+}
+
+#[test]
+fn two_assets_pool_supports_pair_operations() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool(
+			STABLE_SWAP,
+			&[(USDT, Permill::from_percent(50)), (USDC, Permill::from_percent(50))],
+		);
+		assert_eq!(<Pablo as Amm>::currency_pair(pool_id), Ok(CurrencyPair::new(USDT, USDC)));
+		assert_ok!(Tokens::mint_into(USDT, &ALICE, 1_000_000 * UNIT));
+		assert_ok!(Tokens::mint_into(USDC, &ALICE, 1_000_000 * UNIT));
+		assert_ok!(Pablo::add_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			1_000_000 * UNIT,
+			1_000_000 * UNIT,
+			0,
+			false
+		));
+
+		let amount = 1_000 * UNIT;
+		assert_ok!(Tokens::mint_into(USDC, &BOB, amount));
+		assert_ok!(<Pablo as Amm>::sell(&BOB, pool_id, USDC, amount, 0, false));
+		let received = Tokens::balance(USDT, &BOB);
+		assert!(received > 989 * UNIT && received < 990 * UNIT);
+
+		let lp_token = Pablo::lp_token(pool_id).expect("pool has an lp token");
+		let lp_amount = Tokens::balance(lp_token, &ALICE);
+		assert_ok!(Pablo::remove_liquidity(Origin::signed(ALICE), pool_id, lp_amount, 0, 0));
+		assert_eq!(Tokens::balance(lp_token, &ALICE), 0);
+	});
+}
+
+#[test]
+fn pair_pools_are_not_multi_asset_pools() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = Pablo::do_create_pool(PoolInitConfiguration::ConstantProduct {
+			owner: ALICE,
+			pair: CurrencyPair::new(BTC, USDT),
+			fee: FEE,
+			base_weight: Permill::from_percent(50),
+		})
+		.expect("pool creation failed");
+		assert_eq!(<Pablo as MultiAssetAmm>::assets(pool_id), Ok(vec![BTC, USDT]));
+		assert_ok!(Tokens::mint_into(BTC, &ALICE, UNIT));
+		assert_noop!(
+			Pablo::add_liquidity_assets(
+				Origin::signed(ALICE),
+				pool_id,
+				asset_amounts(&[(BTC, UNIT)]),
+				0,
+				false
+			),
+			Error::<Test>::NotMultiAssetPool
+		);
+	});
+}
@@ -0,0 +1,162 @@
+use crate::{
+	types::{Order, OrderKind, OrderTrigger, PriceSource},
+	Config, Error, Event, OrderCount, OrderOf, Orders, Pallet, PriceRatio, TWAPState,
+};
+use composable_support::math::safe::SafeAdd;
+use composable_traits::{
+	defi::{CurrencyPair, Rate},
+	dex::Amm,
+};
+use frame_support::{pallet_prelude::*, traits::fungibles::Transfer, transactional};
+use sp_runtime::traits::{AccountIdConversion, BlockNumberProvider, One, Zero};
+
+/// Resting limit and stop orders, their funds being escrowed in a single account of the pallet.
+pub(crate) struct OrderBook<T>(PhantomData<T>);
+
+impl<T: Config> OrderBook<T> {
+	pub(crate) fn account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(b"orders")
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) fn place(
+		who: &T::AccountId,
+		pool_id: T::PoolId,
+		pair: CurrencyPair<T::AssetId>,
+		amount: T::Balance,
+		min_receive: T::Balance,
+		trigger: OrderTrigger,
+		keeper_reward: T::Balance,
+		expires_at: T::BlockNumber,
+	) -> Result<T::OrderId, DispatchError> {
+		ensure!(amount > T::Balance::zero(), Error::<T>::InvalidAmount);
+		ensure!(
+			expires_at >= frame_system::Pallet::<T>::current_block_number(),
+			Error::<T>::InvalidOrderExpiry
+		);
+		// orders can only be placed on pools having a price, that is of two assets, selling either
+		// of them as the equality of pairs ignores their orientation
+		ensure!(Pallet::<T>::currency_pair(pool_id)? == pair, Error::<T>::PairMismatch);
+		ensure!(
+			trigger.source == PriceSource::Spot || TWAPState::<T>::contains_key(pool_id),
+			Error::<T>::TwapNotEnabled
+		);
+		T::Assets::transfer(
+			pair.quote,
+			who,
+			&Self::account_id(),
+			amount.safe_add(&keeper_reward)?,
+			false,
+		)?;
+		OrderCount::<T>::try_mutate(|order_count| -> Result<T::OrderId, DispatchError> {
+			let order_id = *order_count;
+			Orders::<T>::insert(
+				order_id,
+				Order {
+					owner: who.clone(),
+					pool_id,
+					pair,
+					amount,
+					min_receive,
+					trigger,
+					keeper_reward,
+					expires_at,
+				},
+			);
+			*order_count = order_id.safe_add(&T::OrderId::one())?;
+			Ok(order_id)
+		})
+	}
+
+	/// Current price of the pool in base asset per quote asset of the order.
+	fn price(order: &OrderOf<T>) -> Result<Rate, DispatchError> {
+		let swapped = Pallet::<T>::currency_pair(order.pool_id)?.base != order.pair.base;
+		match order.trigger.source {
+			PriceSource::Spot => Pallet::<T>::do_get_exchange_rate(
+				order.pool_id,
+				if swapped { PriceRatio::Swapped } else { PriceRatio::NotSwapped },
+			),
+			PriceSource::Twap => {
+				let twap = TWAPState::<T>::get(order.pool_id).ok_or(Error::<T>::TwapNotEnabled)?;
+				Ok(if swapped { twap.quote_twap } else { twap.base_twap })
+			},
+		}
+	}
+
+	pub(crate) fn is_triggered(order: &OrderOf<T>) -> Result<bool, DispatchError> {
+		let price = Self::price(order)?;
+		Ok(match order.trigger.kind {
+			OrderKind::Limit => price >= order.trigger.price,
+			OrderKind::Stop => price <= order.trigger.price,
+		})
+	}
+
+	pub(crate) fn is_expired(order: &OrderOf<T>) -> bool {
+		order.expires_at < frame_system::Pallet::<T>::current_block_number()
+	}
+
+	/// Sell the escrowed funds of a triggered order, the keeper reward being paid to `keeper`.
+	///
+	/// Returns the amount of base asset received by the owner of the order.
+	#[transactional]
+	pub(crate) fn fill(
+		order_id: T::OrderId,
+		order: &OrderOf<T>,
+		keeper: &T::AccountId,
+	) -> Result<T::Balance, DispatchError> {
+		ensure!(!Self::is_expired(order), Error::<T>::InvalidOrderExpiry);
+		ensure!(Self::is_triggered(order)?, Error::<T>::OrderNotTriggered);
+		let account = Self::account_id();
+		let base_amount = <Pallet<T> as Amm>::exchange(
+			&account,
+			order.pool_id,
+			order.pair,
+			order.amount,
+			order.min_receive,
+			false,
+		)?;
+		T::Assets::transfer(order.pair.base, &account, &order.owner, base_amount, false)?;
+		T::Assets::transfer(order.pair.quote, &account, keeper, order.keeper_reward, false)?;
+		Orders::<T>::remove(order_id);
+		Pallet::<T>::deposit_event(Event::<T>::OrderFilled {
+			order_id,
+			pool_id: order.pool_id,
+			owner: order.owner.clone(),
+			quote_amount: order.amount,
+			base_amount,
+		});
+		Ok(base_amount)
+	}
+
+	/// Return the escrowed funds of an order to its owner, the keeper reward being paid to
+	/// `keeper`.
+	#[transactional]
+	pub(crate) fn close(
+		order_id: T::OrderId,
+		order: &OrderOf<T>,
+		keeper: &T::AccountId,
+	) -> Result<(), DispatchError> {
+		let account = Self::account_id();
+		T::Assets::transfer(order.pair.quote, &account, &order.owner, order.amount, false)?;
+		T::Assets::transfer(order.pair.quote, &account, keeper, order.keeper_reward, false)?;
+		Orders::<T>::remove(order_id);
+		Ok(())
+	}
+
+	/// Fill or expire the order if possible, on behalf of its owner.
+	///
+	/// Orders neither triggered nor expired, or failing to be filled, keep resting.
+	pub(crate) fn process(order_id: T::OrderId, order: &OrderOf<T>) {
+		if Self::is_expired(order) {
+			if Self::close(order_id, order, &order.owner).is_ok() {
+				Pallet::<T>::deposit_event(Event::<T>::OrderExpired {
+					order_id,
+					pool_id: order.pool_id,
+					owner: order.owner.clone(),
+				});
+			}
+		} else if matches!(Self::is_triggered(order), Ok(true)) {
+			let _ = Self::fill(order_id, order, &order.owner);
+		}
+	}
+}
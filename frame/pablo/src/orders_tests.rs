@@ -0,0 +1,361 @@
+#[cfg(test)]
+use crate::{
+	common_test_functions::assert_has_event,
+	mock,
+	mock::{Pablo, *},
+	orders::OrderBook,
+	types::{OrderKind, OrderTrigger, PriceSource},
+	Error, PoolInitConfiguration,
+};
+use composable_traits::defi::{CurrencyPair, Rate};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungibles::{Inspect, Mutate},
+		Get, Hooks,
+	},
+};
+use sp_runtime::{traits::One, FixedPointNumber, Permill};
+
+const UNIT: Balance = 1_000_000_000_000;
+const REWARD: Balance = UNIT;
+
+/// Constant product pool of 1000 BTC and 1000 USDT, so that the price starts at one.
+fn create_pool() -> PoolId {
+	System::set_block_number(1);
+	let pool_id = Pablo::do_create_pool(PoolInitConfiguration::ConstantProduct {
+		owner: ALICE,
+		pair: CurrencyPair::new(BTC, USDT),
+		fee: Permill::from_percent(1),
+		base_weight: Permill::from_percent(50),
+	})
+	.expect("pool creation failed");
+	assert_ok!(Tokens::mint_into(BTC, &ALICE, 1_000 * UNIT));
+	assert_ok!(Tokens::mint_into(USDT, &ALICE, 1_000 * UNIT));
+	assert_ok!(Pablo::add_liquidity(
+		Origin::signed(ALICE),
+		pool_id,
+		1_000 * UNIT,
+		1_000 * UNIT,
+		0,
+		false
+	));
+	pool_id
+}
+
+fn trigger(kind: OrderKind, source: PriceSource, price: Rate) -> OrderTrigger {
+	OrderTrigger { kind, source, price }
+}
+
+/// Order of `BOB` selling 100 USDT for BTC, expiring at block 20.
+fn place_order(pool_id: PoolId, trigger: OrderTrigger, min_receive: Balance) -> u128 {
+	assert_ok!(Tokens::mint_into(USDT, &BOB, 100 * UNIT + REWARD));
+	assert_ok!(Pablo::place_order(
+		Origin::signed(BOB),
+		pool_id,
+		CurrencyPair::new(BTC, USDT),
+		100 * UNIT,
+		min_receive,
+		trigger,
+		REWARD,
+		20
+	));
+	Pablo::order_count() - 1
+}
+
+/// Sell `amount` of `asset_id` to move the price of the pool.
+fn sell(pool_id: PoolId, asset_id: AssetId, amount: Balance) {
+	assert_ok!(Tokens::mint_into(asset_id, &ALICE, amount));
+	assert_ok!(Pablo::sell(Origin::signed(ALICE), pool_id, asset_id, amount, 0, false));
+}
+
+fn run_to_block(n: BlockNumber) {
+	for b in (System::block_number() + 1)..=n {
+		Timestamp::set_timestamp(MILLISECS_PER_BLOCK * b);
+		System::set_block_number(b);
+		Pablo::on_initialize(b);
+	}
+}
+
+#[test]
+fn place_order_escrows_funds_until_cancelled() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let order_id = place_order(
+			pool_id,
+			trigger(OrderKind::Limit, PriceSource::Spot, Rate::saturating_from_rational(11, 10)),
+			0,
+		);
+		assert_has_event::<Test, _>(|e| {
+			matches!(
+				e.event,
+				mock::Event::Pablo(crate::Event::OrderPlaced { order_id: placed, owner: BOB, .. })
+					if placed == order_id
+			)
+		});
+		assert_eq!(Tokens::balance(USDT, &BOB), 0);
+		assert_eq!(Tokens::balance(USDT, &OrderBook::<Test>::account_id()), 100 * UNIT + REWARD);
+
+		assert_noop!(
+			Pablo::cancel_order(Origin::signed(CHARLIE), order_id),
+			Error::<Test>::NotOrderOwner
+		);
+		assert_ok!(Pablo::cancel_order(Origin::signed(BOB), order_id));
+		assert_eq!(Tokens::balance(USDT, &BOB), 100 * UNIT + REWARD);
+		assert_eq!(Tokens::balance(USDT, &OrderBook::<Test>::account_id()), 0);
+		assert!(Pablo::order(order_id).is_none());
+		assert_noop!(
+			Pablo::cancel_order(Origin::signed(BOB), order_id),
+			Error::<Test>::OrderNotFound
+		);
+	});
+}
+
+#[test]
+fn place_order_with_invalid_parameters() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		System::set_block_number(10);
+		assert_ok!(Tokens::mint_into(USDT, &BOB, 100 * UNIT));
+		let place = |pair, amount, trigger, expires_at| {
+			Pablo::place_order(
+				Origin::signed(BOB),
+				pool_id,
+				pair,
+				amount,
+				0,
+				trigger,
+				0,
+				expires_at,
+			)
+		};
+		let spot = trigger(OrderKind::Limit, PriceSource::Spot, Rate::one());
+		let pair = CurrencyPair::new(BTC, USDT);
+		assert_noop!(place(pair, 0, spot, 20), Error::<Test>::InvalidAmount);
+		assert_noop!(place(pair, UNIT, spot, 9), Error::<Test>::InvalidOrderExpiry);
+		assert_noop!(
+			place(CurrencyPair::new(BTC, USDC), UNIT, spot, 20),
+			Error::<Test>::PairMismatch
+		);
+		assert_noop!(
+			place(pair, UNIT, trigger(OrderKind::Limit, PriceSource::Twap, Rate::one()), 20),
+			Error::<Test>::TwapNotEnabled
+		);
+	});
+}
+
+#[test]
+fn limit_order_is_filled_once_the_price_rises_above_the_trigger() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let order_id = place_order(
+			pool_id,
+			trigger(OrderKind::Limit, PriceSource::Spot, Rate::saturating_from_rational(11, 10)),
+			0,
+		);
+		run_to_block(2);
+		assert!(Pablo::order(order_id).is_some());
+		assert_noop!(
+			Pablo::fill_order(Origin::signed(CHARLIE), order_id),
+			Error::<Test>::OrderNotTriggered
+		);
+
+		// about 1.2 BTC per USDT
+		sell(pool_id, BTC, 100 * UNIT);
+		run_to_block(3);
+		assert!(Pablo::order(order_id).is_none());
+		let received = Tokens::balance(BTC, &BOB);
+		assert!(received > 100 * UNIT);
+		// the keeper reward is returned to the owner when filled by the chain
+		assert_eq!(Tokens::balance(USDT, &BOB), REWARD);
+		assert_has_event::<Test, _>(|e| {
+			matches!(
+				e.event,
+				mock::Event::Pablo(crate::Event::OrderFilled { order_id: filled, base_amount, .. })
+					if filled == order_id && base_amount == received
+			)
+		});
+	});
+}
+
+#[test]
+fn stop_order_is_filled_by_keeper_for_the_reward() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let order_id = place_order(
+			pool_id,
+			trigger(OrderKind::Stop, PriceSource::Spot, Rate::saturating_from_rational(95, 100)),
+			0,
+		);
+		// about 0.83 BTC per USDT
+		sell(pool_id, USDT, 100 * UNIT);
+		assert_ok!(Pablo::fill_order(Origin::signed(CHARLIE), order_id));
+		assert!(Pablo::order(order_id).is_none());
+		assert_eq!(Tokens::balance(USDT, &CHARLIE), REWARD);
+		assert_eq!(Tokens::balance(USDT, &BOB), 0);
+		let received = Tokens::balance(BTC, &BOB);
+		assert!(received > 0 && received < 83 * UNIT);
+		assert_eq!(Tokens::balance(USDT, &OrderBook::<Test>::account_id()), 0);
+		assert_eq!(Tokens::balance(BTC, &OrderBook::<Test>::account_id()), 0);
+	});
+}
+
+#[test]
+fn triggered_order_rests_until_minimum_is_received() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let order_id = place_order(
+			pool_id,
+			trigger(OrderKind::Limit, PriceSource::Spot, Rate::one()),
+			100 * UNIT,
+		);
+		sell(pool_id, BTC, 10 * UNIT);
+		run_to_block(2);
+		assert!(Pablo::order(order_id).is_some());
+		assert_noop!(
+			Pablo::fill_order(Origin::signed(CHARLIE), order_id),
+			Error::<Test>::CannotRespectMinimumRequested
+		);
+
+		sell(pool_id, BTC, 100 * UNIT);
+		run_to_block(3);
+		assert!(Pablo::order(order_id).is_none());
+		assert!(Tokens::balance(BTC, &BOB) >= 100 * UNIT);
+	});
+}
+
+#[test]
+fn expired_orders_are_returned() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let never = trigger(OrderKind::Stop, PriceSource::Spot, Rate::from_inner(0));
+		let first = place_order(pool_id, never, 0);
+		let second = place_order(pool_id, never, 0);
+
+		run_to_block(20);
+		assert!(Pablo::order(first).is_some());
+
+		// the keeper reward is paid to the account returning an expired order
+		System::set_block_number(21);
+		assert_ok!(Pablo::fill_order(Origin::signed(CHARLIE), first));
+		assert_eq!(Tokens::balance(USDT, &CHARLIE), REWARD);
+		assert_eq!(Tokens::balance(USDT, &BOB), 100 * UNIT);
+
+		Pablo::on_initialize(21);
+		assert!(Pablo::order(second).is_none());
+		assert_eq!(Tokens::balance(USDT, &BOB), 2 * 100 * UNIT + REWARD);
+		assert_has_event::<Test, _>(|e| {
+			matches!(
+				e.event,
+				mock::Event::Pablo(crate::Event::OrderExpired { order_id, .. }) if order_id == second
+			)
+		});
+	});
+}
+
+#[test]
+fn only_the_orders_processed_in_the_block_are_charged() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		let never = trigger(OrderKind::Stop, PriceSource::Spot, Rate::from_inner(0));
+		for _ in 0..MaxOrdersPerBlock::get() + 4 {
+			place_order(pool_id, never, 0);
+		}
+
+		// the first block processes a full chunk, the second the remaining orders
+		let full_chunk = Pablo::on_initialize(2);
+		let remaining = Pablo::on_initialize(3);
+		assert!(remaining < full_chunk);
+	});
+}
+
+#[test]
+fn twap_order_is_filled_once_the_average_price_crosses_the_trigger() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		System::set_block_number(0);
+		assert_ok!(Pablo::enable_twap(Origin::root(), pool_id));
+		run_to_block(1);
+		let order_id = place_order(
+			pool_id,
+			trigger(OrderKind::Limit, PriceSource::Twap, Rate::saturating_from_rational(11, 10)),
+			0,
+		);
+		sell(pool_id, BTC, 100 * UNIT);
+
+		// the spot price crossed the trigger but not yet the average price
+		run_to_block(2);
+		assert!(Pablo::order(order_id).is_some());
+
+		run_to_block(TWAP_INTERVAL + 1);
+		assert!(Pablo::order(order_id).is_none());
+		assert!(Tokens::balance(BTC, &BOB) > 100 * UNIT);
+	});
+}
+
+#[test]
+fn order_selling_the_base_asset_of_the_pool_is_filled() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+		assert_ok!(Tokens::mint_into(BTC, &BOB, 100 * UNIT + REWARD));
+		assert_ok!(Pablo::place_order(
+			Origin::signed(BOB),
+			pool_id,
+			CurrencyPair::new(USDT, BTC),
+			100 * UNIT,
+			0,
+			trigger(OrderKind::Limit, PriceSource::Spot, Rate::saturating_from_rational(11, 10)),
+			REWARD,
+			20
+		));
+		let order_id = Pablo::order_count() - 1;
+		assert_eq!(Tokens::balance(BTC, &OrderBook::<Test>::account_id()), 100 * UNIT + REWARD);
+
+		// about 1.2 USDT per BTC
+		sell(pool_id, USDT, 100 * UNIT);
+		assert_ok!(Pablo::fill_order(Origin::signed(CHARLIE), order_id));
+		assert!(Tokens::balance(USDT, &BOB) > 100 * UNIT);
+		assert_eq!(Tokens::balance(BTC, &CHARLIE), REWARD);
+	});
+}
+
+#[test]
+fn spot_price_of_weighted_pools_accounts_for_the_weights() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = Pablo::do_create_pool(PoolInitConfiguration::ConstantProduct {
+			owner: ALICE,
+			pair: CurrencyPair::new(BTC, USDT),
+			fee: Permill::zero(),
+			base_weight: Permill::from_percent(80),
+		})
+		.expect("pool creation failed");
+		assert_ok!(Tokens::mint_into(BTC, &ALICE, 4_000 * UNIT));
+		assert_ok!(Tokens::mint_into(USDT, &ALICE, 1_000 * UNIT));
+		assert_ok!(Pablo::add_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			4_000 * UNIT,
+			1_000 * UNIT,
+			0,
+			false
+		));
+
+		// the reserves are four BTC per USDT, the weights bring the price back to one
+		let order_id = place_order(
+			pool_id,
+			trigger(OrderKind::Limit, PriceSource::Spot, Rate::saturating_from_rational(2, 1)),
+			0,
+		);
+		assert_noop!(
+			Pablo::fill_order(Origin::signed(CHARLIE), order_id),
+			Error::<Test>::OrderNotTriggered
+		);
+		let order_id = place_order(
+			pool_id,
+			trigger(OrderKind::Limit, PriceSource::Spot, Rate::saturating_from_rational(99, 100)),
+			0,
+		);
+		assert_ok!(Pablo::fill_order(Origin::signed(CHARLIE), order_id));
+	});
+}
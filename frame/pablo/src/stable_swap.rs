@@ -92,6 +92,32 @@ impl<T: Config> StableSwap<T> {
 		Ok(exchange_value)
 	}
 
+	/// Amount of the other asset of the pair required to obtain `amount` of `asset_id`, which
+	/// can be either asset of the pair. (Note this does not include fees.)
+	pub fn get_amount_in(
+		pool: &StableSwapPoolInfo<T::AccountId, T::AssetId>,
+		pool_account: &T::AccountId,
+		asset_id: T::AssetId,
+		amount: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		ensure!(pool.pair.contains(asset_id), Error::<T>::InvalidAsset);
+		let pair = if asset_id == pool.pair.base { pool.pair } else { pool.pair.swap() };
+		let balance_out = T::Assets::balance(pair.base, pool_account);
+		let balance_in = T::Assets::balance(pair.quote, pool_account);
+		ensure!(!balance_out.is_zero() && !balance_in.is_zero(), Error::<T>::NotEnoughLiquidity);
+		ensure!(amount < balance_out, Error::<T>::NotEnoughLiquidity);
+		let amp = T::Convert::convert(pool.amplification_coefficient.into());
+		let d = Self::get_invariant(balance_out, balance_in, amp)?;
+		let new_balance_out = balance_out.safe_sub(&amount)?;
+		// the invariant is symmetric, the balance of either asset is computed the same way
+		let new_balance_in = T::Convert::convert(compute_base(
+			T::Convert::convert(new_balance_out),
+			T::Convert::convert(amp),
+			T::Convert::convert(d),
+		)?);
+		Ok(new_balance_in.safe_sub(&balance_in)?)
+	}
+
 	fn abs_difference(
 		new_balance: T::Balance,
 		old_balance: T::Balance,
@@ -1,7 +1,7 @@
-use codec::{Decode, Encode};
-use composable_traits::defi::Rate;
+use codec::{Decode, Encode, MaxEncodedLen};
+use composable_traits::defi::{CurrencyPair, Rate};
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{FixedU128, RuntimeDebug};
 
 #[derive(Encode, Decode, TypeInfo, Clone, Default, PartialEq, Eq, RuntimeDebug)]
 pub struct TimeWeightedAveragePrice<Timestamp, Balance> {
@@ -17,3 +17,85 @@ pub struct PriceCumulative<Timestamp, Balance> {
 	pub base_price_cumulative: Balance,
 	pub quote_price_cumulative: Balance,
 }
+
+/// Current state of a concentrated liquidity pool.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct ConcentratedLiquidityState {
+	/// Square root of the price, in quote per base.
+	pub sqrt_price: FixedU128,
+	/// Greatest tick whose price is lower or equal to the current price.
+	pub tick: i32,
+	/// Liquidity of the positions whose range contains the current price.
+	pub liquidity: u128,
+	/// Fees earned per unit of liquidity since the creation of the pool, scaled by `10^18`.
+	/// Wraps around on overflow, only differences between two values are meaningful.
+	pub fee_growth_global_base: u128,
+	pub fee_growth_global_quote: u128,
+}
+
+/// Liquidity referenced by positions bounded by a tick.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct TickInfo {
+	/// Total liquidity of the positions bounded by the tick, the tick is dropped once zero.
+	pub liquidity_gross: u128,
+	/// Liquidity added when the price crosses the tick upward, removed when crossing downward.
+	pub liquidity_net: i128,
+	/// Fee growth on the other side of the tick relative to the current tick.
+	pub fee_growth_outside_base: u128,
+	pub fee_growth_outside_quote: u128,
+}
+
+/// Liquidity provided over a range of ticks, owned by the holder of the matching financial NFT.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct Position {
+	pub lower_tick: i32,
+	pub upper_tick: i32,
+	pub liquidity: u128,
+	/// Fee growth inside the range when the fees were last collected.
+	pub fee_growth_inside_base_last: u128,
+	pub fee_growth_inside_quote_last: u128,
+}
+
+/// Direction in which the price must cross the trigger price for an order to be filled.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum OrderKind {
+	/// Filled once the price is at or above the trigger price.
+	Limit,
+	/// Filled once the price is at or below the trigger price.
+	Stop,
+}
+
+/// Price of the pool compared to the trigger price of an order.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum PriceSource {
+	/// Ratio of the reserves of the pool.
+	Spot,
+	/// Time weighted average price, the TWAP of the pool must be enabled.
+	Twap,
+}
+
+/// Condition under which an order is filled.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct OrderTrigger {
+	pub kind: OrderKind,
+	pub source: PriceSource,
+	/// Price in base asset per quote asset of the order.
+	pub price: Rate,
+}
+
+/// Resting order selling `pair.quote` for `pair.base` once its trigger condition is met.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Order<AccountId, PoolId, AssetId, Balance, BlockNumber> {
+	pub owner: AccountId,
+	pub pool_id: PoolId,
+	pub pair: CurrencyPair<AssetId>,
+	/// Amount of quote asset escrowed to be sold.
+	pub amount: Balance,
+	/// Minimum amount of base asset received when filled.
+	pub min_receive: Balance,
+	pub trigger: OrderTrigger,
+	/// Amount of quote asset escrowed along the order, paid to the keeper filling it.
+	pub keeper_reward: Balance,
+	/// Last block at which the order can be filled.
+	pub expires_at: BlockNumber,
+}
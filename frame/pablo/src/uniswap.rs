@@ -93,6 +93,33 @@ impl<T: Config> Uniswap<T> {
 		Ok(T::Convert::convert(exchange_amount))
 	}
 
+	/// Amount of the other asset of the pair required to obtain `amount` of `asset_id`, which
+	/// can be either asset of the pair. (Note this does not include fees.)
+	pub(crate) fn get_amount_in(
+		pool: &ConstantProductPoolInfo<T::AccountId, T::AssetId>,
+		pool_account: &T::AccountId,
+		asset_id: T::AssetId,
+		amount: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		ensure!(pool.pair.contains(asset_id), Error::<T>::InvalidAsset);
+		let (pair, weight_in, weight_out) = if asset_id == pool.pair.base {
+			(pool.pair, pool.quote_weight, pool.base_weight)
+		} else {
+			(pool.pair.swap(), pool.base_weight, pool.quote_weight)
+		};
+		let balance_in = T::Convert::convert(T::Assets::balance(pair.quote, pool_account));
+		let balance_out = T::Convert::convert(T::Assets::balance(pair.base, pool_account));
+		ensure!(!balance_in.is_zero() && !balance_out.is_zero(), Error::<T>::NotEnoughLiquidity);
+		let amount_in = compute_in_given_out(
+			weight_in,
+			weight_out,
+			balance_in,
+			balance_out,
+			T::Convert::convert(amount),
+		)?;
+		Ok(T::Convert::convert(amount_in))
+	}
+
 	pub(crate) fn add_liquidity(
 		who: &T::AccountId,
 		pool: ConstantProductPoolInfo<T::AccountId, T::AssetId>,
@@ -16,6 +16,16 @@ pub trait WeightInfo {
 	fn buy() -> Weight;
 	fn sell() -> Weight;
 	fn swap() -> Weight;
+	fn swap_concentrated_liquidity(s: u32) -> Weight;
+	fn open_position() -> Weight;
+	fn close_position() -> Weight;
+	fn collect_fees() -> Weight;
+	fn add_liquidity_assets() -> Weight;
+	fn remove_liquidity_assets() -> Weight;
+	fn remove_liquidity_single_asset() -> Weight;
+	fn place_order() -> Weight;
+	fn cancel_order() -> Weight;
+	fn fill_order() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -29,4 +39,14 @@ impl WeightInfo for () {
 	fn buy() -> Weight {10_000}
 	fn sell() -> Weight {10_000}
 	fn swap() -> Weight {10_000}
+	fn swap_concentrated_liquidity(s: u32) -> Weight {10_000 + 10_000 * s as Weight}
+	fn open_position() -> Weight {10_000}
+	fn close_position() -> Weight {10_000}
+	fn collect_fees() -> Weight {10_000}
+	fn add_liquidity_assets() -> Weight {10_000}
+	fn remove_liquidity_assets() -> Weight {10_000}
+	fn remove_liquidity_single_asset() -> Weight {10_000}
+	fn place_order() -> Weight {10_000}
+	fn cancel_order() -> Weight {10_000}
+	fn fill_order() -> Weight {10_000}
 }
@@ -1,19 +1,17 @@
 //! Benchmarks
 use crate::{validation::ValidSplitRatio, *};
 
-use composable_support::{abstractions::utils::increment::Increment, validation::Validated};
+use composable_support::validation::Validated;
 use composable_traits::{
 	staking::{
-		lock::{Lock, LockConfig},
-		Reductions, Reward, RewardConfig, RewardPoolConfiguration,
-		RewardPoolConfiguration::RewardRateBasedIncentive,
-		RewardRate, RewardUpdate, Stake,
+		lock::LockConfig, ProtocolStaking, RewardConfig, RewardPoolConfiguration,
+		RewardPoolConfiguration::RewardRateBasedIncentive, RewardRate, RewardUpdate,
 	},
 	time::{DurationSeconds, ONE_HOUR, ONE_MINUTE},
 };
 use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_support::{
-	traits::{fungibles::Mutate, Get, TryCollect, UnixTime},
+	traits::{fungibles::Mutate, Get, TryCollect},
 	BoundedBTreeMap,
 };
 use frame_system::{EventRecord, RawOrigin};
@@ -75,6 +73,21 @@ fn reward_config<T: Config>(
 		.unwrap()
 }
 
+fn add_rewards<T: Config>(pool_id: T::RewardPoolId, amount: T::Balance) {
+	let pool_account = Pallet::<T>::pool_account_id(&pool_id);
+	RewardPools::<T>::mutate(pool_id, |pool| {
+		if let Some(pool) = pool {
+			let mut rewards = pool.rewards.clone().into_inner();
+			for (asset_id, reward) in rewards.iter_mut() {
+				reward.total_rewards = reward.total_rewards + amount;
+				<T::Assets as Mutate<T::AccountId>>::mint_into(*asset_id, &pool_account, amount)
+					.expect("an asset minting expected");
+			}
+			pool.rewards = rewards.try_into().expect("no rewards were added");
+		}
+	});
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::Event = generic_event.into();
@@ -117,7 +130,9 @@ benchmarks! {
 		<T::Assets as Mutate<T::AccountId>>::mint_into(asset_id, &staker, amount * 2.into())?;
 	}: _(RawOrigin::Signed(staker.clone()), pool_id, amount, duration_preset)
 	verify {
-		assert_last_event::<T>(Event::Staked { pool_id, owner: staker, amount, duration_preset, position_id, keep_alive }.into());
+		let fnft_collection_id = RewardPools::<T>::get(pool_id).expect("rewards_pool expected").financial_nft_asset_id;
+		let fnft_instance_id = Stakes::<T>::get(position_id).expect("stake expected").fnft_instance_id;
+		assert_last_event::<T>(Event::Staked { pool_id, owner: staker, amount, duration_preset, position_id, fnft_collection_id, fnft_instance_id, keep_alive }.into());
 	}
 
 	extend {
@@ -161,49 +176,43 @@ benchmarks! {
 		frame_system::Pallet::<T>::set_block_number(1.into());
 		let user: T::AccountId = account("user", 0, 0);
 		let _res = Pallet::<T>::create_reward_pool(RawOrigin::Root.into(), get_reward_pool::<T>(user.clone(), r));
-		let _res = StakeCount::<T>::increment();
-		let new_stake = Stake::<T::AccountId, T::RewardPoolId, T::Balance, Reductions<T::AssetId, T::Balance, T::MaxRewardConfigsPerPool>> {
-			owner: user.clone(),
-			reward_pool_id: 1_u16.into(),
-			stake: 1_000_000_000_000_000_u128.into(),
-			share: 1_000_000_000_000_000_u128.into(),
-			reductions: Reductions::<_,_,_>::new(),
-			lock: Lock {
-				started_at: 10000_u64,
-				duration: 10000000_u64,
-				unlock_penalty: Perbill::from_percent(2)
-			}
-		};
+		<T::Assets as Mutate<T::AccountId>>::mint_into(100.into(), &user, 1_000_000_000_000_000_u128.into())?;
+		<Pallet<T>>::stake(RawOrigin::Signed(user.clone()).into(), 1_u16.into(), 1_000_000_000_000_000_u128.into(), ONE_HOUR)?;
 		let position_id : T::PositionId = 1_u128.into();
-		Stakes::<T>::insert(position_id, new_stake);
 		let ratio =  Permill::from_rational(1_u32,7_u32);
 		let validated_ratio = Validated::<Permill, ValidSplitRatio>::new(ratio).unwrap();
 
 	}: _(RawOrigin::Signed(user), position_id, validated_ratio)
 
-	reward_accumulation_hook_reward_update_calculation {
-		let now = T::UnixTime::now().as_secs();
-
-		let reward = Reward {
-			asset_id: 1_u128.into(),
-			total_rewards: 0_u128.into(),
-			total_dilution_adjustment: 0.into(),
-			max_rewards: 1_000_000.into(),
-			reward_rate: RewardRate::per_second(10_000),
-			last_updated_timestamp: now,
-			claimed_rewards: 0_u128.into()
-		};
-
-		let seconds_per_block = 12;
-
-		let now = now + seconds_per_block;
-	}: {
-		let reward = Pallet::<T>::reward_accumulation_hook_reward_update_calculation(1.into(), reward, now);
-	}
+	claim {
+		let r in 1 .. T::MaxRewardConfigsPerPool::get();
+		let asset_id = 100.into();
+		let pool_id = 1_u16.into();
+		let amount = 100_500_u128.into();
+		let position_id = 1_u128.into();
+		let staker: T::AccountId = whitelisted_caller();
+		let pool_owner: T::AccountId = account("owner", 0, 0);
+		<Pallet<T>>::create_reward_pool(RawOrigin::Root.into(), get_reward_pool::<T>(pool_owner, r))?;
+		<T::Assets as Mutate<T::AccountId>>::mint_into(asset_id, &staker, amount * 2.into())?;
+		<Pallet<T>>::stake(RawOrigin::Signed(staker.clone()).into(), pool_id, amount, ONE_HOUR)?;
+		add_rewards::<T>(pool_id, 100_u128.into());
+	}: _(RawOrigin::Signed(staker), position_id, None, None)
 
-	unix_time_now {}: {
-		T::UnixTime::now()
-	}
+	compound {
+		let r in 1 .. T::MaxRewardConfigsPerPool::get() - 1;
+		let asset_id = 100.into();
+		let pool_id = 1_u16.into();
+		let amount = 100_500_u128.into();
+		let position_id = 1_u128.into();
+		let staker: T::AccountId = whitelisted_caller();
+		let pool_owner: T::AccountId = account("owner", 0, 0);
+		<Pallet<T>>::create_reward_pool(RawOrigin::Root.into(), get_reward_pool::<T>(pool_owner.clone(), r))?;
+		<T::Assets as Mutate<T::AccountId>>::mint_into(asset_id, &staker, amount * 2.into())?;
+		<T::Assets as Mutate<T::AccountId>>::mint_into(asset_id, &pool_owner, amount * 2.into())?;
+		<Pallet<T>>::stake(RawOrigin::Signed(staker.clone()).into(), pool_id, amount, ONE_HOUR)?;
+		// rewards the staked asset
+		<Pallet<T> as ProtocolStaking>::transfer_reward(&pool_owner, &pool_id, asset_id, amount)?;
+	}: _(RawOrigin::Signed(staker), position_id)
 
 	update_rewards_pool {
 		let r in 1 .. T::MaxRewardConfigsPerPool::get();
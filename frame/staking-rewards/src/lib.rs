@@ -35,6 +35,7 @@
 
 #[cfg(any(feature = "runtime-benchmarks", test))]
 mod benchmarking;
+pub mod migrations;
 mod prelude;
 #[cfg(test)]
 mod test;
@@ -64,7 +65,7 @@ pub mod pallet {
 		validation::Validated,
 	};
 	use composable_traits::{
-		currency::{BalanceLike, CurrencyFactory},
+		currency::{BalanceLike, CurrencyFactory, RangeId},
 		fnft::{FinancialNft, FinancialNftProtocol},
 		staking::{
 			RewardPoolConfiguration::RewardRateBasedIncentive, RewardRatePeriod,
@@ -83,7 +84,7 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 	use sp_arithmetic::{traits::One, Permill};
 	use sp_runtime::{
-		traits::{AccountIdConversion, BlockNumberProvider},
+		traits::{AccountIdConversion, BlockNumberProvider, Saturating},
 		PerThing, Perbill,
 	};
 	use sp_std::{cmp::max, fmt::Debug, vec, vec::Vec};
@@ -116,6 +117,10 @@ pub mod pallet {
 			/// Position Id of newly created stake.
 			position_id: T::PositionId,
 			keep_alive: bool,
+			/// Collection of the financial NFT minted for the stake.
+			fnft_collection_id: T::AssetId,
+			/// Instance of the financial NFT minted for the stake.
+			fnft_instance_id: T::FinancialNftInstanceId,
 		},
 		StakeAmountExtended {
 			position_id: T::PositionId,
@@ -151,6 +156,26 @@ pub mod pallet {
 		RewardPoolUpdated {
 			pool_id: T::RewardPoolId,
 		},
+		/// Pending rewards of a position were claimed without unstaking it.
+		RewardsClaimed {
+			/// Position Id of the stake.
+			position_id: T::PositionId,
+			/// Owner of the stake.
+			owner: T::AccountId,
+			/// Account the rewards were transferred to.
+			beneficiary: T::AccountId,
+			/// Amount claimed for each reward asset.
+			rewards: Vec<(T::AssetId, T::Balance)>,
+		},
+		/// Pending rewards of a position were reinvested into it.
+		RewardsCompounded {
+			/// Position Id of the stake.
+			position_id: T::PositionId,
+			/// Amount of rewards added to the stake.
+			amount: T::Balance,
+			/// Share added to the position for the reinvested amount.
+			share: T::Balance,
+		},
 	}
 
 	#[pallet::error]
@@ -182,6 +207,16 @@ pub mod pallet {
 		/// Reward asset not found in reward pool.
 		RewardAssetNotFound,
 		BackToTheFuture,
+		/// Only the owner of the stake can claim its rewards.
+		OnlyStakeOwnerCanClaim,
+		/// Only the owner of the stake can compound its rewards.
+		OnlyStakeOwnerCanCompound,
+		/// The account of the stake does not hold the shares issued for it.
+		NotEnoughShareAssets,
+		/// Only the owner of the stake can split it.
+		OnlyStakeOwnerCanSplit,
+		/// The financial NFT of the stake is listed for sale.
+		StakeIsListed,
 	}
 
 	pub(crate) type AssetIdOf<T> = <T as Config>::AssetId;
@@ -240,8 +275,12 @@ pub mod pallet {
 			+ Zero
 			+ One;
 
-		type FinancialNft: nonfungibles::Mutate<AccountIdOf<Self>>
-			+ nonfungibles::Create<AccountIdOf<Self>>
+		type FinancialNft: nonfungibles::Mutate<
+				AccountIdOf<Self>,
+				CollectionId = Self::AssetId,
+				ItemId = Self::FinancialNftInstanceId,
+			> + nonfungibles::Create<AccountIdOf<Self>>
+			+ nonfungibles::InspectEnumerable<AccountIdOf<Self>>
 			+ FinancialNft<AccountIdOf<Self>>;
 
 		/// Is used to create staked asset per `Self::RewardPoolId`
@@ -292,7 +331,7 @@ pub mod pallet {
 	>;
 
 	/// Abstraction over RewardPool type
-	type RewardPoolOf<T> = RewardPool<
+	pub(crate) type RewardPoolOf<T> = RewardPool<
 		<T as frame_system::Config>::AccountId,
 		<T as Config>::AssetId,
 		<T as Config>::Balance,
@@ -306,8 +345,8 @@ pub mod pallet {
 	>;
 
 	/// Abstraction over Stake type
-	type StakeOf<T> = Stake<
-		<T as frame_system::Config>::AccountId,
+	pub(crate) type StakeOf<T> = Stake<
+		<T as Config>::FinancialNftInstanceId,
 		<T as Config>::RewardPoolId,
 		<T as Config>::Balance,
 		Reductions<
@@ -317,11 +356,22 @@ pub mod pallet {
 		>,
 	>;
 
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub (super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::migrate_to_v1::<T>()
+				.saturating_add(crate::migrations::migrate_to_v2::<T>())
+		}
+	}
+
 	#[pallet::storage]
 	#[pallet::getter(fn pool_count)]
 	#[allow(clippy::disallowed_types)]
@@ -344,13 +394,18 @@ pub mod pallet {
 	pub type Stakes<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::PositionId, StakeOf<T>, OptionQuery>;
 
-	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		/// Weight: see `begin_block`
-		fn on_initialize(_: T::BlockNumber) -> Weight {
-			Self::acumulate_rewards_hook()
-		}
-	}
+	/// Position of each financial NFT issued for staking, by financial NFT collection and instance.
+	#[pallet::storage]
+	#[pallet::getter(fn fnft_positions)]
+	pub type FinancialNftPositions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::FinancialNftInstanceId,
+		T::PositionId,
+		OptionQuery,
+	>;
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -440,6 +495,40 @@ pub mod pallet {
 			T::RewardPoolUpdateOrigin::ensure_origin(origin)?;
 			update_rewards_pool::<T>(pool_id, reward_updates)
 		}
+
+		/// Claim the pending rewards of a position without unstaking it, for all the reward assets
+		/// of the pool or only `assets`. The rewards are transferred to `beneficiary`, or to the
+		/// owner of the position if `None`.
+		///
+		/// Emits `RewardsClaimed` event when successful.
+		#[pallet::weight(T::WeightInfo::claim(T::MaxRewardConfigsPerPool::get()))]
+		pub fn claim(
+			origin: OriginFor<T>,
+			position_id: T::PositionId,
+			assets: Option<BoundedVec<T::AssetId, T::MaxRewardConfigsPerPool>>,
+			beneficiary: Option<T::AccountId>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let beneficiary = beneficiary.unwrap_or_else(|| owner.clone());
+			<Self as Staking>::claim(
+				&owner,
+				&position_id,
+				assets.as_ref().map(|assets| assets.as_slice()),
+				&beneficiary,
+			)
+		}
+
+		/// Reinvest the pending rewards of a position paid in the staked asset into the position.
+		/// The reinvested amount is locked with the rest of the stake, until the end of the lock of
+		/// the position, and its share uses the reward multiplier of the lock duration.
+		///
+		/// Emits `RewardsCompounded` event when successful.
+		#[pallet::weight(T::WeightInfo::compound(T::MaxRewardConfigsPerPool::get()))]
+		pub fn compound(origin: OriginFor<T>, position_id: T::PositionId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			<Self as Staking>::compound(&owner, &position_id)?;
+			Ok(())
+		}
 	}
 
 	impl<T: Config> ManageStaking for Pallet<T> {
@@ -475,6 +564,9 @@ pub mod pallet {
 					);
 
 					let pool_id = RewardPoolCount::<T>::increment()?;
+					let share_asset_id =
+						T::CurrencyFactory::create(RangeId::LP_TOKENS, T::Balance::zero())?;
+					let financial_nft_asset_id = Self::create_financial_nft_collection()?;
 
 					let now_seconds = T::UnixTime::now().as_secs();
 
@@ -496,9 +588,10 @@ pub mod pallet {
 							claimed_shares: T::Balance::zero(),
 							end_block,
 							lock,
+							share_asset_id,
+							financial_nft_asset_id,
 						},
 					);
-					// TODO (vim): Create the financial NFT collection for the rewards pool
 					Ok((owner, pool_id, end_block))
 				},
 				_ => Err(Error::<T>::UnimplementedRewardPoolConfiguration),
@@ -514,21 +607,30 @@ pub mod pallet {
 		type Balance = BalanceOf<T>;
 
 		fn collection_asset_ids() -> Vec<Self::AssetId> {
-			// TODO (vim): Following is a dummy value. Store and retrieve from storage
-			[Self::AssetId::from(1000_u128)].into()
+			RewardPools::<T>::iter_values()
+				.map(|pool| pool.financial_nft_asset_id)
+				.collect()
 		}
 
+		/// The staked amount and the shares of the position.
 		fn value_of(
-			_collection: &Self::AssetId,
-			_instance: &Self::ItemId,
+			collection: &Self::AssetId,
+			instance: &Self::ItemId,
 		) -> Vec<(Self::AssetId, Self::Balance)> {
-			// TODO (vim): Following is a dummy value. Store and retrieve from storage
-			[(Self::AssetId::from(1001_u128), Self::Balance::zero())].into()
+			FinancialNftPositions::<T>::get(collection, instance)
+				.and_then(Stakes::<T>::get)
+				.and_then(|stake| {
+					RewardPools::<T>::get(stake.reward_pool_id).map(|pool| {
+						[(pool.asset_id, stake.stake), (pool.share_asset_id, stake.share)].into()
+					})
+				})
+				.unwrap_or_default()
 		}
 	}
 
 	impl<T: Config> Staking for Pallet<T> {
 		type AccountId = T::AccountId;
+		type AssetId = T::AssetId;
 		type RewardPoolId = T::RewardPoolId;
 		type Balance = T::Balance;
 		type PositionId = T::PositionId;
@@ -541,8 +643,10 @@ pub mod pallet {
 			duration_preset: DurationSeconds,
 			keep_alive: bool,
 		) -> Result<Self::PositionId, DispatchError> {
-			let mut rewards_pool =
-				RewardPools::<T>::try_get(pool_id).map_err(|_| Error::<T>::RewardsPoolNotFound)?;
+			let mut rewards_pool = Self::accumulate_pool_rewards(
+				*pool_id,
+				RewardPools::<T>::try_get(pool_id).map_err(|_| Error::<T>::RewardsPoolNotFound)?,
+			);
 
 			let reward_multiplier = Self::reward_multiplier(&rewards_pool, duration_preset)
 				.ok_or(Error::<T>::NoDurationPresetsConfigured)?;
@@ -559,8 +663,14 @@ pub mod pallet {
 			let (rewards, reductions) =
 				Self::compute_rewards_and_reductions(boosted_amount, &rewards_pool)?;
 
+			let position_id = StakeCount::<T>::increment()?;
+			let fnft_collection_id = rewards_pool.financial_nft_asset_id;
+			let fnft_instance_id = Self::mint_financial_nft(&fnft_collection_id, who)?;
+			let fnft_account =
+				T::FinancialNft::asset_account(&fnft_collection_id, &fnft_instance_id);
+
 			let new_position = Stake {
-				owner: who.clone(),
+				fnft_instance_id,
 				reward_pool_id: *pool_id,
 				stake: amount,
 				share: boosted_amount,
@@ -571,31 +681,14 @@ pub mod pallet {
 					unlock_penalty: rewards_pool.lock.unlock_penalty,
 				},
 			};
-			// TODO (vim): Transfer the shares with share asset ID to the Financial NFT account and
-			// lock it. T::Assets::mint_into(rewards_pool.share_asset_id fnft_account, amount)?;
-
 			rewards_pool.total_shares = rewards_pool.total_shares.safe_add(&boosted_amount)?;
 			rewards_pool.rewards = rewards;
 
-			let position_id = StakeCount::<T>::increment()?;
-			// TODO (vim):
-			// 	1. Mint the NFT into the relevant NFT collection mapped to the reward pool
-			//  2. Map and store the nft_id -> position_id
-			// let next_nft_id = T::FinancialNFT::get_next_nft_id(reward_pool.fnft_collection_id)?;
-			// T::FinancialNFT::mint_into(reward_pool.fnft_collection_id, next_nft_id)?;
-			// FnftToPositionId<T>::insert(next_nft_id, position_id);
-			// let fnft_account = T::FinancialNFT::asset_account(reward_pool.fnft_collection_id,
-			// next_nft_id); TODO (vim): transfer the staked amount to the NFT account and lock it
-			// T::Assets::transfer(rewards_pool.asset_id, who, fnft_account, amount)?;
-			T::Assets::transfer(
-				rewards_pool.asset_id,
-				who,
-				&Self::pool_account_id(pool_id),
-				amount,
-				keep_alive,
-			)?;
+			T::Assets::transfer(rewards_pool.asset_id, who, &fnft_account, amount, keep_alive)?;
+			T::Assets::mint_into(rewards_pool.share_asset_id, &fnft_account, boosted_amount)?;
 			RewardPools::<T>::insert(pool_id, rewards_pool);
 			Stakes::<T>::insert(position_id, new_position);
+			FinancialNftPositions::<T>::insert(fnft_collection_id, fnft_instance_id, position_id);
 
 			Self::deposit_event(Event::<T>::Staked {
 				pool_id: *pool_id,
@@ -604,6 +697,8 @@ pub mod pallet {
 				duration_preset,
 				position_id,
 				keep_alive,
+				fnft_collection_id,
+				fnft_instance_id,
 			});
 
 			Ok(position_id)
@@ -617,9 +712,13 @@ pub mod pallet {
 			keep_alive: bool,
 		) -> Result<Self::PositionId, DispatchError> {
 			let mut stake = Stakes::<T>::get(position).ok_or(Error::<T>::StakeNotFound)?;
-			let mut rewards_pool = RewardPools::<T>::try_get(stake.reward_pool_id)
-				.map_err(|_| Error::<T>::RewardsPoolNotFound)?;
-			let reward_multiplier = Perbill::one();
+			let mut rewards_pool = Self::accumulate_pool_rewards(
+				stake.reward_pool_id,
+				RewardPools::<T>::try_get(stake.reward_pool_id)
+					.map_err(|_| Error::<T>::RewardsPoolNotFound)?,
+			);
+			let reward_multiplier = Self::reward_multiplier(&rewards_pool, stake.lock.duration)
+				.ok_or(Error::<T>::NoDurationPresetsConfigured)?;
 
 			ensure!(
 				matches!(
@@ -635,7 +734,7 @@ pub mod pallet {
 				Self::compute_rewards_and_reductions(boosted_amount, &rewards_pool)?;
 			rewards_pool.total_shares = rewards_pool.total_shares.safe_add(&boosted_amount)?;
 			rewards_pool.rewards = rewards;
-			stake.stake = stake.stake.safe_add(&boosted_amount)?;
+			stake.stake = stake.stake.safe_add(&amount)?;
 			stake.share = stake.share.safe_add(&boosted_amount)?;
 			for (asset, additional_inflation) in reductions.iter() {
 				let inflation =
@@ -643,16 +742,9 @@ pub mod pallet {
 				*inflation = inflation.safe_add(additional_inflation)?;
 			}
 
-			// TODO (vim): transfer the staked amount to the NFT account and lock it
-			// TODO (vim): Transfer the shares with share asset ID to the Financial NFT account and
-			// lock it.
-			T::Assets::transfer(
-				rewards_pool.asset_id,
-				who,
-				&Self::pool_account_id(&stake.reward_pool_id),
-				amount,
-				keep_alive,
-			)?;
+			let fnft_account = Self::stake_account(&rewards_pool, &stake);
+			T::Assets::transfer(rewards_pool.asset_id, who, &fnft_account, amount, keep_alive)?;
+			T::Assets::mint_into(rewards_pool.share_asset_id, &fnft_account, boosted_amount)?;
 			RewardPools::<T>::insert(stake.reward_pool_id, rewards_pool);
 			Stakes::<T>::insert(position, stake);
 			Self::deposit_event(Event::<T>::StakeAmountExtended { position_id: position, amount });
@@ -662,46 +754,53 @@ pub mod pallet {
 		#[transactional]
 		fn unstake(who: &Self::AccountId, position_id: &Self::PositionId) -> DispatchResult {
 			let keep_alive = false;
-			let stake = Stakes::<T>::try_get(position_id).map_err(|_| Error::<T>::StakeNotFound)?;
-			ensure!(who == &stake.owner, Error::<T>::OnlyStakeOwnerCanUnstake);
+			let mut stake =
+				Stakes::<T>::try_get(position_id).map_err(|_| Error::<T>::StakeNotFound)?;
 			let early_unlock = stake.lock.started_at.safe_add(&stake.lock.duration)? >=
 				T::UnixTime::now().as_secs();
 			let pool_id = stake.reward_pool_id;
-			let mut rewards_pool =
-				RewardPools::<T>::try_get(pool_id).map_err(|_| Error::<T>::RewardsPoolNotFound)?;
+			let mut rewards_pool = Self::accumulate_pool_rewards(
+				pool_id,
+				RewardPools::<T>::try_get(pool_id).map_err(|_| Error::<T>::RewardsPoolNotFound)?,
+			);
+			ensure!(
+				who == &Self::stake_owner(&rewards_pool, &stake)?,
+				Error::<T>::OnlyStakeOwnerCanUnstake
+			);
+			let fnft_collection_id = rewards_pool.financial_nft_asset_id;
+			let fnft_account = Self::stake_account(&rewards_pool, &stake);
+			ensure!(
+				T::Assets::balance(rewards_pool.share_asset_id, &fnft_account) >= stake.share,
+				Error::<T>::NotEnoughShareAssets
+			);
 
-			let mut inner_rewards = rewards_pool.rewards.into_inner();
-			for (asset_id, reward) in inner_rewards.iter_mut() {
-				let inflation = stake.reductions.get(asset_id).cloned().unwrap_or_else(Zero::zero);
-				let claim = if rewards_pool.total_shares == Zero::zero() {
-					Zero::zero()
-				} else {
-					reward
-						.total_rewards
-						.safe_mul(&stake.share)?
-						.safe_div(&rewards_pool.total_shares)?
-						.safe_sub(&inflation)?
-				};
-				let claim = if early_unlock {
+			let assets = rewards_pool.rewards.keys().cloned().collect::<Vec<_>>();
+			for asset_id in assets {
+				let claim = Self::claim_reward(&mut stake, &mut rewards_pool, asset_id)?;
+				let claim_with_penalty = if early_unlock {
 					(Perbill::one() - stake.lock.unlock_penalty).mul_ceil(claim)
 				} else {
 					claim
 				};
-				let claim = sp_std::cmp::min(
-					claim,
-					reward.total_rewards.safe_sub(&reward.claimed_rewards)?,
-				);
-				reward.claimed_rewards = reward.claimed_rewards.safe_add(&claim)?;
-				T::Assets::transfer(
-					reward.asset_id,
-					&Self::pool_account_id(&pool_id),
-					&stake.owner,
-					claim,
-					keep_alive,
-				)?;
+				// the penalty is left unclaimed in the pool
+				let penalty = claim.safe_sub(&claim_with_penalty)?;
+				if !penalty.is_zero() {
+					let reward = rewards_pool
+						.rewards
+						.get_mut(&asset_id)
+						.ok_or(Error::<T>::RewardAssetNotFound)?;
+					reward.claimed_rewards = reward.claimed_rewards.safe_sub(&penalty)?;
+				}
+				if !claim_with_penalty.is_zero() {
+					T::Assets::transfer(
+						asset_id,
+						&Self::pool_account_id(&pool_id),
+						who,
+						claim_with_penalty,
+						keep_alive,
+					)?;
+				}
 			}
-			rewards_pool.rewards = Rewards::try_from(inner_rewards)
-				.expect("Conversion must work as it's the same data structure; qed;");
 			rewards_pool.claimed_shares = rewards_pool.claimed_shares.safe_add(&stake.share)?;
 
 			let stake_with_penalty = if early_unlock {
@@ -710,19 +809,30 @@ pub mod pallet {
 				stake.stake
 			};
 
-			// TODO (vim): Unlock staked amount on financial NFT account and transfer from that
-			// account to the owner of the NFT
 			T::Assets::transfer(
 				rewards_pool.asset_id,
-				&Self::pool_account_id(&pool_id),
-				&stake.owner,
+				&fnft_account,
+				who,
 				stake_with_penalty,
 				keep_alive,
 			)?;
+			// the penalty goes to the pool
+			let penalty = stake.stake.safe_sub(&stake_with_penalty)?;
+			if !penalty.is_zero() {
+				T::Assets::transfer(
+					rewards_pool.asset_id,
+					&fnft_account,
+					&Self::pool_account_id(&pool_id),
+					penalty,
+					keep_alive,
+				)?;
+			}
 
+			T::Assets::burn_from(rewards_pool.share_asset_id, &fnft_account, stake.share)?;
 			RewardPools::<T>::insert(pool_id, rewards_pool);
 			Stakes::<T>::remove(position_id);
-			// TODO (vim): burn the financial NFT and the shares it holds
+			FinancialNftPositions::<T>::remove(fnft_collection_id, stake.fnft_instance_id);
+			T::FinancialNft::burn(&fnft_collection_id, &stake.fnft_instance_id, Some(who))?;
 
 			Self::deposit_event(Event::<T>::Unstaked {
 				owner: who.clone(),
@@ -734,10 +844,19 @@ pub mod pallet {
 
 		#[transactional]
 		fn split(
-			_who: &Self::AccountId,
+			who: &Self::AccountId,
 			position: &Self::PositionId,
 			ratio: Permill,
 		) -> Result<[Self::PositionId; 2], DispatchError> {
+			let stake = Stakes::<T>::try_get(position).map_err(|_| Error::<T>::StakeNotFound)?;
+			let rewards_pool = RewardPools::<T>::try_get(stake.reward_pool_id)
+				.map_err(|_| Error::<T>::RewardsPoolNotFound)?;
+			ensure!(
+				who == &Self::stake_owner(&rewards_pool, &stake)?,
+				Error::<T>::OnlyStakeOwnerCanSplit
+			);
+			ensure!(!Self::is_listed(&rewards_pool, &stake), Error::<T>::StakeIsListed);
+
 			let mut old_position =
 				Stakes::<T>::try_mutate(position, |old_stake| match old_stake {
 					Some(stake) => {
@@ -764,22 +883,146 @@ pub mod pallet {
 				}
 			}
 
+			let fnft_collection_id = rewards_pool.financial_nft_asset_id;
+			let fnft_instance_id = Self::mint_financial_nft(&fnft_collection_id, who)?;
 			let new_stake = StakeOf::<T> {
+				fnft_instance_id,
 				stake: left_from_one_ratio.mul_floor(old_position.stake),
 				share: left_from_one_ratio.mul_floor(old_position.share),
 				..old_position
 			};
+
+			let old_fnft_account = Self::stake_account(&rewards_pool, &stake);
+			let new_fnft_account = Self::stake_account(&rewards_pool, &new_stake);
+			T::Assets::transfer(
+				rewards_pool.asset_id,
+				&old_fnft_account,
+				&new_fnft_account,
+				new_stake.stake,
+				false,
+			)?;
+			T::Assets::transfer(
+				rewards_pool.share_asset_id,
+				&old_fnft_account,
+				&new_fnft_account,
+				new_stake.share,
+				false,
+			)?;
+
 			let new_position = StakeCount::<T>::increment()?;
 			Stakes::<T>::insert(new_position, new_stake);
-			// TODO (vim):
-			// 	1. Create the new financial NFT for the new position
-			//	2. transfer the split staked amount to the NFT account and lock it
-			//	3. transfer the split share amount to the NFT account and lock it
+			FinancialNftPositions::<T>::insert(fnft_collection_id, fnft_instance_id, new_position);
 			Self::deposit_event(Event::<T>::SplitPosition {
 				positions: vec![*position, new_position],
 			});
 			Ok([*position, new_position])
 		}
+
+		#[transactional]
+		fn claim(
+			who: &Self::AccountId,
+			position: &Self::PositionId,
+			assets: Option<&[Self::AssetId]>,
+			to: &Self::AccountId,
+		) -> DispatchResult {
+			let mut stake =
+				Stakes::<T>::try_get(position).map_err(|_| Error::<T>::StakeNotFound)?;
+			let pool_id = stake.reward_pool_id;
+			let mut rewards_pool = Self::accumulate_pool_rewards(
+				pool_id,
+				RewardPools::<T>::try_get(pool_id).map_err(|_| Error::<T>::RewardsPoolNotFound)?,
+			);
+			ensure!(
+				who == &Self::stake_owner(&rewards_pool, &stake)?,
+				Error::<T>::OnlyStakeOwnerCanClaim
+			);
+			ensure!(!Self::is_listed(&rewards_pool, &stake), Error::<T>::StakeIsListed);
+
+			let assets = match assets {
+				Some(assets) => assets.to_vec(),
+				None => rewards_pool.rewards.keys().cloned().collect(),
+			};
+			let mut rewards = Vec::with_capacity(assets.len());
+			for asset_id in assets {
+				let claim = Self::claim_reward(&mut stake, &mut rewards_pool, asset_id)?;
+				if !claim.is_zero() {
+					T::Assets::transfer(
+						asset_id,
+						&Self::pool_account_id(&pool_id),
+						to,
+						claim,
+						false,
+					)?;
+				}
+				rewards.push((asset_id, claim));
+			}
+
+			RewardPools::<T>::insert(pool_id, rewards_pool);
+			Stakes::<T>::insert(position, stake);
+			Self::deposit_event(Event::<T>::RewardsClaimed {
+				position_id: *position,
+				owner: who.clone(),
+				beneficiary: to.clone(),
+				rewards,
+			});
+			Ok(())
+		}
+
+		#[transactional]
+		fn compound(
+			who: &Self::AccountId,
+			position: &Self::PositionId,
+		) -> Result<Self::Balance, DispatchError> {
+			let mut stake =
+				Stakes::<T>::try_get(position).map_err(|_| Error::<T>::StakeNotFound)?;
+			let pool_id = stake.reward_pool_id;
+			let mut rewards_pool = Self::accumulate_pool_rewards(
+				pool_id,
+				RewardPools::<T>::try_get(pool_id).map_err(|_| Error::<T>::RewardsPoolNotFound)?,
+			);
+			ensure!(
+				who == &Self::stake_owner(&rewards_pool, &stake)?,
+				Error::<T>::OnlyStakeOwnerCanCompound
+			);
+			ensure!(!Self::is_listed(&rewards_pool, &stake), Error::<T>::StakeIsListed);
+
+			let staked_asset_id = rewards_pool.asset_id;
+			let amount = Self::claim_reward(&mut stake, &mut rewards_pool, staked_asset_id)?;
+			let reward_multiplier = Self::reward_multiplier(&rewards_pool, stake.lock.duration)
+				.ok_or(Error::<T>::NoDurationPresetsConfigured)?;
+			let boosted_amount = Self::boosted_amount(reward_multiplier, amount);
+
+			let (rewards, reductions) =
+				Self::compute_rewards_and_reductions(boosted_amount, &rewards_pool)?;
+			rewards_pool.total_shares = rewards_pool.total_shares.safe_add(&boosted_amount)?;
+			rewards_pool.rewards = rewards;
+			stake.stake = stake.stake.safe_add(&amount)?;
+			stake.share = stake.share.safe_add(&boosted_amount)?;
+			for (asset, additional_inflation) in reductions.into_iter() {
+				Self::add_reduction(&mut stake, asset, additional_inflation)?;
+			}
+
+			// the claimed rewards are moved from the pool account to the account of the stake
+			let fnft_account = Self::stake_account(&rewards_pool, &stake);
+			if !amount.is_zero() {
+				T::Assets::transfer(
+					staked_asset_id,
+					&Self::pool_account_id(&pool_id),
+					&fnft_account,
+					amount,
+					false,
+				)?;
+			}
+			T::Assets::mint_into(rewards_pool.share_asset_id, &fnft_account, boosted_amount)?;
+			RewardPools::<T>::insert(pool_id, rewards_pool);
+			Stakes::<T>::insert(position, stake);
+			Self::deposit_event(Event::<T>::RewardsCompounded {
+				position_id: *position,
+				amount,
+				share: boosted_amount,
+			});
+			Ok(amount)
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -787,6 +1030,61 @@ pub mod pallet {
 			T::PalletId::get().into_sub_account_truncating(pool_id)
 		}
 
+		/// The account owning the financial NFT collections of the pools.
+		pub(crate) fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Creates the collection of the financial NFTs issued for the positions of a new pool.
+		pub(crate) fn create_financial_nft_collection() -> Result<T::AssetId, DispatchError> {
+			let collection = T::CurrencyFactory::create(RangeId::TOKENS, T::Balance::zero())?;
+			T::FinancialNft::create_collection(
+				&collection,
+				&Self::account_id(),
+				&Self::account_id(),
+			)?;
+			Ok(collection)
+		}
+
+		/// Mints the financial NFT of a new position into `owner`.
+		pub(crate) fn mint_financial_nft(
+			collection: &T::AssetId,
+			owner: &T::AccountId,
+		) -> Result<T::FinancialNftInstanceId, DispatchError> {
+			let instance = T::FinancialNft::get_next_nft_id(collection)?;
+			T::FinancialNft::mint_into(collection, &instance, owner)?;
+			Ok(instance)
+		}
+
+		/// The owner of a stake is the owner of its financial NFT.
+		pub(crate) fn stake_owner(
+			rewards_pool: &RewardPoolOf<T>,
+			stake: &StakeOf<T>,
+		) -> Result<T::AccountId, DispatchError> {
+			T::FinancialNft::owner(&rewards_pool.financial_nft_asset_id, &stake.fnft_instance_id)
+				.ok_or_else(|| Error::<T>::StakeNotFound.into())
+		}
+
+		/// Whether the financial NFT of a stake is listed for sale, in which case no value can be
+		/// taken out of the stake.
+		pub(crate) fn is_listed(rewards_pool: &RewardPoolOf<T>, stake: &StakeOf<T>) -> bool {
+			T::FinancialNft::is_listed(
+				&rewards_pool.financial_nft_asset_id,
+				&stake.fnft_instance_id,
+			)
+		}
+
+		/// The account of the financial NFT of a stake, holding its staked amount and shares.
+		pub(crate) fn stake_account(
+			rewards_pool: &RewardPoolOf<T>,
+			stake: &StakeOf<T>,
+		) -> T::AccountId {
+			T::FinancialNft::asset_account(
+				&rewards_pool.financial_nft_asset_id,
+				&stake.fnft_instance_id,
+			)
+		}
+
 		pub(crate) fn reward_multiplier(
 			rewards_pool: &RewardPoolOf<T>,
 			duration_preset: DurationSeconds,
@@ -838,7 +1136,56 @@ pub mod pallet {
 			Ok((rewards_btree_map, reductions))
 		}
 
-		pub(crate) fn reward_accumulation_hook_reward_update_calculation(
+		/// Computes the pending reward of `asset_id` for the stake, and books it as claimed in the
+		/// stake reductions and the pool rewards. The reward is not transferred.
+		fn claim_reward(
+			stake: &mut StakeOf<T>,
+			rewards_pool: &mut RewardPoolOf<T>,
+			asset_id: T::AssetId,
+		) -> Result<T::Balance, DispatchError> {
+			let reward =
+				rewards_pool.rewards.get_mut(&asset_id).ok_or(Error::<T>::RewardAssetNotFound)?;
+			let reduction = stake.reductions.get(&asset_id).cloned().unwrap_or_else(Zero::zero);
+			let claim = if rewards_pool.total_shares.is_zero() {
+				Zero::zero()
+			} else {
+				reward
+					.total_rewards
+					.safe_mul(&stake.share)?
+					.safe_div(&rewards_pool.total_shares)?
+					.saturating_sub(reduction)
+			};
+			let claim =
+				sp_std::cmp::min(claim, reward.total_rewards.safe_sub(&reward.claimed_rewards)?);
+			if claim.is_zero() {
+				return Ok(claim)
+			}
+
+			reward.claimed_rewards = reward.claimed_rewards.safe_add(&claim)?;
+			Self::add_reduction(stake, asset_id, claim)?;
+			Ok(claim)
+		}
+
+		/// Increases the reduction of `asset_id` for the stake, which may not have one yet if the
+		/// asset was added to the pool rewards after the stake was created.
+		fn add_reduction(
+			stake: &mut StakeOf<T>,
+			asset_id: T::AssetId,
+			amount: T::Balance,
+		) -> DispatchResult {
+			match stake.reductions.get_mut(&asset_id) {
+				Some(reduction) => *reduction = reduction.safe_add(&amount)?,
+				None => {
+					stake
+						.reductions
+						.try_insert(asset_id, amount)
+						.map_err(|_| Error::<T>::ReductionConfigProblem)?;
+				},
+			}
+			Ok(())
+		}
+
+		pub(crate) fn reward_accumulation_update_calculation(
 			pool_id: T::RewardPoolId,
 			reward: Reward<T::AssetId, T::Balance>,
 			now_seconds: u64,
@@ -865,57 +1212,74 @@ pub mod pallet {
 			}
 		}
 
-		pub(crate) fn acumulate_rewards_hook() -> Weight {
+		/// Accumulates the rewards of the pool from their reward rate, for the time elapsed since
+		/// they were last updated.
+		///
+		/// Pools are not updated on each block. Instead, the rewards of a pool are accumulated
+		/// whenever it is read to change the shares, claim or transfer rewards, so that the rewards
+		/// per share of the pool (`total_rewards / total_shares`) are up to date before the shares
+		/// change.
+		pub(crate) fn accumulate_pool_rewards(
+			pool_id: T::RewardPoolId,
+			reward_pool: RewardPoolOf<T>,
+		) -> RewardPoolOf<T> {
 			let now_seconds = T::UnixTime::now().as_secs();
-			let unix_time_now_weight = T::WeightInfo::unix_time_now();
 
-			let updated_pools = RewardPools::<T>::iter()
+			let updated_rewards = reward_pool
+				.rewards
 				.into_iter()
-				.map(|(pool_id, reward_pool)| {
-					let updated_rewards = reward_pool
-						.rewards
-						.into_iter()
-						.map(|(asset_id, reward)| {
-							(
-								asset_id,
-								Self::reward_accumulation_hook_reward_update_calculation(
-									pool_id,
-									reward,
-									now_seconds,
-								),
-							)
-						})
-						.try_collect()
-						// SAFETY(benluelo): No elements were added to the BTreeMap; the only
-						// operation was `.map()`. This expect call will be unnecessary once this PR
-						// is merged and we update to whatever version it's included in:
-						// https://github.com/paritytech/substrate/pull/11869
-						.expect("no elements were added; qed;");
-
-					(pool_id, RewardPool { rewards: updated_rewards, ..reward_pool })
+				.map(|(asset_id, reward)| {
+					(
+						asset_id,
+						Self::reward_accumulation_update_calculation(pool_id, reward, now_seconds),
+					)
 				})
-				// NOTE(benluelo): As per these docs on `StorageMap::iter`:
-				// https://github.com/paritytech/substrate/blob/cac91f59b9e3fb8fd59842c023f87b4206931993/frame/support/src/storage/types/map.rs,
-				// "If you alter the map while doing this, you'll get undefined results."
-				// hence the double iteration.
-				.collect::<Vec<_>>();
-
-			let mut total_weight = unix_time_now_weight;
-
-			for (pool_id, reward_pool) in updated_pools {
-				// 128 bit platforms don't exist as of writing this so this usize -> u64 cast should
-				// be ok
-				let number_of_rewards_in_pool = reward_pool.rewards.len() as u64;
+				.try_collect()
+				// SAFETY(benluelo): No elements were added to the BTreeMap; the only
+				// operation was `.map()`. This expect call will be unnecessary once this PR
+				// is merged and we update to whatever version it's included in:
+				// https://github.com/paritytech/substrate/pull/11869
+				.expect("no elements were added; qed;");
+
+			RewardPool { rewards: updated_rewards, ..reward_pool }
+		}
+	}
 
-				RewardPools::<T>::insert(pool_id, reward_pool);
+	impl<T: Config> StakingVotingPower for Pallet<T> {
+		type AccountId = T::AccountId;
+		type RewardPoolId = T::RewardPoolId;
+		type Balance = T::Balance;
 
-				total_weight += (number_of_rewards_in_pool * T::WeightInfo::reward_accumulation_hook_reward_update_calculation()) +
-						// NOTE: `StorageMap::iter` does one read per item
-						T::DbWeight::get().reads(1) +
-						T::DbWeight::get().writes(1)
-			}
+		fn voting_power(
+			pool_id: &Self::RewardPoolId,
+			who: &Self::AccountId,
+		) -> Result<Self::Balance, DispatchError> {
+			let rewards_pool =
+				RewardPools::<T>::try_get(pool_id).map_err(|_| Error::<T>::RewardsPoolNotFound)?;
+			T::FinancialNft::owned_in_collection(&rewards_pool.financial_nft_asset_id, who)
+				.try_fold(
+					T::Assets::balance(rewards_pool.share_asset_id, who),
+					|voting_power, instance| {
+						let fnft_account = T::FinancialNft::asset_account(
+							&rewards_pool.financial_nft_asset_id,
+							&instance,
+						);
+						voting_power
+							.safe_add(&T::Assets::balance(
+								rewards_pool.share_asset_id,
+								&fnft_account,
+							))
+							.map_err(Into::into)
+					},
+				)
+		}
 
-			total_weight
+		fn total_voting_power(
+			pool_id: &Self::RewardPoolId,
+		) -> Result<Self::Balance, DispatchError> {
+			let rewards_pool =
+				RewardPools::<T>::try_get(pool_id).map_err(|_| Error::<T>::RewardsPoolNotFound)?;
+			Ok(T::Assets::total_issuance(rewards_pool.share_asset_id))
 		}
 	}
 
@@ -943,6 +1307,7 @@ pub mod pallet {
 			RewardPools::<T>::try_mutate(pool, |reward_pool| {
 				match reward_pool {
 					Some(reward_pool) => {
+						*reward_pool = Self::accumulate_pool_rewards(*pool, reward_pool.clone());
 						match reward_pool.rewards.get_mut(&reward_currency) {
 							Some(mut reward) => {
 								let new_total_reward =
@@ -0,0 +1,219 @@
+use crate::{Config, FinancialNftPositions, Pallet, RewardPools, Stakes};
+use composable_traits::{
+	currency::{CurrencyFactory, RangeId},
+	fnft::FinancialNft,
+	staking::{lock::LockConfig, RewardPool, Stake},
+};
+use frame_support::{
+	storage::with_storage_layer,
+	traits::{
+		fungibles::{Inspect, Mutate, Transfer},
+		Get, GetStorageVersion, StorageVersion,
+	},
+	weights::Weight,
+};
+use parity_scale_codec::{Decode, Encode};
+use sp_runtime::{traits::Zero, DispatchError};
+use sp_std::{cmp::min, collections::btree_map::BTreeMap, vec::Vec};
+
+/// `RewardPool` before the pools issued share assets.
+#[derive(Encode, Decode)]
+pub struct RewardPoolV0<AccountId, AssetId, Balance, BlockNumber, DurationPresets, Rewards> {
+	pub owner: AccountId,
+	pub asset_id: AssetId,
+	pub rewards: Rewards,
+	pub total_shares: Balance,
+	pub claimed_shares: Balance,
+	pub end_block: BlockNumber,
+	pub lock: LockConfig<DurationPresets>,
+}
+
+/// Storage layout before the staking positions were financial NFTs.
+pub mod v1 {
+	use super::*;
+	use crate::AccountIdOf;
+	use composable_traits::staking::{
+		lock::Lock, Reductions, Rewards, StakingDurationToRewardsMultiplierConfig,
+	};
+	use frame_support::Blake2_128Concat;
+
+	/// `RewardPool` before the pools issued financial NFTs for their positions.
+	#[derive(Encode, Decode)]
+	pub struct RewardPoolV1<AccountId, AssetId, Balance, BlockNumber, DurationPresets, Rewards> {
+		pub owner: AccountId,
+		pub asset_id: AssetId,
+		pub rewards: Rewards,
+		pub total_shares: Balance,
+		pub claimed_shares: Balance,
+		pub end_block: BlockNumber,
+		pub lock: LockConfig<DurationPresets>,
+		pub share_asset_id: AssetId,
+	}
+
+	/// `Stake` before the stakes were owned through financial NFTs.
+	#[derive(Encode, Decode)]
+	pub struct StakeV1<AccountId, RewardPoolId, Balance, Reductions> {
+		pub owner: AccountId,
+		pub reward_pool_id: RewardPoolId,
+		pub stake: Balance,
+		pub share: Balance,
+		pub reductions: Reductions,
+		pub lock: Lock,
+	}
+
+	pub type RewardPoolV1Of<T> = RewardPoolV1<
+		AccountIdOf<T>,
+		<T as Config>::AssetId,
+		<T as Config>::Balance,
+		<T as frame_system::Config>::BlockNumber,
+		StakingDurationToRewardsMultiplierConfig<<T as Config>::MaxStakingDurationPresets>,
+		Rewards<
+			<T as Config>::AssetId,
+			<T as Config>::Balance,
+			<T as Config>::MaxRewardConfigsPerPool,
+		>,
+	>;
+
+	pub type StakeV1Of<T> = StakeV1<
+		AccountIdOf<T>,
+		<T as Config>::RewardPoolId,
+		<T as Config>::Balance,
+		Reductions<
+			<T as Config>::AssetId,
+			<T as Config>::Balance,
+			<T as Config>::MaxRewardConfigsPerPool,
+		>,
+	>;
+
+	#[frame_support::storage_alias]
+	pub type RewardPools<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, <T as Config>::RewardPoolId, RewardPoolV1Of<T>>;
+
+	#[frame_support::storage_alias]
+	pub type Stakes<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, <T as Config>::PositionId, StakeV1Of<T>>;
+}
+
+/// Creates a share asset for each existing pool and mints the shares of the existing stakes to
+/// their owners.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= 1 {
+		return T::DbWeight::get().reads(1)
+	}
+	let mut share_asset_ids = BTreeMap::new();
+	for pool_id in RewardPools::<T>::iter_keys() {
+		match T::CurrencyFactory::create(RangeId::LP_TOKENS, T::Balance::zero()) {
+			Ok(share_asset_id) => {
+				share_asset_ids.insert(pool_id, share_asset_id);
+			},
+			// the pools are left as they are and migrated on the next upgrade
+			Err(_) => return T::DbWeight::get().reads(share_asset_ids.len() as Weight + 1),
+		}
+	}
+	let pools = share_asset_ids.len() as Weight;
+
+	v1::RewardPools::<T>::translate::<RewardPoolV0<_, _, _, _, _, _>, _>(|pool_id, pool| {
+		share_asset_ids.get(&pool_id).map(|share_asset_id| v1::RewardPoolV1 {
+			owner: pool.owner,
+			asset_id: pool.asset_id,
+			rewards: pool.rewards,
+			total_shares: pool.total_shares,
+			claimed_shares: pool.claimed_shares,
+			end_block: pool.end_block,
+			lock: pool.lock,
+			share_asset_id: *share_asset_id,
+		})
+	});
+
+	let mut stakes: Weight = 0;
+	for (_, stake) in v1::Stakes::<T>::iter() {
+		stakes += 1;
+		if let Some(share_asset_id) = share_asset_ids.get(&stake.reward_pool_id) {
+			// minting only fails on overflow of the issuance, which the shares cannot exceed
+			let _ = T::Assets::mint_into(*share_asset_id, &stake.owner, stake.share);
+		}
+	}
+
+	StorageVersion::new(1).put::<Pallet<T>>();
+	T::DbWeight::get().reads_writes(3 * pools + 3 * stakes + 1, 3 * pools + 2 * stakes + 1)
+}
+
+/// Creates a financial NFT collection for each existing pool and mints a financial NFT for each
+/// existing stake into its owner. The staked amount is moved from the pool account and the shares
+/// still held by the owner to the account of the financial NFT.
+///
+/// The migration is applied as a whole or not at all, in which case it is retried on the next
+/// upgrade.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() != 1 {
+		return T::DbWeight::get().reads(1)
+	}
+	let pools = v1::RewardPools::<T>::iter_keys().count() as Weight;
+	let stakes = v1::Stakes::<T>::iter_keys().count() as Weight;
+
+	match with_storage_layer(migrate_stakes_to_fnft::<T>) {
+		Ok(()) => {
+			StorageVersion::new(2).put::<Pallet<T>>();
+			T::DbWeight::get()
+				.reads_writes(3 * pools + 10 * stakes + 1, 4 * pools + 10 * stakes + 1)
+		},
+		Err(_) => T::DbWeight::get().reads(3 * pools + 10 * stakes + 1),
+	}
+}
+
+fn migrate_stakes_to_fnft<T: Config>() -> Result<(), DispatchError> {
+	let mut collections = BTreeMap::new();
+	for pool_id in v1::RewardPools::<T>::iter_keys() {
+		collections.insert(pool_id, Pallet::<T>::create_financial_nft_collection()?);
+	}
+
+	RewardPools::<T>::translate::<v1::RewardPoolV1Of<T>, _>(|pool_id, pool| {
+		collections.get(&pool_id).map(|financial_nft_asset_id| RewardPool {
+			owner: pool.owner,
+			asset_id: pool.asset_id,
+			rewards: pool.rewards,
+			total_shares: pool.total_shares,
+			claimed_shares: pool.claimed_shares,
+			end_block: pool.end_block,
+			lock: pool.lock,
+			share_asset_id: pool.share_asset_id,
+			financial_nft_asset_id: *financial_nft_asset_id,
+		})
+	});
+
+	let stakes = v1::Stakes::<T>::iter().collect::<Vec<_>>();
+	for (position_id, stake) in stakes {
+		let pool = RewardPools::<T>::get(stake.reward_pool_id)
+			.ok_or(DispatchError::Other("reward pool of stake not found"))?;
+		let collection = pool.financial_nft_asset_id;
+		let instance = Pallet::<T>::mint_financial_nft(&collection, &stake.owner)?;
+		let fnft_account = T::FinancialNft::asset_account(&collection, &instance);
+
+		T::Assets::transfer(
+			pool.asset_id,
+			&Pallet::<T>::pool_account_id(&stake.reward_pool_id),
+			&fnft_account,
+			stake.stake,
+			false,
+		)?;
+		// only the shares the owner still holds are moved to the account of the NFT, the ones
+		// transferred away stay where they are and must be sent back to the account to unstake
+		let held_shares = min(T::Assets::balance(pool.share_asset_id, &stake.owner), stake.share);
+		T::Assets::burn_from(pool.share_asset_id, &stake.owner, held_shares)?;
+		T::Assets::mint_into(pool.share_asset_id, &fnft_account, held_shares)?;
+
+		FinancialNftPositions::<T>::insert(collection, instance, position_id);
+		Stakes::<T>::insert(
+			position_id,
+			Stake {
+				fnft_instance_id: instance,
+				reward_pool_id: stake.reward_pool_id,
+				stake: stake.stake,
+				share: stake.share,
+				reductions: stake.reductions,
+				lock: stake.lock,
+			},
+		);
+	}
+	Ok(())
+}
@@ -1,10 +1,9 @@
 pub(crate) use crate::test::runtime::{new_test_ext, Test}; // for benchmarks
 use crate::{
 	test::{prelude::H256, runtime::*},
-	Config, RewardPools, StakeCount, Stakes,
+	Config, RewardPools, Stakes,
 };
-use composable_support::abstractions::utils::increment::Increment;
-use composable_tests_helpers::test::currency::{CurrencyId, BTC, PICA, USDT};
+use composable_tests_helpers::test::currency::{BTC, PICA, USDT};
 use composable_traits::{
 	staking::{
 		lock::{Lock, LockConfig},
@@ -31,7 +30,9 @@ use sp_std::collections::btree_map::BTreeMap;
 mod prelude;
 mod runtime;
 
-mod test_reward_accumulation_hook;
+mod test_claim_and_compound;
+mod test_reward_accumulation;
+mod test_share_assets;
 mod test_update_reward_pools;
 
 #[test]
@@ -117,7 +118,7 @@ fn stake_in_case_of_zero_inflation_should_work() {
 		assert_eq!(
 			StakingRewards::stakes(StakingRewards::stake_count()),
 			Some(Stake {
-				owner: staker,
+				fnft_instance_id: 0,
 				reward_pool_id: pool_id,
 				stake: amount,
 				share: StakingRewards::boosted_amount(reward_multiplier, amount),
@@ -130,7 +131,7 @@ fn stake_in_case_of_zero_inflation_should_work() {
 			})
 		);
 		assert_eq!(balance(staked_asset_id, &staker), amount);
-		assert_eq!(balance(staked_asset_id, &StakingRewards::pool_account_id(&pool_id)), amount);
+		assert_eq!(balance(staked_asset_id, &stake_account(1)), amount);
 		assert_last_event::<Test, _>(|e| {
 			matches!(
 				e.event,
@@ -181,7 +182,7 @@ fn stake_in_case_of_not_zero_inflation_should_work() {
 		assert_eq!(
 			StakingRewards::stakes(StakingRewards::stake_count()),
 			Some(Stake {
-				owner: staker,
+				fnft_instance_id: 0,
 				reward_pool_id: pool_id,
 				stake: amount,
 				share: StakingRewards::boosted_amount(reward_multiplier, amount),
@@ -194,7 +195,7 @@ fn stake_in_case_of_not_zero_inflation_should_work() {
 			})
 		);
 		assert_eq!(balance(staked_asset_id, &staker), amount);
-		assert_eq!(balance(staked_asset_id, &StakingRewards::pool_account_id(&pool_id)), amount);
+		assert_eq!(balance(staked_asset_id, &stake_account(1)), amount);
 		assert_last_event::<Test, _>(|e| {
 			matches!(
 				e.event,
@@ -239,9 +240,11 @@ fn test_extend_stake_amount() {
 		for (_asset_id, reward) in rewards_pool.rewards.iter() {
 			total_rewards += reward.total_rewards;
 		}
-		let inflation_extended = extend_amount * total_rewards / rewards_pool.total_shares;
+		let boosted_extend_amount =
+			StakingRewards::boosted_amount(reward_multiplier, extend_amount);
+		let inflation_extended = boosted_extend_amount * total_rewards / rewards_pool.total_shares;
 		let inflation = inflation + inflation_extended;
-		assert_eq!(inflation, 50710);
+		assert_eq!(inflation, 1004);
 		let reductions = Reductions::try_from(
 			rewards_pool
 				.rewards
@@ -254,10 +257,10 @@ fn test_extend_stake_amount() {
 		assert_eq!(
 			StakingRewards::stakes(StakingRewards::stake_count()),
 			Some(Stake {
-				owner: staker.clone(),
+				fnft_instance_id: 0,
 				reward_pool_id: pool_id,
 				stake: amount + extend_amount,
-				share: boosted_amount + extend_amount,
+				share: boosted_amount + boosted_extend_amount,
 				reductions,
 				lock: Lock {
 					started_at: <Test as crate::Config>::UnixTime::now(),
@@ -267,10 +270,7 @@ fn test_extend_stake_amount() {
 			})
 		);
 		assert_eq!(balance(staked_asset_id, &staker), amount);
-		assert_eq!(
-			balance(staked_asset_id, &StakingRewards::pool_account_id(&pool_id)),
-			amount + extend_amount
-		);
+		assert_eq!(balance(staked_asset_id, &stake_account(1)), amount + extend_amount);
 		assert_last_event::<Test, _>(|e| {
 			matches!(e.event,
             Event::StakingRewards(crate::Event::StakeAmountExtended { position_id, amount})
@@ -544,30 +544,26 @@ fn test_split_postion() {
 		System::set_block_number(1);
 		let pool_init_config = get_default_reward_pool();
 		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), pool_init_config));
-		let new_position = StakeCount::<Test>::increment();
-		assert_ok!(new_position);
+		let amount = 1_000_000_000_000_000_u128;
+		mint_assets(vec![ALICE], vec![PICA::ID], amount);
+		assert_ok!(StakingRewards::stake(Origin::signed(ALICE), 1, amount, ONE_HOUR));
 		let reduction = 10_000_000_000_000_u128;
-		let stake = Stake::<
-			<Test as frame_system::Config>::AccountId,
-			RewardPoolId,
-			Balance,
-			Reductions<CurrencyId, Balance, MaxRewardConfigsPerPool>,
-		> {
-			owner: ALICE,
-			reward_pool_id: 1,
-			stake: 1000_000_000_000_000_u128,
-			share: 1000_000_000_000_000_u128,
-			reductions: Reductions::<_, _, _>::try_from(BTreeMap::from([(USDT::ID, reduction)]))
-				.expect("BoundedBTreeMap creation failed"),
-			lock: Lock {
-				started_at: 10000_u64,
-				duration: 10000000_u64,
-				unlock_penalty: Perbill::from_percent(2),
-			},
-		};
-		Stakes::<Test>::insert(1, stake.clone());
+		Stakes::<Test>::mutate(1, |stake| {
+			if let Some(stake) = stake {
+				stake.reductions =
+					Reductions::<_, _, _>::try_from(BTreeMap::from([(USDT::ID, reduction)]))
+						.expect("BoundedBTreeMap creation failed");
+			}
+		});
+		let stake = Stakes::<Test>::get(1).expect("stake expected");
+		let share_asset_id =
+			StakingRewards::pools(1).expect("rewards_pool expected").share_asset_id;
 		let ratio = Permill::from_rational(1_u32, 7_u32);
 		let left_from_one_ratio = ratio.left_from_one();
+		assert_noop!(
+			<StakingRewards as Staking>::split(&BOB, &1_u128, ratio),
+			crate::Error::<Test>::OnlyStakeOwnerCanSplit
+		);
 		let split = <StakingRewards as Staking>::split(&ALICE, &1_u128, ratio);
 		assert_ok!(split);
 		let stake1 = Stakes::<Test>::get(1);
@@ -586,6 +582,12 @@ fn test_split_postion() {
 			stake2.reductions.get(&USDT::ID),
 			Some(&left_from_one_ratio.mul_floor(reduction))
 		);
+		// the new position is a new financial NFT of the owner holding its part of the stake
+		assert_eq!(stake2.fnft_instance_id, 1);
+		assert_eq!(balance(PICA::ID, &stake_account(1)), stake1.stake);
+		assert_eq!(balance(share_asset_id, &stake_account(1)), stake1.share);
+		assert_eq!(balance(PICA::ID, &stake_account(2)), stake2.stake);
+		assert_eq!(balance(share_asset_id, &stake_account(2)), stake2.share);
 		assert_last_event::<Test, _>(|e| {
 			matches!(&e.event,
             Event::StakingRewards(crate::Event::SplitPosition { positions })
@@ -690,6 +692,13 @@ fn balance(asset_id: u128, account: &Public) -> u128 {
 	)
 }
 
+/// The account of the financial NFT of a position, holding its staked amount and shares.
+fn stake_account(position_id: u128) -> Public {
+	let stake = StakingRewards::stakes(position_id).expect("stake expected");
+	let rewards_pool = StakingRewards::pools(stake.reward_pool_id).expect("rewards_pool expected");
+	StakingRewards::stake_account(&rewards_pool, &stake)
+}
+
 fn update_total_rewards_and_total_shares_in_rewards_pool(
 	pool_id: u16,
 	total_rewards: u128,
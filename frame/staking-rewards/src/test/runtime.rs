@@ -200,7 +200,12 @@ impl pallet_fnft::Config for Test {
 	type ProxyType = ProxyType;
 	type AccountProxy = Proxy;
 	type ProxyTypeSelector = FnftAccountProxyType;
+	type AssetId = CurrencyId;
+	type Balance = Balance;
+	type Assets = Assets;
+	type FinancialNftProtocol = StakingRewards;
 	type PalletId = FnftPalletId;
+	type WeightInfo = ();
 }
 
 parameter_types! {
@@ -0,0 +1,159 @@
+use composable_tests_helpers::test::currency::{PICA, USDT};
+use composable_traits::{
+	staking::{RewardConfig, RewardPoolConfiguration, RewardRate},
+	time::ONE_HOUR,
+};
+use frame_support::{assert_noop, assert_ok, traits::TryCollect, BoundedVec};
+
+use crate::test::{
+	assert_last_event, balance, default_lock_config, get_default_reward_pool, mint_assets,
+	new_test_ext,
+	runtime::{Event, Origin, StakingRewards, System, ALICE, BOB},
+	stake_account, update_total_rewards_and_total_shares_in_rewards_pool, Test,
+};
+
+#[test]
+fn claim_transfers_rewards_to_beneficiary() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), get_default_reward_pool()));
+		let pool_id = StakingRewards::pool_count();
+		let pool_account = StakingRewards::pool_account_id(&pool_id);
+		let amount = 100_500;
+		mint_assets(vec![ALICE], vec![PICA::ID], amount);
+		mint_assets(vec![pool_account], vec![USDT::ID], 1_000);
+		assert_ok!(StakingRewards::stake(Origin::signed(ALICE), pool_id, amount, ONE_HOUR));
+		let position_id = StakingRewards::stake_count();
+		let total_shares = StakingRewards::pools(pool_id).expect("rewards_pool expected").total_shares;
+		update_total_rewards_and_total_shares_in_rewards_pool(pool_id, 100, total_shares);
+
+		assert_noop!(
+			StakingRewards::claim(Origin::signed(BOB), position_id, None, None),
+			crate::Error::<Test>::OnlyStakeOwnerCanClaim
+		);
+
+		let assets: BoundedVec<_, _> = vec![USDT::ID].try_into().expect("assets expected");
+		assert_ok!(StakingRewards::claim(
+			Origin::signed(ALICE),
+			position_id,
+			Some(assets),
+			Some(BOB)
+		));
+		assert_last_event::<Test, _>(|e| {
+			matches!(&e.event,
+				Event::StakingRewards(crate::Event::RewardsClaimed { position_id: id, owner, beneficiary, rewards })
+				if *id == position_id && *owner == ALICE && *beneficiary == BOB
+					&& rewards == &vec![(USDT::ID, 100)])
+		});
+		assert_eq!(balance(USDT::ID, &BOB), 100);
+		assert_eq!(balance(USDT::ID, &pool_account), 900);
+		let stake = StakingRewards::stakes(position_id).expect("stake expected");
+		assert_eq!(stake.reductions.get(&USDT::ID), Some(&100));
+		let rewards_pool = StakingRewards::pools(pool_id).expect("rewards_pool expected");
+		assert_eq!(rewards_pool.rewards.get(&USDT::ID).expect("reward expected").claimed_rewards, 100);
+
+		// nothing is left to claim
+		assert_ok!(StakingRewards::claim(Origin::signed(ALICE), position_id, None, None));
+		assert_last_event::<Test, _>(|e| {
+			matches!(&e.event,
+				Event::StakingRewards(crate::Event::RewardsClaimed { beneficiary, rewards, .. })
+				if *beneficiary == ALICE && rewards == &vec![(USDT::ID, 0)])
+		});
+		assert_eq!(balance(USDT::ID, &ALICE), 0);
+
+		// unstaking does not pay the claimed rewards again
+		assert_ok!(StakingRewards::unstake(Origin::signed(ALICE), position_id));
+		assert_eq!(balance(USDT::ID, &ALICE), 0);
+		assert_eq!(balance(USDT::ID, &pool_account), 900);
+	});
+}
+
+#[test]
+fn position_can_be_unstaked_after_claiming_and_dilution_by_a_new_stake() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), get_default_reward_pool()));
+		let pool_id = StakingRewards::pool_count();
+		let pool_account = StakingRewards::pool_account_id(&pool_id);
+		mint_assets(vec![ALICE], vec![PICA::ID], 100_500);
+		mint_assets(vec![BOB], vec![PICA::ID], 50_000);
+		mint_assets(vec![pool_account], vec![USDT::ID], 1_000);
+		assert_ok!(StakingRewards::stake(Origin::signed(ALICE), pool_id, 100_500, ONE_HOUR));
+		let position_id = StakingRewards::stake_count();
+		let total_shares =
+			StakingRewards::pools(pool_id).expect("rewards_pool expected").total_shares;
+		update_total_rewards_and_total_shares_in_rewards_pool(pool_id, 100, total_shares);
+		assert_ok!(StakingRewards::claim(Origin::signed(ALICE), position_id, None, None));
+		assert_eq!(balance(USDT::ID, &ALICE), 100);
+
+		// the rewards of the position now round one below its reduction
+		assert_ok!(StakingRewards::stake(Origin::signed(BOB), pool_id, 50_000, ONE_HOUR));
+
+		assert_ok!(StakingRewards::unstake(Origin::signed(ALICE), position_id));
+		assert_eq!(balance(USDT::ID, &ALICE), 100);
+		assert!(StakingRewards::stakes(position_id).is_none());
+	});
+}
+
+#[test]
+fn compound_adds_rewards_to_the_stake() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(
+			Origin::root(),
+			RewardPoolConfiguration::RewardRateBasedIncentive {
+				owner: ALICE,
+				asset_id: PICA::ID,
+				end_block: 5,
+				reward_configs: [(
+					PICA::ID,
+					RewardConfig {
+						asset_id: PICA::ID,
+						max_rewards: 100,
+						reward_rate: RewardRate::per_second(10),
+					},
+				)]
+				.into_iter()
+				.try_collect()
+				.expect("reward configs expected"),
+				lock: default_lock_config(),
+			}
+		));
+		let pool_id = StakingRewards::pool_count();
+		let pool_account = StakingRewards::pool_account_id(&pool_id);
+		let amount = 100_500;
+		mint_assets(vec![ALICE], vec![PICA::ID], amount);
+		mint_assets(vec![pool_account], vec![PICA::ID], 100);
+		assert_ok!(StakingRewards::stake(Origin::signed(ALICE), pool_id, amount, ONE_HOUR));
+		let position_id = StakingRewards::stake_count();
+		let stake = StakingRewards::stakes(position_id).expect("stake expected");
+		let total_shares =
+			StakingRewards::pools(pool_id).expect("rewards_pool expected").total_shares;
+		update_total_rewards_and_total_shares_in_rewards_pool(pool_id, 100, total_shares);
+
+		assert_noop!(
+			StakingRewards::compound(Origin::signed(BOB), position_id),
+			crate::Error::<Test>::OnlyStakeOwnerCanCompound
+		);
+
+		assert_ok!(StakingRewards::compound(Origin::signed(ALICE), position_id));
+		// the lock of ONE_HOUR boosts the reinvested amount by 1%
+		let share = 1;
+		assert_last_event::<Test, _>(|e| {
+			matches!(&e.event,
+				Event::StakingRewards(crate::Event::RewardsCompounded { position_id: id, amount, share: s })
+				if *id == position_id && *amount == 100 && *s == share)
+		});
+		let compounded = StakingRewards::stakes(position_id).expect("stake expected");
+		assert_eq!(compounded.stake, stake.stake + 100);
+		assert_eq!(compounded.share, stake.share + share);
+		assert_eq!(compounded.lock, stake.lock);
+		assert_eq!(compounded.reductions.get(&PICA::ID), Some(&100));
+		let rewards_pool = StakingRewards::pools(pool_id).expect("rewards_pool expected");
+		assert_eq!(rewards_pool.total_shares, total_shares + share);
+		// the rewards move from the pool account to the account of the position
+		assert_eq!(balance(PICA::ID, &ALICE), 0);
+		assert_eq!(balance(PICA::ID, &pool_account), 0);
+		assert_eq!(balance(PICA::ID, &stake_account(position_id)), amount + 100);
+	});
+}
@@ -4,9 +4,11 @@ use composable_tests_helpers::test::{
 	block::process_and_progress_blocks,
 	helper::{assert_last_event, assert_no_event},
 };
-use frame_support::traits::TryCollect;
+use frame_support::traits::{TryCollect, UnixTime};
 
-use crate::{test::prelude::*, Pallet};
+use crate::{
+	reward_accumulation_calculation, test::prelude::*, Pallet, RewardAccumulationCalculationError,
+};
 
 use super::*;
 
@@ -39,7 +41,7 @@ fn test_reward_update_calculation() {
 			|reward, (expected_total_rewards_units, current_block_number)| {
 				System::set_block_number(current_block_number);
 
-				let reward = Pallet::<Test>::reward_accumulation_hook_reward_update_calculation(
+				let reward = Pallet::<Test>::reward_accumulation_update_calculation(
 					POOL_ID,
 					reward,
 					now + (SECONDS_PER_BLOCK * current_block_number),
@@ -59,7 +61,7 @@ fn test_reward_update_calculation() {
 
 		let current_block = (expected.len() + 1) as u64;
 
-		let reward = Pallet::<Test>::reward_accumulation_hook_reward_update_calculation(
+		let reward = Pallet::<Test>::reward_accumulation_update_calculation(
 			POOL_ID,
 			reward,
 			now + (SECONDS_PER_BLOCK * current_block),
@@ -80,7 +82,7 @@ fn test_reward_update_calculation() {
 
 #[test]
 // takes about 3 minutes to run
-fn test_acumulate_rewards_hook() {
+fn test_rewards_accumulate_lazily() {
 	new_test_ext().execute_with(|| {
 		type A = Currency<97, 12>;
 		type B = Currency<98, 12>;
@@ -172,27 +174,9 @@ fn test_acumulate_rewards_hook() {
 			StakingRewards::create_reward_pool(Origin::root(), cfg).unwrap();
 		}
 
-		fn check_events(mut expected_events: Vec<crate::Event<Test>>) {
-			dbg!(System::events());
-			for record in System::events() {
-				match record.event {
-					Event::StakingRewards(staking_event) => {
-						let idx = expected_events
-							.iter()
-							.position(|e| e.eq(&&staking_event))
-							.expect(&format!("unexpected event: {staking_event:#?}"));
-
-						expected_events.remove(idx);
-					},
-					_ => {},
-				}
-			}
-
-			assert!(
-				expected_events.is_empty(),
-				"not all expected events were emtted, missing {expected_events:#?}",
-			);
-		}
+		// pools are not updated as blocks go, rewards are computed from the time elapsed since
+		// they were last updated
+		let mut pools = RewardPools::<Test>::iter().collect::<BTreeMap<_, _>>();
 
 		fn progress_to_block(block: u64, counter: &mut u64) {
 			assert!(
@@ -235,7 +219,7 @@ fn test_acumulate_rewards_hook() {
 				),
 			]);
 
-			check_events(vec![]);
+			assert_eq!(RewardPools::<Test>::iter().collect::<BTreeMap<_, _>>(), pools);
 		}
 
 		{
@@ -260,7 +244,7 @@ fn test_acumulate_rewards_hook() {
 				),
 			]);
 
-			check_events(vec![]);
+			assert_eq!(RewardPools::<Test>::iter().collect::<BTreeMap<_, _>>(), pools);
 		}
 
 		{
@@ -285,10 +269,7 @@ fn test_acumulate_rewards_hook() {
 				),
 			]);
 
-			check_events(vec![crate::Event::<Test>::MaxRewardsAccumulated {
-				pool_id: 2,
-				asset_id: E::ID,
-			}]);
+			assert_eq!(RewardPools::<Test>::iter().collect::<BTreeMap<_, _>>(), pools);
 		}
 
 		{
@@ -306,10 +287,7 @@ fn test_acumulate_rewards_hook() {
 				(BOB, C::ID, &[(D::ID, c_d_max_rewards), (E::ID, c_e_max_rewards)]),
 			]);
 
-			check_events(vec![crate::Event::<Test>::MaxRewardsAccumulated {
-				pool_id: 2,
-				asset_id: D::ID,
-			}]);
+			assert_eq!(RewardPools::<Test>::iter().collect::<BTreeMap<_, _>>(), pools);
 		}
 
 		// add a new, zero-reward pool
@@ -334,6 +312,7 @@ fn test_acumulate_rewards_hook() {
 			},
 		)
 		.unwrap();
+		pools = RewardPools::<Test>::iter().collect::<BTreeMap<_, _>>();
 
 		{
 			progress_to_block(4167, &mut current_block);
@@ -351,10 +330,7 @@ fn test_acumulate_rewards_hook() {
 				(CHARLIE, F::ID, &[(F::ID, 0)]),
 			]);
 
-			check_events(vec![crate::Event::<Test>::MaxRewardsAccumulated {
-				pool_id: 1,
-				asset_id: B::ID,
-			}]);
+			assert_eq!(RewardPools::<Test>::iter().collect::<BTreeMap<_, _>>(), pools);
 		}
 
 		{
@@ -366,14 +342,67 @@ fn test_acumulate_rewards_hook() {
 				(CHARLIE, F::ID, &[(F::ID, 0)]),
 			]);
 
-			check_events(vec![crate::Event::<Test>::MaxRewardsAccumulated {
-				pool_id: 1,
-				asset_id: A::ID,
-			}]);
+			assert_eq!(RewardPools::<Test>::iter().collect::<BTreeMap<_, _>>(), pools);
 		}
 	});
 }
 
+#[test]
+fn test_rewards_are_accumulated_before_shares_change() {
+	new_test_ext().execute_with(|| {
+		process_and_progress_blocks::<StakingRewards, Test>(1);
+
+		StakingRewards::create_reward_pool(
+			Origin::root(),
+			RewardPoolConfiguration::RewardRateBasedIncentive {
+				owner: ALICE,
+				asset_id: PICA::ID,
+				end_block: ONE_YEAR_OF_BLOCKS,
+				reward_configs: [(
+					USDT::ID,
+					RewardConfig {
+						asset_id: USDT::ID,
+						max_rewards: 1_000_000_u128,
+						reward_rate: RewardRate::per_second(10_u128),
+					},
+				)]
+				.into_iter()
+				.try_collect()
+				.unwrap(),
+				lock: default_lock_config(),
+			},
+		)
+		.unwrap();
+		let pool_id = StakingRewards::pool_count();
+		mint_assets(vec![ALICE, BOB], vec![PICA::ID], PICA::units(100));
+
+		assert_ok!(StakingRewards::stake(
+			Origin::signed(ALICE),
+			pool_id,
+			PICA::units(10),
+			ONE_HOUR
+		));
+
+		process_and_progress_blocks::<StakingRewards, Test>(1);
+		let reward =
+			StakingRewards::pools(pool_id).unwrap().rewards.get(&USDT::ID).cloned().unwrap();
+		assert_eq!(reward.total_rewards, 0);
+
+		// the rewards accumulated while ALICE was the only staker are not diluted by BOB
+		assert_ok!(StakingRewards::stake(Origin::signed(BOB), pool_id, PICA::units(10), ONE_HOUR));
+		let accumulated = 10 * block_seconds(1);
+		let reward =
+			StakingRewards::pools(pool_id).unwrap().rewards.get(&USDT::ID).cloned().unwrap();
+		assert_eq!(reward.total_rewards, accumulated * 2);
+		assert_eq!(
+			reward.last_updated_timestamp,
+			<Test as crate::Config>::UnixTime::now().as_secs()
+		);
+		let bob_stake = StakingRewards::stakes(StakingRewards::stake_count()).unwrap();
+		assert_eq!(bob_stake.reductions.get(&USDT::ID), Some(&accumulated));
+	});
+}
+
 // TODO(benluelo): Consider adding the pool_id to the paramaters, currently this assumes they've
 // been created from 1 on.
 pub(crate) fn check_rewards(
@@ -387,6 +416,7 @@ pub(crate) fn check_rewards(
 		)],
 	)],
 ) {
+	let now_seconds = <Test as crate::Config>::UnixTime::now().as_secs();
 	let mut all_rewards = RewardPools::<Test>::iter().collect::<BTreeMap<_, _>>();
 
 	for ((owner, asset_id, rewards), pool_id) in expected.into_iter().zip(1..) {
@@ -402,6 +432,14 @@ pub(crate) fn check_rewards(
 				.rewards
 				.remove(&reward_asset_id)
 				.expect(&format!("reward asset {reward_asset_id} not present in pool {pool_id}"));
+			let reward = match reward_accumulation_calculation::<Test>(reward, now_seconds) {
+				Ok(reward) |
+				Err(RewardAccumulationCalculationError::BackToTheFuture(reward)) |
+				Err(RewardAccumulationCalculationError::MaxRewardsAccumulated(reward)) |
+				Err(RewardAccumulationCalculationError::MaxRewardsAccumulatedPreviously(
+					reward,
+				)) => reward,
+			};
 
 			assert_eq!(
 				reward.asset_id, *reward_asset_id,
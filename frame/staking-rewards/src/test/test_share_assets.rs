@@ -0,0 +1,365 @@
+use composable_support::validation::Validated;
+use composable_tests_helpers::test::currency::PICA;
+use composable_traits::{
+	fnft::FinancialNftProtocol,
+	staking::{lock::Lock, StakingVotingPower},
+	time::{ONE_HOUR, ONE_MINUTE},
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::unhashed,
+	traits::{
+		fungibles::Transfer, tokens::nonfungibles::Inspect, GetStorageVersion, StorageVersion,
+	},
+};
+use sp_runtime::{Perbill, Permill};
+
+use crate::{
+	migrations::{
+		migrate_to_v1, migrate_to_v2,
+		v1::{self, RewardPoolV1, StakeV1},
+		RewardPoolV0,
+	},
+	test::{
+		balance, get_default_reward_pool, mint_assets, new_test_ext,
+		runtime::{FinancialNft, Origin, StakingRewards, System, ALICE, BOB},
+		stake_account, Test,
+	},
+};
+
+#[test]
+fn stake_mints_shares_boosted_by_lock_duration() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), get_default_reward_pool()));
+		let pool_id = StakingRewards::pool_count();
+		let share_asset_id =
+			StakingRewards::pools(pool_id).expect("rewards_pool expected").share_asset_id;
+		let amount = 100_500;
+		mint_assets(vec![ALICE, BOB], vec![PICA::ID], amount);
+
+		assert_ok!(StakingRewards::stake(Origin::signed(ALICE), pool_id, amount, ONE_HOUR));
+		assert_ok!(StakingRewards::stake(Origin::signed(BOB), pool_id, amount, ONE_MINUTE));
+		// the longer lock gets ten times the shares of the shorter one
+		assert_eq!(balance(share_asset_id, &stake_account(1)), 1_005);
+		assert_eq!(balance(share_asset_id, &stake_account(2)), 101);
+		// the shares of the positions count as the voting power of their owners
+		assert_eq!(StakingRewards::voting_power(&pool_id, &ALICE), Ok(1_005));
+		assert_eq!(StakingRewards::voting_power(&pool_id, &BOB), Ok(101));
+		assert_eq!(StakingRewards::total_voting_power(&pool_id), Ok(1_106));
+		assert_noop!(
+			StakingRewards::voting_power(&42, &ALICE),
+			crate::Error::<Test>::RewardsPoolNotFound
+		);
+	});
+}
+
+#[test]
+fn extend_mints_shares_boosted_by_the_lock_of_the_position() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), get_default_reward_pool()));
+		let pool_id = StakingRewards::pool_count();
+		let share_asset_id =
+			StakingRewards::pools(pool_id).expect("rewards_pool expected").share_asset_id;
+		let amount = 100_500;
+		mint_assets(vec![ALICE], vec![PICA::ID], amount * 2);
+
+		assert_ok!(StakingRewards::stake(Origin::signed(ALICE), pool_id, amount, ONE_MINUTE));
+		let position_id = StakingRewards::stake_count();
+		assert_ok!(StakingRewards::extend(Origin::signed(ALICE), position_id, amount));
+
+		let stake = StakingRewards::stakes(position_id).expect("stake expected");
+		assert_eq!(stake.stake, amount * 2);
+		assert_eq!(stake.share, 202);
+		assert_eq!(balance(share_asset_id, &stake_account(position_id)), 202);
+	});
+}
+
+#[test]
+fn unstake_burns_the_shares_of_the_position() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), get_default_reward_pool()));
+		let pool_id = StakingRewards::pool_count();
+		let share_asset_id =
+			StakingRewards::pools(pool_id).expect("rewards_pool expected").share_asset_id;
+		let amount = 100_500;
+		mint_assets(vec![ALICE], vec![PICA::ID], amount);
+		assert_ok!(StakingRewards::stake(Origin::signed(ALICE), pool_id, amount, ONE_HOUR));
+		let position_id = StakingRewards::stake_count();
+		let stake_account = stake_account(position_id);
+
+		// the shares are transferable, but must be held by the position to unstake
+		assert_ok!(<<Test as crate::Config>::Assets as Transfer<_>>::transfer(
+			share_asset_id,
+			&stake_account,
+			&BOB,
+			5,
+			false
+		));
+		assert_eq!(StakingRewards::voting_power(&pool_id, &BOB), Ok(5));
+		assert_eq!(StakingRewards::voting_power(&pool_id, &ALICE), Ok(1_000));
+		assert_noop!(
+			StakingRewards::unstake(Origin::signed(ALICE), position_id),
+			crate::Error::<Test>::NotEnoughShareAssets
+		);
+
+		assert_ok!(<<Test as crate::Config>::Assets as Transfer<_>>::transfer(
+			share_asset_id,
+			&BOB,
+			&stake_account,
+			5,
+			false
+		));
+		assert_ok!(StakingRewards::unstake(Origin::signed(ALICE), position_id));
+		assert_eq!(balance(share_asset_id, &stake_account), 0);
+		assert_eq!(StakingRewards::total_voting_power(&pool_id), Ok(0));
+	});
+}
+
+#[test]
+fn migrate_to_v1_issues_shares_of_existing_stakes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), get_default_reward_pool()));
+		let pool_id = StakingRewards::pool_count();
+		let pool = StakingRewards::pools(pool_id).expect("rewards_pool expected");
+		unhashed::put(
+			&v1::RewardPools::<Test>::hashed_key_for(pool_id),
+			&RewardPoolV0 {
+				owner: pool.owner,
+				asset_id: pool.asset_id,
+				rewards: pool.rewards,
+				total_shares: 1_005,
+				claimed_shares: pool.claimed_shares,
+				end_block: pool.end_block,
+				lock: pool.lock,
+			},
+		);
+		let stake = StakeV1 {
+			owner: ALICE,
+			reward_pool_id: pool_id,
+			stake: 100_500,
+			share: 1_005,
+			reductions: Default::default(),
+			lock: Lock {
+				started_at: 0,
+				duration: ONE_HOUR,
+				unlock_penalty: Perbill::from_percent(5),
+			},
+		};
+		v1::Stakes::<Test>::insert(1, stake);
+		StorageVersion::new(0).put::<StakingRewards>();
+
+		migrate_to_v1::<Test>();
+
+		assert_eq!(StakingRewards::on_chain_storage_version(), 1);
+		let migrated = v1::RewardPools::<Test>::get(pool_id).expect("rewards_pool expected");
+		assert_ne!(migrated.share_asset_id, pool.share_asset_id);
+		assert_eq!(migrated.total_shares, 1_005);
+		assert_eq!(balance(migrated.share_asset_id, &ALICE), 1_005);
+	});
+}
+
+#[test]
+fn migrate_to_v2_issues_financial_nfts_of_existing_stakes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), get_default_reward_pool()));
+		let pool_id = StakingRewards::pool_count();
+		let pool = StakingRewards::pools(pool_id).expect("rewards_pool expected");
+		v1::RewardPools::<Test>::insert(
+			pool_id,
+			RewardPoolV1 {
+				owner: pool.owner,
+				asset_id: pool.asset_id,
+				rewards: pool.rewards,
+				total_shares: 1_005,
+				claimed_shares: pool.claimed_shares,
+				end_block: pool.end_block,
+				lock: pool.lock,
+				share_asset_id: pool.share_asset_id,
+			},
+		);
+		v1::Stakes::<Test>::insert(
+			1,
+			StakeV1 {
+				owner: ALICE,
+				reward_pool_id: pool_id,
+				stake: 100_500,
+				share: 1_005,
+				reductions: Default::default(),
+				lock: Lock {
+					started_at: 0,
+					duration: ONE_HOUR,
+					unlock_penalty: Perbill::from_percent(5),
+				},
+			},
+		);
+		mint_assets(vec![StakingRewards::pool_account_id(&pool_id)], vec![PICA::ID], 100_500);
+		mint_assets(vec![ALICE], vec![pool.share_asset_id], 1_005);
+		StorageVersion::new(1).put::<StakingRewards>();
+
+		migrate_to_v2::<Test>();
+
+		assert_eq!(StakingRewards::on_chain_storage_version(), 2);
+		let migrated = StakingRewards::pools(pool_id).expect("rewards_pool expected");
+		assert_ne!(migrated.financial_nft_asset_id, pool.financial_nft_asset_id);
+		let stake = StakingRewards::stakes(1).expect("stake expected");
+		assert_eq!(
+			FinancialNft::owner(&migrated.financial_nft_asset_id, &stake.fnft_instance_id),
+			Some(ALICE)
+		);
+		assert_eq!(
+			StakingRewards::fnft_positions(migrated.financial_nft_asset_id, stake.fnft_instance_id),
+			Some(1)
+		);
+		assert_eq!(balance(PICA::ID, &StakingRewards::pool_account_id(&pool_id)), 0);
+		assert_eq!(balance(PICA::ID, &stake_account(1)), 100_500);
+		assert_eq!(balance(migrated.share_asset_id, &ALICE), 0);
+		assert_eq!(balance(migrated.share_asset_id, &stake_account(1)), 1_005);
+	});
+}
+
+#[test]
+fn migrate_to_v2_moves_only_the_shares_held_by_the_owner() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), get_default_reward_pool()));
+		let pool_id = StakingRewards::pool_count();
+		let pool = StakingRewards::pools(pool_id).expect("rewards_pool expected");
+		v1::RewardPools::<Test>::insert(
+			pool_id,
+			RewardPoolV1 {
+				owner: pool.owner,
+				asset_id: pool.asset_id,
+				rewards: pool.rewards,
+				total_shares: 1_005,
+				claimed_shares: pool.claimed_shares,
+				end_block: pool.end_block,
+				lock: pool.lock,
+				share_asset_id: pool.share_asset_id,
+			},
+		);
+		v1::Stakes::<Test>::insert(
+			1,
+			StakeV1 {
+				owner: ALICE,
+				reward_pool_id: pool_id,
+				stake: 100_500,
+				share: 1_005,
+				reductions: Default::default(),
+				lock: Lock {
+					started_at: 0,
+					duration: ONE_HOUR,
+					unlock_penalty: Perbill::from_percent(5),
+				},
+			},
+		);
+		mint_assets(vec![StakingRewards::pool_account_id(&pool_id)], vec![PICA::ID], 100_500);
+		mint_assets(vec![ALICE], vec![pool.share_asset_id], 1_000);
+		mint_assets(vec![BOB], vec![pool.share_asset_id], 5);
+		StorageVersion::new(1).put::<StakingRewards>();
+
+		migrate_to_v2::<Test>();
+
+		assert_eq!(StakingRewards::on_chain_storage_version(), 2);
+		assert_eq!(balance(pool.share_asset_id, &ALICE), 0);
+		assert_eq!(balance(pool.share_asset_id, &BOB), 5);
+		assert_eq!(balance(pool.share_asset_id, &stake_account(1)), 1_000);
+		assert_eq!(StakingRewards::total_voting_power(&pool_id), Ok(1_005));
+	});
+}
+
+#[test]
+fn financial_nft_of_position_values_its_stake_and_shares() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), get_default_reward_pool()));
+		let pool_id = StakingRewards::pool_count();
+		let pool = StakingRewards::pools(pool_id).expect("rewards_pool expected");
+		mint_assets(vec![ALICE], vec![PICA::ID], 100_500);
+		assert_ok!(StakingRewards::stake(Origin::signed(ALICE), pool_id, 100_500, ONE_HOUR));
+
+		assert_eq!(StakingRewards::collection_asset_ids(), vec![pool.financial_nft_asset_id]);
+		assert_eq!(
+			StakingRewards::value_of(&pool.financial_nft_asset_id, &0),
+			vec![(PICA::ID, 100_500), (pool.share_asset_id, 1_005)]
+		);
+		assert_eq!(StakingRewards::value_of(&pool.financial_nft_asset_id, &1), vec![]);
+	});
+}
+
+#[test]
+fn new_owner_of_financial_nft_owns_the_position() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), get_default_reward_pool()));
+		let pool_id = StakingRewards::pool_count();
+		let pool = StakingRewards::pools(pool_id).expect("rewards_pool expected");
+		mint_assets(vec![ALICE], vec![PICA::ID], 100_500);
+		assert_ok!(StakingRewards::stake(Origin::signed(ALICE), pool_id, 100_500, ONE_HOUR));
+		let position_id = StakingRewards::stake_count();
+
+		assert_ok!(FinancialNft::transfer(
+			Origin::signed(ALICE),
+			pool.financial_nft_asset_id,
+			0,
+			BOB
+		));
+		assert_noop!(
+			StakingRewards::unstake(Origin::signed(ALICE), position_id),
+			crate::Error::<Test>::OnlyStakeOwnerCanUnstake
+		);
+		// the voting power of the position follows its financial NFT
+		assert_eq!(StakingRewards::voting_power(&pool_id, &ALICE), Ok(0));
+		assert_eq!(StakingRewards::voting_power(&pool_id, &BOB), Ok(1_005));
+		assert_ok!(StakingRewards::unstake(Origin::signed(BOB), position_id));
+		assert_eq!(FinancialNft::owner(&pool.financial_nft_asset_id, &0), None);
+		assert_eq!(StakingRewards::fnft_positions(pool.financial_nft_asset_id, 0), None);
+		// the early unlock penalty stays in the pool
+		assert_eq!(balance(PICA::ID, &BOB), 95_475);
+	});
+}
+
+#[test]
+fn listed_position_can_not_be_split_claimed_or_compounded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingRewards::create_reward_pool(Origin::root(), get_default_reward_pool()));
+		let pool_id = StakingRewards::pool_count();
+		let pool = StakingRewards::pools(pool_id).expect("rewards_pool expected");
+		mint_assets(vec![ALICE], vec![PICA::ID], 100_500);
+		assert_ok!(StakingRewards::stake(Origin::signed(ALICE), pool_id, 100_500, ONE_HOUR));
+		let position_id = StakingRewards::stake_count();
+
+		assert_ok!(FinancialNft::list(
+			Origin::signed(ALICE),
+			pool.financial_nft_asset_id,
+			0,
+			pallet_fnft::Sale::FixedPrice { asset_id: PICA::ID, price: 100_500 }
+		));
+		// the buyer pays for the value of the position at the time of listing
+		let ratio = Validated::new(Permill::from_percent(50)).expect("valid split ratio");
+		assert_noop!(
+			StakingRewards::split(Origin::signed(ALICE), position_id, ratio),
+			crate::Error::<Test>::StakeIsListed
+		);
+		assert_noop!(
+			StakingRewards::claim(Origin::signed(ALICE), position_id, None, None),
+			crate::Error::<Test>::StakeIsListed
+		);
+		assert_noop!(
+			StakingRewards::compound(Origin::signed(ALICE), position_id),
+			crate::Error::<Test>::StakeIsListed
+		);
+
+		assert_ok!(FinancialNft::cancel_listing(
+			Origin::signed(ALICE),
+			pool.financial_nft_asset_id,
+			0
+		));
+		assert_ok!(StakingRewards::claim(Origin::signed(ALICE), position_id, None, None));
+	});
+}
@@ -12,7 +12,7 @@ use crate::test::{
 	default_lock_config, new_test_ext,
 	prelude::{block_seconds, ONE_YEAR_OF_BLOCKS},
 	runtime::{MaxRewardConfigsPerPool, Origin, StakingRewards, System, ALICE},
-	test_reward_accumulation_hook::check_rewards,
+	test_reward_accumulation::check_rewards,
 	Test,
 };
 
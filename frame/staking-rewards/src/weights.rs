@@ -6,9 +6,9 @@ pub trait WeightInfo {
 	fn extend(r: u32) -> Weight;
 	fn unstake(r: u32) -> Weight;
 	fn split(r: u32) -> Weight;
-	fn reward_accumulation_hook_reward_update_calculation() -> Weight;
-	fn unix_time_now() -> Weight;
 	fn update_rewards_pool(r: u32) -> Weight;
+	fn claim(r: u32) -> Weight;
+	fn compound(r: u32) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -32,15 +32,15 @@ impl WeightInfo for () {
 		10_000
 	}
 
-	fn reward_accumulation_hook_reward_update_calculation() -> Weight {
+	fn update_rewards_pool(_r: u32) -> Weight {
 		10_000
 	}
 
-	fn unix_time_now() -> Weight {
+	fn claim(_r: u32) -> Weight {
 		10_000
 	}
 
-	fn update_rewards_pool(_r: u32) -> Weight {
+	fn compound(_r: u32) -> Weight {
 		10_000
 	}
 }
@@ -73,7 +73,7 @@ pub fn recode_unwrap_u128<
 benchmarks! {
 	create {
 		let caller: T::AccountId = whitelisted_caller();
-		let asset_id = recode_unwrap_u128(A);
+		let asset_id: T::AssetId = recode_unwrap_u128(A);
 		let reserved = Perquintill::from_percent(100);
 		let manager = whitelisted_caller();
 		let strategies = Default::default();
@@ -165,7 +165,7 @@ benchmarks! {
 	claim_surcharge {
 		let vault = recode_unwrap_u128(1u128);
 		let caller: T::AccountId = whitelisted_caller();
-		let asset_id = recode_unwrap_u128(A);
+		let asset_id: T::AssetId = recode_unwrap_u128(A);
 		let reserved = Perquintill::from_percent(100);
 		let strategies = Default::default();
 		let amount = T::CreationDeposit::get() * 10u32.into();
@@ -198,6 +198,23 @@ benchmarks! {
 		// Wait until the vault is deletable.
 		System::<T>::set_block_number(System::<T>::block_number() + T::TombstoneDuration::get());
 	}: _(RawOrigin::Signed(caller), vault, None)
+
+	approve_strategy {
+		let strategy: T::AccountId = whitelisted_caller();
+		let asset_id: T::AssetId = recode_unwrap_u128(A);
+	}: _(RawOrigin::Root, asset_id, strategy.clone())
+	verify {
+		assert_last_event::<T>(Event::StrategyApproved { asset_id, strategy }.into());
+	}
+
+	revoke_strategy {
+		let strategy: T::AccountId = whitelisted_caller();
+		let asset_id: T::AssetId = recode_unwrap_u128(A);
+		Vault::<T>::approve_strategy(RawOrigin::Root.into(), asset_id, strategy.clone())?;
+	}: _(RawOrigin::Root, asset_id, strategy.clone())
+	verify {
+		assert_last_event::<T>(Event::StrategyRevoked { asset_id, strategy }.into());
+	}
 }
 
 impl_benchmark_test_suite!(
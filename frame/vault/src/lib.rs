@@ -38,6 +38,7 @@
 #![doc = include_str!("../README.md")]
 
 mod capabilities;
+pub mod migrations;
 pub mod models;
 mod rent;
 mod traits;
@@ -74,13 +75,14 @@ pub mod pallet {
 		defi::Rate,
 		vault::{
 			CapabilityVault, Deposit, FundsAvailability, ReportableStrategicVault, Vault,
-			VaultConfig,
+			VaultConfig, VaultStrategy,
 		},
 	};
 	use frame_support::{
 		dispatch::DispatchResultWithPostInfo,
 		ensure,
 		pallet_prelude::*,
+		storage::{with_transaction, TransactionOutcome},
 		traits::{
 			fungible::{
 				Inspect as InspectNative, Mutate as MutateNative, MutateHold as MutateHoldNative,
@@ -104,7 +106,7 @@ pub mod pallet {
 		},
 		ArithmeticError, DispatchError, FixedPointNumber, Perquintill,
 	};
-	use sp_std::fmt::Debug;
+	use sp_std::{fmt::Debug, vec::Vec};
 
 	#[allow(missing_docs)]
 	pub type AssetIdOf<T> =
@@ -219,12 +221,36 @@ pub mod pallet {
 		/// avoid name collisions with other pallets and vaults.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+
+		/// The pallets running the strategies that vaults rebalance, such as `(Lending,)`.
+		type Strategy: VaultStrategy<Self::AccountId, Self::VaultId, Self::Balance>;
+
+		/// The origin allowed to approve and revoke the strategies of an asset.
+		type StrategyApprovalOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of blocks between two rebalancing of the strategies of a vault.
+		#[pallet::constant]
+		type RebalanceInterval: Get<Self::BlockNumber>;
 	}
 
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::rebalance_strategies(now, remaining_weight)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::migrate_to_v1::<T>()
+		}
+	}
+
 	/// The number of vaults, also used to generate the next vault identifier.
 	///
 	/// # Note
@@ -264,6 +290,27 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Strategies approved by governance for the vaults of an asset. The approved strategies of a
+	/// vault are rebalanced every [`RebalanceInterval`](Config::RebalanceInterval) blocks, when the
+	/// chain is idle.
+	#[pallet::storage]
+	#[pallet::getter(fn approved_strategies)]
+	pub type ApprovedStrategies<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// The block at which the strategies of a vault were last rebalanced.
+	#[pallet::storage]
+	#[pallet::getter(fn last_rebalance)]
+	pub type LastRebalance<T: Config> =
+		StorageMap<_, Twox64Concat, T::VaultId, T::BlockNumber, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -304,6 +351,31 @@ pub mod pallet {
 			/// The ID of the vault.
 			vault: T::VaultId,
 		},
+		/// Emitted after a strategy is approved for the vaults of an asset.
+		StrategyApproved {
+			/// The asset of the vaults.
+			asset_id: T::AssetId,
+			/// The account of the strategy.
+			strategy: T::AccountId,
+		},
+		/// Emitted after a strategy is no longer approved for the vaults of an asset.
+		StrategyRevoked {
+			/// The asset of the vaults.
+			asset_id: T::AssetId,
+			/// The account of the strategy.
+			strategy: T::AccountId,
+		},
+		/// Emitted after the funds of a vault are rebalanced with one of its strategies.
+		StrategyRebalanced {
+			/// The ID of the vault.
+			vault: T::VaultId,
+			/// The account of the strategy.
+			strategy: T::AccountId,
+			/// The balance of the strategy after rebalancing.
+			balance: T::Balance,
+			/// The yield realised by the strategy since its previous report.
+			realised_yield: T::Balance,
+		},
 	}
 
 	#[allow(missing_docs)]
@@ -365,6 +437,8 @@ pub mod pallet {
 		TombstoneDurationNotExceeded,
 		/// Existentially funded vaults do not require extra funds.
 		InvalidAddSurcharge,
+		/// The strategy is not approved for the vaults of the asset.
+		StrategyNotApproved,
 	}
 
 	#[pallet::call]
@@ -637,6 +711,51 @@ pub mod pallet {
 			});
 			Ok(())
 		}
+
+		/// Approves `strategy` for the vaults of `asset_id`, which then rebalance their funds with
+		/// it when it is one of their strategies.
+		///
+		/// # Emits
+		///  - Event::StrategyApproved
+		///
+		/// # Errors
+		///  - When the origin is not [`StrategyApprovalOrigin`](Config::StrategyApprovalOrigin).
+		#[pallet::weight(<T as Config>::WeightInfo::approve_strategy())]
+		pub fn approve_strategy(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			strategy: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::StrategyApprovalOrigin::ensure_origin(origin)?;
+			ApprovedStrategies::<T>::insert(asset_id, &strategy, ());
+			Self::deposit_event(Event::StrategyApproved { asset_id, strategy });
+			Ok(().into())
+		}
+
+		/// Revokes the approval of `strategy` for the vaults of `asset_id`, which stop rebalancing
+		/// their funds with it.
+		///
+		/// # Emits
+		///  - Event::StrategyRevoked
+		///
+		/// # Errors
+		///  - When the origin is not [`StrategyApprovalOrigin`](Config::StrategyApprovalOrigin).
+		///  - When `strategy` is not approved for `asset_id`.
+		#[pallet::weight(<T as Config>::WeightInfo::revoke_strategy())]
+		pub fn revoke_strategy(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			strategy: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::StrategyApprovalOrigin::ensure_origin(origin)?;
+			ensure!(
+				ApprovedStrategies::<T>::contains_key(asset_id, &strategy),
+				Error::<T>::StrategyNotApproved
+			);
+			ApprovedStrategies::<T>::remove(asset_id, &strategy);
+			Self::deposit_event(Event::StrategyRevoked { asset_id, strategy });
+			Ok(().into())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -688,6 +807,7 @@ pub mod pallet {
 							balance: T::Balance::zero(),
 							lifetime_withdrawn: T::Balance::zero(),
 							lifetime_deposited: T::Balance::zero(),
+							realised_yield: T::Balance::zero(),
 						},
 					);
 				});
@@ -885,6 +1005,104 @@ pub mod pallet {
 		fn vault_info(vault_idx: &T::VaultId) -> Result<VaultInfo<T>, DispatchError> {
 			Ok(Vaults::<T>::try_get(vault_idx).map_err(|_err| Error::<T>::VaultDoesNotExist)?)
 		}
+
+		/// Sets the balance of `strategy`, recording its growth as realised yield.
+		///
+		/// Returns the yield realised since the previous report.
+		fn do_update_strategy_report(
+			vault_id: &T::VaultId,
+			strategy: &T::AccountId,
+			balance: T::Balance,
+		) -> Result<T::Balance, DispatchError> {
+			CapitalStructure::<T>::try_mutate(vault_id, strategy, |state| {
+				let realised_yield = balance.saturating_sub(&state.balance);
+				state.realised_yield = state
+					.realised_yield
+					.checked_add(&realised_yield)
+					.ok_or(ArithmeticError::Overflow)?;
+				state.balance = balance;
+				Ok(realised_yield)
+			})
+		}
+
+		/// Rebalances the approved strategies of the vaults which were not rebalanced in the last
+		/// [`RebalanceInterval`](Config::RebalanceInterval) blocks, as long as `remaining_weight`
+		/// allows.
+		///
+		/// Returns the weight consumed.
+		pub(crate) fn rebalance_strategies(
+			now: T::BlockNumber,
+			remaining_weight: Weight,
+		) -> Weight {
+			let mut consumed_weight: Weight = 0;
+			for (vault_id, vault) in Vaults::<T>::iter() {
+				consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().reads(2));
+				if consumed_weight > remaining_weight {
+					break
+				}
+				let rebalance_due = LastRebalance::<T>::get(vault_id)
+					.and_then(|at| at.checked_add(&T::RebalanceInterval::get()))
+					.map_or(true, |due| now >= due);
+				if !rebalance_due {
+					continue
+				}
+
+				let strategies: Vec<T::AccountId> =
+					CapitalStructure::<T>::iter_key_prefix(vault_id)
+						.filter(|strategy| {
+							ApprovedStrategies::<T>::contains_key(vault.asset_id, strategy) &&
+								T::Strategy::is_strategy(strategy)
+						})
+						.collect();
+				let rebalance_weight = <T as Config>::WeightInfo::rebalance_strategy()
+					.saturating_add(T::Strategy::rebalance_weight())
+					.saturating_mul(strategies.len() as Weight)
+					.saturating_add(T::DbWeight::get().writes(1));
+				if consumed_weight.saturating_add(rebalance_weight) > remaining_weight {
+					break
+				}
+				consumed_weight = consumed_weight.saturating_add(rebalance_weight);
+
+				for strategy in strategies {
+					if let Err(error) = Self::rebalance_strategy(&vault_id, &strategy) {
+						log::error!(
+							"Strategy rebalancing failed, vault: {:?}, strategy: {:?}, error: {:?}",
+							vault_id,
+							strategy,
+							error
+						);
+					}
+				}
+				LastRebalance::<T>::insert(vault_id, now);
+			}
+			consumed_weight
+		}
+
+		/// Records the yield realised by `strategy`, then moves funds between the vault and the
+		/// strategy towards its allocation. Nothing is changed if any step fails.
+		fn rebalance_strategy(vault_id: &T::VaultId, strategy: &T::AccountId) -> DispatchResult {
+			with_transaction(|| {
+				let result = Self::do_rebalance_strategy(vault_id, strategy);
+				if result.is_ok() {
+					TransactionOutcome::Commit(result)
+				} else {
+					TransactionOutcome::Rollback(result)
+				}
+			})
+		}
+
+		fn do_rebalance_strategy(vault_id: &T::VaultId, strategy: &T::AccountId) -> DispatchResult {
+			let balance = T::Strategy::balance(vault_id, strategy)?;
+			let realised_yield = Self::do_update_strategy_report(vault_id, strategy, balance)?;
+			T::Strategy::rebalance(vault_id, strategy)?;
+			Self::deposit_event(Event::StrategyRebalanced {
+				vault: *vault_id,
+				strategy: strategy.clone(),
+				balance: CapitalStructure::<T>::get(vault_id, strategy).balance,
+				realised_yield,
+			});
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Vault for Pallet<T> {
@@ -1089,7 +1307,7 @@ pub mod pallet {
 			strategy: &Self::AccountId,
 			report: &Self::Report,
 		) -> Result<(), DispatchError> {
-			CapitalStructure::<T>::mutate(vault, strategy, |state| state.balance = *report);
+			Self::do_update_strategy_report(vault, strategy, *report)?;
 			Ok(())
 		}
 	}
@@ -0,0 +1,36 @@
+use crate::{models::StrategyOverview, CapitalStructure, Config, Pallet};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::{traits::Zero, Perquintill};
+
+/// `StrategyOverview` before the realised yield of strategies was recorded.
+#[derive(Encode, Decode)]
+pub struct StrategyOverviewV0<Balance> {
+	pub allocation: Perquintill,
+	pub balance: Balance,
+	pub lifetime_withdrawn: Balance,
+	pub lifetime_deposited: Balance,
+}
+
+/// Starts the realised yield of the existing strategies at zero.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= 1 {
+		return T::DbWeight::get().reads(1)
+	}
+	let mut translated: Weight = 0;
+	CapitalStructure::<T>::translate::<StrategyOverviewV0<T::Balance>, _>(|_, _, overview| {
+		translated += 1;
+		Some(StrategyOverview {
+			allocation: overview.allocation,
+			balance: overview.balance,
+			lifetime_withdrawn: overview.lifetime_withdrawn,
+			lifetime_deposited: overview.lifetime_deposited,
+			realised_yield: T::Balance::zero(),
+		})
+	});
+	StorageVersion::new(1).put::<Pallet<T>>();
+	T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+}
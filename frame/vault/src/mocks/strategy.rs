@@ -6,7 +6,7 @@ pub use pallet::*;
 #[frame_support::pallet]
 pub mod pallet {
 	use crate::traits::{FundsAvailability, ReportableStrategicVault, StrategicVault};
-	use composable_traits::vault::Vault;
+	use composable_traits::vault::{Vault, VaultStrategy};
 	use frame_support::{
 		pallet_prelude::*,
 		traits::fungibles::{Inspect, Mutate, Transfer},
@@ -62,6 +62,50 @@ pub mod pallet {
 		fn account_id() -> T::AccountId {
 			T::PalletId::get().into_account_truncating()
 		}
+
+		fn do_rebalance(vault: &VaultIdOf<T>) -> DispatchResult {
+			let asset_id = T::Vault::asset_id(vault)?;
+			let task = T::Vault::available_funds(vault, &Self::account_id())?;
+			let action = match task {
+				FundsAvailability::MustLiquidate => {
+					let balance = T::Currency::balance(asset_id, &Self::account_id());
+					<T::Vault as StrategicVault>::deposit(vault, &Self::account_id(), balance)?;
+					balance
+				},
+				FundsAvailability::Withdrawable(balance) => {
+					<T::Vault as StrategicVault>::withdraw(vault, &Self::account_id(), balance)?;
+					balance
+				},
+				FundsAvailability::Depositable(balance) => {
+					<T::Vault as StrategicVault>::deposit(vault, &Self::account_id(), balance)?;
+					balance
+				},
+			};
+			Self::deposit_event(Event::Rebalanced(task, action));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> VaultStrategy<T::AccountId, VaultIdOf<T>, BalanceOf<T>> for Pallet<T> {
+		fn is_strategy(strategy: &T::AccountId) -> bool {
+			*strategy == Self::account_id()
+		}
+
+		fn rebalance(vault: &VaultIdOf<T>, _strategy: &T::AccountId) -> DispatchResult {
+			Self::do_rebalance(vault)
+		}
+
+		fn balance(
+			vault: &VaultIdOf<T>,
+			_strategy: &T::AccountId,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let asset_id = T::Vault::asset_id(vault)?;
+			Ok(T::Currency::balance(asset_id, &Self::account_id()))
+		}
+
+		fn rebalance_weight() -> Weight {
+			10_000
+		}
 	}
 
 	#[pallet::call]
@@ -95,42 +139,7 @@ pub mod pallet {
 		#[pallet::weight(10_000)]
 		pub fn rebalance(origin: OriginFor<T>, vault: VaultIdOf<T>) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
-			let asset_id = T::Vault::asset_id(&vault)?;
-			let task = T::Vault::available_funds(&vault, &Self::account_id())?;
-			let action = match task {
-				FundsAvailability::MustLiquidate => {
-					let balance = T::Currency::balance(asset_id, &Self::account_id());
-					T::Currency::transfer(
-						asset_id,
-						&Self::account_id(),
-						&T::Vault::account_id(&vault),
-						balance,
-						true,
-					)?;
-					balance
-				},
-				FundsAvailability::Withdrawable(balance) => {
-					T::Currency::transfer(
-						asset_id,
-						&T::Vault::account_id(&vault),
-						&Self::account_id(),
-						balance,
-						true,
-					)?;
-					balance
-				},
-				FundsAvailability::Depositable(balance) => {
-					T::Currency::transfer(
-						asset_id,
-						&Self::account_id(),
-						&T::Vault::account_id(&vault),
-						balance,
-						true,
-					)?;
-					balance
-				},
-			};
-			Self::deposit_event(Event::Rebalanced(task, action));
+			Self::do_rebalance(&vault)?;
 			Ok(().into())
 		}
 	}
@@ -6,6 +6,7 @@ use frame_support::{
 	PalletId,
 };
 use frame_system as system;
+use frame_system::EnsureRoot;
 use num_traits::Zero;
 use orml_traits::parameter_type_with_key;
 use sp_core::H256;
@@ -84,6 +85,7 @@ parameter_types! {
 	pub const RentPerBlock: Balance = 1;
 	pub const TestPalletID: PalletId = PalletId(*b"test_pid");
 	pub const StrategyTestPalletID: PalletId = PalletId(*b"sest_pid");
+	pub const RebalanceInterval: BlockNumber = 10;
 	pub const MinimumDeposit: Balance = 0;
 	pub const MinimumWithdrawal: Balance = 0;
 	pub const TombstoneDuration: BlockNumber = 10;
@@ -107,6 +109,9 @@ impl pallet_vault::Config for Test {
 	type TombstoneDuration = TombstoneDuration;
 	type VaultId = u64;
 	type WeightInfo = ();
+	type Strategy = Strategy;
+	type StrategyApprovalOrigin = EnsureRoot<AccountId>;
+	type RebalanceInterval = RebalanceInterval;
 }
 
 parameter_type_with_key! {
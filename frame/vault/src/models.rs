@@ -23,4 +23,6 @@ pub struct StrategyOverview<Balance> {
 	pub lifetime_withdrawn: Balance,
 	/// Sum of all deposited funds.
 	pub lifetime_deposited: Balance,
+	/// Sum of the yield realised by the strategy, as the growth of its reported balance.
+	pub realised_yield: Balance,
 }
@@ -3,8 +3,8 @@ use crate::{
 		currency_factory::MockCurrencyId,
 		tests::{
 			AccountId, Balance, Balances, BlockNumber, CreationDeposit, Event, ExistentialDeposit,
-			ExtBuilder, Origin, System, Test, Tokens, TombstoneDuration, Vaults,
-			ACCOUNT_FREE_START, ALICE, BOB, CHARLIE, MINIMUM_BALANCE,
+			ExtBuilder, Origin, RebalanceInterval, StrategyTestPalletID, System, Test, Tokens,
+			TombstoneDuration, Vaults, ACCOUNT_FREE_START, ALICE, BOB, CHARLIE, MINIMUM_BALANCE,
 		},
 	},
 	models::VaultInfo,
@@ -23,10 +23,17 @@ use composable_traits::{
 };
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::fungibles::{Inspect, Mutate},
+	traits::{
+		fungibles::{Inspect, Mutate},
+		Hooks,
+	},
+	weights::Weight,
 };
 use proptest::prelude::*;
-use sp_runtime::{ArithmeticError, FixedPointNumber, Perbill, Perquintill};
+use sp_runtime::{
+	traits::AccountIdConversion, ArithmeticError, DispatchError, FixedPointNumber, Perbill,
+	Perquintill,
+};
 
 const DEFAULT_STRATEGY_SHARE: Perquintill = Perquintill::from_percent(90);
 // dependent on the previous value, both should be changed
@@ -981,3 +988,90 @@ fn do_liquidate_strategy_successfully_liquidates_a_strategy_account(
 		assert_eq!(Tokens::balance(currency_id, &strategy_account_id), 0);
 	});
 }
+
+fn strategy_pallet_account() -> AccountId {
+	StrategyTestPalletID::get().into_account_truncating()
+}
+
+#[test]
+fn approve_and_revoke_strategy() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let strategy = strategy_pallet_account();
+		assert_noop!(
+			Vaults::approve_strategy(Origin::signed(ALICE), MockCurrencyId::A, strategy),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Vaults::revoke_strategy(Origin::root(), MockCurrencyId::A, strategy),
+			Error::<Test>::StrategyNotApproved
+		);
+
+		assert_ok!(Vaults::approve_strategy(Origin::root(), MockCurrencyId::A, strategy));
+		assert_eq!(Vaults::approved_strategies(MockCurrencyId::A, strategy), Some(()));
+		System::assert_last_event(Event::Vaults(crate::Event::StrategyApproved {
+			asset_id: MockCurrencyId::A,
+			strategy,
+		}));
+
+		assert_noop!(
+			Vaults::revoke_strategy(Origin::signed(ALICE), MockCurrencyId::A, strategy),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Vaults::revoke_strategy(Origin::root(), MockCurrencyId::A, strategy));
+		assert_eq!(Vaults::approved_strategies(MockCurrencyId::A, strategy), None);
+		System::assert_last_event(Event::Vaults(crate::Event::StrategyRevoked {
+			asset_id: MockCurrencyId::A,
+			strategy,
+		}));
+	});
+}
+
+#[test]
+fn approved_strategies_are_rebalanced_when_idle() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let strategy = strategy_pallet_account();
+		let (id, _) = create_vault(strategy, MockCurrencyId::A);
+		Tokens::mint_into(MockCurrencyId::A, &ALICE, 1_000).unwrap();
+		assert_ok!(Vaults::deposit(Origin::signed(ALICE), id, 1_000));
+		assert_ok!(Vaults::approve_strategy(Origin::root(), MockCurrencyId::A, strategy));
+
+		Vaults::on_idle(1, Weight::MAX);
+		let expected_strategy_funds = DEFAULT_STRATEGY_SHARE.mul_floor(1_000);
+		assert_eq!(Tokens::balance(MockCurrencyId::A, &strategy), expected_strategy_funds);
+		assert_eq!(Vaults::last_rebalance(id), Some(1));
+
+		// the strategy generates revenue, which is recorded on the next rebalancing only
+		Tokens::mint_into(MockCurrencyId::A, &strategy, 100).unwrap();
+		Vaults::on_idle(RebalanceInterval::get(), Weight::MAX);
+		assert_eq!(CapitalStructure::<Test>::get(id, strategy).realised_yield, 0);
+
+		Vaults::on_idle(1 + RebalanceInterval::get(), Weight::MAX);
+		assert_eq!(CapitalStructure::<Test>::get(id, strategy).realised_yield, 100);
+		assert_eq!(Vaults::last_rebalance(id), Some(1 + RebalanceInterval::get()));
+		assert_eq!(
+			<Vaults as StrategicVault>::available_funds(&id, &strategy),
+			Ok(FundsAvailability::Depositable(0))
+		);
+	});
+}
+
+#[test]
+fn unapproved_strategies_are_not_rebalanced() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let strategy = strategy_pallet_account();
+		let (id, _) = create_vault(strategy, MockCurrencyId::A);
+		Tokens::mint_into(MockCurrencyId::A, &ALICE, 1_000).unwrap();
+		assert_ok!(Vaults::deposit(Origin::signed(ALICE), id, 1_000));
+
+		Vaults::on_idle(1, Weight::MAX);
+		assert_eq!(Tokens::balance(MockCurrencyId::A, &strategy), 0);
+
+		assert_ok!(Vaults::approve_strategy(Origin::root(), MockCurrencyId::A, strategy));
+		assert_ok!(Vaults::revoke_strategy(Origin::root(), MockCurrencyId::A, strategy));
+		Vaults::on_idle(1 + RebalanceInterval::get(), Weight::MAX);
+		assert_eq!(Tokens::balance(MockCurrencyId::A, &strategy), 0);
+	});
+}
@@ -18,6 +18,9 @@ pub trait WeightInfo {
 	fn add_surcharge() -> Weight;
 	fn claim_surcharge() -> Weight;
 	fn delete_tombstoned() -> Weight;
+	fn approve_strategy() -> Weight;
+	fn revoke_strategy() -> Weight;
+	fn rebalance_strategy() -> Weight;
 }
 
 pub struct SubstrateWeight<T>(PhantomData<T>);
@@ -86,6 +89,23 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Storage: Vault ApprovedStrategies (r:0 w:1)
+	fn approve_strategy() -> Weight {
+		(21_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Vault ApprovedStrategies (r:1 w:1)
+	fn revoke_strategy() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Vault ApprovedStrategies (r:1 w:0)
+	// Storage: Vault CapitalStructure (r:1 w:1)
+	fn rebalance_strategy() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 impl WeightInfo for () {
@@ -153,4 +173,21 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	// Storage: Vault ApprovedStrategies (r:0 w:1)
+	fn approve_strategy() -> Weight {
+		(21_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Vault ApprovedStrategies (r:1 w:1)
+	fn revoke_strategy() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Vault ApprovedStrategies (r:1 w:0)
+	// Storage: Vault CapitalStructure (r:1 w:1)
+	fn rebalance_strategy() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
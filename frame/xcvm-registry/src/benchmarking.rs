@@ -0,0 +1,46 @@
+//! Benchmarks and sanity tests for the XCVM registry. Only test that action do not error, not that
+//! produce positive side effects
+
+use super::*;
+use crate::{self as pallet_xcvm_registry};
+
+#[allow(unused_imports)]
+use crate::Pallet as XcvmRegistry;
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+use xcvm_core::{AssetId, BridgeSecurity, NetworkId};
+
+benchmarks! {
+	where_clause {
+		where
+			T: pallet_xcvm_registry::Config + frame_system::Config,
+			<T as pallet_xcvm_registry::Config>::LocalNetworkId: From<u32>,
+			<T as pallet_xcvm_registry::Config>::LocalAssetId: From<u128>,
+	}
+
+	register_network {
+		let network_id = NetworkId(42);
+		let local_network_id: T::LocalNetworkId = 2000_u32.into();
+	}: _(RawOrigin::Root, network_id, local_network_id, BridgeSecurity::Deterministic)
+
+	unregister_network {
+		let network_id = NetworkId(42);
+		let local_network_id: T::LocalNetworkId = 2000_u32.into();
+		XcvmRegistry::<T>::register_network(RawOrigin::Root.into(), network_id, local_network_id, BridgeSecurity::Deterministic).unwrap();
+	}: _(RawOrigin::Root, network_id)
+
+	register_asset {
+		let asset_id = AssetId(42);
+		let local_asset_id: T::LocalAssetId = 1000_u128.into();
+	}: _(RawOrigin::Root, asset_id, local_asset_id, None)
+
+	unregister_asset {
+		let asset_id = AssetId(42);
+		let local_asset_id: T::LocalAssetId = 1000_u128.into();
+		XcvmRegistry::<T>::register_asset(RawOrigin::Root.into(), asset_id, local_asset_id, Some(12)).unwrap();
+	}: _(RawOrigin::Root, asset_id)
+}
+
+impl_benchmark_test_suite!(XcvmRegistry, crate::runtime::new_test_ext(), crate::runtime::Runtime);
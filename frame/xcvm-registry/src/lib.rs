@@ -0,0 +1,255 @@
+#![cfg_attr(
+	not(test),
+	deny(
+		clippy::disallowed_methods,
+		clippy::disallowed_types,
+		clippy::indexing_slicing,
+		clippy::todo,
+		clippy::unwrap_used,
+		clippy::panic
+	)
+)] // allow in tests
+#![deny(clippy::unseparated_literal_suffix, unused_imports, dead_code)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![doc = include_str!("../README.md")]
+
+pub use pallet::*;
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarking;
+#[cfg(test)]
+mod runtime;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	pub use crate::weights::WeightInfo;
+	use codec::FullCodec;
+	use composable_traits::{currency::Exponent, xcm::assets::RemoteAssetRegistryInspect};
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo, pallet_prelude::*, traits::EnsureOrigin,
+	};
+	use frame_system::pallet_prelude::*;
+	use scale_info::TypeInfo;
+	use sp_std::{fmt::Debug, vec::Vec};
+	use xcvm_core::{AssetId, AssetInfo, BridgeSecurity, NetworkId, NetworkInfo, Registry};
+
+	/// Decimals of an asset for which neither the registration nor the assets registry tell.
+	pub const DEFAULT_DECIMALS: Exponent = 12;
+
+	/// The module configuration trait.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Identifier of a network for the local bridge, i.e. a parachain id.
+		type LocalNetworkId: FullCodec
+			+ Eq
+			+ PartialEq
+			+ Clone
+			+ MaybeSerializeDeserialize
+			+ Debug
+			+ TypeInfo;
+
+		/// Identifier for the class of local asset.
+		type LocalAssetId: FullCodec
+			+ Eq
+			+ PartialEq
+			+ Copy
+			+ MaybeSerializeDeserialize
+			+ Debug
+			+ TypeInfo;
+
+		/// Registry used to lookup the decimals of an asset when not provided on registration.
+		type AssetRegistry: RemoteAssetRegistryInspect<AssetId = Self::LocalAssetId>;
+
+		/// The origin which may register networks and assets.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// Mapping XCVM network to local network.
+	#[pallet::storage]
+	#[pallet::getter(fn network)]
+	pub type Networks<T: Config> =
+		StorageMap<_, Twox64Concat, NetworkId, NetworkInfo<T::LocalNetworkId>, OptionQuery>;
+
+	/// Mapping XCVM asset to local asset.
+	#[pallet::storage]
+	#[pallet::getter(fn asset)]
+	pub type Assets<T: Config> =
+		StorageMap<_, Twox64Concat, AssetId, AssetInfo<T::LocalAssetId>, OptionQuery>;
+
+	/// Mapping local asset to XCVM asset.
+	#[pallet::storage]
+	#[pallet::getter(fn from_local_asset)]
+	pub type LocalToAsset<T: Config> =
+		StorageMap<_, Twox64Concat, T::LocalAssetId, AssetId, OptionQuery>;
+
+	/// Used to register the statically known networks and assets under their compile time
+	/// identifiers.
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub networks: Vec<(NetworkId, NetworkInfo<T::LocalNetworkId>)>,
+		pub assets: Vec<(AssetId, AssetInfo<T::LocalAssetId>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { networks: Default::default(), assets: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (network_id, info) in &self.networks {
+				Networks::<T>::insert(network_id, info);
+			}
+			for (asset_id, info) in &self.assets {
+				LocalToAsset::<T>::insert(info.local, asset_id);
+				Assets::<T>::insert(asset_id, info);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		NetworkRegistered {
+			network_id: NetworkId,
+			local: T::LocalNetworkId,
+			security: BridgeSecurity,
+		},
+		NetworkUnregistered {
+			network_id: NetworkId,
+		},
+		AssetRegistered {
+			asset_id: AssetId,
+			local: T::LocalAssetId,
+			decimals: Exponent,
+		},
+		AssetUnregistered {
+			asset_id: AssetId,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Identifier reserved for `InvalidNetwork` or `InvalidAsset`.
+		ReservedId,
+		NetworkAlreadyRegistered,
+		NetworkNotFound,
+		AssetAlreadyRegistered,
+		/// The local asset is already mapped to another XCVM asset.
+		LocalAssetAlreadyMapped,
+		AssetNotFound,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Makes `network_id` reachable through the local bridge identifier `local`.
+		/// Raises `NetworkRegistered` event
+		///
+		/// # Parameters:
+		///
+		/// `security` - security level of the bridge used to reach the network.
+		#[pallet::weight(<T as Config>::WeightInfo::register_network())]
+		pub fn register_network(
+			origin: OriginFor<T>,
+			network_id: NetworkId,
+			local: T::LocalNetworkId,
+			security: BridgeSecurity,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(network_id != NetworkId::INVALID, Error::<T>::ReservedId);
+			ensure!(!Networks::<T>::contains_key(network_id), Error::<T>::NetworkAlreadyRegistered);
+			Networks::<T>::insert(network_id, NetworkInfo { local: local.clone(), security });
+			Self::deposit_event(Event::<T>::NetworkRegistered { network_id, local, security });
+			Ok(().into())
+		}
+
+		/// Removes the mapping of `network_id`, programs can no longer be spawned to it.
+		/// Raises `NetworkUnregistered` event
+		#[pallet::weight(<T as Config>::WeightInfo::unregister_network())]
+		pub fn unregister_network(
+			origin: OriginFor<T>,
+			network_id: NetworkId,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Networks::<T>::take(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+			Self::deposit_event(Event::<T>::NetworkUnregistered { network_id });
+			Ok(().into())
+		}
+
+		/// Maps `asset_id` to the `local` asset, bidirectionally.
+		/// Raises `AssetRegistered` event
+		///
+		/// # Parameters:
+		///
+		/// `decimals` - decimals of the local asset, if `None` taken from the assets registry
+		/// metadata, defaulting to 12.
+		#[pallet::weight(<T as Config>::WeightInfo::register_asset())]
+		pub fn register_asset(
+			origin: OriginFor<T>,
+			asset_id: AssetId,
+			local: T::LocalAssetId,
+			decimals: Option<Exponent>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(asset_id != AssetId::INVALID, Error::<T>::ReservedId);
+			ensure!(!Assets::<T>::contains_key(asset_id), Error::<T>::AssetAlreadyRegistered);
+			ensure!(!LocalToAsset::<T>::contains_key(local), Error::<T>::LocalAssetAlreadyMapped);
+			let decimals = decimals
+				.or_else(|| T::AssetRegistry::asset_to_remote(local).and_then(|x| x.decimals))
+				.unwrap_or(DEFAULT_DECIMALS);
+			Assets::<T>::insert(asset_id, AssetInfo { local, decimals });
+			LocalToAsset::<T>::insert(local, asset_id);
+			Self::deposit_event(Event::<T>::AssetRegistered { asset_id, local, decimals });
+			Ok(().into())
+		}
+
+		/// Removes the mapping of `asset_id`, in both directions.
+		/// Raises `AssetUnregistered` event
+		#[pallet::weight(<T as Config>::WeightInfo::unregister_asset())]
+		pub fn unregister_asset(
+			origin: OriginFor<T>,
+			asset_id: AssetId,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let info = Assets::<T>::take(asset_id).ok_or(Error::<T>::AssetNotFound)?;
+			LocalToAsset::<T>::remove(info.local);
+			Self::deposit_event(Event::<T>::AssetUnregistered { asset_id });
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Registry for Pallet<T> {
+		type LocalNetworkId = T::LocalNetworkId;
+		type LocalAssetId = T::LocalAssetId;
+
+		fn network(network: NetworkId) -> Option<NetworkInfo<Self::LocalNetworkId>> {
+			Networks::<T>::get(network)
+		}
+
+		fn asset(asset: AssetId) -> Option<AssetInfo<Self::LocalAssetId>> {
+			Assets::<T>::get(asset)
+		}
+
+		fn from_local_asset(local: Self::LocalAssetId) -> Option<AssetId> {
+			LocalToAsset::<T>::get(local)
+		}
+	}
+}
@@ -0,0 +1,126 @@
+use crate::{self as pallet_xcvm_registry, weights::SubstrateWeight};
+use composable_traits::xcm::assets::{ForeignMetadata, RemoteAssetRegistryInspect};
+use cumulus_primitives_core::ParaId;
+use frame_support::{
+	ord_parameter_types, parameter_types,
+	traits::{EnsureOneOf, Everything},
+};
+use frame_system::{self as system, EnsureRoot, EnsureSignedBy};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use xcvm_core::{Asset, AssetInfo, BridgeSecurity, Network, NetworkInfo, Picasso, PICA};
+
+pub type AccountId = u32;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+
+pub const ROOT: AccountId = 0_u32;
+pub const ALICE: AccountId = 1_u32;
+
+/// Local asset for which the assets registry knows the decimals.
+pub const KNOWN_LOCAL_ASSET: AssetId = 1000;
+pub const KNOWN_LOCAL_ASSET_DECIMALS: u32 = 8;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+
+		XcvmRegistry: pallet_xcvm_registry::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Runtime {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+ord_parameter_types! {
+	pub const RootAccount: AccountId = ROOT;
+}
+
+pub type AssetId = u128;
+pub type NetworkId = u32;
+
+pub struct AssetsRegistry;
+
+impl RemoteAssetRegistryInspect for AssetsRegistry {
+	type AssetId = AssetId;
+	type AssetNativeLocation = ();
+	type Balance = u64;
+
+	fn asset_to_remote(asset_id: Self::AssetId) -> Option<ForeignMetadata<()>> {
+		(asset_id == KNOWN_LOCAL_ASSET)
+			.then(|| ForeignMetadata { decimals: Some(KNOWN_LOCAL_ASSET_DECIMALS), location: () })
+	}
+
+	fn location_to_asset(_location: ()) -> Option<Self::AssetId> {
+		None
+	}
+
+	fn min_xcm_fee(_parachain_id: ParaId, _remote_asset_id: ()) -> Option<Self::Balance> {
+		None
+	}
+}
+
+impl pallet_xcvm_registry::Config for Runtime {
+	type Event = Event;
+	type LocalNetworkId = NetworkId;
+	type LocalAssetId = AssetId;
+	type AssetRegistry = AssetsRegistry;
+	type UpdateOrigin = EnsureOneOf<
+		EnsureSignedBy<RootAccount, AccountId>, // for tests
+		EnsureRoot<AccountId>,                  // for benchmarks
+	>;
+	type WeightInfo = SubstrateWeight<Self>;
+}
+
+// Build genesis storage according to the mock runtime, with the statically known `Picasso` and
+// `PICA` registered.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	pallet_xcvm_registry::GenesisConfig::<Runtime> {
+		networks: vec![(
+			Picasso::ID,
+			NetworkInfo { local: 2087, security: BridgeSecurity::Deterministic },
+		)],
+		assets: vec![(PICA::ID, AssetInfo { local: 1, decimals: 12 })],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	storage.into()
+}
@@ -0,0 +1,173 @@
+use crate::{runtime::*, Error};
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use xcvm_core::{
+	Asset, AssetId, AssetInfo, BridgeSecurity, Ethereum, Network, NetworkId, NetworkInfo, Picasso,
+	Registry, ETH, PICA,
+};
+
+#[test]
+fn genesis_registers_static_ids() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			<XcvmRegistry as Registry>::network(Picasso::ID),
+			Some(NetworkInfo { local: 2087, security: BridgeSecurity::Deterministic })
+		);
+		assert_eq!(
+			<XcvmRegistry as Registry>::asset(PICA::ID),
+			Some(AssetInfo { local: 1, decimals: 12 })
+		);
+		assert_eq!(<XcvmRegistry as Registry>::from_local_asset(1), Some(PICA::ID));
+		assert_eq!(<XcvmRegistry as Registry>::network(Ethereum::ID), None);
+	});
+}
+
+#[test]
+fn register_network() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(XcvmRegistry::register_network(
+			Origin::signed(ROOT),
+			Ethereum::ID,
+			3000,
+			BridgeSecurity::Optimistic
+		));
+		assert_eq!(
+			<XcvmRegistry as Registry>::network(Ethereum::ID),
+			Some(NetworkInfo { local: 3000, security: BridgeSecurity::Optimistic })
+		);
+		System::assert_last_event(Event::XcvmRegistry(crate::Event::NetworkRegistered {
+			network_id: Ethereum::ID,
+			local: 3000,
+			security: BridgeSecurity::Optimistic,
+		}));
+		assert_noop!(
+			XcvmRegistry::register_network(
+				RawOrigin::Root.into(),
+				Ethereum::ID,
+				3001,
+				BridgeSecurity::Optimistic
+			),
+			Error::<Runtime>::NetworkAlreadyRegistered
+		);
+
+		assert_ok!(XcvmRegistry::unregister_network(Origin::signed(ROOT), Ethereum::ID));
+		assert_eq!(<XcvmRegistry as Registry>::network(Ethereum::ID), None);
+		assert_noop!(
+			XcvmRegistry::unregister_network(Origin::signed(ROOT), Ethereum::ID),
+			Error::<Runtime>::NetworkNotFound
+		);
+	});
+}
+
+#[test]
+fn register_new_network_without_protocol_release() {
+	new_test_ext().execute_with(|| {
+		let network_id = NetworkId(42);
+		assert_ok!(XcvmRegistry::register_network(
+			Origin::signed(ROOT),
+			network_id,
+			4000,
+			BridgeSecurity::Probabilistic
+		));
+		assert_eq!(
+			<XcvmRegistry as Registry>::network(network_id),
+			Some(NetworkInfo { local: 4000, security: BridgeSecurity::Probabilistic })
+		);
+	});
+}
+
+#[test]
+fn reserved_ids_cannot_be_registered() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XcvmRegistry::register_network(
+				Origin::signed(ROOT),
+				NetworkId::INVALID,
+				3000,
+				BridgeSecurity::Deterministic
+			),
+			Error::<Runtime>::ReservedId
+		);
+		assert_noop!(
+			XcvmRegistry::register_asset(Origin::signed(ROOT), AssetId::INVALID, 2, None),
+			Error::<Runtime>::ReservedId
+		);
+	});
+}
+
+#[test]
+fn only_update_origin_can_register() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XcvmRegistry::register_network(
+				Origin::signed(ALICE),
+				Ethereum::ID,
+				3000,
+				BridgeSecurity::Deterministic
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XcvmRegistry::register_asset(Origin::signed(ALICE), ETH::ID, 2, None),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn register_asset() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(XcvmRegistry::register_asset(Origin::signed(ROOT), ETH::ID, 2, Some(18)));
+		assert_eq!(
+			<XcvmRegistry as Registry>::asset(ETH::ID),
+			Some(AssetInfo { local: 2, decimals: 18 })
+		);
+		assert_eq!(<XcvmRegistry as Registry>::from_local_asset(2), Some(ETH::ID));
+		System::assert_last_event(Event::XcvmRegistry(crate::Event::AssetRegistered {
+			asset_id: ETH::ID,
+			local: 2,
+			decimals: 18,
+		}));
+
+		assert_noop!(
+			XcvmRegistry::register_asset(Origin::signed(ROOT), ETH::ID, 3, None),
+			Error::<Runtime>::AssetAlreadyRegistered
+		);
+		assert_noop!(
+			XcvmRegistry::register_asset(Origin::signed(ROOT), AssetId(42), 2, None),
+			Error::<Runtime>::LocalAssetAlreadyMapped
+		);
+
+		assert_ok!(XcvmRegistry::unregister_asset(Origin::signed(ROOT), ETH::ID));
+		assert_eq!(<XcvmRegistry as Registry>::asset(ETH::ID), None);
+		assert_eq!(<XcvmRegistry as Registry>::from_local_asset(2), None);
+		assert_noop!(
+			XcvmRegistry::unregister_asset(Origin::signed(ROOT), ETH::ID),
+			Error::<Runtime>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn asset_decimals_fallback() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XcvmRegistry::register_asset(
+			Origin::signed(ROOT),
+			AssetId(42),
+			KNOWN_LOCAL_ASSET,
+			None
+		));
+		assert_eq!(
+			<XcvmRegistry as Registry>::asset(AssetId(42)),
+			Some(AssetInfo { local: KNOWN_LOCAL_ASSET, decimals: KNOWN_LOCAL_ASSET_DECIMALS })
+		);
+
+		assert_ok!(XcvmRegistry::register_asset(Origin::signed(ROOT), AssetId(43), 43, None));
+		assert_eq!(
+			<XcvmRegistry as Registry>::asset(AssetId(43)),
+			Some(AssetInfo { local: 43, decimals: crate::DEFAULT_DECIMALS })
+		);
+	});
+}
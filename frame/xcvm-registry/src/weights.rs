@@ -0,0 +1,57 @@
+#![allow(unused_parens, unused_imports, clippy::unnecessary_cast)]
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+// The weight info trait for `pallet_xcvm_registry`.
+pub trait WeightInfo {
+	fn register_network() -> Weight;
+	fn unregister_network() -> Weight;
+	fn register_asset() -> Weight;
+	fn unregister_asset() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn register_network() -> Weight {
+		0
+	}
+
+	fn unregister_network() -> Weight {
+		0
+	}
+
+	fn register_asset() -> Weight {
+		0
+	}
+
+	fn unregister_asset() -> Weight {
+		0
+	}
+}
+
+/// Weights for pallet_xcvm_registry using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn register_network() -> Weight {
+		(9_958_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn unregister_network() -> Weight {
+		(9_958_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn register_asset() -> Weight {
+		(12_416_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn unregister_asset() -> Weight {
+		(12_416_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
@@ -21,8 +21,8 @@ use crate::{
 	client::{FullBackend, FullClient},
 	runtime::{
 		assets::ExtendWithAssetsApi, crowdloan_rewards::ExtendWithCrowdloanRewardsApi,
-		ibc::ExtendWithIbcApi, lending::ExtendWithLendingApi, pablo::ExtendWithPabloApi,
-		BaseHostRuntimeApis,
+		fnft::ExtendWithFnftApi, ibc::ExtendWithIbcApi, lending::ExtendWithLendingApi,
+		oracle::ExtendWithOracleApi, pablo::ExtendWithPabloApi, BaseHostRuntimeApis,
 	},
 };
 
@@ -66,6 +66,8 @@ where
 			+ ExtendWithCrowdloanRewardsApi<RuntimeApi, Executor>
 			+ ExtendWithPabloApi<RuntimeApi, Executor>
 			+ ExtendWithLendingApi<RuntimeApi, Executor>
+			+ ExtendWithFnftApi<RuntimeApi, Executor>
+			+ ExtendWithOracleApi<RuntimeApi, Executor>
 			+ ExtendWithIbcApi<RuntimeApi, Executor>,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
@@ -97,6 +99,16 @@ where
 		deps.clone(),
 	)?;
 
+	<FullClient<RuntimeApi, Executor> as ProvideRuntimeApi<OpaqueBlock>>::Api::extend_with_fnft_api(
+		&mut io,
+		deps.clone(),
+	)?;
+
+	<FullClient<RuntimeApi, Executor> as ProvideRuntimeApi<OpaqueBlock>>::Api::extend_with_oracle_api(
+		&mut io,
+		deps.clone(),
+	)?;
+
 	<FullClient<RuntimeApi, Executor> as ProvideRuntimeApi<OpaqueBlock>>::Api::extend_with_ibc_api(
 		&mut io, deps,
 	)?;
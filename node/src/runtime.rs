@@ -2,8 +2,10 @@ use assets_rpc::{Assets, AssetsApiServer};
 use common::{AccountId, Balance, Index, OpaqueBlock};
 use crowdloan_rewards_rpc::{CrowdloanRewards, CrowdloanRewardsApiServer};
 use cumulus_primitives_core::CollectCollationInfo;
+use fnft_rpc::{Fnft, FnftApiServer};
 use ibc_rpc::{IbcApiServer, IbcRpcHandler};
 use lending_rpc::{Lending, LendingApiServer};
+use oracle_rpc::{Oracle, OracleApiServer};
 use pablo_rpc::{Pablo, PabloApiServer};
 use pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi;
 use sp_api::{ApiExt, Metadata, StateBackend};
@@ -224,6 +226,42 @@ define_trait! {
 		}
 	}
 
+	mod fnft {
+		pub trait ExtendWithFnftApi {
+			fn extend_with_fnft_api(io, deps);
+		}
+
+		#[cfg(feature = "composable")]
+		impl for composable_runtime {}
+
+		impl for picasso_runtime {}
+
+		#[cfg(feature = "dali")]
+		impl for dali_runtime {
+			fn (io, deps) {
+				io.merge(Fnft::new(deps.client).into_rpc())
+			}
+		}
+	}
+
+	mod oracle {
+		pub trait ExtendWithOracleApi {
+			fn extend_with_oracle_api(io, deps);
+		}
+
+		#[cfg(feature = "composable")]
+		impl for composable_runtime {}
+
+		impl for picasso_runtime {}
+
+		#[cfg(feature = "dali")]
+		impl for dali_runtime {
+			fn (io, deps) {
+				io.merge(Oracle::new(deps.client).into_rpc())
+			}
+		}
+	}
+
 	mod ibc {
 		pub trait ExtendWithIbcApi {
 			fn extend_with_ibc_api(io, deps);
@@ -31,8 +31,8 @@ use crate::{
 	rpc,
 	runtime::{
 		assets::ExtendWithAssetsApi, crowdloan_rewards::ExtendWithCrowdloanRewardsApi,
-		ibc::ExtendWithIbcApi, lending::ExtendWithLendingApi, pablo::ExtendWithPabloApi,
-		BaseHostRuntimeApis,
+		fnft::ExtendWithFnftApi, ibc::ExtendWithIbcApi, lending::ExtendWithLendingApi,
+		pablo::ExtendWithPabloApi, BaseHostRuntimeApis,
 	},
 };
 
@@ -287,6 +287,7 @@ where
 		+ ExtendWithCrowdloanRewardsApi<RuntimeApi, Executor>
 		+ ExtendWithPabloApi<RuntimeApi, Executor>
 		+ ExtendWithLendingApi<RuntimeApi, Executor>
+		+ ExtendWithFnftApi<RuntimeApi, Executor>
 		+ ExtendWithIbcApi<RuntimeApi, Executor>,
 	StateBackendFor<FullBackend, OpaqueBlock>: StateBackend<BlakeTwo256>,
 	Executor: NativeExecutionDispatch + 'static,
@@ -48,6 +48,8 @@ use composable_traits::{
 	assets::Asset,
 	defi::{CurrencyPair, Rate},
 	dex::{Amm, PriceAggregate, RemoveLiquiditySimulationResult},
+	lending::math::{InterestRateModel, InterestRatePoint},
+	oracle::{CurrentPrice, Ohlc, OracleAccuracy, Price},
 };
 use primitives::currency::{CurrencyId, ValidateCurrencyId};
 use sp_api::impl_runtime_apis;
@@ -90,7 +92,7 @@ use scale_info::TypeInfo;
 use sp_runtime::AccountId32;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{FixedPointNumber, Perbill, Permill, Perquintill};
+pub use sp_runtime::{FixedPointNumber, Perbill, Percent, Permill, Perquintill};
 use sp_std::{collections::btree_map::BTreeMap, fmt::Debug, vec::Vec};
 use system::{
 	limits::{BlockLength, BlockWeights},
@@ -469,6 +471,7 @@ parameter_types! {
 	pub const TwapWindow: u16 = 3;
 	pub const OraclePalletId: PalletId = PalletId(*b"plt_orac");
 	pub const MsPerBlock: u64 = MILLISECS_PER_BLOCK as u64;
+	pub const MaxPriceFallbacks: u32 = 4;
 }
 
 impl oracle::Config for Runtime {
@@ -495,6 +498,9 @@ impl oracle::Config for Runtime {
 	type Moment = Moment;
 	type Time = Timestamp;
 	type PalletId = OraclePalletId;
+	type PoolId = PoolId;
+	type PoolTwap = Pablo;
+	type MaxFallbacks = MaxPriceFallbacks;
 }
 
 // Parachain stuff.
@@ -716,7 +722,12 @@ impl pallet_fnft::Config for Runtime {
 	type ProxyType = ProxyType;
 	type AccountProxy = Proxy;
 	type ProxyTypeSelector = FnftAccountProxyType;
+	type AssetId = CurrencyId;
+	type Balance = Balance;
+	type Assets = Assets;
+	type FinancialNftProtocol = StakingRewards;
 	type PalletId = FnftPalletId;
+	type WeightInfo = weights::pallet_fnft::WeightInfo<Runtime>;
 }
 
 parameter_types! {
@@ -744,6 +755,7 @@ parameter_types! {
 	pub const VaultMinimumWithdrawal: Balance = 10_000;
 	pub const VaultPalletId: PalletId = PalletId(*b"cubic___");
 	pub const TombstoneDuration: BlockNumber = DAYS * 7;
+	pub const VaultRebalanceInterval: BlockNumber = HOURS;
 }
 
 impl vault::Config for Runtime {
@@ -764,6 +776,9 @@ impl vault::Config for Runtime {
 	type TombstoneDuration = TombstoneDuration;
 	type VaultId = u64;
 	type WeightInfo = weights::vault::WeightInfo<Runtime>;
+	type Strategy = Lending;
+	type StrategyApprovalOrigin = EnsureRootOrHalfNativeCouncil;
+	type RebalanceInterval = VaultRebalanceInterval;
 }
 
 impl currency_factory::Config for Runtime {
@@ -852,10 +867,80 @@ pub struct BaseCallFilter;
 impl Contains<Call> for BaseCallFilter {
 	fn contains(call: &Call) -> bool {
 		!(call_filter::Pallet::<Runtime>::contains(call) ||
+			UnbenchmarkedCalls::contains(call) ||
 			matches!(call, Call::Tokens(_) | Call::Indices(_) | Call::Treasury(_)))
 	}
 }
 
+/// Calls whose weights are still estimated by hand, kept out of the runtime until they are
+/// benchmarked.
+pub struct UnbenchmarkedCalls;
+impl Contains<Call> for UnbenchmarkedCalls {
+	fn contains(call: &Call) -> bool {
+		matches!(
+			call,
+			// concentrated liquidity pools
+			Call::Pablo(
+				pablo::Call::create {
+					pool: pablo::PoolInitConfiguration::ConcentratedLiquidity { .. }
+				} | pablo::Call::open_position { .. } |
+					pablo::Call::close_position { .. } |
+					pablo::Call::collect_fees { .. }
+			) |
+			// multi-asset pools
+			Call::Pablo(
+				pablo::Call::create { pool: pablo::PoolInitConfiguration::MultiAsset { .. } } |
+					pablo::Call::add_liquidity_assets { .. } |
+					pablo::Call::remove_liquidity_assets { .. } |
+					pablo::Call::remove_liquidity_single_asset { .. }
+			) |
+			// limit orders
+			Call::Pablo(
+				pablo::Call::place_order { .. } |
+					pablo::Call::cancel_order { .. } |
+					pablo::Call::fill_order { .. }
+			) |
+			// circuit breaker and price fallbacks
+			Call::Oracle(
+				oracle::Call::set_circuit_breaker { .. } |
+					oracle::Call::unfreeze_price { .. } |
+					oracle::Call::set_price_fallbacks { .. }
+			) |
+			// commit-reveal price submission
+			Call::Oracle(
+				oracle::Call::set_commit_reveal { .. } |
+					oracle::Call::commit_price { .. } |
+					oracle::Call::reveal_price { .. }
+			) |
+			// XCM liquidations, no position can be sent to an engine which can not respond
+			Call::Liquidations(
+				liquidations::Call::add_liquidation_strategy {
+					configuraiton: liquidations::LiquidationStrategyConfiguration::Xcm(_)
+				} | liquidations::Call::xcm_sell_response { .. }
+			) |
+			// partial liquidations
+			Call::Lending(lending::Call::liquidate_borrow { .. }) |
+			// cross-margin accounts
+			Call::Lending(
+				lending::Call::enter_cross_margin { .. } | lending::Call::exit_cross_margin { .. }
+			) |
+			// flash loans
+			Call::Lending(lending::Call::flash_loan { .. }) |
+			// interest rate model updates
+			Call::Lending(lending::Call::update_interest_rate_model { .. }) |
+			// vault strategies, nothing is rebalanced until a strategy is approved
+			Call::Vault(vault::Call::approve_strategy { .. } | vault::Call::revoke_strategy { .. }) |
+			// claiming and compounding staking rewards
+			Call::StakingRewards(
+				pallet_staking_rewards::Call::claim { .. } |
+					pallet_staking_rewards::Call::compound { .. }
+			) |
+			// financial NFT transfers, approvals and sales
+			Call::Fnft(_)
+		)
+	}
+}
+
 impl call_filter::Config for Runtime {
 	type Event = Event;
 	type UpdateOrigin = EnsureRootOrOneThirdNativeTechnical;
@@ -950,6 +1035,7 @@ pub type OrderId = u128;
 
 parameter_types! {
 	pub const LiquidationsPalletId: PalletId = PalletId(*b"liqdatns");
+	pub const XcmSellRequestWeight: u64 = 1_000_000_000;
 }
 
 impl liquidations::Config for Runtime {
@@ -962,7 +1048,14 @@ impl liquidations::Config for Runtime {
 	type PalletId = LiquidationsPalletId;
 	type CanModifyStrategies = EnsureRootOrHalfNativeCouncil;
 	type XcmSender = XcmRouter;
+	type XcmOrigin = Origin;
 	type MaxLiquidationStrategiesAmount = ConstU32<10>;
+	type Amm = Pablo;
+	type PoolId = PoolId;
+	type Oracle = Oracle;
+	type MultiCurrency = Assets;
+	type ParachainId = ParachainInfo;
+	type XcmSellRequestWeight = XcmSellRequestWeight;
 }
 
 parameter_types! {
@@ -970,6 +1063,9 @@ parameter_types! {
 	pub LendingPalletId: PalletId = PalletId(*b"liqiudat");
 	pub OracleMarketCreationStake: Balance = 300;
 	pub const MaxLiquidationBatchSize: u32 = 1000;
+	pub const MaxCrossMarginMarkets: u32 = 5;
+	/// 0.09% of the amount lent.
+	pub const FlashLoanFee: Perquintill = Perquintill::from_parts(900_000_000_000_000);
 }
 
 impl lending::Config for Runtime {
@@ -989,6 +1085,10 @@ impl lending::Config for Runtime {
 	type PalletId = LendingPalletId;
 	type NativeCurrency = Balances;
 	type MaxLiquidationBatchSize = MaxLiquidationBatchSize;
+	type MaxCrossMarginMarkets = MaxCrossMarginMarkets;
+	type Call = Call;
+	type FlashLoanFee = FlashLoanFee;
+	type UpdateInterestRateModelOrigin = EnsureRootOrHalfNativeCouncil;
 	type WeightToFee = WeightToFee;
 }
 
@@ -1001,6 +1101,9 @@ parameter_types! {
   pub TWAPInterval: u64 = (MILLISECS_PER_BLOCK as u64) * 10;
   pub const MaxStakingRewardPools: u32 = 10;
   pub const MillisecsPerBlock: u32 = MILLISECS_PER_BLOCK;
+  pub const MaxAssetsPerPool: u32 = 8;
+  pub const MaxOrdersPerBlock: u32 = 16;
+  pub const MaxSwapSteps: u32 = 64;
 }
 
 impl pablo::Config for Runtime {
@@ -1030,6 +1133,12 @@ impl pablo::Config for Runtime {
 	type ManageStaking = StakingRewards;
 	type ProtocolStaking = StakingRewards;
 	type MsPerBlock = MillisecsPerBlock;
+	type FinancialNftInstanceId = FinancialNftInstanceId;
+	type FinancialNft = Fnft;
+	type MaxAssetsPerPool = MaxAssetsPerPool;
+	type OrderId = u128;
+	type MaxOrdersPerBlock = MaxOrdersPerBlock;
+	type MaxSwapSteps = MaxSwapSteps;
 }
 
 parameter_types! {
@@ -1274,6 +1383,7 @@ mod benches {
 		[assets_registry, AssetsRegistry]
 		[pablo, Pablo]
 		[pallet_staking_rewards, StakingRewards]
+		[pallet_fnft, Fnft]
 		[pallet_account_proxy, Proxy]
 		[dex_router, DexRouter]
     // TODO: Broken
@@ -1292,6 +1402,25 @@ impl_runtime_apis! {
 				// 	.unwrap_or_else(Rate::zero)
 			)
 		}
+
+		fn simulate_interest_rate_model(
+			interest_rate_model: InterestRateModel,
+			utilization_step: Percent,
+		) -> Option<Vec<InterestRatePoint>> {
+			Lending::simulate_interest_rate_model(interest_rate_model, utilization_step)
+		}
+	}
+
+	impl fnft_runtime_api::FnftRuntimeApi<Block, CurrencyId, FinancialNftInstanceId, Balance> for Runtime {
+		fn value_of(
+			collection: CurrencyId,
+			instance: FinancialNftInstanceId,
+		) -> Vec<(CurrencyId, SafeRpcWrapper<Balance>)> {
+			Fnft::value_of(&collection, &instance)
+				.into_iter()
+				.map(|(asset_id, value)| (asset_id, SafeRpcWrapper(value)))
+				.collect()
+		}
 	}
 
 	impl assets_runtime_api::AssetsRuntimeApi<Block, CurrencyId, AccountId, Balance> for Runtime {
@@ -1313,6 +1442,65 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl oracle_runtime_api::OracleRuntimeApi<Block, CurrencyId, Balance, BlockNumber, AccountId> for Runtime {
+		fn current_price(
+			asset_id: SafeRpcWrapper<CurrencyId>,
+		) -> Option<CurrentPrice<SafeRpcWrapper<Balance>, SafeRpcWrapper<BlockNumber>>> {
+			Oracle::current_price(asset_id.0).map(|price| CurrentPrice {
+				price: SafeRpcWrapper(price.price),
+				block: SafeRpcWrapper(price.block),
+				frozen: price.frozen,
+				fallback: price.fallback,
+			})
+		}
+
+		fn price_history(
+			asset_id: SafeRpcWrapper<CurrencyId>,
+		) -> Vec<Price<SafeRpcWrapper<Balance>, SafeRpcWrapper<BlockNumber>>> {
+			Oracle::price_history(asset_id.0)
+				.into_iter()
+				.map(|price| Price { price: SafeRpcWrapper(price.price), block: SafeRpcWrapper(price.block) })
+				.collect()
+		}
+
+		fn twap(
+			asset_id: SafeRpcWrapper<CurrencyId>,
+			window: SafeRpcWrapper<BlockNumber>,
+		) -> Option<SafeRpcWrapper<Balance>> {
+			Oracle::twap_over_window(asset_id.0, window.0).map(SafeRpcWrapper)
+		}
+
+		fn ohlc(
+			asset_id: SafeRpcWrapper<CurrencyId>,
+			bucket_size: SafeRpcWrapper<BlockNumber>,
+		) -> Vec<Ohlc<SafeRpcWrapper<Balance>, SafeRpcWrapper<BlockNumber>>> {
+			Oracle::ohlc(asset_id.0, bucket_size.0)
+				.into_iter()
+				.map(|bucket| Ohlc {
+					start: SafeRpcWrapper(bucket.start),
+					open: SafeRpcWrapper(bucket.open),
+					high: SafeRpcWrapper(bucket.high),
+					low: SafeRpcWrapper(bucket.low),
+					close: SafeRpcWrapper(bucket.close),
+				})
+				.collect()
+		}
+
+		fn oracle_accuracy(
+			asset_id: SafeRpcWrapper<CurrencyId>,
+		) -> Vec<OracleAccuracy<SafeRpcWrapper<AccountId>>> {
+			Oracle::oracle_accuracy(asset_id.0)
+				.into_iter()
+				.map(|accuracy| OracleAccuracy {
+					oracle: SafeRpcWrapper(accuracy.oracle),
+					answers: accuracy.answers,
+					slashes: accuracy.slashes,
+					average_accuracy: accuracy.average_accuracy,
+				})
+				.collect()
+		}
+	}
+
 	impl pablo_runtime_api::PabloRuntimeApi<Block, AccountId, PoolId, CurrencyId, Balance> for Runtime {
 		fn prices_for(
 			pool_id: PoolId,
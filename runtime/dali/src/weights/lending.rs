@@ -121,24 +121,98 @@ impl<T: frame_system::Config> lending::WeightInfo for WeightInfo<T> {
 			.saturating_add((27_417_000 as Weight).saturating_mul(b as Weight))
 			.saturating_add(T::DbWeight::get().reads(9 as Weight))
 	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Lending Markets (r:1 w:0)
+	// Storage: Oracle Prices (r:2 w:0)
+	// Storage: Oracle PriceHistory (r:2 w:0)
+	// Storage: Vault Vaults (r:1 w:0)
+	// Storage: Lending AccountCollateral (r:1 w:1)
+	// Storage: Lending DebtTokenForMarket (r:1 w:0)
+	// Storage: Lending DebtIndex (r:1 w:1)
+	// Storage: Lending BorrowIndex (r:1 w:0)
+	// Storage: Tokens Accounts (r:5 w:5)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	// Storage: Lending BorrowTimestamp (r:0 w:1)
+	// Storage: Lending BorrowRent (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn liquidate_borrow() -> Weight {
+		(268_404_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(20 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Lending Markets (r:1 w:0)
+	// Storage: Lending CrossMarginMarkets (r:1 w:1)
+	// Storage: Lending CrossMarginAccounts (r:0 w:1)
+	fn enter_cross_margin() -> Weight {
+		(24_170_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Lending CrossMarginMarkets (r:1 w:1)
+	// Storage: Lending CrossMarginAccounts (r:0 w:1)
+	// Storage: Lending Markets (r:1 w:0)
+	// Storage: Lending AccountCollateral (r:1 w:0)
+	// Storage: Oracle Prices (r:2 w:0)
+	// Storage: Oracle PriceHistory (r:2 w:0)
+	// Storage: Vault Vaults (r:1 w:0)
+	// Storage: Lending DebtTokenForMarket (r:1 w:0)
+	// Storage: Lending DebtIndex (r:1 w:0)
+	fn exit_cross_margin(m: u32, ) -> Weight {
+		(35_884_000 as Weight)
+			.saturating_add((63_519_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((9 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Lending FlashLoanInProgress (r:1 w:1)
+	// Storage: Lending Markets (r:1 w:0)
+	// Storage: Vault Vaults (r:1 w:0)
+	// Storage: Tokens Accounts (r:3 w:3)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Vault CapitalStructure (r:1 w:1)
+	fn flash_loan() -> Weight {
+		(91_758_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Lending FlashLoanInProgress (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Lending DebtTokenForMarket (r:1 w:0)
+	// Storage: Tokens Accounts (r:2 w:1)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	// Storage: Lending Markets (r:1 w:1)
+	// Storage: Vault Vaults (r:1 w:0)
+	// Storage: Lending InterestAccruedAt (r:1 w:1)
+	// Storage: Lending LastBlockTimestamp (r:1 w:0)
+	// Storage: Lending BorrowIndex (r:1 w:1)
+	fn update_interest_rate_model() -> Weight {
+		(101_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
 	// Storage: Timestamp Now (r:1 w:0)
 	fn now() -> Weight {
 		(3_697_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
 	// Storage: Lending DebtTokenForMarket (r:1 w:0)
 	// Storage: Tokens Accounts (r:2 w:0)
 	// Storage: Tokens TotalIssuance (r:1 w:0)
 	// Storage: Lending Markets (r:1 w:1)
 	// Storage: Vault Vaults (r:1 w:0)
+	// Storage: Lending InterestAccruedAt (r:1 w:1)
 	// Storage: Lending LastBlockTimestamp (r:1 w:0)
 	// Storage: Lending BorrowIndex (r:1 w:1)
 	fn accrue_interest(x: u32, ) -> Weight {
 		(79_623_000 as Weight)
-			// Standard Error: 0
 			.saturating_add((20_000 as Weight).saturating_mul(x as Weight))
-			.saturating_add(T::DbWeight::get().reads(8 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn account_id() -> Weight {
 		(1_369_000 as Weight)
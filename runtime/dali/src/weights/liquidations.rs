@@ -52,4 +52,13 @@ impl<T: frame_system::Config> liquidations::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(x as Weight)))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Liquidations XcmSellOrders (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn xcm_sell_response() -> Weight {
+		(58_307_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 }
@@ -22,6 +22,7 @@ pub mod ibc_transfer;
 pub mod lending;
 pub mod liquidations;
 pub mod pablo;
+pub mod pallet_fnft;
 pub mod pallet_ibc;
 pub mod pallet_staking_rewards;
 pub mod session;
@@ -113,4 +113,79 @@ impl<T: frame_system::Config> oracle::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Oracle AssetsInfo (r:1 w:1)
+	fn set_circuit_breaker() -> Weight {
+		(19_240_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Oracle FrozenPrices (r:1 w:1)
+	// Storage: Oracle PriceHistory (r:1 w:1)
+	// Storage: Oracle Prices (r:0 w:1)
+	fn unfreeze_price() -> Weight {
+		(27_315_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Oracle AssetsInfo (r:1 w:0)
+	// Storage: Oracle PriceFallbacks (r:0 w:1)
+	fn set_price_fallbacks(s: u32, ) -> Weight {
+		(20_106_000 as Weight)
+			.saturating_add((98_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Oracle AssetsInfo (r:1 w:0)
+	// Storage: Oracle PriceCommitments (r:1 w:1)
+	// Storage: Oracle AnswerInTransit (r:1 w:1)
+	// Storage: Oracle CommitRevealPeriods (r:0 w:1)
+	fn set_commit_reveal(p: u32, ) -> Weight {
+		(19_832_000 as Weight)
+			.saturating_add((5_417_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Oracle CommitRevealPeriods (r:1 w:0)
+	// Storage: Oracle OracleStake (r:1 w:0)
+	// Storage: Oracle Prices (r:1 w:0)
+	// Storage: Oracle AssetsInfo (r:1 w:0)
+	// Storage: Oracle AnswerInTransit (r:1 w:1)
+	// Storage: Oracle PrePrices (r:1 w:0)
+	// Storage: Oracle PriceCommitments (r:1 w:1)
+	fn commit_price(p: u32, ) -> Weight {
+		(58_914_000 as Weight)
+			.saturating_add((268_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Oracle CommitRevealPeriods (r:1 w:0)
+	// Storage: Oracle PriceCommitments (r:1 w:1)
+	// Storage: Oracle PrePrices (r:1 w:1)
+	fn reveal_price(p: u32, ) -> Weight {
+		(43_526_000 as Weight)
+			.saturating_add((297_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Oracle PriceCommitments (r:1 w:1)
+	// Storage: Oracle OracleAnswerStatistics (r:1 w:1)
+	// Storage: Oracle OracleStake (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Oracle AnswerInTransit (r:1 w:1)
+	fn close_commit_reveal_round(p: u32, ) -> Weight {
+		(9_671_000 as Weight)
+			.saturating_add((38_902_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(p as Weight)))
+	}
 }
@@ -122,4 +122,138 @@ impl<T: frame_system::Config> pablo::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(12 as Weight))
 			.saturating_add(T::DbWeight::get().writes(9 as Weight))
 	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Pablo Pools (r:1 w:0)
+	// Storage: Pablo ConcentratedLiquidityStates (r:1 w:1)
+	// Storage: Pablo TickBitmap (r:1 w:0)
+	// Storage: Pablo Ticks (r:1 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Pablo PriceCumulativeState (r:1 w:1)
+	fn swap_concentrated_liquidity(s: u32, ) -> Weight {
+		(301_274_000 as Weight)
+			.saturating_add((21_836_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Pablo Pools (r:1 w:0)
+	// Storage: Pablo ConcentratedLiquidityStates (r:1 w:1)
+	// Storage: Pablo Ticks (r:2 w:2)
+	// Storage: Pablo TickBitmap (r:1 w:1)
+	// Storage: Fnft Collection (r:1 w:0)
+	// Storage: Fnft FinancialNftId (r:1 w:1)
+	// Storage: Fnft Instance (r:1 w:1)
+	// Storage: Fnft OwnerInstances (r:1 w:1)
+	// Storage: Proxy Proxies (r:1 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Pablo Positions (r:0 w:1)
+	// Storage: Pablo PriceCumulativeState (r:1 w:1)
+	fn open_position() -> Weight {
+		(298_214_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Pablo Pools (r:1 w:0)
+	// Storage: Pablo ConcentratedLiquidityStates (r:1 w:1)
+	// Storage: Pablo Positions (r:1 w:1)
+	// Storage: Pablo Ticks (r:2 w:2)
+	// Storage: Pablo TickBitmap (r:1 w:1)
+	// Storage: Fnft Instance (r:1 w:1)
+	// Storage: Fnft OwnerInstances (r:1 w:1)
+	// Storage: Proxy Proxies (r:1 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Pablo PriceCumulativeState (r:1 w:1)
+	fn close_position() -> Weight {
+		(276_930_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(16 as Weight))
+			.saturating_add(T::DbWeight::get().writes(15 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Pablo Pools (r:1 w:0)
+	// Storage: Pablo ConcentratedLiquidityStates (r:1 w:0)
+	// Storage: Pablo Positions (r:1 w:1)
+	// Storage: Pablo Ticks (r:2 w:0)
+	// Storage: Fnft Instance (r:1 w:0)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: System Account (r:2 w:2)
+	fn collect_fees() -> Weight {
+		(154_038_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Pablo Pools (r:1 w:0)
+	// Storage: Tokens Accounts (r:17 w:17)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Pablo PriceCumulativeState (r:1 w:1)
+	fn add_liquidity_assets() -> Weight {
+		(512_316_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(21 as Weight))
+			.saturating_add(T::DbWeight::get().writes(20 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Pablo Pools (r:1 w:0)
+	// Storage: Tokens Accounts (r:17 w:17)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Pablo PriceCumulativeState (r:1 w:1)
+	fn remove_liquidity_assets() -> Weight {
+		(318_904_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(21 as Weight))
+			.saturating_add(T::DbWeight::get().writes(19 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Pablo Pools (r:1 w:0)
+	// Storage: Tokens Accounts (r:11 w:5)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	// Storage: Pablo StakingRewardPools (r:1 w:0)
+	// Storage: StakingRewards RewardPools (r:1 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Pablo PriceCumulativeState (r:1 w:1)
+	fn remove_liquidity_single_asset() -> Weight {
+		(421_577_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Pablo Pools (r:1 w:0)
+	// Storage: Pablo TWAPState (r:1 w:0)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Pablo OrderCount (r:1 w:1)
+	// Storage: Pablo Orders (r:0 w:1)
+	fn place_order() -> Weight {
+		(98_417_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Pablo Orders (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:0)
+	fn cancel_order() -> Weight {
+		(79_652_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Pablo Orders (r:1 w:1)
+	// Storage: Pablo Pools (r:1 w:0)
+	// Storage: Tokens Accounts (r:7 w:7)
+	// Storage: System Account (r:3 w:2)
+	// Storage: Pablo StakingRewardPools (r:1 w:0)
+	// Storage: StakingRewards RewardPools (r:1 w:1)
+	// Storage: Pablo PriceCumulativeState (r:1 w:1)
+	fn fill_order() -> Weight {
+		(236_105_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
 }
@@ -0,0 +1,102 @@
+//! Placeholder weights for `pallet_fnft`
+//!
+//! NOT GENERATED BY THE BENCHMARK CLI: these weights are estimated by hand from the storage
+//! accesses of each extrinsic, until the pallet is benchmarked on the reference hardware with:
+
+// ./target/release/composable
+// benchmark
+// pallet
+// --chain=dali-dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --wasm-instantiation-strategy=legacy-instance-reuse
+// --pallet=pallet_fnft
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --output=runtime/dali/src/weights
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_fnft`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_fnft::WeightInfo for WeightInfo<T> {
+	// Storage: Fnft Instance (r:1 w:1)
+	// Storage: Fnft Listings (r:1 w:1)
+	// Storage: Fnft Approvals (r:1 w:1)
+	// Storage: Fnft OwnerInstances (r:2 w:2)
+	fn transfer() -> Weight {
+		(38_461_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Fnft Instance (r:1 w:0)
+	// Storage: Fnft Listings (r:1 w:0)
+	// Storage: Fnft Approvals (r:0 w:1)
+	fn approve() -> Weight {
+		(24_113_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Fnft Instance (r:1 w:0)
+	// Storage: Fnft Approvals (r:0 w:1)
+	fn revoke_approval() -> Weight {
+		(21_590_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Fnft Instance (r:1 w:0)
+	// Storage: Fnft Listings (r:1 w:1)
+	// Storage: StakingRewards Stakes (r:1 w:0)
+	fn list() -> Weight {
+		(29_847_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Fnft Instance (r:1 w:0)
+	// Storage: Fnft Listings (r:1 w:1)
+	// Storage: Fnft Bids (r:2 w:1)
+	fn cancel_listing() -> Weight {
+		(27_306_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Fnft Instance (r:1 w:1)
+	// Storage: Fnft Listings (r:2 w:2)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Fnft Approvals (r:1 w:1)
+	// Storage: Fnft OwnerInstances (r:2 w:2)
+	fn buy() -> Weight {
+		(72_038_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Fnft Instance (r:1 w:0)
+	// Storage: Fnft Listings (r:1 w:0)
+	// Storage: Fnft Bids (r:1 w:1)
+	// Storage: Tokens Accounts (r:3 w:3)
+	// Storage: System Account (r:2 w:2)
+	fn bid() -> Weight {
+		(64_752_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Fnft Instance (r:1 w:1)
+	// Storage: Fnft Listings (r:2 w:2)
+	// Storage: Fnft Bids (r:2 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Fnft Approvals (r:1 w:1)
+	// Storage: Fnft OwnerInstances (r:2 w:2)
+	fn settle_auction() -> Weight {
+		(78_915_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+}
@@ -31,52 +31,56 @@ use sp_std::marker::PhantomData;
 /// Weight functions for `pallet_staking_rewards`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_staking_rewards::WeightInfo for WeightInfo<T> {
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
 	// Storage: StakingRewards RewardPoolCount (r:1 w:1)
+	// Storage: CurrencyFactory AssetIdRanges (r:1 w:1)
 	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: CurrencyFactory AssetEd (r:0 w:1)
 	// Storage: StakingRewards RewardPools (r:0 w:1)
 	fn create_reward_pool(r: u32, ) -> Weight {
-		(46_627_000 as Weight)
-			// Standard Error: 49_000
+		(58_214_000 as Weight)
 			.saturating_add((616_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
 	// Storage: StakingRewards RewardPools (r:1 w:1)
-	// Storage: Tokens Accounts (r:2 w:2)
-	// Storage: Tokens TotalIssuance (r:1 w:0)
+	// Storage: Tokens Accounts (r:3 w:3)
+	// Storage: Tokens TotalIssuance (r:2 w:1)
 	// Storage: Timestamp Now (r:1 w:0)
 	// Storage: StakingRewards StakeCount (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
 	// Storage: StakingRewards Stakes (r:0 w:1)
 	fn stake(r: u32, ) -> Weight {
-		(118_543_000 as Weight)
-			// Standard Error: 142_000
+		(131_902_000 as Weight)
 			.saturating_add((1_455_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(7 as Weight))
-			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
 	// Storage: StakingRewards Stakes (r:1 w:1)
 	// Storage: StakingRewards RewardPools (r:1 w:1)
-	// Storage: Tokens Accounts (r:2 w:2)
-	// Storage: Tokens TotalIssuance (r:1 w:0)
+	// Storage: Tokens Accounts (r:3 w:3)
+	// Storage: Tokens TotalIssuance (r:2 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
 	fn extend(r: u32, ) -> Weight {
-		(94_805_000 as Weight)
-			// Standard Error: 136_000
+		(108_126_000 as Weight)
 			.saturating_add((1_681_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
 	// Storage: StakingRewards Stakes (r:1 w:1)
 	// Storage: Timestamp Now (r:1 w:0)
 	// Storage: StakingRewards RewardPools (r:1 w:1)
-	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Tokens Accounts (r:3 w:3)
 	// Storage: System Account (r:1 w:0)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
 	fn unstake(r: u32, ) -> Weight {
-		(97_251_000 as Weight)
-			// Standard Error: 117_000
+		(109_687_000 as Weight)
 			.saturating_add((1_795_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
 	// Storage: StakingRewards Stakes (r:1 w:2)
 	// Storage: StakingRewards StakeCount (r:1 w:1)
@@ -87,14 +91,6 @@ impl<T: frame_system::Config> pallet_staking_rewards::WeightInfo for WeightInfo<
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
-	fn reward_accumulation_hook_reward_update_calculation() -> Weight {
-		(562_000 as Weight)
-	}
-	// Storage: Timestamp Now (r:1 w:0)
-	fn unix_time_now() -> Weight {
-		(3_451_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-	}
 	// Storage: StakingRewards RewardPools (r:1 w:1)
 	// Storage: Timestamp Now (r:1 w:0)
 	fn update_rewards_pool(r: u32, ) -> Weight {
@@ -104,4 +100,30 @@ impl<T: frame_system::Config> pallet_staking_rewards::WeightInfo for WeightInfo<
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: StakingRewards Stakes (r:1 w:1)
+	// Storage: StakingRewards RewardPools (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn claim(r: u32, ) -> Weight {
+		(62_420_000 as Weight)
+			.saturating_add((24_310_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: StakingRewards Stakes (r:1 w:1)
+	// Storage: StakingRewards RewardPools (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	fn compound(r: u32, ) -> Weight {
+		(63_540_000 as Weight)
+			.saturating_add((1_702_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 }
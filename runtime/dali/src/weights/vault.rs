@@ -95,4 +95,25 @@ impl<T: frame_system::Config> vault::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Vault ApprovedStrategies (r:0 w:1)
+	fn approve_strategy() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Vault ApprovedStrategies (r:1 w:1)
+	fn revoke_strategy() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder: estimated by hand, not generated by the benchmark CLI.
+	// Storage: Vault ApprovedStrategies (r:1 w:0)
+	// Storage: Vault CapitalStructure (r:1 w:1)
+	fn rebalance_strategy() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -0,0 +1,454 @@
+use crate::{
+	asset::Asset,
+	cache::{Cache, ThreadSafePriceCache},
+	feed::{Exponent, FeedIdentifier, Price, TimeStamp, TimeStampedPrice},
+};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Tolerated drift, in seconds, between the clocks of the feeds and the server, as tolerated by the
+/// oracle between the server and its offchain worker.
+const MAX_CLOCK_DRIFT: i64 = 5;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Serialize, Copy, Clone, Debug)]
+#[repr(transparent)]
+pub struct NormalizedPrice(pub(crate) u64);
+
+/// How the prices of the sources of an asset are merged.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationMethod {
+	/// Weighted median of the prices.
+	Median,
+	/// Weighted mean of the prices, once the `trim_percent` lowest and highest prices dropped.
+	TrimmedMean,
+}
+
+impl Default for AggregationMethod {
+	fn default() -> Self {
+		AggregationMethod::Median
+	}
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Deserialize)]
+pub struct AggregationConfig {
+	#[serde(default)]
+	pub method: AggregationMethod,
+	/// Percentage of the lowest and highest prices dropped by the trimmed mean.
+	#[serde(default)]
+	pub trim_percent: u8,
+	/// Minimum number of sources agreeing on a price for it to be published.
+	#[serde(default = "default_min_sources")]
+	pub min_sources: usize,
+	/// Maximum deviation from the median, in basis points, before a source is rejected.
+	pub max_deviation_bps: Option<u32>,
+	/// Duration, in seconds, after which the price of a source is ignored.
+	#[serde(default = "default_max_staleness")]
+	pub max_staleness: u32,
+}
+
+fn default_min_sources() -> usize {
+	1
+}
+
+fn default_max_staleness() -> u32 {
+	10
+}
+
+impl Default for AggregationConfig {
+	fn default() -> Self {
+		AggregationConfig {
+			method: AggregationMethod::default(),
+			trim_percent: 0,
+			min_sources: default_min_sources(),
+			max_deviation_bps: None,
+			max_staleness: default_max_staleness(),
+		}
+	}
+}
+
+#[derive(PartialEq, Eq, Serialize, Copy, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceStatus {
+	Accepted,
+	Stale,
+	Outlier,
+}
+
+/// The price of a source, as taken into account by the aggregation.
+#[derive(PartialEq, Eq, Serialize, Copy, Clone, Debug)]
+pub struct SourcePrice {
+	pub feed: FeedIdentifier,
+	pub weight: u32,
+	pub price: NormalizedPrice,
+	pub timestamp: TimeStamp,
+	pub status: SourceStatus,
+}
+
+#[derive(PartialEq, Eq, Serialize, Clone, Debug)]
+pub struct AggregatedPrice {
+	pub price: NormalizedPrice,
+	pub sources: Vec<SourcePrice>,
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum AggregationError {
+	UnknownAsset,
+	NotEnoughSources { required: usize, available: usize },
+}
+
+/// The feeds an asset is priced from, along with their weight and the way they are aggregated.
+#[derive(Clone, Debug)]
+pub struct PriceSources {
+	pub sources: Vec<(FeedIdentifier, u32)>,
+	pub aggregation: AggregationConfig,
+}
+
+/// Merge the prices supplied by the different feeds of each asset.
+pub struct Aggregator {
+	prices: ThreadSafePriceCache,
+	sources: HashMap<Asset, PriceSources>,
+	expected_exponent: Exponent,
+}
+
+impl Aggregator {
+	pub fn new(
+		prices: ThreadSafePriceCache,
+		sources: HashMap<Asset, PriceSources>,
+		expected_exponent: Exponent,
+	) -> Self {
+		Aggregator { prices, sources, expected_exponent }
+	}
+
+	pub fn aggregate(
+		&self,
+		asset: Asset,
+		now: TimeStamp,
+	) -> Result<AggregatedPrice, AggregationError> {
+		let PriceSources { sources, aggregation } =
+			self.sources.get(&asset).ok_or(AggregationError::UnknownAsset)?;
+		let max_staleness = Duration::seconds(aggregation.max_staleness.into());
+		let prices = sources
+			.iter()
+			.filter_map(|&(feed, weight)| {
+				self.prices.get(&(feed, asset)).map(|timestamped_price| SourcePrice {
+					feed,
+					weight,
+					price: normalize_price(self.expected_exponent, timestamped_price.value),
+					timestamp: timestamped_price.timestamp,
+					status: match ensure_uptodate_price(&max_staleness, &now, &timestamped_price) {
+						Some(_) => SourceStatus::Accepted,
+						None => SourceStatus::Stale,
+					},
+				})
+			})
+			.collect();
+		aggregate(aggregation, prices)
+	}
+}
+
+fn accepted(sources: &[SourcePrice]) -> Vec<(NormalizedPrice, u32)> {
+	sources
+		.iter()
+		.filter(|source| source.status == SourceStatus::Accepted && source.weight > 0)
+		.map(|source| (source.price, source.weight))
+		.collect()
+}
+
+fn ensure_quorum(
+	config: &AggregationConfig,
+	prices: &[(NormalizedPrice, u32)],
+) -> Result<(), AggregationError> {
+	if prices.len() < config.min_sources.max(1) {
+		Err(AggregationError::NotEnoughSources {
+			required: config.min_sources.max(1),
+			available: prices.len(),
+		})
+	} else {
+		Ok(())
+	}
+}
+
+/// Aggregate the fresh prices of the sources, rejecting the ones deviating too much from the
+/// median.
+fn aggregate(
+	config: &AggregationConfig,
+	mut sources: Vec<SourcePrice>,
+) -> Result<AggregatedPrice, AggregationError> {
+	let fresh = accepted(&sources);
+	ensure_quorum(config, &fresh)?;
+	if let (Some(max_deviation_bps), Some(NormalizedPrice(median))) =
+		(config.max_deviation_bps, weighted_median(fresh))
+	{
+		sources
+			.iter_mut()
+			.filter(|source| source.status == SourceStatus::Accepted)
+			.for_each(|source| {
+				let deviation = (source.price.0 as u128).abs_diff(median as u128) * 10_000;
+				if deviation > max_deviation_bps as u128 * median as u128 {
+					source.status = SourceStatus::Outlier;
+				}
+			});
+	}
+	let prices = accepted(&sources);
+	ensure_quorum(config, &prices)?;
+	let price = match config.method {
+		AggregationMethod::Median => weighted_median(prices),
+		AggregationMethod::TrimmedMean => trimmed_mean(prices, config.trim_percent),
+	}
+	.ok_or(AggregationError::NotEnoughSources { required: 1, available: 0 })?;
+	Ok(AggregatedPrice { price, sources })
+}
+
+/// The lowest price for which the cumulated weight reaches half of the total weight.
+fn weighted_median(mut prices: Vec<(NormalizedPrice, u32)>) -> Option<NormalizedPrice> {
+	prices.sort();
+	let total_weight = prices.iter().map(|&(_, weight)| weight as u64).sum::<u64>();
+	let mut cumulated_weight = 0_u64;
+	prices.into_iter().find_map(|(price, weight)| {
+		cumulated_weight += weight as u64;
+		if total_weight > 0 && cumulated_weight * 2 >= total_weight {
+			Some(price)
+		} else {
+			None
+		}
+	})
+}
+
+/// Weighted mean of the prices, once the `trim_percent` lowest and highest prices dropped. The
+/// middle prices are always kept.
+fn trimmed_mean(
+	mut prices: Vec<(NormalizedPrice, u32)>,
+	trim_percent: u8,
+) -> Option<NormalizedPrice> {
+	prices.sort();
+	let trimmed = (prices.len() * trim_percent.min(50) as usize / 100)
+		.min(prices.len().saturating_sub(1) / 2);
+	let kept = prices.get(trimmed..prices.len() - trimmed)?;
+	let (total, total_weight) = kept.iter().fold(
+		(0_u128, 0_u128),
+		|(total, total_weight), &(NormalizedPrice(price), weight)| {
+			(total + price as u128 * weight as u128, total_weight + weight as u128)
+		},
+	);
+	match total_weight {
+		0 => None,
+		_ => u64::try_from(total / total_weight).ok().map(NormalizedPrice),
+	}
+}
+
+/// Ensure that the price is neither outdated nor ahead of the current time by more than the
+/// tolerated clock drift.
+fn ensure_uptodate_price(
+	&max_cache_duration: &Duration,
+	current_timestamp: &TimeStamp,
+	timestamped_price: &TimeStampedPrice,
+) -> Option<(Price, Exponent)> {
+	let elapsed = current_timestamp.elapsed_since(&timestamped_price.timestamp);
+	if -Duration::seconds(MAX_CLOCK_DRIFT) <= elapsed && elapsed < max_cache_duration {
+		Some(timestamped_price.value)
+	} else {
+		None
+	}
+}
+
+/// Normalize the price to the expected exponent.
+pub fn normalize_price(
+	Exponent(expected_exponent): Exponent,
+	(Price(p), Exponent(q)): (Price, Exponent),
+) -> NormalizedPrice {
+	// NOTE(hussein-aitlahcen): we want to go from x*10^q to x*10^expected_exponent
+	let dt = expected_exponent - q;
+	let normalized_price = match dt.signum() {
+		0 => p,
+		1 => p * u64::pow(10_u64, dt as u32),
+		-1 => p / u64::pow(10_u64, dt.abs() as u32),
+		_ => unreachable!(),
+	};
+	NormalizedPrice(normalized_price)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::feed::TimeStamped;
+
+	fn source(feed: FeedIdentifier, price: u64, weight: u32, status: SourceStatus) -> SourcePrice {
+		SourcePrice { feed, weight, price: NormalizedPrice(price), timestamp: TimeStamp(0), status }
+	}
+
+	fn prices(prices: &[(u64, u32)]) -> Vec<(NormalizedPrice, u32)> {
+		prices.iter().map(|&(price, weight)| (NormalizedPrice(price), weight)).collect()
+	}
+
+	#[test]
+	fn test_ensure_uptodate_price() {
+		let value = (Price(0x1337), Exponent(10));
+		[
+			(
+				(
+					Duration::seconds(1),
+					TimeStamp(1),
+					TimeStamped { value, timestamp: TimeStamp(0) },
+				),
+				None,
+			),
+			(
+				(
+					Duration::seconds(5),
+					TimeStamp(6),
+					TimeStamped { value, timestamp: TimeStamp(0) },
+				),
+				None,
+			),
+			(
+				(
+					Duration::seconds(20),
+					TimeStamp(20),
+					TimeStamped { value, timestamp: TimeStamp(1) },
+				),
+				Some(value),
+			),
+			(
+				(
+					Duration::seconds(10),
+					TimeStamp(14),
+					TimeStamped { value, timestamp: TimeStamp(5) },
+				),
+				Some(value),
+			),
+			(
+				(
+					Duration::seconds(10),
+					TimeStamp(14),
+					TimeStamped { value, timestamp: TimeStamp(19) },
+				),
+				Some(value),
+			),
+			(
+				(
+					Duration::seconds(10),
+					TimeStamp(14),
+					TimeStamped { value, timestamp: TimeStamp(20) },
+				),
+				None,
+			),
+		]
+		.iter()
+		.for_each(|((max_cache_duration, current_timestamp, timestamped_price), expected)| {
+			assert_eq!(
+				ensure_uptodate_price(max_cache_duration, current_timestamp, timestamped_price),
+				*expected
+			);
+		})
+	}
+
+	#[test]
+	fn test_get_normalized_price() {
+		let expected_exponent = Exponent(2);
+		[
+			((Price(0xCAFEBABE), Exponent(-2)), NormalizedPrice(0xCAFEBABE * u64::pow(10, 4))),
+			((Price(0xDEADBEEF), Exponent(2)), NormalizedPrice(0xDEADBEEF)),
+			((Price(1), Exponent(0)), NormalizedPrice(u64::pow(10, 2))),
+			((Price(12), Exponent(-1)), NormalizedPrice(12 * u64::pow(10, 3))),
+			((Price(454000), Exponent(4)), NormalizedPrice(4540)),
+		]
+		.iter()
+		.for_each(|&(price, expected_price)| {
+			assert_eq!(normalize_price(expected_exponent, price), expected_price);
+		});
+	}
+
+	#[test]
+	fn test_weighted_median() {
+		[
+			(prices(&[]), None),
+			(prices(&[(100, 0)]), None),
+			(prices(&[(100, 1)]), Some(NormalizedPrice(100))),
+			(prices(&[(300, 1), (100, 1), (200, 1)]), Some(NormalizedPrice(200))),
+			(prices(&[(100, 1), (200, 1)]), Some(NormalizedPrice(100))),
+			(prices(&[(100, 1), (200, 3)]), Some(NormalizedPrice(200))),
+		]
+		.into_iter()
+		.for_each(|(prices, expected)| {
+			assert_eq!(weighted_median(prices), expected);
+		});
+	}
+
+	#[test]
+	fn test_trimmed_mean() {
+		[
+			(prices(&[]), 0, None),
+			(prices(&[(100, 1), (200, 1)]), 0, Some(NormalizedPrice(150))),
+			(prices(&[(100, 3), (200, 1)]), 0, Some(NormalizedPrice(125))),
+			(prices(&[(1, 1), (100, 1), (200, 1), (10_000, 1)]), 25, Some(NormalizedPrice(150))),
+			(prices(&[(100, 1), (200, 1), (300, 1)]), 50, Some(NormalizedPrice(200))),
+			(prices(&[(100, 1), (200, 1)]), 50, Some(NormalizedPrice(150))),
+			(prices(&[(1, 1), (100, 1), (200, 1), (10_000, 1)]), 50, Some(NormalizedPrice(150))),
+			(prices(&[(u64::MAX, 2), (u64::MAX, 1)]), 0, Some(NormalizedPrice(u64::MAX))),
+		]
+		.into_iter()
+		.for_each(|(prices, trim_percent, expected)| {
+			assert_eq!(trimmed_mean(prices, trim_percent), expected);
+		});
+	}
+
+	#[test]
+	fn test_aggregate_rejects_outliers() {
+		let config = AggregationConfig {
+			method: AggregationMethod::TrimmedMean,
+			trim_percent: 0,
+			min_sources: 2,
+			max_deviation_bps: Some(500),
+			max_staleness: 10,
+		};
+		let sources = vec![
+			source(FeedIdentifier::Binance, 100, 1, SourceStatus::Accepted),
+			source(FeedIdentifier::Pyth, 104, 1, SourceStatus::Accepted),
+			source(FeedIdentifier::Composable, 200, 1, SourceStatus::Accepted),
+			source(FeedIdentifier::Mock, 102, 1, SourceStatus::Stale),
+		];
+		let aggregated = aggregate(&config, sources).expect("enough sources");
+		assert_eq!(aggregated.price, NormalizedPrice(102));
+		assert_eq!(
+			aggregated.sources.iter().map(|source| source.status).collect::<Vec<_>>(),
+			vec![
+				SourceStatus::Accepted,
+				SourceStatus::Accepted,
+				SourceStatus::Outlier,
+				SourceStatus::Stale
+			]
+		);
+	}
+
+	#[test]
+	fn test_aggregate_requires_quorum() {
+		let config = AggregationConfig {
+			min_sources: 2,
+			max_deviation_bps: Some(500),
+			..AggregationConfig::default()
+		};
+		assert_eq!(
+			aggregate(
+				&config,
+				vec![
+					source(FeedIdentifier::Binance, 100, 1, SourceStatus::Accepted),
+					source(FeedIdentifier::Pyth, 100, 1, SourceStatus::Stale),
+				]
+			),
+			Err(AggregationError::NotEnoughSources { required: 2, available: 1 })
+		);
+		// both sources are fresh, but they don't agree
+		assert_eq!(
+			aggregate(
+				&config,
+				vec![
+					source(FeedIdentifier::Binance, 100, 1, SourceStatus::Accepted),
+					source(FeedIdentifier::Pyth, 200, 1, SourceStatus::Accepted),
+				]
+			),
+			Err(AggregationError::NotEnoughSources { required: 2, available: 1 })
+		);
+	}
+}
@@ -2,52 +2,88 @@ use primitives::currency::CurrencyId;
 use std::{
 	collections::{HashMap, HashSet},
 	convert::TryFrom,
-	fmt::Display,
+	fmt::{Debug, Display},
+	str::FromStr,
+	sync::RwLock,
 };
 
-custom_derive! {
-	#[derive(EnumFromStr, Copy, Clone, PartialEq, Eq, Hash, Debug)]
-	pub enum Asset {
-		KSM,
-		PICA,
-		USDT,
-		USDC,
+/// An asset, identified by its symbol.
+/// Only the assets declared in the configuration are registered and can be priced.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Asset(&'static str);
+
+impl Display for Asset {
+	#[inline(always)]
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.0)
 	}
 }
 
-pub const VALID_PRICE_QUOTE_ASSETS: &[Asset] = &[Asset::USDT, Asset::USDC];
+impl Debug for Asset {
+	#[inline(always)]
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct AssetPair(pub Asset, pub Asset);
+#[derive(Default)]
+struct AssetRegistry {
+	currency_ids: HashMap<Asset, CurrencyId>,
+	assets: HashMap<CurrencyId, Asset>,
+	quote_assets: HashSet<Asset>,
+}
 
 lazy_static! {
 	/*
-	  The map of valid asset we are allowed to ask price for.
-	  We must not swap two indexes.
+	  The map of valid asset we are allowed to ask price for, filled from the configuration.
+	  The currency ids must match the ones of the oracle pallet.
 	*/
-	pub static ref INDEX_TO_ASSET: HashMap<CurrencyId, Asset> = [
-		(CurrencyId::KSM, Asset::KSM),
-		(CurrencyId::PICA, Asset::PICA),
-		(CurrencyId::USDT, Asset::USDT),
-		(CurrencyId::USDC, Asset::USDC),
-	]
-	.into_iter()
-	.collect();
-
-	pub static ref ASSET_TO_INDEX: HashMap<Asset, CurrencyId> =
-		INDEX_TO_ASSET.iter().map(|(&i, &a)| (a, i)).collect();
-
-	pub static ref VALID_ASSETS: HashSet<Asset> =
-		INDEX_TO_ASSET.values().copied().collect();
+	static ref REGISTRY: RwLock<AssetRegistry> = RwLock::new(AssetRegistry::default());
 }
 
+/// Register an asset along its on-chain currency id.
+/// `quote` assets are the ones other assets can be priced in.
+pub fn register(symbol: &str, currency_id: CurrencyId, quote: bool) -> Asset {
+	let mut registry = REGISTRY.write().expect("could not acquire write lock");
+	let asset = match registry.currency_ids.keys().find(|asset| asset.0 == symbol) {
+		Some(&asset) => asset,
+		// NOTE: registered assets live as long as the server, the symbol is leaked once.
+		None => Asset(Box::leak(symbol.to_string().into_boxed_str())),
+	};
+	registry.currency_ids.insert(asset, currency_id);
+	registry.assets.insert(currency_id, asset);
+	if quote {
+		registry.quote_assets.insert(asset);
+	}
+	asset
+}
+
+impl FromStr for Asset {
+	type Err = ();
+	fn from_str(symbol: &str) -> Result<Asset, Self::Err> {
+		REGISTRY
+			.read()
+			.expect("could not acquire read lock")
+			.currency_ids
+			.keys()
+			.find(|asset| asset.0 == symbol)
+			.copied()
+			.ok_or(())
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct AssetPair(pub Asset, pub Asset);
+
 impl AssetPair {
 	/*
-	  We currently only allow X/(USD|Stablecoin)
+	  We currently only allow X/(USD|Stablecoin), the quote assets being flagged as such in the
+	  configuration.
 	*/
 	pub fn new(x: Asset, y: Asset) -> Option<Self> {
+		let registry = REGISTRY.read().expect("could not acquire read lock");
 		match (x, y) {
-			(_, y) if VALID_PRICE_QUOTE_ASSETS.contains(&y) => Some(AssetPair(x, y)),
+			(_, y) if registry.quote_assets.contains(&y) => Some(AssetPair(x, y)),
 			_ => None,
 		}
 	}
@@ -56,14 +92,26 @@ impl AssetPair {
 impl TryFrom<Asset> for CurrencyId {
 	type Error = ();
 	fn try_from(asset: Asset) -> Result<CurrencyId, Self::Error> {
-		ASSET_TO_INDEX.get(&asset).copied().ok_or(())
+		REGISTRY
+			.read()
+			.expect("could not acquire read lock")
+			.currency_ids
+			.get(&asset)
+			.copied()
+			.ok_or(())
 	}
 }
 
 impl TryFrom<CurrencyId> for Asset {
 	type Error = ();
 	fn try_from(currency_index: CurrencyId) -> Result<Asset, Self::Error> {
-		INDEX_TO_ASSET.get(&currency_index).copied().ok_or(())
+		REGISTRY
+			.read()
+			.expect("could not acquire read lock")
+			.assets
+			.get(&currency_index)
+			.copied()
+			.ok_or(())
 	}
 }
 
@@ -82,7 +130,7 @@ impl Display for ConcatSymbol {
 	#[inline(always)]
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		let ConcatSymbol(AssetPair(x, y)) = self;
-		write!(f, "{:?}{:?}", x, y)
+		write!(f, "{}{}", x, y)
 	}
 }
 
@@ -101,6 +149,25 @@ impl Display for SlashSymbol {
 	#[inline(always)]
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		let SlashSymbol(AssetPair(x, y)) = self;
-		write!(f, "{:?}/{:?}", x, y)
+		write!(f, "{}/{}", x, y)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_registered_assets() {
+		let ksm = register("KSM", CurrencyId::KSM, false);
+		let usdt = register("USDT", CurrencyId::USDT, true);
+		assert_eq!(Asset::from_str("KSM"), Ok(ksm));
+		assert_eq!(Asset::from_str("UNKNOWN"), Err(()));
+		assert_eq!(CurrencyId::try_from(ksm), Ok(CurrencyId::KSM));
+		assert_eq!(Asset::try_from(CurrencyId::USDT), Ok(usdt));
+		assert_eq!(AssetPair::new(ksm, usdt), Some(AssetPair(ksm, usdt)));
+		assert_eq!(AssetPair::new(usdt, ksm), None);
+		assert_eq!(format!("{}", ConcatSymbol::new(AssetPair(ksm, usdt))), "KSMUSDT");
+		assert_eq!(format!("{}", SlashSymbol::new(AssetPair(ksm, usdt))), "KSM/USDT");
 	}
 }
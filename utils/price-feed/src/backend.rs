@@ -30,22 +30,20 @@ where
 }
 
 impl<TFeed, TAsset, TPrice> TryFrom<FeedNotification<TFeed, TAsset, TPrice>>
-	for FeedNotificationAction<TAsset, TPrice>
+	for FeedNotificationAction<(TFeed, TAsset), TPrice>
 where
-	TFeed: Debug,
+	TFeed: Debug + Copy,
 	TAsset: Debug + Copy,
 	TPrice: Copy,
 {
 	type Error = ();
-	/* TODO: how are we going to handles X feeds:
-	  - do we just expose every one of them from their own endpoint?
-	  - do we merge the prices (median?), if so, merging will depend on timestamp?
-	  On notification close, do we remove the price as we are no
-	  longer getting new prices?
+	/* NOTE: prices are cached per feed, the aggregator merging the prices of the feeds an asset
+	  is configured with whenever it is requested.
+	  TODO: on notification close, do we remove the price as we are no longer getting new prices?
 	*/
 	fn try_from(
 		notification: FeedNotification<TFeed, TAsset, TPrice>,
-	) -> Result<FeedNotificationAction<TAsset, TPrice>, Self::Error> {
+	) -> Result<FeedNotificationAction<(TFeed, TAsset), TPrice>, Self::Error> {
 		match notification {
 			FeedNotification::Started { feed } => {
 				log::info!("{:?} started successfully", feed);
@@ -59,8 +57,8 @@ where
 				log::info!("{:?} has closed a channel for {:?}", feed, asset);
 				Err(())
 			},
-			FeedNotification::AssetPriceUpdated { asset, price, .. } =>
-				Ok(FeedNotificationAction::UpdateCache { key: asset, value: price }),
+			FeedNotification::AssetPriceUpdated { feed, asset, price } =>
+				Ok(FeedNotificationAction::UpdateCache { key: (feed, asset), value: price }),
 			FeedNotification::Stopped { feed } => {
 				log::info!("{:?} stopped", feed);
 				Err(())
@@ -121,7 +119,7 @@ impl Backend {
 mod tests {
 	use super::Backend;
 	use crate::{
-		asset::{Asset, VALID_ASSETS},
+		asset::{self, Asset},
 		backend::{FeedNotificationAction, Transition},
 		cache::{PriceCache, ThreadSafePriceCache},
 		feed::{
@@ -130,6 +128,7 @@ mod tests {
 		},
 	};
 	use futures::stream::StreamExt;
+	use primitives::currency::CurrencyId;
 	use signal_hook_tokio::Signals;
 	use std::{
 		collections::HashMap,
@@ -139,6 +138,13 @@ mod tests {
 	use tokio::sync::mpsc;
 	use tokio_stream::wrappers::ReceiverStream;
 
+	fn assets() -> [Asset; 2] {
+		[
+			asset::register("KSM", CurrencyId::KSM, false),
+			asset::register("PICA", CurrencyId::PICA, false),
+		]
+	}
+
 	#[test]
 	fn test_feed_notification_transition() {
 		let feed = FeedIdentifier::Binance;
@@ -146,7 +152,7 @@ mod tests {
 			value: (Price(0xCAFEBABE), Exponent(0x1337)),
 			timestamp: TimeStamp::now(),
 		};
-		VALID_ASSETS.iter().for_each(|&asset| {
+		assets().iter().for_each(|&asset| {
 			[
 				(FeedNotification::AssetOpened { feed, asset }, None),
 				(FeedNotification::AssetClosed { feed, asset }, None),
@@ -154,17 +160,19 @@ mod tests {
 					FeedNotification::AssetPriceUpdated { feed, asset, price: timestamped_price },
 					Some((
 						FeedNotificationAction::UpdateCache {
-							key: asset,
+							key: (feed, asset),
 							value: timestamped_price,
 						},
-						[(asset, timestamped_price)].iter().copied().collect(),
+						[((feed, asset), timestamped_price)].iter().copied().collect(),
 					)),
 				),
 			]
 			.iter()
 			.for_each(|(notification, expected)| {
 				if let (Ok(actual_action), Some((expected_action, expected_state))) = (
-					FeedNotificationAction::<Asset, TimeStampedPrice>::try_from(*notification),
+					FeedNotificationAction::<(FeedIdentifier, Asset), TimeStampedPrice>::try_from(
+						*notification,
+					),
 					expected,
 				) {
 					assert_eq!(&actual_action, expected_action);
@@ -182,7 +190,7 @@ mod tests {
 			|x, y| TimeStamped { value: (Price(x), Exponent(y)), timestamp: TimeStamp::now() };
 		let (price1, price2, price3) = (mk_price(123, -3), mk_price(3134, -1), mk_price(93424, -4));
 		let feed = FeedIdentifier::Binance;
-		for &asset in VALID_ASSETS.iter() {
+		for &asset in assets().iter() {
 			let tests = [
 				(
 					vec![
@@ -190,7 +198,7 @@ mod tests {
 						FeedNotification::AssetPriceUpdated { feed, asset, price: price1 },
 						FeedNotification::AssetClosed { feed, asset },
 					],
-					[(asset, price1)],
+					[((feed, asset), price1)],
 				),
 				(
 					vec![
@@ -200,7 +208,7 @@ mod tests {
 						FeedNotification::AssetPriceUpdated { feed, asset, price: price2 },
 						FeedNotification::AssetClosed { feed, asset },
 					],
-					[(asset, price2)],
+					[((feed, asset), price2)],
 				),
 				(
 					vec![
@@ -210,7 +218,7 @@ mod tests {
 						FeedNotification::AssetPriceUpdated { feed, asset, price: price3 },
 						FeedNotification::AssetClosed { feed, asset },
 					],
-					[(asset, price3)],
+					[((feed, asset), price3)],
 				),
 			];
 			for (events, expected) in &tests {
@@ -220,7 +228,7 @@ mod tests {
 				let signals = Signals::new(&[]).expect("could not create signals stream").fuse();
 				let backend = Backend::new::<
 					FeedNotification<FeedIdentifier, Asset, TimeStampedPrice>,
-					FeedNotificationAction<Asset, TimeStampedPrice>,
+					FeedNotificationAction<(FeedIdentifier, Asset), TimeStampedPrice>,
 					_,
 					_,
 					_,
@@ -1,11 +1,15 @@
-use crate::{asset::Asset, feed::TimeStampedPrice};
+use crate::{
+	asset::Asset,
+	feed::{FeedIdentifier, TimeStampedPrice},
+};
 use std::{
 	collections::HashMap,
 	hash::Hash,
 	sync::{Arc, RwLock},
 };
 
-pub type PriceCache = HashMap<Asset, TimeStampedPrice>;
+/// Latest price of each asset, per feed.
+pub type PriceCache = HashMap<(FeedIdentifier, Asset), TimeStampedPrice>;
 
 pub type ThreadSafePriceCache = Arc<RwLock<PriceCache>>;
 
@@ -0,0 +1,276 @@
+use crate::{aggregation::AggregationConfig, feed::FeedIdentifier};
+use serde::Deserialize;
+use std::{
+	collections::HashSet,
+	fs,
+	path::{Path, PathBuf},
+};
+
+/// Configuration of the server, describing the assets to price, the feeds supplying them and the
+/// weight of each feed in the published price.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+	pub assets: Vec<AssetConfig>,
+	#[serde(default)]
+	pub feeds: FeedsConfig,
+	/// Default aggregation of the prices, unless overridden by an asset.
+	#[serde(default)]
+	pub aggregation: AggregationConfig,
+	pub prices: Vec<PriceConfig>,
+}
+
+/// An asset known by the server, along with its on-chain currency id.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AssetConfig {
+	pub symbol: String,
+	pub currency_id: u128,
+	/// Whether other assets can be priced in this asset.
+	#[serde(default)]
+	pub quote: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct FeedsConfig {
+	pub binance: Option<BinanceConfig>,
+	pub pyth: Option<PythConfig>,
+	pub composable: Option<ComposableConfig>,
+	pub mock: Option<MockConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BinanceConfig {
+	pub quote_asset: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PythConfig {
+	/// Host address of the pythd server.
+	pub url: String,
+	pub quote_asset: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ComposableConfig {
+	/// Host address of the composable node.
+	pub url: String,
+	pub quote_asset: String,
+}
+
+/// Local feed publishing fixed or recorded prices, useful for development and testing.
+#[derive(Clone, Debug, Deserialize)]
+pub struct MockConfig {
+	/// Milliseconds between two price updates.
+	#[serde(default = "default_mock_interval")]
+	pub interval_ms: u64,
+	#[serde(default)]
+	pub prices: Vec<MockPriceConfig>,
+	/// File of recorded prices, one `ASSET PRICE EXPONENT` record per line, replayed in order.
+	pub replay_file: Option<PathBuf>,
+}
+
+fn default_mock_interval() -> u64 {
+	1000
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MockPriceConfig {
+	pub asset: String,
+	pub price: u64,
+	pub exponent: i32,
+}
+
+/// The feeds an asset is priced from.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PriceConfig {
+	pub asset: String,
+	pub sources: Vec<SourceConfig>,
+	pub aggregation: Option<AggregationConfig>,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub struct SourceConfig {
+	pub feed: FeedIdentifier,
+	/// Relative weight of the feed when averaging the prices of the asset.
+	#[serde(default = "default_weight")]
+	pub weight: u32,
+}
+
+fn default_weight() -> u32 {
+	1
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+	Io(std::io::Error),
+	Parse(toml::de::Error),
+	DuplicateAsset(String),
+	UnknownAsset(String),
+	FeedNotConfigured(FeedIdentifier),
+	InvalidWeight(String),
+	InvalidAggregation(String),
+}
+
+impl Config {
+	pub fn load(path: &Path) -> Result<Self, ConfigError> {
+		let content = fs::read_to_string(path).map_err(ConfigError::Io)?;
+		Self::parse(&content)
+	}
+
+	pub fn parse(content: &str) -> Result<Self, ConfigError> {
+		let config: Config = toml::from_str(content).map_err(ConfigError::Parse)?;
+		config.validate()?;
+		Ok(config)
+	}
+
+	fn validate(&self) -> Result<(), ConfigError> {
+		let mut symbols = HashSet::new();
+		for asset in &self.assets {
+			if !symbols.insert(asset.symbol.as_str()) {
+				return Err(ConfigError::DuplicateAsset(asset.symbol.clone()))
+			}
+		}
+		let ensure_declared = |symbol: &String| {
+			if symbols.contains(symbol.as_str()) {
+				Ok(())
+			} else {
+				Err(ConfigError::UnknownAsset(symbol.clone()))
+			}
+		};
+		if let Some(binance) = &self.feeds.binance {
+			ensure_declared(&binance.quote_asset)?;
+		}
+		if let Some(pyth) = &self.feeds.pyth {
+			ensure_declared(&pyth.quote_asset)?;
+		}
+		if let Some(composable) = &self.feeds.composable {
+			ensure_declared(&composable.quote_asset)?;
+		}
+		if let Some(mock) = &self.feeds.mock {
+			mock.prices.iter().try_for_each(|price| ensure_declared(&price.asset))?;
+		}
+		let mut priced = HashSet::new();
+		for price in &self.prices {
+			ensure_declared(&price.asset)?;
+			if !priced.insert(price.asset.as_str()) {
+				return Err(ConfigError::DuplicateAsset(price.asset.clone()))
+			}
+			if price.sources.iter().all(|source| source.weight == 0) {
+				return Err(ConfigError::InvalidWeight(price.asset.clone()))
+			}
+			let aggregation = price.aggregation.unwrap_or(self.aggregation);
+			if aggregation.min_sources > price.sources.len() || aggregation.trim_percent >= 50 {
+				return Err(ConfigError::InvalidAggregation(price.asset.clone()))
+			}
+			for source in &price.sources {
+				if !self.is_configured(source.feed) {
+					return Err(ConfigError::FeedNotConfigured(source.feed))
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn is_configured(&self, feed: FeedIdentifier) -> bool {
+		match feed {
+			FeedIdentifier::Binance => self.feeds.binance.is_some(),
+			FeedIdentifier::Pyth => self.feeds.pyth.is_some(),
+			FeedIdentifier::Composable => self.feeds.composable.is_some(),
+			FeedIdentifier::Mock => self.feeds.mock.is_some(),
+		}
+	}
+
+	/// The symbols of the assets priced from `feed`.
+	pub fn assets_of(&self, feed: FeedIdentifier) -> Vec<&str> {
+		self.prices
+			.iter()
+			.filter(|price| price.sources.iter().any(|source| source.feed == feed))
+			.map(|price| price.asset.as_str())
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::aggregation::AggregationMethod;
+
+	const CONFIG: &str = r#"
+		[[assets]]
+		symbol = "KSM"
+		currency_id = 4
+
+		[[assets]]
+		symbol = "USDT"
+		currency_id = 130
+		quote = true
+
+		[feeds.binance]
+		quote_asset = "USDT"
+
+		[feeds.mock]
+		prices = [{ asset = "KSM", price = 5000, exponent = 2 }]
+
+		[aggregation]
+		method = "trimmed_mean"
+		trim_percent = 10
+		max_deviation_bps = 500
+
+		[[prices]]
+		asset = "KSM"
+		sources = [{ feed = "binance", weight = 3 }, { feed = "mock" }]
+		aggregation = { min_sources = 2 }
+	"#;
+
+	#[test]
+	fn test_parse_config() {
+		let config = Config::parse(CONFIG).expect("valid config");
+		assert_eq!(config.assets.len(), 2);
+		assert!(config.assets[1].quote);
+		assert_eq!(config.feeds.mock.as_ref().map(|mock| mock.interval_ms), Some(1000));
+		let weights = config.prices[0]
+			.sources
+			.iter()
+			.map(|source| (source.feed, source.weight))
+			.collect::<Vec<_>>();
+		assert_eq!(weights, vec![(FeedIdentifier::Binance, 3), (FeedIdentifier::Mock, 1)]);
+		assert_eq!(config.assets_of(FeedIdentifier::Binance), vec!["KSM"]);
+		assert_eq!(config.aggregation.method, AggregationMethod::TrimmedMean);
+		assert_eq!(config.aggregation.max_deviation_bps, Some(500));
+		assert_eq!(config.aggregation.max_staleness, 10);
+		assert_eq!(
+			config.prices[0].aggregation,
+			Some(AggregationConfig { min_sources: 2, ..AggregationConfig::default() })
+		);
+		assert!(config.assets_of(FeedIdentifier::Pyth).is_empty());
+	}
+
+	#[test]
+	fn test_invalid_config() {
+		assert!(matches!(
+			Config::parse(&CONFIG.replace("quote_asset = \"USDT\"", "quote_asset = \"USDC\"")),
+			Err(ConfigError::UnknownAsset(symbol)) if symbol == "USDC"
+		));
+		assert!(matches!(
+			Config::parse(&CONFIG.replace("feed = \"mock\"", "feed = \"pyth\"")),
+			Err(ConfigError::FeedNotConfigured(FeedIdentifier::Pyth))
+		));
+		assert!(matches!(
+			Config::parse(
+				&CONFIG.replace("currency_id = 130", "currency_id = 130\nsymbol = \"KSM\"")
+			),
+			Err(ConfigError::Parse(_))
+		));
+		assert!(matches!(
+			Config::parse(&CONFIG.replace("symbol = \"USDT\"", "symbol = \"KSM\"")),
+			Err(ConfigError::DuplicateAsset(symbol)) if symbol == "KSM"
+		));
+		assert!(matches!(
+			Config::parse(&CONFIG.replace("min_sources = 2", "min_sources = 3")),
+			Err(ConfigError::InvalidAggregation(symbol)) if symbol == "KSM"
+		));
+		assert!(matches!(
+			Config::parse(&CONFIG.replace("min_sources = 2", "min_sources = 2, trim_percent = 50")),
+			Err(ConfigError::InvalidAggregation(symbol)) if symbol == "KSM"
+		));
+	}
+}
@@ -0,0 +1,143 @@
+use super::{
+	Exponent, Feed, FeedError, FeedIdentifier, FeedNotification, FeedResult, Price, TimeStamp,
+	TimeStamped, TimeStampedPrice, CHANNEL_BUFFER_SIZE,
+};
+use crate::asset::Asset;
+use std::{collections::HashMap, str::FromStr, time::Duration};
+use tokio::sync::{mpsc, watch};
+use tokio_stream::wrappers::ReceiverStream;
+
+/// Prices published by the mock feed for each asset, cycled through on every tick.
+pub type MockPrices = HashMap<Asset, Vec<(Price, Exponent)>>;
+
+pub struct MockFeed;
+
+impl MockFeed {
+	pub async fn start(
+		mut shutdown_message: watch::Receiver<bool>,
+		interval: Duration,
+		prices: MockPrices,
+	) -> FeedResult<Feed<FeedIdentifier, Asset, TimeStampedPrice>> {
+		let (sink, source) = mpsc::channel(CHANNEL_BUFFER_SIZE);
+
+		sink.send(FeedNotification::Started { feed: FeedIdentifier::Mock })
+			.await
+			.map_err(|_| FeedError::ChannelIsBroken)?;
+
+		for &asset in prices.keys() {
+			sink.send(FeedNotification::AssetOpened { feed: FeedIdentifier::Mock, asset })
+				.await
+				.map_err(|_| FeedError::ChannelIsBroken)?;
+		}
+
+		let handle = tokio::spawn(async move {
+			let mut ticker = tokio::time::interval(interval);
+			let mut tick: usize = 0;
+			loop {
+				tokio::select! {
+					biased;
+
+					_ = shutdown_message.changed() => {
+						if *shutdown_message.borrow() {
+							break;
+						}
+					}
+
+					_ = ticker.tick() => {
+						let timestamp = TimeStamp::now();
+						for (&asset, asset_prices) in prices.iter() {
+							let index = tick % asset_prices.len().max(1);
+							if let Some(&value) = asset_prices.get(index) {
+								sink.send(FeedNotification::AssetPriceUpdated {
+									feed: FeedIdentifier::Mock,
+									asset,
+									price: TimeStamped { value, timestamp },
+								})
+								.await
+								.map_err(|_| FeedError::ChannelIsBroken)?;
+							}
+						}
+						tick = tick.wrapping_add(1);
+					}
+				}
+			}
+
+			for &asset in prices.keys() {
+				sink.send(FeedNotification::AssetClosed { feed: FeedIdentifier::Mock, asset })
+					.await
+					.map_err(|_| FeedError::ChannelIsBroken)?;
+			}
+
+			sink.send(FeedNotification::Stopped { feed: FeedIdentifier::Mock })
+				.await
+				.map_err(|_| FeedError::ChannelIsBroken)?;
+
+			Ok(())
+		});
+
+		Ok((handle, ReceiverStream::new(source)))
+	}
+}
+
+/// Parse recorded prices, one `ASSET PRICE EXPONENT` record per line.
+/// Empty lines and lines starting with `#` are ignored.
+pub fn parse_replay(content: &str) -> Result<Vec<(Asset, Price, Exponent)>, String> {
+	content
+		.lines()
+		.map(str::trim)
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.map(|line| match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+			[asset, price, exponent] => Ok((
+				Asset::from_str(asset).map_err(|_| format!("unknown asset {}", asset))?,
+				Price(price.parse().map_err(|_| format!("invalid price {}", price))?),
+				Exponent(exponent.parse().map_err(|_| format!("invalid exponent {}", exponent))?),
+			)),
+			_ => Err(format!("invalid record {}", line)),
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::asset;
+	use futures::StreamExt;
+	use primitives::currency::CurrencyId;
+
+	#[test]
+	fn test_parse_replay() {
+		let ksm = asset::register("KSM", CurrencyId::KSM, false);
+		assert_eq!(
+			parse_replay("# recorded prices\nKSM 5000 2\n\n  KSM 5100 2 \n"),
+			Ok(vec![(ksm, Price(5000), Exponent(2)), (ksm, Price(5100), Exponent(2))])
+		);
+		assert!(parse_replay("KSM 5000").is_err());
+		assert!(parse_replay("UNKNOWN 5000 2").is_err());
+	}
+
+	#[tokio::test]
+	async fn test_mock_feed_cycles_through_prices() {
+		let ksm = asset::register("KSM", CurrencyId::KSM, false);
+		let (shutdown_sender, shutdown_receiver) = watch::channel(false);
+		let prices = [(ksm, vec![(Price(1), Exponent(0)), (Price(2), Exponent(0))])]
+			.into_iter()
+			.collect();
+		let (handle, stream) = MockFeed::start(shutdown_receiver, Duration::from_millis(1), prices)
+			.await
+			.expect("mock feed starts");
+		let updates = stream
+			.filter_map(|notification| async move {
+				match notification {
+					FeedNotification::AssetPriceUpdated { price, .. } => Some(price.value.0),
+					_ => None,
+				}
+			})
+			.take(3)
+			.collect::<Vec<_>>()
+			.await;
+		assert_eq!(updates, vec![Price(1), Price(2), Price(1)]);
+		// the feed may already have stopped as the stream has been dropped
+		let _ = shutdown_sender.send(true);
+		let _ = handle.await;
+	}
+}
@@ -6,6 +6,7 @@ pub mod binance;
 pub mod composable;
 #[allow(clippy::all)]
 pub mod composable_api;
+pub mod mock;
 pub mod pyth;
 
 use chrono::{Duration, Utc};
@@ -58,11 +59,13 @@ pub enum FeedNotification<F, A, P> {
 }
 
 /// The feed identifiers.
-#[derive(PartialEq, Eq, Copy, Clone, Hash, Debug)]
+#[derive(PartialEq, Eq, Copy, Clone, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum FeedIdentifier {
 	Pyth,
 	Binance,
 	Composable,
+	Mock,
 }
 
 /// The possible errors hapenning while feeds are running.
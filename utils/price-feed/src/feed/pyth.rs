@@ -223,6 +223,7 @@ impl PythFeed {
 		url: Url,
 		keep_running: Arc<AtomicBool>,
 		assets: &HashSet<Asset>,
+		quote_asset: Asset,
 	) -> FeedResult<Feed<FeedIdentifier, Asset, TimeStampedPrice>> {
 		let mut pyth = Pyth::new(&url).await.map_err(|_| FeedError::NetworkFailure)?;
 
@@ -233,7 +234,7 @@ impl PythFeed {
 			.map_err(|_| FeedError::ChannelIsBroken)?;
 
 		for &asset in assets.iter() {
-			if let Some(asset_pair) = AssetPair::new(asset, Asset::USDT) {
+			if let Some(asset_pair) = AssetPair::new(asset, quote_asset) {
 				pyth.subscribe_to_asset(keep_running.clone(), &sink, &asset_pair)
 					.await
 					.expect("failed to subscribe to asset");
@@ -254,6 +255,7 @@ impl PythFeed {
 mod tests {
 	use super::*;
 	use crate::{asset::*, feed::*};
+	use primitives::currency::CurrencyId;
 
 	#[test]
 	fn test_notify_price_action() {
@@ -261,32 +263,34 @@ mod tests {
 		let product_price = PythProductPrice { account, price_exponent: Exponent(0x1337) };
 		let price = Price(0xCAFEBABE);
 		let timestamp = TimeStamp::now();
-		VALID_ASSETS.iter().for_each(|&asset| {
-			[
-				(PythSymbolStatus::Halted, None),
-				(PythSymbolStatus::Unknown, None),
-				(
-					PythSymbolStatus::Trading,
-					Some(PythNotifyPriceAction::YieldFeedNotification(
-						FeedNotification::AssetPriceUpdated {
-							feed: FeedIdentifier::Pyth,
-							asset,
-							price: TimeStamped {
-								value: (price, product_price.price_exponent),
-								timestamp,
-							},
-						},
-					)),
-				),
-			]
+		[register("KSM", CurrencyId::KSM, false), register("PICA", CurrencyId::PICA, false)]
 			.iter()
-			.for_each(|&(status, expected_action)| {
-				let notify_price = PythNotifyPrice { status, price };
-				assert_eq!(
-					expected_action,
-					notify_price_action(asset, &product_price, &notify_price, &timestamp)
-				)
+			.for_each(|&asset| {
+				[
+					(PythSymbolStatus::Halted, None),
+					(PythSymbolStatus::Unknown, None),
+					(
+						PythSymbolStatus::Trading,
+						Some(PythNotifyPriceAction::YieldFeedNotification(
+							FeedNotification::AssetPriceUpdated {
+								feed: FeedIdentifier::Pyth,
+								asset,
+								price: TimeStamped {
+									value: (price, product_price.price_exponent),
+									timestamp,
+								},
+							},
+						)),
+					),
+				]
+				.iter()
+				.for_each(|&(status, expected_action)| {
+					let notify_price = PythNotifyPrice { status, price };
+					assert_eq!(
+						expected_action,
+						notify_price_action(asset, &product_price, &notify_price, &timestamp)
+					)
+				});
 			});
-		});
 	}
 }
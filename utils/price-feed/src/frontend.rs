@@ -1,19 +1,14 @@
 use crate::{
+	aggregation::{AggregatedPrice, Aggregator, SourcePrice},
 	asset::Asset,
-	cache::{Cache, PriceCache, ThreadSafePriceCache},
-	feed::{Exponent, Price, TimeStamp, TimeStampedPrice},
+	feed::TimeStamp,
 };
-use chrono::Duration;
 use futures::channel::oneshot;
 use primitives::currency::CurrencyId;
+use scale_codec::Encode;
 use serde::Serialize;
-use std::{
-	collections::HashMap,
-	convert::TryFrom,
-	net::SocketAddr,
-	str::FromStr,
-	sync::{Arc, RwLock},
-};
+use std::{collections::HashMap, convert::TryFrom, net::SocketAddr, str::FromStr, sync::Arc};
+use subxt::sp_core::{hexdisplay::HexDisplay, sr25519, Pair};
 use tokio::task::JoinHandle;
 use warp::{
 	hyper::StatusCode,
@@ -21,9 +16,20 @@ use warp::{
 	Filter,
 };
 
-#[derive(PartialEq, Eq, Serialize, Copy, Clone, Debug)]
-#[repr(transparent)]
-pub struct NormalizedPrice(u64);
+/// The price of an asset, along with the breakdown of its sources and the signature of the
+/// operator of the server.
+#[derive(Serialize, Clone, Debug)]
+struct PriceResponse {
+	/// The oracle is expecting an object with the asset as key and it's price as value.
+	#[serde(flatten)]
+	price: HashMap<String, u64>,
+	timestamp: TimeStamp,
+	sources: Vec<SourcePrice>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	signer: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	signature: Option<String>,
+}
 
 pub struct Frontend {
 	pub shutdown_trigger: oneshot::Sender<()>,
@@ -33,16 +39,18 @@ pub struct Frontend {
 impl Frontend {
 	pub async fn new(
 		listening_address: &str,
-		prices_cache: Arc<RwLock<PriceCache>>,
-		cache_duration: Duration,
-		expected_exponent: Exponent,
+		aggregator: Aggregator,
+		operator: Option<sr25519::Pair>,
 	) -> Self {
+		let aggregator = Arc::new(aggregator);
+		let operator = Arc::new(operator);
+
 		let get_asset_id_endpoint =
 			warp::path!("asset_id" / Asset).and(warp::get()).map(get_asset_id);
 
 		let get_price_endpoint =
 			warp::path!("price" / CurrencyId).and(warp::get()).map(move |currency_index| {
-				get_price(prices_cache.clone(), currency_index, cache_duration, expected_exponent)
+				get_price(&aggregator, (*operator).as_ref(), currency_index)
 			});
 
 		let (shutdown_trigger, shutdown) = oneshot::channel::<()>();
@@ -68,133 +76,100 @@ fn get_asset_id(x: Asset) -> WithStatus<Json> {
 }
 
 fn get_price(
-	prices: ThreadSafePriceCache,
+	aggregator: &Aggregator,
+	operator: Option<&sr25519::Pair>,
 	currency_index: CurrencyId,
-	cache_duration: Duration,
-	expected_exponent: Exponent,
 ) -> WithStatus<Json> {
-	match Asset::try_from(currency_index).and_then(|asset| {
-		let now = TimeStamp::now();
-
-		prices
-			.get(&asset)
-			.and_then(|timestamped_price| {
-				ensure_uptodate_price(&cache_duration, &now, &timestamped_price)
-			})
-			.map(|x| normalize_price(expected_exponent, x))
-			.ok_or(())
-	}) {
-		// The oracle is expecting an object with the asset as key and it's price as value.
-		Ok(normalized_price) => reply::with_status(
-			reply::json(
-				&[(format!("{}", currency_index), normalized_price)]
-					.into_iter()
-					.collect::<HashMap<_, _>>(),
-			),
+	let timestamp = TimeStamp::now();
+	match Asset::try_from(currency_index)
+		.and_then(|asset| aggregator.aggregate(asset, timestamp).map_err(|_| ()))
+	{
+		Ok(aggregated_price) => reply::with_status(
+			reply::json(&price_response(operator, currency_index, timestamp, aggregated_price)),
 			StatusCode::OK,
 		),
 		Err(_) => reply::with_status(reply::json(&()), StatusCode::NOT_FOUND),
 	}
 }
 
-/// Ensure that the price is not outdated.
-fn ensure_uptodate_price(
-	&max_cache_duration: &Duration,
-	current_timestamp: &TimeStamp,
-	timestamped_price: &TimeStampedPrice,
-) -> Option<(Price, Exponent)> {
-	let elapsed = current_timestamp.elapsed_since(&timestamped_price.timestamp);
-	if elapsed < max_cache_duration {
-		Some(timestamped_price.value)
-	} else {
-		None
-	}
+/// The payload signed by the operator, SCALE encoded so that it can be verified on chain.
+pub fn signed_payload(currency_index: CurrencyId, price: u64, timestamp: TimeStamp) -> Vec<u8> {
+	(currency_index.0, price, timestamp.0).encode()
 }
 
-/// Normalize the price to the expected exponent.
-fn normalize_price(
-	Exponent(expected_exponent): Exponent,
-	(Price(p), Exponent(q)): (Price, Exponent),
-) -> NormalizedPrice {
-	// NOTE(hussein-aitlahcen): we want to go from x*10^q to x*10^expected_exponent
-	let dt = expected_exponent - q;
-	let normalized_price = match dt.signum() {
-		0 => p,
-		1 => p * u64::pow(10_u64, dt as u32),
-		-1 => p / u64::pow(10_u64, dt.abs() as u32),
-		_ => unreachable!(),
+fn price_response(
+	operator: Option<&sr25519::Pair>,
+	currency_index: CurrencyId,
+	timestamp: TimeStamp,
+	AggregatedPrice { price, sources }: AggregatedPrice,
+) -> PriceResponse {
+	let (signer, signature) = match operator {
+		Some(operator) => {
+			let signature = operator.sign(&signed_payload(currency_index, price.0, timestamp));
+			(
+				Some(format!("0x{}", HexDisplay::from(&operator.public().0))),
+				Some(format!("0x{}", HexDisplay::from(&signature.0))),
+			)
+		},
+		None => (None, None),
 	};
-	NormalizedPrice(normalized_price)
+	PriceResponse {
+		price: [(format!("{}", currency_index), price.0)].into_iter().collect(),
+		timestamp,
+		sources,
+		signer,
+		signature,
+	}
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{normalize_price, NormalizedPrice};
+	use super::*;
 	use crate::{
-		feed::{Exponent, Price, TimeStamp, TimeStamped},
-		frontend::ensure_uptodate_price,
+		aggregation::{NormalizedPrice, SourceStatus},
+		feed::FeedIdentifier,
 	};
-	use chrono::Duration;
 
 	#[test]
-	fn test_ensure_uptodate_price() {
-		let value = (Price(0x1337), Exponent(10));
-		[
-			(
-				(
-					Duration::seconds(1),
-					TimeStamp(1),
-					TimeStamped { value, timestamp: TimeStamp(0) },
-				),
-				None,
-			),
-			(
-				(
-					Duration::seconds(5),
-					TimeStamp(6),
-					TimeStamped { value, timestamp: TimeStamp(0) },
-				),
-				None,
-			),
-			(
-				(
-					Duration::seconds(20),
-					TimeStamp(20),
-					TimeStamped { value, timestamp: TimeStamp(1) },
-				),
-				Some(value),
-			),
-			(
-				(
-					Duration::seconds(10),
-					TimeStamp(14),
-					TimeStamped { value, timestamp: TimeStamp(5) },
-				),
-				Some(value),
-			),
-		]
-		.iter()
-		.for_each(|((max_cache_duration, current_timestamp, timestamped_price), expected)| {
-			assert_eq!(
-				ensure_uptodate_price(max_cache_duration, current_timestamp, timestamped_price),
-				*expected
-			);
-		})
-	}
+	fn test_price_response() {
+		let operator = sr25519::Pair::from_string("//Alice", None).expect("valid seed");
+		let sources = vec![SourcePrice {
+			feed: FeedIdentifier::Binance,
+			weight: 1,
+			price: NormalizedPrice(1234),
+			timestamp: TimeStamp(42),
+			status: SourceStatus::Accepted,
+		}];
+		let aggregated_price = AggregatedPrice { price: NormalizedPrice(1234), sources };
 
-	#[test]
-	fn test_get_normalized_price() {
-		let expected_exponent = Exponent(2);
-		[
-			((Price(0xCAFEBABE), Exponent(-2)), NormalizedPrice(0xCAFEBABE * u64::pow(10, 4))),
-			((Price(0xDEADBEEF), Exponent(2)), NormalizedPrice(0xDEADBEEF)),
-			((Price(1), Exponent(0)), NormalizedPrice(u64::pow(10, 2))),
-			((Price(12), Exponent(-1)), NormalizedPrice(12 * u64::pow(10, 3))),
-			((Price(454000), Exponent(4)), NormalizedPrice(4540)),
-		]
-		.iter()
-		.for_each(|&(price, expected_price)| {
-			assert_eq!(normalize_price(expected_exponent, price), expected_price);
+		let unsigned = price_response(None, CurrencyId(4), TimeStamp(43), aggregated_price.clone());
+		assert_eq!(
+			serde_json::to_value(&unsigned).expect("serializable"),
+			serde_json::json!({
+				"4": 1234,
+				"timestamp": 43,
+				"sources": [{
+					"feed": "binance",
+					"weight": 1,
+					"price": 1234,
+					"timestamp": 42,
+					"status": "accepted",
+				}],
+			})
+		);
+
+		let signed =
+			price_response(Some(&operator), CurrencyId(4), TimeStamp(43), aggregated_price);
+		let signature = signed.signature.expect("signed response");
+		let mut raw_signature = [0_u8; 64];
+		raw_signature.iter_mut().enumerate().for_each(|(i, byte)| {
+			*byte = u8::from_str_radix(&signature[2 + 2 * i..4 + 2 * i], 16).expect("hex encoded")
 		});
+		assert!(sr25519::Pair::verify(
+			&sr25519::Signature::from_raw(raw_signature),
+			signed_payload(CurrencyId(4), 1234, TimeStamp(43)),
+			&operator.public()
+		));
+		assert_eq!(signed.signer, Some(format!("0x{}", HexDisplay::from(&operator.public().0))));
 	}
 }
@@ -1,30 +1,32 @@
+mod aggregation;
 mod asset;
 mod backend;
 mod cache;
+mod config;
 mod feed;
 mod frontend;
 mod opts;
 
-#[macro_use]
-extern crate custom_derive;
-#[macro_use]
-extern crate enum_derive;
 #[macro_use]
 extern crate lazy_static;
 
 use crate::{
+	aggregation::{Aggregator, PriceSources},
 	asset::Asset,
 	backend::{Backend, FeedNotificationAction},
 	cache::ThreadSafePriceCache,
+	config::Config,
 	feed::{
-		binance::BinanceFeed, composable::ComposableFeed, Exponent, FeedHandle, FeedIdentifier,
-		FeedNotification, FeedStream, TimeStampedPrice,
+		binance::BinanceFeed,
+		composable::ComposableFeed,
+		mock::{self, MockFeed, MockPrices},
+		pyth::PythFeed,
+		Exponent, FeedIdentifier, FeedNotification, Price, TimeStampedPrice,
 	},
 	frontend::Frontend,
 	opts::Opts,
 };
 
-use chrono::Duration;
 use clap::Parser;
 use futures::{
 	future::join_all,
@@ -32,10 +34,13 @@ use futures::{
 };
 use signal_hook::consts::signal::*;
 use signal_hook_tokio::{Signals, SignalsInfo};
+use subxt::sp_core::{sr25519, Pair};
 use tokio::sync::watch;
 
+use primitives::currency::CurrencyId;
 use std::{
-	collections::HashMap,
+	collections::{HashMap, HashSet},
+	path::Path,
 	str::FromStr,
 	sync::{
 		atomic::{AtomicBool, Ordering},
@@ -60,21 +65,38 @@ async fn main() {
 	// created above to send the shutdown message
 	let keep_running = Arc::new(AtomicBool::new(true));
 
-	let binance = BinanceFeed::start(
-		keep_running.clone(),
-		&[Asset::KSM].into_iter().collect(),
-		Asset::from_str(&opts.quote_asset).expect("invalid quote asset"),
-	)
-	.await
-	.expect("unable to start binance feed");
+	let config = Config::load(Path::new(&opts.config)).expect("invalid configuration");
 
-	let composable = ComposableFeed::start(
-		feed_shutdown_receiver,
-		opts.composable_node,
-		&[(Asset::PICA, Asset::USDC)].into_iter().collect(),
-	)
-	.await
-	.expect("unable to start composable feed");
+	for asset in &config.assets {
+		asset::register(&asset.symbol, CurrencyId(asset.currency_id), asset.quote);
+	}
+
+	let assets_of = |feed| {
+		config
+			.assets_of(feed)
+			.into_iter()
+			.map(|symbol| Asset::from_str(symbol).expect("asset is registered"))
+			.collect::<HashSet<_>>()
+	};
+	let quote_asset = |symbol: &str| Asset::from_str(symbol).expect("quote asset is registered");
+
+	let price_sources = config
+		.prices
+		.iter()
+		.map(|price| {
+			(
+				Asset::from_str(&price.asset).expect("asset is registered"),
+				PriceSources {
+					sources: price
+						.sources
+						.iter()
+						.map(|source| (source.feed, source.weight))
+						.collect(),
+					aggregation: price.aggregation.unwrap_or(config.aggregation),
+				},
+			)
+		})
+		.collect();
 
 	/* NOTE(hussein-aitlahcen):
 		 Introducing a new feed is a matter of merge it with the existing ones.
@@ -84,8 +106,84 @@ async fn main() {
 
 		 ... merge(vec![..., new_feed])
 	*/
+	let mut feeds = Vec::new();
+
+	if let Some(binance) = &config.feeds.binance {
+		feeds.push(
+			BinanceFeed::start(
+				keep_running.clone(),
+				&assets_of(FeedIdentifier::Binance),
+				quote_asset(&binance.quote_asset),
+			)
+			.await
+			.expect("unable to start binance feed"),
+		);
+	}
+
+	if let Some(pyth) = &config.feeds.pyth {
+		feeds.push(
+			PythFeed::start(
+				url::Url::parse(&pyth.url).expect("invalid pythd url"),
+				keep_running.clone(),
+				&assets_of(FeedIdentifier::Pyth),
+				quote_asset(&pyth.quote_asset),
+			)
+			.await
+			.expect("unable to start pyth feed"),
+		);
+	}
+
+	if let Some(composable) = &config.feeds.composable {
+		let quote = quote_asset(&composable.quote_asset);
+		feeds.push(
+			ComposableFeed::start(
+				feed_shutdown_receiver.clone(),
+				composable.url.clone(),
+				&assets_of(FeedIdentifier::Composable)
+					.into_iter()
+					.map(|base| (base, quote))
+					.collect(),
+			)
+			.await
+			.expect("unable to start composable feed"),
+		);
+	}
+
+	if let Some(mock) = &config.feeds.mock {
+		let mut prices = MockPrices::new();
+		let replayed = match &mock.replay_file {
+			Some(path) => mock::parse_replay(
+				&std::fs::read_to_string(path).expect("unable to read replay file"),
+			)
+			.expect("invalid replay file"),
+			None => Vec::new(),
+		};
+		mock.prices
+			.iter()
+			.map(|price| {
+				(
+					Asset::from_str(&price.asset).expect("asset is registered"),
+					Price(price.price),
+					Exponent(price.exponent),
+				)
+			})
+			.chain(replayed)
+			.for_each(|(asset, price, exponent)| {
+				prices.entry(asset).or_default().push((price, exponent))
+			});
+		feeds.push(
+			MockFeed::start(
+				feed_shutdown_receiver.clone(),
+				std::time::Duration::from_millis(mock.interval_ms),
+				prices,
+			)
+			.await
+			.expect("unable to start mock feed"),
+		);
+	}
+
 	let (feeds_handle, feeds_source) = {
-		let (handles, sources) = [binance, composable].into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
+		let (handles, sources) = feeds.into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
 		(join_all(handles), futures::stream::select_all(sources))
 	};
 
@@ -95,7 +193,7 @@ async fn main() {
 
 	let backend = Backend::new::<
 		FeedNotification<FeedIdentifier, Asset, TimeStampedPrice>,
-		FeedNotificationAction<Asset, TimeStampedPrice>,
+		FeedNotificationAction<(FeedIdentifier, Asset), TimeStampedPrice>,
 		_,
 		_,
 		_,
@@ -103,11 +201,14 @@ async fn main() {
 	>(prices_cache.clone(), feeds_source, backend_shutdown_trigger)
 	.await;
 
+	let operator = opts.operator_key.as_ref().map(|secret_uri| {
+		sr25519::Pair::from_string(secret_uri, None).expect("invalid operator key")
+	});
+
 	let frontend = Frontend::new(
 		&opts.listening_address,
-		prices_cache,
-		Duration::seconds(opts.cache_duration.into()),
-		Exponent(opts.expected_exponent),
+		Aggregator::new(prices_cache, price_sources, Exponent(opts.expected_exponent)),
+		operator,
 	)
 	.await;
 
@@ -2,7 +2,6 @@ use crate::abstraction::IndexOf;
 use alloc::{collections::BTreeMap, string::ToString};
 use codec::{Decode, Encode};
 use core::ops::Add;
-use fixed::{types::extra::U16, FixedU128};
 use num::Zero;
 use scale_info::TypeInfo;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -28,6 +27,11 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 #[repr(transparent)]
 pub struct AssetId(pub u128);
 
+impl AssetId {
+	/// Reserved identifier of [`InvalidAsset`], must never be used.
+	pub const INVALID: AssetId = AssetId(<Assets as IndexOf<InvalidAsset, _>>::INDEX as u128);
+}
+
 impl From<AssetId> for u128 {
 	fn from(val: AssetId) -> Self {
 		val.0
@@ -182,19 +186,41 @@ impl From<u128> for Amount {
 }
 
 impl Amount {
+	/// Resolve the amount against the currently available `value`.
+	/// The result is `intercept + value * slope / u128::MAX`, bounded by `value`.
 	#[inline]
 	pub fn apply(&self, value: u128) -> u128 {
-		let amount = FixedU128::<U16>::from_num(value)
-			.saturating_mul(
-				FixedU128::<U16>::from_num(self.slope)
-					.saturating_div(FixedU128::<U16>::from_num(u128::MAX)),
-			)
-			.to_num::<u128>()
-			.saturating_add(self.intercept.0);
-		u128::min(value, amount)
+		u128::min(value, self.apply_unbounded(value))
+	}
+
+	/// Same as [`Amount::apply`], without bounding the result by `value`.
+	/// Used when `value` is a reference point rather than the available amount, i.e. to express a
+	/// minimum to receive relative to a quote.
+	#[inline]
+	pub fn apply_unbounded(&self, value: u128) -> u128 {
+		mul_ratio(value, self.slope).saturating_add(self.intercept.0)
 	}
 }
 
+/// Compute `value * parts / u128::MAX` (rounded down) without intermediate overflow.
+fn mul_ratio(value: u128, parts: u128) -> u128 {
+	const HALF: u32 = 64;
+	const LOW: u128 = u64::MAX as u128;
+	let (a_hi, a_lo) = (value >> HALF, value & LOW);
+	let (b_hi, b_lo) = (parts >> HALF, parts & LOW);
+	let (lo_lo, lo_hi, hi_lo, hi_hi) = (a_lo * b_lo, a_lo * b_hi, a_hi * b_lo, a_hi * b_hi);
+	let middle = (lo_lo >> HALF) + (lo_hi & LOW) + (hi_lo & LOW);
+	let low = (lo_lo & LOW) | (middle << HALF);
+	let high = hi_hi + (lo_hi >> HALF) + (hi_lo >> HALF) + (middle >> HALF);
+	// high * 2^128 + low = high * u128::MAX + (high + low)
+	let (rest, carry) = high.overflowing_add(low);
+	let carry = u128::from(carry);
+	// carry * 2^128 + rest = carry * u128::MAX + (rest + carry), which cannot overflow as the
+	// product is bounded by u128::MAX^2.
+	let remainder = rest + carry;
+	high.saturating_add(carry).saturating_add(u128::from(remainder == u128::MAX))
+}
+
 #[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
 #[derive(
 	Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Encode, Decode, TypeInfo, Serialize, Deserialize,
@@ -248,9 +274,27 @@ mod tests {
 	use super::*;
 	#[test]
 	fn asset_ids() {
+		assert_eq!(AssetId::INVALID, AssetId(0));
 		assert_eq!(PICA::ID, AssetId(1));
 		assert_eq!(ETH::ID, AssetId(2));
 		assert_eq!(USDT::ID, AssetId(3));
 		assert_eq!(USDC::ID, AssetId(4));
 	}
+
+	#[test]
+	fn amount_apply() {
+		assert_eq!(Amount::absolute(5).apply(10), 5);
+		assert_eq!(Amount::absolute(50).apply(10), 10);
+		assert_eq!(Amount::ratio(u128::MAX).apply(u128::MAX), u128::MAX);
+		assert_eq!(Amount::ratio(u128::MAX).apply(1337), 1337);
+		assert_eq!(Amount::ratio(u128::MAX / 2).apply(1000), 499);
+		assert_eq!(Amount::ratio(u128::MAX / 2 + 1).apply(1000), 500);
+		assert_eq!(Amount::ratio(0).apply(1000), 0);
+		assert_eq!((Amount::ratio(u128::MAX / 4 + 1) + Amount::absolute(10)).apply(100), 35);
+		assert_eq!(Amount::absolute(50).apply_unbounded(10), 50);
+		assert_eq!(
+			(Amount::ratio(u128::MAX / 2 + 1) + Amount::absolute(10)).apply_unbounded(100),
+			60
+		);
+	}
 }
@@ -0,0 +1,110 @@
+use crate::{AssetId, Displayed};
+use alloc::{boxed::Box, vec::Vec};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+
+/// Boolean expression over the balances held by the program, used by the `If` instruction to
+/// branch on the outcome of the previous instructions.
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+	/// Holds if the balance of `asset` is greater than or equal to `amount`.
+	#[serde(rename_all = "snake_case")]
+	AtLeast {
+		asset: AssetId,
+		amount: Displayed<u128>,
+	},
+	/// Holds if the balance of `asset` is lower than or equal to `amount`.
+	#[serde(rename_all = "snake_case")]
+	AtMost {
+		asset: AssetId,
+		amount: Displayed<u128>,
+	},
+	Not(Box<Condition>),
+	/// Holds if all the conditions hold, trivially true if empty.
+	All(Vec<Condition>),
+	/// Holds if any of the conditions holds, trivially false if empty.
+	Any(Vec<Condition>),
+}
+
+impl Condition {
+	#[inline]
+	pub fn at_least(asset: impl Into<AssetId>, amount: u128) -> Self {
+		Condition::AtLeast { asset: asset.into(), amount: Displayed(amount) }
+	}
+
+	#[inline]
+	pub fn at_most(asset: impl Into<AssetId>, amount: u128) -> Self {
+		Condition::AtMost { asset: asset.into(), amount: Displayed(amount) }
+	}
+
+	#[allow(clippy::should_implement_trait)]
+	#[inline]
+	pub fn not(self) -> Self {
+		Condition::Not(Box::new(self))
+	}
+
+	/// Evaluate the condition, `balance` being used to query the current balance of an asset.
+	pub fn evaluate<E>(&self, balance: &impl Fn(AssetId) -> Result<u128, E>) -> Result<bool, E> {
+		match self {
+			Condition::AtLeast { asset, amount: Displayed(amount) } =>
+				Ok(balance(*asset)? >= *amount),
+			Condition::AtMost { asset, amount: Displayed(amount) } =>
+				Ok(balance(*asset)? <= *amount),
+			Condition::Not(condition) => condition.evaluate(balance).map(|x| !x),
+			Condition::All(conditions) => {
+				for condition in conditions {
+					if !condition.evaluate(balance)? {
+						return Ok(false)
+					}
+				}
+				Ok(true)
+			},
+			Condition::Any(conditions) => {
+				for condition in conditions {
+					if condition.evaluate(balance)? {
+						return Ok(true)
+					}
+				}
+				Ok(false)
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Asset, PICA, USDC};
+	use alloc::vec;
+
+	#[test]
+	fn evaluate() {
+		let balance = |asset| -> Result<u128, ()> {
+			Ok(match asset {
+				PICA::ID => 100,
+				USDC::ID => 10,
+				_ => 0,
+			})
+		};
+		assert_eq!(Condition::at_least(PICA, 100).evaluate(&balance), Ok(true));
+		assert_eq!(Condition::at_least(PICA, 101).evaluate(&balance), Ok(false));
+		assert_eq!(Condition::at_most(USDC, 10).evaluate(&balance), Ok(true));
+		assert_eq!(Condition::at_most(USDC, 9).evaluate(&balance), Ok(false));
+		assert_eq!(Condition::at_most(USDC, 9).not().evaluate(&balance), Ok(true));
+		assert_eq!(
+			Condition::All(vec![Condition::at_least(PICA, 1), Condition::at_least(USDC, 11)])
+				.evaluate(&balance),
+			Ok(false)
+		);
+		assert_eq!(
+			Condition::Any(vec![Condition::at_least(PICA, 1), Condition::at_least(USDC, 11)])
+				.evaluate(&balance),
+			Ok(true)
+		);
+		assert_eq!(Condition::All(vec![]).evaluate(&balance), Ok(true));
+		assert_eq!(Condition::Any(vec![]).evaluate(&balance), Ok(false));
+	}
+}
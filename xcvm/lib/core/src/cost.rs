@@ -0,0 +1,263 @@
+//! Estimation of the fees required to execute a program tree.
+//!
+//! Every instruction is accounted once, whatever the branch taken at runtime, and loops are not
+//! unrolled: the estimate is the cost of a straight execution of every program of the tree.
+
+use crate::{AssetId, Funds, Instruction, NetworkId, Program};
+use alloc::collections::{BTreeMap, VecDeque};
+use codec::Encode;
+
+type EstimatedProgram<Payload, Account> =
+	Program<VecDeque<Instruction<NetworkId, Payload, Account, Funds>>>;
+
+/// A fee, expressed in an asset chosen by the network charging it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Fee {
+	pub asset: AssetId,
+	pub amount: u128,
+}
+
+/// Per network pricing of the execution and bridging of programs.
+pub trait CostModel {
+	type Error;
+
+	/// Fee charged by `network` to execute the `instruction`, excluding the program spawned by a
+	/// `Spawn` which is accounted on its own network.
+	fn execution<Payload: AsRef<[u8]>, Account>(
+		&self,
+		network: NetworkId,
+		instruction: &Instruction<NetworkId, Payload, Account, Funds>,
+	) -> Result<Fee, Self::Error>;
+
+	/// Fee charged to bridge a program of `size` encoded bytes, along the `assets`, from the
+	/// network `from` to the network `to`.
+	fn bridging(
+		&self,
+		from: NetworkId,
+		to: NetworkId,
+		assets: &Funds,
+		size: u32,
+	) -> Result<Fee, Self::Error>;
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum EstimationError<E> {
+	Model(E),
+	Overflow,
+}
+
+impl<E> From<E> for EstimationError<E> {
+	fn from(error: E) -> Self {
+		EstimationError::Model(error)
+	}
+}
+
+/// Fees of a program tree, summed by network and asset.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Estimate {
+	pub execution: BTreeMap<NetworkId, BTreeMap<AssetId, u128>>,
+	/// Bridging fees, indexed by the network the program is spawned from.
+	pub bridging: BTreeMap<NetworkId, BTreeMap<AssetId, u128>>,
+}
+
+impl Estimate {
+	/// Sum of all the fees, by asset.
+	pub fn total(&self) -> Option<Funds<u128>> {
+		let mut total = BTreeMap::<AssetId, u128>::new();
+		for (asset, amount) in self
+			.execution
+			.values()
+			.chain(self.bridging.values())
+			.flat_map(|fees| fees.iter())
+		{
+			let sum = total.entry(*asset).or_default();
+			*sum = sum.checked_add(*amount)?;
+		}
+		Some(Funds(total))
+	}
+}
+
+/// Estimate the fees of executing `program` on `network`, including all the spawned programs.
+pub fn estimate<M, Payload, Account>(
+	model: &M,
+	network: NetworkId,
+	program: &EstimatedProgram<Payload, Account>,
+) -> Result<Estimate, EstimationError<M::Error>>
+where
+	M: CostModel,
+	Payload: AsRef<[u8]> + Encode,
+	Account: Encode,
+{
+	let mut estimate = Estimate::default();
+	estimate_into(model, network, program, &mut estimate)?;
+	Ok(estimate)
+}
+
+fn estimate_into<M, Payload, Account>(
+	model: &M,
+	network: NetworkId,
+	program: &EstimatedProgram<Payload, Account>,
+	estimate: &mut Estimate,
+) -> Result<(), EstimationError<M::Error>>
+where
+	M: CostModel,
+	Payload: AsRef<[u8]> + Encode,
+	Account: Encode,
+{
+	for instruction in &program.instructions {
+		add(&mut estimate.execution, network, model.execution(network, instruction)?)?;
+		if let Instruction::Spawn { network: target, assets, program, .. } = instruction {
+			let size =
+				u32::try_from(program.encoded_size()).map_err(|_| EstimationError::Overflow)?;
+			add(&mut estimate.bridging, network, model.bridging(network, *target, assets, size)?)?;
+			estimate_into(model, *target, program, estimate)?;
+		}
+	}
+	Ok(())
+}
+
+fn add<E>(
+	fees: &mut BTreeMap<NetworkId, BTreeMap<AssetId, u128>>,
+	network: NetworkId,
+	Fee { asset, amount }: Fee,
+) -> Result<(), EstimationError<E>> {
+	let sum = fees.entry(network).or_default().entry(asset).or_default();
+	*sum = sum.checked_add(amount).ok_or(EstimationError::Overflow)?;
+	Ok(())
+}
+
+/// Linear pricing of a network.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct NetworkCosts {
+	/// Asset in which the network charges its fees.
+	pub fee_asset: AssetId,
+	/// Base fee of any instruction.
+	pub instruction: u128,
+	/// Additional fee per byte of payload, for `Call` and liquidity instructions.
+	pub payload_byte: u128,
+	/// Base fee of sending a program to another network.
+	pub bridge: u128,
+	/// Additional bridging fee per byte of the sent program.
+	pub bridge_byte: u128,
+	/// Additional bridging fee per asset sent along the program.
+	pub bridge_asset: u128,
+}
+
+/// A [`CostModel`] pricing each network linearly, bridging being charged by the sending network.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CostTable(pub BTreeMap<NetworkId, NetworkCosts>);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CostTableError {
+	UnknownNetwork(NetworkId),
+}
+
+impl CostTable {
+	fn costs(&self, network: NetworkId) -> Result<&NetworkCosts, CostTableError> {
+		self.0.get(&network).ok_or(CostTableError::UnknownNetwork(network))
+	}
+}
+
+impl CostModel for CostTable {
+	type Error = CostTableError;
+
+	fn execution<Payload: AsRef<[u8]>, Account>(
+		&self,
+		network: NetworkId,
+		instruction: &Instruction<NetworkId, Payload, Account, Funds>,
+	) -> Result<Fee, Self::Error> {
+		let costs = self.costs(network)?;
+		let payload = match instruction {
+			Instruction::Call { encoded: payload } |
+			Instruction::AddLiquidity { pool: payload, .. } |
+			Instruction::RemoveLiquidity { pool: payload, .. } => payload.as_ref().len(),
+			_ => 0,
+		};
+		let amount = costs
+			.payload_byte
+			.saturating_mul(payload as u128)
+			.saturating_add(costs.instruction);
+		Ok(Fee { asset: costs.fee_asset, amount })
+	}
+
+	fn bridging(
+		&self,
+		from: NetworkId,
+		to: NetworkId,
+		assets: &Funds,
+		size: u32,
+	) -> Result<Fee, Self::Error> {
+		let costs = self.costs(from)?;
+		// Fail early if the program could not be executed on arrival.
+		self.costs(to)?;
+		let amount = costs
+			.bridge_byte
+			.saturating_mul(size.into())
+			.saturating_add(costs.bridge_asset.saturating_mul(assets.0.len() as u128))
+			.saturating_add(costs.bridge);
+		Ok(Fee { asset: costs.fee_asset, amount })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Asset, Ethereum, Juno, Network, Picasso, ProgramBuilder, ETH, PICA};
+	use alloc::{vec, vec::Vec};
+
+	fn table() -> CostTable {
+		let costs = |fee_asset, instruction| NetworkCosts {
+			fee_asset,
+			instruction,
+			payload_byte: 1,
+			bridge: 1000,
+			bridge_byte: 0,
+			bridge_asset: 100,
+		};
+		CostTable(BTreeMap::from([
+			(Picasso::ID, costs(PICA::ID, 10)),
+			(Ethereum::ID, costs(ETH::ID, 20)),
+		]))
+	}
+
+	#[test]
+	fn estimate_nested_programs() {
+		let program = ProgramBuilder::<Picasso, Vec<u8>, Funds>::new("root".into())
+			.call_raw(vec![0; 5])
+			.spawn::<Ethereum, _, (), _>(
+				"child".into(),
+				vec![],
+				Funds::from([(PICA::ID, 100_u128), (ETH::ID, 100_u128)]),
+				|child| Ok(child.call_raw(vec![0; 2]).transfer(vec![1], Funds::empty())),
+			)
+			.expect("valid program")
+			.build();
+		let estimate = estimate(&table(), Picasso::ID, &program).expect("known networks");
+		assert_eq!(
+			estimate,
+			Estimate {
+				execution: BTreeMap::from([
+					(Picasso::ID, BTreeMap::from([(PICA::ID, 15 + 10)])),
+					(Ethereum::ID, BTreeMap::from([(ETH::ID, 22 + 20)])),
+				]),
+				bridging: BTreeMap::from([(Picasso::ID, BTreeMap::from([(PICA::ID, 1200)]))]),
+			}
+		);
+		assert_eq!(
+			estimate.total(),
+			Some(Funds(BTreeMap::from([(PICA::ID, 1225), (ETH::ID, 42)])))
+		);
+	}
+
+	#[test]
+	fn unknown_network() {
+		let program = ProgramBuilder::<Picasso, Vec<u8>, Funds>::new("root".into())
+			.spawn::<Juno, _, (), _>("child".into(), vec![], Funds::empty(), Ok)
+			.expect("valid program")
+			.build();
+		assert_eq!(
+			estimate(&table(), Picasso::ID, &program),
+			Err(EstimationError::Model(CostTableError::UnknownNetwork(Juno::ID)))
+		);
+	}
+}
@@ -1,4 +1,4 @@
-use crate::Program;
+use crate::{Amount, Condition, Program};
 use alloc::{collections::VecDeque, vec::Vec};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
@@ -31,4 +31,25 @@ pub enum Instruction<Network, Payload, Account, Assets> {
 	/// The salt is used to track the program when events are dispatched in the network.
 	#[serde(rename_all = "snake_case")]
 	Spawn { network: Network, salt: Vec<u8>, assets: Assets, program: Program<VecDeque<Self>> },
+	/// Exchange the `give` [`Assets`] against the `want` [`Assets`] on the current [`Network`].
+	///
+	/// The `want` amounts are the minimum to receive, resolved against the quoted output. An
+	/// absolute amount is a hard floor while a ratio expresses the tolerated slippage.
+	#[serde(rename_all = "snake_case")]
+	Swap { give: Assets, want: Assets },
+	/// Provide the [`Assets`] as liquidity to the `pool`, identified by a [`Network`] specific
+	/// payload. The pool shares are credited to the current program.
+	#[serde(rename_all = "snake_case")]
+	AddLiquidity { pool: Payload, assets: Assets },
+	/// Redeem `shares` of the `pool`, resolved against the shares held by the current program.
+	#[serde(rename_all = "snake_case")]
+	RemoveLiquidity { pool: Payload, shares: Amount },
+	/// Continue the execution at the instruction index `to` of the current program.
+	/// Jumping to the length of the program ends the execution.
+	#[serde(rename_all = "snake_case")]
+	Jump { to: u32 },
+	/// Continue with the next instruction if the `condition` holds, otherwise jump to the
+	/// instruction index `otherwise` of the current program.
+	#[serde(rename_all = "snake_case")]
+	If { condition: Condition, otherwise: u32 },
 }
@@ -4,12 +4,19 @@ extern crate alloc;
 
 mod abstraction;
 mod asset;
+mod condition;
+pub mod cost;
 mod instruction;
 mod network;
 mod program;
+pub mod protobuf;
 mod protocol;
+mod registry;
+pub mod validation;
 
-pub use crate::{asset::*, instruction::*, network::*, program::*, protocol::*};
+pub use crate::{
+	asset::*, condition::*, instruction::*, network::*, program::*, protocol::*, registry::*,
+};
 use alloc::{collections::VecDeque, vec::Vec};
 use core::marker::PhantomData;
 
@@ -79,6 +86,57 @@ where
 		protocol.serialize().map(|encoded_call| self.call_raw(encoded_call))
 	}
 
+	#[inline]
+	pub fn swap(mut self, give: Assets, want: Assets) -> Self {
+		self.instructions.push_back(Instruction::Swap { give, want });
+		self
+	}
+
+	#[inline]
+	pub fn add_liquidity(mut self, pool: CurrentNetwork::EncodedCall, assets: Assets) -> Self {
+		self.instructions
+			.push_back(Instruction::AddLiquidity { pool: pool.into(), assets });
+		self
+	}
+
+	#[inline]
+	pub fn remove_liquidity(mut self, pool: CurrentNetwork::EncodedCall, shares: Amount) -> Self {
+		self.instructions
+			.push_back(Instruction::RemoveLiquidity { pool: pool.into(), shares });
+		self
+	}
+
+	/// Jump to the absolute instruction index `to`, see [`ProgramBuilder::position`].
+	#[inline]
+	pub fn jump(mut self, to: u32) -> Self {
+		self.instructions.push_back(Instruction::Jump { to });
+		self
+	}
+
+	/// Only execute the instructions added by `f` if the `condition` holds.
+	/// The builder given to `f` already contains the previous instructions, so absolute indexes
+	/// used for jumps stay valid.
+	#[inline]
+	pub fn if_<E, F>(mut self, condition: Condition, f: F) -> Result<Self, E>
+	where
+		F: FnOnce(Self) -> Result<Self, E>,
+	{
+		let at = self.instructions.len();
+		self.instructions.push_back(Instruction::If { condition, otherwise: 0 });
+		let mut builder = f(self)?;
+		let end = builder.position();
+		if let Some(Instruction::If { otherwise, .. }) = builder.instructions.get_mut(at) {
+			*otherwise = end;
+		}
+		Ok(builder)
+	}
+
+	/// Index of the next instruction to be added.
+	#[inline]
+	pub fn position(&self) -> u32 {
+		u32::try_from(self.instructions.len()).unwrap_or(u32::MAX)
+	}
+
 	#[inline]
 	pub fn build(self) -> Program<VecDeque<Instruction<NetworkId, Vec<u8>, Account, Assets>>> {
 		Program { tag: self.tag, instructions: self.instructions }
@@ -188,4 +246,38 @@ mod tests {
 			},
 		);
 	}
+
+	#[test]
+	fn can_build_branches() {
+		let program = ProgramBuilder::<Picasso, (), Funds>::new("Branches".as_bytes().to_vec())
+			.swap(
+				Funds::from([(PICA::ID, Amount::ratio(u128::MAX))]),
+				Funds::from([(USDC::ID, Amount::absolute(100))]),
+			)
+			.if_::<(), _>(Condition::at_least(USDC, 100), |then| {
+				Ok(then
+					.add_liquidity(vec![0x01], Funds::from([(USDC::ID, Amount::ratio(u128::MAX))]))
+					.jump(0))
+			})
+			.expect("valid program")
+			.remove_liquidity(vec![0x01], Amount::ratio(u128::MAX))
+			.build();
+
+		assert_eq!(
+			program.instructions,
+			VecDeque::from([
+				Instruction::Swap {
+					give: Funds::from([(PICA::ID, Amount::ratio(u128::MAX))]),
+					want: Funds::from([(USDC::ID, Amount::absolute(100))]),
+				},
+				Instruction::If { condition: Condition::at_least(USDC, 100), otherwise: 4 },
+				Instruction::AddLiquidity {
+					pool: vec![0x01],
+					assets: Funds::from([(USDC::ID, Amount::ratio(u128::MAX))]),
+				},
+				Instruction::Jump { to: 0 },
+				Instruction::RemoveLiquidity { pool: vec![0x01], shares: Amount::ratio(u128::MAX) },
+			])
+		);
+	}
 }
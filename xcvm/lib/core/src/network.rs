@@ -25,6 +25,11 @@ use serde::{Deserialize, Serialize};
 #[repr(transparent)]
 pub struct NetworkId(pub u8);
 
+impl NetworkId {
+	/// Reserved identifier of [`InvalidNetwork`], must never be used.
+	pub const INVALID: NetworkId = NetworkId(<Networks as IndexOf<InvalidNetwork, _>>::INDEX);
+}
+
 impl From<u8> for NetworkId {
 	fn from(x: u8) -> Self {
 		NetworkId(x)
@@ -89,6 +94,7 @@ mod tests {
 	use super::*;
 	#[test]
 	fn network_ids() {
+		assert_eq!(NetworkId::INVALID, NetworkId(0u8));
 		assert_eq!(Picasso::ID, NetworkId(1u8));
 		assert_eq!(Ethereum::ID, NetworkId(2u8));
 		assert_eq!(Juno::ID, NetworkId(3u8));
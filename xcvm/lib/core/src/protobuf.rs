@@ -0,0 +1,549 @@
+//! Protobuf wire format of XCVM programs, used for chain to chain communication.
+//! The canonical schema is `proto/xcvm.proto`, the [`wire`] types mirror it field by field.
+
+use crate::{Amount, AssetId, Condition, Displayed, Funds, Instruction, NetworkId, Program};
+use alloc::{
+	boxed::Box,
+	collections::{BTreeMap, VecDeque},
+	vec::Vec,
+};
+use prost::Message;
+
+type WireProgram<Account> = Program<VecDeque<Instruction<NetworkId, Vec<u8>, Account, Funds>>>;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DecodeError {
+	/// The bytes are not a valid protobuf `Program` message.
+	Protobuf,
+	/// A `oneof` field that must be set is missing.
+	MissingField(&'static str),
+	InvalidNetwork(u32),
+	InvalidAccount,
+	DuplicateAsset(AssetId),
+}
+
+impl From<prost::DecodeError> for DecodeError {
+	fn from(_: prost::DecodeError) -> Self {
+		DecodeError::Protobuf
+	}
+}
+
+/// Encode a program in its protobuf representation.
+pub fn encode<Account: AsRef<[u8]>>(program: &WireProgram<Account>) -> Vec<u8> {
+	wire::Program::from(program).encode_to_vec()
+}
+
+/// Decode a program from its protobuf representation.
+pub fn decode<Account: TryFrom<Vec<u8>>>(
+	bytes: &[u8],
+) -> Result<WireProgram<Account>, DecodeError> {
+	wire::Program::decode(bytes)?.try_into()
+}
+
+impl From<u128> for wire::Uint128 {
+	fn from(x: u128) -> Self {
+		wire::Uint128 { high_bits: (x >> 64) as u64, low_bits: x as u64 }
+	}
+}
+
+impl From<wire::Uint128> for u128 {
+	fn from(wire::Uint128 { high_bits, low_bits }: wire::Uint128) -> Self {
+		(u128::from(high_bits) << 64) | u128::from(low_bits)
+	}
+}
+
+/// Missing scalar messages are decoded as their default value, as for any protobuf scalar.
+fn uint128(x: Option<wire::Uint128>) -> u128 {
+	x.map(Into::into).unwrap_or_default()
+}
+
+impl From<&Amount> for wire::Amount {
+	fn from(Amount { intercept: Displayed(intercept), slope }: &Amount) -> Self {
+		wire::Amount { intercept: Some((*intercept).into()), slope: Some((*slope).into()) }
+	}
+}
+
+impl From<wire::Amount> for Amount {
+	fn from(wire::Amount { intercept, slope }: wire::Amount) -> Self {
+		Amount { intercept: Displayed(uint128(intercept)), slope: uint128(slope) }
+	}
+}
+
+impl From<&Funds> for wire::Funds {
+	fn from(Funds(assets): &Funds) -> Self {
+		wire::Funds {
+			assets: assets
+				.iter()
+				.map(|(AssetId(asset_id), amount)| wire::Asset {
+					asset_id: Some((*asset_id).into()),
+					amount: Some(amount.into()),
+				})
+				.collect(),
+		}
+	}
+}
+
+impl TryFrom<wire::Funds> for Funds {
+	type Error = DecodeError;
+	fn try_from(wire::Funds { assets }: wire::Funds) -> Result<Self, Self::Error> {
+		let mut funds = BTreeMap::new();
+		for wire::Asset { asset_id, amount } in assets {
+			let asset_id = AssetId(uint128(asset_id));
+			let amount = amount.map(Into::into).unwrap_or_else(|| Amount::absolute(0));
+			if funds.insert(asset_id, amount).is_some() {
+				return Err(DecodeError::DuplicateAsset(asset_id))
+			}
+		}
+		Ok(Funds(funds))
+	}
+}
+
+fn funds(x: Option<wire::Funds>) -> Result<Funds, DecodeError> {
+	x.map(TryInto::try_into).transpose().map(|x| x.unwrap_or_else(Funds::empty))
+}
+
+impl From<&Condition> for wire::Condition {
+	fn from(condition: &Condition) -> Self {
+		use wire::condition::Condition as C;
+		let balance = |AssetId(asset_id): &AssetId, Displayed(amount): &Displayed<u128>| {
+			wire::Balance { asset_id: Some((*asset_id).into()), amount: Some((*amount).into()) }
+		};
+		let conditions = |conditions: &Vec<Condition>| wire::Conditions {
+			conditions: conditions.iter().map(Into::into).collect(),
+		};
+		wire::Condition {
+			condition: Some(match condition {
+				Condition::AtLeast { asset, amount } => C::AtLeast(balance(asset, amount)),
+				Condition::AtMost { asset, amount } => C::AtMost(balance(asset, amount)),
+				Condition::Not(condition) => C::Not(Box::new(condition.as_ref().into())),
+				Condition::All(all) => C::All(conditions(all)),
+				Condition::Any(any) => C::Any(conditions(any)),
+			}),
+		}
+	}
+}
+
+impl TryFrom<wire::Condition> for Condition {
+	type Error = DecodeError;
+	fn try_from(wire::Condition { condition }: wire::Condition) -> Result<Self, Self::Error> {
+		use wire::condition::Condition as C;
+		let balance = |wire::Balance { asset_id, amount }: wire::Balance| {
+			(AssetId(uint128(asset_id)), Displayed(uint128(amount)))
+		};
+		let conditions = |wire::Conditions { conditions }: wire::Conditions| {
+			conditions.into_iter().map(TryInto::try_into).collect::<Result<Vec<_>, _>>()
+		};
+		Ok(match condition.ok_or(DecodeError::MissingField("Condition.condition"))? {
+			C::AtLeast(x) => {
+				let (asset, amount) = balance(x);
+				Condition::AtLeast { asset, amount }
+			},
+			C::AtMost(x) => {
+				let (asset, amount) = balance(x);
+				Condition::AtMost { asset, amount }
+			},
+			C::Not(condition) => Condition::Not(Box::new((*condition).try_into()?)),
+			C::All(all) => Condition::All(conditions(all)?),
+			C::Any(any) => Condition::Any(conditions(any)?),
+		})
+	}
+}
+
+impl<Account: AsRef<[u8]>> From<&Instruction<NetworkId, Vec<u8>, Account, Funds>>
+	for wire::Instruction
+{
+	fn from(instruction: &Instruction<NetworkId, Vec<u8>, Account, Funds>) -> Self {
+		use wire::instruction::Instruction as I;
+		wire::Instruction {
+			instruction: Some(match instruction {
+				Instruction::Transfer { to, assets } => I::Transfer(wire::Transfer {
+					account: to.as_ref().to_vec(),
+					assets: Some(assets.into()),
+				}),
+				Instruction::Call { encoded } => I::Call(wire::Call { payload: encoded.clone() }),
+				Instruction::Spawn { network: NetworkId(network), salt, assets, program } =>
+					I::Spawn(wire::Spawn {
+						network: u32::from(*network),
+						salt: salt.clone(),
+						assets: Some(assets.into()),
+						program: Some(program.into()),
+					}),
+				Instruction::Swap { give, want } =>
+					I::Swap(wire::Swap { give: Some(give.into()), want: Some(want.into()) }),
+				Instruction::AddLiquidity { pool, assets } => I::AddLiquidity(wire::AddLiquidity {
+					pool: pool.clone(),
+					assets: Some(assets.into()),
+				}),
+				Instruction::RemoveLiquidity { pool, shares } =>
+					I::RemoveLiquidity(wire::RemoveLiquidity {
+						pool: pool.clone(),
+						shares: Some(shares.into()),
+					}),
+				Instruction::Jump { to } => I::Jump(wire::Jump { to: *to }),
+				Instruction::If { condition, otherwise } =>
+					I::Branch(wire::If { condition: Some(condition.into()), otherwise: *otherwise }),
+			}),
+		}
+	}
+}
+
+impl<Account: TryFrom<Vec<u8>>> TryFrom<wire::Instruction>
+	for Instruction<NetworkId, Vec<u8>, Account, Funds>
+{
+	type Error = DecodeError;
+	fn try_from(wire::Instruction { instruction }: wire::Instruction) -> Result<Self, Self::Error> {
+		use wire::instruction::Instruction as I;
+		Ok(match instruction.ok_or(DecodeError::MissingField("Instruction.instruction"))? {
+			I::Transfer(wire::Transfer { account, assets }) => Instruction::Transfer {
+				to: Account::try_from(account).map_err(|_| DecodeError::InvalidAccount)?,
+				assets: funds(assets)?,
+			},
+			I::Call(wire::Call { payload }) => Instruction::Call { encoded: payload },
+			I::Spawn(wire::Spawn { network, salt, assets, program }) => Instruction::Spawn {
+				network: u8::try_from(network)
+					.map(NetworkId)
+					.map_err(|_| DecodeError::InvalidNetwork(network))?,
+				salt,
+				assets: funds(assets)?,
+				program: program.unwrap_or_default().try_into()?,
+			},
+			I::Swap(wire::Swap { give, want }) =>
+				Instruction::Swap { give: funds(give)?, want: funds(want)? },
+			I::AddLiquidity(wire::AddLiquidity { pool, assets }) =>
+				Instruction::AddLiquidity { pool, assets: funds(assets)? },
+			I::RemoveLiquidity(wire::RemoveLiquidity { pool, shares }) =>
+				Instruction::RemoveLiquidity {
+					pool,
+					shares: shares.map(Into::into).unwrap_or_else(|| Amount::absolute(0)),
+				},
+			I::Jump(wire::Jump { to }) => Instruction::Jump { to },
+			I::Branch(wire::If { condition, otherwise }) => Instruction::If {
+				condition: condition
+					.ok_or(DecodeError::MissingField("If.condition"))?
+					.try_into()?,
+				otherwise,
+			},
+		})
+	}
+}
+
+impl<Account: AsRef<[u8]>> From<&WireProgram<Account>> for wire::Program {
+	fn from(Program { tag, instructions }: &WireProgram<Account>) -> Self {
+		wire::Program {
+			tag: tag.clone(),
+			instructions: instructions.iter().map(Into::into).collect(),
+		}
+	}
+}
+
+impl<Account: TryFrom<Vec<u8>>> TryFrom<wire::Program> for WireProgram<Account> {
+	type Error = DecodeError;
+	fn try_from(wire::Program { tag, instructions }: wire::Program) -> Result<Self, Self::Error> {
+		Ok(Program {
+			tag,
+			instructions: instructions
+				.into_iter()
+				.map(TryInto::try_into)
+				.collect::<Result<VecDeque<_>, _>>()?,
+		})
+	}
+}
+
+/// Message types of `proto/xcvm.proto`.
+pub mod wire {
+	use alloc::vec::Vec;
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Uint128 {
+		#[prost(uint64, tag = "1")]
+		pub high_bits: u64,
+		#[prost(uint64, tag = "2")]
+		pub low_bits: u64,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Program {
+		#[prost(bytes = "vec", tag = "1")]
+		pub tag: Vec<u8>,
+		#[prost(message, repeated, tag = "2")]
+		pub instructions: Vec<Instruction>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Instruction {
+		#[prost(oneof = "instruction::Instruction", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
+		pub instruction: Option<instruction::Instruction>,
+	}
+
+	pub mod instruction {
+		#[derive(Clone, PartialEq, prost::Oneof)]
+		pub enum Instruction {
+			#[prost(message, tag = "1")]
+			Transfer(super::Transfer),
+			#[prost(message, tag = "2")]
+			Call(super::Call),
+			#[prost(message, tag = "3")]
+			Spawn(super::Spawn),
+			#[prost(message, tag = "4")]
+			Swap(super::Swap),
+			#[prost(message, tag = "5")]
+			AddLiquidity(super::AddLiquidity),
+			#[prost(message, tag = "6")]
+			RemoveLiquidity(super::RemoveLiquidity),
+			#[prost(message, tag = "7")]
+			Jump(super::Jump),
+			#[prost(message, tag = "8")]
+			Branch(super::If),
+		}
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Transfer {
+		#[prost(bytes = "vec", tag = "1")]
+		pub account: Vec<u8>,
+		#[prost(message, optional, tag = "2")]
+		pub assets: Option<Funds>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Call {
+		#[prost(bytes = "vec", tag = "1")]
+		pub payload: Vec<u8>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Spawn {
+		#[prost(uint32, tag = "1")]
+		pub network: u32,
+		#[prost(bytes = "vec", tag = "2")]
+		pub salt: Vec<u8>,
+		#[prost(message, optional, tag = "3")]
+		pub assets: Option<Funds>,
+		#[prost(message, optional, tag = "4")]
+		pub program: Option<Program>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Swap {
+		#[prost(message, optional, tag = "1")]
+		pub give: Option<Funds>,
+		#[prost(message, optional, tag = "2")]
+		pub want: Option<Funds>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct AddLiquidity {
+		#[prost(bytes = "vec", tag = "1")]
+		pub pool: Vec<u8>,
+		#[prost(message, optional, tag = "2")]
+		pub assets: Option<Funds>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct RemoveLiquidity {
+		#[prost(bytes = "vec", tag = "1")]
+		pub pool: Vec<u8>,
+		#[prost(message, optional, tag = "2")]
+		pub shares: Option<Amount>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Jump {
+		#[prost(uint32, tag = "1")]
+		pub to: u32,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct If {
+		#[prost(message, optional, tag = "1")]
+		pub condition: Option<Condition>,
+		#[prost(uint32, tag = "2")]
+		pub otherwise: u32,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Funds {
+		#[prost(message, repeated, tag = "1")]
+		pub assets: Vec<Asset>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Asset {
+		#[prost(message, optional, tag = "1")]
+		pub asset_id: Option<Uint128>,
+		#[prost(message, optional, tag = "2")]
+		pub amount: Option<Amount>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Amount {
+		#[prost(message, optional, tag = "1")]
+		pub intercept: Option<Uint128>,
+		#[prost(message, optional, tag = "2")]
+		pub slope: Option<Uint128>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Condition {
+		#[prost(oneof = "condition::Condition", tags = "1, 2, 3, 4, 5")]
+		pub condition: Option<condition::Condition>,
+	}
+
+	pub mod condition {
+		use alloc::boxed::Box;
+
+		#[derive(Clone, PartialEq, prost::Oneof)]
+		pub enum Condition {
+			#[prost(message, tag = "1")]
+			AtLeast(super::Balance),
+			#[prost(message, tag = "2")]
+			AtMost(super::Balance),
+			#[prost(message, tag = "3")]
+			Not(Box<super::Condition>),
+			#[prost(message, tag = "4")]
+			All(super::Conditions),
+			#[prost(message, tag = "5")]
+			Any(super::Conditions),
+		}
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Balance {
+		#[prost(message, optional, tag = "1")]
+		pub asset_id: Option<Uint128>,
+		#[prost(message, optional, tag = "2")]
+		pub amount: Option<Uint128>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Conditions {
+		#[prost(message, repeated, tag = "1")]
+		pub conditions: Vec<Condition>,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Asset, Ethereum, Network, Picasso, ProgramBuilder, PICA, USDC};
+	use alloc::vec;
+	use proptest::{collection, prelude::*};
+
+	type Account = Vec<u8>;
+
+	#[test]
+	fn round_trip_builder_program() {
+		let program = ProgramBuilder::<Picasso, Account, Funds>::new(b"main".to_vec())
+			.call_raw(vec![0xCA, 0xFE])
+			.spawn::<Ethereum, Ethereum, (), _>(
+				b"child".to_vec(),
+				b"salt".to_vec(),
+				Funds::from([(PICA::ID, Amount::ratio(u128::MAX))]),
+				|child| {
+					child
+						.swap(
+							Funds::from([(PICA::ID, Amount::ratio(u128::MAX))]),
+							Funds::from([(USDC::ID, Amount::absolute(u128::MAX - 1))]),
+						)
+						.if_(Condition::at_least(USDC, 1).not(), |then| {
+							Ok(then.transfer(vec![1, 2, 3], Funds::empty()).jump(0))
+						})
+				},
+			)
+			.expect("valid program")
+			.build();
+
+		assert_eq!(decode::<Account>(&encode(&program)), Ok(program));
+	}
+
+	#[test]
+	fn reject_invalid_bytes() {
+		let spawn = |network| wire::Program {
+			tag: Vec::new(),
+			instructions: vec![wire::Instruction {
+				instruction: Some(wire::instruction::Instruction::Spawn(wire::Spawn {
+					network,
+					..Default::default()
+				})),
+			}],
+		};
+		assert!(decode::<Account>(&spawn(u32::from(Ethereum::ID.0)).encode_to_vec()).is_ok());
+		assert_eq!(
+			decode::<Account>(&spawn(256).encode_to_vec()),
+			Err(DecodeError::InvalidNetwork(256))
+		);
+		let empty = wire::Program { tag: Vec::new(), instructions: vec![Default::default()] };
+		assert_eq!(
+			decode::<Account>(&empty.encode_to_vec()),
+			Err(DecodeError::MissingField("Instruction.instruction"))
+		);
+		assert_eq!(decode::<Account>(&[0xFF, 0xFF]), Err(DecodeError::Protobuf));
+	}
+
+	fn amount() -> impl Strategy<Value = Amount> {
+		(any::<u128>(), any::<u128>())
+			.prop_map(|(intercept, slope)| Amount { intercept: Displayed(intercept), slope })
+	}
+
+	fn funds() -> impl Strategy<Value = Funds> {
+		collection::btree_map(any::<u128>().prop_map(AssetId), amount(), 0..4).prop_map(Funds)
+	}
+
+	fn condition() -> impl Strategy<Value = Condition> {
+		let leaf = prop_oneof![
+			(any::<u128>(), any::<u128>()).prop_map(|(asset, amount)| Condition::AtLeast {
+				asset: AssetId(asset),
+				amount: Displayed(amount)
+			}),
+			(any::<u128>(), any::<u128>()).prop_map(|(asset, amount)| Condition::AtMost {
+				asset: AssetId(asset),
+				amount: Displayed(amount)
+			}),
+		];
+		leaf.prop_recursive(3, 16, 4, |inner| {
+			prop_oneof![
+				inner.clone().prop_map(Condition::not),
+				collection::vec(inner.clone(), 0..4).prop_map(Condition::All),
+				collection::vec(inner, 0..4).prop_map(Condition::Any),
+			]
+		})
+	}
+
+	fn bytes() -> impl Strategy<Value = Vec<u8>> {
+		collection::vec(any::<u8>(), 0..16)
+	}
+
+	fn program() -> impl Strategy<Value = WireProgram<Account>> {
+		let leaf = prop_oneof![
+			(bytes(), funds()).prop_map(|(to, assets)| Instruction::Transfer { to, assets }),
+			bytes().prop_map(|encoded| Instruction::Call { encoded }),
+			(funds(), funds()).prop_map(|(give, want)| Instruction::Swap { give, want }),
+			(bytes(), funds())
+				.prop_map(|(pool, assets)| Instruction::AddLiquidity { pool, assets }),
+			(bytes(), amount())
+				.prop_map(|(pool, shares)| Instruction::RemoveLiquidity { pool, shares }),
+			any::<u32>().prop_map(|to| Instruction::Jump { to }),
+			(condition(), any::<u32>())
+				.prop_map(|(condition, otherwise)| Instruction::If { condition, otherwise }),
+		];
+		let instructions = leaf.prop_recursive(3, 32, 4, |inner| {
+			(any::<u8>(), bytes(), funds(), bytes(), collection::vec_deque(inner, 0..4)).prop_map(
+				|(network, salt, assets, tag, instructions)| Instruction::Spawn {
+					network: NetworkId(network),
+					salt,
+					assets,
+					program: Program { tag, instructions },
+				},
+			)
+		});
+		(bytes(), collection::vec_deque(instructions, 0..8))
+			.prop_map(|(tag, instructions)| Program { tag, instructions })
+	}
+
+	proptest! {
+		#[test]
+		fn round_trip(program in program()) {
+			prop_assert_eq!(decode::<Account>(&encode(&program)), Ok(program));
+		}
+	}
+}
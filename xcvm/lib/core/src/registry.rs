@@ -0,0 +1,66 @@
+use crate::{AssetId, NetworkId};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+
+/// Security level of the bridge used to reach a network.
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[derive(
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Debug,
+	Encode,
+	Decode,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeSecurity {
+	/// Light client based, with a consensus protocol having deterministic finality.
+	Deterministic,
+	/// Light client based, with a consensus protocol having at most probabilistic finality.
+	Probabilistic,
+	/// Proof-less, relying on a dispute mechanism.
+	Optimistic,
+}
+
+/// Local view of an XCVM network.
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct NetworkInfo<LocalNetworkId> {
+	/// How the network is identified by the local bridge, i.e. a parachain id or an IBC channel.
+	pub local: LocalNetworkId,
+	pub security: BridgeSecurity,
+}
+
+/// Local view of an XCVM asset.
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AssetInfo<LocalAssetId> {
+	pub local: LocalAssetId,
+	/// Decimals of the local representation of the asset.
+	pub decimals: u32,
+}
+
+/// Runtime mapping of the XCVM identifiers to their local counterparts.
+///
+/// The statically known [`crate::Networks`] and [`crate::Assets`] are expected to be registered
+/// under their compile time identifiers, new ones being added without a protocol release.
+pub trait Registry {
+	type LocalNetworkId;
+	type LocalAssetId;
+
+	fn network(network: NetworkId) -> Option<NetworkInfo<Self::LocalNetworkId>>;
+
+	fn asset(asset: AssetId) -> Option<AssetInfo<Self::LocalAssetId>>;
+
+	/// Reverse lookup of [`Registry::asset`].
+	fn from_local_asset(local: Self::LocalAssetId) -> Option<AssetId>;
+}
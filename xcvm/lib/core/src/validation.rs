@@ -0,0 +1,551 @@
+//! Static checks of a program before it gets signed and submitted.
+//!
+//! The validation only relies on the program itself, balances being unknown until execution. The
+//! holdings of a program are approximated by an upper bound per asset, which is exact for the
+//! assets given to a spawned program until it executes an instruction crediting unknown amounts
+//! (`Call`, `Swap`, `RemoveLiquidity`). The assets taken by `Spawn` and `Transfer` are deducted
+//! from the bound until the first `Jump` or `If`, after which instructions may be skipped.
+
+use crate::{Amount, AssetId, Condition, Funds, Instruction, NetworkId, Program};
+use alloc::{
+	collections::{BTreeMap, VecDeque},
+	vec::Vec,
+};
+
+type ValidatedProgram<Payload, Account> =
+	Program<VecDeque<Instruction<NetworkId, Payload, Account, Funds>>>;
+
+/// Position of an instruction in the program tree: the indexes of the enclosing `Spawn`
+/// instructions, from the root program, followed by the index of the instruction itself.
+pub type InstructionPath = Vec<u32>;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ValidationError {
+	/// A `Spawn` targets the reserved [`NetworkId::INVALID`].
+	InvalidNetwork {
+		at: InstructionPath,
+	},
+	/// An instruction refers to the reserved [`AssetId::INVALID`].
+	InvalidAsset {
+		at: InstructionPath,
+	},
+	SpawnDepthExceeded {
+		at: InstructionPath,
+		limit: u32,
+	},
+	/// The whole program tree contains more than `limit` instructions.
+	TooManyInstructions {
+		limit: u32,
+	},
+	/// The `Amount` can resolve to more than `u128::MAX`.
+	AmountOverflow {
+		at: InstructionPath,
+		asset: AssetId,
+	},
+	/// A `Jump` or `If` targets an index past the end of its program.
+	InvalidJump {
+		at: InstructionPath,
+		to: u32,
+	},
+	/// A `Spawn` or `Transfer` requires more of `asset` than the program could hold at that point.
+	InsufficientFunds {
+		at: InstructionPath,
+		asset: AssetId,
+		amount: u128,
+		available: u128,
+	},
+}
+
+/// Bounds enforced on a program tree.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Limits {
+	/// Maximum nesting of `Spawn` instructions, the root program being at depth 0.
+	pub max_spawn_depth: u32,
+	/// Maximum number of instructions, summed over the whole program tree.
+	pub max_instructions: u32,
+}
+
+impl Limits {
+	pub const DEFAULT_MAX_SPAWN_DEPTH: u32 = 8;
+	pub const DEFAULT_MAX_INSTRUCTIONS: u32 = 256;
+}
+
+impl Default for Limits {
+	#[inline]
+	fn default() -> Self {
+		Limits {
+			max_spawn_depth: Self::DEFAULT_MAX_SPAWN_DEPTH,
+			max_instructions: Self::DEFAULT_MAX_INSTRUCTIONS,
+		}
+	}
+}
+
+/// Validate a program whose initial holdings are unknown.
+pub fn validate<Payload, Account>(
+	program: &ValidatedProgram<Payload, Account>,
+	limits: &Limits,
+) -> Result<(), ValidationError> {
+	Validator::new(limits).program(program, Holdings::unbounded())
+}
+
+/// Validate a program that is going to be executed with the given `holdings`.
+pub fn validate_with_holdings<Payload, Account>(
+	program: &ValidatedProgram<Payload, Account>,
+	limits: &Limits,
+	holdings: &Funds<u128>,
+) -> Result<(), ValidationError> {
+	Validator::new(limits).program(program, Holdings::bounded(holdings.0.clone()))
+}
+
+/// Upper bound of the amount of each asset a program can hold.
+struct Holdings {
+	bounds: BTreeMap<AssetId, u128>,
+	/// Whether assets missing from `bounds` may be held in any amount.
+	unbounded: bool,
+	/// Whether all of the instructions so far are executed exactly once, so that the assets they
+	/// take can be deducted from the bounds.
+	linear: bool,
+}
+
+impl Holdings {
+	fn unbounded() -> Self {
+		Holdings { bounds: BTreeMap::new(), unbounded: true, linear: true }
+	}
+
+	fn bounded(bounds: BTreeMap<AssetId, u128>) -> Self {
+		Holdings { bounds, unbounded: false, linear: true }
+	}
+
+	fn get(&self, asset: AssetId) -> u128 {
+		match self.bounds.get(&asset) {
+			Some(bound) => *bound,
+			None if self.unbounded => u128::MAX,
+			None => 0,
+		}
+	}
+
+	/// Takes `amount` of `asset`, returning the upper bound of the amount taken, or `None` if it
+	/// requires more than the holdings can contain.
+	fn take(&mut self, asset: AssetId, amount: &Amount) -> Option<u128> {
+		let available = self.get(asset);
+		if amount.intercept.0 > available {
+			return None
+		}
+		let taken = amount.apply(available);
+		if self.linear {
+			self.bounds.insert(asset, available.saturating_sub(taken));
+		}
+		Some(taken)
+	}
+}
+
+struct Validator<'a> {
+	limits: &'a Limits,
+	path: InstructionPath,
+	instructions: u32,
+}
+
+impl<'a> Validator<'a> {
+	fn new(limits: &'a Limits) -> Self {
+		Validator { limits, path: Vec::new(), instructions: 0 }
+	}
+
+	fn program<Payload, Account>(
+		&mut self,
+		program: &ValidatedProgram<Payload, Account>,
+		mut holdings: Holdings,
+	) -> Result<(), ValidationError> {
+		let length = u32::try_from(program.instructions.len()).unwrap_or(u32::MAX);
+		self.instructions = self.instructions.saturating_add(length);
+		if self.instructions > self.limits.max_instructions {
+			return Err(ValidationError::TooManyInstructions { limit: self.limits.max_instructions })
+		}
+		for (index, instruction) in (0_u32..).zip(program.instructions.iter()) {
+			self.path.push(index);
+			self.instruction(instruction, length, &mut holdings)?;
+			self.path.pop();
+		}
+		Ok(())
+	}
+
+	fn instruction<Payload, Account>(
+		&mut self,
+		instruction: &Instruction<NetworkId, Payload, Account, Funds>,
+		length: u32,
+		holdings: &mut Holdings,
+	) -> Result<(), ValidationError> {
+		match instruction {
+			Instruction::Transfer { assets, .. } => {
+				self.funds(assets)?;
+				self.take(assets, holdings).map(drop)
+			},
+			Instruction::Call { .. } => {
+				holdings.bounds.clear();
+				holdings.unbounded = true;
+				Ok(())
+			},
+			Instruction::Spawn { network, assets, program, .. } => {
+				if *network == NetworkId::INVALID {
+					return Err(ValidationError::InvalidNetwork { at: self.path.clone() })
+				}
+				let depth = u32::try_from(self.path.len()).unwrap_or(u32::MAX);
+				if depth > self.limits.max_spawn_depth {
+					return Err(ValidationError::SpawnDepthExceeded {
+						at: self.path.clone(),
+						limit: self.limits.max_spawn_depth,
+					})
+				}
+				self.funds(assets)?;
+				let spawned = self.take(assets, holdings)?;
+				self.program(program, Holdings::bounded(spawned))
+			},
+			Instruction::Swap { give, want } => {
+				self.funds(give)?;
+				self.funds(want)?;
+				for asset in want.0.keys() {
+					holdings.bounds.insert(*asset, u128::MAX);
+				}
+				Ok(())
+			},
+			Instruction::AddLiquidity { assets, .. } => self.funds(assets),
+			Instruction::RemoveLiquidity { .. } => {
+				holdings.bounds.clear();
+				holdings.unbounded = true;
+				Ok(())
+			},
+			Instruction::Jump { to } => {
+				holdings.linear = false;
+				self.jump(*to, length)
+			},
+			Instruction::If { condition, otherwise } => {
+				holdings.linear = false;
+				self.condition(condition)?;
+				self.jump(*otherwise, length)
+			},
+		}
+	}
+
+	fn funds(&self, funds: &Funds) -> Result<(), ValidationError> {
+		for (asset, amount) in &funds.0 {
+			self.asset(*asset)?;
+			if !amount_fits(amount) {
+				return Err(ValidationError::AmountOverflow { at: self.path.clone(), asset: *asset })
+			}
+		}
+		Ok(())
+	}
+
+	/// Takes `assets` from the `holdings`, returning the upper bound of the amount taken of each
+	/// asset.
+	fn take(
+		&self,
+		assets: &Funds,
+		holdings: &mut Holdings,
+	) -> Result<BTreeMap<AssetId, u128>, ValidationError> {
+		let mut taken = BTreeMap::new();
+		for (asset, amount) in &assets.0 {
+			let available = holdings.get(*asset);
+			let amount_taken = holdings.take(*asset, amount).ok_or_else(|| {
+				ValidationError::InsufficientFunds {
+					at: self.path.clone(),
+					asset: *asset,
+					amount: amount.intercept.0,
+					available,
+				}
+			})?;
+			taken.insert(*asset, amount_taken);
+		}
+		Ok(taken)
+	}
+
+	fn condition(&self, condition: &Condition) -> Result<(), ValidationError> {
+		match condition {
+			Condition::AtLeast { asset, .. } | Condition::AtMost { asset, .. } =>
+				self.asset(*asset),
+			Condition::Not(condition) => self.condition(condition),
+			Condition::All(conditions) | Condition::Any(conditions) =>
+				conditions.iter().try_for_each(|condition| self.condition(condition)),
+		}
+	}
+
+	fn asset(&self, asset: AssetId) -> Result<(), ValidationError> {
+		if asset == AssetId::INVALID {
+			Err(ValidationError::InvalidAsset { at: self.path.clone() })
+		} else {
+			Ok(())
+		}
+	}
+
+	fn jump(&self, to: u32, length: u32) -> Result<(), ValidationError> {
+		if to > length {
+			Err(ValidationError::InvalidJump { at: self.path.clone(), to })
+		} else {
+			Ok(())
+		}
+	}
+}
+
+/// Whether `amount` resolves without saturating, whatever the value it is applied to.
+/// The ratio part is at most `slope`, reached when applied to `u128::MAX`.
+fn amount_fits(amount: &Amount) -> bool {
+	amount.intercept.0.checked_add(amount.slope).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Asset, Displayed, Ethereum, Juno, Picasso, ProgramBuilder, ETH, PICA, USDC};
+	use alloc::vec;
+
+	type Builder<N> = ProgramBuilder<N, Vec<u8>, Funds>;
+
+	#[test]
+	fn valid_program() {
+		let program = Builder::<Picasso>::new("root".into())
+			.transfer(vec![1], Funds::from([(PICA::ID, Amount::ratio(u128::MAX / 2))]))
+			.spawn::<Ethereum, _, (), _>(
+				"child".into(),
+				vec![],
+				Funds::from([(PICA::ID, 100_u128)]),
+				|child| {
+					child.spawn::<Juno, _, (), _>(
+						"grandchild".into(),
+						vec![],
+						Funds::from([(PICA::ID, 100_u128)]),
+						Ok,
+					)
+				},
+			)
+			.expect("valid program")
+			.build();
+		assert_eq!(validate(&program, &Limits::default()), Ok(()));
+	}
+
+	#[test]
+	fn invalid_identifiers() {
+		let program = Builder::<Picasso>::new("root".into())
+			.transfer(vec![1], Funds::from([(AssetId::INVALID, 1_u128)]))
+			.build();
+		assert_eq!(
+			validate(&program, &Limits::default()),
+			Err(ValidationError::InvalidAsset { at: vec![0] })
+		);
+
+		let mut program = Builder::<Picasso>::new("root".into())
+			.spawn::<Ethereum, _, (), _>("child".into(), vec![], Funds::empty(), Ok)
+			.expect("valid program")
+			.build();
+		if let Some(Instruction::Spawn { network, .. }) = program.instructions.get_mut(0) {
+			*network = NetworkId::INVALID;
+		}
+		assert_eq!(
+			validate(&program, &Limits::default()),
+			Err(ValidationError::InvalidNetwork { at: vec![0] })
+		);
+
+		let program = Builder::<Picasso>::new("root".into())
+			.if_::<(), _>(Condition::at_least(AssetId::INVALID, 1).not(), Ok)
+			.expect("valid program")
+			.build();
+		assert_eq!(
+			validate(&program, &Limits::default()),
+			Err(ValidationError::InvalidAsset { at: vec![0] })
+		);
+	}
+
+	#[test]
+	fn limits() {
+		let program = Builder::<Picasso>::new("root".into())
+			.spawn::<Ethereum, _, (), _>("child".into(), vec![], Funds::empty(), |child| {
+				child.spawn::<Picasso, _, (), _>("grandchild".into(), vec![], Funds::empty(), Ok)
+			})
+			.expect("valid program")
+			.build();
+		let limits = Limits { max_spawn_depth: 1, max_instructions: 2 };
+		assert_eq!(
+			validate(&program, &limits),
+			Err(ValidationError::SpawnDepthExceeded { at: vec![0, 0], limit: 1 })
+		);
+
+		let program = Builder::<Picasso>::new("root".into())
+			.call_raw(vec![])
+			.spawn::<Ethereum, _, (), _>("child".into(), vec![], Funds::empty(), |child| {
+				Ok(child.call_raw(vec![]))
+			})
+			.expect("valid program")
+			.build();
+		assert_eq!(
+			validate(&program, &limits),
+			Err(ValidationError::TooManyInstructions { limit: 2 })
+		);
+	}
+
+	#[test]
+	fn amount_overflow() {
+		let amount = Amount { intercept: Displayed(u128::MAX), slope: 1 };
+		let program = Builder::<Picasso>::new("root".into())
+			.swap(Funds::from([(PICA::ID, amount)]), Funds::from([(USDC::ID, 1_u128)]))
+			.build();
+		assert_eq!(
+			validate(&program, &Limits::default()),
+			Err(ValidationError::AmountOverflow { at: vec![0], asset: PICA::ID })
+		);
+	}
+
+	#[test]
+	fn invalid_jump() {
+		let program = Builder::<Picasso>::new("root".into()).jump(1).jump(3).build();
+		assert_eq!(
+			validate(&program, &Limits::default()),
+			Err(ValidationError::InvalidJump { at: vec![1], to: 3 })
+		);
+	}
+
+	#[test]
+	fn spawn_funds_bounded_by_parent() {
+		let holdings = Funds(BTreeMap::from([(PICA::ID, 100)]));
+		let spawn = |amount: u128| {
+			Builder::<Picasso>::new("root".into())
+				.spawn::<Ethereum, _, (), _>(
+					"child".into(),
+					vec![],
+					Funds::from([(PICA::ID, amount)]),
+					Ok,
+				)
+				.expect("valid program")
+				.build()
+		};
+		assert_eq!(validate_with_holdings(&spawn(100), &Limits::default(), &holdings), Ok(()));
+		assert_eq!(
+			validate_with_holdings(&spawn(101), &Limits::default(), &holdings),
+			Err(ValidationError::InsufficientFunds {
+				at: vec![0],
+				asset: PICA::ID,
+				amount: 101,
+				available: 100
+			})
+		);
+
+		// The child only holds half of the parent's PICA and no ETH.
+		let program = Builder::<Picasso>::new("root".into())
+			.spawn::<Ethereum, _, (), _>(
+				"child".into(),
+				vec![],
+				Funds::from([(PICA::ID, Amount::ratio(u128::MAX / 2))]),
+				|child| {
+					child.spawn::<Juno, _, (), _>(
+						"grandchild".into(),
+						vec![],
+						Funds::from([(PICA::ID, 40_u128), (ETH::ID, 1_u128)]),
+						Ok,
+					)
+				},
+			)
+			.expect("valid program")
+			.build();
+		assert_eq!(
+			validate_with_holdings(&program, &Limits::default(), &holdings),
+			Err(ValidationError::InsufficientFunds {
+				at: vec![0, 0],
+				asset: ETH::ID,
+				amount: 1,
+				available: 0
+			})
+		);
+
+		// Swapping for ETH lifts the bound.
+		let program = Builder::<Picasso>::new("root".into())
+			.spawn::<Ethereum, _, (), _>(
+				"child".into(),
+				vec![],
+				Funds::from([(PICA::ID, 100_u128)]),
+				|child| {
+					child
+						.swap(
+							Funds::from([(PICA::ID, 100_u128)]),
+							Funds::from([(ETH::ID, Amount::ratio(0))]),
+						)
+						.spawn::<Juno, _, (), _>(
+							"grandchild".into(),
+							vec![],
+							Funds::from([(ETH::ID, 1_u128)]),
+							Ok,
+						)
+				},
+			)
+			.expect("valid program")
+			.build();
+		assert_eq!(validate_with_holdings(&program, &Limits::default(), &holdings), Ok(()));
+	}
+
+	#[test]
+	fn spawns_and_transfers_take_from_the_holdings() {
+		let holdings = Funds(BTreeMap::from([(PICA::ID, 100)]));
+		let spawn_twice = Builder::<Picasso>::new("root".into())
+			.spawn::<Ethereum, _, (), _>(
+				"first".into(),
+				vec![],
+				Funds::from([(PICA::ID, 60_u128)]),
+				Ok,
+			)
+			.and_then(|builder| {
+				builder.spawn::<Juno, _, (), _>(
+					"second".into(),
+					vec![],
+					Funds::from([(PICA::ID, 60_u128)]),
+					Ok,
+				)
+			})
+			.expect("valid program")
+			.build();
+		assert_eq!(
+			validate_with_holdings(&spawn_twice, &Limits::default(), &holdings),
+			Err(ValidationError::InsufficientFunds {
+				at: vec![1],
+				asset: PICA::ID,
+				amount: 60,
+				available: 40
+			})
+		);
+
+		let transfer_then_spawn = Builder::<Picasso>::new("root".into())
+			.transfer(vec![1], Funds::from([(PICA::ID, Amount::ratio(u128::MAX / 2 + 1))]))
+			.spawn::<Ethereum, _, (), _>(
+				"child".into(),
+				vec![],
+				Funds::from([(PICA::ID, 51_u128)]),
+				Ok,
+			)
+			.expect("valid program")
+			.build();
+		assert_eq!(
+			validate_with_holdings(&transfer_then_spawn, &Limits::default(), &holdings),
+			Err(ValidationError::InsufficientFunds {
+				at: vec![1],
+				asset: PICA::ID,
+				amount: 51,
+				available: 50
+			})
+		);
+
+		// The transfer may be skipped, the spawn may have all of the PICA.
+		let conditional_transfer = Builder::<Picasso>::new("root".into())
+			.if_::<(), _>(Condition::at_least(PICA::ID, 1_000), |builder| {
+				Ok(builder.transfer(vec![1], Funds::from([(PICA::ID, 100_u128)])))
+			})
+			.and_then(|builder| {
+				builder.spawn::<Ethereum, _, (), _>(
+					"child".into(),
+					vec![],
+					Funds::from([(PICA::ID, 100_u128)]),
+					Ok,
+				)
+			})
+			.expect("valid program")
+			.build();
+		assert_eq!(
+			validate_with_holdings(&conditional_transfer, &Limits::default(), &holdings),
+			Ok(())
+		);
+	}
+}
@@ -0,0 +1,52 @@
+use crate::XCVMProgram;
+use xcvm_core::{AssetId, Funds, NetworkId};
+
+/// Network specific capabilities required by the interpreter to execute a program.
+///
+/// All the operations are executed on behalf of the interpreter instance, i.e. balances are the
+/// ones held by the instance and transfers are debited from it.
+pub trait Environment {
+	type Account;
+	type Error;
+
+	/// Amount of `asset` currently held by the interpreter instance.
+	fn balance(&self, asset: AssetId) -> Result<u128, Self::Error>;
+
+	/// Move `amount` of `asset` from the interpreter instance to the `to` account.
+	fn transfer(
+		&mut self,
+		to: &Self::Account,
+		asset: AssetId,
+		amount: u128,
+	) -> Result<(), Self::Error>;
+
+	/// Dispatch a raw call, encoded as expected by the current network.
+	fn call(&mut self, encoded: &[u8]) -> Result<(), Self::Error>;
+
+	/// Queue the sub-`program` for execution on the target `network`.
+	///
+	/// The `assets` are already resolved and must be debited from the interpreter instance and
+	/// escrowed along the program until it is picked up by the bridge.
+	fn spawn(
+		&mut self,
+		network: NetworkId,
+		salt: &[u8],
+		assets: &Funds<u128>,
+		program: &XCVMProgram<Self::Account>,
+	) -> Result<(), Self::Error>;
+
+	/// Exchange the `give` assets, returning the received assets.
+	///
+	/// The `want` amounts are the minimum to receive, to be resolved with
+	/// [`xcvm_core::Amount::apply_unbounded`] against the quoted output of each asset.
+	fn swap(&mut self, give: &Funds<u128>, want: &Funds) -> Result<Funds<u128>, Self::Error>;
+
+	/// Shares of the `pool` held by the interpreter instance.
+	fn liquidity(&self, pool: &[u8]) -> Result<u128, Self::Error>;
+
+	/// Provide the `assets` to the `pool`, returning the amount of shares minted.
+	fn add_liquidity(&mut self, pool: &[u8], assets: &Funds<u128>) -> Result<u128, Self::Error>;
+
+	/// Redeem `shares` of the `pool`, returning the withdrawn assets.
+	fn remove_liquidity(&mut self, pool: &[u8], shares: u128) -> Result<Funds<u128>, Self::Error>;
+}
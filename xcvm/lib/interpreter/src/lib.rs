@@ -0,0 +1,494 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod environment;
+mod memory;
+mod trace;
+
+pub use crate::{environment::*, memory::*, trace::*};
+use alloc::{collections::VecDeque, vec::Vec};
+use xcvm_core::{Funds, Instruction, NetworkId, Program};
+
+pub type XCVMInstruction<Account> = Instruction<NetworkId, Vec<u8>, Account, Funds>;
+pub type XCVMProgram<Account> = Program<VecDeque<XCVMInstruction<Account>>>;
+
+/// Maximum number of instructions executed by a program unless specified otherwise, bounding the
+/// execution of programs looping through jumps.
+pub const DEFAULT_STEP_LIMIT: u32 = 1024;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InterpreterError<E> {
+	Environment(E),
+	/// Jump outside of the program, the length of the program being a valid target.
+	InvalidJump {
+		to: u32,
+	},
+	StepLimitExceeded,
+}
+
+impl<E> From<E> for InterpreterError<E> {
+	fn from(error: E) -> Self {
+		InterpreterError::Environment(error)
+	}
+}
+
+/// Failure of a program execution.
+/// The trace contains the effects of the instructions executed before the failing one.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ExecutionError<Account, Error> {
+	pub ip: u32,
+	pub error: InterpreterError<Error>,
+	pub trace: Trace<Account>,
+}
+
+/// Reference XCVM interpreter, executing programs against an [`Environment`].
+pub struct Interpreter<E> {
+	env: E,
+	step_limit: u32,
+}
+
+impl<E> Interpreter<E>
+where
+	E: Environment,
+	E::Account: Clone,
+{
+	#[inline]
+	pub fn new(env: E) -> Self {
+		Self::with_step_limit(env, DEFAULT_STEP_LIMIT)
+	}
+
+	#[inline]
+	pub fn with_step_limit(env: E, step_limit: u32) -> Self {
+		Interpreter { env, step_limit }
+	}
+
+	#[inline]
+	pub fn environment(&self) -> &E {
+		&self.env
+	}
+
+	#[inline]
+	pub fn into_environment(self) -> E {
+		self.env
+	}
+
+	/// Execute the instructions of the program, stopping at the first failure.
+	pub fn execute(
+		&mut self,
+		Program { tag, instructions }: XCVMProgram<E::Account>,
+	) -> Result<Trace<E::Account>, ExecutionError<E::Account, E::Error>> {
+		let instructions = Vec::from(instructions);
+		let mut trace = Trace::new(tag);
+		let mut ip = 0_u32;
+		let mut steps = 0_u32;
+		while let Some(instruction) = instructions.get(ip as usize) {
+			let result = if steps < self.step_limit {
+				steps += 1;
+				self.step(instruction, instructions.len())
+			} else {
+				Err(InterpreterError::StepLimitExceeded)
+			};
+			match result {
+				Ok((event, next)) => {
+					trace.entries.push(TraceEntry { ip, event });
+					ip = next.unwrap_or(ip + 1);
+				},
+				Err(error) => return Err(ExecutionError { ip, error, trace }),
+			}
+		}
+		Ok(trace)
+	}
+
+	/// Execute a single instruction, returning the index of the next one if it is not the
+	/// following.
+	fn step(
+		&mut self,
+		instruction: &XCVMInstruction<E::Account>,
+		length: usize,
+	) -> Result<(TraceEvent<E::Account>, Option<u32>), InterpreterError<E::Error>> {
+		let ensure_jump = |to: u32| {
+			if to as usize <= length {
+				Ok(Some(to))
+			} else {
+				Err(InterpreterError::InvalidJump { to })
+			}
+		};
+		match instruction {
+			Instruction::Transfer { to, assets } => {
+				let assets = self.resolve(assets)?;
+				for (asset, amount) in &assets.0 {
+					self.env.transfer(to, *asset, *amount)?;
+				}
+				Ok((TraceEvent::Transferred { to: to.clone(), assets }, None))
+			},
+			Instruction::Call { encoded } => {
+				self.env.call(encoded)?;
+				Ok((TraceEvent::Called { encoded: encoded.clone() }, None))
+			},
+			Instruction::Spawn { network, salt, assets, program } => {
+				let assets = self.resolve(assets)?;
+				self.env.spawn(*network, salt, &assets, program)?;
+				Ok((TraceEvent::Spawned { network: *network, salt: salt.clone(), assets }, None))
+			},
+			Instruction::Swap { give, want } => {
+				let given = self.resolve(give)?;
+				let received = self.env.swap(&given, want)?;
+				Ok((TraceEvent::Swapped { given, received }, None))
+			},
+			Instruction::AddLiquidity { pool, assets } => {
+				let assets = self.resolve(assets)?;
+				let shares = self.env.add_liquidity(pool, &assets)?;
+				Ok((TraceEvent::LiquidityAdded { pool: pool.clone(), assets, shares }, None))
+			},
+			Instruction::RemoveLiquidity { pool, shares } => {
+				let shares = shares.apply(self.env.liquidity(pool)?);
+				let assets = self.env.remove_liquidity(pool, shares)?;
+				Ok((TraceEvent::LiquidityRemoved { pool: pool.clone(), shares, assets }, None))
+			},
+			Instruction::Jump { to } => Ok((TraceEvent::Jumped { to: *to }, ensure_jump(*to)?)),
+			Instruction::If { condition, otherwise } => {
+				let holds = condition.evaluate(&|asset| self.env.balance(asset))?;
+				let next = if holds { None } else { ensure_jump(*otherwise)? };
+				Ok((TraceEvent::Evaluated { holds }, next))
+			},
+		}
+	}
+
+	/// Resolve the amounts against the current holdings of the interpreter instance.
+	fn resolve(&self, Funds(assets): &Funds) -> Result<Funds<u128>, E::Error> {
+		assets
+			.iter()
+			.map(|(asset, amount)| Ok((*asset, amount.apply(self.env.balance(*asset)?))))
+			.collect::<Result<_, _>>()
+			.map(Funds)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::{collections::BTreeMap, vec};
+	use xcvm_core::{
+		Amount, Asset, AssetId, Condition, Ethereum, Network, Picasso, ProgramBuilder, ETH, PICA,
+		USDC,
+	};
+
+	type Account = u32;
+	const ALICE: Account = 1;
+	const BOB: Account = 2;
+
+	#[test]
+	fn transfer_resolves_amounts_against_holdings() {
+		let program = ProgramBuilder::<Picasso, Account, Funds>::new(b"transfer".to_vec())
+			.transfer(ALICE, Funds::from([(PICA::ID, Amount::absolute(100))]))
+			.transfer(BOB, Funds::from([(PICA::ID, Amount::ratio(u128::MAX / 2 + 1))]))
+			.build();
+		let mut interpreter =
+			Interpreter::new(InMemoryEnvironment::new([(PICA::ID, 1100), (USDC::ID, 10)]));
+
+		let trace = interpreter.execute(program).expect("program must succeed");
+
+		assert_eq!(
+			trace,
+			Trace {
+				tag: b"transfer".to_vec(),
+				entries: vec![
+					TraceEntry {
+						ip: 0,
+						event: TraceEvent::Transferred {
+							to: ALICE,
+							assets: Funds(BTreeMap::from([(PICA::ID, 100)]))
+						}
+					},
+					TraceEntry {
+						ip: 1,
+						event: TraceEvent::Transferred {
+							to: BOB,
+							assets: Funds(BTreeMap::from([(PICA::ID, 500)]))
+						}
+					},
+				]
+			}
+		);
+		let env = interpreter.into_environment();
+		assert_eq!(env.balance_of(&ALICE, PICA::ID), 100);
+		assert_eq!(env.balance_of(&BOB, PICA::ID), 500);
+		assert_eq!(env.balance(PICA::ID), Ok(500));
+		assert_eq!(env.balance(USDC::ID), Ok(10));
+	}
+
+	#[test]
+	fn spawn_escrows_funds_in_outbox() {
+		let program = ProgramBuilder::<Picasso, Account, Funds>::new(b"spawn".to_vec())
+			.call_raw(vec![0xCA, 0xFE])
+			.spawn::<Ethereum, Ethereum, (), _>(
+				b"child".to_vec(),
+				b"salt".to_vec(),
+				Funds::from([(ETH::ID, Amount::ratio(u128::MAX))]),
+				|child| {
+					Ok(child.transfer(ALICE, Funds::from([(ETH::ID, Amount::ratio(u128::MAX))])))
+				},
+			)
+			.expect("valid program")
+			.build();
+		let child = match program.instructions.back() {
+			Some(Instruction::Spawn { program, .. }) => program.clone(),
+			_ => panic!("last instruction must be a spawn"),
+		};
+		let mut interpreter = Interpreter::new(InMemoryEnvironment::new([(ETH::ID, 42)]));
+
+		let trace = interpreter.execute(program).expect("program must succeed");
+
+		assert_eq!(
+			trace.entries,
+			vec![
+				TraceEntry { ip: 0, event: TraceEvent::Called { encoded: vec![0xCA, 0xFE] } },
+				TraceEntry {
+					ip: 1,
+					event: TraceEvent::Spawned {
+						network: Ethereum::ID,
+						salt: b"salt".to_vec(),
+						assets: Funds(BTreeMap::from([(ETH::ID, 42)]))
+					}
+				},
+			]
+		);
+		let env = interpreter.into_environment();
+		assert_eq!(env.calls, vec![vec![0xCA, 0xFE]]);
+		assert_eq!(env.balance(ETH::ID), Ok(0));
+		assert_eq!(
+			env.outbox,
+			vec![SpawnedProgram {
+				network: Ethereum::ID,
+				salt: b"salt".to_vec(),
+				assets: Funds(BTreeMap::from([(ETH::ID, 42)])),
+				program: child,
+			}]
+		);
+	}
+
+	/// Wraps the in-memory environment, rejecting a specific call.
+	struct RejectingEnvironment {
+		inner: InMemoryEnvironment<Account>,
+		rejected: Vec<u8>,
+	}
+
+	impl Environment for RejectingEnvironment {
+		type Account = Account;
+		type Error = Vec<u8>;
+
+		fn balance(&self, asset: AssetId) -> Result<u128, Self::Error> {
+			self.inner.balance(asset).map_err(|_| Vec::new())
+		}
+
+		fn transfer(
+			&mut self,
+			to: &Account,
+			asset: AssetId,
+			amount: u128,
+		) -> Result<(), Self::Error> {
+			self.inner.transfer(to, asset, amount).map_err(|_| Vec::new())
+		}
+
+		fn call(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+			if encoded == self.rejected.as_slice() {
+				return Err(encoded.to_vec());
+			}
+			self.inner.call(encoded).map_err(|_| Vec::new())
+		}
+
+		fn spawn(
+			&mut self,
+			network: NetworkId,
+			salt: &[u8],
+			assets: &Funds<u128>,
+			program: &XCVMProgram<Account>,
+		) -> Result<(), Self::Error> {
+			self.inner.spawn(network, salt, assets, program).map_err(|_| Vec::new())
+		}
+
+		fn swap(&mut self, give: &Funds<u128>, want: &Funds) -> Result<Funds<u128>, Self::Error> {
+			self.inner.swap(give, want).map_err(|_| Vec::new())
+		}
+
+		fn liquidity(&self, pool: &[u8]) -> Result<u128, Self::Error> {
+			self.inner.liquidity(pool).map_err(|_| Vec::new())
+		}
+
+		fn add_liquidity(
+			&mut self,
+			pool: &[u8],
+			assets: &Funds<u128>,
+		) -> Result<u128, Self::Error> {
+			self.inner.add_liquidity(pool, assets).map_err(|_| Vec::new())
+		}
+
+		fn remove_liquidity(
+			&mut self,
+			pool: &[u8],
+			shares: u128,
+		) -> Result<Funds<u128>, Self::Error> {
+			self.inner.remove_liquidity(pool, shares).map_err(|_| Vec::new())
+		}
+	}
+
+	#[test]
+	fn failure_reports_partial_trace() {
+		let program = ProgramBuilder::<Picasso, Account, Funds>::new(b"failure".to_vec())
+			.transfer(ALICE, Funds::from([(PICA::ID, Amount::absolute(10))]))
+			.call_raw(vec![0xDE, 0xAD])
+			.transfer(BOB, Funds::from([(PICA::ID, Amount::absolute(10))]))
+			.build();
+		let mut interpreter = Interpreter::new(RejectingEnvironment {
+			inner: InMemoryEnvironment::new([(PICA::ID, 100)]),
+			rejected: vec![0xDE, 0xAD],
+		});
+
+		assert_eq!(
+			interpreter.execute(program),
+			Err(ExecutionError {
+				ip: 1,
+				error: InterpreterError::Environment(vec![0xDE, 0xAD]),
+				trace: Trace {
+					tag: b"failure".to_vec(),
+					entries: vec![TraceEntry {
+						ip: 0,
+						event: TraceEvent::Transferred {
+							to: ALICE,
+							assets: Funds(BTreeMap::from([(PICA::ID, 10)]))
+						}
+					}]
+				}
+			})
+		);
+		let env = interpreter.into_environment().inner;
+		assert_eq!(env.balance_of(&ALICE, PICA::ID), 10);
+		assert_eq!(env.balance_of(&BOB, PICA::ID), 0);
+	}
+
+	#[test]
+	fn swap_and_provide_liquidity() {
+		let program = ProgramBuilder::<Picasso, Account, Funds>::new(b"liquidity".to_vec())
+			.swap(
+				Funds::from([(PICA::ID, Amount::absolute(100))]),
+				Funds::from([(USDC::ID, Amount::ratio(u128::MAX / 100 * 99))]),
+			)
+			.add_liquidity(
+				b"pool".to_vec(),
+				Funds::from([
+					(PICA::ID, Amount::absolute(50)),
+					(USDC::ID, Amount::ratio(u128::MAX)),
+				]),
+			)
+			.remove_liquidity(b"pool".to_vec(), Amount::ratio(u128::MAX / 2 + 1))
+			.build();
+		let mut interpreter = Interpreter::new(
+			InMemoryEnvironment::new([(PICA::ID, 1000)]).with_rate(PICA::ID, USDC::ID, 1, 2),
+		);
+
+		let trace = interpreter.execute(program).expect("program must succeed");
+
+		assert_eq!(
+			trace.entries.into_iter().map(|entry| entry.event).collect::<Vec<_>>(),
+			vec![
+				TraceEvent::Swapped {
+					given: Funds(BTreeMap::from([(PICA::ID, 100)])),
+					received: Funds(BTreeMap::from([(USDC::ID, 50)])),
+				},
+				TraceEvent::LiquidityAdded {
+					pool: b"pool".to_vec(),
+					assets: Funds(BTreeMap::from([(PICA::ID, 50), (USDC::ID, 50)])),
+					shares: 100,
+				},
+				TraceEvent::LiquidityRemoved {
+					pool: b"pool".to_vec(),
+					shares: 50,
+					assets: Funds(BTreeMap::from([(PICA::ID, 25), (USDC::ID, 25)])),
+				},
+			]
+		);
+		let env = interpreter.into_environment();
+		assert_eq!(env.balance(PICA::ID), Ok(875));
+		assert_eq!(env.balance(USDC::ID), Ok(25));
+		assert_eq!(env.liquidity(b"pool"), Ok(50));
+	}
+
+	#[test]
+	fn swap_below_minimum_fails() {
+		let program = ProgramBuilder::<Picasso, Account, Funds>::new(b"slippage".to_vec())
+			.swap(
+				Funds::from([(PICA::ID, Amount::absolute(100))]),
+				Funds::from([(USDC::ID, Amount::absolute(51))]),
+			)
+			.build();
+		let mut interpreter = Interpreter::new(
+			InMemoryEnvironment::new([(PICA::ID, 1000)]).with_rate(PICA::ID, USDC::ID, 1, 2),
+		);
+
+		assert_eq!(
+			interpreter.execute(program).map_err(|e| e.error),
+			Err(InterpreterError::Environment(InMemoryError::SlippageExceeded {
+				asset: USDC::ID,
+				minimum: 51,
+				received: 50
+			}))
+		);
+	}
+
+	#[test]
+	fn branch_on_received_amount() {
+		let program = |minimum| {
+			ProgramBuilder::<Picasso, Account, Funds>::new(b"branch".to_vec())
+				.swap(
+					Funds::from([(PICA::ID, Amount::ratio(u128::MAX))]),
+					Funds::from([(USDC::ID, Amount::absolute(0))]),
+				)
+				.if_::<(), _>(Condition::at_least(USDC, minimum), |then| {
+					Ok(then.transfer(ALICE, Funds::from([(USDC::ID, Amount::ratio(u128::MAX))])))
+				})
+				.expect("valid program")
+				.transfer(BOB, Funds::from([(USDC::ID, Amount::ratio(u128::MAX))]))
+				.build()
+		};
+		let env =
+			|| InMemoryEnvironment::new([(PICA::ID, 100)]).with_rate(PICA::ID, USDC::ID, 1, 1);
+
+		let mut interpreter = Interpreter::new(env());
+		let trace = interpreter.execute(program(100)).expect("program must succeed");
+		assert_eq!(trace.entries[1].event, TraceEvent::Evaluated { holds: true });
+		let env_after = interpreter.into_environment();
+		assert_eq!(env_after.balance_of(&ALICE, USDC::ID), 100);
+		assert_eq!(env_after.balance_of(&BOB, USDC::ID), 0);
+
+		let mut interpreter = Interpreter::new(env());
+		let trace = interpreter.execute(program(101)).expect("program must succeed");
+		assert_eq!(trace.entries.iter().map(|entry| entry.ip).collect::<Vec<_>>(), vec![0, 1, 3]);
+		let env_after = interpreter.into_environment();
+		assert_eq!(env_after.balance_of(&ALICE, USDC::ID), 0);
+		assert_eq!(env_after.balance_of(&BOB, USDC::ID), 100);
+	}
+
+	#[test]
+	fn jumps_are_bounded() {
+		let looping = ProgramBuilder::<Picasso, Account, Funds>::new(b"loop".to_vec())
+			.call_raw(vec![0x01])
+			.jump(0)
+			.build();
+		let mut interpreter = Interpreter::with_step_limit(InMemoryEnvironment::new([]), 10);
+		let error = interpreter.execute(looping).expect_err("program must not terminate");
+		assert_eq!(error.error, InterpreterError::StepLimitExceeded);
+		assert_eq!(error.trace.entries.len(), 10);
+		assert_eq!(interpreter.environment().calls.len(), 5);
+
+		let invalid = ProgramBuilder::<Picasso, Account, Funds>::new(b"invalid".to_vec())
+			.jump(2)
+			.build();
+		assert_eq!(
+			Interpreter::new(InMemoryEnvironment::new([]))
+				.execute(invalid)
+				.map_err(|e| e.error),
+			Err(InterpreterError::InvalidJump { to: 2 })
+		);
+	}
+}
@@ -0,0 +1,260 @@
+use crate::{Environment, XCVMProgram};
+use alloc::{collections::BTreeMap, vec::Vec};
+use xcvm_core::{AssetId, Funds, NetworkId};
+
+/// A program queued by [`Environment::spawn`], waiting to be bridged.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SpawnedProgram<Account> {
+	pub network: NetworkId,
+	pub salt: Vec<u8>,
+	pub assets: Funds<u128>,
+	pub program: XCVMProgram<Account>,
+}
+
+/// A liquidity pool of which the interpreter instance is the only provider.
+/// Shares are minted one for one against the provided amounts.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct InMemoryPool {
+	pub reserves: BTreeMap<AssetId, u128>,
+	pub shares: u128,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InMemoryError {
+	InsufficientBalance {
+		asset: AssetId,
+		balance: u128,
+		amount: u128,
+	},
+	BalanceOverflow {
+		asset: AssetId,
+	},
+	/// Only swaps of a single asset against a single asset are supported.
+	UnsupportedSwap,
+	NoRate {
+		from: AssetId,
+		to: AssetId,
+	},
+	SlippageExceeded {
+		asset: AssetId,
+		minimum: u128,
+		received: u128,
+	},
+	UnknownPool,
+	InsufficientShares {
+		shares: u128,
+		amount: u128,
+	},
+	ArithmeticOverflow,
+}
+
+/// In-memory [`Environment`], used to locally execute programs.
+///
+/// Calls are recorded but have no effect on the balances. Swaps are executed at fixed rates,
+/// against unlimited liquidity.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InMemoryEnvironment<Account> {
+	/// Funds held by the interpreter instance.
+	pub holdings: BTreeMap<AssetId, u128>,
+	/// Funds received by other accounts.
+	pub accounts: BTreeMap<Account, BTreeMap<AssetId, u128>>,
+	/// Calls dispatched, in order.
+	pub calls: Vec<Vec<u8>>,
+	/// Programs spawned, in order.
+	pub outbox: Vec<SpawnedProgram<Account>>,
+	/// Exchange rates, `(from, to) => (numerator, denominator)` being the amount of `to`
+	/// received for each unit of `from`.
+	pub rates: BTreeMap<(AssetId, AssetId), (u128, u128)>,
+	/// Liquidity pools, by identifier.
+	pub pools: BTreeMap<Vec<u8>, InMemoryPool>,
+}
+
+impl<Account> Default for InMemoryEnvironment<Account> {
+	fn default() -> Self {
+		InMemoryEnvironment {
+			holdings: BTreeMap::new(),
+			accounts: BTreeMap::new(),
+			calls: Vec::new(),
+			outbox: Vec::new(),
+			rates: BTreeMap::new(),
+			pools: BTreeMap::new(),
+		}
+	}
+}
+
+impl<Account: Ord> InMemoryEnvironment<Account> {
+	#[inline]
+	pub fn new(holdings: impl IntoIterator<Item = (AssetId, u128)>) -> Self {
+		InMemoryEnvironment { holdings: holdings.into_iter().collect(), ..Default::default() }
+	}
+
+	/// Amount of `asset` received by `account`.
+	#[inline]
+	pub fn balance_of(&self, account: &Account, asset: AssetId) -> u128 {
+		self.accounts
+			.get(account)
+			.and_then(|balances| balances.get(&asset))
+			.copied()
+			.unwrap_or_default()
+	}
+
+	#[inline]
+	pub fn with_rate(
+		mut self,
+		from: AssetId,
+		to: AssetId,
+		numerator: u128,
+		denominator: u128,
+	) -> Self {
+		self.rates.insert((from, to), (numerator, denominator));
+		self
+	}
+
+	fn ensure_holdings(&self, asset: AssetId, amount: u128) -> Result<(), InMemoryError> {
+		let balance = self.holdings.get(&asset).copied().unwrap_or_default();
+		if balance < amount {
+			return Err(InMemoryError::InsufficientBalance { asset, balance, amount });
+		}
+		Ok(())
+	}
+
+	fn withdraw(&mut self, asset: AssetId, amount: u128) -> Result<(), InMemoryError> {
+		self.ensure_holdings(asset, amount)?;
+		let balance = self.holdings.entry(asset).or_default();
+		*balance -= amount;
+		Ok(())
+	}
+
+	fn deposit(&mut self, asset: AssetId, amount: u128) -> Result<(), InMemoryError> {
+		let balance = self.holdings.entry(asset).or_default();
+		*balance = balance.checked_add(amount).ok_or(InMemoryError::BalanceOverflow { asset })?;
+		Ok(())
+	}
+}
+
+impl<Account: Ord + Clone> Environment for InMemoryEnvironment<Account> {
+	type Account = Account;
+	type Error = InMemoryError;
+
+	fn balance(&self, asset: AssetId) -> Result<u128, Self::Error> {
+		Ok(self.holdings.get(&asset).copied().unwrap_or_default())
+	}
+
+	fn transfer(
+		&mut self,
+		to: &Self::Account,
+		asset: AssetId,
+		amount: u128,
+	) -> Result<(), Self::Error> {
+		self.withdraw(asset, amount)?;
+		let balance = self.accounts.entry(to.clone()).or_default().entry(asset).or_default();
+		*balance = balance.checked_add(amount).ok_or(InMemoryError::BalanceOverflow { asset })?;
+		Ok(())
+	}
+
+	fn call(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+		self.calls.push(encoded.to_vec());
+		Ok(())
+	}
+
+	fn spawn(
+		&mut self,
+		network: NetworkId,
+		salt: &[u8],
+		assets: &Funds<u128>,
+		program: &XCVMProgram<Self::Account>,
+	) -> Result<(), Self::Error> {
+		for (asset, amount) in &assets.0 {
+			self.ensure_holdings(*asset, *amount)?;
+		}
+		for (asset, amount) in &assets.0 {
+			self.withdraw(*asset, *amount)?;
+		}
+		self.outbox.push(SpawnedProgram {
+			network,
+			salt: salt.to_vec(),
+			assets: assets.clone(),
+			program: program.clone(),
+		});
+		Ok(())
+	}
+
+	fn swap(&mut self, give: &Funds<u128>, want: &Funds) -> Result<Funds<u128>, Self::Error> {
+		let (from, amount, to, minimum) = match (single(&give.0), single(&want.0)) {
+			(Some((from, amount)), Some((to, minimum))) => (*from, *amount, *to, minimum),
+			_ => return Err(InMemoryError::UnsupportedSwap),
+		};
+		let (numerator, denominator) =
+			self.rates.get(&(from, to)).copied().ok_or(InMemoryError::NoRate { from, to })?;
+		let received = amount
+			.checked_mul(numerator)
+			.and_then(|x| x.checked_div(denominator))
+			.ok_or(InMemoryError::ArithmeticOverflow)?;
+		let minimum = minimum.apply_unbounded(received);
+		if received < minimum {
+			return Err(InMemoryError::SlippageExceeded { asset: to, minimum, received })
+		}
+		self.withdraw(from, amount)?;
+		self.deposit(to, received)?;
+		Ok(Funds(BTreeMap::from([(to, received)])))
+	}
+
+	fn liquidity(&self, pool: &[u8]) -> Result<u128, Self::Error> {
+		Ok(self.pools.get(pool).map(|pool| pool.shares).unwrap_or_default())
+	}
+
+	fn add_liquidity(&mut self, pool: &[u8], assets: &Funds<u128>) -> Result<u128, Self::Error> {
+		let minted = assets
+			.0
+			.values()
+			.try_fold(0_u128, |total, amount| total.checked_add(*amount))
+			.ok_or(InMemoryError::ArithmeticOverflow)?;
+		for (asset, amount) in &assets.0 {
+			self.ensure_holdings(*asset, *amount)?;
+		}
+		for (asset, amount) in &assets.0 {
+			self.withdraw(*asset, *amount)?;
+		}
+		let pool = self.pools.entry(pool.to_vec()).or_default();
+		for (asset, amount) in &assets.0 {
+			let reserve = pool.reserves.entry(*asset).or_default();
+			*reserve = reserve.checked_add(*amount).ok_or(InMemoryError::ArithmeticOverflow)?;
+		}
+		pool.shares = pool.shares.checked_add(minted).ok_or(InMemoryError::ArithmeticOverflow)?;
+		Ok(minted)
+	}
+
+	fn remove_liquidity(&mut self, pool: &[u8], shares: u128) -> Result<Funds<u128>, Self::Error> {
+		let pool = self.pools.get_mut(pool).ok_or(InMemoryError::UnknownPool)?;
+		if pool.shares < shares {
+			return Err(InMemoryError::InsufficientShares { shares: pool.shares, amount: shares })
+		}
+		let withdrawn = pool
+			.reserves
+			.iter()
+			.map(|(asset, reserve)| {
+				reserve
+					.checked_mul(shares)
+					.and_then(|x| x.checked_div(pool.shares))
+					.map(|amount| (*asset, amount))
+					.ok_or(InMemoryError::ArithmeticOverflow)
+			})
+			.collect::<Result<BTreeMap<_, _>, _>>()?;
+		for (asset, amount) in &withdrawn {
+			pool.reserves.entry(*asset).and_modify(|reserve| *reserve -= amount);
+		}
+		pool.shares -= shares;
+		for (asset, amount) in &withdrawn {
+			self.deposit(*asset, *amount)?;
+		}
+		Ok(Funds(withdrawn))
+	}
+}
+
+fn single<K, V>(map: &BTreeMap<K, V>) -> Option<(&K, &V)> {
+	if map.len() == 1 {
+		map.iter().next()
+	} else {
+		None
+	}
+}
@@ -0,0 +1,39 @@
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use xcvm_core::{Funds, NetworkId};
+
+/// Effect of a single instruction, with every [`xcvm_core::Amount`] resolved against the
+/// balances at the time the instruction was executed.
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, TypeInfo)]
+pub enum TraceEvent<Account> {
+	Transferred { to: Account, assets: Funds<u128> },
+	Called { encoded: Vec<u8> },
+	Spawned { network: NetworkId, salt: Vec<u8>, assets: Funds<u128> },
+	Swapped { given: Funds<u128>, received: Funds<u128> },
+	LiquidityAdded { pool: Vec<u8>, assets: Funds<u128>, shares: u128 },
+	LiquidityRemoved { pool: Vec<u8>, shares: u128, assets: Funds<u128> },
+	Jumped { to: u32 },
+	Evaluated { holds: bool },
+}
+
+/// A [`TraceEvent`] along the instruction pointer that produced it.
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, TypeInfo)]
+pub struct TraceEntry<Account> {
+	pub ip: u32,
+	pub event: TraceEvent<Account>,
+}
+
+/// Ordered record of what a program did while being executed.
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, TypeInfo)]
+pub struct Trace<Account> {
+	pub tag: Vec<u8>,
+	pub entries: Vec<TraceEntry<Account>>,
+}
+
+impl<Account> Trace<Account> {
+	#[inline]
+	pub fn new(tag: Vec<u8>) -> Self {
+		Trace { tag, entries: Vec::new() }
+	}
+}